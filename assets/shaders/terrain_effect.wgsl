@@ -0,0 +1,162 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct EffectUniforms {
+    time: f32,
+    daylight: f32,  // 0 at night to 1 in the day
+    edge_mask: u32, // Neighbours that are another ground: N, NE, E, SE, S, SW, W, NW from the lowest bit up
+    depth: f32,     // 0 at the edge of the patch to 1 well inside it
+    kind: u32,      // 0 water, 1 lava, 2 sway
+    speed: f32,
+    strength: f32,
+    color: vec4<f32>,
+}
+
+@group(2) @binding(0) var<uniform> effect: EffectUniforms;
+
+// Convert RGB to HSV
+fn rgb_to_hsv(color: vec3<f32>) -> vec3<f32> {
+    let max_val = max(max(color.r, color.g), color.b);
+    let min_val = min(min(color.r, color.g), color.b);
+    let delta = max_val - min_val;
+    
+    var hue: f32 = 0.0;
+    let saturation = select(0.0, delta / max_val, max_val > 0.0);
+    let value = max_val;
+    
+    if (delta > 0.0) {
+        if (max_val == color.r) {
+            hue = (color.g - color.b) / delta;
+        } else if (max_val == color.g) {
+            hue = 2.0 + (color.b - color.r) / delta;
+        } else {
+            hue = 4.0 + (color.r - color.g) / delta;
+        }
+        hue = hue / 6.0;
+        if (hue < 0.0) {
+            hue = hue + 1.0;
+        }
+    }
+    
+    return vec3<f32>(hue, saturation, value);
+}
+
+// Convert HSV to RGB
+fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
+    let hue = hsv.x * 6.0;
+    let saturation = hsv.y;
+    let value = hsv.z;
+    
+    let c = value * saturation;
+    let x = c * (1.0 - abs((hue % 2.0) - 1.0));
+    let m = value - c;
+    
+    var rgb: vec3<f32>;
+    
+    if (hue < 1.0) {
+        rgb = vec3<f32>(c, x, 0.0);
+    } else if (hue < 2.0) {
+        rgb = vec3<f32>(x, c, 0.0);
+    } else if (hue < 3.0) {
+        rgb = vec3<f32>(0.0, c, x);
+    } else if (hue < 4.0) {
+        rgb = vec3<f32>(0.0, x, c);
+    } else if (hue < 5.0) {
+        rgb = vec3<f32>(x, 0.0, c);
+    } else {
+        rgb = vec3<f32>(c, 0.0, x);
+    }
+    
+    return rgb + m;
+}
+
+fn is_edge(bit: u32) -> bool {
+    return (effect.edge_mask & bit) != 0u;
+}
+
+// How far into the tile this point is from the nearest edge or corner with another ground beyond
+// it, 0 on the edge to 1 where there's none close by. North is the top of the tile, at uv.y = 0.
+fn edge_distance(uv: vec2<f32>) -> f32 {
+    var distance = 1.0;
+    if (is_edge(1u)) { distance = min(distance, uv.y); }
+    if (is_edge(4u)) { distance = min(distance, 1.0 - uv.x); }
+    if (is_edge(16u)) { distance = min(distance, 1.0 - uv.y); }
+    if (is_edge(64u)) { distance = min(distance, uv.x); }
+    if (is_edge(2u)) { distance = min(distance, length(uv - vec2<f32>(1.0, 0.0))); }
+    if (is_edge(8u)) { distance = min(distance, length(uv - vec2<f32>(1.0, 1.0))); }
+    if (is_edge(32u)) { distance = min(distance, length(uv - vec2<f32>(0.0, 1.0))); }
+    if (is_edge(128u)) { distance = min(distance, length(uv)); }
+    return distance;
+}
+
+fn water(world_pos: vec2<f32>, uv: vec2<f32>, time: f32) -> vec4<f32> {
+    // Base hue rotation with time (very subtle)
+    let base_rotation = time * 0.1;
+    
+    // Add spatial variation based on world position (subtle)
+    let spatial_variation = sin(world_pos.x * 0.01 + time * 0.4) * 0.05 + 
+                           cos(world_pos.y * 0.01 + time * 0.3) * 0.04;
+    
+    // Add oscillating variation (subtle)
+    let wave_variation = sin(time * 0.6) * 0.03 + cos(time * 0.35) * 0.02;
+    
+    // Combine all variations (much smaller range)
+    let total_hue_shift = base_rotation + spatial_variation + wave_variation;
+    
+    // Create a subtle tint based on hue shift
+    let hue_color = hsv_to_rgb(vec3<f32>(total_hue_shift, 0.3, 0.8));
+    
+    // Deep water darkens toward its colour away from the shore
+    let day_color = mix(hue_color * 0.2, effect.color.rgb, effect.depth * 0.8);
+
+    // At night the tint fades to a dim, cold moonlit blue
+    let night_color = vec3<f32>(0.05, 0.08, 0.2) * (1.0 - effect.depth * 0.5) + hue_color * 0.05;
+    var tint = mix(night_color, day_color, effect.daylight);
+    var alpha = 0.6 + effect.depth * 0.2;
+
+    // Foam laps at the shore, its edge rolling in and out with the waves
+    let wave = sin(time * 2.0 + (world_pos.x + world_pos.y) * 0.15) * 0.5 + 0.5;
+    let foam_width = 0.12 + wave * 0.1;
+    let foam = 1.0 - smoothstep(foam_width * 0.5, foam_width, edge_distance(uv));
+    let foam_color = vec3<f32>(0.9, 0.95, 1.0) * mix(0.25, 1.0, effect.daylight);
+    tint = mix(tint, foam_color, foam * 0.8);
+    alpha = mix(alpha, 0.85, foam);
+
+    return vec4<f32>(tint, alpha);
+}
+
+fn lava(world_pos: vec2<f32>, uv: vec2<f32>, time: f32) -> vec4<f32> {
+    // Slow crossing currents, hotter toward the middle of the flow
+    let flow = sin(world_pos.x * 0.08 + time * 1.3) * cos(world_pos.y * 0.07 - time * 0.9) * 0.5 + 0.5;
+    let pulse = sin(time * 2.0 + world_pos.x * 0.02) * 0.5 + 0.5;
+    let heat = mix(0.45, 1.0, flow) * mix(0.7, 1.0, effect.depth) * mix(0.85, 1.0, pulse);
+
+    // A dark crust where it's cooled against other ground. It glows just as bright at night.
+    let crust = 1.0 - smoothstep(0.05, 0.3, edge_distance(uv));
+    let tint = mix(effect.color.rgb * heat, vec3<f32>(0.15, 0.05, 0.02), crust * 0.7);
+    return vec4<f32>(tint, 0.55 + heat * 0.35);
+}
+
+fn sway(world_pos: vec2<f32>, time: f32) -> vec4<f32> {
+    // Broad gusts roll across, rippling the blades as they pass
+    let gust = sin(dot(world_pos, vec2<f32>(0.05, 0.02)) - time * 1.5) * 0.5 + 0.5;
+    let ripple = sin(world_pos.x * 0.4 + time * 3.0 + sin(world_pos.y * 0.3)) * 0.5 + 0.5;
+    let band = smoothstep(0.6, 1.0, gust) * mix(0.5, 1.0, ripple);
+    let tint = effect.color.rgb * mix(0.3, 1.0, effect.daylight);
+    return vec4<f32>(tint, band * 0.3);
+}
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let world_pos = mesh.world_position.xy;
+    let time = effect.time * effect.speed;
+
+    var color: vec4<f32>;
+    if (effect.kind == 1u) {
+        color = lava(world_pos, mesh.uv, time);
+    } else if (effect.kind == 2u) {
+        color = sway(world_pos, time);
+    } else {
+        color = water(world_pos, mesh.uv, time);
+    }
+    return vec4<f32>(color.rgb, color.a * effect.strength);
+}
@@ -5,15 +5,86 @@
 //! corresponding tilesets by merging all sprites. For each tileset, it creates
 //! a YAML file listing all sprites and their indices.
 //! 
-//! Usage: cargo run --bin tileset_generator [assets_path]
+//! Usage: cargo run --bin tileset_generator -- [assets_path] [--tile-size N] [--columns N] [--padding N] [--no-extrude] [--fps N] [--output DIR] [--force] [--watch]
+//!
+//! A tileset directory can hold a tilesets.toml giving its own tile_size, columns, padding,
+//! extrude, fps or output, which win over the command line for that tileset.
+//!
+//! A directory named `<sprite name>.anim` holding `frame_<n>.png` files is an animation strip:
+//! its frames are packed one after another and listed in order under `strips` in the index.
+//!
+//! Each index records a hash of the sprites and settings it was made from, and tilesets whose
+//! hash hasn't changed are skipped. With --watch the tool keeps running and regenerates a tileset
+//! whenever anything in its directory changes.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::Path;
-use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+use clap::Parser;
+use notify::{EventKind, RecursiveMode, Watcher};
 use walkdir::WalkDir;
 use image::{ImageBuffer, Rgba, RgbaImage};
 use serde::{Deserialize, Serialize};
 
+/// The per-directory settings file
+const DIRECTORY_CONFIG_FILE: &str = "tilesets.toml";
+/// Directories of animation strip frames are named for the strip with this on the end
+const STRIP_DIR_SUFFIX: &str = ".anim";
+/// Each file in a strip directory is a frame named this and its number
+const STRIP_FRAME_PREFIX: &str = "frame_";
+/// Frames a second recorded for animation strips, unless set otherwise
+const DEFAULT_STRIP_FPS: f32 = 8.0;
+/// How long changes have to settle in watch mode before a tileset is regenerated, so an editor
+/// saving several files at once sets off one rebuild
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Merges the sprites in each directory under assets/tilesets/ into a tileset image and YAML index
+#[derive(Debug, Parser)]
+#[command(name = "tileset_generator")]
+struct Args {
+    /// Assets directory holding tilesets/
+    #[arg(default_value = "assets")]
+    assets_path: PathBuf,
+    /// Width and height each sprite is resized to, in pixels
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u32).range(1..))]
+    tile_size: u32,
+    /// Tiles in each row of a tileset image
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u32).range(1..))]
+    columns: u32,
+    /// Pixels between tiles
+    #[arg(long, default_value_t = 0)]
+    padding: u32,
+    /// Leave the padding transparent rather than filling it with the edge pixels of the tiles either side
+    #[arg(long)]
+    no_extrude: bool,
+    /// Frames a second recorded for each animation strip
+    #[arg(long, default_value_t = DEFAULT_STRIP_FPS)]
+    fps: f32,
+    /// Directory tileset images and indexes are written to [default: <assets_path>/tilesets]
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Regenerate every tileset, even those whose sprites and settings haven't changed
+    #[arg(long)]
+    force: bool,
+    /// Keep running, regenerating a tileset whenever anything in its directory changes
+    #[arg(long)]
+    watch: bool,
+}
+
+/// A tileset directory's tilesets.toml - anything left out comes from the command line
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DirectoryConfig {
+    tile_size: Option<u32>,
+    columns: Option<u32>,
+    padding: Option<u32>,
+    extrude: Option<bool>,
+    fps: Option<f32>,
+    output: Option<PathBuf>, // Relative to the directory
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SpriteInfo {
     name: String,
@@ -30,24 +101,153 @@ struct TilesetIndex {
     tile_size: u32,
     tiles_per_row: u32,
     total_tiles: u32,
+    #[serde(default)]
+    padding: u32, // Pixels between tiles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source_hash: Option<String>, // Of the sprites and settings it was made from
     sprites: Vec<SpriteInfo>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    strips: BTreeMap<String, AnimationStrip>, // From .anim directories, by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    animations: Option<serde_yaml::Value>, // Written by hand, kept as-is when regenerating
+}
+
+/// An animation strip packed from a `.anim` directory - the sprite names of its frames in order,
+/// and how fast to play them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct AnimationStrip {
+    frames: Vec<String>,
+    fps: f32,
+}
+
+/// A sprite to pack, with the name it's listed under and the strip it's a frame of, if any
+#[derive(Debug, Clone, PartialEq)]
+struct NamedSprite {
+    name: String,
+    path: PathBuf,
+    strip: Option<String>,
+}
+
+/// The strip a sprite is a frame of and its frame number, for sprites in a `.anim` directory.
+/// Files there that aren't named as frames give no number.
+fn strip_frame(path: &Path) -> Option<(String, Option<u32>)> {
+    let dir_name = path.parent()?.file_name()?.to_str()?;
+    let strip = dir_name.strip_suffix(STRIP_DIR_SUFFIX)?;
+    let number = path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.strip_prefix(STRIP_FRAME_PREFIX))
+        .and_then(|number| number.parse().ok());
+    Some((strip.to_string(), number))
 }
 
+/// Put sprites in packing order and name them. Sprites are in path order, except that each
+/// strip's frames go by frame number, so frame_10 comes after frame_9; frames are named for their
+/// strip and their place in it - walk_0, walk_1 and so on for walk.anim/.
+fn name_sprites(mut sprites: Vec<PathBuf>) -> Vec<NamedSprite> {
+    let sort_key = |path: &PathBuf| match strip_frame(path) {
+        Some((_, Some(number))) => path.with_file_name(format!("{}{:010}", STRIP_FRAME_PREFIX, number)),
+        _ => path.clone(),
+    };
+    sprites.sort_by_cached_key(sort_key);
+
+    let mut frame_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut named = Vec::new();
+    for path in sprites {
+        let (name, strip) = match strip_frame(&path) {
+            Some((strip, Some(_))) => {
+                let count = frame_counts.entry(strip.clone()).or_default();
+                *count += 1;
+                (format!("{}_{}", strip, *count - 1), Some(strip))
+            }
+            Some(_) => {
+                println!("Skipping {:?}: frames of an animation strip are named {}<n>", path, STRIP_FRAME_PREFIX);
+                continue;
+            }
+            None => (path.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown").to_string(), None),
+        };
+        named.push(NamedSprite { name, path, strip });
+    }
+    named
+}
+
+#[derive(Debug, Clone, PartialEq)]
 struct TilesetGenerator {
     tile_size: u32,
     tiles_per_row: u32,
+    padding: u32,
+    extrude: bool, // Fill the padding with the tiles' edge pixels
+    fps: f32,      // Of animation strips
+    output: PathBuf,
 }
 
 impl TilesetGenerator {
-    fn new(tile_size: u32, tiles_per_row: u32) -> Self {
+    fn new(tile_size: u32, tiles_per_row: u32, padding: u32, output: PathBuf) -> Self {
         Self {
             tile_size,
             tiles_per_row,
+            padding,
+            extrude: true,
+            fps: DEFAULT_STRIP_FPS,
+            output,
         }
     }
 
-    fn generate_tilesets(&self, assets_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let tilesets_path = Path::new(assets_path).join("tilesets");
+    /// These settings with any a tileset directory's tilesets.toml gives in their place
+    fn for_directory(&self, dir_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = dir_path.join(DIRECTORY_CONFIG_FILE);
+        if !config_path.is_file() {
+            return Ok(self.clone());
+        }
+        let config: DirectoryConfig = toml::from_str(&fs::read_to_string(&config_path)?)
+            .map_err(|e| format!("{}: {}", config_path.display(), e))?;
+        if config.tile_size == Some(0) || config.columns == Some(0) {
+            return Err(format!("{}: tile_size and columns must be at least 1", config_path.display()).into());
+        }
+        if config.fps.is_some_and(|fps| fps <= 0.0) {
+            return Err(format!("{}: fps must be more than 0", config_path.display()).into());
+        }
+        Ok(Self {
+            tile_size: config.tile_size.unwrap_or(self.tile_size),
+            tiles_per_row: config.columns.unwrap_or(self.tiles_per_row),
+            padding: config.padding.unwrap_or(self.padding),
+            extrude: config.extrude.unwrap_or(self.extrude),
+            fps: config.fps.unwrap_or(self.fps),
+            output: config.output.map_or_else(|| self.output.clone(), |output| dir_path.join(output)),
+        })
+    }
+
+    /// Where a tile's top-left corner goes in the tileset image
+    fn tile_position(&self, index: u32) -> (u32, u32) {
+        let row = index / self.tiles_per_row;
+        let col = index % self.tiles_per_row;
+        (col * (self.tile_size + self.padding), row * (self.tile_size + self.padding))
+    }
+
+    /// A hash of the sprites' names and contents and the layout they're put in, so a tileset
+    /// made from the same is known to be up to date. FNV-1a, as it's the same on every run and
+    /// every Rust version.
+    fn source_hash(&self, dir_path: &Path, sprites: &[PathBuf]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut add = |bytes: &[u8]| {
+            for byte in bytes {
+                hash = (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for setting in [self.tile_size, self.tiles_per_row, self.padding, self.extrude as u32, self.fps.to_bits()] {
+            add(&setting.to_le_bytes());
+        }
+        for sprite_path in sprites {
+            let name = sprite_path.strip_prefix(dir_path).unwrap_or(sprite_path);
+            add(name.to_string_lossy().as_bytes());
+            let contents = fs::read(sprite_path)?;
+            add(&(contents.len() as u64).to_le_bytes());
+            add(&contents);
+        }
+        Ok(format!("{:016x}", hash))
+    }
+
+    fn generate_tilesets(&self, assets_path: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let tilesets_path = assets_path.join("tilesets");
         
         if !tilesets_path.exists() {
             fs::create_dir_all(&tilesets_path)?;
@@ -61,17 +261,55 @@ impl TilesetGenerator {
             let path = entry.path();
             
             if path.is_dir() {
-                if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                    println!("Processing tileset directory: {}", dir_name);
-                    self.process_tileset_directory(&path, dir_name, &tilesets_path)?;
-                }
+                self.generate_tileset(&path, force)?;
             }
         }
 
         Ok(())
     }
 
-    fn process_tileset_directory(&self, dir_path: &Path, tileset_name: &str, tilesets_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    /// Generate the tileset for one directory under assets/tilesets/, with its own settings
+    fn generate_tileset(&self, dir_path: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir_name) = dir_path.file_name().and_then(|n| n.to_str()) {
+            let generator = self.for_directory(dir_path)?;
+            println!("Processing tileset directory: {} ({}px tiles, {} per row)", dir_name, generator.tile_size, generator.tiles_per_row);
+            generator.process_tileset_directory(dir_path, dir_name, force)?;
+        }
+        Ok(())
+    }
+
+    /// Regenerate each tileset as its directory changes, until the tool is stopped. A tileset that
+    /// fails to generate is reported and watched for the fix.
+    fn watch(&self, assets_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let tilesets_path = assets_path.join("tilesets").canonicalize()?;
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(&tilesets_path, RecursiveMode::Recursive)?;
+        println!("\nWatching {:?} for changes (Ctrl+C to stop)", tilesets_path);
+
+        while let Ok(first) = receiver.recv() {
+            // Gather the changes until they settle
+            let mut changed = BTreeSet::new();
+            let mut next = Some(first);
+            while let Some(result) = next {
+                match result {
+                    // Reading the sprites to hash them is an access too
+                    Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                    Ok(event) => changed.extend(event.paths.iter().filter_map(|path| changed_tileset(&tilesets_path, path))),
+                    Err(e) => eprintln!("Watch error: {}", e),
+                }
+                next = receiver.recv_timeout(WATCH_DEBOUNCE).ok();
+            }
+            for dir_path in changed.iter().filter(|dir_path| dir_path.is_dir()) {
+                if let Err(e) = self.generate_tileset(dir_path, false) {
+                    eprintln!("Failed to generate tileset {:?}: {}", dir_path, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn process_tileset_directory(&self, dir_path: &Path, tileset_name: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
         // Collect all image files in the directory and subdirectories
         let mut sprites = Vec::new();
         
@@ -96,30 +334,49 @@ impl TilesetGenerator {
             return Ok(());
         }
 
-        // Sort sprites by name for consistent ordering
-        sprites.sort();
+        // Sort sprites by name for consistent ordering, with strips' frames in frame order
+        let sprites = name_sprites(sprites);
+        let sprite_paths: Vec<PathBuf> = sprites.iter().map(|sprite| sprite.path.clone()).collect();
+
+        let yaml_filename = format!("{}.yaml", tileset_name);
+        let yaml_path = self.output.join(&yaml_filename);
+        let tileset_filename = format!("{}.png", tileset_name);
+        let tileset_path = self.output.join(&tileset_filename);
+
+        // Skip tilesets made from the same sprites and settings as last time
+        let source_hash = self.source_hash(dir_path, &sprite_paths)?;
+        let previous = fs::read_to_string(&yaml_path)
+            .ok()
+            .and_then(|contents| serde_yaml::from_str::<TilesetIndex>(&contents).ok());
+        let unchanged = previous.as_ref().and_then(|previous| previous.source_hash.as_ref()) == Some(&source_hash);
+        if unchanged && !force && tileset_path.is_file() {
+            println!("Unchanged, skipping: {}", tileset_name);
+            return Ok(());
+        }
 
         // Load and process sprites
         let mut sprite_images = Vec::new();
         let mut sprite_infos = Vec::new();
+        let mut strips: BTreeMap<String, AnimationStrip> = BTreeMap::new();
 
-        for (index, sprite_path) in sprites.iter().enumerate() {
-            match self.load_and_resize_sprite(sprite_path) {
+        for sprite in &sprites {
+            match self.load_and_resize_sprite(&sprite.path) {
                 Ok(sprite_img) => {
-                    let sprite_name = sprite_path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
+                    // Packed one after another, so a strip's frames take consecutive indices
+                    let index = sprite_infos.len() as u32;
+                    let (x, y) = self.tile_position(index);
 
-                    let row = (index as u32) / self.tiles_per_row;
-                    let col = (index as u32) % self.tiles_per_row;
+                    if let Some(strip) = &sprite.strip {
+                        strips.entry(strip.clone())
+                            .or_insert_with(|| AnimationStrip { frames: Vec::new(), fps: self.fps })
+                            .frames.push(sprite.name.clone());
+                    }
 
                     let sprite_info = SpriteInfo {
-                        name: sprite_name,
-                        index: index as u32,
-                        x: col * self.tile_size,
-                        y: row * self.tile_size,
+                        name: sprite.name.clone(),
+                        index,
+                        x,
+                        y,
                         width: self.tile_size,
                         height: self.tile_size,
                     };
@@ -128,7 +385,7 @@ impl TilesetGenerator {
                     sprite_infos.push(sprite_info);
                 }
                 Err(e) => {
-                    println!("Failed to load sprite {:?}: {}", sprite_path, e);
+                    println!("Failed to load sprite {:?}: {}", sprite.path, e);
                 }
             }
         }
@@ -142,22 +399,26 @@ impl TilesetGenerator {
         let tileset_image = self.create_tileset_image(&sprite_images)?;
         
         // Save tileset
-        let tileset_filename = format!("{}.png", tileset_name);
-        let tileset_path = tilesets_path.join(&tileset_filename);
+        fs::create_dir_all(&self.output)?;
         tileset_image.save(&tileset_path)?;
         println!("Generated tileset: {:?}", tileset_path);
 
+        // Keep the animations of the previous index
+        let animations = previous.and_then(|previous| previous.animations);
+
         // Create and save index YAML
         let tileset_index = TilesetIndex {
             tileset_name: tileset_name.to_string(),
             tile_size: self.tile_size,
             tiles_per_row: self.tiles_per_row,
             total_tiles: sprite_infos.len() as u32,
+            padding: self.padding,
+            source_hash: Some(source_hash),
             sprites: sprite_infos,
+            strips,
+            animations,
         };
 
-        let yaml_filename = format!("{}.yaml", tileset_name);
-        let yaml_path = tilesets_path.join(&yaml_filename);
         let yaml_content = serde_yaml::to_string(&tileset_index)?;
         fs::write(&yaml_path, yaml_content)?;
         println!("Generated index: {:?}", yaml_path);
@@ -178,8 +439,9 @@ impl TilesetGenerator {
     fn create_tileset_image(&self, sprites: &[RgbaImage]) -> Result<RgbaImage, Box<dyn std::error::Error>> {
         let sprite_count = sprites.len() as u32;
         let rows = (sprite_count + self.tiles_per_row - 1) / self.tiles_per_row;
-        let tileset_width = self.tiles_per_row * self.tile_size;
-        let tileset_height = rows * self.tile_size;
+        // Padding goes between tiles, not around the edges
+        let tileset_width = self.tiles_per_row * self.tile_size + (self.tiles_per_row - 1) * self.padding;
+        let tileset_height = rows * self.tile_size + (rows - 1) * self.padding;
 
         let mut tileset = ImageBuffer::new(tileset_width, tileset_height);
 
@@ -188,18 +450,28 @@ impl TilesetGenerator {
             *pixel = Rgba([0, 0, 0, 0]);
         }
 
+        // Extruded tiles run their edge pixels out over their half of the padding either side, so
+        // sampling just past a tile's edge at a zoom that isn't a whole number finds the tile
+        // rather than its neighbour. The outer edges need none, as sampling clamps to them.
+        let (before, after) = if self.extrude {
+            (self.padding / 2, self.padding - self.padding / 2)
+        } else {
+            (0, 0)
+        };
+
         // Place sprites in the tileset
         for (index, sprite) in sprites.iter().enumerate() {
-            let row = (index as u32) / self.tiles_per_row;
-            let col = (index as u32) % self.tiles_per_row;
-            let x_offset = col * self.tile_size;
-            let y_offset = row * self.tile_size;
-
-            for y in 0..self.tile_size {
-                for x in 0..self.tile_size {
-                    if x < sprite.width() && y < sprite.height() {
-                        let sprite_pixel = sprite.get_pixel(x, y);
-                        tileset.put_pixel(x_offset + x, y_offset + y, *sprite_pixel);
+            let (x_offset, y_offset) = self.tile_position(index as u32);
+            let x_range = x_offset.saturating_sub(before)..(x_offset + self.tile_size + after).min(tileset_width);
+            let y_range = y_offset.saturating_sub(before)..(y_offset + self.tile_size + after).min(tileset_height);
+
+            for y in y_range {
+                for x in x_range.clone() {
+                    let sprite_x = x.saturating_sub(x_offset).min(self.tile_size - 1);
+                    let sprite_y = y.saturating_sub(y_offset).min(self.tile_size - 1);
+                    if sprite_x < sprite.width() && sprite_y < sprite.height() {
+                        let sprite_pixel = sprite.get_pixel(sprite_x, sprite_y);
+                        tileset.put_pixel(x, y, *sprite_pixel);
                     }
                 }
             }
@@ -209,28 +481,188 @@ impl TilesetGenerator {
     }
 }
 
+/// The tileset directory a changed path is in, if it's in one rather than being a generated file
+/// beside them
+fn changed_tileset(tilesets_path: &Path, changed: &Path) -> Option<PathBuf> {
+    let mut components = changed.strip_prefix(tilesets_path).ok()?.components();
+    let dir_name = components.next()?;
+    components.next()?;
+    Some(tilesets_path.join(dir_name))
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Standalone Tileset Generator");
     println!("============================");
     
-    // Get assets path from command line or use default
-    let args: Vec<String> = env::args().collect();
-    let assets_path = if args.len() > 1 {
-        &args[1]
-    } else {
-        "assets"
-    };
+    let args = Args::parse();
 
     // Check if assets directory exists
-    if !Path::new(assets_path).exists() {
-        eprintln!("Assets directory not found: {}", assets_path);
+    if !args.assets_path.exists() {
+        eprintln!("Assets directory not found: {}", args.assets_path.display());
         std::process::exit(1);
     }
 
-    // Generate tilesets
-    let generator = TilesetGenerator::new(16, 16); // 16x16 tiles, 16 tiles per row
-    generator.generate_tilesets(assets_path)?;
+    // Generate tilesets, each directory's tilesets.toml overriding these
+    let output = args.output.unwrap_or_else(|| args.assets_path.join("tilesets"));
+    let generator = TilesetGenerator {
+        extrude: !args.no_extrude,
+        fps: args.fps,
+        ..TilesetGenerator::new(args.tile_size, args.columns, args.padding, output)
+    };
+    if let Err(e) = generator.generate_tilesets(&args.assets_path, args.force) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
     
     println!("\nTileset generation completed!");
+
+    if args.watch {
+        generator.watch(&args.assets_path)?;
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory_with_config(name: &str, config: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(DIRECTORY_CONFIG_FILE), config).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_directory_config_overrides_the_command_line() {
+        let defaults = TilesetGenerator::new(16, 16, 0, PathBuf::from("assets/tilesets"));
+        assert_eq!(defaults.for_directory(Path::new("no_such_tileset")).unwrap(), defaults);
+
+        let dir = directory_with_config("tileset_config", "tile_size = 32\npadding = 1\nextrude = false\noutput = \"../characters\"\n");
+        let generator = defaults.for_directory(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let expected = TilesetGenerator { extrude: false, ..TilesetGenerator::new(32, 16, 1, dir.join("../characters")) };
+        assert_eq!(generator, expected, "Columns left out stay as given");
+    }
+
+    #[test]
+    fn test_bad_directory_config_is_an_error() {
+        let defaults = TilesetGenerator::new(16, 16, 0, PathBuf::from("assets/tilesets"));
+        let misspelt = directory_with_config("tileset_config_misspelt", "tile_sise = 32\n");
+        let error = defaults.for_directory(&misspelt).unwrap_err().to_string();
+        fs::remove_dir_all(&misspelt).unwrap();
+        assert!(error.contains("tile_sise"), "{}", error);
+
+        let no_columns = directory_with_config("tileset_config_no_columns", "columns = 0\n");
+        let result = defaults.for_directory(&no_columns);
+        fs::remove_dir_all(&no_columns).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_only_changed_tilesets_are_regenerated() {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "tileset_hash", std::process::id()));
+        let sprites_dir = dir.join("things");
+        fs::create_dir_all(&sprites_dir).unwrap();
+        let sprite = sprites_dir.join("red.png");
+        RgbaImage::from_pixel(16, 16, Rgba([255, 0, 0, 255])).save(&sprite).unwrap();
+        let sprites = [sprite.clone()];
+        let generator = TilesetGenerator::new(16, 16, 0, dir.clone());
+        let hash = generator.source_hash(&sprites_dir, &sprites).unwrap();
+
+        generator.process_tileset_directory(&sprites_dir, "things", false).unwrap();
+        let modified = || fs::metadata(dir.join("things.png")).unwrap().modified().unwrap();
+        let first = modified();
+        std::thread::sleep(Duration::from_millis(20));
+        generator.process_tileset_directory(&sprites_dir, "things", false).unwrap();
+        assert_eq!(modified(), first, "Same sprites and settings, skipped");
+        generator.process_tileset_directory(&sprites_dir, "things", true).unwrap();
+        assert_ne!(modified(), first, "Forced");
+
+        let wider = TilesetGenerator::new(16, 8, 0, dir.clone());
+        assert_ne!(wider.source_hash(&sprites_dir, &sprites).unwrap(), hash, "Settings count");
+        RgbaImage::from_pixel(16, 16, Rgba([0, 0, 255, 255])).save(&sprite).unwrap();
+        let repainted = generator.source_hash(&sprites_dir, &sprites).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_ne!(repainted, hash, "Contents count");
+    }
+
+    #[test]
+    fn test_strip_frames_go_in_frame_order() {
+        let paths = ["pawns/wolf.png", "pawns/bat.anim/frame_10.png", "pawns/bat.anim/frame_2.png", "pawns/bat.anim/notes.png", "pawns/bat.anim/frame_9.png", "pawns/ant.png"];
+        let named = name_sprites(paths.iter().map(PathBuf::from).collect());
+        let listed: Vec<(&str, Option<&str>)> = named.iter().map(|sprite| (sprite.name.as_str(), sprite.strip.as_deref())).collect();
+        assert_eq!(listed, vec![
+            ("ant", None),
+            ("bat_0", Some("bat")),
+            ("bat_1", Some("bat")),
+            ("bat_2", Some("bat")),
+            ("wolf", None),
+        ], "notes.png isn't a frame");
+        assert_eq!(named[3].path, PathBuf::from("pawns/bat.anim/frame_10.png"));
+    }
+
+    #[test]
+    fn test_strips_are_packed_together_and_listed_in_the_index() {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "tileset_strips", std::process::id()));
+        let sprites_dir = dir.join("things");
+        let strip_dir = sprites_dir.join("spin.anim");
+        fs::create_dir_all(&strip_dir).unwrap();
+        RgbaImage::from_pixel(4, 4, Rgba([0, 255, 0, 255])).save(sprites_dir.join("still.png")).unwrap();
+        for frame in 0..3 {
+            RgbaImage::from_pixel(4, 4, Rgba([frame * 100, 0, 0, 255])).save(strip_dir.join(format!("frame_{}.png", frame))).unwrap();
+        }
+        let generator = TilesetGenerator { fps: 12.0, ..TilesetGenerator::new(4, 16, 0, dir.clone()) };
+        generator.process_tileset_directory(&sprites_dir, "things", false).unwrap();
+        let index: TilesetIndex = serde_yaml::from_str(&fs::read_to_string(dir.join("things.yaml")).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let sprites: Vec<(&str, u32)> = index.sprites.iter().map(|sprite| (sprite.name.as_str(), sprite.index)).collect();
+        assert_eq!(sprites, vec![("spin_0", 0), ("spin_1", 1), ("spin_2", 2), ("still", 3)]);
+        assert_eq!(index.strips.len(), 1);
+        assert_eq!(index.strips["spin"], AnimationStrip { frames: vec!["spin_0".into(), "spin_1".into(), "spin_2".into()], fps: 12.0 });
+    }
+
+    #[test]
+    fn test_changes_are_put_down_to_their_tileset() {
+        let tilesets = Path::new("/assets/tilesets");
+        assert_eq!(changed_tileset(tilesets, Path::new("/assets/tilesets/pawns/wolf.png")), Some(tilesets.join("pawns")));
+        assert_eq!(changed_tileset(tilesets, Path::new("/assets/tilesets/pawns/old/wolf.png")), Some(tilesets.join("pawns")));
+        assert_eq!(changed_tileset(tilesets, Path::new("/assets/tilesets/pawns.png")), None, "Generated, not a sprite");
+        assert_eq!(changed_tileset(tilesets, Path::new("/assets/pawns.yaml")), None);
+    }
+
+    #[test]
+    fn test_padding_goes_between_tiles() {
+        let generator = TilesetGenerator { extrude: false, ..TilesetGenerator::new(8, 2, 1, PathBuf::new()) };
+        assert_eq!(generator.tile_position(0), (0, 0));
+        assert_eq!(generator.tile_position(1), (9, 0));
+        assert_eq!(generator.tile_position(2), (0, 9));
+
+        let sprites = vec![RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])); 3];
+        let tileset = generator.create_tileset_image(&sprites).unwrap();
+        assert_eq!(tileset.dimensions(), (17, 17));
+        assert_eq!(tileset.get_pixel(8, 0)[3], 0, "Padding is left transparent");
+        assert_eq!(*tileset.get_pixel(9, 0), Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_tiles_are_extruded_into_the_padding() {
+        let generator = TilesetGenerator::new(2, 2, 3, PathBuf::new());
+        let red = Rgba([255, 0, 0, 255]);
+        let blue = Rgba([0, 0, 255, 255]);
+        let mut left = RgbaImage::from_pixel(2, 2, red);
+        left.put_pixel(1, 1, Rgba([255, 255, 0, 255])); // Bottom-right corner
+        let sprites = vec![left, RgbaImage::from_pixel(2, 2, blue), RgbaImage::from_pixel(2, 2, blue)];
+        let tileset = generator.create_tileset_image(&sprites).unwrap();
+        assert_eq!(tileset.dimensions(), (7, 7));
+
+        // Across the row: the first tile's right edge runs two pixels out, the second's left one
+        let row: Vec<_> = (0..7).map(|x| *tileset.get_pixel(x, 0)).collect();
+        assert_eq!(row, vec![red, red, red, red, blue, blue, blue]);
+        // Its bottom-right corner fills the corner of the padding
+        assert_eq!(*tileset.get_pixel(3, 3), Rgba([255, 255, 0, 255]));
+        // Past the last tile in the last row there's nothing to extrude
+        assert_eq!(tileset.get_pixel(6, 6)[3], 0);
+    }
+}
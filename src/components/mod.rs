@@ -4,4 +4,9 @@ use bevy::prelude::*;
 pub struct TerrainLayer {
     pub layer_id: u32,
     pub z_index: f32,
-}
\ No newline at end of file
+}
+
+/// Marks an entity (debug text, waypoint lines, bars, portraits...) as belonging to another entity.
+/// `despawn_detached_entities` removes it once the owner is gone.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttachedTo(pub Entity);
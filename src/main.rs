@@ -1,5 +1,8 @@
 use bevy::prelude::*;
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::audio::{AudioPlugin, GlobalVolume};
+use bevy::app::RunFixedMainLoopSystem;
+use bevy::ecs::schedule::ScheduleLabel;
 
 mod systems;
 mod components;
@@ -8,93 +11,517 @@ mod resources;
 #[cfg(test)]
 mod tests;
 
-use resources::GameConfig;
-use systems::world_gen::{generate_world, TerrainChanges, update_terrain_visuals};
-use systems::camera::{CameraController, MouseDragState, camera_movement, camera_zoom, mouse_camera_pan};
+use resources::{GameConfig, SETTINGS_FILE};
+use systems::world_gen::{generate_world, NextWorldSeed, TerrainChanges, update_terrain_visuals};
+use systems::camera::{CameraController, CameraFollow, MouseDragState, camera_movement, camera_zoom, mouse_camera_pan, toggle_camera_follow, follow_camera, keep_camera_over_map};
+use systems::config_files::{ConfigPaths, path_arg};
+use systems::config_validation::validate_configs;
+use systems::display::{window_from_config, limit_frame_rate};
 use systems::fps_counter::{setup_fps_counter, update_fps_counter};
-use systems::spawn::spawn_all_pawns;
-use systems::input::handle_player_input;
-use systems::pawn::{move_pawn_to_target, endurance_health_loss_system, pawn_death_system, endurance_behavior_switching_system, TilesetManager};
+use systems::spawn::{SpawnConfig, spawn_all_pawns};
+use systems::sim_rng::SimRng;
+use systems::population::{PopulationManager, population_manager_system};
+use systems::replay::{REPLAY_ARG, Replay, ReplayMode, PlayerCommands, take_player_commands, start_recording, start_replay, save_replay};
+use systems::input::{handle_player_input, apply_order_commands};
+use systems::pawn::{move_pawn_to_target, endurance_health_loss_system, health_regen_system, pawn_death_system, drowning_system, TilesetManager, pack_direct_sprites};
+use systems::needs::{need_decay_system, needs_behaviour_switching_system, setup_drinking_ai, drink_ai_system, sleep_system};
 use systems::pawn_config::PawnConfig;
-use systems::ai::{wandering_ai_system, setup_wandering_ai, hunt_solo_ai_system, setup_hunt_solo_ai};
+use systems::faction::FactionConfig;
+use systems::perception::{threat_perception_system, update_vision_facing};
+use systems::panic::panic_system;
+use systems::memory::memory_system;
+use systems::utility::utility_behaviour_system;
+use systems::scent::{ScentMap, scent_deposit_system, scent_decay_system};
+use systems::ai::{wandering_ai_system, setup_wandering_ai, hunt_solo_ai_system, setup_hunt_solo_ai, handle_pathfinding_failures, behaviour_tree_system, flee_ai_system};
 use systems::async_pathfinding::{
     spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding, 
-    cleanup_stale_pathfinding, PathfindingRequestCounter, GlobalPathfindingCache
+    cleanup_stale_pathfinding, PathfindingRequestCounter,
+    PathfindingCompleted, PathfindingFailed
 };
-use systems::debug_display::{DebugDisplayState, toggle_debug_display, manage_debug_text_entities, update_debug_text, cleanup_orphaned_debug_text, manage_waypoint_lines, update_waypoint_lines, cleanup_orphaned_waypoint_lines};
-use systems::water_shader::WaterShaderPlugin;
+use systems::debug_display::{DebugDisplayState, toggle_debug_display, inspect_clicked_tile, manage_debug_text_entities, update_debug_text, draw_waypoint_lines};
+use systems::terrain_effects::TerrainEffectPlugin;
+use systems::y_sort::y_sort_system;
+use systems::shadows::update_pawn_shadows;
+use systems::fog_of_war::{setup_fog_of_war, update_fog_of_war};
+use systems::audio::{SoundConfig, load_sound_effects, play_event_sounds, play_footsteps, play_click_sounds};
+use systems::culling::cull_overlay_chunks;
+use systems::footsteps::{Footstep, track_footsteps, spawn_footstep_particles, update_footstep_particles};
+use systems::soundscape::{spawn_ambient_loops, update_ambience, update_music};
+use systems::level_of_detail::{LevelOfDetail, update_level_of_detail, apply_level_of_detail};
+use systems::save::{SaveRequest, LoadRequest, WorldLoaded, CONFIG_FILES, hash_config_files, track_playtime, quick_save, quick_load, clear_world_for_load};
+use systems::attack::{cancel_abandoned_attacks, attack_visual_system};
+use systems::herd::{setup_herding_ai, herding_ai_system};
+use systems::pack_hunt::{setup_hunt_pack_ai, form_hunting_packs, hunt_pack_ai_system};
+use systems::charge::{handle_charge_input, apply_charge_commands, charge_movement_system, stun_recovery_system, charge_cooldown_system};
+use systems::pathfinding_cache::CachePlugin;
+use systems::pathfinding_stats::update_pathfinding_stats_panel;
+use systems::render_stats::update_render_stats_panel;
+use systems::game_state::{GameState, GameSet, GameStatePlugin};
+use systems::menu::{MenuPage, NewWorldForm, menu_back_input, seed_input, menu_buttons, update_menu_screen};
+use systems::minimap::{setup_minimap, update_minimap_terrain, update_minimap_markers, minimap_click};
+use systems::passability_overlay::{PassabilityOverlay, cycle_passability_size, update_passability_overlay};
+use systems::statistics::{Statistics, record_population_events, sample_population, update_population_panel, update_population_graph};
+use systems::group_pathfinding::GroupPathfindingPlugin;
+use systems::spatial_grid::SpatialGridPlugin;
+use systems::scripting::ScriptingPlugin;
+use systems::den::{place_dens, release_homeless_pawns};
+use systems::follow::{setup_follow_ai, follow_ai_system};
+use systems::ambush::{setup_ambush_ai, ambush_ai_system};
+use systems::eating::eating_system;
+use systems::migration::{RegionFood, update_region_food, migration_system};
+use systems::taming::{FeedPawn, handle_feed_input, apply_feed_commands, feeding_system, follow_owner_system};
+use systems::pathfinding_heatmap::{PathfindingHeatmap, sync_heatmap_recording, decay_pathfinding_heatmap, update_heatmap_overlay};
+use systems::attachment::despawn_detached_entities;
+use systems::sim_tick::{SimTick, SimulationTick, SimulationSpeed, advance_sim_tick, simulation_speed_input, apply_simulation_speed};
+use systems::world_clock::{WorldClock, update_world_clock};
+use systems::seasons::{Seasons, reset_seasons, update_season, repaint_for_season};
+use systems::weather::{Weather, weather_system, apply_weather_to_vision, update_weather_overlays, spawn_snow_overlays, update_snow_overlays};
+use systems::lighting::update_night_tint;
+use systems::infusion::{TerrainInfluence, accumulate_terrain_influence};
+use systems::aging::aging_system;
+use systems::animation::walk_animation_system;
+use systems::selection::{SelectionDrag, handle_selection_input, handle_select_all_input, apply_selection_commands, update_selection_box, update_selection_rings, update_selection_panel};
+use systems::control::{take_control_of_selected, release_control, handle_hold_input, steer_controlled_pawns, apply_control_commands, attack_order_system};
+use systems::grazing::{setup_grazing_ai, grazing_ai_system};
+use systems::vegetation::{VegetationGrowth, vegetation_growth_system};
+use systems::bestiary::{BestiaryProgress, BestiaryState, record_bestiary_encounters, record_bestiary_kills, toggle_bestiary, update_bestiary_panel};
+use systems::combat::{CombatEvent, DeathEvent, log_combat_events};
+use systems::combat_feedback::{spawn_combat_feedback, update_combat_feedback};
+use systems::inventory::{ItemConfig, scatter_items, item_pickup_system, drop_inventory_on_death};
+use systems::growth::{PawnFed, growth_system};
+use systems::keybinds::InputMap;
+use systems::camera_bookmarks::{CameraBookmarks, camera_bookmark_input, camera_bookmark_travel};
+use systems::behaviour_icons::update_behaviour_icons;
+use systems::tile_tooltip::{TileHover, track_hovered_tile, update_tile_tooltip};
+use systems::gamepad::{gamepad_camera, gamepad_selection};
+use systems::interpolation::{restore_simulated_positions, record_previous_positions, interpolate_drawn_positions};
+use systems::status_effects::{StatusEffectConfig, ApplyStatusEffect, inflict_attack_effects, ground_status_effects, apply_status_effects, status_effect_system};
 
 fn main() {
+    // A replay given on the command line is read before the working directory changes under it
+    let replay = path_arg(std::env::args().skip(1), REPLAY_ARG).map(|path| {
+        Replay::load_from_file(&path).unwrap_or_else(|e| {
+            eprintln!("Could not load replay {:?}: {}", path, e);
+            std::process::exit(1);
+        })
+    });
+
+    // Config files are found along a search path, so the game runs from anywhere. Assets, scripts
+    // and saves are relative to whichever directory on it has the assets.
+    let config_paths = ConfigPaths::from_environment();
+    let data_dir = config_paths.data_dir();
+    if let Err(e) = std::env::set_current_dir(&data_dir) {
+        eprintln!("Warning: Could not change to {:?} ({}), assets may not load", data_dir, e);
+    }
+
+    // Report everything wrong with the config files up front, rather than panicking on the first
+    // or quietly running on defaults
+    let problems = validate_configs(&config_paths);
+    if !problems.is_empty() {
+        eprintln!("Found {} problem(s) in the config files:", problems.len());
+        for problem in &problems {
+            eprintln!("  {}", problem);
+        }
+        std::process::exit(1);
+    }
+
     // Load settings from YAML file, fall back to defaults if file doesn't exist
-    let config = GameConfig::load_from_file("settings.yaml")
+    let mut config = config_paths.load(SETTINGS_FILE, GameConfig::from_yaml)
         .unwrap_or_else(|e| {
-            eprintln!("Warning: Could not load settings.yaml ({}), using defaults", e);
+            eprintln!("Warning: Could not load {} ({}), using defaults", SETTINGS_FILE, e);
             GameConfig::default()
         });
 
     // Load pawn configuration from YAML file
-    let pawn_config = PawnConfig::load_from_file("pawns.yaml")
+    let mut pawn_config = config_paths.load("pawns.yaml", PawnConfig::from_yaml)
         .expect("Failed to load pawns.yaml configuration file");
+    pawn_config.factions = config_paths.load("factions.yaml", FactionConfig::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load factions.yaml ({}), pawns will only hunt what they eat", e);
+            FactionConfig::default()
+        });
+    let status_effect_config = config_paths.load("status_effects.yaml", StatusEffectConfig::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load status_effects.yaml ({}), nothing will inflict status effects", e);
+            StatusEffectConfig::default()
+        });
+    let item_config = config_paths.load("items.yaml", ItemConfig::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load items.yaml ({}), there will be no items", e);
+            ItemConfig::default()
+        });
+    let spawn_config = config_paths.load("spawns.yaml", SpawnConfig::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load spawns.yaml ({}), pawns will spawn by spawn_count", e);
+            SpawnConfig::default()
+        });
+    let input_map = config_paths.load("keybinds.yaml", InputMap::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load keybinds.yaml ({}), using the default keys", e);
+            InputMap::default()
+        });
+
+    let sound_config = config_paths.load("sounds.yaml", SoundConfig::from_yaml)
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Could not load sounds.yaml ({}), there will be no sound effects", e);
+            SoundConfig::default()
+        });
+
+    // A replay plays out on the world it was recorded in
+    let mut next_seed = NextWorldSeed::default();
+    let mut replay_mode = ReplayMode::default();
+    if let Some(replay) = replay {
+        for warning in replay.metadata.compatibility_warnings(&config_paths.mods, &hash_config_files(&CONFIG_FILES, &config_paths)) {
+            eprintln!("Warning: {}, the replay may not play out as it was recorded", warning);
+        }
+        config.map_width = replay.metadata.options.map_width;
+        config.map_height = replay.metadata.options.map_height;
+        config.tile_size = replay.metadata.options.tile_size;
+        next_seed.0 = Some(replay.metadata.seed);
+        replay_mode = ReplayMode::Playing { replay, next: 0 };
+    }
 
     let mut app = App::new();
     
-    app.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+    app.add_plugins(DefaultPlugins
+            .set(ImagePlugin::default_nearest())
+            .set(WindowPlugin { primary_window: Some(window_from_config(&config)), ..default() })
+            .set(AudioPlugin { global_volume: GlobalVolume::new(config.volume), ..default() })
+            .set(AssetPlugin { file_path: data_dir.join("assets").to_string_lossy().into_owned(), ..default() }))
         .add_plugins(bevy_ecs_tilemap::TilemapPlugin)
-        .add_plugins(WaterShaderPlugin)
+        .add_plugins(TerrainEffectPlugin)
+        .add_plugins(GameStatePlugin)
+        .insert_resource(MenuPage::default())
+        .insert_resource(NewWorldForm::default())
+        .insert_resource(next_seed)
+        .insert_resource(replay_mode)
+        .insert_resource(PlayerCommands::default())
         .insert_resource(MouseDragState::default())
-        .insert_resource(TilesetManager::default())
+        .insert_resource(CameraFollow::default())
+        .insert_resource(CameraBookmarks::default())
+        .insert_resource(TilesetManager::new(data_dir.join("assets")))
         .insert_resource(DebugDisplayState::default())
+        .insert_resource(PathfindingHeatmap::default())
         .insert_resource(TerrainChanges::default())
+        .init_resource::<LevelOfDetail>()
         .insert_resource(PathfindingRequestCounter::default())
-        .insert_resource(GlobalPathfindingCache::default())
+        .insert_resource(Time::<Fixed>::from_hz(config.sim_ticks_per_second as f64))
+        .insert_resource(SimTick::new(config.sim_ticks_per_second).with_day_length(config.day_length_secs))
+        .insert_resource(SimRng::default())
+        .insert_resource(SimulationSpeed::default())
+        .add_event::<SimulationTick>()
+        .insert_resource(WorldClock::default())
+        .insert_resource(Weather::default())
+        .insert_resource(Seasons::default())
+        .insert_resource(TerrainInfluence::default())
+        .insert_resource(VegetationGrowth::default())
+        .insert_resource(ScentMap::default())
+        .insert_resource(BestiaryProgress::default())
+        .insert_resource(BestiaryState::default())
+        .insert_resource(SelectionDrag::default())
+        .insert_resource(TileHover::default())
+        .insert_resource(Statistics::default())
+        .insert_resource(PassabilityOverlay::default())
+        .insert_resource(RegionFood::default())
+        .insert_resource(PopulationManager::default())
+        .add_plugins(CachePlugin {
+            max_path_entries: config.path_cache_max_entries,
+            max_passability_entries: config.passability_cache_max_entries,
+        })
+        .add_plugins(GroupPathfindingPlugin { schedule: FixedUpdate.intern() })
+        .add_plugins(SpatialGridPlugin { schedule: FixedFirst.intern() })
+        .add_plugins(ScriptingPlugin { schedule: FixedUpdate.intern() })
         .insert_resource(pawn_config)
-        .add_systems(Startup, (
-            setup_camera,
+        .insert_resource(status_effect_config)
+        .insert_resource(item_config)
+        .insert_resource(spawn_config)
+        .insert_resource(input_map)
+        .insert_resource(sound_config)
+        .insert_resource(config_paths)
+        .add_event::<PathfindingCompleted>()
+        .add_event::<PathfindingFailed>()
+        .add_event::<FeedPawn>()
+        .add_event::<CombatEvent>()
+        .add_event::<DeathEvent>()
+        .add_event::<ApplyStatusEffect>()
+        .add_event::<PawnFed>()
+        .add_event::<SaveRequest>()
+        .add_event::<LoadRequest>()
+        .add_event::<WorldLoaded>()
+        .add_event::<Footstep>()
+        .add_systems(Startup, (setup_camera, start_replay))
+        .add_systems(Startup, (pack_direct_sprites, load_sound_effects, spawn_ambient_loops.after(load_sound_effects)))
+        // The world is made when the main menu is left, for a new world or one being loaded
+        .add_systems(OnExit(GameState::MainMenu), (
             generate_world,
-            spawn_all_pawns.after(generate_world),
+            place_dens.after(generate_world),
+            spawn_all_pawns.after(place_dens),
+            scatter_items.after(generate_world),
+            setup_minimap.after(generate_world),
+            start_recording.after(generate_world),
+            spawn_snow_overlays.after(generate_world),
+            reset_seasons.after(generate_world),
+            setup_fog_of_war.after(generate_world),
         ))
+        .add_systems(Update, (
+            // Main and pause menus
+            menu_back_input,
+            seed_input,
+            menu_buttons,
+            update_menu_screen.after(menu_back_input).after(seed_input).after(menu_buttons),
+        ))
+        .add_systems(FixedUpdate, (advance_sim_tick, update_world_clock.after(advance_sim_tick)).in_set(GameSet::Simulation))
+        // Pawns move in simulation steps and are drawn between them - see SmoothMotion
+        .add_systems(RunFixedMainLoop, (
+            restore_simulated_positions.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            interpolate_drawn_positions.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+        ))
+        .add_systems(FixedFirst, record_previous_positions.in_set(GameSet::Simulation))
         .add_systems(Update, (
             // Input and camera
             camera_movement, 
             camera_zoom, 
             mouse_camera_pan,
             handle_player_input,
+            handle_charge_input,
+            handle_feed_input,
+            handle_selection_input,
+            handle_select_all_input,
+            gamepad_camera,
+            toggle_camera_follow,
+            follow_camera.after(toggle_camera_follow).after(camera_movement).after(mouse_camera_pan).after(gamepad_camera),
+            camera_bookmark_input,
+            camera_bookmark_travel.after(camera_bookmark_input),
+            keep_camera_over_map.after(follow_camera).after(camera_zoom).after(camera_bookmark_travel),
+            gamepad_selection,
             toggle_debug_display,
-        ))
-        .add_systems(Update, (
-            // Async pathfinding systems - run early in frame
+            inspect_clicked_tile,
+            simulation_speed_input,
+            apply_simulation_speed.after(simulation_speed_input),
+        ).in_set(GameSet::Simulation))
+        // Everything that plays the game out runs on FixedUpdate, at settings' sim_ticks_per_second,
+        // so it goes at the same pace whatever the frame rate
+        .add_systems(FixedUpdate, (
+            // Async pathfinding systems - run early in the step
             spawn_cached_pathfinding_tasks,
             handle_completed_cached_pathfinding,
             cleanup_stale_pathfinding,
-        ))
-        .add_systems(Update, (
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
             // Movement and AI systems
             move_pawn_to_target,
-            setup_wandering_ai,
+            charge_movement_system,
+            stun_recovery_system,
+            charge_cooldown_system,
+            behaviour_tree_system,
+            setup_wandering_ai.after(behaviour_tree_system),
             wandering_ai_system,
-            setup_hunt_solo_ai,
+            setup_hunt_solo_ai.after(behaviour_tree_system),
             hunt_solo_ai_system,
-            endurance_health_loss_system,
-            endurance_behavior_switching_system.after(endurance_health_loss_system),
+            flee_ai_system.after(behaviour_tree_system),
+            handle_pathfinding_failures.after(handle_completed_cached_pathfinding).before(hunt_solo_ai_system),
+            need_decay_system,
+            endurance_health_loss_system.after(need_decay_system),
+            health_regen_system.after(need_decay_system).before(pawn_death_system),
+            needs_behaviour_switching_system.after(need_decay_system),
+            drowning_system.before(pawn_death_system),
+            aging_system.before(pawn_death_system),
             pawn_death_system,
-            update_terrain_visuals,
-        ))
+            accumulate_terrain_influence,
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Pack hunting
+            setup_hunt_pack_ai.after(behaviour_tree_system),
+            form_hunting_packs.after(setup_hunt_pack_ai),
+            hunt_pack_ai_system.after(form_hunting_packs).after(handle_pathfinding_failures),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Attack windup, strike and recovery, then eating the kill and growing from it
+            cancel_abandoned_attacks.after(behaviour_tree_system).before(hunt_solo_ai_system).before(hunt_pack_ai_system),
+            attack_visual_system.after(hunt_solo_ai_system).after(hunt_pack_ai_system),
+            eating_system.after(threat_perception_system).before(needs_behaviour_switching_system),
+            growth_system.after(eating_system).after(grazing_ai_system).after(feeding_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Taming and following
+            feeding_system,
+            follow_owner_system.after(feeding_system).before(hunt_solo_ai_system),
+            setup_follow_ai.after(behaviour_tree_system).after(follow_owner_system),
+            follow_ai_system.after(setup_follow_ai).after(handle_pathfinding_failures),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Status effects from blows and ground
+            inflict_attack_effects.after(hunt_solo_ai_system).after(hunt_pack_ai_system),
+            ground_status_effects,
+            apply_status_effects.after(inflict_attack_effects).after(ground_status_effects),
+            status_effect_system.after(apply_status_effects).before(pawn_death_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Picking items up and dropping them on death
+            item_pickup_system.after(move_pawn_to_target),
+            drop_inventory_on_death.before(pawn_death_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(Update, (
+            // Steering and holding the selected pawns
+            steer_controlled_pawns,
+            handle_hold_input,
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // The player's commands, live or from a replay, carried out on the tick they're for
+            take_player_commands.after(advance_sim_tick),
+            apply_selection_commands.after(take_player_commands),
+            take_control_of_selected.after(apply_selection_commands),
+            release_control.after(apply_selection_commands),
+            apply_control_commands.after(take_control_of_selected).after(release_control).before(move_pawn_to_target),
+            apply_order_commands.after(take_control_of_selected).after(release_control).before(attack_order_system),
+            apply_charge_commands.after(take_player_commands).before(charge_movement_system),
+            apply_feed_commands.after(take_player_commands).before(feeding_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Carrying out attack orders
+            attack_order_system.after(cancel_abandoned_attacks).before(move_pawn_to_target),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // New arrivals for species that have dwindled
+            population_manager_system.after(pawn_death_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(Update, (
+            // Walk cycles, turned and paced by how pawns were drawn moving this frame
+            walk_animation_system,
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Lying in ambush
+            setup_ambush_ai.after(behaviour_tree_system),
+            ambush_ai_system.after(setup_ambush_ai).after(scent_deposit_system).before(charge_movement_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Herding
+            setup_herding_ai.after(behaviour_tree_system),
+            herding_ai_system.after(setup_herding_ai).after(handle_pathfinding_failures),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Grazing and vegetation regrowth
+            setup_grazing_ai.after(behaviour_tree_system),
+            grazing_ai_system.after(setup_grazing_ai),
+            vegetation_growth_system,
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Drinking, sleeping, sight, smell, panic and utility scoring
+            threat_perception_system.before(behaviour_tree_system).before(needs_behaviour_switching_system),
+            panic_system.after(threat_perception_system).before(behaviour_tree_system).before(flee_ai_system),
+            utility_behaviour_system.after(need_decay_system).after(threat_perception_system),
+            update_vision_facing.after(move_pawn_to_target).after(charge_movement_system),
+            memory_system.after(update_vision_facing).before(hunt_solo_ai_system).before(flee_ai_system),
+            scent_decay_system,
+            scent_deposit_system.after(scent_decay_system).after(move_pawn_to_target).before(hunt_solo_ai_system),
+            setup_drinking_ai.after(behaviour_tree_system).after(needs_behaviour_switching_system).after(utility_behaviour_system),
+            drink_ai_system.after(setup_drinking_ai),
+            release_homeless_pawns.before(sleep_system),
+            sleep_system.after(behaviour_tree_system).after(needs_behaviour_switching_system).after(utility_behaviour_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Weather and seasons, and fog closing in on what pawns can see
+            weather_system.after(advance_sim_tick),
+            update_season.after(update_world_clock).before(need_decay_system).before(vegetation_growth_system),
+            apply_weather_to_vision.after(weather_system).before(memory_system).before(threat_perception_system),
+        ).in_set(GameSet::Simulation))
+        .add_systems(FixedUpdate, (
+            // Migration away from territories that have run out of food
+            update_region_food,
+            migration_system.after(update_region_food).after(behaviour_tree_system).before(release_homeless_pawns),
+        ).in_set(GameSet::Simulation))
         .add_systems(Update, (
             // Debug and UI systems
+            update_terrain_visuals,
             manage_debug_text_entities,
             update_debug_text.after(manage_debug_text_entities),
-            cleanup_orphaned_debug_text.after(pawn_death_system),
-            manage_waypoint_lines,
-            update_waypoint_lines.after(manage_waypoint_lines),
-            cleanup_orphaned_waypoint_lines.after(move_pawn_to_target),
-        ));
+            despawn_detached_entities,
+            draw_waypoint_lines,
+            sync_heatmap_recording.after(toggle_debug_display),
+            decay_pathfinding_heatmap,
+            update_heatmap_overlay.after(decay_pathfinding_heatmap),
+            record_bestiary_encounters,
+            record_bestiary_kills,
+            toggle_bestiary,
+            update_bestiary_panel.after(toggle_bestiary).after(record_bestiary_encounters).after(record_bestiary_kills),
+            log_combat_events,
+            update_selection_rings.after(handle_selection_input),
+            update_pawn_shadows,
+            update_selection_panel.after(handle_selection_input),
+            update_selection_box.after(handle_selection_input),
+            track_hovered_tile.after(keep_camera_over_map),
+            update_tile_tooltip.after(track_hovered_tile),
+            update_night_tint.after(keep_camera_over_map),
+        ).in_set(GameSet::World))
+        .add_systems(Update, (
+            // Statistics panels for the debug overlays
+            update_pathfinding_stats_panel.after(toggle_debug_display),
+            update_render_stats_panel.after(toggle_debug_display),
+            record_population_events,
+            update_population_panel.after(record_population_events).after(toggle_debug_display),
+            sample_population,
+            update_population_graph.after(sample_population).after(toggle_debug_display),
+        ).in_set(GameSet::World))
+        .add_systems(Update, (
+            // Passability overlay, before terrain changes are cleared
+            cycle_passability_size,
+            update_passability_overlay
+                .after(cycle_passability_size)
+                .after(toggle_debug_display)
+                .after(handle_player_input)
+                .before(update_terrain_visuals),
+        ).in_set(GameSet::World))
+        .add_systems(Update, (
+            // Rain, snow and fog over the view, snow settling on the ground and the season's colours
+            update_weather_overlays.after(keep_camera_over_map),
+            update_snow_overlays.before(update_terrain_visuals),
+            repaint_for_season.before(update_terrain_visuals),
+        ).in_set(GameSet::World))
+        .add_systems(Update, (
+            // Minimap in the corner
+            update_minimap_terrain.after(handle_player_input).before(update_terrain_visuals),
+            minimap_click.before(keep_camera_over_map),
+            update_minimap_markers.after(keep_camera_over_map),
+        ).in_set(GameSet::World))
+        .add_systems(Update, (
+            // Save/load systems
+            track_playtime,
+            quick_save,
+            quick_load,
+            // A loaded world is stocked as a new one is, from its seed at the tick it was saved on
+            (clear_world_for_load, place_dens, spawn_all_pawns, scatter_items).chain().after(quick_load).run_if(on_event::<WorldLoaded>),
+            save_replay,
+        ).in_set(GameSet::World));
 
-    // Conditionally add FPS counter based on settings
-    if config.show_fps {
-        app.add_plugins(FrameTimeDiagnosticsPlugin::default())
-            .add_systems(Startup, setup_fps_counter)
-            .add_systems(Update, update_fps_counter);
-    }
+    // FPS counter and behaviour icons, each shown or hidden live from the settings menu
+    app.add_plugins(FrameTimeDiagnosticsPlugin::default())
+        .add_systems(Startup, setup_fps_counter)
+        .add_systems(Update, update_fps_counter)
+        .add_systems(Update, update_behaviour_icons.in_set(GameSet::World))
+        .add_systems(Update, (spawn_combat_feedback, update_combat_feedback).in_set(GameSet::World))
+        // Less drawn per pawn the further out the camera is zoomed
+        .add_systems(Update, (
+            update_level_of_detail.after(camera_movement),
+            apply_level_of_detail.after(update_level_of_detail),
+        ).in_set(GameSet::World))
+        // Terrain effect and snow overlays drawn only for chunks in view
+        .add_systems(Update, cull_overlay_chunks.after(camera_movement).in_set(GameSet::World))
+        // Sound effects for blows and deaths, and footsteps heard and kicked up as dust or splashes
+        .add_systems(Update, (
+            play_event_sounds,
+            track_footsteps,
+            play_footsteps.after(track_footsteps),
+            spawn_footstep_particles.after(track_footsteps).after(apply_level_of_detail),
+            update_footstep_particles,
+        ).in_set(GameSet::World))
+        // Clicks, ambience and music carry on in the menus
+        .add_systems(Update, (play_click_sounds, update_ambience, update_music))
+        // Depth order for props, dens, items and pawns, once pawns are where they'll be drawn
+        .add_systems(Update, y_sort_system.in_set(GameSet::World))
+        // Fog over what the player's pawns can't see, hiding the pawns in it
+        .add_systems(Update, update_fog_of_war.after(update_behaviour_icons).after(update_minimap_markers).in_set(GameSet::World))
+        // Caps the frame rate at target_fps
+        .add_systems(Last, limit_frame_rate);
 
     app.insert_resource(config)
         .run();
@@ -1,6 +1,20 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use crate::systems::pathfinding_cache::{DEFAULT_MAX_PATH_ENTRIES, DEFAULT_MAX_PASSABILITY_ENTRIES};
+use crate::systems::sim_tick::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY};
+use crate::systems::lighting::DEFAULT_NIGHT_DARKNESS;
+use crate::systems::ai_tick::AiTickIntervals;
+use crate::systems::gamepad::GamepadTuning;
+use crate::systems::weather::WeatherSettings;
+use crate::systems::seasons::SeasonSettings;
+use crate::systems::y_sort::YSortSettings;
+use crate::systems::fog_of_war::FogOfWarSettings;
+use crate::systems::level_of_detail::LevelOfDetailSettings;
+use crate::systems::config_files::embedded_config;
+
+/// Where the game's settings are read from, and written back to from the settings menu
+pub const SETTINGS_FILE: &str = "settings.yaml";
 
 #[derive(Resource)]
 pub struct GameConfig {
@@ -11,11 +25,35 @@ pub struct GameConfig {
     pub zoom_min: f32,
     pub zoom_max: f32,
     pub mouse_sensitivity: f32,
+    pub camera_bounds_padding: f32, // Tiles the view can go past the edge of the map
     pub window_title: String,
-    pub target_fps: u32,
+    pub target_fps: u32, // 0 for no limit
+    pub window_width: u32,
+    pub window_height: u32,
+    pub fullscreen: bool,
+    pub vsync: bool,
     pub show_fps: bool,
+    pub show_behaviour_icons: bool,
+    pub show_damage_numbers: bool, // And hit flashes
+    pub volume: f32, // 0-1
+    pub sfx_volume: f32, // 0-1, on top of volume
+    pub music_volume: f32, // 0-1, on top of volume
     pub endurance_cost_per_cell: f32,
     pub health_loss_interval: f32,
+    pub sim_ticks_per_second: u32,
+    pub day_length_secs: u64, // Simulated seconds from one midnight to the next
+    pub night_darkness: f32,  // How dark the night tint gets, 0 for none and 1 for black
+    pub water_animation_real_time: bool,
+    pub path_cache_max_entries: usize,
+    pub passability_cache_max_entries: usize,
+    pub prevent_corner_cutting: bool,
+    pub ai_ticks: AiTickIntervals,
+    pub gamepad: GamepadTuning,
+    pub weather: WeatherSettings,
+    pub seasons: SeasonSettings,
+    pub y_sort: YSortSettings,
+    pub fog_of_war: FogOfWarSettings,
+    pub level_of_detail: LevelOfDetailSettings,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -23,6 +61,14 @@ struct Settings {
     world: WorldSettings,
     camera: CameraSettings,
     game: GameSettings,
+    pathfinding: Option<PathfindingSettings>,
+    ai: Option<AiTickIntervals>,
+    gamepad: Option<GamepadTuning>,
+    weather: Option<WeatherSettings>,
+    seasons: Option<SeasonSettings>,
+    y_sort: Option<YSortSettings>,
+    fog_of_war: Option<FogOfWarSettings>,
+    level_of_detail: Option<LevelOfDetailSettings>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,21 +84,42 @@ struct CameraSettings {
     zoom_min: f32,
     zoom_max: f32,
     mouse_sensitivity: f32,
+    bounds_padding: Option<f32>,
 }
 
 #[derive(Deserialize, Serialize)]
 struct GameSettings {
     window_title: String,
     target_fps: u32,
+    window_width: Option<u32>,
+    window_height: Option<u32>,
+    fullscreen: Option<bool>,
+    vsync: Option<bool>,
     show_fps: bool,
+    show_behaviour_icons: Option<bool>,
+    show_damage_numbers: Option<bool>,
+    volume: Option<f32>,
+    sfx_volume: Option<f32>,
+    music_volume: Option<f32>,
     endurance_cost_per_cell: Option<f32>,
     health_loss_interval: Option<f32>,
+    sim_ticks_per_second: Option<u32>,
+    day_length_secs: Option<u64>,
+    night_darkness: Option<f32>,
+    water_animation_real_time: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct PathfindingSettings {
+    path_cache_max_entries: Option<usize>,
+    passability_cache_max_entries: Option<usize>,
+    prevent_corner_cutting: Option<bool>,
 }
 
 impl GameConfig {
-    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        let settings: Settings = serde_yaml::from_str(&content)?;
+    pub fn from_yaml(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let settings: Settings = serde_yaml::from_str(content)?;
+        let pathfinding = settings.pathfinding.as_ref();
         
         Ok(GameConfig {
             tile_size: settings.world.tile_size,
@@ -62,11 +129,41 @@ impl GameConfig {
             zoom_min: settings.camera.zoom_min,
             zoom_max: settings.camera.zoom_max,
             mouse_sensitivity: settings.camera.mouse_sensitivity,
+            camera_bounds_padding: settings.camera.bounds_padding.unwrap_or(0.0),
             window_title: settings.game.window_title,
             target_fps: settings.game.target_fps,
+            window_width: settings.game.window_width.unwrap_or(1280),
+            window_height: settings.game.window_height.unwrap_or(720),
+            fullscreen: settings.game.fullscreen.unwrap_or(false),
+            vsync: settings.game.vsync.unwrap_or(true),
             show_fps: settings.game.show_fps,
+            show_behaviour_icons: settings.game.show_behaviour_icons.unwrap_or(true),
+            show_damage_numbers: settings.game.show_damage_numbers.unwrap_or(true),
+            volume: settings.game.volume.unwrap_or(1.0),
+            sfx_volume: settings.game.sfx_volume.unwrap_or(1.0),
+            music_volume: settings.game.music_volume.unwrap_or(0.6),
             endurance_cost_per_cell: settings.game.endurance_cost_per_cell.unwrap_or(0.1),
             health_loss_interval: settings.game.health_loss_interval.unwrap_or(5.0),
+            sim_ticks_per_second: settings.game.sim_ticks_per_second.unwrap_or(DEFAULT_TICKS_PER_SECOND),
+            day_length_secs: settings.game.day_length_secs.unwrap_or(SECONDS_PER_DAY),
+            night_darkness: settings.game.night_darkness.unwrap_or(DEFAULT_NIGHT_DARKNESS),
+            water_animation_real_time: settings.game.water_animation_real_time.unwrap_or(false),
+            path_cache_max_entries: pathfinding
+                .and_then(|p| p.path_cache_max_entries)
+                .unwrap_or(DEFAULT_MAX_PATH_ENTRIES),
+            passability_cache_max_entries: pathfinding
+                .and_then(|p| p.passability_cache_max_entries)
+                .unwrap_or(DEFAULT_MAX_PASSABILITY_ENTRIES),
+            prevent_corner_cutting: pathfinding
+                .and_then(|p| p.prevent_corner_cutting)
+                .unwrap_or(true),
+            ai_ticks: settings.ai.unwrap_or_default(),
+            gamepad: settings.gamepad.unwrap_or_default(),
+            weather: settings.weather.unwrap_or_default(),
+            seasons: settings.seasons.unwrap_or_default(),
+            y_sort: settings.y_sort.unwrap_or_default(),
+            fog_of_war: settings.fog_of_war.unwrap_or_default(),
+            level_of_detail: settings.level_of_detail.unwrap_or_default(),
         })
     }
 
@@ -79,11 +176,101 @@ impl GameConfig {
             zoom_min: 0.1,
             zoom_max: 10.0,
             mouse_sensitivity: 1.0,
+            camera_bounds_padding: 0.0,
             window_title: "Elementals RPG".to_string(),
             target_fps: 60,
+            window_width: 1280,
+            window_height: 720,
+            fullscreen: false,
+            vsync: true,
             show_fps: false, // Disabled by default in code
+            show_behaviour_icons: true,
+            show_damage_numbers: true,
+            volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 0.6,
             endurance_cost_per_cell: 0.1,
             health_loss_interval: 5.0,
+            sim_ticks_per_second: DEFAULT_TICKS_PER_SECOND,
+            day_length_secs: SECONDS_PER_DAY,
+            night_darkness: DEFAULT_NIGHT_DARKNESS,
+            water_animation_real_time: false,
+            path_cache_max_entries: DEFAULT_MAX_PATH_ENTRIES,
+            passability_cache_max_entries: DEFAULT_MAX_PASSABILITY_ENTRIES,
+            prevent_corner_cutting: true,
+            ai_ticks: AiTickIntervals::default(),
+            gamepad: GamepadTuning::default(),
+            weather: WeatherSettings::default(),
+            seasons: SeasonSettings::default(),
+            y_sort: YSortSettings::default(),
+            fog_of_war: FogOfWarSettings::default(),
+            level_of_detail: LevelOfDetailSettings::default(),
+        }
+    }
+
+    /// Write the options the settings menu changes back into the settings file, leaving everything
+    /// else in it - comments included - as it was. A new file starts from the built-in settings.
+    pub fn save_settings(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut content = fs::read_to_string(path)
+            .unwrap_or_else(|_| embedded_config(SETTINGS_FILE).unwrap_or_default().to_string());
+        let values = [
+            ("camera", "movement_speed", yaml_number(self.camera_speed)),
+            ("camera", "zoom_min", yaml_number(self.zoom_min)),
+            ("camera", "zoom_max", yaml_number(self.zoom_max)),
+            ("camera", "mouse_sensitivity", yaml_number(self.mouse_sensitivity)),
+            ("game", "show_fps", self.show_fps.to_string()),
+            ("game", "show_behaviour_icons", self.show_behaviour_icons.to_string()),
+            ("game", "show_damage_numbers", self.show_damage_numbers.to_string()),
+            ("game", "volume", yaml_number(self.volume)),
+        ];
+        for (section, key, value) in values {
+            content = set_yaml_value(&content, section, key, &value);
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// A setting rounded to two decimal places, so steps of 0.1 don't come out as 0.30000001
+fn yaml_number(value: f32) -> String {
+    format!("{:?}", (value * 100.0).round() / 100.0)
+}
+
+/// Set `key` in a top-level `section` of a YAML file's text, keeping any comment after the old
+/// value. A missing key is added at the end of its section, and a missing section at the end of
+/// the file.
+pub fn set_yaml_value(content: &str, section: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let is_indented = |line: &str| line.starts_with(' ') || line.starts_with('\t');
+    let header = format!("{}:", section);
+    let start = match lines.iter().position(|line| line.split('#').next().unwrap_or("").trim_end() == header) {
+        Some(start) => start,
+        None => {
+            if lines.last().is_some_and(|line| !line.is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(header);
+            lines.push(format!("  {}: {}", key, value));
+            return lines.join("\n") + "\n";
+        }
+    };
+
+    // The section runs until the next line that isn't indented, blank or a comment
+    let mut last_entry = start;
+    for index in start + 1..lines.len() {
+        let line = &lines[index];
+        if is_indented(line) {
+            if line.trim_start().starts_with(&format!("{}:", key)) {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let comment = line.find(" #").map(|at| line[at..].to_string()).unwrap_or_default();
+                lines[index] = format!("{}{}: {}{}", indent, key, value, comment);
+                return lines.join("\n") + "\n";
+            }
+            last_entry = index;
+        } else if !line.trim().is_empty() && !line.trim_start().starts_with('#') {
+            break;
         }
     }
-}
\ No newline at end of file
+    lines.insert(last_entry + 1, format!("  {}: {}", key, value));
+    lines.join("\n") + "\n"
+}
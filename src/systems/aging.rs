@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use crate::systems::animation::WalkAnimation;
+use crate::systems::growth::{Grown, growth_scale};
+use crate::systems::level_of_detail::PawnMarker;
+use crate::systems::pawn::{Pawn, Size, Health, TilesetManager};
+use crate::systems::pawn_config::{PawnConfig, AgingConfig, LifeStage};
+use crate::systems::sim_tick::{SimulationTick, TickPeriod, SECONDS_PER_DAY};
+
+/// Simulated seconds a pawn has lived and the life stage that puts it in
+#[derive(Component, Debug, Clone)]
+pub struct Age {
+    pub seconds: u64,
+    pub stage: LifeStage,
+}
+
+impl Age {
+    pub fn new(age_days: f32, aging: &AgingConfig) -> Self {
+        Self {
+            seconds: (age_days * SECONDS_PER_DAY as f32) as u64,
+            stage: aging.stage_at(age_days),
+        }
+    }
+
+    pub fn days(&self) -> f32 {
+        self.seconds as f32 / SECONDS_PER_DAY as f32
+    }
+}
+
+/// Point an atlas sprite at another sprite of the same tileset, e.g. "tileset::pawns::rabbit_young",
+/// or at another direct image path packed alongside its own
+fn set_atlas_sprite(sprite: &mut Sprite, sprite_path: &str, tileset_manager: &TilesetManager) {
+    let parts: Vec<&str> = sprite_path.split("::").collect();
+    let index = if parts.len() == 3 && parts[0] == "tileset" {
+        tileset_manager.get_sprite_index(parts[1], parts[2]).map(|index| index as usize)
+    } else if tileset_manager.is_packed(sprite) {
+        tileset_manager.packed_index(sprite_path)
+    } else {
+        None
+    };
+    if let (Some(atlas), Some(index)) = (sprite.texture_atlas.as_mut(), index) {
+        atlas.index = index;
+    }
+}
+
+/// Age pawns one second per simulated second: they change size, stats and sprite between
+/// life stages and die of old age at their max_age
+pub fn aging_system(
+    pawn_config: Res<PawnConfig>,
+    tileset_manager: Option<Res<TilesetManager>>,
+    mut tick_events: EventReader<SimulationTick>,
+    mut commands: Commands,
+    mut pawn_query: Query<(Entity, &Pawn, &mut Age, &mut Size, &mut Health, Option<&mut Sprite>, Option<&mut PawnMarker>, Option<&Grown>, Has<WalkAnimation>)>,
+) {
+    let seconds = tick_events.read().filter(|event| event.period == TickPeriod::Second).count() as u64;
+    if seconds == 0 {
+        return;
+    }
+
+    for (entity, pawn, mut age, mut size, mut health, sprite, marker, grown, animated) in pawn_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let aging = match definition.aging.as_ref() {
+            Some(aging) => aging,
+            None => continue,
+        };
+
+        age.seconds += seconds;
+        if age.days() >= aging.max_age {
+            if health.current > 0.0 {
+                health.current = 0.0;
+                println!("{} died of old age ({:.1} days)", pawn.pawn_type, age.days());
+            }
+            continue;
+        }
+
+        let stage = aging.stage_at(age.days());
+        if stage == age.stage {
+            continue;
+        }
+        age.stage = stage;
+        size.value = definition.size_for(Some(stage)) * growth_scale(grown);
+        // Pawns drawn as dots change the sprite they'll be drawn with up close
+        let sprite = match marker {
+            Some(marker) => Some(&mut marker.into_inner().sprite),
+            None => sprite.map(|sprite| sprite.into_inner()),
+        };
+        if let (Some(sprite), Some(tileset_manager)) = (sprite, tileset_manager.as_ref()) {
+            let sprite_path = definition.sprite_for(Some(stage));
+            set_atlas_sprite(sprite, sprite_path, tileset_manager);
+            // The new stage walks with its own sprite's cycle, or stands still as its static sprite
+            match WalkAnimation::for_sprite(sprite_path, tileset_manager) {
+                Some(walk_animation) => {
+                    commands.entity(entity).insert(walk_animation);
+                }
+                None if animated => {
+                    commands.entity(entity).remove::<WalkAnimation>();
+                }
+                None => {}
+            }
+        }
+        println!("{} is now {:?} ({:.1} days old)", pawn.pawn_type, stage, age.days());
+    }
+}
@@ -1,10 +1,121 @@
 use bevy::prelude::*;
 use rand::prelude::*;
-use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Endurance, Size};
-use crate::systems::pawn_config::PawnConfig;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Endurance, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType, BehaviourNode, BehaviourCondition, NeedKind, PawnType};
 use crate::systems::world_gen::{TerrainMap, GroundConfigs};
-use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingPriority, request_pathfinding};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask, PathfindingPriority, PathfindingFailed, PathfindFailure, PathfindingRequestCounter, request_pathfinding};
 use crate::resources::GameConfig;
+use crate::systems::combat::CombatEvent;
+use crate::systems::control::{Controlled, HoldPosition};
+use crate::systems::eating::{Eating, start_eating};
+use crate::systems::aging::Age;
+use crate::systems::growth::Grown;
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::attack::{Attacking, blow_damage};
+use crate::systems::world_clock::{WorldClock, DayPhase};
+use crate::systems::charge::{Charge, Stunned};
+use crate::systems::needs::{Thirst, Energy, Asleep, need_fractions, need_tended_by, urgent_need};
+use crate::systems::herd::HerdingAI;
+use crate::systems::memory::Memory;
+use crate::systems::migration::Migrating;
+use crate::systems::pack_hunt::HuntPackAI;
+use crate::systems::perception::{Threatened, Vision};
+use crate::systems::panic::Panicked;
+use crate::systems::scent::ScentMap;
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::taming::Owner;
+use crate::systems::sim_rng::SimRng;
+
+/// Delay before a wanderer whose path failed picks another destination
+pub const WANDER_RETRY_DELAY: f32 = 0.5;
+/// How often behaviour trees re-check their conditions unless settings.yaml says otherwise
+pub const BEHAVIOUR_TREE_TICK_SECS: f32 = 0.25;
+/// Fleeing pawns only run from predators closer than this, in tiles
+pub const FLEE_DETECTION_TILES: f32 = 8.0;
+/// How far a fleeing pawn tries to run in one go, in tiles
+pub const FLEE_DISTANCE_TILES: f32 = 6.0;
+
+/// The action a pawn's behaviour tree last picked. Replaces the behaviour configured for the
+/// pawn's CurrentBehavior state, which the tree keeps set to the action's name for display.
+#[derive(Component, Debug, Clone)]
+pub struct ActiveBehaviour {
+    pub action: BehaviourConfig,
+}
+
+/// What a behaviour tree knows about a pawn when it ticks
+#[derive(Debug, Clone, Default)]
+pub struct BehaviourContext {
+    pub endurance: f32,                // Fraction of max endurance
+    pub health: f32,                   // Fraction of max health
+    pub nearest_predator: Option<f32>, // Tiles
+    pub nearest_prey: Option<f32>,     // Tiles
+    pub thirst: Option<f32>,           // Fraction of max thirst, for pawns that get thirsty
+    pub energy: Option<f32>,           // Fraction of max energy, for pawns that get tired
+    pub urgent_need: Option<NeedKind>,
+    pub rest_time: bool,               // Outside the pawn's active hours
+    pub night: bool,
+    pub threatened: bool,              // Perception has spotted a predator, or a neighbour's panic has been caught
+}
+
+impl BehaviourContext {
+    pub fn check(&self, condition: &BehaviourCondition) -> bool {
+        match condition {
+            BehaviourCondition::EnduranceBelow(fraction) => self.endurance <= *fraction,
+            BehaviourCondition::EnduranceAbove(fraction) => self.endurance > *fraction,
+            BehaviourCondition::HealthBelow(fraction) => self.health <= *fraction,
+            BehaviourCondition::PredatorWithin(tiles) => self.nearest_predator.is_some_and(|distance| distance <= *tiles as f32),
+            BehaviourCondition::PreyWithin(tiles) => self.nearest_prey.is_some_and(|distance| distance <= *tiles as f32),
+            BehaviourCondition::ThirstBelow(fraction) => self.thirst.is_some_and(|thirst| thirst <= *fraction),
+            BehaviourCondition::EnergyBelow(fraction) => self.energy.is_some_and(|energy| energy <= *fraction),
+            BehaviourCondition::UrgentNeed(need) => self.urgent_need == Some(*need),
+            BehaviourCondition::RestTime => self.rest_time,
+            BehaviourCondition::Night => self.night,
+            BehaviourCondition::Threatened => self.threatened,
+            BehaviourCondition::Not(condition) => !self.check(condition),
+        }
+    }
+}
+
+/// The action a behaviour tree picks this tick, or None if it fails or succeeds without reaching one
+pub fn evaluate_behaviour_tree<'a>(node: &'a BehaviourNode, context: &BehaviourContext) -> Option<&'a BehaviourConfig> {
+    tick_behaviour_node(node, context).flatten()
+}
+
+/// None = failure, Some(None) = success without an action, Some(Some(action)) = running that action
+fn tick_behaviour_node<'a>(node: &'a BehaviourNode, context: &BehaviourContext) -> Option<Option<&'a BehaviourConfig>> {
+    match node {
+        BehaviourNode::Selector(children) => {
+            children.iter().find_map(|child| tick_behaviour_node(child, context))
+        }
+        BehaviourNode::Sequence(children) => {
+            for child in children {
+                // Actions keep running, so a sequence stops at the first one it reaches
+                if let Some(action) = tick_behaviour_node(child, context)? {
+                    return Some(Some(action));
+                }
+            }
+            Some(None)
+        }
+        BehaviourNode::Condition(condition) => {
+            if context.check(condition) { Some(None) } else { None }
+        }
+        BehaviourNode::Action(action) => Some(Some(action)),
+    }
+}
+
+/// The behaviour a pawn is following: its behaviour tree's action if it has one,
+/// otherwise whatever is configured for its current state
+pub fn resolve_behaviour<'a>(
+    pawn_config: &'a PawnConfig,
+    pawn: &Pawn,
+    current_behavior: &CurrentBehavior,
+    active_behaviour: Option<&'a ActiveBehaviour>,
+) -> Option<&'a BehaviourConfig> {
+    match active_behaviour {
+        Some(active) => Some(&active.action),
+        None => pawn_config.get_behaviour_config(&pawn.pawn_type, &current_behavior.state),
+    }
+}
 
 #[derive(Component)]
 pub struct WanderingAI {
@@ -18,8 +129,7 @@ impl WanderingAI {
         }
     }
 
-    pub fn schedule_next_move(&mut self, min_interval: f32, max_interval: f32) {
-        let mut rng = rand::thread_rng();
+    pub fn schedule_next_move(&mut self, min_interval: f32, max_interval: f32, rng: &mut impl Rng) {
         let interval = rng.gen_range(min_interval..=max_interval);
         self.next_move_time = interval;
     }
@@ -30,6 +140,7 @@ pub struct HuntSoloAI {
     pub target_entity: Option<Entity>,
     pub last_attack_time: f32,
     pub search_timer: f32,
+    pub unreachable_target: Option<Entity>, // Last prey we couldn't path to - skipped when searching
 }
 
 impl HuntSoloAI {
@@ -38,6 +149,7 @@ impl HuntSoloAI {
             target_entity: None,
             last_attack_time: 0.0,
             search_timer: 0.0,
+            unreachable_target: None,
         }
     }
 }
@@ -48,24 +160,33 @@ pub fn wandering_ai_system(
     ground_configs: Res<GroundConfigs>,
     pawn_config: Res<PawnConfig>,
     config: Res<GameConfig>,
+    mut ticker: Local<AiTicker>,
+    mut rng: ResMut<SimRng>,
     mut commands: Commands,
-    mut wandering_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut WanderingAI), (With<Pawn>, Without<PawnTarget>, Without<PathfindingRequest>)>,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut wandering_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut WanderingAI, Has<Swimmer>, Option<&ActiveBehaviour>), (With<Pawn>, Without<PawnTarget>, Without<PathfindingRequest>, Without<HoldPosition>)>,
 ) {
-    let mut rng = rand::thread_rng();
+    let rng = &mut *rng;
+    let interval = config.ai_ticks.wander;
+    ticker.advance(time.delta_secs());
     
-    for (entity, transform, pawn, size, current_behavior, mut ai) in wandering_query.iter_mut() {
+    for (entity, transform, pawn, size, current_behavior, mut ai, is_swimmer, active_behaviour) in wandering_query.iter_mut() {
         // Get wandering config for this pawn's current behavior
-        let wandering_config = match pawn_config.get_wandering_config(&pawn.pawn_type, &current_behavior.state) {
-            Some(config) => config,
-            None => continue, // Skip pawns without wandering behavior for current state
+        let wandering_config = match resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour) {
+            Some(BehaviourConfig::Wandering { wandering }) => wandering,
+            _ => continue, // Skip pawns without wandering behavior for current state
         };
+        if !ticker.is_due(entity, interval) {
+            continue;
+        }
 
-        // Update timer
-        ai.next_move_time -= time.delta_secs();
+        // Update timer with the time since this pawn's last turn
+        ai.next_move_time -= time.delta_secs().max(interval);
         
         // Time to move?
         if ai.next_move_time <= 0.0 {
             let current_pos = (transform.translation.x, transform.translation.y);
+            let movement_configs = ground_configs.for_movement(is_swimmer);
             
             // Try to find a random nearby passable location
             let mut attempts = 0;
@@ -83,16 +204,18 @@ pub fn wandering_ai_system(
                 let target_y = current_pos.1 + angle.sin() * distance;
                 let target_pos = (target_x, target_y);
                 
-                // Check if target is potentially passable (quick check)
-                if terrain_map.is_position_passable_for_size(target_pos.0, target_pos.1, size.value, &ground_configs) {
+                // Check if target is potentially passable and reachable (quick checks)
+                if terrain_map.is_position_passable_for_size(target_pos.0, target_pos.1, size.value, &movement_configs)
+                    && terrain_map.are_connected(current_pos, target_pos, size.value, &movement_configs)
+                {
                     // Request async pathfinding
-                    request_pathfinding(&mut commands, entity, current_pos, target_pos, size.value);
+                    request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, target_pos, size.value);
                     break;
                 }
             }
             
             // Schedule next move regardless of whether we found a path
-            ai.schedule_next_move(wandering_config.move_interval_min, wandering_config.move_interval_max);
+            ai.schedule_next_move(wandering_config.move_interval_min, wandering_config.move_interval_max, rng);
         }
     }
 }
@@ -101,13 +224,14 @@ pub fn wandering_ai_system(
 pub fn setup_wandering_ai(
     mut commands: Commands,
     pawn_config: Res<PawnConfig>,
-    wandering_query: Query<(Entity, &Pawn, &CurrentBehavior), (With<Pawn>, Without<WanderingAI>)>,
+    mut rng: ResMut<SimRng>,
+    wandering_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), (With<Pawn>, Without<WanderingAI>)>,
 ) {
-    for (entity, pawn, current_behavior) in wandering_query.iter() {
+    for (entity, pawn, current_behavior, active_behaviour) in wandering_query.iter() {
         // Check if this pawn has wandering behavior configured for its current state
-        if let Some(wandering_config) = pawn_config.get_wandering_config(&pawn.pawn_type, &current_behavior.state) {
+        if let Some(BehaviourConfig::Wandering { wandering: wandering_config }) = resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour) {
             let mut ai = WanderingAI::new();
-            ai.schedule_next_move(wandering_config.move_interval_min, wandering_config.move_interval_max);
+            ai.schedule_next_move(wandering_config.move_interval_min, wandering_config.move_interval_max, &mut *rng);
             commands.entity(entity).insert(ai);
         }
     }
@@ -117,12 +241,12 @@ pub fn setup_wandering_ai(
 pub fn setup_hunt_solo_ai(
     mut commands: Commands,
     pawn_config: Res<PawnConfig>,
-    hunt_query: Query<(Entity, &Pawn, &CurrentBehavior), (With<Pawn>, Without<HuntSoloAI>)>,
+    hunt_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), (With<Pawn>, Without<HuntSoloAI>)>,
 ) {
-    for (entity, pawn, current_behavior) in hunt_query.iter() {
-        // Check if this pawn has hunt_solo behavior configured for its current state
-        if let Some(behavior_config) = pawn_config.get_behaviour_config(&pawn.pawn_type, &current_behavior.state) {
-            if matches!(behavior_config, crate::systems::pawn_config::BehaviourConfig::Simple(crate::systems::pawn_config::BehaviourType::HuntSolo)) {
+    for (entity, pawn, current_behavior, active_behaviour) in hunt_query.iter() {
+        // Check if this pawn has hunt_solo (or eat) behavior configured for its current state
+        if let Some(behavior_config) = resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour) {
+            if matches!(behavior_config, BehaviourConfig::Simple(BehaviourType::HuntSolo | BehaviourType::Eat)) {
                 commands.entity(entity).insert(HuntSoloAI::new());
             }
         }
@@ -133,24 +257,33 @@ pub fn hunt_solo_ai_system(
     time: Res<Time>,
     pawn_config: Res<PawnConfig>,
     config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    scent_map: Option<Res<ScentMap>>,
+    mut combat_events: EventWriter<CombatEvent>,
     mut commands: Commands,
-    mut hunter_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut HuntSoloAI, &mut Endurance, Option<&PawnTarget>), (With<Pawn>, Without<PathfindingRequest>)>,
+    mut hunter_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut HuntSoloAI, Option<&PawnTarget>, Has<Swimmer>, Option<&ActiveBehaviour>, Option<&mut Attacking>), (With<Pawn>, Without<PathfindingRequest>, Without<Charge>, Without<Stunned>)>,
     mut prey_query: Query<(Entity, &Transform, &Pawn, &mut Health), (With<Pawn>, Without<HuntSoloAI>)>,
+    age_query: Query<&Age>,
+    grown_query: Query<&Grown>,
+    sleeper_query: Query<(), With<Asleep>>,
+    vision_query: Query<&Vision>,
+    mut memory_query: Query<&mut Memory>,
+    spatial_grid: Res<SpatialGrid>,
 ) {
-    for (hunter_entity, hunter_transform, hunter_pawn, hunter_size, current_behavior, mut hunt_ai, mut hunter_endurance, current_target) in hunter_query.iter_mut() {
-        // Only process if in hunt_solo behavior state
-        if let Some(behavior_config) = pawn_config.get_behaviour_config(&hunter_pawn.pawn_type, &current_behavior.state) {
-            if !matches!(behavior_config, crate::systems::pawn_config::BehaviourConfig::Simple(crate::systems::pawn_config::BehaviourType::HuntSolo)) {
-                continue;
-            }
-        } else {
-            continue;
-        }
+    for (hunter_entity, hunter_transform, hunter_pawn, hunter_size, current_behavior, mut hunt_ai, current_target, is_swimmer, active_behaviour, mut attacking) in hunter_query.iter_mut() {
+        // Only process if in hunt_solo (or eat) behavior state - eaters attack prey in reach but never chase
+        let chases_prey = match resolve_behaviour(&pawn_config, hunter_pawn, current_behavior, active_behaviour) {
+            Some(BehaviourConfig::Simple(BehaviourType::HuntSolo)) => true,
+            Some(BehaviourConfig::Simple(BehaviourType::Eat)) => false,
+            _ => continue,
+        };
 
         let hunter_def = match pawn_config.get_pawn_definition(&hunter_pawn.pawn_type) {
             Some(def) => def,
             None => continue,
         };
+        let reach_distance = hunter_def.reach as f32 * config.tile_size;
 
         // Update attack timer
         hunt_ai.last_attack_time += time.delta_secs();
@@ -161,33 +294,94 @@ pub fn hunt_solo_ai_system(
             if let Ok((_, target_transform, target_pawn, mut target_health)) = prey_query.get_mut(target_entity) {
                 // Check distance to target
                 let distance = hunter_transform.translation.distance(target_transform.translation);
-                let reach_distance = hunter_def.reach as f32 * config.tile_size;
 
-                // If within reach, attack
-                if distance <= reach_distance {
-                    let attack_interval = 1.0 / hunter_def.attack_speed;
-                    if hunt_ai.last_attack_time >= attack_interval {
+                // Wind up a new attack once the last one has had time to land
+                let in_reach = distance <= reach_distance;
+                let mut new_attack = None;
+                if in_reach && attacking.is_none() && hunt_ai.last_attack_time >= 1.0 / hunter_def.attack_speed {
+                    new_attack = Some(Attacking::new(target_entity));
+                    hunt_ai.last_attack_time = 0.0;
+                }
+                let delta = if new_attack.is_some() { 0.0 } else { time.delta_secs() };
+
+                if let Some(attack) = attacking.as_deref_mut().or(new_attack.as_mut()) {
+                    let step = attack.advance(delta, &hunter_def.attack_config());
+                    if step.struck && !in_reach {
+                        println!("{} dodged {}'s attack", target_pawn.pawn_type, hunter_pawn.pawn_type);
+                    } else if step.struck {
                         // Calculate damage
                         let target_def = pawn_config.get_pawn_definition(&target_pawn.pawn_type).unwrap();
-                        let damage = (hunter_def.strength as f32 - target_def.defence as f32).max(0.0);
+                        let hunter_stage = age_query.get(hunter_entity).ok().map(|age| age.stage);
+                        let target_stage = age_query.get(target_entity).ok().map(|age| age.stage);
+                        let damage = blow_damage(hunter_def, hunter_stage, grown_query.get(hunter_entity).ok(), target_def, target_stage, sleeper_query.contains(target_entity));
                         
                         target_health.current = (target_health.current - damage).max(0.0);
-                        hunt_ai.last_attack_time = 0.0;
-                        
-                        println!("{} attacks {} for {} damage (health: {:.1})", 
-                                hunter_pawn.pawn_type, target_pawn.pawn_type, damage, target_health.current);
+                        combat_events.send(CombatEvent { attacker: hunter_entity, victim: target_entity, damage });
 
                         // Check if target died
                         if target_health.current <= 0.0 {
-                            // Add target's max health to hunter's endurance
-                            hunter_endurance.current = (hunter_endurance.current + target_def.max_health as f32).min(hunter_endurance.max);
-                            println!("{} gained {} endurance from killing {}", 
-                                    hunter_pawn.pawn_type, target_def.max_health, target_pawn.pawn_type);
+                            // Settle down to eat the kill - worth its max health in endurance
+                            println!("{} killed {} and settles down to eat", hunter_pawn.pawn_type, target_pawn.pawn_type);
+                            start_eating(&mut commands, hunter_entity, &target_pawn.pawn_type, target_def.max_health as f32, &hunter_def.eats.meal_config());
                             hunt_ai.target_entity = None;
                         }
                     }
-                    continue; // Don't move if attacking
+
+                    if step.finished {
+                        if new_attack.is_none() {
+                            commands.entity(hunter_entity).remove::<Attacking>();
+                        }
+                    } else if let Some(new_attack) = new_attack {
+                        commands.entity(hunter_entity).insert(new_attack);
+                    }
+                    continue; // Attackers stand their ground
+                }
+
+                if in_reach {
+                    continue; // Don't move between attacks
+                } else if !chases_prey {
+                    // Prey got out of reach and eaters don't give chase
+                    hunt_ai.target_entity = None;
                 } else {
+                    let sees_target = vision_query.get(hunter_entity).map_or(true, |vision| {
+                        vision.can_see(hunter_transform.translation.truncate(), target_transform.translation.truncate(), &terrain_map, &ground_configs)
+                    });
+                    if !sees_target {
+                        // Out of sight - follow the prey's scent trail, or failing that make for where it was last seen
+                        if current_target.is_some_and(|pawn_target| !pawn_target.path.is_empty()) {
+                            continue;
+                        }
+                        let hunter_tile = terrain_map.world_to_tile_coords(hunter_transform.translation.x, hunter_transform.translation.y);
+                        let trail_pos = hunter_tile
+                            .zip(scent_map.as_ref())
+                            .and_then(|(tile, scent_map)| scent_map.follow_trail(tile, target_entity))
+                            .map(|(tile_x, tile_y)| terrain_map.tile_to_world_coords(tile_x, tile_y));
+                        // Once the hunter has reached the last sighting there's nothing more to go on
+                        let last_seen_pos = memory_query
+                            .get(hunter_entity)
+                            .ok()
+                            .and_then(|memory| memory.recall(target_entity))
+                            .filter(|last_seen| hunter_transform.translation.truncate().distance(*last_seen) > config.tile_size)
+                            .map(|last_seen| (last_seen.x, last_seen.y));
+                        match trail_pos.or(last_seen_pos) {
+                            Some(goal) => {
+                                let current_pos = (hunter_transform.translation.x, hunter_transform.translation.y);
+                                commands.entity(hunter_entity).insert(
+                                    PathfindingRequest::new(current_pos, goal, hunter_size.value)
+                                        .with_priority(PathfindingPriority::High)
+                                );
+                            }
+                            None => {
+                                println!("{} lost the trail of {}", hunter_pawn.pawn_type, target_pawn.pawn_type);
+                                if let Ok(mut memory) = memory_query.get_mut(hunter_entity) {
+                                    memory.forget(target_entity);
+                                }
+                                hunt_ai.target_entity = None;
+                            }
+                        }
+                        continue;
+                    }
+
                     // Move towards target - only create new path if hunter doesn't have one
                     let needs_new_path = match current_target {
                         Some(pawn_target) => {
@@ -220,32 +414,234 @@ pub fn hunt_solo_ai_system(
         if hunt_ai.search_timer >= 2.0 {
             hunt_ai.search_timer = 0.0;
             
-            let mut closest_target: Option<(Entity, f32)> = None;
             let hunter_pos = hunter_transform.translation;
+            let movement_configs = ground_configs.for_movement(is_swimmer);
+            let vision = vision_query.get(hunter_entity).ok();
+            let memory = memory_query.get(hunter_entity).ok();
+            let unreachable_target = hunt_ai.unreachable_target;
+            // Eaters only take what's already in reach
+            let search_radius = if chases_prey { f32::INFINITY } else { reach_distance };
 
-            for (prey_entity, prey_transform, prey_pawn, prey_health) in prey_query.iter() {
-                // Skip dead prey
-                if prey_health.current <= 0.0 {
-                    continue;
+            let closest_target = spatial_grid.nearest(hunter_pos.truncate(), search_radius, |prey_entity, _| {
+                let (_, prey_transform, prey_pawn, prey_health) = match prey_query.get(prey_entity) {
+                    Ok(prey) => prey,
+                    Err(_) => return false,
+                };
+                // Skip dead or unreachable prey
+                if prey_health.current <= 0.0 || unreachable_target == Some(prey_entity) {
+                    return false;
                 }
-                
-                // Check if hunter can eat this prey
-                if pawn_config.can_eat_by_tags(&hunter_pawn.pawn_type, &prey_pawn.pawn_type) {
-                    let distance = hunter_pos.distance(prey_transform.translation);
-                    
-                    if let Some((_, closest_dist)) = closest_target {
-                        if distance < closest_dist {
-                            closest_target = Some((prey_entity, distance));
-                        }
-                    } else {
-                        closest_target = Some((prey_entity, distance));
+
+                // Check if hunter can eat this prey and could ever reach it
+                let hunter_pos_2d = (hunter_pos.x, hunter_pos.y);
+                let prey_pos_2d = (prey_transform.translation.x, prey_transform.translation.y);
+                pawn_config.will_attack(&hunter_pawn.pawn_type, &prey_pawn.pawn_type)
+                    && terrain_map.are_connected(hunter_pos_2d, prey_pos_2d, hunter_size.value, &movement_configs)
+                    && (vision.is_none_or(|vision| {
+                        vision.can_see(hunter_pos.truncate(), prey_transform.translation.truncate(), &terrain_map, &ground_configs)
+                    }) || memory.is_some_and(|memory| memory.recall(prey_entity).is_some()))
+            });
+
+            if let Some((target_entity, _)) = closest_target {
+                hunt_ai.target_entity = Some(target_entity);
+            }
+        }
+    }
+}
+
+/// React to failed pathfinding: wanderers and herders pick a new destination, hunters drop unreachable prey.
+/// Prey that only failed because it was off the map isn't walled off, so it isn't skipped in later searches.
+pub fn handle_pathfinding_failures(
+    mut failed_events: EventReader<PathfindingFailed>,
+    mut wandering_query: Query<&mut WanderingAI>,
+    mut herd_query: Query<&mut HerdingAI>,
+    mut hunter_query: Query<&mut HuntSoloAI>,
+    mut pack_query: Query<&mut HuntPackAI>,
+) {
+    for event in failed_events.read() {
+        let unreachable = event.reason == PathfindFailure::NoPath;
+
+        if let Ok(mut hunt_ai) = hunter_query.get_mut(event.entity) {
+            if let Some(target) = hunt_ai.target_entity.take() {
+                if unreachable {
+                    hunt_ai.unreachable_target = Some(target);
+                }
+                continue;
+            }
+        }
+
+        // Pack hunters give up on the pack's prey; a leader also stops leading the pack to it
+        if let Ok(pack_ai) = pack_query.get(event.entity) {
+            let pack_target = pack_ai.leader
+                .and_then(|leader| pack_query.get(leader).ok())
+                .and_then(|leader_ai| leader_ai.target_entity);
+            if let Some(target) = pack_target {
+                if let Ok(mut pack_ai) = pack_query.get_mut(event.entity) {
+                    if unreachable {
+                        pack_ai.unreachable_target = Some(target);
+                    }
+                    if pack_ai.leader == Some(event.entity) {
+                        pack_ai.target_entity = None;
                     }
                 }
+                continue;
             }
+        }
 
-            if let Some((target_entity, _)) = closest_target {
-                hunt_ai.target_entity = Some(target_entity);
+        if let Ok(mut wandering_ai) = wandering_query.get_mut(event.entity) {
+            wandering_ai.next_move_time = wandering_ai.next_move_time.min(WANDER_RETRY_DELAY);
+        }
+        if let Ok(mut herd_ai) = herd_query.get_mut(event.entity) {
+            herd_ai.next_move_time = herd_ai.next_move_time.min(WANDER_RETRY_DELAY);
+        }
+    }
+}
+
+
+/// Tick every pawn's behaviour tree and switch it to the action the tree picks.
+/// Each pawn's conditions are re-checked once per behaviour tree tick interval; new pawns are
+/// ticked straight away.
+pub fn behaviour_tree_system(
+    time: Res<Time>,
+    mut ticker: Local<AiTicker>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    world_clock: Option<Res<WorldClock>>,
+    spatial_grid: Res<SpatialGrid>,
+    mut commands: Commands,
+    mut pawn_query: Query<(Entity, &Transform, &Pawn, &Health, &Endurance, Option<&Thirst>, Option<&Energy>, Has<Threatened>, Has<Panicked>, &mut CurrentBehavior, Option<&ActiveBehaviour>), (Without<Owner>, Without<Migrating>, Without<Eating>, Without<Controlled>)>, // Tamed pawns answer to their owner, migrating and eating ones keep at it, controlled ones do as the player says
+    other_query: Query<(&Pawn, &Health)>,
+) {
+    ticker.advance(time.delta_secs());
+
+    for (entity, transform, pawn, health, endurance, thirst, energy, threatened, panicked, mut current_behavior, active_behaviour) in pawn_query.iter_mut() {
+        if active_behaviour.is_some() && !ticker.is_due(entity, config.ai_ticks.behaviour_tree) {
+            continue;
+        }
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let tree = match definition.behaviour_tree.as_ref() {
+            Some(tree) => tree,
+            None => continue,
+        };
+
+        let tending = active_behaviour.and_then(|active| need_tended_by(&active.action));
+        let mut context = BehaviourContext {
+            endurance: endurance.current / endurance.max,
+            health: health.current / health.max,
+            nearest_predator: None,
+            nearest_prey: None,
+            thirst: thirst.map(|thirst| thirst.current / thirst.max),
+            energy: energy.map(|energy| energy.current / energy.max),
+            urgent_need: urgent_need(definition, &need_fractions(endurance, thirst, energy), tending),
+            rest_time: world_clock.as_ref().is_some_and(|clock| definition.schedule.rests_during(clock.phase())),
+            night: world_clock.as_ref().is_some_and(|clock| clock.phase() == DayPhase::Night),
+            threatened: threatened || panicked,
+        };
+        // Nearest living pawn that would attack this one, or that this one would attack, in tiles
+        let nearest_living = |attacks: &dyn Fn(&PawnType) -> bool| {
+            spatial_grid
+                .nearest(transform.translation.truncate(), f32::INFINITY, |other, _| {
+                    other != entity && other_query.get(other).is_ok_and(|(other_pawn, other_health)| other_health.current > 0.0 && attacks(&other_pawn.pawn_type))
+                })
+                .map(|(_, distance)| distance / config.tile_size)
+        };
+        context.nearest_predator = nearest_living(&|other_type| pawn_config.will_attack(other_type, &pawn.pawn_type));
+        context.nearest_prey = nearest_living(&|other_type| pawn_config.will_attack(&pawn.pawn_type, other_type));
+
+        let action = evaluate_behaviour_tree(tree, &context)
+            .cloned()
+            .unwrap_or(BehaviourConfig::Simple(BehaviourType::Null));
+        if let Some(active) = active_behaviour {
+            if active.action.name() == action.name() {
+                continue;
             }
+            // Drop whatever the previous action was doing
+            commands.entity(entity).remove::<(PawnTarget, PathfindingRequest, PathfindingTask)>();
         }
+
+        current_behavior.state = action.name().to_string();
+        commands.entity(entity).insert(ActiveBehaviour { action });
     }
-}
\ No newline at end of file
+}
+
+/// Fleeing pawns run directly away from the nearest predator, veering off if the way is blocked.
+/// Pawns with vision run from predators they can see, or from where they last saw them, and
+/// panicked pawns with no predator in sight run from where their neighbours spotted one.
+pub fn flee_ai_system(
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut commands: Commands,
+    flee_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, Has<Swimmer>, Option<&ActiveBehaviour>), (Without<PawnTarget>, Without<PathfindingRequest>, Without<PathfindingTask>)>,
+    threat_query: Query<(Entity, &Transform, &Pawn, &Health)>,
+    vision_query: Query<(&Vision, Option<&Memory>)>,
+    panic_query: Query<&Panicked>,
+    spatial_grid: Res<SpatialGrid>,
+) {
+    let detection_distance = FLEE_DETECTION_TILES * config.tile_size;
+
+    for (entity, transform, pawn, size, current_behavior, is_swimmer, active_behaviour) in flee_query.iter() {
+        if !matches!(resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour), Some(BehaviourConfig::Simple(BehaviourType::Flee))) {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let sight = vision_query.get(entity).ok();
+        // Predators nearby now, plus any remembered nearby that have since moved off
+        let remembered = sight.and_then(|(_, memory)| memory).into_iter().flat_map(|memory| memory.sightings.keys().copied());
+        let candidates = spatial_grid.within(position, detection_distance).map(|(threat, _)| threat).chain(remembered);
+        let mut nearest_threat: Option<(Vec2, f32)> = None;
+        for candidate in candidates {
+            let (threat, threat_transform, threat_pawn, threat_health) = match threat_query.get(candidate) {
+                Ok(threat) => threat,
+                Err(_) => continue,
+            };
+            if threat == entity || threat_health.current <= 0.0 || !pawn_config.will_attack(&threat_pawn.pawn_type, &pawn.pawn_type) {
+                continue;
+            }
+            let threat_position = match sight {
+                Some((vision, memory)) if !vision.can_see(position, threat_transform.translation.truncate(), &terrain_map, &ground_configs) => {
+                    match memory.and_then(|memory| memory.recall(threat)) {
+                        Some(last_seen) => last_seen,
+                        None => continue,
+                    }
+                }
+                _ => threat_transform.translation.truncate(),
+            };
+            let distance = position.distance(threat_position);
+            if distance <= detection_distance && nearest_threat.is_none_or(|(_, nearest)| distance < nearest) {
+                nearest_threat = Some((threat_position, distance));
+            }
+        }
+        // Panicked pawns that can't see the predator run from where their neighbours saw it
+        let panic_source = panic_query.get(entity).ok().map(|panicked| panicked.from);
+        let threat_position = match nearest_threat.map(|(threat_position, _)| threat_position).or(panic_source) {
+            Some(threat_position) => threat_position,
+            None => continue,
+        };
+
+        let away = (position - threat_position).try_normalize().unwrap_or(Vec2::X);
+        let current_pos = (position.x, position.y);
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+
+        // Straight away first, then veer further and further to either side
+        for angle in [0.0f32, 30.0, -30.0, 60.0, -60.0, 90.0, -90.0] {
+            let direction = Vec2::from_angle(angle.to_radians()).rotate(away);
+            let target = position + direction * FLEE_DISTANCE_TILES * config.tile_size;
+            let target_pos = (target.x, target.y);
+            if terrain_map.is_position_passable_for_size(target_pos.0, target_pos.1, size.value, &movement_configs)
+                && terrain_map.are_connected(current_pos, target_pos, size.value, &movement_configs)
+            {
+                commands.entity(entity).insert(
+                    PathfindingRequest::new(current_pos, target_pos, size.value)
+                        .with_priority(PathfindingPriority::High)
+                );
+                break;
+            }
+        }
+    }
+}
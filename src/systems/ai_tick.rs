@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::systems::ai::BEHAVIOUR_TREE_TICK_SECS;
+use crate::systems::memory::MEMORY_TICK_SECS;
+use crate::systems::perception::PERCEPTION_TICK_SECS;
+use crate::systems::scripting::SCRIPT_TICK_SECS;
+
+/// How often (in seconds) each AI system gives every pawn a turn, from the `ai` section of
+/// settings.yaml. 0 gives every pawn a turn every simulation step. Movement runs once per
+/// simulation step too, and is interpolated between steps for display.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AiTickIntervals {
+    pub wander: f32,         // Wandering and herding pawns deciding whether to move on
+    pub perception: f32,     // Prey looking out for predators
+    pub behaviour_tree: f32, // Behaviour trees re-checking their conditions
+    pub memory: f32,         // Pawns with vision noting who they can see
+    pub script: f32,         // Scripted pawns running their scripts
+}
+
+impl Default for AiTickIntervals {
+    fn default() -> Self {
+        Self {
+            wander: 0.0,
+            perception: PERCEPTION_TICK_SECS,
+            behaviour_tree: BEHAVIOUR_TREE_TICK_SECS,
+            memory: MEMORY_TICK_SECS,
+            script: SCRIPT_TICK_SECS,
+        }
+    }
+}
+
+/// Spreads a system's per-pawn work over its tick interval: each pawn gets one turn per
+/// interval, at an offset picked from its entity, so pawns don't all think on the same frame
+#[derive(Debug, Default)]
+pub struct AiTicker {
+    previous: f64,
+    elapsed: f64,
+}
+
+impl AiTicker {
+    /// Move the clock on - call once per run, before asking which pawns are due
+    pub fn advance(&mut self, delta: f32) {
+        self.previous = self.elapsed;
+        self.elapsed += delta as f64;
+    }
+
+    /// Whether the pawn's turn came round since the last run
+    pub fn is_due(&self, entity: Entity, interval: f32) -> bool {
+        if interval <= 0.0 {
+            return true;
+        }
+        let interval = interval as f64;
+        let offset = stagger_offset(entity) as f64;
+        (self.elapsed / interval - offset).floor() > (self.previous / interval - offset).floor()
+    }
+}
+
+/// Where in each interval a pawn takes its turn, as a fraction. Fibonacci hashing keeps
+/// consecutive entities well spread.
+pub fn stagger_offset(entity: Entity) -> f32 {
+    entity.index().wrapping_mul(2_654_435_769) as f32 / u32::MAX as f32
+}
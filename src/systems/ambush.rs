@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::charge::{Charge, Stunned};
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::scent::ScentMap;
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// How close (in tiles) to its hiding spot an ambusher has to be to count as in position
+pub const AMBUSH_SPOT_TOLERANCE_TILES: f32 = 0.5;
+
+/// A pawn lying still in ambush. Prey only notice it within `1 - stealth` of their usual
+/// detection radius.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Hidden {
+    pub stealth: f32,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct AmbushAI {
+    pub spot: Option<Vec2>, // Where it's hiding, or heading to hide
+    pub waited: f32,        // Seconds spent hidden there without prey coming by
+}
+
+fn is_ambushing(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::Ambush))
+    )
+}
+
+/// The reachable tile within `radius` tiles that prey have left the most scent on, or the
+/// pawn's own tile if no prey has been by
+pub fn pick_ambush_spot(
+    position: Vec2,
+    radius: i32,
+    size: f32,
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+    scent_map: Option<&ScentMap>,
+) -> Option<(i32, i32)> {
+    let (start_x, start_y) = terrain_map.world_to_tile_coords(position.x, position.y)?;
+    let mut candidates: Vec<((i32, i32), f32)> = Vec::new();
+    if let Some(scent_map) = scent_map {
+        for dx in -radius..=radius {
+            for dy in -radius..=radius {
+                let tile = (start_x + dx, start_y + dy);
+                let traffic = scent_map.traffic_at(tile);
+                if traffic > 0.0 {
+                    candidates.push((tile, traffic));
+                }
+            }
+        }
+    }
+    candidates.sort_by(|(a_tile, a_traffic), (b_tile, b_traffic)| b_traffic.total_cmp(a_traffic).then(a_tile.cmp(b_tile)));
+
+    let current_pos = (position.x, position.y);
+    candidates
+        .into_iter()
+        .map(|(tile, _)| tile)
+        .find(|&(x, y)| {
+            let tile_pos = terrain_map.tile_to_world_coords(x, y);
+            terrain_map.is_position_passable_for_size(tile_pos.0, tile_pos.1, size, ground_configs)
+                && terrain_map.are_connected(current_pos, tile_pos, size, ground_configs)
+        })
+        .or(Some((start_x, start_y)))
+}
+
+// System to add AmbushAI component to pawns with ambush behavior
+pub fn setup_ambush_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    ambush_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), Without<AmbushAI>>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in ambush_query.iter() {
+        if is_ambushing(&pawn_config, pawn, current_behavior, active_behaviour) {
+            commands.entity(entity).insert(AmbushAI::default());
+        }
+    }
+}
+
+/// Ambushers find the busiest spot nearby by the scent prey have left, go there and lie
+/// still, hidden. The first prey to come within strike range gets burst out at - a charge at
+/// `burst_speed` - after which they look for a spot again. So do ambushers that have waited
+/// out their patience.
+pub fn ambush_ai_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    scent_map: Option<Res<ScentMap>>,
+    spatial_grid: Res<SpatialGrid>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut ambusher_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut AmbushAI, Option<&ActiveBehaviour>, Option<&PawnTarget>, Has<PathfindingRequest>, Has<Swimmer>, Has<Hidden>), (Without<Charge>, Without<Stunned>)>,
+    prey_query: Query<(&Transform, &Pawn, &Health)>,
+) {
+    for (entity, transform, pawn, size, current_behavior, mut ambush_ai, active_behaviour, pawn_target, has_path_request, is_swimmer, hidden) in ambusher_query.iter_mut() {
+        if !is_ambushing(&pawn_config, pawn, current_behavior, active_behaviour) {
+            if hidden {
+                commands.entity(entity).remove::<Hidden>();
+            }
+            ambush_ai.spot = None;
+            continue;
+        }
+        let def = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(def) => def,
+            None => continue,
+        };
+        let ambush = def.ambush_config();
+        let position = transform.translation.truncate();
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+
+        let spot = match ambush_ai.spot {
+            Some(spot) => spot,
+            None => {
+                let tile = match pick_ambush_spot(position, ambush.search_radius as i32, size.value, &terrain_map, &movement_configs, scent_map.as_deref()) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                let (x, y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+                ambush_ai.spot = Some(Vec2::new(x, y));
+                ambush_ai.waited = 0.0;
+                Vec2::new(x, y)
+            }
+        };
+
+        // Get into position first
+        if position.distance(spot) > AMBUSH_SPOT_TOLERANCE_TILES * config.tile_size {
+            if hidden {
+                commands.entity(entity).remove::<Hidden>();
+            }
+            if pawn_target.is_none() && !has_path_request {
+                request_pathfinding(&mut commands, &mut request_counter, entity, (position.x, position.y), (spot.x, spot.y), size.value);
+            }
+            continue;
+        }
+        if pawn_target.is_some() {
+            commands.entity(entity).remove::<PawnTarget>();
+        }
+        if !hidden {
+            println!("{} lies in ambush", pawn.pawn_type);
+            commands.entity(entity).insert(Hidden { stealth: def.stealth.clamp(0.0, 1.0) });
+        }
+
+        // Spring the trap on the nearest prey within strike range
+        let strike_range = ambush.strike_range as f32 * config.tile_size;
+        let prey = spatial_grid.nearest(position, strike_range, |other, _| {
+            other != entity && prey_query.get(other).is_ok_and(|(_, prey_pawn, prey_health)| {
+                prey_health.current > 0.0 && pawn_config.will_attack(&pawn.pawn_type, &prey_pawn.pawn_type)
+            })
+        });
+        if let Some((prey_transform, prey_pawn, _)) = prey.and_then(|(prey, _)| prey_query.get(prey).ok()) {
+            println!("{} bursts out of hiding at {}", pawn.pawn_type, prey_pawn.pawn_type);
+            let direction = (prey_transform.translation.truncate() - position).normalize_or_zero();
+            commands.entity(entity)
+                .remove::<Hidden>()
+                .insert(Charge {
+                    direction,
+                    speed: ambush.burst_speed,
+                    max_speed: ambush.burst_speed,
+                    acceleration: 0.0,
+                    remaining_distance: strike_range + config.tile_size,
+                });
+            ambush_ai.spot = None;
+            continue;
+        }
+
+        ambush_ai.waited += time.delta_secs();
+        if ambush_ai.waited >= ambush.patience {
+            ambush_ai.spot = None;
+        }
+    }
+}
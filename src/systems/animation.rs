@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::systems::level_of_detail::{Detail, LevelOfDetail};
+use crate::systems::pawn::TilesetManager;
+
+/// Pixels a pawn walks before its animation moves on a frame, unless the animation says otherwise
+pub const DEFAULT_FRAME_DISTANCE: f32 = 8.0;
+/// Pawns that moved less than this many pixels since the last frame are standing still
+const STILL_DISTANCE: f32 = 0.01;
+
+/// Walk cycle for a sprite, listed under `animations` in its tileset's YAML by the sprite's name.
+/// Each direction is a sequence of sprites from the same tileset, the first one doubling as the
+/// standing pose.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SpriteAnimation {
+    #[serde(default)]
+    pub down: Vec<String>,
+    #[serde(default)]
+    pub up: Vec<String>,
+    #[serde(default)]
+    pub left: Vec<String>,
+    #[serde(default)]
+    pub right: Vec<String>,
+    #[serde(default = "default_frame_distance")]
+    pub frame_distance: f32, // Pixels walked per frame, so faster pawns step faster
+}
+
+fn default_frame_distance() -> f32 {
+    DEFAULT_FRAME_DISTANCE
+}
+
+impl SpriteAnimation {
+    /// Frames for a direction, falling back to facing down for directions without any
+    pub fn frames(&self, facing: Facing) -> &[String] {
+        let frames = match facing {
+            Facing::Down => &self.down,
+            Facing::Up => &self.up,
+            Facing::Left => &self.left,
+            Facing::Right => &self.right,
+        };
+        if frames.is_empty() { &self.down } else { frames }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Facing {
+    #[default]
+    Down,
+    Up,
+    Left,
+    Right,
+}
+
+impl Facing {
+    /// Direction a movement mostly goes in
+    pub fn from_movement(movement: Vec2) -> Self {
+        if movement.x.abs() > movement.y.abs() {
+            if movement.x > 0.0 { Facing::Right } else { Facing::Left }
+        } else if movement.y > 0.0 {
+            Facing::Up
+        } else {
+            Facing::Down
+        }
+    }
+}
+
+/// Pawn whose sprite has a walk cycle in its tileset
+#[derive(Component, Debug, Clone)]
+pub struct WalkAnimation {
+    pub tileset: String,
+    pub animation: String,
+    pub facing: Facing,
+    pub frame: usize,
+    pub distance: f32, // Walked since the last frame change
+    pub last_position: Option<Vec2>,
+}
+
+impl WalkAnimation {
+    /// Walk cycle for a "tileset::name::sprite" reference, if its tileset has one for that sprite
+    pub fn for_sprite(sprite_path: &str, tileset_manager: &TilesetManager) -> Option<Self> {
+        let parts: Vec<&str> = sprite_path.split("::").collect();
+        if parts.len() != 3 || parts[0] != "tileset" {
+            return None;
+        }
+        tileset_manager.get_animation(parts[1], parts[2])?;
+        Some(Self {
+            tileset: parts[1].to_string(),
+            animation: parts[2].to_string(),
+            facing: Facing::Down,
+            frame: 0,
+            distance: 0.0,
+            last_position: None,
+        })
+    }
+}
+
+/// Turn animated pawns towards where they're going and step through their walk cycle a frame
+/// every `frame_distance` pixels they actually cover. Standing pawns show their first frame.
+pub fn walk_animation_system(
+    tileset_manager: Res<TilesetManager>,
+    level_of_detail: Res<LevelOfDetail>,
+    mut pawn_query: Query<(&Transform, &mut WalkAnimation, &mut Sprite)>,
+) {
+    // Too far out to make out a walk cycle - they pick up where they are when the view comes back in
+    if level_of_detail.detail > Detail::Full {
+        return;
+    }
+    for (transform, mut walk, mut sprite) in pawn_query.iter_mut() {
+        let animation = match tileset_manager.get_animation(&walk.tileset, &walk.animation) {
+            Some(animation) => animation,
+            None => continue,
+        };
+        let position = transform.translation.truncate();
+        let movement = walk.last_position.map_or(Vec2::ZERO, |last| position - last);
+        walk.last_position = Some(position);
+
+        let moved = movement.length();
+        if moved < STILL_DISTANCE {
+            walk.frame = 0;
+            walk.distance = 0.0;
+        } else {
+            let facing = Facing::from_movement(movement);
+            if facing != walk.facing {
+                walk.facing = facing;
+                walk.frame = 0;
+                walk.distance = 0.0;
+            }
+            let frame_count = animation.frames(walk.facing).len().max(1);
+            walk.distance += moved;
+            let frame_distance = animation.frame_distance.max(STILL_DISTANCE);
+            while walk.distance >= frame_distance {
+                walk.distance -= frame_distance;
+                walk.frame = (walk.frame + 1) % frame_count;
+            }
+        }
+
+        let frame = match animation.frames(walk.facing).get(walk.frame) {
+            Some(frame) => frame,
+            None => continue,
+        };
+        if let (Some(atlas), Some(index)) = (sprite.texture_atlas.as_mut(), tileset_manager.get_sprite_index(&walk.tileset, frame)) {
+            if atlas.index != index as usize {
+                atlas.index = index as usize;
+            }
+        }
+    }
+}
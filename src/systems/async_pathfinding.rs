@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use bevy::tasks::{AsyncComputeTaskPool, Task};
 use crate::systems::world_gen::{TerrainMap, GroundConfigs};
-use crate::systems::pawn::PawnTarget;
+use crate::systems::pawn::{PawnTarget, Swimmer};
 use crate::systems::pathfinding_cache::PathfindingCache;
+use crate::systems::pathfinding_heatmap::PathfindingHeatmap;
+
+/// Simulation steps a search gets to run in the background. Its path is handed over on exactly
+/// that step, waiting for the search if it has to, so a game plays out the same however busy the
+/// thread pool is.
+pub const PATH_DELIVERY_TICKS: u32 = 1;
 
 /// Component that holds a running pathfinding task
 #[derive(Component)]
@@ -12,6 +18,18 @@ pub struct PathfindingTask {
     pub goal: (f32, f32),
     pub size: f32,
     pub request_id: u64,
+    pub ticks_waited: u32,
+}
+
+impl PathfindingTask {
+    /// The search's result once it's had PATH_DELIVERY_TICKS steps to run, counting this one
+    fn take_result(&mut self) -> Option<PathfindingResult> {
+        if self.ticks_waited < PATH_DELIVERY_TICKS {
+            self.ticks_waited += 1;
+            return None;
+        }
+        Some(bevy::tasks::block_on(&mut self.task))
+    }
 }
 
 /// Result of a pathfinding computation
@@ -22,6 +40,8 @@ pub struct PathfindingResult {
     pub goal: (f32, f32),
     pub size: f32,
     pub request_id: u64,
+    pub expanded_tiles: Vec<(i32, i32)>, // Only filled while the heatmap is recording
+    pub can_swim: bool,
 }
 
 /// Component to mark entities that need pathfinding
@@ -31,7 +51,7 @@ pub struct PathfindingRequest {
     pub goal: (f32, f32),
     pub size: f32,
     pub priority: PathfindingPriority,
-    pub request_id: u64,
+    pub request_id: Option<u64>, // Given by request_pathfinding, otherwise when its search starts
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -42,6 +62,29 @@ pub enum PathfindingPriority {
     Critical = 3,
 }
 
+/// Why a pathfinding request produced no path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathfindFailure {
+    NoPath,      // Start and goal are on the map but not connected for this size
+    OutOfBounds, // Start or goal lies outside the map
+}
+
+/// Sent when a pathfinding request (cached or computed) yields a path
+#[derive(Event, Debug, Clone)]
+pub struct PathfindingCompleted {
+    pub entity: Entity,
+    pub request_id: u64,
+    pub path_length: usize, // Waypoints in the path
+}
+
+/// Sent when a pathfinding request (cached or computed) yields no path
+#[derive(Event, Debug, Clone)]
+pub struct PathfindingFailed {
+    pub entity: Entity,
+    pub request_id: u64,
+    pub reason: PathfindFailure,
+}
+
 /// Resource to track pathfinding request IDs
 #[derive(Resource, Default)]
 pub struct PathfindingRequestCounter {
@@ -70,6 +113,22 @@ impl Default for GlobalPathfindingCache {
     }
 }
 
+impl GlobalPathfindingCache {
+    pub fn with_limits(max_path_entries: usize, max_passability_entries: usize) -> Self {
+        Self {
+            cache: PathfindingCache::with_limits(max_path_entries, max_passability_entries),
+        }
+    }
+
+    pub fn cache(&self) -> &PathfindingCache {
+        &self.cache
+    }
+
+    pub fn cache_mut(&mut self) -> &mut PathfindingCache {
+        &mut self.cache
+    }
+}
+
 impl PathfindingRequest {
     pub fn new(start: (f32, f32), goal: (f32, f32), size: f32) -> Self {
         Self {
@@ -77,7 +136,7 @@ impl PathfindingRequest {
             goal,
             size,
             priority: PathfindingPriority::Normal,
-            request_id: 0, // Will be set by the system
+            request_id: None,
         }
     }
 
@@ -85,6 +144,11 @@ impl PathfindingRequest {
         self.priority = priority;
         self
     }
+
+    pub fn with_id(mut self, request_id: u64) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
 }
 
 /// System to spawn pathfinding tasks for entities with PathfindingRequest
@@ -102,8 +166,7 @@ pub fn spawn_pathfinding_tasks(
     requests.sort_by(|a, b| b.1.priority.cmp(&a.1.priority));
     
     for (entity, request) in requests {
-        // Generate unique request ID
-        let request_id = request_counter.next_id();
+        let request_id = request.request_id.unwrap_or_else(|| request_counter.next_id());
         
         // Clone data for the async task
         let terrain_clone = terrain_map.clone();
@@ -123,6 +186,8 @@ pub fn spawn_pathfinding_tasks(
                 goal,
                 size,
                 request_id,
+                expanded_tiles: Vec::new(),
+                can_swim: false,
             }
         });
         
@@ -136,6 +201,7 @@ pub fn spawn_pathfinding_tasks(
                     goal,
                     size,
                     request_id,
+                    ticks_waited: 0,
                 });
         }
     }
@@ -147,8 +213,7 @@ pub fn handle_completed_pathfinding(
     mut completed_query: Query<(Entity, &mut PathfindingTask)>,
 ) {
     for (entity, mut pathfinding_task) in completed_query.iter_mut() {
-        // Check if task is finished
-        if let Some(result) = bevy::tasks::block_on(bevy::tasks::poll_once(&mut pathfinding_task.task)) {
+        if let Some(result) = pathfinding_task.take_result() {
             // Task completed, process result
             if let Some(path) = result.path {
                 // Create PawnTarget with the computed path
@@ -178,7 +243,10 @@ pub fn spawn_cached_pathfinding_tasks(
     ground_configs: Res<GroundConfigs>,
     mut global_cache: ResMut<GlobalPathfindingCache>,
     mut request_counter: ResMut<PathfindingRequestCounter>,
-    request_query: Query<(Entity, &PathfindingRequest), Without<PathfindingTask>>,
+    mut heatmap: ResMut<PathfindingHeatmap>,
+    mut completed_events: EventWriter<PathfindingCompleted>,
+    mut failed_events: EventWriter<PathfindingFailed>,
+    request_query: Query<(Entity, &PathfindingRequest, Has<Swimmer>), Without<PathfindingTask>>,
 ) {
     let task_pool = AsyncComputeTaskPool::get();
     
@@ -186,47 +254,65 @@ pub fn spawn_cached_pathfinding_tasks(
     let mut requests: Vec<_> = request_query.iter().collect();
     requests.sort_by(|a, b| b.1.priority.cmp(&a.1.priority));
     
-    for (entity, request) in requests {
-        // Generate unique request ID
-        let request_id = request_counter.next_id();
-        
-        // Check cache first (synchronously, should be fast)
-        let start_tile = terrain_map.world_to_tile_coords(request.start.0, request.start.1);
-        let goal_tile = terrain_map.world_to_tile_coords(request.goal.0, request.goal.1);
+    for (entity, request, can_swim) in requests {
+        let request_id = request.request_id.unwrap_or_else(|| request_counter.next_id());
         
-        if let (Some(start_tile), Some(goal_tile)) = (start_tile, goal_tile) {
-            if let Some(cached_path) = global_cache.cache.get_path(start_tile, goal_tile, request.size) {
-                // Cache hit! Use cached result immediately
-                if let Some(path) = cached_path {
-                    let target_pos = Vec3::new(request.goal.0, request.goal.1, 100.0);
-                    let mut pawn_target = PawnTarget::new(target_pos);
-                    pawn_target.set_path(path.clone());
-                    
-                    if let Some(mut entity_commands) = commands.get_entity(entity) {
-                        entity_commands
-                            .remove::<PathfindingRequest>()
-                            .insert(pawn_target);
+        // Check cache first (synchronously, should be fast).
+        // Swimmer paths are never cached - cache keys don't tell swimmers and walkers apart.
+        if !can_swim {
+            let start_tile = terrain_map.world_to_tile_coords(request.start.0, request.start.1);
+            let goal_tile = terrain_map.world_to_tile_coords(request.goal.0, request.goal.1);
+            
+            if let (Some(start_tile), Some(goal_tile)) = (start_tile, goal_tile) {
+                if let Some(cached_path) = global_cache.cache.get_path(start_tile, goal_tile, request.size) {
+                    // Cache hit! Use cached result immediately
+                    if heatmap.recording {
+                        heatmap.record_cache_hit(start_tile);
                     }
-                } else {
-                    // Cached "no path" result
-                    if let Some(mut entity_commands) = commands.get_entity(entity) {
-                        entity_commands.remove::<PathfindingRequest>();
+                    if let Some(path) = cached_path {
+                        completed_events.send(PathfindingCompleted { entity, request_id, path_length: path.len() });
+
+                        let target_pos = Vec3::new(request.goal.0, request.goal.1, 100.0);
+                        let mut pawn_target = PawnTarget::new(target_pos);
+                        pawn_target.set_path(path.clone());
+                        
+                        if let Some(mut entity_commands) = commands.get_entity(entity) {
+                            entity_commands
+                                .remove::<PathfindingRequest>()
+                                .insert(pawn_target);
+                        }
+                    } else {
+                        // Cached "no path" result
+                        failed_events.send(PathfindingFailed {
+                            entity,
+                            request_id,
+                            reason: PathfindFailure::NoPath,
+                        });
+                        if let Some(mut entity_commands) = commands.get_entity(entity) {
+                            entity_commands.remove::<PathfindingRequest>();
+                        }
                     }
+                    continue;
                 }
-                continue;
             }
         }
         
         // Cache miss, spawn async task
         let terrain_clone = terrain_map.clone();
-        let ground_configs_clone = ground_configs.clone();
+        let ground_configs_clone = ground_configs.for_movement(can_swim).into_owned();
         let start = request.start;
         let goal = request.goal;
         let size = request.size;
+        let trace = heatmap.recording;
         
         let task = task_pool.spawn(async move {
             // Perform pathfinding computation in background thread
-            let path = terrain_clone.find_path_for_size(start, goal, size, &ground_configs_clone);
+            let mut expanded_tiles = Vec::new();
+            let path = if trace {
+                terrain_clone.find_path_for_size_traced(start, goal, size, &ground_configs_clone, &mut expanded_tiles)
+            } else {
+                terrain_clone.find_path_for_size(start, goal, size, &ground_configs_clone)
+            };
             
             PathfindingResult {
                 path,
@@ -234,6 +320,8 @@ pub fn spawn_cached_pathfinding_tasks(
                 goal,
                 size,
                 request_id,
+                expanded_tiles,
+                can_swim,
             }
         });
         
@@ -246,6 +334,7 @@ pub fn spawn_cached_pathfinding_tasks(
                     goal,
                     size,
                     request_id,
+                    ticks_waited: 0,
                 });
         }
     }
@@ -256,16 +345,42 @@ pub fn handle_completed_cached_pathfinding(
     mut commands: Commands,
     terrain_map: Res<TerrainMap>,
     mut global_cache: ResMut<GlobalPathfindingCache>,
+    mut heatmap: ResMut<PathfindingHeatmap>,
+    mut completed_events: EventWriter<PathfindingCompleted>,
+    mut failed_events: EventWriter<PathfindingFailed>,
     mut completed_query: Query<(Entity, &mut PathfindingTask)>,
 ) {
     for (entity, mut pathfinding_task) in completed_query.iter_mut() {
-        if let Some(result) = bevy::tasks::block_on(bevy::tasks::poll_once(&mut pathfinding_task.task)) {
+        if let Some(result) = pathfinding_task.take_result() {
+            if heatmap.recording {
+                heatmap.record_expanded(&result.expanded_tiles);
+            }
+
             // Update cache with result
-            if let (Some(start_tile), Some(goal_tile)) = (
+            let in_bounds = if let (Some(start_tile), Some(goal_tile)) = (
                 terrain_map.world_to_tile_coords(result.start.0, result.start.1),
                 terrain_map.world_to_tile_coords(result.goal.0, result.goal.1)
             ) {
-                global_cache.cache.cache_path(start_tile, goal_tile, result.size, result.path.clone(), &terrain_map);
+                if !result.can_swim {
+                    global_cache.cache.cache_path(start_tile, goal_tile, result.size, result.path.clone(), &terrain_map);
+                }
+                true
+            } else {
+                false
+            };
+            
+            // Let AI systems know how the request turned out
+            match &result.path {
+                Some(path) => {
+                    completed_events.send(PathfindingCompleted { entity, request_id: result.request_id, path_length: path.len() });
+                }
+                None => {
+                    failed_events.send(PathfindingFailed {
+                        entity,
+                        request_id: result.request_id,
+                        reason: if in_bounds { PathfindFailure::NoPath } else { PathfindFailure::OutOfBounds },
+                    });
+                }
             }
             
             // Process result
@@ -300,31 +415,34 @@ pub fn cleanup_stale_pathfinding(
     }
 }
 
-/// Helper function to request pathfinding for an entity
+/// Helper function to request pathfinding for an entity. Returns the request's id, which the
+/// PathfindingCompleted or PathfindingFailed event for it will carry.
 pub fn request_pathfinding(
     commands: &mut Commands,
+    request_counter: &mut PathfindingRequestCounter,
     entity: Entity,
     start: (f32, f32),
     goal: (f32, f32),
     size: f32,
-) {
-    if let Some(mut entity_commands) = commands.get_entity(entity) {
-        entity_commands.insert(PathfindingRequest::new(start, goal, size));
-    }
+) -> u64 {
+    request_priority_pathfinding(commands, request_counter, entity, start, goal, size, PathfindingPriority::Normal)
 }
 
 /// Helper function to request high-priority pathfinding (e.g., player input)
 pub fn request_priority_pathfinding(
     commands: &mut Commands,
+    request_counter: &mut PathfindingRequestCounter,
     entity: Entity,
     start: (f32, f32),
     goal: (f32, f32),
     size: f32,
     priority: PathfindingPriority,
-) {
+) -> u64 {
+    let request_id = request_counter.next_id();
     if let Some(mut entity_commands) = commands.get_entity(entity) {
         entity_commands.insert(
-            PathfindingRequest::new(start, goal, size).with_priority(priority)
+            PathfindingRequest::new(start, goal, size).with_priority(priority).with_id(request_id)
         );
     }
+    request_id
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use bevy::ecs::entity::Entities;
+use crate::components::AttachedTo;
+
+/// Despawn every attached entity whose owner no longer exists - the single place linked visuals get cleaned up
+pub fn despawn_detached_entities(
+    mut commands: Commands,
+    entities: &Entities,
+    attached_query: Query<(Entity, &AttachedTo)>,
+) {
+    for (entity, attached_to) in attached_query.iter() {
+        if !entities.contains(attached_to.0) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
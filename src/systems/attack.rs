@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::combat_feedback::{HitFlash, HIT_FLASH_TINT};
+use crate::systems::control::AttackOrder;
+use crate::systems::growth::{Grown, growth_scale};
+use crate::systems::pawn::{Pawn, CurrentBehavior, Health};
+use crate::systems::pawn_config::{PawnConfig, PawnDefinition, AttackConfig, BehaviourConfig, BehaviourType, LifeStage};
+
+/// Tint a pawn's sprite turns as it winds up an attack
+pub const WINDUP_TINT: Color = Color::srgb(1.0, 0.45, 0.3);
+/// How far the sprite lunges towards its target on a strike, as a fraction of its size
+pub const STRIKE_LUNGE: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackPhase {
+    Windup,  // Telegraphing - the target can still get out of reach
+    Strike,  // The damage has landed
+    Recover, // Catching its breath before moving on
+}
+
+/// An attack in progress. The attacker stands its ground until it's over.
+#[derive(Component, Debug, Clone)]
+pub struct Attacking {
+    pub target: Entity,
+    pub phase: AttackPhase,
+    pub elapsed: f32, // Seconds into the current phase
+}
+
+/// What happened to an attack as it advanced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttackStep {
+    pub struck: bool,   // The windup ended and the damage is due
+    pub finished: bool, // Recovered - the attacker is free again
+}
+
+impl Attacking {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            phase: AttackPhase::Windup,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Move through as many phases as `delta` covers - with no durations configured an attack
+    /// strikes and finishes straight away
+    pub fn advance(&mut self, delta: f32, attack: &AttackConfig) -> AttackStep {
+        let mut step = AttackStep::default();
+        self.elapsed += delta;
+        loop {
+            let duration = match self.phase {
+                AttackPhase::Windup => attack.windup,
+                AttackPhase::Strike => attack.strike,
+                AttackPhase::Recover => attack.recover,
+            };
+            if self.elapsed < duration {
+                return step;
+            }
+            self.elapsed -= duration;
+            match self.phase {
+                AttackPhase::Windup => {
+                    self.phase = AttackPhase::Strike;
+                    step.struck = true;
+                }
+                AttackPhase::Strike => self.phase = AttackPhase::Recover,
+                AttackPhase::Recover => {
+                    step.finished = true;
+                    return step;
+                }
+            }
+        }
+    }
+}
+
+/// Damage a blow does: the attacker's strength for its age and growth, less the target's defence
+/// for its age and whether it's asleep
+pub fn blow_damage(
+    attacker_def: &PawnDefinition,
+    attacker_stage: Option<LifeStage>,
+    attacker_grown: Option<&Grown>,
+    target_def: &PawnDefinition,
+    target_stage: Option<LifeStage>,
+    target_asleep: bool,
+) -> f32 {
+    let strength = attacker_def.strength_for(attacker_stage) * growth_scale(attacker_grown);
+    (strength - target_def.defence_for(target_stage, target_asleep)).max(0.0)
+}
+
+/// Drop attacks whose attacker stopped hunting (or following an attack order) or whose target died or vanished
+pub fn cancel_abandoned_attacks(
+    pawn_config: Res<PawnConfig>,
+    mut commands: Commands,
+    attacker_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>, &Attacking, Has<AttackOrder>)>,
+    target_query: Query<&Health>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour, attacking, ordered) in attacker_query.iter() {
+        let hunting = ordered || matches!(
+            resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour),
+            Some(BehaviourConfig::Simple(BehaviourType::HuntSolo | BehaviourType::HuntPack | BehaviourType::Eat) | BehaviourConfig::Script { .. })
+        );
+        let target_alive = target_query.get(attacking.target).is_ok_and(|health| health.current > 0.0);
+        if !hunting || !target_alive {
+            commands.entity(entity).remove::<Attacking>();
+        }
+    }
+}
+
+/// Attackers redden as they wind up and lunge at their target as they strike, and pawns that
+/// have just been hit flash red
+pub fn attack_visual_system(
+    pawn_config: Res<PawnConfig>,
+    mut pawn_query: Query<(&mut Sprite, &Transform, &Pawn, Option<&Attacking>, Option<&HitFlash>)>,
+    target_query: Query<&Transform, With<Pawn>>,
+) {
+    for (mut sprite, transform, pawn, attacking, hit_flash) in pawn_query.iter_mut() {
+        let (color, anchor) = match attacking {
+            Some(attacking) if attacking.phase == AttackPhase::Windup => {
+                let windup = pawn_config.get_pawn_definition(&pawn.pawn_type).map_or(0.0, |def| def.attack_config().windup);
+                let progress = if windup > 0.0 { (attacking.elapsed / windup).min(1.0) } else { 1.0 };
+                (Color::WHITE.mix(&WINDUP_TINT, progress), Anchor::Center)
+            }
+            Some(attacking) if attacking.phase == AttackPhase::Strike => {
+                let direction = target_query
+                    .get(attacking.target)
+                    .ok()
+                    .and_then(|target| (target.translation - transform.translation).truncate().try_normalize())
+                    .unwrap_or(Vec2::ZERO);
+                // Moving the anchor away from the target draws the sprite towards it
+                (Color::WHITE, Anchor::Custom(-direction * STRIKE_LUNGE))
+            }
+            _ => (Color::WHITE, Anchor::Center),
+        };
+        let color = match hit_flash {
+            Some(hit_flash) => color.mix(&HIT_FLASH_TINT, hit_flash.strength()),
+            None => color,
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+        if sprite.anchor != anchor {
+            sprite.anchor = anchor;
+        }
+    }
+}
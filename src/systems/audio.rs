@@ -0,0 +1,201 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use crate::resources::GameConfig;
+use crate::systems::camera::CameraController;
+use crate::systems::combat::{CombatEvent, DeathEvent};
+use crate::systems::fog_of_war::{Exploration, TileFog};
+use crate::systems::footsteps::Footstep;
+use crate::systems::pawn::Pawn;
+use crate::systems::soundscape::{AmbientLoopConfig, MusicConfig, Soundscape};
+use crate::systems::world_gen::{GroundConfigs, TerrainMap};
+
+/// sounds.yaml - the sound played for each event, as paths under assets/, and how they carry.
+/// Events left out are silent.
+#[derive(Resource, Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    pub hearing_distance: f32,  // Tiles from the middle of the view a sound fades out over
+    pub footstep_distance: f32, // Tiles a pawn walks between footsteps, heard and seen
+    pub attack_hit: Option<String>,
+    pub death: Option<String>,
+    pub ui_click: Option<String>,
+    pub footsteps: HashMap<String, String>, // By ground name
+    pub ambience_radius: f32, // Tiles around the middle of the view ambience listens to the ground of
+    pub ambience: Vec<AmbientLoopConfig>,
+    pub music: MusicConfig,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            hearing_distance: 30.0,
+            footstep_distance: 1.0,
+            attack_hit: None,
+            death: None,
+            ui_click: None,
+            footsteps: HashMap::new(),
+            ambience_radius: 12.0,
+            ambience: Vec::new(),
+            music: MusicConfig::default(),
+        }
+    }
+}
+
+impl SoundConfig {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+}
+
+/// The sounds from sounds.yaml whose files were found, ready to play
+#[derive(Resource, Debug, Default)]
+pub struct SoundEffects {
+    pub attack_hit: Option<Handle<AudioSource>>,
+    pub death: Option<Handle<AudioSource>>,
+    pub ui_click: Option<Handle<AudioSource>>,
+    pub footsteps: HashMap<String, Handle<AudioSource>>,
+}
+
+/// How loud a sound is at a distance from the middle of the view, in world units: full at the
+/// middle, fading evenly to nothing at the hearing distance
+pub fn attenuation(distance: f32, hearing_distance: f32) -> f32 {
+    if hearing_distance <= 0.0 {
+        return 0.0;
+    }
+    (1.0 - distance / hearing_distance).clamp(0.0, 1.0)
+}
+
+/// Load a sound from assets/, or note it as missing if its file isn't there
+pub fn load_sound(asset_server: &AssetServer, path: &str, missing: &mut Vec<String>) -> Option<Handle<AudioSource>> {
+    if Path::new("assets").join(path).is_file() {
+        Some(asset_server.load(path.to_string()))
+    } else {
+        missing.push(path.to_string());
+        None
+    }
+}
+
+/// Load the sounds named in sounds.yaml, skipping any whose file isn't in assets/. Ambience and
+/// music are loaded here too, so there's one warning for every file that's missing.
+pub fn load_sound_effects(
+    mut commands: Commands,
+    sound_config: Res<SoundConfig>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut missing = Vec::new();
+    let mut load = |path: &String| load_sound(&asset_server, path, &mut missing);
+    let sound_effects = SoundEffects {
+        attack_hit: sound_config.attack_hit.as_ref().and_then(&mut load),
+        death: sound_config.death.as_ref().and_then(&mut load),
+        ui_click: sound_config.ui_click.as_ref().and_then(&mut load),
+        footsteps: sound_config.footsteps.iter()
+            .filter_map(|(ground, path)| Some((ground.clone(), load(path)?)))
+            .collect(),
+    };
+    commands.insert_resource(sound_effects);
+    commands.insert_resource(Soundscape::load(&sound_config, &asset_server, &mut missing));
+    if !missing.is_empty() {
+        missing.sort();
+        eprintln!("Warning: {} sounds in sounds.yaml aren't in assets/ and won't play: {}", missing.len(), missing.join(", "));
+    }
+}
+
+/// Play a sound at a point in the world, quieter the further it is from the middle of the view
+/// and not at all past the hearing distance
+fn play_at(commands: &mut Commands, sound: &Handle<AudioSource>, position: Vec2, listener: Vec2, sfx_volume: f32, hearing_distance: f32) {
+    let volume = sfx_volume * attenuation(position.distance(listener), hearing_distance);
+    if volume > 0.0 {
+        commands.spawn((
+            AudioPlayer::new(sound.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        ));
+    }
+}
+
+/// Sounds for blows that land and deaths
+pub fn play_event_sounds(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    sound_config: Res<SoundConfig>,
+    sound_effects: Option<Res<SoundEffects>>,
+    mut combat_events: EventReader<CombatEvent>,
+    mut death_events: EventReader<DeathEvent>,
+    camera_query: Query<&Transform, With<CameraController>>,
+    pawn_query: Query<(&Transform, Option<&Visibility>), With<Pawn>>,
+    fog: (Option<Res<TerrainMap>>, Option<Res<Exploration>>),
+) {
+    let Some(sound_effects) = sound_effects else {
+        return;
+    };
+    let listener = camera_query.get_single().map(|transform| transform.translation.truncate()).unwrap_or_default();
+    let hearing_distance = sound_config.hearing_distance * config.tile_size;
+    // Pawns hidden in the fog make no sound
+    let heard_at = |entity: Entity| match pawn_query.get(entity) {
+        Ok((transform, visibility)) if visibility != Some(&Visibility::Hidden) => Some(transform.translation.truncate()),
+        _ => None,
+    };
+
+    for event in combat_events.read() {
+        if let (Some(sound), Some(position), true) = (&sound_effects.attack_hit, heard_at(event.victim), event.damage > 0.0) {
+            play_at(&mut commands, sound, position, listener, config.sfx_volume, hearing_distance);
+        }
+    }
+    // The victim's already gone, so deaths in the fog are told by the tile they happened on
+    let (terrain_map, exploration) = fog;
+    let in_fog = |position: Vec2| match (config.fog_of_war.enabled, terrain_map.as_ref(), exploration.as_ref()) {
+        (true, Some(terrain_map), Some(exploration)) => terrain_map.world_to_tile_coords(position.x, position.y)
+            .is_some_and(|(x, y)| exploration.fog_at(x as u32, y as u32) != TileFog::Visible),
+        _ => false,
+    };
+    for event in death_events.read() {
+        if let (Some(sound), false) = (&sound_effects.death, in_fog(event.position)) {
+            play_at(&mut commands, sound, event.position, listener, config.sfx_volume, hearing_distance);
+        }
+    }
+}
+
+/// A click for every button pressed, in the menus as well as in game
+pub fn play_click_sounds(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    sound_effects: Option<Res<SoundEffects>>,
+    button_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+) {
+    let Some(sound) = sound_effects.as_ref().and_then(|sound_effects| sound_effects.ui_click.as_ref()) else {
+        return;
+    };
+    for _ in button_query.iter().filter(|interaction| **interaction == Interaction::Pressed) {
+        commands.spawn((
+            AudioPlayer::new(sound.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::new(config.sfx_volume)),
+        ));
+    }
+}
+
+/// The sound of each footstep's ground, for footsteps within hearing of the middle of the view
+pub fn play_footsteps(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    sound_config: Res<SoundConfig>,
+    sound_effects: Option<Res<SoundEffects>>,
+    ground_configs: Option<Res<GroundConfigs>>,
+    camera_query: Query<&Transform, With<CameraController>>,
+    mut footstep_events: EventReader<Footstep>,
+) {
+    let (Some(sound_effects), Some(ground_configs)) = (sound_effects, ground_configs) else {
+        footstep_events.clear();
+        return;
+    };
+    let listener = camera_query.get_single().map(|transform| transform.translation.truncate()).unwrap_or_default();
+    let hearing_distance = sound_config.hearing_distance * config.tile_size;
+    for event in footstep_events.read() {
+        let sound = ground_configs.name_of(event.terrain_type)
+            .and_then(|ground| sound_effects.footsteps.get(ground));
+        if let Some(sound) = sound {
+            play_at(&mut commands, sound, event.position, listener, config.sfx_volume, hearing_distance);
+        }
+    }
+}
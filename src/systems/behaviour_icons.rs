@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::AttachedTo;
+use crate::resources::GameConfig;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::level_of_detail::LevelOfDetail;
+use crate::systems::needs::Asleep;
+use crate::systems::pawn::{Pawn, Size, CurrentBehavior, TilesetManager, create_sprite};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+
+/// Above pawns, below the selection box
+const ICON_Z: f32 = 200.0;
+/// Width and height of an icon, in tiles
+const ICON_SIZE_TILES: f32 = 0.6;
+
+/// Icon over a pawn showing what it's up to, named after its sprite in the icons tileset
+#[derive(Component, Debug)]
+pub struct BehaviourIcon {
+    pub icon: &'static str,
+}
+
+/// The icon for a behaviour, if it has one: zzz for sleeping, crossed swords for hunting and an
+/// exclamation mark for fleeing
+pub fn behaviour_icon(behaviour: Option<&BehaviourConfig>, asleep: bool) -> Option<&'static str> {
+    if asleep {
+        return Some("sleep");
+    }
+    match behaviour? {
+        BehaviourConfig::Simple(BehaviourType::Sleep) => Some("sleep"),
+        BehaviourConfig::Simple(BehaviourType::HuntSolo | BehaviourType::HuntPack | BehaviourType::Ambush) => Some("hunt"),
+        BehaviourConfig::Simple(BehaviourType::Flee) => Some("flee"),
+        _ => None,
+    }
+}
+
+/// Keep an icon over every pawn whose behaviour has one, just above its head
+pub fn update_behaviour_icons(
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    level_of_detail: Res<LevelOfDetail>,
+    asset_server: Res<AssetServer>,
+    mut tileset_manager: ResMut<TilesetManager>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, Option<&ActiveBehaviour>, Has<Asleep>), Without<BehaviourIcon>>,
+    mut icon_query: Query<(Entity, &AttachedTo, &mut BehaviourIcon, &mut Transform, &mut Sprite)>,
+) {
+    // Switched off from the settings menu, or too far out to make out
+    if !config.show_behaviour_icons || level_of_detail.hides_overlays() {
+        for (icon_entity, ..) in icon_query.iter() {
+            commands.entity(icon_entity).despawn();
+        }
+        return;
+    }
+    let icon_for = |pawn: &Pawn, behavior: &CurrentBehavior, active: Option<&ActiveBehaviour>, asleep: bool| {
+        behaviour_icon(resolve_behaviour(&pawn_config, pawn, behavior, active), asleep)
+    };
+    let above = |transform: &Transform, size: &Size| {
+        let offset = (size.value.max(1.0) / 2.0 + ICON_SIZE_TILES / 2.0) * config.tile_size;
+        Vec3::new(transform.translation.x, transform.translation.y + offset, ICON_Z)
+    };
+    let mut icon_sprite = |icon: &str| {
+        let mut sprite = create_sprite(&asset_server, &mut tileset_manager, &mut texture_atlas_layouts, &format!("tileset::icons::{}", icon));
+        sprite.custom_size = Some(Vec2::splat(ICON_SIZE_TILES * config.tile_size));
+        sprite
+    };
+
+    let mut shown: HashSet<Entity> = HashSet::new();
+    for (icon_entity, attached_to, mut icon, mut transform, mut sprite) in icon_query.iter_mut() {
+        let (_, pawn_transform, pawn, size, behavior, active, asleep) = match pawn_query.get(attached_to.0) {
+            Ok(pawn) => pawn,
+            Err(_) => continue, // Cleaned up with the other detached entities
+        };
+        match icon_for(pawn, behavior, active, asleep) {
+            Some(name) => {
+                if icon.icon != name {
+                    icon.icon = name;
+                    *sprite = icon_sprite(name);
+                }
+                transform.translation = above(pawn_transform, size);
+                shown.insert(attached_to.0);
+            }
+            None => commands.entity(icon_entity).despawn(),
+        }
+    }
+
+    for (entity, transform, pawn, size, behavior, active, asleep) in pawn_query.iter() {
+        if shown.contains(&entity) {
+            continue;
+        }
+        if let Some(name) = icon_for(pawn, behavior, active, asleep) {
+            commands.spawn((
+                icon_sprite(name),
+                Transform::from_translation(above(transform, size)),
+                BehaviourIcon { icon: name },
+                AttachedTo(entity),
+            ));
+        }
+    }
+}
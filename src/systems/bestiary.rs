@@ -0,0 +1,321 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::combat::DeathEvent;
+use crate::systems::pawn::{Pawn, TilesetManager};
+use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnType, BehaviourConfig, BehaviourType};
+use crate::systems::keybinds::{Action, ActionInput, InputMap};
+
+/// Distance (in tiles) at which the player counts as having encountered a species
+pub const ENCOUNTER_RADIUS_TILES: f32 = 6.0;
+
+/// How much the player knows about a species - each level reveals more bestiary detail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryLevel {
+    Unknown,
+    Encountered,
+    Killed,
+    Tamed,
+}
+
+#[derive(Debug, Clone, Default, Resource, Deserialize, Serialize)]
+pub struct BestiaryProgress {
+    #[serde(flatten)]
+    pub species: HashMap<PawnType, DiscoveryLevel>,
+}
+
+impl BestiaryProgress {
+    pub fn level(&self, pawn_type: &str) -> DiscoveryLevel {
+        self.species.get(pawn_type).copied().unwrap_or(DiscoveryLevel::Unknown)
+    }
+
+    /// Raise a species to at least `level`; returns true if anything new was unlocked
+    pub fn unlock(&mut self, pawn_type: &str, level: DiscoveryLevel) -> bool {
+        if self.level(pawn_type) >= level {
+            return false;
+        }
+        self.species.insert(pawn_type.to_string(), level);
+        true
+    }
+
+    pub fn record_encounter(&mut self, pawn_type: &str) -> bool {
+        self.unlock(pawn_type, DiscoveryLevel::Encountered)
+    }
+
+    pub fn record_kill(&mut self, pawn_type: &str) -> bool {
+        self.unlock(pawn_type, DiscoveryLevel::Killed)
+    }
+
+    pub fn record_tame(&mut self, pawn_type: &str) -> bool {
+        self.unlock(pawn_type, DiscoveryLevel::Tamed)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct BestiaryState {
+    pub open: bool,
+}
+
+#[derive(Component)]
+pub struct BestiaryPanel;
+
+/// Summarise the behaviour slots a pawn definition actually configures
+pub fn behaviour_summary(definition: &PawnDefinition) -> Vec<String> {
+    let slots = [
+        ("idle", &definition.behaviours.idle),
+        ("hunted", &definition.behaviours.hunted),
+        ("looking_for_food", &definition.behaviours.looking_for_food),
+        ("eat", &definition.behaviours.eat),
+        ("controlled", &definition.behaviours.controlled),
+        ("flee", &definition.behaviours.flee),
+    ];
+
+    let mut summary: Vec<String> = slots
+        .iter()
+        .filter_map(|(state, config)| {
+            let description = match config.as_ref()? {
+                BehaviourConfig::Simple(BehaviourType::Null) => return None,
+                config => config.name(),
+            };
+            Some(format!("{}: {}", state, description))
+        })
+        .collect();
+
+    if let Some(tree) = &definition.behaviour_tree {
+        let mut actions: Vec<&str> = Vec::new();
+        for action in tree.actions() {
+            if !matches!(action, BehaviourConfig::Simple(BehaviourType::Null)) && !actions.contains(&action.name()) {
+                actions.push(action.name());
+            }
+        }
+        if !actions.is_empty() {
+            summary.push(format!("tree: {}", actions.join(" / ")));
+        }
+    }
+
+    summary
+}
+
+/// Build the bestiary entry text for a species, revealing only what the player has unlocked
+pub fn describe_species(pawn_type: &str, definition: &PawnDefinition, level: DiscoveryLevel) -> String {
+    if level == DiscoveryLevel::Unknown {
+        return "??? - not yet encountered".to_string();
+    }
+
+    let mut lines = vec![format!("{} [{}]", pawn_type, definition.tags.join(", "))];
+
+    if level >= DiscoveryLevel::Killed {
+        lines.push(format!(
+            "HP {}  END {}  STR {}  DEF {}  SPD {:.0}  SIZE {:.1}",
+            definition.max_health,
+            definition.max_endurance,
+            definition.strength,
+            definition.defence,
+            definition.move_speed,
+            definition.size
+        ));
+    } else {
+        lines.push("Stats: defeat one to learn more".to_string());
+    }
+
+    if level >= DiscoveryLevel::Tamed {
+        let mut diet_parts: Vec<String> = Vec::new();
+        if !definition.eats.pawns.is_empty() {
+            diet_parts.push(definition.eats.pawns.join(" + "));
+        }
+        if !definition.eats.grounds.is_empty() {
+            diet_parts.push(format!("grazes {}", definition.eats.grounds.join(" + ")));
+        }
+        let diet = if diet_parts.is_empty() {
+            "nothing".to_string()
+        } else {
+            diet_parts.join(", ")
+        };
+        lines.push(format!("Eats: {}", diet));
+        lines.push(format!("Behaviours: {}", behaviour_summary(definition).join(", ")));
+    } else {
+        lines.push("Diet & behaviours: tame one to learn more".to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Mark species as encountered when they come near a player pawn
+pub fn record_bestiary_encounters(
+    config: Res<GameConfig>,
+    mut progress: ResMut<BestiaryProgress>,
+    pawn_query: Query<(&Transform, &Pawn)>,
+) {
+    let encounter_distance = ENCOUNTER_RADIUS_TILES * config.tile_size;
+    let players: Vec<Vec3> = pawn_query
+        .iter()
+        .filter(|(_, pawn)| pawn.pawn_type == "player")
+        .map(|(transform, _)| transform.translation)
+        .collect();
+
+    for (transform, pawn) in pawn_query.iter() {
+        if pawn.pawn_type == "player" || progress.level(&pawn.pawn_type) >= DiscoveryLevel::Encountered {
+            continue;
+        }
+
+        let seen = players
+            .iter()
+            .any(|player_pos| player_pos.truncate().distance(transform.translation.truncate()) <= encounter_distance);
+
+        if seen && progress.record_encounter(&pawn.pawn_type) {
+            println!("Bestiary: encountered {}", pawn.pawn_type);
+        }
+    }
+}
+
+/// Mark species as killed when a player pawn lands the killing blow
+pub fn record_bestiary_kills(
+    mut progress: ResMut<BestiaryProgress>,
+    mut death_events: EventReader<DeathEvent>,
+    pawn_query: Query<&Pawn>,
+) {
+    for event in death_events.read() {
+        let by_player = event.killer
+            .and_then(|killer| pawn_query.get(killer).ok())
+            .is_some_and(|killer| killer.pawn_type == "player");
+        if by_player && progress.record_kill(&event.victim_type) {
+            println!("Bestiary: killed {}", event.victim_type);
+        }
+    }
+}
+
+pub fn toggle_bestiary(
+    actions: ActionInput,
+    mut bestiary_state: ResMut<BestiaryState>,
+) {
+    if actions.just_pressed(Action::ToggleBestiary) {
+        bestiary_state.open = !bestiary_state.open;
+    }
+}
+
+/// Rebuild the bestiary panel whenever it is opened or the player learns something new
+pub fn update_bestiary_panel(
+    mut commands: Commands,
+    bestiary_state: Res<BestiaryState>,
+    progress: Res<BestiaryProgress>,
+    pawn_config: Res<PawnConfig>,
+    input_map: Res<InputMap>,
+    asset_server: Res<AssetServer>,
+    mut tileset_manager: ResMut<TilesetManager>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    panel_query: Query<Entity, With<BestiaryPanel>>,
+) {
+    if !bestiary_state.is_changed() && !progress.is_changed() {
+        return;
+    }
+
+    for panel in panel_query.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+
+    if !bestiary_state.open {
+        return;
+    }
+
+    let mut species: Vec<_> = pawn_config.pawns.iter().collect();
+    species.sort_by(|a, b| a.0.cmp(b.0));
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                right: Val::Px(10.0),
+                width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+            BestiaryPanel,
+        ))
+        .with_children(|panel| {
+            let title = match input_map.bindings(Action::ToggleBestiary).first() {
+                Some(binding) => format!("Bestiary ({} to close)", binding.label()),
+                None => "Bestiary".to_string(),
+            };
+            panel.spawn((
+                Text::new(title),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            for (pawn_type, definition) in species {
+                let level = progress.level(pawn_type);
+
+                panel
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        // Only reveal the sprite once the species has been seen
+                        if level >= DiscoveryLevel::Encountered {
+                            if let Some(icon) = species_icon(&definition.sprite, &asset_server, &mut tileset_manager, &mut texture_atlas_layouts) {
+                                row.spawn((
+                                    icon,
+                                    Node {
+                                        width: Val::Px(32.0),
+                                        height: Val::Px(32.0),
+                                        ..default()
+                                    },
+                                ));
+                            }
+                        }
+
+                        row.spawn((
+                            Text::new(describe_species(pawn_type, definition, level)),
+                            TextFont {
+                                font_size: 12.0,
+                                ..default()
+                            },
+                            TextColor(if level == DiscoveryLevel::Unknown {
+                                Color::srgb(0.5, 0.5, 0.5)
+                            } else {
+                                Color::WHITE
+                            }),
+                        ));
+                    });
+            }
+        });
+}
+
+fn species_icon(
+    sprite: &str,
+    asset_server: &AssetServer,
+    tileset_manager: &mut TilesetManager,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+) -> Option<ImageNode> {
+    let parts: Vec<&str> = sprite.split("::").collect();
+    if parts.len() != 3 || parts[0] != "tileset" {
+        return Some(ImageNode::new(asset_server.load(sprite.to_string())));
+    }
+
+    let (tileset_name, sprite_name) = (parts[1], parts[2]);
+    if tileset_manager.get_sprite_index(tileset_name, sprite_name).is_none() {
+        tileset_manager.load_tileset(tileset_name).ok()?;
+    }
+
+    let layout = tileset_manager.create_atlas_layout(tileset_name, texture_atlas_layouts)?;
+    let index = tileset_manager.get_sprite_index(tileset_name, sprite_name)?;
+
+    Some(ImageNode::from_atlas_image(
+        asset_server.load(format!("tilesets/{}.png", tileset_name)),
+        TextureAtlas {
+            layout,
+            index: index as usize,
+        },
+    ))
+}
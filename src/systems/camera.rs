@@ -1,6 +1,13 @@
 use bevy::prelude::*;
 use bevy::input::mouse::{MouseWheel, MouseScrollUnit, MouseMotion};
 use crate::resources::GameConfig;
+use crate::systems::control::Controlled;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::pawn::Pawn;
+use crate::systems::selection::Selected;
+
+/// How quickly a following camera closes on its pawn - the higher, the tighter it sticks
+const FOLLOW_SHARPNESS: f32 = 5.0;
 
 #[derive(Component)]
 pub struct CameraController;
@@ -11,38 +18,126 @@ pub struct MouseDragState {
     pub last_position: Vec2,
 }
 
+/// Pawn the camera is following, if any. Panning the camera by hand lets it go.
+#[derive(Resource, Default)]
+pub struct CameraFollow {
+    pub target: Option<Entity>,
+}
+
 pub fn camera_movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
+    actions: ActionInput,
+    time: Res<Time<Real>>, // The camera keeps moving while the game is paused
     config: Res<GameConfig>,
+    mut follow: ResMut<CameraFollow>,
     mut query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+    controlled_query: Query<(), With<Controlled>>,
 ) {
+    // The steering keys walk the controlled pawn while there is one, leaving the camera its others
+    let steering: &[Action] = if controlled_query.is_empty() { &[] } else { &Action::STEERING };
+    let pressed = |action: Action| actions.pressed_except(action, steering);
+
     for mut transform in &mut query {
         let mut direction = Vec3::ZERO;
 
-        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
+        if pressed(Action::CameraUp) {
             direction.y += 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
+        if pressed(Action::CameraDown) {
             direction.y -= 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
+        if pressed(Action::CameraLeft) {
             direction.x -= 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
+        if pressed(Action::CameraRight) {
             direction.x += 1.0;
         }
 
         if direction.length() > 0.0 {
             direction = direction.normalize();
             transform.translation += direction * config.camera_speed * time.delta_secs();
+            follow.target = None;
+        }
+    }
+}
+
+/// Start following the selected pawn, or stop following if the camera already is
+pub fn toggle_camera_follow(
+    actions: ActionInput,
+    mut follow: ResMut<CameraFollow>,
+    selected_query: Query<(Entity, &Pawn), With<Selected>>,
+) {
+    if !actions.just_pressed(Action::FollowSelected) {
+        return;
+    }
+    if follow.target.take().is_some() {
+        println!("Camera stopped following");
+        return;
+    }
+    // With several selected, follow whichever comes first
+    if let Some((entity, pawn)) = selected_query.iter().next() {
+        println!("Camera following {}", pawn.pawn_type);
+        follow.target = Some(entity);
+    }
+}
+
+/// Ease the camera towards the pawn it's following, letting go once the pawn is gone
+pub fn follow_camera(
+    time: Res<Time<Real>>, // Keeps up while the game is paused, like the other camera controls
+    mut follow: ResMut<CameraFollow>,
+    pawn_query: Query<&Transform, With<Pawn>>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>, Without<Pawn>)>,
+) {
+    let target = match follow.target {
+        Some(target) => target,
+        None => return,
+    };
+    let pawn_position = match pawn_query.get(target) {
+        Ok(pawn_transform) => pawn_transform.translation.truncate(),
+        Err(_) => {
+            follow.target = None;
+            return;
+        }
+    };
+    // The same share of the remaining distance each second, whatever the frame rate
+    let progress = 1.0 - (-FOLLOW_SHARPNESS * time.delta_secs()).exp();
+    for mut transform in camera_query.iter_mut() {
+        let position = transform.translation.truncate().lerp(pawn_position, progress);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+/// Furthest the camera can sit from the centre of the map, given half the size of its view, so
+/// the view never goes more than `padding` past the map's edges. A view wider than the map and
+/// padding stays centred on it.
+pub fn clamp_to_map(position: Vec2, half_view: Vec2, half_map: Vec2, padding: f32) -> Vec2 {
+    let limit = (half_map + Vec2::splat(padding) - half_view).max(Vec2::ZERO);
+    position.clamp(-limit, limit)
+}
+
+/// Runs after everything that moves or zooms the camera, pulling it back over the map
+pub fn keep_camera_over_map(
+    config: Res<GameConfig>,
+    windows: Query<&Window>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), (With<Camera>, With<CameraController>)>,
+) {
+    let window = match windows.get_single() {
+        Ok(window) => window,
+        Err(_) => return,
+    };
+    let half_map = Vec2::new(config.map_width as f32, config.map_height as f32) * config.tile_size / 2.0;
+    let padding = config.camera_bounds_padding * config.tile_size;
+    for (mut transform, projection) in camera_query.iter_mut() {
+        let half_view = window.size() * projection.scale / 2.0;
+        let clamped = clamp_to_map(transform.translation.truncate(), half_view, half_map, padding);
+        if clamped != transform.translation.truncate() {
+            transform.translation = clamped.extend(transform.translation.z);
         }
     }
 }
 
 pub fn camera_zoom(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
+    actions: ActionInput,
+    time: Res<Time<Real>>,
     config: Res<GameConfig>,
     mut scroll_events: EventReader<MouseWheel>,
     mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
@@ -52,11 +147,11 @@ pub fn camera_zoom(
         let zoom_speed = 2.0;
 
         // Keyboard zoom (centered)
-        if keyboard_input.pressed(KeyCode::Equal) || keyboard_input.pressed(KeyCode::NumpadAdd) {
+        if actions.pressed(Action::ZoomIn) {
             projection.scale *= 1.0 - zoom_speed * time.delta_secs();
             projection.scale = projection.scale.max(config.zoom_min);
         }
-        if keyboard_input.pressed(KeyCode::Minus) || keyboard_input.pressed(KeyCode::NumpadSubtract) {
+        if actions.pressed(Action::ZoomOut) {
             projection.scale *= 1.0 + zoom_speed * time.delta_secs();
             projection.scale = projection.scale.min(config.zoom_max);
         }
@@ -100,19 +195,20 @@ pub fn camera_zoom(
 }
 
 pub fn mouse_camera_pan(
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     mut mouse_motion: EventReader<MouseMotion>,
     mut drag_state: ResMut<MouseDragState>,
+    mut follow: ResMut<CameraFollow>,
     config: Res<GameConfig>,
     mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
     projection_query: Query<&OrthographicProjection, With<Camera>>,
 ) {
     // Handle mouse button press/release for drag state
-    if mouse_input.just_pressed(MouseButton::Middle) {
+    if actions.just_pressed(Action::PanCamera) {
         drag_state.is_dragging = true;
     }
     
-    if mouse_input.just_released(MouseButton::Middle) {
+    if actions.just_released(Action::PanCamera) {
         drag_state.is_dragging = false;
     }
 
@@ -140,6 +236,7 @@ pub fn mouse_camera_pan(
                 );
                 
                 camera_transform.translation += movement;
+                follow.target = None;
             }
         }
     }
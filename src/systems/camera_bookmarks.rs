@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::systems::camera::{CameraController, CameraFollow};
+use crate::systems::keybinds::{Action, ActionInput};
+
+/// Number row keys for each bookmark slot, held with the store or recall modifier
+pub const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3,
+    KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6,
+    KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+];
+
+/// How long (in seconds) the camera takes to glide back to a bookmark
+const BOOKMARK_TRAVEL_SECS: f32 = 0.4;
+
+/// A remembered camera position and zoom
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct CameraBookmark {
+    pub x: f32,
+    pub y: f32,
+    pub zoom: f32,
+}
+
+impl CameraBookmark {
+    pub fn of(transform: &Transform, projection: &OrthographicProjection) -> Self {
+        Self { x: transform.translation.x, y: transform.translation.y, zoom: projection.scale }
+    }
+
+    pub fn position(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+/// The camera on its way from one place to a bookmark
+#[derive(Debug, Clone, Copy)]
+pub struct BookmarkTravel {
+    pub from: CameraBookmark,
+    pub to: CameraBookmark,
+    pub elapsed: f32,
+}
+
+/// Camera bookmarks stored on the number row, one per slot. They're kept in save files.
+#[derive(Resource, Debug, Default)]
+pub struct CameraBookmarks {
+    pub slots: [Option<CameraBookmark>; 9],
+    pub travel: Option<BookmarkTravel>,
+}
+
+/// Ctrl and a number remembers where the camera is; Alt and the number goes back there
+pub fn camera_bookmark_input(
+    actions: ActionInput,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut follow: ResMut<CameraFollow>,
+    camera_query: Query<(&Transform, &OrthographicProjection), (With<Camera>, With<CameraController>)>,
+) {
+    let storing = actions.pressed(Action::StoreBookmark);
+    let recalling = actions.pressed(Action::RecallBookmark);
+    if !storing && !recalling {
+        return;
+    }
+    let current = match camera_query.get_single() {
+        Ok((transform, projection)) => CameraBookmark::of(transform, projection),
+        Err(_) => return,
+    };
+
+    for (slot, key) in BOOKMARK_KEYS.iter().enumerate() {
+        if !keys.just_pressed(*key) {
+            continue;
+        }
+        if storing {
+            println!("Stored camera bookmark {}", slot + 1);
+            bookmarks.slots[slot] = Some(current);
+        } else if let Some(bookmark) = bookmarks.slots[slot] {
+            follow.target = None;
+            bookmarks.travel = Some(BookmarkTravel { from: current, to: bookmark, elapsed: 0.0 });
+        }
+    }
+}
+
+/// Glide the camera to the bookmark it's headed for, easing in and out
+pub fn camera_bookmark_travel(
+    time: Res<Time<Real>>, // The camera keeps moving while the game is paused
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), (With<Camera>, With<CameraController>)>,
+) {
+    let travel = match bookmarks.travel.as_mut() {
+        Some(travel) => travel,
+        None => return,
+    };
+    travel.elapsed += time.delta_secs();
+    let progress = (travel.elapsed / BOOKMARK_TRAVEL_SECS).min(1.0);
+    let eased = progress * progress * (3.0 - 2.0 * progress);
+
+    for (mut transform, mut projection) in camera_query.iter_mut() {
+        let position = travel.from.position().lerp(travel.to.position(), eased);
+        transform.translation = position.extend(transform.translation.z);
+        projection.scale = travel.from.zoom + (travel.to.zoom - travel.from.zoom) * eased;
+    }
+    if progress >= 1.0 {
+        bookmarks.travel = None;
+    }
+}
@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::aging::Age;
+use crate::systems::combat::CombatEvent;
+use crate::systems::needs::Asleep;
+use crate::systems::pawn::{Pawn, PawnTarget, Size, Health};
+use crate::systems::pawn_config::{PawnConfig, ChargeConfig};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
+use crate::systems::selection::cursor_world_position;
+
+/// Damage dealt per tile-per-second of charge speed, per unit of charger size
+pub const CHARGE_DAMAGE_PER_SPEED: f32 = 4.0;
+/// How far (as seconds of the charger's speed) a struck pawn is knocked back
+pub const KNOCKBACK_SECONDS: f32 = 0.2;
+/// How long a charger is stunned after running into impassable terrain
+pub const WALL_STUN_SECS: f32 = 1.5;
+/// Sample spacing for swept terrain checks, as a fraction of a tile
+const SWEEP_STEP_TILES: f32 = 0.25;
+
+/// A pawn charging in a straight line, accelerating until it hits something or runs out of range
+#[derive(Component, Debug, Clone)]
+pub struct Charge {
+    pub direction: Vec2,
+    pub speed: f32,
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub remaining_distance: f32,
+}
+
+impl Charge {
+    pub fn new(direction: Vec2, charge_config: &ChargeConfig, tile_size: f32) -> Self {
+        Self {
+            direction: direction.normalize_or_zero(),
+            speed: 0.0,
+            max_speed: charge_config.max_speed,
+            acceleration: charge_config.acceleration,
+            remaining_distance: charge_config.range as f32 * tile_size,
+        }
+    }
+}
+
+/// Pawn can't move or act until the timer runs out
+#[derive(Component, Debug, Clone)]
+pub struct Stunned {
+    pub remaining: f32,
+}
+
+/// Pawn must wait before charging again
+#[derive(Component, Debug, Clone)]
+pub struct ChargeCooldown {
+    pub remaining: f32,
+}
+
+/// Fraction along `start -> end` at which a circle moving along the segment first touches `center`
+pub fn segment_circle_hit(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let segment = end - start;
+    let to_start = start - center;
+
+    // Already overlapping at the start of the step
+    if to_start.length_squared() <= radius * radius {
+        return Some(0.0);
+    }
+
+    let a = segment.length_squared();
+    if a == 0.0 {
+        return None;
+    }
+    let b = 2.0 * to_start.dot(segment);
+    let c = to_start.length_squared() - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if (0.0..=1.0).contains(&t) {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// Walk the segment in small steps and return the furthest fraction a pawn of `size` can reach,
+/// or None if the whole segment is passable
+pub fn sweep_terrain(start: Vec2, end: Vec2, size: f32, terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> Option<f32> {
+    let length = start.distance(end);
+    let step = terrain_map.tile_size * SWEEP_STEP_TILES;
+    let samples = (length / step).ceil().max(1.0) as u32;
+
+    let mut last_free = 0.0;
+    for i in 1..=samples {
+        let t = i as f32 / samples as f32;
+        let point = start.lerp(end, t);
+        if !terrain_map.is_position_passable_for_size(point.x, point.y, size, ground_configs) {
+            return Some(last_free);
+        }
+        last_free = t;
+    }
+    None
+}
+
+/// Damage a charge impact deals before the target's defence is applied
+pub fn charge_impact_damage(speed: f32, charger_size: f32, tile_size: f32) -> f32 {
+    (speed / tile_size) * charger_size * CHARGE_DAMAGE_PER_SPEED
+}
+
+/// Start a charge for a pawn whose definition has one, dropping any path it was following
+pub fn start_charge(commands: &mut Commands, entity: Entity, direction: Vec2, charge_config: &ChargeConfig, tile_size: f32) {
+    if direction == Vec2::ZERO {
+        return;
+    }
+    if let Some(mut entity_commands) = commands.get_entity(entity) {
+        entity_commands
+            .remove::<PawnTarget>()
+            .insert(Charge::new(direction, charge_config, tile_size))
+            .insert(ChargeCooldown { remaining: charge_config.cooldown });
+    }
+}
+
+/// Player pawns with a charge ability charge towards the cursor on C
+pub fn handle_charge_input(
+    actions: ActionInput,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    mut player_commands: ResMut<PlayerCommands>,
+) {
+    if !actions.just_pressed(Action::Charge) {
+        return;
+    }
+    if let Some(world_position) = cursor_world_position(&windows, &camera_query) {
+        player_commands.give(PlayerCommand::Charge { towards: world_position.into() });
+    }
+}
+
+/// Carry out this tick's charge commands for every player pawn that's ready to charge
+pub fn apply_charge_commands(
+    player_commands: Res<PlayerCommands>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    mut commands: Commands,
+    player_query: Query<(Entity, &Transform, &Pawn), (Without<Charge>, Without<Stunned>, Without<ChargeCooldown>)>,
+) {
+    for command in &player_commands.this_tick {
+        let world_position = match *command {
+            PlayerCommand::Charge { towards } => Vec2::from(towards),
+            _ => continue,
+        };
+        for (entity, transform, pawn) in player_query.iter() {
+            if pawn.pawn_type != "player" {
+                continue;
+            }
+            if let Some(charge_config) = pawn_config.get_pawn_definition(&pawn.pawn_type).and_then(|def| def.charge.as_ref()) {
+                let direction = world_position - transform.translation.truncate();
+                start_charge(&mut commands, entity, direction, charge_config, config.tile_size);
+                println!("{} charges towards {:?}", pawn.pawn_type, world_position);
+            }
+        }
+    }
+}
+
+/// Advance charging pawns with swept collision against other pawns and impassable terrain
+pub fn charge_movement_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    pawn_config: Res<PawnConfig>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut commands: Commands,
+    mut charger_query: Query<(Entity, &mut Transform, &mut Charge, &Size, &Pawn)>,
+    mut target_query: Query<(Entity, &mut Transform, &mut Health, &Size, &Pawn), Without<Charge>>,
+    age_query: Query<&Age>,
+    sleeper_query: Query<(), With<Asleep>>,
+) {
+    let delta = time.delta_secs();
+
+    for (charger_entity, mut charger_transform, mut charge, charger_size, charger_pawn) in charger_query.iter_mut() {
+        charge.speed = (charge.speed + charge.acceleration * delta).min(charge.max_speed);
+        let step = (charge.speed * delta).min(charge.remaining_distance);
+
+        let start = charger_transform.translation.truncate();
+        let end = start + charge.direction * step;
+        let charger_radius = charger_size.value * config.tile_size / 2.0;
+
+        // Earliest pawn along the swept segment
+        let mut pawn_hit: Option<(Entity, f32)> = None;
+        for (target_entity, target_transform, _, target_size, _) in target_query.iter() {
+            let target_radius = target_size.value * config.tile_size / 2.0;
+            if let Some(t) = segment_circle_hit(start, end, target_transform.translation.truncate(), charger_radius + target_radius) {
+                if pawn_hit.is_none_or(|(_, best)| t < best) {
+                    pawn_hit = Some((target_entity, t));
+                }
+            }
+        }
+
+        let wall_hit = sweep_terrain(start, end, charger_size.value, &terrain_map, &ground_configs);
+
+        match (pawn_hit, wall_hit) {
+            (Some((target_entity, t)), wall) if wall.is_none_or(|wall_t| t <= wall_t) => {
+                let impact_point = start.lerp(end, t);
+                charger_transform.translation.x = impact_point.x;
+                charger_transform.translation.y = impact_point.y;
+
+                if let Ok((_, mut target_transform, mut target_health, target_size, target_pawn)) = target_query.get_mut(target_entity) {
+                    let defence = pawn_config
+                        .get_pawn_definition(&target_pawn.pawn_type)
+                        .map(|def| def.defence_for(age_query.get(target_entity).ok().map(|age| age.stage), sleeper_query.contains(target_entity)))
+                        .unwrap_or(0.0);
+                    let damage = (charge_impact_damage(charge.speed, charger_size.value, config.tile_size) - defence).max(0.0);
+                    target_health.current = (target_health.current - damage).max(0.0);
+                    combat_events.send(CombatEvent { attacker: charger_entity, victim: target_entity, damage });
+
+                    // Bigger chargers shove smaller targets further; knockback stops at walls
+                    let knockback = charge.speed * KNOCKBACK_SECONDS * (charger_size.value / target_size.value.max(0.1));
+                    let target_start = target_transform.translation.truncate();
+                    let target_end = target_start + charge.direction * knockback;
+                    let reached = sweep_terrain(target_start, target_end, target_size.value, &terrain_map, &ground_configs).unwrap_or(1.0);
+                    let knocked_to = target_start.lerp(target_end, reached);
+                    target_transform.translation.x = knocked_to.x;
+                    target_transform.translation.y = knocked_to.y;
+                }
+
+                commands.entity(target_entity).remove::<PawnTarget>();
+                commands.entity(charger_entity).remove::<Charge>();
+            }
+            (_, Some(wall_t)) => {
+                let stop_point = start.lerp(end, wall_t);
+                charger_transform.translation.x = stop_point.x;
+                charger_transform.translation.y = stop_point.y;
+
+                println!("{} charged into a wall and is stunned", charger_pawn.pawn_type);
+                commands.entity(charger_entity)
+                    .remove::<Charge>()
+                    .insert(Stunned { remaining: WALL_STUN_SECS });
+            }
+            _ => {
+                charger_transform.translation.x = end.x;
+                charger_transform.translation.y = end.y;
+                charge.remaining_distance -= step;
+
+                if charge.remaining_distance <= 0.0 {
+                    commands.entity(charger_entity).remove::<Charge>();
+                }
+            }
+        }
+    }
+}
+
+pub fn stun_recovery_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut stunned_query: Query<(Entity, &mut Stunned)>,
+) {
+    for (entity, mut stunned) in stunned_query.iter_mut() {
+        stunned.remaining -= time.delta_secs();
+        if stunned.remaining <= 0.0 {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+pub fn charge_cooldown_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut cooldown_query: Query<(Entity, &mut ChargeCooldown)>,
+) {
+    for (entity, mut cooldown) in cooldown_query.iter_mut() {
+        cooldown.remaining -= time.delta_secs();
+        if cooldown.remaining <= 0.0 {
+            commands.entity(entity).remove::<ChargeCooldown>();
+        }
+    }
+}
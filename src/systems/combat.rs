@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use crate::systems::pawn::{Pawn, Health};
+use crate::systems::pawn_config::PawnType;
+
+/// A blow that landed - sent by every system that deals damage, even when defence soaks it all
+#[derive(Event, Debug, Clone)]
+pub struct CombatEvent {
+    pub attacker: Entity,
+    pub victim: Entity,
+    pub damage: f32,
+}
+
+/// A pawn died, sent by `pawn_death_system` as it's despawned. `killer` is whoever landed the
+/// blow that finished it off, if anyone did - starving, drowning and old age have no killer.
+#[derive(Event, Debug, Clone)]
+pub struct DeathEvent {
+    pub victim_type: PawnType, // The victim is gone by the time any reader sees this
+    pub position: Vec2,        // Where it died
+    pub killer: Option<Entity>,
+}
+
+/// Write combat and deaths to the console
+pub fn log_combat_events(
+    mut combat_events: EventReader<CombatEvent>,
+    mut death_events: EventReader<DeathEvent>,
+    pawn_query: Query<(&Pawn, Option<&Health>)>,
+) {
+    for event in combat_events.read() {
+        if let (Ok((attacker, _)), Ok((victim, victim_health))) = (pawn_query.get(event.attacker), pawn_query.get(event.victim)) {
+            let health = victim_health.map_or(0.0, |health| health.current);
+            println!("{} attacks {} for {:.1} damage (health: {:.1})", attacker.pawn_type, victim.pawn_type, event.damage, health);
+        }
+    }
+
+    for event in death_events.read() {
+        match event.killer.and_then(|killer| pawn_query.get(killer).ok()) {
+            Some((killer, _)) => println!("{} was killed by {}", event.victim_type, killer.pawn_type),
+            None => println!("{} has died!", event.victim_type),
+        }
+    }
+}
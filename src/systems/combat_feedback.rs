@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::combat::CombatEvent;
+use crate::systems::pawn::{Pawn, Size};
+
+/// Above pawns, below behaviour icons
+const DAMAGE_NUMBER_Z: f32 = 190.0;
+/// Seconds a damage number floats for before it's gone
+pub const DAMAGE_NUMBER_SECS: f32 = 0.8;
+/// How far a damage number rises over its life, in tiles
+const DAMAGE_NUMBER_RISE_TILES: f32 = 1.0;
+const DAMAGE_NUMBER_COLOR: Color = Color::srgb(1.0, 0.9, 0.8);
+/// Seconds a pawn stays tinted after being hit
+pub const HIT_FLASH_SECS: f32 = 0.25;
+/// Tint a pawn's sprite flashes when it's hit
+pub const HIT_FLASH_TINT: Color = Color::srgb(1.0, 0.2, 0.2);
+
+/// Floating text showing the damage a blow did, rising and fading over the victim
+#[derive(Component, Debug)]
+pub struct DamageNumber {
+    pub age: f32, // Seconds since the blow
+}
+
+/// A pawn that's just been hit, drawn tinted red until the flash fades. The tint itself is mixed
+/// in by attack_visual_system, which sets the pawn's colour.
+#[derive(Component, Debug)]
+pub struct HitFlash {
+    pub remaining: f32, // Seconds
+}
+
+impl HitFlash {
+    /// How much of the tint shows, fading from full to none
+    pub fn strength(&self) -> f32 {
+        (self.remaining / HIT_FLASH_SECS).clamp(0.0, 1.0)
+    }
+}
+
+/// Put a damage number over every pawn hit since the last frame and flash it red, unless
+/// switched off in the settings. Blows that did no damage show a 0 but don't flash.
+pub fn spawn_combat_feedback(
+    config: Res<GameConfig>,
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatEvent>,
+    victim_query: Query<(&Transform, &Size, Option<&Visibility>), With<Pawn>>,
+) {
+    if !config.show_damage_numbers {
+        combat_events.clear();
+        return;
+    }
+    for event in combat_events.read() {
+        let Ok((transform, size, visibility)) = victim_query.get(event.victim) else {
+            continue;
+        };
+        // Nothing to give away about pawns hidden in the fog
+        if visibility == Some(&Visibility::Hidden) {
+            continue;
+        }
+        let above = size.value.max(1.0) / 2.0 * config.tile_size;
+        commands.spawn((
+            Text2d::new(format!("{:.0}", event.damage)),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(DAMAGE_NUMBER_COLOR),
+            Transform::from_xyz(transform.translation.x, transform.translation.y + above, DAMAGE_NUMBER_Z),
+            DamageNumber { age: 0.0 },
+        ));
+        if event.damage > 0.0 {
+            commands.entity(event.victim).insert(HitFlash { remaining: HIT_FLASH_SECS });
+        }
+    }
+}
+
+/// Float damage numbers upwards and fade them out, and let hit flashes die away
+pub fn update_combat_feedback(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut commands: Commands,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor)>,
+    mut flash_query: Query<(Entity, &mut HitFlash)>,
+) {
+    let delta = time.delta_secs();
+    let rise_speed = DAMAGE_NUMBER_RISE_TILES * config.tile_size / DAMAGE_NUMBER_SECS;
+    for (entity, mut number, mut transform, mut color) in number_query.iter_mut() {
+        number.age += delta;
+        if number.age >= DAMAGE_NUMBER_SECS {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += rise_speed * delta;
+        color.0 = DAMAGE_NUMBER_COLOR.with_alpha(1.0 - number.age / DAMAGE_NUMBER_SECS);
+    }
+
+    for (entity, mut flash) in flash_query.iter_mut() {
+        flash.remaining -= delta;
+        if flash.remaining <= 0.0 {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Command line option naming the directory to look for config files in first
+pub const CONFIG_DIR_ARG: &str = "--config-dir";
+/// Environment variable naming a directory to look for config files in, after the command line
+pub const CONFIG_DIR_VAR: &str = "ELEMENTALS_CONFIG_DIR";
+
+/// The config files as they were when the game was built, used for any that aren't found in the
+/// search path so the game still starts without them
+pub const EMBEDDED_CONFIGS: [(&str, &str); 10] = [
+    ("settings.yaml", include_str!("../../settings.yaml")),
+    ("grounds.yaml", include_str!("../../grounds.yaml")),
+    ("pawns.yaml", include_str!("../../pawns.yaml")),
+    ("factions.yaml", include_str!("../../factions.yaml")),
+    ("props.yaml", include_str!("../../props.yaml")),
+    ("status_effects.yaml", include_str!("../../status_effects.yaml")),
+    ("items.yaml", include_str!("../../items.yaml")),
+    ("spawns.yaml", include_str!("../../spawns.yaml")),
+    ("keybinds.yaml", include_str!("../../keybinds.yaml")),
+    ("sounds.yaml", include_str!("../../sounds.yaml")),
+];
+
+pub fn embedded_config(name: &str) -> Option<&'static str> {
+    EMBEDDED_CONFIGS.iter().find(|(file, _)| *file == name).map(|(_, contents)| *contents)
+}
+
+/// The path given with a command line option, as `<option> <path>` or `<option>=<path>`
+pub fn path_arg(args: impl IntoIterator<Item = String>, option: &str) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == option {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix(option).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+/// The directory given with `--config-dir <dir>` or `--config-dir=<dir>`, if any
+pub fn config_dir_arg(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    path_arg(args, CONFIG_DIR_ARG)
+}
+
+/// Where config files are looked for, in order. Each file is read from the first directory that
+/// has it, falling back to the built-in copy.
+#[derive(Resource, Debug, Clone)]
+pub struct ConfigPaths {
+    pub dirs: Vec<PathBuf>,
+    pub embedded: bool,    // Whether to fall back to EMBEDDED_CONFIGS
+    pub mods: Vec<String>, // Names of the directories from the command line or environment overriding the game's own
+}
+
+impl ConfigPaths {
+    /// The command line, then the environment variable, then the directory the game is in, then
+    /// the working directory
+    pub fn from_environment() -> Self {
+        let cwd = std::env::current_dir().ok();
+        let absolute = |dir: PathBuf| match &cwd {
+            Some(cwd) => cwd.join(dir),
+            None => dir,
+        };
+        let cli = config_dir_arg(std::env::args().skip(1)).map(absolute);
+        let env = std::env::var_os(CONFIG_DIR_VAR).map(PathBuf::from).map(absolute);
+        let exe = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf));
+        Self::search_order(cli, env, exe, cwd.clone())
+    }
+
+    pub fn search_order(cli: Option<PathBuf>, env: Option<PathBuf>, exe: Option<PathBuf>, cwd: Option<PathBuf>) -> Self {
+        // Directories given on the command line or in the environment are mods, unless they're the game's own
+        let mut mods: Vec<String> = Vec::new();
+        for dir in [&cli, &env].into_iter().flatten() {
+            if Some(dir) == exe.as_ref() || Some(dir) == cwd.as_ref() {
+                continue;
+            }
+            if let Some(name) = dir.file_name().map(|name| name.to_string_lossy().into_owned()) {
+                if !mods.contains(&name) {
+                    mods.push(name);
+                }
+            }
+        }
+
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for dir in [cli, env, exe, cwd].into_iter().flatten() {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        Self { dirs, embedded: true, mods }
+    }
+
+    /// The file on disk a config is read from, if it isn't the built-in copy
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        self.dirs.iter().map(|dir| dir.join(name)).find(|path| path.is_file())
+    }
+
+    /// Whether there's a copy of a config file to read, on disk or built in
+    pub fn has(&self, name: &str) -> bool {
+        self.find(name).is_some() || (self.embedded && embedded_config(name).is_some())
+    }
+
+    pub fn read(&self, name: &str) -> io::Result<String> {
+        match self.find(name) {
+            Some(path) => fs::read_to_string(path),
+            None => match embedded_config(name).filter(|_| self.embedded) {
+                Some(contents) => Ok(contents.to_string()),
+                None => Err(io::Error::new(io::ErrorKind::NotFound, format!("{} not found in {:?}", name, self.dirs))),
+            },
+        }
+    }
+
+    /// Read and parse a config file
+    pub fn load<T>(&self, name: &str, parse: impl FnOnce(&str) -> Result<T, Box<dyn std::error::Error>>) -> Result<T, Box<dyn std::error::Error>> {
+        parse(&self.read(name)?)
+    }
+
+    /// Where changes to a config file are written: over the one in use, or into the data
+    /// directory when it's the built-in copy
+    pub fn write_path(&self, name: &str) -> PathBuf {
+        self.find(name).unwrap_or_else(|| self.data_dir().join(name))
+    }
+
+    /// The directory with the game's assets in, which scripts and saves are relative to too: the
+    /// first one searched that has an assets folder
+    pub fn data_dir(&self) -> PathBuf {
+        self.dirs
+            .iter()
+            .find(|dir| dir.join("assets").is_dir())
+            .or(self.dirs.first())
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
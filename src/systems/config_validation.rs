@@ -0,0 +1,548 @@
+use serde::de::DeserializeOwned;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use crate::resources::{GameConfig, SETTINGS_FILE};
+use crate::systems::audio::SoundConfig;
+use crate::systems::config_files::ConfigPaths;
+use crate::systems::faction::FactionConfig;
+use crate::systems::inventory::ItemConfig;
+use crate::systems::keybinds::InputMap;
+use crate::systems::pawn_config::{BehaviourType, PawnConfig, BEHAVIOUR_STATES, resolve_pawn_templates};
+use crate::systems::spawn::SpawnConfig;
+use crate::systems::status_effects::StatusEffectConfig;
+use crate::systems::weather::WeatherSettings;
+use crate::systems::world_gen::{GroundConfig, PropsConfig, TilesetIndex};
+
+/// How far apart two height bounds can be and still count as the same
+const HEIGHT_EPSILON: f32 = 1e-4;
+
+/// Something wrong in one of the config files, and where
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    pub file: String,
+    pub field: String, // Path to the entry, e.g. wolf.eats.pawns - empty for the file as a whole
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub fn new(file: &str, field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { file: file.to_string(), field: field.into(), message: message.into() }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}: {}", self.file, self.message)
+        } else {
+            write!(f, "{}: {}: {}", self.file, self.field, self.message)
+        }
+    }
+}
+
+/// Grounds' height ranges, sorted by where they start, must run from 0 to 1 with each one picking
+/// up where the last left off
+pub fn check_height_ranges(grounds: &HashMap<String, GroundConfig>) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut ranges: Vec<(&String, &GroundConfig)> = grounds.iter().collect();
+    ranges.sort_by(|a, b| a.1.height_min.total_cmp(&b.1.height_min).then(a.0.cmp(b.0)));
+
+    for (name, ground) in &ranges {
+        if ground.height_min >= ground.height_max {
+            problems.push(ConfigProblem::new(
+                "grounds.yaml",
+                format!("{}.height_min", name),
+                format!("{} is not below height_max {}", ground.height_min, ground.height_max),
+            ));
+        }
+    }
+
+    let mut covered_to = 0.0;
+    let mut previous: Option<&String> = None;
+    for (name, ground) in &ranges {
+        let field = format!("{}.height_min", name);
+        if ground.height_min > covered_to + HEIGHT_EPSILON {
+            let after = previous.map_or("0".to_string(), |previous| format!("{}'s height_max", previous));
+            problems.push(ConfigProblem::new(
+                "grounds.yaml",
+                field,
+                format!("no ground covers heights {} to {} - it should start at {}", covered_to, ground.height_min, after),
+            ));
+        } else if ground.height_min < covered_to - HEIGHT_EPSILON {
+            let previous = previous.map_or(String::new(), |previous| previous.clone());
+            problems.push(ConfigProblem::new(
+                "grounds.yaml",
+                field,
+                format!("{} overlaps {}, which goes up to {}", ground.height_min, previous, covered_to),
+            ));
+        }
+        if ground.height_max > covered_to {
+            covered_to = ground.height_max;
+            previous = Some(name);
+        }
+    }
+    if covered_to < 1.0 - HEIGHT_EPSILON {
+        problems.push(ConfigProblem::new(
+            "grounds.yaml",
+            "",
+            format!("no ground covers heights {} to 1", covered_to),
+        ));
+    }
+    problems
+}
+
+/// Why a sprite can't be found, if it can't. `tileset::name::sprite` references have to be in
+/// one of the tilesets; anything else is an image under assets/.
+pub fn sprite_problem(sprite: &str, tilesets: &HashMap<String, TilesetIndex>, assets: &Path) -> Option<String> {
+    if !sprite.starts_with("tileset::") {
+        return (!assets.join(sprite).exists()).then(|| format!("image {} not found in assets", sprite));
+    }
+    let parts: Vec<&str> = sprite.split("::").collect();
+    if parts.len() != 3 {
+        return Some(format!("{} should look like tileset::<tileset>::<sprite>", sprite));
+    }
+    match tilesets.get(parts[1]) {
+        None => Some(format!("no tileset named {} in assets/tilesets", parts[1])),
+        Some(tileset) if !tileset.sprites.iter().any(|info| info.name == parts[2]) => {
+            Some(format!("no sprite named {} in the {} tileset", parts[2], parts[1]))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Where behaviours are named in pawns.yaml, check each is one there is. Works on the YAML itself
+/// so that a misspelt behaviour is reported by name rather than as a pawn that doesn't parse.
+pub fn check_behaviour_references(pawns: &Value, base: &Path) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let pawns = match pawns.as_mapping() {
+        Some(pawns) => pawns,
+        None => return problems,
+    };
+    for (pawn_type, definition) in pawns {
+        let pawn_type = pawn_type.as_str().unwrap_or("?");
+        if let Some(behaviours) = definition.get("behaviours").and_then(Value::as_mapping) {
+            for (state, behaviour) in behaviours {
+                let state = state.as_str().unwrap_or("?");
+                let field = format!("{}.behaviours.{}", pawn_type, state);
+                if !BEHAVIOUR_STATES.contains(&state) {
+                    problems.push(ConfigProblem::new("pawns.yaml", field, format!("unknown behaviour state, expected one of {}", BEHAVIOUR_STATES.join(", "))));
+                    continue;
+                }
+                check_behaviour(behaviour, &field, base, &mut problems);
+            }
+        }
+        if let Some(tree) = definition.get("behaviour_tree") {
+            check_behaviour_node(tree, &format!("{}.behaviour_tree", pawn_type), base, &mut problems);
+        }
+    }
+    problems
+}
+
+fn check_behaviour(behaviour: &Value, field: &str, base: &Path, problems: &mut Vec<ConfigProblem>) {
+    match behaviour {
+        Value::Null => {}
+        Value::String(name) => {
+            if !BehaviourType::ALL.iter().any(|known| known.as_str() == name) {
+                let known: Vec<&str> = BehaviourType::ALL.iter().map(BehaviourType::as_str).collect();
+                problems.push(ConfigProblem::new("pawns.yaml", field, format!("unknown behaviour {}, expected one of {}", name, known.join(", "))));
+            }
+        }
+        Value::Mapping(mapping) if mapping.contains_key("wandering") => {}
+        Value::Mapping(mapping) if mapping.contains_key("script") => match mapping.get("script").and_then(Value::as_str) {
+            Some(script) if !base.join(script).exists() => {
+                problems.push(ConfigProblem::new("pawns.yaml", format!("{}.script", field), format!("script {} not found", script)));
+            }
+            Some(_) => {}
+            None => problems.push(ConfigProblem::new("pawns.yaml", format!("{}.script", field), "should be a path to a script")),
+        },
+        _ => problems.push(ConfigProblem::new("pawns.yaml", field, "should be a behaviour name, wandering or script")),
+    }
+}
+
+fn check_behaviour_node(node: &Value, field: &str, base: &Path, problems: &mut Vec<ConfigProblem>) {
+    let (kind, value) = match node.as_mapping().filter(|mapping| mapping.len() == 1).and_then(|mapping| mapping.iter().next()) {
+        Some((kind, value)) => (kind.as_str().unwrap_or("?"), value),
+        None => {
+            problems.push(ConfigProblem::new("pawns.yaml", field, "should be one of selector, sequence, condition or action"));
+            return;
+        }
+    };
+    let field = format!("{}.{}", field, kind);
+    match kind {
+        "selector" | "sequence" => {
+            for (index, child) in value.as_sequence().into_iter().flatten().enumerate() {
+                check_behaviour_node(child, &format!("{}[{}]", field, index), base, problems);
+            }
+        }
+        "condition" => {}
+        "action" => check_behaviour(value, &field, base, problems),
+        _ => problems.push(ConfigProblem::new("pawns.yaml", field, "unknown node, expected selector, sequence, condition or action")),
+    }
+}
+
+/// Grounds name sprites and status effects that have to exist
+pub fn check_grounds(
+    grounds: &HashMap<String, GroundConfig>,
+    tilesets: &HashMap<String, TilesetIndex>,
+    status_effects: &StatusEffectConfig,
+    assets: &Path,
+) -> Vec<ConfigProblem> {
+    let mut problems = check_height_ranges(grounds);
+    for (name, ground) in grounds {
+        if let Some(message) = sprite_problem(&ground.sprite, tilesets, assets) {
+            problems.push(ConfigProblem::new("grounds.yaml", format!("{}.sprite", name), message));
+        }
+        for (season, sprite) in &ground.seasonal_sprites {
+            if let Some(message) = sprite_problem(sprite, tilesets, assets) {
+                problems.push(ConfigProblem::new("grounds.yaml", format!("{}.seasonal_sprites.{}", name, season.label().to_lowercase()), message));
+            }
+        }
+        if let Some(effect) = &ground.status_effect {
+            if status_effects.get(effect).is_none() {
+                problems.push(ConfigProblem::new("grounds.yaml", format!("{}.status_effect", name), format!("no status effect named {} in status_effects.yaml", effect)));
+            }
+        }
+        if let Some(effect) = &ground.effect {
+            if !(0.0..=1.0).contains(&effect.strength) {
+                problems.push(ConfigProblem::new("grounds.yaml", format!("{}.effect.strength", name), format!("{} is outside 0 to 1", effect.strength)));
+            }
+            if effect.speed < 0.0 {
+                problems.push(ConfigProblem::new("grounds.yaml", format!("{}.effect.speed", name), format!("{} is negative", effect.speed)));
+            }
+        }
+        if ground.footstep.as_ref().is_some_and(|footstep| footstep.particles == 0) {
+            problems.push(ConfigProblem::new("grounds.yaml", format!("{}.footstep.particles", name), "0 particles - leave footstep out instead"));
+        }
+    }
+    problems
+}
+
+/// Everything a pawn refers to in the other config files has to be there
+pub fn check_pawns(
+    pawn_config: &PawnConfig,
+    grounds: &HashMap<String, GroundConfig>,
+    tilesets: &HashMap<String, TilesetIndex>,
+    status_effects: &StatusEffectConfig,
+    assets: &Path,
+) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let mut problem = |field: String, message: String| problems.push(ConfigProblem::new("pawns.yaml", field, message));
+    let unknown_ground = |ground: &str| format!("no ground named {} in grounds.yaml", ground);
+
+    for (pawn_type, definition) in &pawn_config.pawns {
+        let mut sprites = vec![("sprite".to_string(), &definition.sprite)];
+        if let Some(aging) = &definition.aging {
+            for (stage, config) in [("juvenile", &aging.juvenile), ("elderly", &aging.elderly)] {
+                if let Some(sprite) = config.as_ref().and_then(|config| config.sprite.as_ref()) {
+                    sprites.push((format!("aging.{}.sprite", stage), sprite));
+                }
+            }
+        }
+        for (field, sprite) in sprites {
+            if let Some(message) = sprite_problem(sprite, tilesets, assets) {
+                problem(format!("{}.{}", pawn_type, field), message);
+            }
+        }
+
+        if let Some(faction) = &definition.faction {
+            if !pawn_config.factions.factions.contains_key(faction) {
+                problem(format!("{}.faction", pawn_type), format!("no faction named {} in factions.yaml", faction));
+            }
+        }
+        // Prey is whatever has all of these tags, so each has to be on some pawn
+        for tag in &definition.eats.pawns {
+            if !pawn_config.pawns.values().any(|prey| prey.tags.contains(tag)) {
+                problem(format!("{}.eats.pawns", pawn_type), format!("no pawn is tagged {}", tag));
+            }
+        }
+
+        let mut ground_references: Vec<(String, &String)> = Vec::new();
+        ground_references.extend(definition.eats.grounds.iter().map(|ground| ("eats.grounds".to_string(), ground)));
+        if let Some(leaves) = definition.eats.graze.as_ref().and_then(|graze| graze.leaves.as_ref()) {
+            ground_references.push(("eats.graze.leaves".to_string(), leaves));
+        }
+        if let Some(infuses) = &definition.infuses {
+            ground_references.push(("infuses.terrain".to_string(), &infuses.terrain));
+        }
+        if let Some(den) = &definition.den {
+            ground_references.extend(den.grounds.iter().map(|ground| ("den.grounds".to_string(), ground)));
+        }
+        for (field, ground) in ground_references {
+            if !grounds.contains_key(ground) {
+                problem(format!("{}.{}", pawn_type, field), unknown_ground(ground));
+            }
+        }
+
+        for effect in &definition.inflicts {
+            if status_effects.get(effect).is_none() {
+                problem(format!("{}.inflicts", pawn_type), format!("no status effect named {} in status_effects.yaml", effect));
+            }
+        }
+        if let Some(utility) = &definition.utility {
+            for (index, option) in utility.options.iter().enumerate() {
+                if !BEHAVIOUR_STATES.contains(&option.state.as_str()) {
+                    problem(
+                        format!("{}.utility.options[{}].state", pawn_type, index),
+                        format!("unknown behaviour state {}, expected one of {}", option.state, BEHAVIOUR_STATES.join(", ")),
+                    );
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Parse each pawn on its own, so one that doesn't is reported by name, then check what they
+/// refer to. Behaviour names are checked first, so a misspelt one is pointed out rather than
+/// failing the whole pawn.
+fn check_pawn_definitions(
+    pawns_yaml: Value,
+    factions: FactionConfig,
+    grounds: &HashMap<String, GroundConfig>,
+    tilesets: &HashMap<String, TilesetIndex>,
+    status_effects: &StatusEffectConfig,
+    base: &Path,
+) -> Vec<ConfigProblem> {
+    let mut problems = check_behaviour_references(&pawns_yaml, base);
+    let mut pawn_config = PawnConfig { pawns: HashMap::new(), factions };
+    let entries = match pawns_yaml {
+        Value::Mapping(entries) => entries,
+        Value::Null => Mapping::new(),
+        _ => return vec![ConfigProblem::new("pawns.yaml", "", "should be a list of pawns by name")],
+    };
+    for (key, definition) in entries {
+        let name = key.as_str().unwrap_or("?").to_string();
+        // Through PawnConfig rather than straight into a PawnDefinition, which can't read
+        // enums written as maps (like behaviour tree nodes) from a Value
+        let single = Value::Mapping(Mapping::from_iter([(key, definition)]));
+        match serde_yaml::from_value::<PawnConfig>(single) {
+            Ok(parsed) => pawn_config.pawns.extend(parsed.pawns),
+            Err(e) => {
+                if !problems.iter().any(|problem| problem.field.starts_with(&format!("{}.", name))) {
+                    problems.push(ConfigProblem::new("pawns.yaml", name, e.to_string()));
+                }
+            }
+        }
+    }
+    problems.extend(check_pawns(&pawn_config, grounds, tilesets, status_effects, &base.join("assets")));
+    problems
+}
+
+/// Props need a sprite and grounds to grow on that exist
+pub fn check_props(
+    props: &HashMap<String, PropsConfig>,
+    grounds: &HashMap<String, GroundConfig>,
+    tilesets: &HashMap<String, TilesetIndex>,
+    assets: &Path,
+) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    for (name, prop) in props {
+        if let Some(message) = sprite_problem(&prop.sprite, tilesets, assets) {
+            problems.push(ConfigProblem::new("props.yaml", format!("{}.sprite", name), message));
+        }
+        let floors = prop.spawn.floors.iter().chain(prop.spawn.floor.iter()).flat_map(|floors| floors.keys());
+        for floor in floors {
+            if !grounds.contains_key(floor) {
+                problems.push(ConfigProblem::new("props.yaml", format!("{}.spawn", name), format!("no ground named {} in grounds.yaml", floor)));
+            }
+        }
+    }
+    problems
+}
+
+/// Spawn groups need a pawn type and grounds that exist, and one way of saying how many
+/// Footsteps and ambience for grounds that exist, distances sounds can be heard and walked over
+/// and tracks long enough to crossfade. Sound
+/// files that aren't there are left to the game to warn about, so sounds.yaml can name sounds
+/// before they're made.
+pub fn check_sounds(sounds: &SoundConfig, grounds: &HashMap<String, GroundConfig>) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    for ground in sounds.footsteps.keys() {
+        if !grounds.contains_key(ground) {
+            problems.push(ConfigProblem::new("sounds.yaml", format!("footsteps.{}", ground), format!("no ground named {} in grounds.yaml", ground)));
+        }
+    }
+    if sounds.hearing_distance <= 0.0 {
+        problems.push(ConfigProblem::new("sounds.yaml", "hearing_distance", "should be more than 0"));
+    }
+    if sounds.footstep_distance <= 0.0 {
+        problems.push(ConfigProblem::new("sounds.yaml", "footstep_distance", "should be more than 0"));
+    }
+    for (index, ambient) in sounds.ambience.iter().enumerate() {
+        for ground in &ambient.grounds {
+            if !grounds.contains_key(ground) {
+                problems.push(ConfigProblem::new("sounds.yaml", format!("ambience[{}].grounds", index), format!("no ground named {} in grounds.yaml", ground)));
+            }
+        }
+        if !(0.0..=1.0).contains(&ambient.volume) {
+            problems.push(ConfigProblem::new("sounds.yaml", format!("ambience[{}].volume", index), format!("{} is outside 0 to 1", ambient.volume)));
+        }
+    }
+    if sounds.music.track_secs <= sounds.music.crossfade_secs {
+        problems.push(ConfigProblem::new("sounds.yaml", "music.track_secs", "should be longer than crossfade_secs"));
+    }
+    problems
+}
+
+pub fn check_spawns(spawns: &SpawnConfig, pawn_types: &[String], grounds: &HashMap<String, GroundConfig>) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    for (name, group) in &spawns.groups {
+        if !pawn_types.contains(&group.pawn) {
+            problems.push(ConfigProblem::new("spawns.yaml", format!("{}.pawn", name), format!("no pawn type named {} in pawns.yaml", group.pawn)));
+        }
+        for ground in &group.grounds {
+            if !grounds.contains_key(ground) {
+                problems.push(ConfigProblem::new("spawns.yaml", format!("{}.grounds", name), format!("no ground named {} in grounds.yaml", ground)));
+            }
+        }
+        match (group.count, group.density) {
+            (None, None) => problems.push(ConfigProblem::new("spawns.yaml", name.as_str(), "needs a count or a density")),
+            (Some(_), Some(_)) => problems.push(ConfigProblem::new("spawns.yaml", name.as_str(), "has both a count and a density - pick one")),
+            (None, Some(density)) if density < 0.0 => {
+                problems.push(ConfigProblem::new("spawns.yaml", format!("{}.density", name), "can't be negative"));
+            }
+            _ => {}
+        }
+    }
+    problems
+}
+
+/// The world's biome has to be one of those with weather listed, and spells can't end before
+/// they start
+pub fn check_weather(weather: &WeatherSettings) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    if !weather.biomes.contains_key(&weather.biome) {
+        let known: Vec<&str> = weather.biomes.keys().map(String::as_str).collect();
+        problems.push(ConfigProblem::new(SETTINGS_FILE, "weather.biome", format!("no biome named {} in weather.biomes (there's {})", weather.biome, known.join(", "))));
+    }
+    if weather.max_secs < weather.min_secs {
+        problems.push(ConfigProblem::new(SETTINGS_FILE, "weather.max_secs", format!("{} is below min_secs {}", weather.max_secs, weather.min_secs)));
+    }
+    problems
+}
+
+/// Factions' stances have to be towards factions there are
+pub fn check_factions(factions: &FactionConfig) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    for (name, faction) in &factions.factions {
+        for other in faction.stances.keys() {
+            if !factions.factions.contains_key(other) {
+                problems.push(ConfigProblem::new("factions.yaml", format!("{}.stances", name), format!("no faction named {}", other)));
+            }
+        }
+    }
+    problems
+}
+
+/// Read a config file from wherever it's found. A file that's missing gives None, leaving it to
+/// the caller whether that's a problem; one that doesn't parse is reported with serde's line and
+/// column.
+fn parse_file<T: DeserializeOwned>(config_paths: &ConfigPaths, file: &str, problems: &mut Vec<ConfigProblem>) -> Option<T> {
+    let contents = config_paths.read(file).ok()?;
+    parse_yaml(file, &contents, problems)
+}
+
+fn parse_yaml<T: DeserializeOwned>(file: &str, contents: &str, problems: &mut Vec<ConfigProblem>) -> Option<T> {
+    match serde_yaml::from_str(contents) {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            problems.push(ConfigProblem::new(file, "", e.to_string()));
+            None
+        }
+    }
+}
+
+fn require<T>(parsed: Option<T>, config_paths: &ConfigPaths, file: &str, problems: &mut Vec<ConfigProblem>) -> Option<T> {
+    if parsed.is_none() && !config_paths.has(file) {
+        problems.push(ConfigProblem::new(file, "", "file not found"));
+    }
+    parsed
+}
+
+/// Every tileset index under assets/tilesets, by file name
+fn load_tilesets(assets: &Path, problems: &mut Vec<ConfigProblem>) -> HashMap<String, TilesetIndex> {
+    let mut tilesets = HashMap::new();
+    let entries = match fs::read_dir(assets.join("tilesets")) {
+        Ok(entries) => entries,
+        Err(_) => return tilesets,
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|s| s.to_str()) != Some("yaml") {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            let file = format!("assets/tilesets/{}.yaml", stem);
+            let contents = fs::read_to_string(&path).unwrap_or_default();
+            if let Some(tileset) = parse_yaml(&file, &contents, problems) {
+                tilesets.insert(stem.to_string(), tileset);
+            }
+        }
+    }
+    tilesets
+}
+
+/// Check every config file the game would load, collecting all the problems found rather than
+/// stopping at the first. Optional files that are missing are left out, as the game falls back
+/// to defaults for them.
+pub fn validate_configs(config_paths: &ConfigPaths) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+    let base = config_paths.data_dir();
+    let base = base.as_path();
+    let assets = base.join("assets");
+
+    if let Ok(contents) = config_paths.read(SETTINGS_FILE) {
+        match GameConfig::from_yaml(&contents) {
+            Ok(config) => problems.extend(check_weather(&config.weather)),
+            Err(e) => problems.push(ConfigProblem::new(SETTINGS_FILE, "", e.to_string())),
+        }
+    }
+    if let Ok(contents) = config_paths.read("keybinds.yaml") {
+        if let Err(e) = InputMap::from_yaml(&contents) {
+            problems.push(ConfigProblem::new("keybinds.yaml", "", e.to_string()));
+        }
+    }
+
+    let tilesets = load_tilesets(&assets, &mut problems);
+    let status_effects: StatusEffectConfig = parse_file(config_paths, "status_effects.yaml", &mut problems).unwrap_or_default();
+    let factions: FactionConfig = parse_file(config_paths, "factions.yaml", &mut problems).unwrap_or_default();
+    problems.extend(check_factions(&factions));
+    let items: ItemConfig = parse_file(config_paths, "items.yaml", &mut problems).unwrap_or_default();
+    for (name, item) in &items.items {
+        if let Some(message) = sprite_problem(&item.sprite, &tilesets, &assets) {
+            problems.push(ConfigProblem::new("items.yaml", format!("{}.sprite", name), message));
+        }
+    }
+
+    let parsed = parse_file(config_paths, "grounds.yaml", &mut problems);
+    let grounds: HashMap<String, GroundConfig> = require(parsed, config_paths, "grounds.yaml", &mut problems).unwrap_or_default();
+    problems.extend(check_grounds(&grounds, &tilesets, &status_effects, &assets));
+
+    let parsed = parse_file(config_paths, "props.yaml", &mut problems);
+    if let Some(props) = require(parsed, config_paths, "props.yaml", &mut problems) {
+        problems.extend(check_props(&props, &grounds, &tilesets, &assets));
+    }
+
+    let parsed: Option<Value> = parse_file(config_paths, "pawns.yaml", &mut problems);
+    let mut pawn_types: Vec<String> = Vec::new();
+    if let Some(pawns_yaml) = require(parsed, config_paths, "pawns.yaml", &mut problems) {
+        match resolve_pawn_templates(pawns_yaml) {
+            Ok(pawns_yaml) => {
+                let names = pawns_yaml.as_mapping().into_iter().flat_map(|pawns| pawns.keys());
+                pawn_types.extend(names.filter_map(Value::as_str).map(String::from));
+                problems.extend(check_pawn_definitions(pawns_yaml, factions, &grounds, &tilesets, &status_effects, base));
+            }
+            Err(message) => problems.push(ConfigProblem::new("pawns.yaml", "", message)),
+        }
+    }
+
+    if let Some(spawns) = parse_file::<SpawnConfig>(config_paths, "spawns.yaml", &mut problems) {
+        problems.extend(check_spawns(&spawns, &pawn_types, &grounds));
+    }
+    if let Some(sounds) = parse_file::<SoundConfig>(config_paths, "sounds.yaml", &mut problems) {
+        problems.extend(check_sounds(&sounds, &grounds));
+    }
+    problems
+}
@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::aging::Age;
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask, PathfindingPriority};
+use crate::systems::attack::{Attacking, blow_damage};
+use crate::systems::combat::CombatEvent;
+use crate::systems::eating::Eating;
+use crate::systems::faction::Stance;
+use crate::systems::growth::Grown;
+use crate::systems::needs::Asleep;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Size, Swimmer, Health};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::selection::Selected;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
+
+/// Furthest (in tiles) a group move order spreads its pawns' goals from the clicked tile
+pub const MAX_FAN_OUT_TILES: i32 = 8;
+
+/// Selected pawn being driven by the player instead of its AI. It holds an ActiveBehaviour of
+/// player_input for as long as it's controlled, which every AI system leaves alone.
+#[derive(Component, Debug)]
+pub struct Controlled;
+
+/// Pawn told to stay where it is. It doesn't wander or herd until it's given another order.
+#[derive(Component, Debug)]
+pub struct HoldPosition;
+
+/// Controlled pawn told to go after another pawn. It chases and strikes it the way a solo hunter
+/// does until one of them is dead or it's given another order.
+#[derive(Component, Debug)]
+pub struct AttackOrder {
+    pub target: Entity,
+    pub since_last_attack: f32,
+}
+
+impl AttackOrder {
+    pub fn new(target: Entity) -> Self {
+        // Ready to strike as soon as it's in reach
+        Self { target, since_last_attack: f32::INFINITY }
+    }
+}
+
+/// Pawns whose `controlled` behaviour is player_input can be taken over by selecting them
+pub fn is_controllable(pawn_config: &PawnConfig, pawn_type: &str) -> bool {
+    matches!(
+        pawn_config.get_behaviour_config(pawn_type, "controlled"),
+        Some(BehaviourConfig::Simple(BehaviourType::PlayerInput))
+    )
+}
+
+/// Whether the player can send a pawn of one type after another: anything it isn't allied with
+pub fn can_be_ordered_to_attack(pawn_config: &PawnConfig, attacker_type: &str, target_type: &str) -> bool {
+    pawn_config.stance_between(attacker_type, target_type) != Stance::Allied
+}
+
+/// Goal tiles for a group sent to `center`: the passable tiles closest to it, one per pawn, so
+/// they don't all fight over one tile. Nearest first; may come up short if there isn't enough
+/// passable ground within MAX_FAN_OUT_TILES.
+pub fn fan_out_goal_tiles(terrain_map: &TerrainMap, ground_configs: &GroundConfigs, center: (i32, i32), count: usize) -> Vec<(i32, i32)> {
+    let mut tiles: Vec<(i32, i32)> = Vec::new();
+    for radius in 0..=MAX_FAN_OUT_TILES {
+        if tiles.len() >= count {
+            break;
+        }
+        // The ring of tiles `radius` away, closest to the centre first
+        let mut ring: Vec<(i32, i32)> = (-radius..=radius)
+            .flat_map(|dx| (-radius..=radius).map(move |dy| (dx, dy)))
+            .filter(|(dx, dy)| dx.abs().max(dy.abs()) == radius)
+            .map(|(dx, dy)| (center.0 + dx, center.1 + dy))
+            .filter(|(x, y)| terrain_map.is_tile_passable(*x, *y, ground_configs))
+            .collect();
+        ring.sort_by_key(|(x, y)| (x - center.0).pow(2) + (y - center.1).pow(2));
+        tiles.extend(ring);
+    }
+    tiles.truncate(count);
+    tiles
+}
+
+/// Take over selected pawns that can be controlled, dropping whatever their AI had them doing
+pub fn take_control_of_selected(
+    pawn_config: Res<PawnConfig>,
+    mut commands: Commands,
+    mut selected_query: Query<(Entity, &Pawn, &mut CurrentBehavior), (With<Selected>, Without<Controlled>)>,
+) {
+    for (entity, pawn, mut current_behavior) in selected_query.iter_mut() {
+        if !is_controllable(&pawn_config, &pawn.pawn_type) {
+            continue;
+        }
+        println!("Taking control of {}", pawn.pawn_type);
+        current_behavior.state = "controlled".to_string();
+        commands.entity(entity)
+            .remove::<(PawnTarget, PathfindingRequest, PathfindingTask, Eating)>()
+            .insert((
+                Controlled,
+                ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::PlayerInput) },
+            ));
+    }
+}
+
+/// Hand pawns that are no longer selected back to their AI, starting from idle
+pub fn release_control(
+    mut commands: Commands,
+    mut released_query: Query<(Entity, &Pawn, &mut CurrentBehavior), (With<Controlled>, Without<Selected>)>,
+) {
+    for (entity, pawn, mut current_behavior) in released_query.iter_mut() {
+        println!("Released control of {}", pawn.pawn_type);
+        current_behavior.state = "idle".to_string();
+        commands.entity(entity).remove::<(Controlled, ActiveBehaviour, PawnTarget, PathfindingRequest, PathfindingTask, AttackOrder, Attacking)>();
+    }
+}
+
+/// H has the selected pawns hold position
+pub fn handle_hold_input(
+    actions: ActionInput,
+    mut player_commands: ResMut<PlayerCommands>,
+) {
+    if actions.just_pressed(Action::Hold) {
+        player_commands.give(PlayerCommand::Hold);
+    }
+}
+
+/// WASD steers the controlled pawns, given as a new direction whenever the keys held change
+pub fn steer_controlled_pawns(
+    actions: ActionInput,
+    mut player_commands: ResMut<PlayerCommands>,
+    mut steering: Local<Vec2>,
+    controlled_query: Query<(), With<Controlled>>,
+) {
+    let steering_keys = [
+        (Action::SteerUp, Vec2::Y),
+        (Action::SteerDown, Vec2::NEG_Y),
+        (Action::SteerLeft, Vec2::NEG_X),
+        (Action::SteerRight, Vec2::X),
+    ];
+    let direction: Vec2 = steering_keys
+        .iter()
+        .filter(|(action, _)| actions.pressed(*action))
+        .map(|(_, direction)| *direction)
+        .sum();
+    // The same keys move the camera while nothing is controlled, which isn't worth a command
+    if direction == *steering || (controlled_query.is_empty() && *steering == Vec2::ZERO) {
+        return;
+    }
+    *steering = direction;
+    player_commands.give(PlayerCommand::Steer { direction: direction.into() });
+}
+
+/// Carry out this tick's hold and steer commands. Hold stops the selected pawns where they are,
+/// abandoning any path they were on or waiting for. Steering lasts until the next steer
+/// command, walking the controlled pawns a tile at a time that way and overriding any move
+/// order, and stops them where they are once it's zero.
+pub fn apply_control_commands(
+    player_commands: Res<PlayerCommands>,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut steering: Local<Vec2>,
+    mut commands: Commands,
+    selected_query: Query<(Entity, &Pawn), With<Selected>>,
+    controlled_query: Query<(Entity, &Transform, &Size, Has<Swimmer>), With<Controlled>>,
+) {
+    for command in &player_commands.this_tick {
+        match *command {
+            PlayerCommand::Hold => {
+                for (entity, pawn) in selected_query.iter() {
+                    println!("{} is holding position", pawn.pawn_type);
+                    commands.entity(entity)
+                        .remove::<(PawnTarget, PathfindingRequest, PathfindingTask, AttackOrder, Attacking)>()
+                        .insert(HoldPosition);
+                }
+            }
+            PlayerCommand::Steer { direction } => {
+                *steering = Vec2::from(direction);
+                if *steering == Vec2::ZERO {
+                    for (entity, ..) in controlled_query.iter() {
+                        commands.entity(entity).remove::<PawnTarget>();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if *steering == Vec2::ZERO {
+        return;
+    }
+
+    for (entity, transform, size, is_swimmer) in controlled_query.iter() {
+        let position = transform.translation.truncate();
+        let ahead = position + steering.normalize() * config.tile_size;
+        if !terrain_map.is_path_segment_clear((position.x, position.y), (ahead.x, ahead.y), size.value, &ground_configs.for_movement(is_swimmer)) {
+            commands.entity(entity).remove::<PawnTarget>();
+            continue;
+        }
+        commands.entity(entity)
+            .remove::<(PathfindingRequest, PathfindingTask, HoldPosition, AttackOrder, Attacking)>()
+            .insert(PawnTarget::new(ahead.extend(transform.translation.z)));
+    }
+}
+
+/// Controlled pawns with an attack order chase their target with high priority paths and strike
+/// it whenever it's in reach, with the same windup, strength and defence as a solo hunter. The
+/// order ends when the target dies or disappears.
+pub fn attack_order_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut commands: Commands,
+    mut attacker_query: Query<(Entity, &Transform, &Pawn, &Size, &mut AttackOrder, Option<&PawnTarget>, Option<&mut Attacking>), (With<Controlled>, Without<PathfindingRequest>)>,
+    mut target_query: Query<(&Transform, &Pawn, &mut Health)>,
+    age_query: Query<&Age>,
+    grown_query: Query<&Grown>,
+    sleeper_query: Query<(), With<Asleep>>,
+) {
+    for (entity, transform, pawn, size, mut order, current_target, mut attacking) in attacker_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let target_entity = order.target;
+        let (target_transform, target_pawn, mut target_health) = match target_query.get_mut(target_entity) {
+            Ok(target) if target.2.current > 0.0 => target,
+            _ => {
+                commands.entity(entity).remove::<(AttackOrder, Attacking)>();
+                continue;
+            }
+        };
+        order.since_last_attack += time.delta_secs();
+
+        let distance = transform.translation.truncate().distance(target_transform.translation.truncate());
+        let in_reach = distance <= definition.reach as f32 * config.tile_size;
+        let mut new_attack = None;
+        if in_reach && attacking.is_none() && order.since_last_attack >= 1.0 / definition.attack_speed {
+            new_attack = Some(Attacking::new(target_entity));
+            order.since_last_attack = 0.0;
+            commands.entity(entity).remove::<PawnTarget>();
+        }
+        let delta = if new_attack.is_some() { 0.0 } else { time.delta_secs() };
+
+        if let Some(attack) = attacking.as_deref_mut().or(new_attack.as_mut()) {
+            let step = attack.advance(delta, &definition.attack_config());
+            if step.struck && !in_reach {
+                println!("{} dodged {}'s attack", target_pawn.pawn_type, pawn.pawn_type);
+            } else if step.struck {
+                if let Some(target_def) = pawn_config.get_pawn_definition(&target_pawn.pawn_type) {
+                    let attacker_stage = age_query.get(entity).ok().map(|age| age.stage);
+                    let target_stage = age_query.get(target_entity).ok().map(|age| age.stage);
+                    let damage = blow_damage(definition, attacker_stage, grown_query.get(entity).ok(), target_def, target_stage, sleeper_query.contains(target_entity));
+                    target_health.current = (target_health.current - damage).max(0.0);
+                    combat_events.send(CombatEvent { attacker: entity, victim: target_entity, damage });
+                    if target_health.current <= 0.0 {
+                        println!("{} killed {} as ordered", pawn.pawn_type, target_pawn.pawn_type);
+                    }
+                }
+            }
+
+            if step.finished {
+                if new_attack.is_none() {
+                    commands.entity(entity).remove::<Attacking>();
+                }
+            } else if let Some(new_attack) = new_attack {
+                commands.entity(entity).insert(new_attack);
+            }
+            continue; // Attackers stand their ground
+        }
+        if in_reach {
+            continue;
+        }
+
+        // Give chase, re-pathing whenever the target has moved off the end of the current path
+        let target_position = target_transform.translation.truncate();
+        let needs_new_path = current_target.is_none_or(|pawn_target| {
+            pawn_target.target_position.truncate().distance(target_position) > config.tile_size / 2.0 || pawn_target.path.is_empty()
+        });
+        if needs_new_path {
+            let current_pos = (transform.translation.x, transform.translation.y);
+            commands.entity(entity).insert(
+                PathfindingRequest::new(current_pos, (target_position.x, target_position.y), size.value)
+                    .with_priority(PathfindingPriority::High)
+            );
+        }
+    }
+}
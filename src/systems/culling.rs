@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::systems::camera::CameraController;
+use crate::systems::world_gen::TerrainMap;
+
+/// Width and height of an overlay chunk, in tiles
+pub const CHUNK_TILES: u32 = 16;
+/// How far past the edge of the view chunks are still drawn, in tiles, so none pop in as the
+/// camera moves
+const CULL_MARGIN_TILES: f32 = 2.0;
+
+/// A square of tiles whose overlays are drawn or hidden together, as children of it, depending
+/// on whether it's in view
+#[derive(Component, Debug)]
+pub struct OverlayChunk {
+    pub area: Rect, // In world space
+}
+
+/// The chunks one kind of per-tile overlay is split into, made as they're first needed
+#[derive(Debug, Default)]
+pub struct OverlayChunks {
+    pub chunks: HashMap<(u32, u32), Entity>,
+}
+
+impl OverlayChunks {
+    /// The chunk the overlay on a tile goes in
+    pub fn chunk_for(&mut self, commands: &mut Commands, terrain_map: &TerrainMap, x: u32, y: u32) -> Entity {
+        let chunk = (x / CHUNK_TILES, y / CHUNK_TILES);
+        *self.chunks.entry(chunk).or_insert_with(|| {
+            let (first_x, first_y) = terrain_map.tile_to_world_coords((chunk.0 * CHUNK_TILES) as i32, (chunk.1 * CHUNK_TILES) as i32);
+            let min = Vec2::new(first_x, first_y) - terrain_map.tile_size / 2.0;
+            let area = Rect::from_corners(min, min + CHUNK_TILES as f32 * terrain_map.tile_size);
+            commands.spawn((Transform::default(), Visibility::default(), OverlayChunk { area })).id()
+        })
+    }
+}
+
+/// Hide the overlay chunks outside the view, so their tiles aren't checked or drawn every frame
+pub fn cull_overlay_chunks(
+    terrain_map: Option<Res<TerrainMap>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<CameraController>>,
+    mut chunk_query: Query<(&OverlayChunk, &mut Visibility)>,
+) {
+    let (Some(terrain_map), Ok((camera_transform, projection))) = (terrain_map, camera_query.get_single()) else {
+        return;
+    };
+    let view = Rect {
+        min: projection.area.min + camera_transform.translation.truncate(),
+        max: projection.area.max + camera_transform.translation.truncate(),
+    }
+    .inflate(CULL_MARGIN_TILES * terrain_map.tile_size);
+    for (chunk, mut visibility) in chunk_query.iter_mut() {
+        let in_view = !chunk.area.intersect(view).is_empty();
+        visibility.set_if_neq(if in_view { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
@@ -1,47 +1,137 @@
 use bevy::prelude::*;
+use crate::components::AttachedTo;
+use crate::systems::async_pathfinding::GlobalPathfindingCache;
+use crate::systems::pathfinding_cache::PathfindingCache;
 use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, PawnTarget};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::level_of_detail::LevelOfDetail;
+use crate::systems::selection::cursor_world_position;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// Pawn sizes a tile inspection checks the footing of, from rabbits up to bears
+pub const INSPECTED_SIZES: [f32; 4] = [0.5, 1.0, 1.5, 2.0];
+
+/// Debug overlays, cycled in this order with F12
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugMode {
+    Off,
+    Pawns,              // Pawn stats and waypoint lines
+    PathfindingHeatmap, // Recently expanded A* nodes and cache hits
+    Population,         // Live counts per species, births and deaths
+    Passability,        // Where a pawn of the chosen size can stand, as A* sees it
+}
+
+impl DebugMode {
+    pub fn next(&self) -> Self {
+        match self {
+            DebugMode::Off => DebugMode::Pawns,
+            DebugMode::Pawns => DebugMode::PathfindingHeatmap,
+            DebugMode::PathfindingHeatmap => DebugMode::Population,
+            DebugMode::Population => DebugMode::Passability,
+            DebugMode::Passability => DebugMode::Off,
+        }
+    }
+}
 
 #[derive(Resource)]
 pub struct DebugDisplayState {
-    pub enabled: bool,
+    pub mode: DebugMode,
 }
 
 impl Default for DebugDisplayState {
     fn default() -> Self {
         Self {
-            enabled: false,
+            mode: DebugMode::Off,
         }
     }
 }
 
-#[derive(Component)]
-pub struct DebugText {
-    pub pawn_entity: Entity,
+impl DebugDisplayState {
+    /// Any debug overlay is active (enables debug-only input such as terrain editing)
+    pub fn is_enabled(&self) -> bool {
+        self.mode != DebugMode::Off
+    }
+
+    pub fn shows_pawns(&self) -> bool {
+        self.mode == DebugMode::Pawns
+    }
 }
 
 #[derive(Component)]
-pub struct WaypointLine {
+pub struct DebugText {
     pub pawn_entity: Entity,
-    pub line_segments: Vec<Entity>,
 }
 
 pub fn toggle_debug_display(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut debug_state: ResMut<DebugDisplayState>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::F12) {
-        debug_state.enabled = !debug_state.enabled;
-        println!("Debug display: {}", if debug_state.enabled { "ON" } else { "OFF" });
+    if actions.just_pressed(Action::ToggleDebug) {
+        debug_state.mode = debug_state.mode.next();
+        println!("Debug display: {:?}", debug_state.mode);
+    }
+}
+
+/// Everything pathfinding knows about a tile: its ground, whether pawns of each inspected size
+/// can stand centred on it, and the cached paths that cross it
+pub fn inspect_tile(tile: (i32, i32), terrain_map: &TerrainMap, ground_configs: &GroundConfigs, cache: &PathfindingCache) -> String {
+    let terrain_type = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+    let mut lines = vec![format!(
+        "Tile ({}, {}): {} (terrain type {})",
+        tile.0, tile.1, ground_configs.name_of(terrain_type).unwrap_or("unknown"), terrain_type
+    )];
+
+    let (center_x, center_y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+    let footing: Vec<String> = INSPECTED_SIZES
+        .iter()
+        .map(|size| {
+            let passable = terrain_map.is_position_passable_for_size(center_x, center_y, *size, ground_configs);
+            format!("{}: {}", size, if passable { "yes" } else { "no" })
+        })
+        .collect();
+    lines.push(format!("Passable by size - {}", footing.join(", ")));
+
+    let paths = cache.paths_over_tile(tile.0 as u32, tile.1 as u32);
+    if paths.is_empty() {
+        lines.push("Cached paths over it: none".to_string());
+    } else {
+        lines.push(format!("Cached paths over it: {}", paths.len()));
+        for (start, goal, size) in paths {
+            lines.push(format!("  ({}, {}) -> ({}, {}) for size {}", start.0, start.1, goal.0, goal.1, size));
+        }
+    }
+    lines.join("\n")
+}
+
+/// While a debug overlay is on, clicking a tile prints what pathfinding knows about it
+pub fn inspect_clicked_tile(
+    actions: ActionInput,
+    debug_state: Res<DebugDisplayState>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    global_cache: Res<GlobalPathfindingCache>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+) {
+    if !actions.just_pressed(Action::Select) || !debug_state.is_enabled() {
+        return;
+    }
+    let tile = cursor_world_position(&windows, &camera_query)
+        .and_then(|world_position| terrain_map.world_to_tile_coords(world_position.x, world_position.y));
+    if let Some(tile) = tile {
+        println!("{}", inspect_tile(tile, &terrain_map, &ground_configs, global_cache.cache()));
     }
 }
 
 pub fn manage_debug_text_entities(
     mut commands: Commands,
     debug_state: Res<DebugDisplayState>,
+    level_of_detail: Res<LevelOfDetail>,
     pawn_query: Query<Entity, (With<Pawn>, With<Health>, With<Endurance>, With<CurrentBehavior>)>,
     debug_text_query: Query<(Entity, &DebugText)>,
 ) {
-    if debug_state.enabled {
+    // Left out when zoomed too far out to read
+    if debug_state.shows_pawns() && !level_of_detail.hides_overlays() {
         // Create debug text entities for pawns that don't have them
         for pawn_entity in pawn_query.iter() {
             let has_debug_text = debug_text_query.iter().any(|(_, debug_text)| {
@@ -60,11 +150,12 @@ pub fn manage_debug_text_entities(
                     DebugText {
                         pawn_entity,
                     },
+                    AttachedTo(pawn_entity),
                 ));
             }
         }
     } else {
-        // Remove all debug text entities when disabled
+        // Remove all debug text entities when disabled or zoomed out
         for (debug_entity, _) in debug_text_query.iter() {
             commands.entity(debug_entity).despawn();
         }
@@ -76,7 +167,7 @@ pub fn update_debug_text(
     pawn_query: Query<(&Transform, &Health, &Endurance, &CurrentBehavior), With<Pawn>>,
     mut debug_text_query: Query<(&mut Transform, &mut Text2d, &DebugText), Without<Pawn>>,
 ) {
-    if !debug_state.enabled {
+    if !debug_state.shows_pawns() {
         return;
     }
 
@@ -103,168 +194,36 @@ pub fn update_debug_text(
     }
 }
 
-pub fn cleanup_orphaned_debug_text(
-    mut commands: Commands,
-    pawn_query: Query<Entity, With<Pawn>>,
-    debug_text_query: Query<(Entity, &DebugText)>,
-) {
-    let existing_pawns: std::collections::HashSet<Entity> = pawn_query.iter().collect();
-    
-    for (debug_entity, debug_text) in debug_text_query.iter() {
-        if !existing_pawns.contains(&debug_text.pawn_entity) {
-            commands.entity(debug_entity).despawn();
-        }
-    }
-}
-
-pub fn manage_waypoint_lines(
-    mut commands: Commands,
-    debug_state: Res<DebugDisplayState>,
-    pawn_query: Query<Entity, (With<Pawn>, With<PawnTarget>)>,
-    waypoint_line_query: Query<(Entity, &WaypointLine)>,
-) {
-    if debug_state.enabled {
-        // Create waypoint lines for pawns with targets that don't have them
-        for pawn_entity in pawn_query.iter() {
-            let has_waypoint_line = waypoint_line_query.iter().any(|(_, waypoint_line)| {
-                waypoint_line.pawn_entity == pawn_entity
-            });
-            
-            if !has_waypoint_line {
-                commands.spawn(WaypointLine {
-                    pawn_entity,
-                    line_segments: Vec::new(),
-                });
-            }
-        }
+/// Gray for waypoints already passed, green for the one being walked to and yellow for the rest
+fn waypoint_color(index: usize, current_waypoint_index: usize) -> Color {
+    if index < current_waypoint_index {
+        Color::srgb(0.3, 0.3, 0.3)
+    } else if index == current_waypoint_index {
+        Color::srgb(0.0, 1.0, 0.0)
     } else {
-        // Remove all waypoint lines when disabled
-        for (waypoint_line_entity, waypoint_line) in waypoint_line_query.iter() {
-            // Clean up all line segment entities
-            for &segment_entity in &waypoint_line.line_segments {
-                if let Some(mut entity_commands) = commands.get_entity(segment_entity) {
-                    entity_commands.despawn();
-                }
-            }
-            // Remove the waypoint line entity itself
-            commands.entity(waypoint_line_entity).despawn();
-        }
+        Color::srgb(1.0, 1.0, 0.0)
     }
 }
 
-pub fn update_waypoint_lines(
-    mut commands: Commands,
+/// Draw each pawn's path while debug is on: a line from the pawn through its waypoints, with a
+/// small circle on each waypoint
+pub fn draw_waypoint_lines(
+    mut gizmos: Gizmos,
     debug_state: Res<DebugDisplayState>,
     pawn_query: Query<(&Transform, &PawnTarget), With<Pawn>>,
-    mut waypoint_line_query: Query<(Entity, &mut WaypointLine)>,
 ) {
-    if !debug_state.enabled {
+    if !debug_state.shows_pawns() {
         return;
     }
 
-    for (waypoint_line_entity, mut waypoint_line) in waypoint_line_query.iter_mut() {
-        if let Ok((pawn_transform, pawn_target)) = pawn_query.get(waypoint_line.pawn_entity) {
-            // Clean up existing line segments
-            for &segment_entity in &waypoint_line.line_segments {
-                if let Some(mut entity_commands) = commands.get_entity(segment_entity) {
-                    entity_commands.despawn();
-                }
-            }
-            waypoint_line.line_segments.clear();
-
-            // Create new line segments for the current path
-            if !pawn_target.path.is_empty() {
-                let mut previous_point = pawn_transform.translation;
-                
-                // Draw lines from pawn to first waypoint, then between waypoints
-                for (i, &waypoint) in pawn_target.path.iter().enumerate() {
-                    let start_pos = previous_point;
-                    let end_pos = waypoint;
-                    
-                    // Calculate line properties
-                    let direction = (end_pos - start_pos).normalize_or_zero();
-                    let length = start_pos.distance(end_pos);
-                    let center = (start_pos + end_pos) * 0.5;
-                    let angle = direction.y.atan2(direction.x);
-                    
-                    // Choose color based on waypoint status
-                    let color = if i < pawn_target.current_waypoint_index {
-                        Color::srgb(0.3, 0.3, 0.3) // Gray for completed waypoints
-                    } else if i == pawn_target.current_waypoint_index {
-                        Color::srgb(0.0, 1.0, 0.0) // Green for current waypoint
-                    } else {
-                        Color::srgb(1.0, 1.0, 0.0) // Yellow for future waypoints
-                    };
-                    
-                    // Create line segment entity
-                    let line_entity = commands.spawn((
-                        Sprite {
-                            color,
-                            custom_size: Some(Vec2::new(length.max(1.0), 2.0)), // 2px thick line
-                            ..default()
-                        },
-                        Transform {
-                            translation: Vec3::new(center.x, center.y, 150.0), // Below text but above terrain
-                            rotation: Quat::from_rotation_z(angle),
-                            ..default()
-                        },
-                    )).id();
-                    
-                    waypoint_line.line_segments.push(line_entity);
-                    previous_point = end_pos;
-                }
-                
-                // Add waypoint markers (small circles at each waypoint)
-                for (i, &waypoint) in pawn_target.path.iter().enumerate() {
-                    let color = if i < pawn_target.current_waypoint_index {
-                        Color::srgb(0.3, 0.3, 0.3) // Gray for completed waypoints
-                    } else if i == pawn_target.current_waypoint_index {
-                        Color::srgb(0.0, 1.0, 0.0) // Green for current waypoint
-                    } else {
-                        Color::srgb(1.0, 1.0, 0.0) // Yellow for future waypoints
-                    };
-                    
-                    let marker_entity = commands.spawn((
-                        Sprite {
-                            color,
-                            custom_size: Some(Vec2::new(4.0, 4.0)), // 4px diameter circle
-                            ..default()
-                        },
-                        Transform::from_translation(Vec3::new(waypoint.x, waypoint.y, 160.0)),
-                    )).id();
-                    
-                    waypoint_line.line_segments.push(marker_entity);
-                }
-            }
-        } else {
-            // Pawn no longer exists or doesn't have a target, clean up
-            for &segment_entity in &waypoint_line.line_segments {
-                if let Some(mut entity_commands) = commands.get_entity(segment_entity) {
-                    entity_commands.despawn();
-                }
-            }
-            commands.entity(waypoint_line_entity).despawn();
+    for (pawn_transform, pawn_target) in pawn_query.iter() {
+        let mut previous_point = pawn_transform.translation.truncate();
+        for (i, waypoint) in pawn_target.path.iter().enumerate() {
+            let color = waypoint_color(i, pawn_target.current_waypoint_index);
+            let waypoint = waypoint.truncate();
+            gizmos.line_2d(previous_point, waypoint, color);
+            gizmos.circle_2d(waypoint, 2.0, color);
+            previous_point = waypoint;
         }
     }
 }
-
-pub fn cleanup_orphaned_waypoint_lines(
-    mut commands: Commands,
-    pawn_query: Query<Entity, (With<Pawn>, With<PawnTarget>)>,
-    waypoint_line_query: Query<(Entity, &WaypointLine)>,
-) {
-    let existing_pawns_with_targets: std::collections::HashSet<Entity> = pawn_query.iter().collect();
-    
-    for (waypoint_line_entity, waypoint_line) in waypoint_line_query.iter() {
-        if !existing_pawns_with_targets.contains(&waypoint_line.pawn_entity) {
-            // Clean up all line segment entities
-            for &segment_entity in &waypoint_line.line_segments {
-                if let Some(mut entity_commands) = commands.get_entity(segment_entity) {
-                    entity_commands.despawn();
-                }
-            }
-            // Remove the waypoint line entity itself
-            commands.entity(waypoint_line_entity).despawn();
-        }
-    }
-}
\ No newline at end of file
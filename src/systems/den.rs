@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::pawn::Pawn;
+use crate::systems::pawn_config::{PawnConfig, PawnType, DenConfig};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::sim_rng::SimRng;
+use crate::systems::y_sort::{YSort, DepthLayer};
+
+/// Tired pawns within this many tiles of their den sleep where they are instead of heading home
+pub const DEN_SLEEP_RADIUS_TILES: f32 = 1.0;
+/// Random tiles tried for each den before giving up on it
+pub const DEN_PLACEMENT_ATTEMPTS: u32 = 1000;
+/// Dens are drawn as a square of this colour, this fraction of a tile across
+pub const DEN_COLOR: Color = Color::srgb(0.35, 0.22, 0.1);
+pub const DEN_SPRITE_SIZE: f32 = 0.8;
+/// Above terrain and props, below the heatmap overlay and pawns, while dens aren't y-sorted
+const DEN_Z: f32 = 10.0;
+
+/// A nest or den belonging to a species. Its pawns start out there and go back to sleep.
+#[derive(Component, Debug, Clone)]
+pub struct Den {
+    pub species: PawnType,
+}
+
+/// The den a pawn calls home. Removed once the den is gone - a species without dens
+/// has nowhere to raise its young.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HomeDen {
+    pub den: Entity,
+}
+
+/// Up to `den.count` distinct passable tiles on the den's grounds, away from the map edge
+pub fn pick_den_tiles(den: &DenConfig, terrain_map: &TerrainMap, ground_configs: &GroundConfigs, rng: &mut impl Rng) -> Vec<(i32, i32)> {
+    let allowed: Vec<usize> = den
+        .grounds
+        .iter()
+        .filter_map(|ground| ground_configs.terrain_mapping.get(ground).copied())
+        .collect();
+    let mut tiles = Vec::new();
+    if terrain_map.width < 3 || terrain_map.height < 3 {
+        return tiles;
+    }
+
+    for _ in 0..DEN_PLACEMENT_ATTEMPTS * den.count {
+        if tiles.len() >= den.count as usize {
+            break;
+        }
+        let tile = (rng.gen_range(1..terrain_map.width - 1) as i32, rng.gen_range(1..terrain_map.height - 1) as i32);
+        let terrain_type = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+        if terrain_map.is_tile_passable(tile.0, tile.1, ground_configs)
+            && (den.grounds.is_empty() || allowed.contains(&terrain_type))
+            && !tiles.contains(&tile)
+        {
+            tiles.push(tile);
+        }
+    }
+    tiles
+}
+
+/// Dig every species' dens once the world has been generated
+pub fn place_dens(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut rng: ResMut<SimRng>,
+) {
+    let rng = &mut *rng;
+    for pawn_type in pawn_config.get_pawn_types() {
+        let den = match pawn_config.get_pawn_definition(&pawn_type).and_then(|def| def.den.as_ref()) {
+            Some(den) => den,
+            None => continue,
+        };
+        let tiles = pick_den_tiles(den, &terrain_map, &ground_configs, rng);
+        if tiles.len() < den.count as usize {
+            println!("Only found room for {} of {} {} dens", tiles.len(), den.count, pawn_type);
+        }
+        for (tile_x, tile_y) in tiles {
+            let (x, y) = terrain_map.tile_to_world_coords(tile_x, tile_y);
+            commands.spawn((
+                Den { species: pawn_type.clone() },
+                Sprite::from_color(DEN_COLOR, Vec2::splat(config.tile_size * DEN_SPRITE_SIZE)),
+                Transform::from_xyz(x, y, DEN_Z),
+                YSort(DepthLayer::Dens),
+            ));
+        }
+    }
+}
+
+/// Pawns whose den has been destroyed lose their home
+pub fn release_homeless_pawns(
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Pawn, &HomeDen)>,
+    den_query: Query<(), With<Den>>,
+) {
+    for (entity, pawn, home) in pawn_query.iter() {
+        if !den_query.contains(home.den) {
+            println!("{} lost its den", pawn.pawn_type);
+            commands.entity(entity).remove::<HomeDen>();
+        }
+    }
+}
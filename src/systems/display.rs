@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, WindowMode, WindowResolution};
+use std::time::{Duration, Instant};
+use crate::resources::GameConfig;
+
+/// The game's window as settings.yaml describes it
+pub fn window_from_config(config: &GameConfig) -> Window {
+    Window {
+        title: config.window_title.clone(),
+        resolution: WindowResolution::new(config.window_width as f32, config.window_height as f32),
+        present_mode: if config.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync },
+        mode: if config.fullscreen {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        } else {
+            WindowMode::Windowed
+        },
+        ..default()
+    }
+}
+
+/// The shortest a frame may take at `target_fps`, or None for no limit
+pub fn frame_duration(target_fps: u32) -> Option<Duration> {
+    if target_fps == 0 {
+        return None;
+    }
+    Some(Duration::from_secs_f64(1.0 / target_fps as f64))
+}
+
+/// Sleep out whatever is left of the frame so the game runs no faster than target_fps. Runs last
+/// in the frame, timing from where the previous frame's wait ended.
+pub fn limit_frame_rate(config: Res<GameConfig>, mut frame_start: Local<Option<Instant>>) {
+    if let (Some(frame), Some(start)) = (frame_duration(config.target_fps), *frame_start) {
+        let elapsed = start.elapsed();
+        if elapsed < frame {
+            std::thread::sleep(frame - elapsed);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
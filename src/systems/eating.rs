@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::async_pathfinding::PathfindingRequest;
+use crate::systems::combat::CombatEvent;
+use crate::systems::growth::PawnFed;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Endurance};
+use crate::systems::pawn_config::{PawnType, MealConfig, BehaviourConfig, BehaviourType};
+use crate::systems::perception::Threatened;
+
+/// A hunter sitting at its kill. It takes `remaining` seconds to get the rest of the kill's
+/// endurance, and goes hungry for whatever is left if it's disturbed before then.
+#[derive(Component, Debug, Clone)]
+pub struct Eating {
+    pub prey: PawnType,
+    pub remaining: f32,
+    pub endurance_left: f32,
+}
+
+/// Sit a hunter down at its kill to eat `endurance` worth of it over the meal's duration.
+/// Until it's done it stays put in its `eat` state, whatever its needs or behaviour tree say.
+pub fn start_eating(commands: &mut Commands, hunter: Entity, prey: &PawnType, endurance: f32, meal: &MealConfig) {
+    if let Some(mut entity_commands) = commands.get_entity(hunter) {
+        entity_commands
+            .remove::<(PawnTarget, PathfindingRequest)>()
+            .insert((
+                Eating { prey: prey.clone(), remaining: meal.duration, endurance_left: endurance },
+                ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Null) },
+            ));
+    }
+}
+
+fn stop_eating(commands: &mut Commands, entity: Entity, current_behavior: &mut CurrentBehavior) {
+    current_behavior.state = "idle".to_string();
+    commands.entity(entity).remove::<(Eating, ActiveBehaviour)>();
+}
+
+/// Eating hunters gain their kill's endurance a bit at a time. Being attacked or spotting a
+/// predator makes them leave the rest of the meal.
+pub fn eating_system(
+    time: Res<Time>,
+    mut combat_events: EventReader<CombatEvent>,
+    mut fed_events: EventWriter<PawnFed>,
+    mut commands: Commands,
+    mut eater_query: Query<(Entity, &Pawn, &mut Eating, &mut Endurance, &mut CurrentBehavior, Has<Threatened>)>,
+) {
+    let attacked: HashSet<Entity> = combat_events.read().map(|event| event.victim).collect();
+    let delta = time.delta_secs();
+
+    for (entity, pawn, mut eating, mut endurance, mut current_behavior, threatened) in eater_query.iter_mut() {
+        if threatened || attacked.contains(&entity) {
+            println!("{} was driven off its {} kill", pawn.pawn_type, eating.prey);
+            stop_eating(&mut commands, entity, &mut current_behavior);
+            continue;
+        }
+        if current_behavior.state != "eat" {
+            current_behavior.state = "eat".to_string();
+        }
+
+        let gain = if eating.remaining <= delta {
+            eating.endurance_left
+        } else {
+            eating.endurance_left * delta / eating.remaining
+        };
+        endurance.current = (endurance.current + gain).min(endurance.max);
+        eating.endurance_left -= gain;
+        eating.remaining -= delta;
+
+        if eating.remaining <= 0.0 {
+            println!("{} finished eating {}", pawn.pawn_type, eating.prey);
+            fed_events.send(PawnFed { pawn: entity });
+            stop_eating(&mut commands, entity, &mut current_behavior);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How members of one faction treat members of another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stance {
+    Hostile, // Attacked on sight, whether or not they're food
+    Neutral, // Only hunted if they're food
+    Allied,  // Never attacked, even if they're food
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FactionDefinition {
+    #[serde(default)]
+    pub stances: HashMap<String, Stance>, // Towards other factions (or this one) by name - one-way
+}
+
+/// Factions from factions.yaml. Pawns join one with `faction` in pawns.yaml.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct FactionConfig {
+    #[serde(flatten)]
+    pub factions: HashMap<String, FactionDefinition>,
+}
+
+impl FactionConfig {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: FactionConfig = serde_yaml::from_str(contents)?;
+        Ok(config)
+    }
+
+    /// Stance of faction `from` towards faction `to`. Members of the same faction are allied
+    /// and everyone else neutral, unless the faction sets otherwise.
+    pub fn stance(&self, from: &str, to: &str) -> Stance {
+        let configured = self.factions.get(from).and_then(|faction| faction.stances.get(to)).copied();
+        match configured {
+            Some(stance) => stance,
+            None if from == to => Stance::Allied,
+            None => Stance::Neutral,
+        }
+    }
+}
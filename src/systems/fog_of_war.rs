@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+use crate::components::AttachedTo;
+use crate::resources::GameConfig;
+use crate::systems::control::is_controllable;
+use crate::systems::minimap::MinimapDot;
+use crate::systems::pawn::Pawn;
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::perception::Vision;
+use crate::systems::world_gen::TerrainMap;
+
+/// Over everything on the map, under the weather and the night tint
+const FOG_OF_WAR_Z: f32 = 130.0;
+
+/// The `fog_of_war` section of settings.yaml
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FogOfWarSettings {
+    pub enabled: bool,
+    pub reveal_radius: f32,       // Tiles seen around controllable pawns without a vision entry
+    pub remembered_darkness: f32, // 0 to 1, how dark tiles seen before but out of sight now are
+}
+
+impl Default for FogOfWarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reveal_radius: 6.0,
+            remembered_darkness: 0.6,
+        }
+    }
+}
+
+/// One bit per tile of the map
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TileBits {
+    pub width: u32,
+    pub height: u32,
+    pub words: Vec<u64>,
+}
+
+impl TileBits {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width as usize * height as usize).div_ceil(64)],
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| x as usize * self.height as usize + y as usize)
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        self.index(x, y).is_some_and(|index| self.words[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    pub fn set(&mut self, x: u32, y: u32) {
+        if let Some(index) = self.index(x, y) {
+            self.words[index / 64] |= 1 << (index % 64);
+        }
+    }
+}
+
+/// How much of a tile the player can make out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileFog {
+    Unexplored,
+    Remembered, // Seen before, drawn dimmed as it was
+    Visible,
+}
+
+/// Which tiles the player's pawns have seen, saved with the game, and which they can see now
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Exploration {
+    pub explored: TileBits,
+    pub visible: TileBits,
+}
+
+impl Exploration {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            explored: TileBits::new(width, height),
+            visible: TileBits::new(width, height),
+        }
+    }
+
+    pub fn fog_at(&self, x: u32, y: u32) -> TileFog {
+        if self.visible.get(x, y) {
+            TileFog::Visible
+        } else if self.explored.get(x, y) {
+            TileFog::Remembered
+        } else {
+            TileFog::Unexplored
+        }
+    }
+}
+
+/// The sprite stretched over the map, one pixel of its image per tile
+#[derive(Component)]
+pub struct FogOfWar;
+
+/// Every tile within `range` tiles of one of the viewers
+pub fn tiles_in_view(terrain_map: &TerrainMap, viewers: impl Iterator<Item = (Vec2, f32)>) -> TileBits {
+    let mut visible = TileBits::new(terrain_map.width, terrain_map.height);
+    for (position, range) in viewers {
+        let Some((center_x, center_y)) = terrain_map.world_to_tile_coords(position.x, position.y) else {
+            continue;
+        };
+        let reach = range.ceil() as i32;
+        for x in (center_x - reach).max(0)..=(center_x + reach).min(terrain_map.width as i32 - 1) {
+            for y in (center_y - reach).max(0)..=(center_y + reach).min(terrain_map.height as i32 - 1) {
+                let offset = Vec2::new((x - center_x) as f32, (y - center_y) as f32);
+                if offset.length() <= range {
+                    visible.set(x as u32, y as u32);
+                }
+            }
+        }
+    }
+    visible
+}
+
+/// RGBA pixels for the fog over the whole map, rows running top to bottom like the minimap's
+pub fn fog_pixels(exploration: &Exploration, remembered_darkness: f32) -> Vec<u8> {
+    let (width, height) = (exploration.explored.width, exploration.explored.height);
+    let remembered = (remembered_darkness.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let mut data = vec![0; (width * height * 4) as usize];
+    for x in 0..width {
+        for y in 0..height {
+            let alpha = match exploration.fog_at(x, y) {
+                TileFog::Unexplored => 255,
+                TileFog::Remembered => remembered,
+                TileFog::Visible => 0,
+            };
+            let offset = (((height - 1 - y) * width + x) * 4) as usize;
+            data[offset + 3] = alpha;
+        }
+    }
+    data
+}
+
+/// Start the world unexplored, covering the map in fog if it's switched on. Loading a save
+/// brings back what was explored in it.
+pub fn setup_fog_of_war(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let exploration = Exploration::new(terrain_map.width, terrain_map.height);
+    if config.fog_of_war.enabled {
+        let image = Image::new(
+            Extent3d { width: terrain_map.width, height: terrain_map.height, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            fog_pixels(&exploration, config.fog_of_war.remembered_darkness),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        // Smoothed between tiles, so the edge of what's in view is soft
+        let mut sprite = Sprite::from_image(images.add(image));
+        sprite.custom_size = Some(Vec2::new(terrain_map.width as f32, terrain_map.height as f32) * terrain_map.tile_size);
+        commands.spawn((sprite, Transform::from_xyz(0.0, 0.0, FOG_OF_WAR_Z), FogOfWar));
+    }
+    commands.insert_resource(exploration);
+}
+
+/// Reveal what controllable pawns can see, fog over what they've seen before and can't any more,
+/// and hide other pawns - with their icons and minimap dots - while they're out of sight
+pub fn update_fog_of_war(
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    mut exploration: ResMut<Exploration>,
+    mut images: ResMut<Assets<Image>>,
+    fog_query: Query<&Sprite, With<FogOfWar>>,
+    mut pawn_query: Query<(Entity, &Transform, &Pawn, Option<&Vision>, &mut Visibility), (Without<AttachedTo>, Without<MinimapDot>)>,
+    mut attached_query: Query<(&AttachedTo, &mut Visibility), (Without<Pawn>, Without<MinimapDot>)>,
+    mut dot_query: Query<(&MinimapDot, &mut Visibility), (Without<Pawn>, Without<AttachedTo>)>,
+) {
+    if !config.fog_of_war.enabled {
+        return;
+    }
+
+    let viewers = pawn_query
+        .iter()
+        .filter(|(_, _, pawn, _, _)| is_controllable(&pawn_config, &pawn.pawn_type))
+        .map(|(_, transform, _, vision, _)| {
+            let range = vision.map_or(config.fog_of_war.reveal_radius, |vision| vision.range);
+            (transform.translation.truncate(), range)
+        });
+    let visible = tiles_in_view(&terrain_map, viewers);
+    // Changed from outside when a save is loaded
+    if visible != exploration.visible || exploration.is_changed() {
+        for (word, seen) in exploration.explored.words.iter_mut().zip(&visible.words) {
+            *word |= seen;
+        }
+        exploration.visible = visible;
+        let pixels = fog_pixels(&exploration, config.fog_of_war.remembered_darkness);
+        if let Some(image) = fog_query.get_single().ok().and_then(|sprite| images.get_mut(&sprite.image)) {
+            image.data = pixels;
+        }
+    }
+
+    let mut hidden = Vec::new();
+    for (entity, transform, pawn, _, mut visibility) in pawn_query.iter_mut() {
+        let in_sight = is_controllable(&pawn_config, &pawn.pawn_type)
+            || terrain_map
+                .world_to_tile_coords(transform.translation.x, transform.translation.y)
+                .is_some_and(|(x, y)| exploration.visible.get(x as u32, y as u32));
+        visibility.set_if_neq(if in_sight { Visibility::Inherited } else { Visibility::Hidden });
+        if !in_sight {
+            hidden.push(entity);
+        }
+    }
+    for (attached_to, mut visibility) in attached_query.iter_mut() {
+        if pawn_query.contains(attached_to.0) {
+            visibility.set_if_neq(if hidden.contains(&attached_to.0) { Visibility::Hidden } else { Visibility::Inherited });
+        }
+    }
+    for (dot, mut visibility) in dot_query.iter_mut() {
+        visibility.set_if_neq(if hidden.contains(&dot.pawn) { Visibility::Hidden } else { Visibility::Inherited });
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::pack_hunt::HuntPackAI;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::taming::Owner;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// The pawn a `follow` pawn keeps close to. Whatever makes the bond inserts it - taming
+/// for owners, or a parent for its young - and pawns with a follow behaviour but no
+/// leader pick their owner or pack leader.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct FollowTarget {
+    pub leader: Entity,
+}
+
+fn is_following(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::Follow))
+    )
+}
+
+// System to give following pawns without a leader their owner or pack leader
+pub fn setup_follow_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    follower_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>, Option<&Owner>, Option<&HuntPackAI>), Without<FollowTarget>>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour, owner, pack_ai) in follower_query.iter() {
+        if !is_following(&pawn_config, pawn, current_behavior, active_behaviour) {
+            continue;
+        }
+        let pack_leader = pack_ai.and_then(|pack_ai| pack_ai.leader).filter(|leader| *leader != entity);
+        if let Some(leader) = owner.map(|owner| owner.owner).or(pack_leader) {
+            commands.entity(entity).insert(FollowTarget { leader });
+        }
+    }
+}
+
+/// Following pawns keep within their `follow` distance band of their leader: they path
+/// after it once it gets further than `max_distance`, re-path when it moves more than
+/// `repath_distance` from where they're heading, and stop once within `min_distance`.
+/// A leader that dies or disappears is let go.
+pub fn follow_ai_system(
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    follower_query: Query<(Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &FollowTarget, Option<&ActiveBehaviour>, Option<&PawnTarget>, Has<Swimmer>, Has<PathfindingRequest>)>,
+    leader_query: Query<(&Transform, &Health)>,
+) {
+    for (entity, transform, pawn, size, current_behavior, follow_target, active_behaviour, pawn_target, is_swimmer, has_path_request) in follower_query.iter() {
+        if !is_following(&pawn_config, pawn, current_behavior, active_behaviour) {
+            continue;
+        }
+        let leader_pos = match leader_query.get(follow_target.leader) {
+            Ok((leader_transform, leader_health)) if leader_health.current > 0.0 => leader_transform.translation.truncate(),
+            _ => {
+                commands.entity(entity).remove::<FollowTarget>();
+                continue;
+            }
+        };
+        let follow = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(def) => def.follow_config(),
+            None => continue,
+        };
+
+        let position = transform.translation.truncate();
+        let distance = position.distance(leader_pos);
+        if distance <= follow.min_distance * config.tile_size {
+            // Close enough - wait here
+            if pawn_target.is_some() {
+                commands.entity(entity).remove::<PawnTarget>();
+            }
+            continue;
+        }
+
+        let moving = pawn_target.is_some_and(|target| !target.path.is_empty());
+        let heading_off = pawn_target.is_some_and(|target| {
+            target.target_position.truncate().distance(leader_pos) > follow.repath_distance * config.tile_size
+        });
+        let needs_path = if moving { heading_off } else { distance > follow.max_distance * config.tile_size };
+        if !needs_path || has_path_request {
+            continue;
+        }
+
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+        let current_pos = (position.x, position.y);
+        let goal_pos = (leader_pos.x, leader_pos.y);
+        if terrain_map.are_connected(current_pos, goal_pos, size.value, &movement_configs) {
+            request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, goal_pos, size.value);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use crate::resources::GameConfig;
+use crate::systems::audio::SoundConfig;
+use crate::systems::level_of_detail::LevelOfDetail;
+use crate::systems::pawn::{Pawn, FEET_OFFSET_TILES};
+use crate::systems::world_gen::{GroundConfigs, TerrainMap};
+
+/// Over the ground and its overlays, under anything drawn in depth order
+const FOOTSTEP_PARTICLE_Z: f32 = 55.0;
+/// Width and height of a footstep particle, in pixels
+const FOOTSTEP_PARTICLE_SIZE: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FootstepKind {
+    Dust,   // A slow puff drifting up and out
+    Splash, // Droplets flung out fast that soon fall back
+}
+
+impl FootstepKind {
+    /// Seconds a particle lasts
+    fn lifetime(self) -> f32 {
+        match self {
+            FootstepKind::Dust => 0.6,
+            FootstepKind::Splash => 0.35,
+        }
+    }
+
+    /// How fast particles leave the foot, in tiles a second
+    fn speed(self) -> f32 {
+        match self {
+            FootstepKind::Dust => 0.6,
+            FootstepKind::Splash => 2.0,
+        }
+    }
+
+    /// Tiles a second particles rise, on top of spreading out
+    fn rise(self) -> f32 {
+        match self {
+            FootstepKind::Dust => 0.4,
+            FootstepKind::Splash => 0.8,
+        }
+    }
+
+    /// The colour used when a ground leaves out `color`
+    fn default_color(self) -> [u8; 3] {
+        match self {
+            FootstepKind::Dust => [150, 120, 90],
+            FootstepKind::Splash => [200, 225, 255],
+        }
+    }
+}
+
+/// Particles kicked up by each footstep on a ground, from its `footstep` entry in grounds.yaml
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FootstepEffect {
+    pub kind: FootstepKind,
+    #[serde(default = "default_footstep_particles")]
+    pub particles: u32, // Per footstep
+    #[serde(default)]
+    pub color: Option<[u8; 3]>, // RGB, each kind having its own if left out
+}
+
+fn default_footstep_particles() -> u32 {
+    3
+}
+
+/// A pawn put a foot down, on a tile of this ground
+#[derive(Event, Debug, Clone)]
+pub struct Footstep {
+    pub position: Vec2, // Where its feet are
+    pub terrain_type: usize,
+}
+
+/// How far a pawn has walked since its last footstep
+#[derive(Component, Debug)]
+pub struct Footsteps {
+    pub last_position: Vec2,
+    pub walked: f32, // World units
+}
+
+/// A particle kicked up by a footstep, spreading out and fading until it's gone
+#[derive(Component, Debug)]
+pub struct FootstepParticle {
+    pub velocity: Vec2, // World units a second
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+/// A footstep every footstep_distance a pawn walks, so the faster it goes the more it makes.
+/// Pawns hidden in the fog make none.
+pub fn track_footsteps(
+    config: Res<GameConfig>,
+    sound_config: Res<SoundConfig>,
+    terrain_map: Option<Res<TerrainMap>>,
+    mut commands: Commands,
+    mut footstep_events: EventWriter<Footstep>,
+    mut pawn_query: Query<(Entity, &Transform, Option<&Visibility>, Option<&mut Footsteps>), With<Pawn>>,
+) {
+    let Some(terrain_map) = terrain_map else {
+        return;
+    };
+    let step = sound_config.footstep_distance * config.tile_size;
+
+    for (entity, transform, visibility, footsteps) in pawn_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let Some(mut footsteps) = footsteps else {
+            commands.entity(entity).insert(Footsteps { last_position: position, walked: 0.0 });
+            continue;
+        };
+        footsteps.walked += position.distance(footsteps.last_position);
+        footsteps.last_position = position;
+        if footsteps.walked < step {
+            continue;
+        }
+        footsteps.walked %= step;
+        if visibility == Some(&Visibility::Hidden) {
+            continue;
+        }
+        if let Some((x, y)) = terrain_map.world_to_tile_coords(position.x, position.y) {
+            footstep_events.send(Footstep {
+                position: position - Vec2::new(0.0, FEET_OFFSET_TILES * config.tile_size),
+                terrain_type: terrain_map.tiles[x as usize][y as usize],
+            });
+        }
+    }
+}
+
+/// Kick up particles for footsteps on grounds with a `footstep` effect, unless zoomed out too far
+/// to see them. They're spread evenly around the foot, turned a little each step, so drawing them
+/// takes nothing from the simulation's randomness.
+pub fn spawn_footstep_particles(
+    config: Res<GameConfig>,
+    level_of_detail: Res<LevelOfDetail>,
+    ground_configs: Option<Res<GroundConfigs>>,
+    mut commands: Commands,
+    mut footstep_events: EventReader<Footstep>,
+    mut steps_taken: Local<u32>,
+) {
+    let Some(ground_configs) = ground_configs else {
+        footstep_events.clear();
+        return;
+    };
+    if level_of_detail.hides_overlays() {
+        footstep_events.clear();
+        return;
+    }
+    for event in footstep_events.read() {
+        let effect = ground_configs.name_of(event.terrain_type)
+            .and_then(|ground| ground_configs.configs.get(ground))
+            .and_then(|ground| ground.footstep.as_ref());
+        let Some(effect) = effect else {
+            continue;
+        };
+        *steps_taken += 1;
+        let turn = (*steps_taken as f32 * 0.618_034).fract();
+        let [r, g, b] = effect.color.unwrap_or_else(|| effect.kind.default_color());
+        for index in 0..effect.particles {
+            let angle = (index as f32 / effect.particles as f32 + turn) * TAU;
+            let spread = Vec2::from_angle(angle) * effect.kind.speed();
+            commands.spawn((
+                Sprite::from_color(Color::srgb_u8(r, g, b), Vec2::splat(FOOTSTEP_PARTICLE_SIZE)),
+                Transform::from_translation(event.position.extend(FOOTSTEP_PARTICLE_Z)),
+                FootstepParticle {
+                    velocity: (spread + Vec2::new(0.0, effect.kind.rise())) * config.tile_size,
+                    age: 0.0,
+                    lifetime: effect.kind.lifetime(),
+                },
+            ));
+        }
+    }
+}
+
+/// Move footstep particles along, slowing as they go, and fade them out
+pub fn update_footstep_particles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut FootstepParticle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut transform, mut sprite) in particle_query.iter_mut() {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let left = 1.0 - particle.age / particle.lifetime;
+        transform.translation += (particle.velocity * left * delta).extend(0.0);
+        sprite.color.set_alpha(left);
+    }
+}
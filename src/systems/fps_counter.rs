@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, DiagnosticsStore};
+use crate::resources::GameConfig;
+use crate::systems::seasons::Seasons;
+use crate::systems::weather::Weather;
+use crate::systems::world_clock::WorldClock;
 
 #[derive(Component)]
 pub struct FpsText;
@@ -23,15 +27,23 @@ pub fn setup_fps_counter(mut commands: Commands) {
 }
 
 pub fn update_fps_counter(
+    config: Res<GameConfig>,
     diagnostics: Res<DiagnosticsStore>,
-    mut fps_text_query: Query<&mut Text, With<FpsText>>,
+    world_clock: Res<WorldClock>,
+    weather: Res<Weather>,
+    seasons: Res<Seasons>,
+    mut fps_text_query: Query<(&mut Text, &mut Visibility), With<FpsText>>,
 ) {
-    for mut text in &mut fps_text_query {
+    for (mut text, mut visibility) in &mut fps_text_query {
+        // show_fps can be switched from the settings menu, so the counter is always there
+        visibility.set_if_neq(if config.show_fps { Visibility::Inherited } else { Visibility::Hidden });
         if let Some(fps) = diagnostics
             .get(&FrameTimeDiagnosticsPlugin::FPS)
             .and_then(|fps| fps.smoothed())
         {
-            text.0 = format!("FPS: {:.1}", fps);
+            let (hour, minute) = world_clock.hour_minute();
+            let season = seasons.current.unwrap_or_default().label();
+            text.0 = format!("FPS: {:.1} | {} day {} {:02}:{:02} | {}", fps, season, world_clock.day, hour, minute, weather.kind.label());
         }
     }
 }
\ No newline at end of file
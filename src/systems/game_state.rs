@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::ScheduleLabel;
+use crate::systems::sim_tick::SimulationSpeed;
+
+/// Where the game is: on the main menu before there's a world, playing it, or with the pause
+/// menu open over it
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+}
+
+/// Groups of gameplay systems, each with a run condition on the game state
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Simulation, // Plays the world out and takes the player's orders - only while Playing
+    World,      // Draws or reports on the world - whenever there is one, paused or not
+}
+
+/// Whether a world has been made or loaded, i.e. the main menu has been left
+pub fn world_loaded(state: Res<State<GameState>>) -> bool {
+    *state.get() != GameState::MainMenu
+}
+
+/// Game state, its system sets and the clock stopping outside of play
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>();
+        for schedule in [FixedFirst.intern(), FixedUpdate.intern(), Update.intern()] {
+            app.configure_sets(schedule, (
+                GameSet::Simulation.run_if(in_state(GameState::Playing)),
+                GameSet::World.run_if(world_loaded),
+            ));
+        }
+        app.add_systems(OnEnter(GameState::MainMenu), stop_virtual_time)
+            .add_systems(OnEnter(GameState::Paused), stop_virtual_time)
+            .add_systems(OnEnter(GameState::Playing), resume_virtual_time);
+    }
+}
+
+/// Nothing timed by the virtual clock moves outside of play, including anything on FixedUpdate
+fn stop_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Back in play, the clock runs again unless the simulation itself was paused
+fn resume_virtual_time(speed: Res<SimulationSpeed>, mut time: ResMut<Time<Virtual>>) {
+    if !speed.paused {
+        time.unpause();
+    }
+}
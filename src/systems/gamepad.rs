@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::resources::GameConfig;
+use crate::systems::camera::{CameraController, CameraFollow};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
+
+/// How fast the triggers zoom at full pull, matching the +/- keys
+const TRIGGER_ZOOM_SPEED: f32 = 2.0;
+
+/// Gamepad feel, from the `gamepad` section of settings.yaml
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GamepadTuning {
+    pub stick_dead_zone: f32,   // How far the stick can drift from centre before the camera moves (0-1)
+    pub trigger_dead_zone: f32, // How far a trigger can be pulled before it zooms (0-1)
+    pub pan_sensitivity: f32,   // Camera speed at full tilt, as a multiple of the camera's movement_speed
+    pub zoom_sensitivity: f32,  // Zoom speed at full pull, as a multiple of the keyboard zoom
+}
+
+impl Default for GamepadTuning {
+    fn default() -> Self {
+        Self {
+            stick_dead_zone: 0.15,
+            trigger_dead_zone: 0.1,
+            pan_sensitivity: 1.0,
+            zoom_sensitivity: 1.0,
+        }
+    }
+}
+
+/// A stick's position with the dead zone cut out of the middle, rescaled so the edge of the
+/// dead zone reads as 0 and full tilt still reads as 1
+pub fn apply_stick_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let tilt = stick.length();
+    if tilt <= dead_zone || dead_zone >= 1.0 {
+        return Vec2::ZERO;
+    }
+    stick / tilt * ((tilt - dead_zone) / (1.0 - dead_zone)).min(1.0)
+}
+
+/// A trigger's pull with the dead zone cut out, rescaled the same way as the sticks
+pub fn apply_trigger_dead_zone(pull: f32, dead_zone: f32) -> f32 {
+    if pull <= dead_zone || dead_zone >= 1.0 {
+        return 0.0;
+    }
+    ((pull - dead_zone) / (1.0 - dead_zone)).min(1.0)
+}
+
+/// The left stick pans the camera, the right trigger zooms in and the left zooms out
+pub fn gamepad_camera(
+    time: Res<Time<Real>>, // The camera keeps moving while the game is paused
+    config: Res<GameConfig>,
+    mut follow: ResMut<CameraFollow>,
+    gamepads: Query<&Gamepad>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), (With<Camera>, With<CameraController>)>,
+) {
+    let settings = &config.gamepad;
+    for gamepad in gamepads.iter() {
+        let pan = apply_stick_dead_zone(gamepad.left_stick(), settings.stick_dead_zone);
+        let pull = |trigger: GamepadButton| apply_trigger_dead_zone(gamepad.get(trigger).unwrap_or(0.0), settings.trigger_dead_zone);
+        let zoom = pull(GamepadButton::LeftTrigger2) - pull(GamepadButton::RightTrigger2);
+        if pan != Vec2::ZERO {
+            follow.target = None;
+        }
+
+        for (mut transform, mut projection) in camera_query.iter_mut() {
+            transform.translation += (pan * config.camera_speed * settings.pan_sensitivity * time.delta_secs()).extend(0.0);
+            if zoom != 0.0 {
+                projection.scale *= 1.0 + zoom * TRIGGER_ZOOM_SPEED * settings.zoom_sensitivity * time.delta_secs();
+                projection.scale = projection.scale.clamp(config.zoom_min, config.zoom_max);
+            }
+        }
+    }
+}
+
+/// A selects the pawn in the middle of the screen, or clears the selection when there isn't
+/// one, like a click there; B clears the selection
+pub fn gamepad_selection(
+    gamepads: Query<&Gamepad>,
+    mut player_commands: ResMut<PlayerCommands>,
+    camera_query: Query<&Transform, (With<Camera>, With<CameraController>)>,
+) {
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::South) {
+            if let Ok(camera_transform) = camera_query.get_single() {
+                let center = camera_transform.translation.truncate();
+                player_commands.give(PlayerCommand::Select { from: center.into(), to: center.into() });
+            }
+        } else if gamepad.just_pressed(GamepadButton::East) {
+            player_commands.give(PlayerCommand::ClearSelection);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::growth::PawnFed;
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask};
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Endurance, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::sim_tick::SimTick;
+use crate::systems::vegetation::VegetationGrowth;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, TerrainType, GroundConfigs};
+
+/// Seconds between a grazer's searches for an edible tile
+pub const GRAZE_SEARCH_INTERVAL: f32 = 1.0;
+/// How far (in tiles) grazers look for something to eat
+pub const GRAZE_SEARCH_RADIUS_TILES: i32 = 8;
+
+#[derive(Component)]
+pub struct GrazingAI {
+    pub search_timer: f32,
+    pub eating: Option<((u32, u32), f32)>, // Tile being eaten and seconds left
+}
+
+impl GrazingAI {
+    pub fn new() -> Self {
+        Self {
+            search_timer: GRAZE_SEARCH_INTERVAL, // Look straight away
+            eating: None,
+        }
+    }
+}
+
+fn is_grazing(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::Eat))
+    )
+}
+
+/// Ground types a pawn grazes on - unknown ground names are ignored
+fn edible_grounds(pawn_config: &PawnConfig, ground_configs: &GroundConfigs, pawn_type: &str) -> Vec<TerrainType> {
+    pawn_config
+        .get_pawn_definition(pawn_type)
+        .map(|def| def.eats.grounds.iter().filter_map(|ground| ground_configs.terrain_mapping.get(ground).copied()).collect())
+        .unwrap_or_default()
+}
+
+/// Nearest edible tile the pawn can stand on and reach
+pub fn find_nearest_edible_tile(
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+    edible: &[TerrainType],
+    position: Vec2,
+    size: f32,
+) -> Option<(i32, i32)> {
+    terrain_map.find_nearest_reachable_tile((position.x, position.y), GRAZE_SEARCH_RADIUS_TILES, size, ground_configs, |(x, y)| {
+        edible.contains(&terrain_map.tiles[x as usize][y as usize])
+    })
+}
+
+// System to add GrazingAI component to pawns that eat grounds
+pub fn setup_grazing_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    grazer_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), Without<GrazingAI>>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in grazer_query.iter() {
+        let eats_grounds = pawn_config
+            .get_pawn_definition(&pawn.pawn_type)
+            .is_some_and(|def| !def.eats.grounds.is_empty());
+        if eats_grounds && is_grazing(&pawn_config, pawn, current_behavior, active_behaviour) {
+            commands.entity(entity).insert(GrazingAI::new());
+        }
+    }
+}
+
+/// Grazers walk to the nearest edible tile, eat it for a while to restore endurance and,
+/// if configured, leave it eaten down to another ground until the vegetation grows back
+pub fn grazing_ai_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    ground_configs: Res<GroundConfigs>,
+    sim_tick: Res<SimTick>,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut terrain_changes: ResMut<TerrainChanges>,
+    mut vegetation: ResMut<VegetationGrowth>,
+    mut fed_events: EventWriter<PawnFed>,
+    mut commands: Commands,
+    mut grazer_query: Query<(
+        Entity, &Transform, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>, &Size, &mut GrazingAI, &mut Endurance,
+        Has<PawnTarget>, Has<PathfindingRequest>, Has<PathfindingTask>, Has<Swimmer>,
+    )>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, transform, pawn, current_behavior, active_behaviour, size, mut grazing_ai, mut endurance,
+         has_target, has_request, has_task, is_swimmer) in grazer_query.iter_mut() {
+        if !is_grazing(&pawn_config, pawn, current_behavior, active_behaviour) {
+            grazing_ai.eating = None;
+            continue;
+        }
+
+        let graze = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(def) => def.eats.graze_config(),
+            None => continue,
+        };
+        let edible = edible_grounds(&pawn_config, &ground_configs, &pawn.pawn_type);
+        let position = transform.translation.truncate();
+        let current_tile = terrain_map
+            .world_to_tile_coords(position.x, position.y)
+            .map(|(x, y)| (x as u32, y as u32));
+
+        if let Some((tile, remaining)) = grazing_ai.eating {
+            // Pushed or dragged off the tile - start over
+            if current_tile != Some(tile) {
+                grazing_ai.eating = None;
+                continue;
+            }
+
+            let remaining = remaining - delta;
+            if remaining > 0.0 {
+                grazing_ai.eating = Some((tile, remaining));
+                continue;
+            }
+            grazing_ai.eating = None;
+
+            let eaten = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+            if !edible.contains(&eaten) {
+                continue; // Changed under us while eating
+            }
+            endurance.current = (endurance.current + graze.endurance).min(endurance.max);
+            fed_events.send(PawnFed { pawn: entity });
+
+            if let Some(leaves) = graze.leaves.as_ref().and_then(|leaves| ground_configs.terrain_mapping.get(leaves).copied()) {
+                vegetation.clear_tile(tile, leaves, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick);
+            }
+            println!("{} grazed tile ({}, {})", pawn.pawn_type, tile.0, tile.1);
+            continue;
+        }
+
+        // Still on the way somewhere
+        if has_target || has_request || has_task {
+            continue;
+        }
+
+        if let Some(tile) = current_tile {
+            if edible.contains(&terrain_map.tiles[tile.0 as usize][tile.1 as usize]) {
+                grazing_ai.eating = Some((tile, graze.duration));
+                continue;
+            }
+        }
+
+        grazing_ai.search_timer += delta;
+        if grazing_ai.search_timer < GRAZE_SEARCH_INTERVAL {
+            continue;
+        }
+        grazing_ai.search_timer = 0.0;
+
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+        if let Some((tile_x, tile_y)) = find_nearest_edible_tile(&terrain_map, &movement_configs, &edible, position, size.value) {
+            let target_pos = terrain_map.tile_to_world_coords(tile_x, tile_y);
+            commands.entity(entity).insert(PathfindingRequest::new((position.x, position.y), target_pos, size.value));
+        }
+    }
+}
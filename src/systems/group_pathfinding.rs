@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask, PathfindingCompleted, PathfindingFailed, spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding};
+use crate::systems::pawn::{PawnTarget, Size, Swimmer};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// Distance between neighbouring formation slots, in tiles
+pub const DEFAULT_FORMATION_SPACING_TILES: f32 = 1.0;
+/// Pawns further than this (in tiles) from the leader walk their own path rather than fall in behind it
+pub const MAX_FORMATION_GAP_TILES: f32 = 4.0;
+/// Formation members closer than this (in tiles, per unit of size) push each other apart
+pub const AVOIDANCE_RADIUS_TILES: f32 = 0.75;
+/// How fast (in tiles per second) overlapping formation members slide apart
+pub const AVOIDANCE_SPEED_TILES: f32 = 2.0;
+
+/// Pawn whose path is shared with its followers instead of each of them running its own search
+#[derive(Component, Debug, Clone)]
+pub struct FormationLeader {
+    pub followers: Vec<Entity>,
+    pub spacing: f32, // In tiles
+}
+
+/// Pawn that walks the leader's path shifted by its formation slot
+#[derive(Component, Debug, Clone)]
+pub struct FormationFollower {
+    pub leader: Entity,
+    pub offset: Vec2, // In tiles - x is to the right of the leader's heading, y is behind it
+}
+
+/// Wedge formation: slots fill rows behind the leader, alternating left and right
+pub fn formation_offset(slot: usize) -> Vec2 {
+    let row = (slot / 2 + 1) as f32;
+    let side = if slot.is_multiple_of(2) { -1.0 } else { 1.0 };
+    Vec2::new(side * row, row)
+}
+
+/// Whether a pawn at `follower` can walk a copy of the path of a leader at `leader`: close enough
+/// to fall in behind it, no bigger so the path is wide enough, and not a walker behind a swimmer.
+/// Both are `(position, size, swims)`.
+pub fn can_follow(follower: (Vec2, f32, bool), leader: (Vec2, f32, bool), tile_size: f32) -> bool {
+    let (position, size, swims) = follower;
+    let (leader_position, leader_size, leader_swims) = leader;
+    position.distance(leader_position) <= MAX_FORMATION_GAP_TILES * tile_size
+        && size <= leader_size
+        && (swims || !leader_swims)
+}
+
+/// Request one path for a whole group: the leader runs the search and every follower
+/// gets the same route shifted to its formation slot once the leader's path comes back
+pub fn request_group_pathfinding(
+    commands: &mut Commands,
+    leader: Entity,
+    followers: &[Entity],
+    request: PathfindingRequest,
+) {
+    if let Some(mut leader_commands) = commands.get_entity(leader) {
+        leader_commands.insert((
+            request,
+            FormationLeader {
+                followers: followers.to_vec(),
+                spacing: DEFAULT_FORMATION_SPACING_TILES,
+            },
+        ));
+    }
+
+    for (slot, &follower) in followers.iter().enumerate() {
+        if let Some(mut follower_commands) = commands.get_entity(follower) {
+            follower_commands.insert(FormationFollower {
+                leader,
+                offset: formation_offset(slot),
+            });
+        }
+    }
+}
+
+/// Shift the leader's path into a follower's slot, oriented along the leader's heading at each waypoint.
+/// Waypoints whose slot position is blocked fall back to the leader's own waypoint.
+pub fn formation_path(
+    leader_path: &[Vec3],
+    offset: Vec2,
+    spacing: f32,
+    size: f32,
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+) -> Vec<(f32, f32)> {
+    let offset = offset * spacing * terrain_map.tile_size;
+    let mut path: Vec<(f32, f32)> = Vec::with_capacity(leader_path.len());
+
+    for (i, waypoint) in leader_path.iter().enumerate() {
+        let waypoint = waypoint.truncate();
+        let heading = if i > 0 {
+            waypoint - leader_path[i - 1].truncate()
+        } else if leader_path.len() > 1 {
+            leader_path[1].truncate() - waypoint
+        } else {
+            Vec2::ZERO
+        };
+        let heading = heading.try_normalize().unwrap_or(Vec2::Y);
+        let right = Vec2::new(heading.y, -heading.x);
+        let slot = waypoint + right * offset.x - heading * offset.y;
+
+        let slot_is_clear = terrain_map.is_position_passable_for_size(slot.x, slot.y, size, ground_configs)
+            && path.last().is_none_or(|&previous| terrain_map.is_path_segment_clear(previous, (slot.x, slot.y), size, ground_configs));
+        if slot_is_clear {
+            path.push((slot.x, slot.y));
+        } else {
+            path.push((waypoint.x, waypoint.y));
+        }
+    }
+
+    path
+}
+
+/// Once a leader's path is found, hand every follower its offset copy of it
+pub fn assign_formation_targets(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut completed_events: EventReader<PathfindingCompleted>,
+    leader_query: Query<(&FormationLeader, &PawnTarget)>,
+    follower_query: Query<(&FormationFollower, &Size, Has<Swimmer>)>,
+) {
+    for event in completed_events.read() {
+        let (formation, leader_target) = match leader_query.get(event.entity) {
+            Ok(leader) => leader,
+            Err(_) => continue,
+        };
+
+        for &follower in &formation.followers {
+            if let Ok((follower_slot, size, is_swimmer)) = follower_query.get(follower) {
+                // Followers that have since joined another group are left alone
+                if follower_slot.leader != event.entity {
+                    continue;
+                }
+
+                let path = formation_path(
+                    &leader_target.path,
+                    follower_slot.offset,
+                    formation.spacing,
+                    size.value,
+                    &terrain_map,
+                    &ground_configs.for_movement(is_swimmer),
+                );
+                let mut pawn_target = PawnTarget::new(leader_target.target_position);
+                pawn_target.set_path(path);
+
+                commands.entity(follower)
+                    .remove::<(PathfindingRequest, PathfindingTask)>()
+                    .insert(pawn_target);
+            }
+        }
+    }
+}
+
+/// Break up formations whose leader can't reach the goal, has got there or no longer exists.
+/// A formation only lasts the one trip, so later paths of the leader don't drag its followers along.
+pub fn disband_formations(
+    mut commands: Commands,
+    mut failed_events: EventReader<PathfindingFailed>,
+    leader_query: Query<(Entity, &FormationLeader, Has<PawnTarget>, Has<PathfindingRequest>, Has<PathfindingTask>)>,
+    follower_query: Query<(Entity, &FormationFollower)>,
+) {
+    let failed: Vec<Entity> = failed_events.read().map(|event| event.entity).collect();
+
+    for (leader, formation, travelling, requested, searching) in leader_query.iter() {
+        if !failed.contains(&leader) && (travelling || requested || searching) {
+            continue;
+        }
+        for &follower in &formation.followers {
+            if follower_query.get(follower).is_ok_and(|(_, slot)| slot.leader == leader) {
+                commands.entity(follower).remove::<FormationFollower>();
+            }
+        }
+        commands.entity(leader).remove::<FormationLeader>();
+    }
+
+    for (follower, slot) in follower_query.iter() {
+        if !leader_query.contains(slot.leader) {
+            commands.entity(follower).remove::<FormationFollower>();
+        }
+    }
+}
+
+/// Local avoidance: followers that crowd their leader or each other slide apart, as long as
+/// the terrain lets them
+pub fn formation_avoidance_system(
+    time: Res<Time>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    leader_query: Query<(Entity, &Transform), (With<FormationLeader>, Without<FormationFollower>)>,
+    mut follower_query: Query<(Entity, &mut Transform, &FormationFollower, &Size, Has<Swimmer>)>,
+) {
+    // Positions as of the start of the frame, so the result doesn't depend on iteration order
+    let members: Vec<(Entity, Entity, Vec2)> = follower_query
+        .iter()
+        .map(|(entity, transform, slot, _, _)| (entity, slot.leader, transform.translation.truncate()))
+        .chain(leader_query.iter().map(|(entity, transform)| (entity, entity, transform.translation.truncate())))
+        .collect();
+
+    for (entity, mut transform, slot, size, is_swimmer) in follower_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let radius = AVOIDANCE_RADIUS_TILES * terrain_map.tile_size * size.value.max(1.0);
+        let mut push = Vec2::ZERO;
+
+        for &(other, other_leader, other_position) in &members {
+            if other == entity || other_leader != slot.leader {
+                continue;
+            }
+            let away = position - other_position;
+            let distance = away.length();
+            if distance >= radius {
+                continue;
+            }
+            // Perfectly stacked pawns split along x, ordered by entity so they go opposite ways
+            let direction = if distance > f32::EPSILON {
+                away / distance
+            } else if entity.index() > other.index() {
+                Vec2::X
+            } else {
+                Vec2::NEG_X
+            };
+            push += direction * (radius - distance) / radius;
+        }
+
+        if push == Vec2::ZERO {
+            continue;
+        }
+
+        let step = push.clamp_length_max(1.0) * AVOIDANCE_SPEED_TILES * terrain_map.tile_size * time.delta_secs();
+        let next = position + step;
+        if terrain_map.is_position_passable_for_size(next.x, next.y, size.value, &ground_configs.for_movement(is_swimmer)) {
+            transform.translation.x = next.x;
+            transform.translation.y = next.y;
+        }
+    }
+}
+
+/// Group pathfinding: shared leader searches, formation targets for followers and local avoidance
+pub struct GroupPathfindingPlugin {
+    pub schedule: InternedScheduleLabel, // Wherever the pathfinding systems run - FixedUpdate in the game
+}
+
+impl Default for GroupPathfindingPlugin {
+    fn default() -> Self {
+        Self { schedule: Update.intern() }
+    }
+}
+
+impl Plugin for GroupPathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.schedule, (
+            assign_formation_targets
+                .after(spawn_cached_pathfinding_tasks)
+                .after(handle_completed_cached_pathfinding),
+            disband_formations.after(assign_formation_targets),
+            formation_avoidance_system,
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::systems::aging::Age;
+use crate::systems::pawn::{Pawn, Size, Health};
+use crate::systems::pawn_config::PawnConfig;
+
+/// How much bigger than its species' usual size a pawn has grown from feeding. Its size (and so
+/// the paths it can take), sprite, strength and max health are all scaled by it.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Grown {
+    pub scale: f32,
+}
+
+/// A pawn finished eating something - a kill, a grazed tile or food handed to it
+#[derive(Event, Debug, Clone)]
+pub struct PawnFed {
+    pub pawn: Entity,
+}
+
+/// Strength multiplier for a pawn that may have grown
+pub fn growth_scale(grown: Option<&Grown>) -> f32 {
+    grown.map_or(1.0, |grown| grown.scale)
+}
+
+/// Each feeding of a pawn with a `growth` entry closes `per_feeding` of the gap between its
+/// size and `max_size`. Max health grows along with it, as does current health by as much.
+pub fn growth_system(
+    pawn_config: Res<PawnConfig>,
+    mut fed_events: EventReader<PawnFed>,
+    mut commands: Commands,
+    mut pawn_query: Query<(&Pawn, &mut Size, &mut Health, &mut Transform, Option<&Age>, Option<&mut Grown>)>,
+) {
+    for event in fed_events.read() {
+        let (pawn, mut size, mut health, mut transform, age, grown) = match pawn_query.get_mut(event.pawn) {
+            Ok(pawn) => pawn,
+            Err(_) => continue,
+        };
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let growth = match definition.growth.as_ref() {
+            Some(growth) => growth,
+            None => continue,
+        };
+        if definition.size <= 0.0 {
+            continue;
+        }
+
+        let max_scale = (growth.max_size / definition.size).max(1.0);
+        let scale = growth_scale(grown.as_deref());
+        let new_scale = (scale + (max_scale - scale) * growth.per_feeding).min(max_scale);
+        if new_scale <= scale {
+            continue;
+        }
+
+        size.value = definition.size_for(age.map(|age| age.stage)) * new_scale;
+        let max_health = definition.max_health as f32 * new_scale;
+        health.current += max_health - health.max;
+        health.max = max_health;
+        transform.scale = Vec3::new(new_scale, new_scale, transform.scale.z);
+        match grown {
+            Some(mut grown) => grown.scale = new_scale,
+            None => {
+                commands.entity(event.pawn).insert(Grown { scale: new_scale });
+            }
+        }
+        println!("{} grew to size {:.2}", pawn.pawn_type, size.value);
+    }
+}
@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::control::HoldPosition;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, HerdConfig, BehaviourConfig, BehaviourType};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::sim_rng::SimRng;
+
+/// Herd-mates closer than this many tiles push each other apart
+pub const HERD_SEPARATION_TILES: f32 = 1.5;
+
+#[derive(Component)]
+pub struct HerdingAI {
+    pub next_move_time: f32,
+    pub heading: Vec2, // Way the pawn last moved, for its herd-mates to line up with
+    pub last_position: Option<Vec2>,
+}
+
+impl HerdingAI {
+    pub fn new() -> Self {
+        Self {
+            next_move_time: 0.0,
+            heading: Vec2::ZERO,
+            last_position: None,
+        }
+    }
+
+    pub fn schedule_next_move(&mut self, min_interval: f32, max_interval: f32, rng: &mut impl Rng) {
+        self.next_move_time = rng.gen_range(min_interval..=max_interval.max(min_interval));
+    }
+}
+
+fn is_herding(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::Herd))
+    )
+}
+
+/// Direction for a herd pawn's next move: its random `wander` direction plus the weighted
+/// cohesion, alignment and separation pulls of its herd-mates' positions and headings
+pub fn herd_direction(position: Vec2, wander: Vec2, herd_mates: &[(Vec2, Vec2)], herd: &HerdConfig, tile_size: f32) -> Vec2 {
+    if herd_mates.is_empty() {
+        return wander;
+    }
+
+    let count = herd_mates.len() as f32;
+    let centroid = herd_mates.iter().map(|(mate_position, _)| *mate_position).sum::<Vec2>() / count;
+    let cohesion = ((centroid - position) / (herd.radius.max(1) as f32 * tile_size)).clamp_length_max(1.0);
+    let alignment = (herd_mates.iter().map(|(_, heading)| *heading).sum::<Vec2>() / count).clamp_length_max(1.0);
+
+    let separation_distance = HERD_SEPARATION_TILES * tile_size;
+    let separation = herd_mates
+        .iter()
+        .filter_map(|(mate_position, _)| {
+            let away = position - *mate_position;
+            let distance = away.length();
+            // Herd-mates right on top of each other have no way apart, so let the wander pick one
+            (distance > 0.0 && distance < separation_distance).then(|| away / distance * (1.0 - distance / separation_distance))
+        })
+        .sum::<Vec2>()
+        .clamp_length_max(1.0);
+
+    (wander + cohesion * herd.cohesion + alignment * herd.alignment + separation * herd.separation)
+        .try_normalize()
+        .unwrap_or(wander)
+}
+
+// System to add HerdingAI component to pawns with herd behavior
+pub fn setup_herding_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    mut rng: ResMut<SimRng>,
+    herd_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), Without<HerdingAI>>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in herd_query.iter() {
+        if is_herding(&pawn_config, pawn, current_behavior, active_behaviour) {
+            let mut ai = HerdingAI::new();
+            if let Some(definition) = pawn_config.get_pawn_definition(&pawn.pawn_type) {
+                let herd = definition.herd_config();
+                ai.schedule_next_move(herd.move_interval_min, herd.move_interval_max, &mut *rng);
+            }
+            commands.entity(entity).insert(ai);
+        }
+    }
+}
+
+/// Herd pawns wander like wandering pawns, but steer each move by the herd-mates of their
+/// own type within their herd radius, so the herd drifts around together
+pub fn herding_ai_system(
+    time: Res<Time>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    mut ticker: Local<AiTicker>,
+    mut rng: ResMut<SimRng>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut herd_query: Query<(
+        Entity, &Transform, &Pawn, &Size, &CurrentBehavior, &mut HerdingAI, Option<&ActiveBehaviour>,
+        Has<Swimmer>, Has<PawnTarget>, Has<PathfindingRequest>, Has<HoldPosition>,
+    )>,
+) {
+    // Track which way everyone is heading and take a snapshot of the herds
+    let mut herders: Vec<(Entity, Vec2, String, Vec2)> = Vec::new();
+    for (entity, transform, pawn, _, current_behavior, mut herd_ai, active_behaviour, _, _, _, _) in herd_query.iter_mut() {
+        let position = transform.translation.truncate();
+        if let Some(last_position) = herd_ai.last_position {
+            if let Some(direction) = (position - last_position).try_normalize() {
+                herd_ai.heading = direction;
+            }
+        }
+        herd_ai.last_position = Some(position);
+        if is_herding(&pawn_config, pawn, current_behavior, active_behaviour) {
+            herders.push((entity, position, pawn.pawn_type.clone(), herd_ai.heading));
+        }
+    }
+
+    let rng = &mut *rng;
+    let interval = config.ai_ticks.wander;
+    ticker.advance(time.delta_secs());
+
+    for (entity, transform, pawn, size, current_behavior, mut herd_ai, active_behaviour, is_swimmer, has_target, has_request, holding) in herd_query.iter_mut() {
+        // Pawns told to hold position still count towards their herd, they just don't move with it
+        if has_target || has_request || holding || !is_herding(&pawn_config, pawn, current_behavior, active_behaviour) || !ticker.is_due(entity, interval) {
+            continue;
+        }
+
+        herd_ai.next_move_time -= time.delta_secs().max(interval);
+        if herd_ai.next_move_time > 0.0 {
+            continue;
+        }
+
+        let herd = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition.herd_config(),
+            None => continue,
+        };
+        let position = transform.translation.truncate();
+        let herd_radius = herd.radius as f32 * config.tile_size;
+        let herd_mates: Vec<(Vec2, Vec2)> = herders
+            .iter()
+            .filter(|(other, other_position, other_type, _)| {
+                *other != entity && *other_type == pawn.pawn_type && position.distance(*other_position) <= herd_radius
+            })
+            .map(|(_, other_position, _, heading)| (*other_position, *heading))
+            .collect();
+
+        let current_pos = (position.x, position.y);
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+        let move_range_pixels = herd.move_range as f32 * config.tile_size;
+        let min_distance = config.tile_size;
+        let max_distance = move_range_pixels.max(min_distance + 1.0);
+
+        // A few tries at a reachable spot, each with a fresh random pull
+        for _ in 0..10 {
+            let wander = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+            let direction = herd_direction(position, wander, &herd_mates, &herd, config.tile_size);
+            let target = position + direction * rng.gen_range(min_distance..max_distance);
+            let target_pos = (target.x, target.y);
+
+            if terrain_map.is_position_passable_for_size(target_pos.0, target_pos.1, size.value, &movement_configs)
+                && terrain_map.are_connected(current_pos, target_pos, size.value, &movement_configs)
+            {
+                request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, target_pos, size.value);
+                break;
+            }
+        }
+
+        herd_ai.schedule_next_move(herd.move_interval_min, herd.move_interval_max, rng);
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::systems::pawn::Pawn;
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, TerrainType, GroundConfigs};
+
+/// Accumulated influence at which a tile converts to the infusing terrain
+pub const INFUSION_THRESHOLD: f32 = 100.0;
+/// Influence lost per second on every tile, so passing visitors leave no lasting mark
+pub const INFLUENCE_DECAY_PER_SEC: f32 = 0.5;
+
+/// Per-tile influence accumulated by resident creatures, keyed by the terrain they push towards
+#[derive(Resource, Default)]
+pub struct TerrainInfluence {
+    tiles: HashMap<(u32, u32), HashMap<TerrainType, f32>>,
+}
+
+impl TerrainInfluence {
+    pub fn influence(&self, tile: (u32, u32), terrain: TerrainType) -> f32 {
+        self.tiles
+            .get(&tile)
+            .and_then(|influences| influences.get(&terrain))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Add influence towards a terrain; returns true once the tile crosses the threshold.
+    /// A converted tile starts from scratch so competing influences don't carry over.
+    pub fn accumulate(&mut self, tile: (u32, u32), terrain: TerrainType, amount: f32) -> bool {
+        let influences = self.tiles.entry(tile).or_default();
+        let value = influences.entry(terrain).or_insert(0.0);
+        *value += amount;
+
+        if *value >= INFUSION_THRESHOLD {
+            self.tiles.remove(&tile);
+            return true;
+        }
+        false
+    }
+
+    pub fn decay(&mut self, amount: f32) {
+        self.tiles.retain(|_, influences| {
+            influences.retain(|_, value| {
+                *value -= amount;
+                *value > 0.0
+            });
+            !influences.is_empty()
+        });
+    }
+}
+
+/// Let infusing pawns (e.g. elementals) slowly convert the tiles they occupy
+pub fn accumulate_terrain_influence(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    ground_configs: Res<GroundConfigs>,
+    mut influence: ResMut<TerrainInfluence>,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut terrain_changes: ResMut<TerrainChanges>,
+    pawn_query: Query<(&Transform, &Pawn)>,
+) {
+    let delta = time.delta_secs();
+    influence.decay(INFLUENCE_DECAY_PER_SEC * delta);
+
+    for (transform, pawn) in pawn_query.iter() {
+        let infusion = match pawn_config.get_pawn_definition(&pawn.pawn_type).and_then(|def| def.infuses.as_ref()) {
+            Some(infusion) => infusion,
+            None => continue,
+        };
+
+        // Unknown ground names are ignored rather than converting to a bogus terrain index
+        let terrain_type = match ground_configs.terrain_mapping.get(&infusion.terrain) {
+            Some(&terrain_type) => terrain_type,
+            None => continue,
+        };
+
+        if let Some((tile_x, tile_y)) = terrain_map.world_to_tile_coords(transform.translation.x, transform.translation.y) {
+            let tile = (tile_x as u32, tile_y as u32);
+
+            if terrain_map.tiles[tile.0 as usize][tile.1 as usize] == terrain_type {
+                continue;
+            }
+
+            if influence.accumulate(tile, terrain_type, infusion.rate * delta) {
+                terrain_map.set_tile(tile.0, tile.1, terrain_type);
+                terrain_changes.add_change(tile.0, tile.1, terrain_type);
+                println!("{} infused tile ({}, {}) with {}", pawn.pawn_type, tile.0, tile.1, infusion.terrain);
+            }
+        }
+    }
+}
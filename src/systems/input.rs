@@ -1,91 +1,168 @@
 use bevy::prelude::*;
 use crate::resources::GameConfig;
 use crate::systems::world_gen::{TerrainMap, TerrainChanges, GroundConfigs};
-use crate::systems::pawn::{Pawn, Size};
+use crate::systems::pawn::{Pawn, Size, Swimmer};
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::attack::Attacking;
+use crate::systems::control::{Controlled, HoldPosition, AttackOrder, can_be_ordered_to_attack, fan_out_goal_tiles};
+use crate::systems::selection::{pawn_at_position, cursor_world_position};
 use crate::systems::debug_display::DebugDisplayState;
 use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingPriority};
+use crate::systems::group_pathfinding::{FormationFollower, FormationLeader, can_follow, request_group_pathfinding};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
 
+/// Tile a world position falls in, counting from the bottom-left corner of the map. Positions off
+/// the map give tiles outside it.
+pub fn world_to_tile(world_position: Vec2, config: &GameConfig) -> (i32, i32) {
+    let half_width = (config.map_width as f32 * config.tile_size) / 2.0;
+    let half_height = (config.map_height as f32 * config.tile_size) / 2.0;
+    let tile_x = ((world_position.x + half_width) / config.tile_size).floor() as i32;
+    let tile_y = ((world_position.y + half_height) / config.tile_size).floor() as i32;
+    (tile_x, tile_y)
+}
+
+/// World position of a tile's centre
+pub fn tile_center(tile: (i32, i32), config: &GameConfig) -> Vec2 {
+    let half_width = (config.map_width as f32 * config.tile_size) / 2.0;
+    let half_height = (config.map_height as f32 * config.tile_size) / 2.0;
+    Vec2::new(
+        (tile.0 as f32 * config.tile_size) - half_width + (config.tile_size / 2.0),
+        (tile.1 as f32 * config.tile_size) - half_height + (config.tile_size / 2.0),
+    )
+}
+
+/// Right-click orders the controlled pawns to the tile under the cursor, and middle-click edits
+/// it while the debug display is on
 pub fn handle_player_input(
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     windows: Query<&Window>,
     camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    debug_state: Res<DebugDisplayState>,
+    mut player_commands: ResMut<PlayerCommands>,
+) {
+    if actions.just_pressed(Action::Command) {
+        if let Some(world_position) = cursor_world_position(&windows, &camera_query) {
+            player_commands.give(PlayerCommand::Order { position: world_position.into() });
+        }
+    }
+
+    // Debug terrain editing with middle mouse click
+    if actions.just_pressed(Action::EditTerrain) && debug_state.is_enabled() {
+        if let Some(world_position) = cursor_world_position(&windows, &camera_query) {
+            player_commands.give(PlayerCommand::EditTerrain { position: world_position.into() });
+        }
+    }
+}
+
+/// Carry out this tick's move and attack orders and terrain edits
+pub fn apply_order_commands(
+    player_commands: Res<PlayerCommands>,
     config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
     mut terrain_map: ResMut<TerrainMap>,
     ground_configs: Res<GroundConfigs>,
     mut terrain_changes: ResMut<TerrainChanges>,
-    debug_state: Res<DebugDisplayState>,
     mut commands: Commands,
-    player_query: Query<(Entity, &Transform, &Pawn, &Size), With<Pawn>>,
+    controlled_query: Query<(Entity, &Transform, &Size, &Pawn, Has<Swimmer>), With<Controlled>>,
+    pawn_query: Query<(Entity, &Transform, &Size, &Pawn)>,
 ) {
-    if mouse_input.just_pressed(MouseButton::Right) {
-        if let Ok(window) = windows.get_single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if let Ok((camera, camera_transform)) = camera_query.get_single() {
-                    // Convert screen coordinates to world coordinates
-                    if let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) {
-                        // Snap to tile grid - use floor with offset to get tile center
-                        let tile_size = config.tile_size;
-                        
-                        // Convert to tile coordinates first
-                        let half_width = (config.map_width as f32 * tile_size) / 2.0;
-                        let half_height = (config.map_height as f32 * tile_size) / 2.0;
-                        
-                        let tile_x = ((world_position.x + half_width) / tile_size).floor() as i32;
-                        let tile_y = ((world_position.y + half_height) / tile_size).floor() as i32;
-                        
-                        // Convert back to world coordinates at tile center
-                        let snapped_x = (tile_x as f32 * tile_size) - half_width + (tile_size / 2.0);
-                        let snapped_y = (tile_y as f32 * tile_size) - half_height + (tile_size / 2.0);
-                        let target_pos = Vec3::new(snapped_x, snapped_y, 100.0);
+    for command in &player_commands.this_tick {
+        match *command {
+            PlayerCommand::Order { position } => {
+                let world_position = Vec2::from(position);
+                // Snap to the centre of the clicked tile
+                let tile_size = config.tile_size;
+                let (tile_x, tile_y) = world_to_tile(world_position, &config);
+                let snapped = tile_center((tile_x, tile_y), &config);
+                let (snapped_x, snapped_y) = (snapped.x, snapped.y);
+                let target_pos = snapped.extend(100.0);
 
-                        // Use pathfinding to find route to target for players only
-                        for (entity, transform, pawn, size) in player_query.iter() {
-                            if pawn.pawn_type == "player" {
-                                let player_pos = (transform.translation.x, transform.translation.y);
-                                let goal_pos = (snapped_x, snapped_y);
+                // Right-clicking a pawn sends every controlled pawn that isn't allied with it after it
+                let clicked_pawn = pawn_at_position(
+                    world_position,
+                    tile_size,
+                    pawn_query.iter().map(|(entity, transform, size, _)| (entity, transform, size)),
+                );
+                let attack_target = clicked_pawn.and_then(|target| pawn_query.get(target).ok());
 
-                                // Request critical priority pathfinding for player input
-                                commands.entity(entity).insert(
-                                    PathfindingRequest::new(player_pos, goal_pos, size.value)
-                                        .with_priority(PathfindingPriority::Critical)
-                                );
-                                
-                                println!("Pathfinding requested to {:?}", target_pos);
-                            }
+                let mut movers: Vec<(Entity, &Transform, &Size, bool)> = Vec::new();
+                for (entity, transform, size, pawn, is_swimmer) in controlled_query.iter() {
+                    match attack_target {
+                        Some((target, target_transform, _, target_pawn))
+                            if target != entity && can_be_ordered_to_attack(&pawn_config, &pawn.pawn_type, &target_pawn.pawn_type) =>
+                        {
+                            let pawn_pos = (transform.translation.x, transform.translation.y);
+                            let target_pos = (target_transform.translation.x, target_transform.translation.y);
+                            commands.entity(entity).remove::<(HoldPosition, Attacking, FormationLeader, FormationFollower)>().insert((
+                                AttackOrder::new(target),
+                                PathfindingRequest::new(pawn_pos, target_pos, size.value)
+                                    .with_priority(PathfindingPriority::High),
+                            ));
+                            println!("{} ordered to attack {}", pawn.pawn_type, target_pawn.pawn_type);
                         }
+                        _ => movers.push((entity, transform, size, is_swimmer)),
                     }
                 }
-            }
-        }
-    }
-    
-    // Debug terrain editing with middle mouse click
-    if mouse_input.just_pressed(MouseButton::Middle) && debug_state.enabled {
-        if let Ok(window) = windows.get_single() {
-            if let Some(cursor_position) = window.cursor_position() {
-                if let Ok((camera, camera_transform)) = camera_query.get_single() {
-                    // Convert screen coordinates to world coordinates
-                    if let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) {
-                        // Check if current tile is passable and toggle between stone and dirt
-                        let current_terrain = terrain_map.get_terrain_at_world_pos(world_position.x, world_position.y);
-                        
-                        if let Some(terrain_type) = current_terrain {
-                            let new_terrain = if ground_configs.is_passable(terrain_type) {
-                                // Set to stone if currently passable
-                                ground_configs.terrain_mapping.get("stone").copied().unwrap_or(2)
-                            } else {
-                                // Set to dirt if currently impassable
-                                ground_configs.terrain_mapping.get("dirt").copied().unwrap_or(1)
-                            };
-                            
-                            if terrain_map.set_tile_at_world_pos(world_position.x, world_position.y, new_terrain, &mut terrain_changes) {
-                                println!("Debug: Changed tile at ({:.1}, {:.1}) from {:?} to {:?}", 
-                                    world_position.x, world_position.y, terrain_type, new_terrain);
-                            }
+
+                // The pawn closest to the target leads the rest there in formation. Those too far
+                // behind or that can't walk its path spread over the tiles around the target instead,
+                // the closest pawn taking the closest tile.
+                movers.sort_by(|a, b| {
+                    a.1.translation.distance(target_pos).total_cmp(&b.1.translation.distance(target_pos))
+                });
+                let leader = movers.first().map(|&(entity, transform, size, is_swimmer)| {
+                    (entity, (transform.translation.truncate(), size.value, is_swimmer))
+                });
+                let (followers, loners): (Vec<_>, Vec<_>) = movers.into_iter().partition(|&(entity, transform, size, is_swimmer)| {
+                    leader.is_some_and(|(leader, leader_walker)| {
+                        entity != leader && can_follow((transform.translation.truncate(), size.value, is_swimmer), leader_walker, tile_size)
+                    })
+                });
+                let goals = fan_out_goal_tiles(&terrain_map, &ground_configs, (tile_x, tile_y), loners.len());
+                let followers: Vec<Entity> = followers.into_iter().map(|(entity, ..)| entity).collect();
+
+                for (index, (entity, transform, size, _)) in loners.into_iter().enumerate() {
+                    let pawn_pos = (transform.translation.x, transform.translation.y);
+                    let goal_pos = match goals.get(index) {
+                        Some(&(goal_x, goal_y)) => terrain_map.tile_to_world_coords(goal_x, goal_y),
+                        None => (snapped_x, snapped_y),
+                    };
+
+                    // Request critical priority pathfinding for player input
+                    let request = PathfindingRequest::new(pawn_pos, goal_pos, size.value)
+                        .with_priority(PathfindingPriority::Critical);
+                    commands.entity(entity).remove::<(HoldPosition, AttackOrder, Attacking, FormationLeader, FormationFollower)>();
+                    if leader.is_some_and(|(leader, _)| leader == entity) && !followers.is_empty() {
+                        for &follower in &followers {
+                            commands.entity(follower).remove::<(HoldPosition, AttackOrder, Attacking)>();
                         }
+                        request_group_pathfinding(&mut commands, entity, &followers, request);
+                        println!("Pathfinding requested to {:?} for a group of {}", goal_pos, followers.len() + 1);
+                    } else {
+                        commands.entity(entity).insert(request);
+                        println!("Pathfinding requested to {:?}", goal_pos);
+                    }
+                }
+            }
+            PlayerCommand::EditTerrain { position: (x, y) } => {
+                // Check if current tile is passable and toggle between stone and dirt
+                if let Some(terrain_type) = terrain_map.get_terrain_at_world_pos(x, y) {
+                    let new_terrain = if ground_configs.is_passable(terrain_type) {
+                        // Set to stone if currently passable
+                        ground_configs.terrain_mapping.get("stone").copied().unwrap_or(2)
+                    } else {
+                        // Set to dirt if currently impassable
+                        ground_configs.terrain_mapping.get("dirt").copied().unwrap_or(1)
+                    };
+
+                    if terrain_map.set_tile_at_world_pos(x, y, new_terrain, &mut terrain_changes) {
+                        println!("Debug: Changed tile at ({:.1}, {:.1}) from {:?} to {:?}",
+                            x, y, terrain_type, new_terrain);
                     }
                 }
             }
+            _ => {}
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use crate::systems::pawn::Pawn;
+
+/// Where a pawn was before and after the latest simulation step. Pawns only move on FixedUpdate,
+/// so between steps they're drawn part of the way from one to the other, by how far real time
+/// has got towards the next step, instead of jumping a step's worth at a time.
+///
+/// Outside the fixed loop a pawn's Transform holds that drawn position; the simulated one is put
+/// back before the loop runs. Anything outside FixedUpdate that moves a pawn has to set
+/// `current` too, or the move is undone.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SmoothMotion {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl SmoothMotion {
+    pub fn at(position: Vec3) -> Self {
+        Self { previous: position, current: position }
+    }
+}
+
+/// Runs before the fixed loop - put pawns back where the simulation left them
+pub fn restore_simulated_positions(mut query: Query<(&mut Transform, &SmoothMotion)>) {
+    for (mut transform, motion) in query.iter_mut() {
+        transform.translation = motion.current;
+    }
+}
+
+/// Runs at the start of every simulation step - note where pawns start it from, starting to
+/// track any that are new
+pub fn record_previous_positions(
+    mut commands: Commands,
+    mut query: Query<(Entity, &Transform, Option<&mut SmoothMotion>), With<Pawn>>,
+) {
+    for (entity, transform, motion) in query.iter_mut() {
+        match motion {
+            Some(mut motion) => motion.previous = transform.translation,
+            None => {
+                commands.entity(entity).insert(SmoothMotion::at(transform.translation));
+            }
+        }
+    }
+}
+
+/// Runs after the fixed loop - note where the simulation left pawns, then draw them between
+/// there and where the step started
+pub fn interpolate_drawn_positions(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut SmoothMotion)>,
+) {
+    let progress = fixed_time.overstep_fraction();
+    for (mut transform, mut motion) in query.iter_mut() {
+        motion.current = transform.translation;
+        transform.translation = motion.previous.lerp(motion.current, progress);
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::pawn::{Pawn, Health, Size, TilesetManager, create_sprite};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::sim_rng::SimRng;
+use crate::systems::y_sort::{YSort, DepthLayer};
+
+pub type ItemType = String;
+
+/// How much weight a pawn can carry per unit of size
+pub const CARRY_CAPACITY_PER_SIZE: f32 = 10.0;
+/// How close (in tiles) a carrier has to walk to an item to pick it up
+pub const PICKUP_RADIUS_TILES: f32 = 0.5;
+/// Random tiles tried for each item scattered at world generation before giving up on it
+pub const ITEM_PLACEMENT_ATTEMPTS: u32 = 100;
+/// Above terrain, props and dens, below pawns, while items aren't y-sorted
+const ITEM_Z: f32 = 20.0;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ItemDefinition {
+    pub sprite: String,
+    #[serde(default = "default_item_weight")]
+    pub weight: f32,
+    #[serde(default)]
+    pub spawn_count: u32, // Scattered over passable ground when the world is generated
+}
+
+fn default_item_weight() -> f32 {
+    1.0
+}
+
+/// Items from items.yaml by name
+#[derive(Debug, Clone, Default, Resource, Deserialize, Serialize)]
+pub struct ItemConfig {
+    #[serde(flatten)]
+    pub items: HashMap<ItemType, ItemDefinition>,
+}
+
+impl ItemConfig {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: ItemConfig = serde_yaml::from_str(contents)?;
+        Ok(config)
+    }
+
+    pub fn get(&self, item_type: &str) -> Option<&ItemDefinition> {
+        self.items.get(item_type)
+    }
+
+    pub fn weight(&self, item_type: &str) -> f32 {
+        self.get(item_type).map_or(0.0, |item| item.weight)
+    }
+}
+
+/// An item lying in the world, waiting to be picked up
+#[derive(Component, Debug, Clone)]
+pub struct Item {
+    pub item_type: ItemType,
+}
+
+/// What a pawn with `can_carry` is carrying
+#[derive(Component, Debug, Clone, Default)]
+pub struct Inventory {
+    pub items: Vec<ItemType>,
+}
+
+impl Inventory {
+    pub fn weight(&self, item_config: &ItemConfig) -> f32 {
+        self.items.iter().map(|item| item_config.weight(item)).sum()
+    }
+}
+
+/// Most weight a pawn of this size can carry
+pub fn carry_capacity(size: &Size) -> f32 {
+    size.value * CARRY_CAPACITY_PER_SIZE
+}
+
+/// Put an item down in the world, drawn with its sprite when there's anything to draw it with
+pub fn spawn_item(commands: &mut Commands, item_type: &str, position: Vec2, sprite: Option<Sprite>) -> Entity {
+    let mut entity_commands = commands.spawn((
+        Item { item_type: item_type.to_string() },
+        Transform::from_xyz(position.x, position.y, ITEM_Z),
+        YSort(DepthLayer::Items),
+    ));
+    if let Some(sprite) = sprite {
+        entity_commands.insert(sprite);
+    }
+    entity_commands.id()
+}
+
+/// Scatter every item's `spawn_count` over passable tiles once the world has been generated
+pub fn scatter_items(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    item_config: Res<ItemConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut tileset_manager: ResMut<TilesetManager>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut rng: ResMut<SimRng>,
+) {
+    if terrain_map.width == 0 || terrain_map.height == 0 {
+        return;
+    }
+    // In name order, so the same seed scatters them the same way
+    let mut items: Vec<_> = item_config.items.iter().collect();
+    items.sort_by(|a, b| a.0.cmp(b.0));
+    for (item_type, item) in items {
+        let mut placed = 0;
+        for _ in 0..ITEM_PLACEMENT_ATTEMPTS * item.spawn_count {
+            if placed >= item.spawn_count {
+                break;
+            }
+            let tile = (rng.gen_range(0..terrain_map.width) as i32, rng.gen_range(0..terrain_map.height) as i32);
+            if !terrain_map.is_tile_passable(tile.0, tile.1, &ground_configs) {
+                continue;
+            }
+            let (x, y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+            let sprite = create_sprite(&asset_server, &mut tileset_manager, &mut texture_atlas_layouts, &item.sprite);
+            spawn_item(&mut commands, item_type, Vec2::new(x, y), Some(sprite));
+            placed += 1;
+        }
+    }
+}
+
+/// Carriers pick up the items they walk over, as long as they have the capacity left
+pub fn item_pickup_system(
+    config: Res<GameConfig>,
+    item_config: Res<ItemConfig>,
+    mut commands: Commands,
+    mut carrier_query: Query<(&Transform, &Pawn, &Size, &mut Inventory)>,
+    item_query: Query<(Entity, &Transform, &Item)>,
+) {
+    let pickup_radius = PICKUP_RADIUS_TILES * config.tile_size;
+    let mut taken: Vec<Entity> = Vec::new();
+
+    for (transform, pawn, size, mut inventory) in carrier_query.iter_mut() {
+        let position = transform.translation.truncate();
+        let capacity = carry_capacity(size);
+        for (item_entity, item_transform, item) in item_query.iter() {
+            if taken.contains(&item_entity) || position.distance(item_transform.translation.truncate()) > pickup_radius {
+                continue;
+            }
+            if inventory.weight(&item_config) + item_config.weight(&item.item_type) > capacity {
+                continue;
+            }
+            println!("{} picked up {}", pawn.pawn_type, item.item_type);
+            inventory.items.push(item.item_type.clone());
+            taken.push(item_entity);
+            commands.entity(item_entity).despawn();
+        }
+    }
+}
+
+/// Dying carriers drop everything they were carrying where they fall
+pub fn drop_inventory_on_death(
+    item_config: Res<ItemConfig>,
+    asset_server: Option<Res<AssetServer>>,
+    mut tileset_manager: Option<ResMut<TilesetManager>>,
+    mut texture_atlas_layouts: Option<ResMut<Assets<TextureAtlasLayout>>>,
+    mut commands: Commands,
+    mut carrier_query: Query<(&Transform, &Health, &mut Inventory)>,
+) {
+    for (transform, health, mut inventory) in carrier_query.iter_mut() {
+        if health.current > 0.0 || inventory.items.is_empty() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        for item_type in inventory.items.drain(..) {
+            let sprite = match (asset_server.as_ref(), tileset_manager.as_mut(), texture_atlas_layouts.as_mut(), item_config.get(&item_type)) {
+                (Some(asset_server), Some(tileset_manager), Some(texture_atlas_layouts), Some(item)) => {
+                    Some(create_sprite(asset_server, tileset_manager, texture_atlas_layouts, &item.sprite))
+                }
+                _ => None,
+            };
+            spawn_item(&mut commands, &item_type, position, sprite);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::reflect::{DynamicEnum, DynamicVariant, Enum, TypeInfo, Typed, VariantInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Everything the player can do with a key or mouse button, named as in keybinds.yaml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    CameraUp,
+    CameraDown,
+    CameraLeft,
+    CameraRight,
+    ZoomIn,
+    ZoomOut,
+    PanCamera,
+    FollowSelected, // Lock the camera onto the selected pawn, until it's panned away
+    StoreBookmark,  // Held with 1-9 to remember the camera's position and zoom
+    RecallBookmark, // Held with 1-9 to go back to one
+    SteerUp,
+    SteerDown,
+    SteerLeft,
+    SteerRight,
+    Select,    // Click a pawn, or drag a box over several
+    Command,   // Send the controlled pawns somewhere, or after another pawn
+    SelectAll, // Every controllable pawn
+    Hold,
+    Charge,
+    Feed,
+    EditTerrain, // Toggle a tile between stone and dirt while the debug display is on
+    ToggleDebug,
+    CyclePassabilitySize, // Next pawn size for the passability overlay
+    ToggleBestiary,
+    Pause,
+    SpeedNormal,  // 1x
+    SpeedFast,    // 2x
+    SpeedFastest, // 4x
+    QuickSave,
+    QuickLoad,
+    SaveReplay, // Everything done since the world was made, to play back with --replay
+    Menu, // Open and close the pause menu, or go back a screen in a menu
+}
+
+impl Action {
+    /// Actions that walk the controlled pawn - their keys are left to it while there is one
+    pub const STEERING: [Action; 4] = [Action::SteerUp, Action::SteerDown, Action::SteerLeft, Action::SteerRight];
+}
+
+/// A key or mouse button, written in keybinds.yaml as the key's name (`KeyW`, `F12`,
+/// `ArrowUp`, `Space`) or `Mouse` and the button's (`MouseLeft`, `MouseMiddle`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    /// How the binding is shown to the player, e.g. `B` for `KeyB` and `1` for `Digit1`
+    pub fn label(&self) -> String {
+        let name = String::from(*self);
+        let short = name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name);
+        short.to_string()
+    }
+}
+
+/// The named unit variant of a reflected enum, e.g. `KeyCode::KeyW` from "KeyW"
+fn unit_variant<T: FromReflect + Typed>(name: &str) -> Option<T> {
+    // Reflection panics on variants that don't exist, so look the name up first
+    let is_unit = match T::type_info() {
+        TypeInfo::Enum(info) => matches!(info.variant(name), Some(VariantInfo::Unit(_))),
+        _ => false,
+    };
+    if !is_unit {
+        return None;
+    }
+    T::from_reflect(&DynamicEnum::new(name.to_string(), DynamicVariant::Unit))
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let binding = match name.strip_prefix("Mouse") {
+            Some(button) => unit_variant(button).map(Binding::Mouse),
+            None => unit_variant(&name).map(Binding::Key),
+        };
+        binding.ok_or_else(|| format!("unknown key or mouse button '{}'", name))
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> Self {
+        match binding {
+            Binding::Key(key) => key.variant_name().to_string(),
+            Binding::Mouse(button) => format!("Mouse{}", button.variant_name()),
+        }
+    }
+}
+
+/// Which keys and buttons trigger each action. Loaded from keybinds.yaml, where any action
+/// left out keeps its default bindings.
+#[derive(Resource, Debug, Clone)]
+pub struct InputMap {
+    pub bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::{Key, Mouse};
+        let bindings = [
+            (Action::CameraUp, vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp)]),
+            (Action::CameraDown, vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown)]),
+            (Action::CameraLeft, vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft)]),
+            (Action::CameraRight, vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight)]),
+            (Action::ZoomIn, vec![Key(KeyCode::Equal), Key(KeyCode::NumpadAdd)]),
+            (Action::ZoomOut, vec![Key(KeyCode::Minus), Key(KeyCode::NumpadSubtract)]),
+            (Action::PanCamera, vec![Mouse(MouseButton::Middle)]),
+            (Action::FollowSelected, vec![Key(KeyCode::KeyL)]),
+            (Action::StoreBookmark, vec![Key(KeyCode::ControlLeft), Key(KeyCode::ControlRight)]),
+            (Action::RecallBookmark, vec![Key(KeyCode::AltLeft), Key(KeyCode::AltRight)]),
+            (Action::SteerUp, vec![Key(KeyCode::KeyW)]),
+            (Action::SteerDown, vec![Key(KeyCode::KeyS)]),
+            (Action::SteerLeft, vec![Key(KeyCode::KeyA)]),
+            (Action::SteerRight, vec![Key(KeyCode::KeyD)]),
+            (Action::Select, vec![Mouse(MouseButton::Left)]),
+            (Action::Command, vec![Mouse(MouseButton::Right)]),
+            (Action::SelectAll, vec![Key(KeyCode::Tab)]),
+            (Action::Hold, vec![Key(KeyCode::KeyH)]),
+            (Action::Charge, vec![Key(KeyCode::KeyC)]),
+            (Action::Feed, vec![Key(KeyCode::KeyF)]),
+            (Action::EditTerrain, vec![Mouse(MouseButton::Middle)]),
+            (Action::ToggleDebug, vec![Key(KeyCode::F12)]),
+            (Action::CyclePassabilitySize, vec![Key(KeyCode::KeyP)]),
+            (Action::ToggleBestiary, vec![Key(KeyCode::KeyB)]),
+            (Action::Pause, vec![Key(KeyCode::Space)]),
+            (Action::SpeedNormal, vec![Key(KeyCode::Digit1)]),
+            (Action::SpeedFast, vec![Key(KeyCode::Digit2)]),
+            (Action::SpeedFastest, vec![Key(KeyCode::Digit3)]),
+            (Action::QuickSave, vec![Key(KeyCode::F5)]),
+            (Action::QuickLoad, vec![Key(KeyCode::F9)]),
+            (Action::SaveReplay, vec![Key(KeyCode::F10)]),
+            (Action::Menu, vec![Key(KeyCode::Escape)]),
+        ];
+        Self { bindings: bindings.into_iter().collect() }
+    }
+}
+
+impl InputMap {
+    /// The defaults with whatever actions the YAML rebinds replaced
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let rebound: Option<HashMap<Action, Vec<Binding>>> = serde_yaml::from_str(contents)?;
+        let mut input_map = Self::default();
+        input_map.bindings.extend(rebound.unwrap_or_default());
+        Ok(input_map)
+    }
+
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Keyboard and mouse state read through the input map, for systems that respond to actions
+/// rather than particular keys
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    pub input_map: Res<'w, InputMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+}
+
+impl ActionInput<'_> {
+    /// Whether any of the action's bindings, leaving out those shared with `except`, passes the check
+    fn check(
+        &self,
+        action: Action,
+        except: &[Action],
+        key_check: fn(&ButtonInput<KeyCode>, KeyCode) -> bool,
+        button_check: fn(&ButtonInput<MouseButton>, MouseButton) -> bool,
+    ) -> bool {
+        self.input_map.bindings(action).iter()
+            .filter(|binding| !except.iter().any(|other| self.input_map.bindings(*other).contains(binding)))
+            .any(|binding| match *binding {
+                Binding::Key(key) => key_check(&self.keys, key),
+                Binding::Mouse(button) => button_check(&self.mouse, button),
+            })
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.check(action, &[], ButtonInput::pressed, ButtonInput::pressed)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.check(action, &[], ButtonInput::just_pressed, ButtonInput::just_pressed)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.check(action, &[], ButtonInput::just_released, ButtonInput::just_released)
+    }
+
+    /// Whether the action is held on one of its bindings that none of `others` share
+    pub fn pressed_except(&self, action: Action, others: &[Action]) -> bool {
+        self.check(action, others, ButtonInput::pressed, ButtonInput::pressed)
+    }
+}
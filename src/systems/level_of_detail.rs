@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::resources::GameConfig;
+use crate::systems::camera::CameraController;
+use crate::systems::pawn::Pawn;
+use crate::systems::shadows::PawnShadow;
+
+/// Width and height on screen, in pixels, of the dot a pawn is drawn as when zoomed far out
+const MARKER_PIXELS: f32 = 2.0;
+
+/// The `level_of_detail` section of settings.yaml - how far out (as the camera's zoom scale, 1
+/// being a pixel per world unit) the view can go before it drops detail
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LevelOfDetailSettings {
+    pub overlays_scale: f32, // Past this, icons, debug text and shadows go and pawns stop animating
+    pub markers_scale: f32,  // Past this, pawns are drawn as dots
+}
+
+impl Default for LevelOfDetailSettings {
+    fn default() -> Self {
+        Self {
+            overlays_scale: 3.0,
+            markers_scale: 6.0,
+        }
+    }
+}
+
+/// How much is drawn at the current zoom, from the most to the least
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Detail {
+    #[default]
+    Full,
+    Reduced, // No per-pawn overlays or animation
+    Markers, // As Reduced, with pawns drawn as dots
+}
+
+impl Detail {
+    pub fn for_scale(scale: f32, settings: &LevelOfDetailSettings) -> Self {
+        if scale >= settings.markers_scale {
+            Detail::Markers
+        } else if scale >= settings.overlays_scale {
+            Detail::Reduced
+        } else {
+            Detail::Full
+        }
+    }
+}
+
+/// The detail the view is drawn with, and the zoom it was worked out from
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LevelOfDetail {
+    pub detail: Detail,
+    pub scale: f32,
+}
+
+impl LevelOfDetail {
+    /// Whether icons, debug text and the like over each pawn are left out
+    pub fn hides_overlays(&self) -> bool {
+        self.detail >= Detail::Reduced
+    }
+}
+
+/// A pawn drawn as a dot, holding the sprite it's drawn with up close
+#[derive(Component)]
+pub struct PawnMarker {
+    pub sprite: Sprite,
+}
+
+/// Work out the detail from how far the camera is zoomed out
+pub fn update_level_of_detail(
+    config: Res<GameConfig>,
+    mut level_of_detail: ResMut<LevelOfDetail>,
+    camera_query: Query<&OrthographicProjection, With<CameraController>>,
+) {
+    let Ok(projection) = camera_query.get_single() else {
+        return;
+    };
+    let current = LevelOfDetail {
+        detail: Detail::for_scale(projection.scale, &config.level_of_detail),
+        scale: projection.scale,
+    };
+    level_of_detail.set_if_neq(current);
+}
+
+/// Hide shadows when zoomed out, and swap pawns' sprites for dots when zoomed out further. Pawns
+/// spawned while zoomed out get theirs too.
+pub fn apply_level_of_detail(
+    level_of_detail: Res<LevelOfDetail>,
+    mut commands: Commands,
+    mut shadow_query: Query<&mut Visibility, With<PawnShadow>>,
+    mut sprite_query: Query<(Entity, &mut Sprite), (With<Pawn>, Without<PawnMarker>)>,
+    mut marker_query: Query<(Entity, &mut Sprite, &PawnMarker), With<Pawn>>,
+) {
+    let shown = if level_of_detail.hides_overlays() { Visibility::Hidden } else { Visibility::Inherited };
+    for mut visibility in shadow_query.iter_mut() {
+        visibility.set_if_neq(shown);
+    }
+
+    let marker_size = Vec2::splat(MARKER_PIXELS * level_of_detail.scale);
+    if level_of_detail.detail == Detail::Markers {
+        for (entity, mut sprite) in sprite_query.iter_mut() {
+            let marker = Sprite { color: sprite.color, custom_size: Some(marker_size), ..default() };
+            let up_close = std::mem::replace(&mut *sprite, marker);
+            commands.entity(entity).insert(PawnMarker { sprite: up_close });
+        }
+        if level_of_detail.is_changed() {
+            for (_, mut sprite, _) in marker_query.iter_mut() {
+                sprite.custom_size = Some(marker_size);
+            }
+        }
+    } else {
+        for (entity, mut sprite, marker) in marker_query.iter_mut() {
+            *sprite = marker.sprite.clone();
+            commands.entity(entity).remove::<PawnMarker>();
+        }
+    }
+}
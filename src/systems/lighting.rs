@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::camera::CameraController;
+use crate::systems::world_clock::WorldClock;
+
+/// How dark the night tint gets unless settings.yaml says otherwise
+pub const DEFAULT_NIGHT_DARKNESS: f32 = 0.6;
+/// Above pawns and items, below behaviour icons and the selection box so those stay readable
+const NIGHT_TINT_Z: f32 = 150.0;
+/// How far past the edge of the view the tint reaches, so it never lags behind a moving camera
+const NIGHT_TINT_MARGIN: f32 = 1.5;
+
+/// Dark blue wash over the view that deepens as night falls
+#[derive(Component)]
+pub struct NightTint;
+
+/// The tint's opacity for how light it is: clear in the day, up to `darkness` at night
+pub fn night_tint_alpha(daylight: f32, darkness: f32) -> f32 {
+    (1.0 - daylight.clamp(0.0, 1.0)) * darkness.clamp(0.0, 1.0)
+}
+
+/// Keep the night tint over whatever the camera is looking at, as dark as the time of day calls for
+pub fn update_night_tint(
+    config: Res<GameConfig>,
+    world_clock: Res<WorldClock>,
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &OrthographicProjection), (With<CameraController>, Without<NightTint>)>,
+    mut tint_query: Query<(&mut Transform, &mut Sprite), With<NightTint>>,
+) {
+    let (camera_transform, projection) = match camera_query.get_single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    let size = projection.area.size() * NIGHT_TINT_MARGIN;
+    let translation = camera_transform.translation.truncate().extend(NIGHT_TINT_Z);
+    let color = Color::srgba(0.02, 0.03, 0.15, night_tint_alpha(world_clock.daylight(), config.night_darkness));
+
+    match tint_query.get_single_mut() {
+        Ok((mut transform, mut sprite)) => {
+            transform.translation = translation;
+            sprite.custom_size = Some(size);
+            sprite.color = color;
+        }
+        Err(_) => {
+            commands.spawn((
+                Sprite::from_color(color, size),
+                Transform::from_translation(translation),
+                NightTint,
+            ));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::pawn::{Pawn, Health};
+use crate::systems::perception::Vision;
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// How often pawns with vision take note of who they can see unless settings.yaml says otherwise
+pub const MEMORY_TICK_SECS: f32 = 0.25;
+
+/// Where a pawn last saw someone, and when (seconds since startup)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sighting {
+    pub position: Vec2,
+    pub seen_at: f32,
+}
+
+/// Last-known positions of the pawns a pawn with vision has seen. Sightings older than
+/// `span` seconds are forgotten.
+#[derive(Component, Debug, Clone)]
+pub struct Memory {
+    pub span: f32,
+    pub sightings: HashMap<Entity, Sighting>,
+}
+
+impl Memory {
+    pub fn new(span: f32) -> Self {
+        Self {
+            span,
+            sightings: HashMap::new(),
+        }
+    }
+
+    pub fn remember(&mut self, pawn: Entity, position: Vec2, now: f32) {
+        self.sightings.insert(pawn, Sighting { position, seen_at: now });
+    }
+
+    /// Where `pawn` was last seen, if it's still remembered
+    pub fn recall(&self, pawn: Entity) -> Option<Vec2> {
+        self.sightings.get(&pawn).map(|sighting| sighting.position)
+    }
+
+    pub fn forget(&mut self, pawn: Entity) {
+        self.sightings.remove(&pawn);
+    }
+
+    /// Let go of sightings that have been out of sight longer than the memory span
+    pub fn forget_stale(&mut self, now: f32) {
+        let span = self.span;
+        self.sightings.retain(|_, sighting| now - sighting.seen_at <= span);
+    }
+}
+
+/// Pawns with vision remember where they saw every living pawn in view, and forget the
+/// dead and anyone they haven't seen for longer than their memory span
+pub fn memory_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut ticker: Local<AiTicker>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut watcher_query: Query<(Entity, &Transform, &Vision, &mut Memory)>,
+    pawn_query: Query<(Entity, &Transform, &Health), With<Pawn>>,
+    spatial_grid: Res<SpatialGrid>,
+) {
+    ticker.advance(time.delta_secs());
+    let now = time.elapsed_secs();
+
+    for (entity, transform, vision, mut memory) in watcher_query.iter_mut() {
+        if !ticker.is_due(entity, config.ai_ticks.memory) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let in_range = spatial_grid.within(position, vision.range * terrain_map.tile_size);
+        for (other, other_transform, health) in in_range.filter_map(|(other, _)| pawn_query.get(other).ok()) {
+            let other_position = other_transform.translation.truncate();
+            if other != entity && health.current > 0.0 && vision.can_see(position, other_position, &terrain_map, &ground_configs) {
+                memory.remember(other, other_position, now);
+            }
+        }
+        memory.forget_stale(now);
+        memory.sightings.retain(|pawn, _| pawn_query.get(*pawn).is_ok_and(|(_, _, health)| health.current > 0.0));
+    }
+}
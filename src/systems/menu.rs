@@ -0,0 +1,548 @@
+use bevy::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use std::path::{Path, PathBuf};
+use crate::resources::{GameConfig, SETTINGS_FILE};
+use crate::systems::config_files::ConfigPaths;
+use crate::systems::debug_display::DebugDisplayState;
+use crate::systems::game_state::GameState;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::save::{LoadRequest, SaveRequest, WorldMetadata, CONFIG_FILES, SAVE_DIR, hash_config_files, list_saves};
+use crate::systems::world_gen::{NextWorldSeed, TerrainMap};
+
+/// Sizes, in tiles each way, a new world can be made at
+pub const MAP_SIZES: [u32; 4] = [64, 128, 256, 512];
+/// The longest seed there is - u32::MAX has ten digits
+const MAX_SEED_DIGITS: usize = 10;
+const CAMERA_SPEED_STEP: f32 = 50.0;
+const MOUSE_SENSITIVITY_STEP: f32 = 0.25;
+const ZOOM_MIN_STEP: f32 = 0.05;
+const ZOOM_MAX_STEP: f32 = 1.0;
+const VOLUME_STEP: f32 = 0.1;
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.2);
+const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.25, 0.25, 0.32);
+const BUTTON_PRESSED_COLOR: Color = Color::srgb(0.35, 0.45, 0.35);
+const WARNING_COLOR: Color = Color::srgb(0.95, 0.75, 0.3);
+
+/// Which screen of the main or pause menu is showing
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MenuPage {
+    #[default]
+    Main,
+    NewWorld,
+    Load,
+    Settings,
+}
+
+/// The seed typed on the new world screen, random if left empty
+#[derive(Resource, Debug, Default)]
+pub struct NewWorldForm {
+    pub seed: String,
+}
+
+/// A save on the load screen, with what the running game makes of it
+struct SaveEntry {
+    path: PathBuf,
+    metadata: WorldMetadata,
+    thumbnail: Option<(Handle<Image>, Vec2)>,
+    warnings: Vec<String>,
+    rejection: Option<String>, // Why it can't be loaded over the world that's paused
+}
+
+/// Root of whichever menu screen is showing
+#[derive(Component)]
+pub struct MenuScreen;
+
+#[derive(Component, Debug, Clone, PartialEq)]
+pub enum MenuButton {
+    NewWorld,
+    SmallerMap,
+    LargerMap,
+    StartWorld,
+    Load,
+    LoadSave { path: PathBuf, seed: u32, map_width: u32, map_height: u32 },
+    Save,
+    Resume,
+    Settings,
+    SlowerCamera,
+    FasterCamera,
+    LessMouseSensitivity,
+    MoreMouseSensitivity,
+    LessZoomMin,
+    MoreZoomMin,
+    LessZoomMax,
+    MoreZoomMax,
+    QuieterVolume,
+    LouderVolume,
+    ToggleFps,
+    ToggleBehaviourIcons,
+    ToggleDamageNumbers,
+    CycleDebugOverlay,
+    Back,
+    Quit,
+}
+
+/// The seed in what's been typed, or None for a random one
+pub fn parse_seed(text: &str) -> Option<u32> {
+    text.parse().ok()
+}
+
+/// Type a key into the seed field: digits are added, up to the longest seed, and backspace takes
+/// the last one off
+pub fn type_seed(seed: &mut String, key: &Key) {
+    match key {
+        Key::Character(typed) if typed.chars().all(|c| c.is_ascii_digit()) => {
+            for digit in typed.chars() {
+                if seed.len() < MAX_SEED_DIGITS {
+                    seed.push(digit);
+                }
+            }
+        }
+        Key::Backspace => {
+            seed.pop();
+        }
+        _ => {}
+    }
+}
+
+/// The next map size up or down from `current`, staying put at either end
+pub fn step_map_size(current: u32, larger: bool) -> u32 {
+    let next = if larger {
+        MAP_SIZES.iter().find(|&&size| size > current)
+    } else {
+        MAP_SIZES.iter().rev().find(|&&size| size < current)
+    };
+    next.copied().unwrap_or(current)
+}
+
+/// Raise or lower a setting on the settings page, keeping it within sensible limits. Returns
+/// whether the button was one that changes a saved setting.
+pub fn apply_setting_button(config: &mut GameConfig, button: &MenuButton) -> bool {
+    match button {
+        MenuButton::SlowerCamera => config.camera_speed = (config.camera_speed - CAMERA_SPEED_STEP).max(CAMERA_SPEED_STEP),
+        MenuButton::FasterCamera => config.camera_speed += CAMERA_SPEED_STEP,
+        MenuButton::LessMouseSensitivity => {
+            config.mouse_sensitivity = (config.mouse_sensitivity - MOUSE_SENSITIVITY_STEP).max(MOUSE_SENSITIVITY_STEP);
+        }
+        MenuButton::MoreMouseSensitivity => config.mouse_sensitivity += MOUSE_SENSITIVITY_STEP,
+        MenuButton::LessZoomMin => config.zoom_min = (config.zoom_min - ZOOM_MIN_STEP).max(ZOOM_MIN_STEP),
+        MenuButton::MoreZoomMin => config.zoom_min = (config.zoom_min + ZOOM_MIN_STEP).min(config.zoom_max),
+        MenuButton::LessZoomMax => config.zoom_max = (config.zoom_max - ZOOM_MAX_STEP).max(config.zoom_min),
+        MenuButton::MoreZoomMax => config.zoom_max += ZOOM_MAX_STEP,
+        MenuButton::QuieterVolume => config.volume = (config.volume - VOLUME_STEP).max(0.0),
+        MenuButton::LouderVolume => config.volume = (config.volume + VOLUME_STEP).min(1.0),
+        MenuButton::ToggleFps => config.show_fps = !config.show_fps,
+        MenuButton::ToggleBehaviourIcons => config.show_behaviour_icons = !config.show_behaviour_icons,
+        MenuButton::ToggleDamageNumbers => config.show_damage_numbers = !config.show_damage_numbers,
+        _ => return false,
+    }
+    true
+}
+
+/// "On" or "Off" for a toggle's label
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+/// The menu key opens the pause menu during play and closes it again; on any other menu screen
+/// it goes back to the first one
+pub fn menu_back_input(
+    actions: ActionInput,
+    state: Res<State<GameState>>,
+    mut page: ResMut<MenuPage>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !actions.just_pressed(Action::Menu) {
+        return;
+    }
+    match (state.get(), *page) {
+        (GameState::Playing, _) => {
+            *page = MenuPage::Main;
+            next_state.set(GameState::Paused);
+        }
+        (_, MenuPage::NewWorld | MenuPage::Load | MenuPage::Settings) => *page = MenuPage::Main,
+        (GameState::Paused, MenuPage::Main) => next_state.set(GameState::Playing),
+        (GameState::MainMenu, MenuPage::Main) => {}
+    }
+}
+
+/// Typing on the new world screen goes into the seed
+pub fn seed_input(
+    page: Res<MenuPage>,
+    mut form: ResMut<NewWorldForm>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+) {
+    for event in keyboard_events.read() {
+        if *page == MenuPage::NewWorld && event.state.is_pressed() {
+            type_seed(&mut form.seed, &event.logical_key);
+        }
+    }
+}
+
+/// Carry out whatever menu button was clicked, and light buttons up under the cursor
+pub fn menu_buttons(
+    mut button_query: Query<(&Interaction, &MenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    state: Res<State<GameState>>,
+    form: Res<NewWorldForm>,
+    mut page: ResMut<MenuPage>,
+    mut config: ResMut<GameConfig>,
+    mut volume: ResMut<GlobalVolume>,
+    mut debug_state: ResMut<DebugDisplayState>,
+    mut next_seed: ResMut<NextWorldSeed>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut save_requests: EventWriter<SaveRequest>,
+    mut load_requests: EventWriter<LoadRequest>,
+    mut exit: EventWriter<AppExit>,
+    config_paths: Res<ConfigPaths>,
+) {
+    for (interaction, button, mut color) in button_query.iter_mut() {
+        *color = match interaction {
+            Interaction::Pressed => BUTTON_PRESSED_COLOR.into(),
+            Interaction::Hovered => BUTTON_HOVERED_COLOR.into(),
+            Interaction::None => BUTTON_COLOR.into(),
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MenuButton::NewWorld => *page = MenuPage::NewWorld,
+            MenuButton::SmallerMap | MenuButton::LargerMap => {
+                let size = step_map_size(config.map_width, *button == MenuButton::LargerMap);
+                config.map_width = size;
+                config.map_height = size;
+            }
+            MenuButton::StartWorld => {
+                next_seed.0 = parse_seed(&form.seed);
+                *page = MenuPage::Main;
+                next_state.set(GameState::Playing);
+            }
+            MenuButton::Load => *page = MenuPage::Load,
+            MenuButton::LoadSave { path, seed, map_width, map_height } => {
+                // From the main menu the saved world is generated again from its seed first
+                if *state.get() == GameState::MainMenu {
+                    next_seed.0 = Some(*seed);
+                    config.map_width = *map_width;
+                    config.map_height = *map_height;
+                }
+                load_requests.send(LoadRequest(path.clone()));
+                *page = MenuPage::Main;
+                next_state.set(GameState::Playing);
+            }
+            MenuButton::Save => {
+                save_requests.send(SaveRequest);
+            }
+            MenuButton::Resume => next_state.set(GameState::Playing),
+            MenuButton::Settings => *page = MenuPage::Settings,
+            MenuButton::CycleDebugOverlay => debug_state.mode = debug_state.mode.next(),
+            MenuButton::Back => *page = MenuPage::Main,
+            MenuButton::Quit => {
+                exit.send(AppExit::Success);
+            }
+            setting => {
+                if apply_setting_button(&mut config, setting) {
+                    *volume = GlobalVolume::new(config.volume);
+                    if let Err(e) = config.save_settings(&config_paths.write_path(SETTINGS_FILE).to_string_lossy()) {
+                        eprintln!("Warning: Could not save {} ({})", SETTINGS_FILE, e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn spawn_title(parent: &mut ChildBuilder, title: &str) {
+    parent.spawn((
+        Text::new(title),
+        TextFont {
+            font_size: 32.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            margin: UiRect::bottom(Val::Px(12.0)),
+            ..default()
+        },
+    ));
+}
+
+fn spawn_label(parent: &mut ChildBuilder, text: String) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+    ));
+}
+
+fn spawn_button(parent: &mut ChildBuilder, label: &str, button: MenuButton) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                min_width: Val::Px(40.0),
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            button,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_warning(parent: &mut ChildBuilder, text: String) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(WARNING_COLOR),
+    ));
+}
+
+/// A value with buttons either side to lower and raise it
+fn spawn_stepper(parent: &mut ChildBuilder, text: String, lower: MenuButton, raise: MenuButton) {
+    parent
+        .spawn(Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(12.0),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_button(row, "-", lower);
+            spawn_label(row, text);
+            spawn_button(row, "+", raise);
+        });
+}
+
+/// A save's thumbnail beside its load button, or why it can't be loaded, and anything that's
+/// changed since it was made
+fn spawn_save_row(parent: &mut ChildBuilder, save: &SaveEntry) {
+    let metadata = &save.metadata;
+    let label = format!(
+        "{} - seed {}, {} x {}, {:.0} minutes played",
+        metadata.name, metadata.seed, metadata.options.map_width, metadata.options.map_height,
+        metadata.playtime_secs / 60.0
+    );
+    parent
+        .spawn(Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(12.0),
+            ..default()
+        })
+        .with_children(|row| {
+            if let Some((handle, size)) = &save.thumbnail {
+                row.spawn((
+                    ImageNode::new(handle.clone()),
+                    Node {
+                        width: Val::Px(size.x),
+                        height: Val::Px(size.y),
+                        ..default()
+                    },
+                ));
+            }
+            row.spawn(Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            })
+            .with_children(|details| {
+                match &save.rejection {
+                    Some(rejection) => {
+                        spawn_label(details, label);
+                        spawn_warning(details, format!("{} - load it from the main menu", rejection));
+                    }
+                    None => {
+                        let button = MenuButton::LoadSave {
+                            path: save.path.clone(),
+                            seed: metadata.seed,
+                            map_width: metadata.options.map_width,
+                            map_height: metadata.options.map_height,
+                        };
+                        spawn_button(details, &label, button);
+                    }
+                }
+                for warning in &save.warnings {
+                    spawn_warning(details, warning.clone());
+                }
+            });
+        });
+}
+
+fn spawn_page(parent: &mut ChildBuilder, state: GameState, page: MenuPage, form: &NewWorldForm, config: &GameConfig, debug_state: &DebugDisplayState, saves: &[SaveEntry]) {
+    match (state, page) {
+        (GameState::Paused, MenuPage::Main) => {
+            spawn_title(parent, "Paused");
+            spawn_button(parent, "Resume", MenuButton::Resume);
+            spawn_button(parent, "Save", MenuButton::Save);
+            spawn_button(parent, "Load", MenuButton::Load);
+            spawn_button(parent, "Settings", MenuButton::Settings);
+            spawn_button(parent, "Quit", MenuButton::Quit);
+        }
+        (_, MenuPage::Main) => {
+            spawn_title(parent, &config.window_title);
+            spawn_button(parent, "New World", MenuButton::NewWorld);
+            spawn_button(parent, "Load", MenuButton::Load);
+            spawn_button(parent, "Settings", MenuButton::Settings);
+            spawn_button(parent, "Quit", MenuButton::Quit);
+        }
+        (_, MenuPage::NewWorld) => {
+            spawn_title(parent, "New World");
+            let seed = if form.seed.is_empty() { "random".to_string() } else { form.seed.clone() };
+            spawn_label(parent, format!("Seed: {} (type digits, backspace to delete)", seed));
+            spawn_stepper(
+                parent,
+                format!("Size: {} x {}", config.map_width, config.map_height),
+                MenuButton::SmallerMap,
+                MenuButton::LargerMap,
+            );
+            spawn_button(parent, "Start", MenuButton::StartWorld);
+            spawn_button(parent, "Back", MenuButton::Back);
+        }
+        (_, MenuPage::Load) => {
+            spawn_title(parent, "Load");
+            if saves.is_empty() {
+                spawn_label(parent, "No saves yet".to_string());
+            }
+            for save in saves {
+                spawn_save_row(parent, save);
+            }
+            spawn_button(parent, "Back", MenuButton::Back);
+        }
+        (_, MenuPage::Settings) => {
+            spawn_title(parent, "Settings");
+            spawn_stepper(
+                parent,
+                format!("Camera speed: {:.0}", config.camera_speed),
+                MenuButton::SlowerCamera,
+                MenuButton::FasterCamera,
+            );
+            spawn_stepper(
+                parent,
+                format!("Mouse sensitivity: {:.2}", config.mouse_sensitivity),
+                MenuButton::LessMouseSensitivity,
+                MenuButton::MoreMouseSensitivity,
+            );
+            spawn_stepper(
+                parent,
+                format!("Closest zoom: {:.2}", config.zoom_min),
+                MenuButton::LessZoomMin,
+                MenuButton::MoreZoomMin,
+            );
+            spawn_stepper(
+                parent,
+                format!("Furthest zoom: {:.0}", config.zoom_max),
+                MenuButton::LessZoomMax,
+                MenuButton::MoreZoomMax,
+            );
+            spawn_stepper(
+                parent,
+                format!("Volume: {:.0}%", config.volume * 100.0),
+                MenuButton::QuieterVolume,
+                MenuButton::LouderVolume,
+            );
+            spawn_button(parent, &format!("FPS counter: {}", on_off(config.show_fps)), MenuButton::ToggleFps);
+            spawn_button(
+                parent,
+                &format!("Behaviour icons: {}", on_off(config.show_behaviour_icons)),
+                MenuButton::ToggleBehaviourIcons,
+            );
+            spawn_button(
+                parent,
+                &format!("Damage numbers: {}", on_off(config.show_damage_numbers)),
+                MenuButton::ToggleDamageNumbers,
+            );
+            spawn_button(parent, &format!("Debug overlay: {:?}", debug_state.mode), MenuButton::CycleDebugOverlay);
+            spawn_label(parent, format!("Changes are saved to {} - the debug overlay is only for this game", SETTINGS_FILE));
+            spawn_button(parent, "Back", MenuButton::Back);
+        }
+    }
+}
+
+/// Every save on disk for the load screen. From the main menu a save's world is generated again at
+/// its own size, but over a paused world it has to be the same size.
+fn load_screen_saves(state: GameState, terrain_map: Option<&TerrainMap>, config_paths: &ConfigPaths, images: &mut Assets<Image>) -> Vec<SaveEntry> {
+    let current_hashes = hash_config_files(&CONFIG_FILES, config_paths);
+    list_saves(Path::new(SAVE_DIR))
+        .into_iter()
+        .map(|(path, metadata, thumbnail)| {
+            let rejection = terrain_map
+                .filter(|_| state == GameState::Paused)
+                .and_then(|terrain_map| metadata.map_size_mismatch(terrain_map.width, terrain_map.height));
+            SaveEntry {
+                path,
+                warnings: metadata.compatibility_warnings(&config_paths.mods, &current_hashes),
+                thumbnail: thumbnail.to_image().map(|image| (images.add(image), Vec2::new(thumbnail.width as f32, thumbnail.height as f32))),
+                rejection,
+                metadata,
+            }
+        })
+        .collect()
+}
+
+/// Show the screen for the menu page that's open, rebuilt whenever anything on it changes. The
+/// main menu covers the screen; the pause menu leaves the world showing around it.
+pub fn update_menu_screen(
+    state: Res<State<GameState>>,
+    page: Res<MenuPage>,
+    form: Res<NewWorldForm>,
+    config: Res<GameConfig>,
+    debug_state: Res<DebugDisplayState>,
+    terrain_map: Option<Res<TerrainMap>>,
+    config_paths: Res<ConfigPaths>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<MenuScreen>>,
+) {
+    if !(state.is_changed() || page.is_changed() || form.is_changed() || config.is_changed() || debug_state.is_changed()) {
+        return;
+    }
+    for screen in screen_query.iter() {
+        commands.entity(screen).despawn_recursive();
+    }
+    let state = *state.get();
+    if state == GameState::Playing {
+        return;
+    }
+
+    let saves = if *page == MenuPage::Load {
+        load_screen_saves(state, terrain_map.as_deref(), &config_paths, &mut images)
+    } else {
+        Vec::new()
+    };
+
+    let background = if state == GameState::MainMenu {
+        Color::srgb(0.05, 0.05, 0.08)
+    } else {
+        Color::srgba(0.05, 0.05, 0.08, 0.85)
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(background),
+            GlobalZIndex(10), // Over the game's own panels
+            MenuScreen,
+        ))
+        .with_children(|screen| spawn_page(screen, state, *page, &form, &config, &debug_state, &saves));
+}
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::control::Controlled;
+use crate::systems::den::HomeDen;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, PawnType, BehaviourConfig, BehaviourType};
+use crate::systems::taming::Owner;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs, TerrainType};
+
+/// Food is tracked per square region of this many tiles across
+pub const REGION_TILES: i32 = 16;
+/// How often region food is recounted and each pawn looks at what's left in its territory
+pub const MIGRATION_CHECK_SECS: f32 = 5.0;
+/// Richest regions a pawn tries to find a way to before staying put
+pub const MIGRATION_CANDIDATES: usize = 5;
+/// How close (in tiles) to its destination a migrating pawn has to get to settle there
+pub const MIGRATION_ARRIVAL_TILES: f32 = 2.0;
+
+/// Living pawns and tiles of each ground per region, recounted every `MIGRATION_CHECK_SECS`
+#[derive(Resource, Debug, Default)]
+pub struct RegionFood {
+    pub pawns: HashMap<(i32, i32), HashMap<PawnType, u32>>,
+    pub grounds: HashMap<(i32, i32), HashMap<TerrainType, u32>>,
+}
+
+impl RegionFood {
+    /// The region a tile falls in
+    pub fn region(tile: (i32, i32)) -> (i32, i32) {
+        (tile.0.div_euclid(REGION_TILES), tile.1.div_euclid(REGION_TILES))
+    }
+
+    /// Prey pawns plus grazeable tiles in a region for a pawn of this type
+    pub fn food_for(&self, region: (i32, i32), eater: &PawnType, pawn_config: &PawnConfig, ground_configs: &GroundConfigs) -> u32 {
+        let def = match pawn_config.get_pawn_definition(eater) {
+            Some(def) => def,
+            None => return 0,
+        };
+        let prey: u32 = self.pawns.get(&region).map_or(0, |pawns| {
+            pawns
+                .iter()
+                .filter(|(prey_type, _)| pawn_config.can_eat_by_tags(eater, prey_type))
+                .map(|(_, count)| count)
+                .sum()
+        });
+        let grazing: u32 = self.grounds.get(&region).map_or(0, |grounds| {
+            def.eats.grounds
+                .iter()
+                .filter_map(|ground| ground_configs.terrain_mapping.get(ground))
+                .filter_map(|terrain_type| grounds.get(terrain_type))
+                .sum()
+        });
+        prey + grazing
+    }
+}
+
+/// The region a pawn calls home, set where it first checks its food
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Territory {
+    pub region: (i32, i32),
+}
+
+/// A pawn on its way to a new territory
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Migrating {
+    pub destination: Vec2,
+    pub region: (i32, i32),
+}
+
+#[derive(Debug)]
+pub struct RegionFoodTimer(Timer);
+
+impl Default for RegionFoodTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(MIGRATION_CHECK_SECS, TimerMode::Repeating))
+    }
+}
+
+/// Recount each region's pawns and grounds. Counted straight away the first time round.
+pub fn update_region_food(
+    time: Res<Time>,
+    mut timer: Local<RegionFoodTimer>,
+    terrain_map: Res<TerrainMap>,
+    mut region_food: ResMut<RegionFood>,
+    pawn_query: Query<(&Transform, &Pawn, &Health)>,
+) {
+    let due = timer.0.tick(time.delta()).just_finished();
+    if !due && !region_food.grounds.is_empty() {
+        return;
+    }
+
+    region_food.grounds.clear();
+    for x in 0..terrain_map.width as i32 {
+        for y in 0..terrain_map.height as i32 {
+            let terrain_type = terrain_map.tiles[x as usize][y as usize];
+            *region_food.grounds
+                .entry(RegionFood::region((x, y)))
+                .or_default()
+                .entry(terrain_type)
+                .or_default() += 1;
+        }
+    }
+
+    region_food.pawns.clear();
+    for (transform, pawn, health) in pawn_query.iter() {
+        if health.current <= 0.0 {
+            continue;
+        }
+        if let Some(tile) = terrain_map.world_to_tile_coords(transform.translation.x, transform.translation.y) {
+            *region_food.pawns
+                .entry(RegionFood::region(tile))
+                .or_default()
+                .entry(pawn.pawn_type.clone())
+                .or_default() += 1;
+        }
+    }
+}
+
+/// Pawns with a `migrate` config keep an eye on the food in their territory. Once it drops
+/// below `min_food` they set off for the richest region at least `min_distance` regions away
+/// they can find a way to, and settle there as their new territory - leaving their den behind.
+pub fn migration_system(
+    time: Res<Time>,
+    mut ticker: Local<AiTicker>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    region_food: Res<RegionFood>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut pawn_query: Query<(Entity, &Transform, &Pawn, &Size, &Health, &mut CurrentBehavior, Option<&Territory>, Option<&Migrating>, Option<&PawnTarget>, Has<PathfindingRequest>, Has<PathfindingTask>, Has<Swimmer>), (Without<Owner>, Without<Controlled>)>, // Tamed pawns go where their owner goes, controlled ones where the player sends them
+) {
+    ticker.advance(time.delta_secs());
+    let regions = (
+        (terrain_map.width as i32 + REGION_TILES - 1) / REGION_TILES,
+        (terrain_map.height as i32 + REGION_TILES - 1) / REGION_TILES,
+    );
+
+    for (entity, transform, pawn, size, health, mut current_behavior, territory, migrating, pawn_target, has_path_request, has_path_task, is_swimmer) in pawn_query.iter_mut() {
+        if health.current <= 0.0 {
+            continue;
+        }
+        let migrate = match pawn_config.get_pawn_definition(&pawn.pawn_type).and_then(|def| def.migrate.as_ref()) {
+            Some(migrate) => migrate,
+            None => continue,
+        };
+        let position = transform.translation.truncate();
+
+        if let Some(migrating) = migrating {
+            if position.distance(migrating.destination) <= MIGRATION_ARRIVAL_TILES * config.tile_size {
+                println!("{} settled into a new territory", pawn.pawn_type);
+                current_behavior.state = "idle".to_string();
+                commands.entity(entity)
+                    .remove::<(Migrating, ActiveBehaviour, PawnTarget)>()
+                    .insert(Territory { region: migrating.region });
+            } else if pawn_target.is_none_or(|target| target.path.is_empty()) && !has_path_request && !has_path_task {
+                // The path ran out or couldn't be found
+                println!("{} gave up migrating", pawn.pawn_type);
+                current_behavior.state = "idle".to_string();
+                commands.entity(entity).remove::<(Migrating, ActiveBehaviour)>();
+            }
+            continue;
+        }
+
+        let tile = match terrain_map.world_to_tile_coords(position.x, position.y) {
+            Some(tile) => tile,
+            None => continue,
+        };
+        let home = match territory {
+            Some(territory) => territory.region,
+            None => {
+                commands.entity(entity).insert(Territory { region: RegionFood::region(tile) });
+                continue;
+            }
+        };
+        if !ticker.is_due(entity, MIGRATION_CHECK_SECS) {
+            continue;
+        }
+        let home_food = region_food.food_for(home, &pawn.pawn_type, &pawn_config, &ground_configs);
+        if home_food >= migrate.min_food {
+            continue;
+        }
+
+        // Richer regions far enough away, best first
+        let mut candidates: Vec<((i32, i32), u32)> = Vec::new();
+        for region_x in 0..regions.0 {
+            for region_y in 0..regions.1 {
+                let region = (region_x, region_y);
+                let distance = (region_x - home.0).abs().max((region_y - home.1).abs());
+                let food = region_food.food_for(region, &pawn.pawn_type, &pawn_config, &ground_configs);
+                if distance >= migrate.min_distance as i32 && food >= migrate.min_food && food > home_food {
+                    candidates.push((region, food));
+                }
+            }
+        }
+        candidates.sort_by(|(a_region, a_food), (b_region, b_food)| b_food.cmp(a_food).then(a_region.cmp(b_region)));
+
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+        let current_pos = (position.x, position.y);
+        let destination = candidates.iter().take(MIGRATION_CANDIDATES).find_map(|&(region, _)| {
+            let centre = (
+                (region.0 * REGION_TILES + REGION_TILES / 2).min(terrain_map.width as i32 - 1),
+                (region.1 * REGION_TILES + REGION_TILES / 2).min(terrain_map.height as i32 - 1),
+            );
+            let centre_pos = terrain_map.tile_to_world_coords(centre.0, centre.1);
+            terrain_map
+                .find_nearest_passable_tile(centre_pos, &movement_configs)
+                .filter(|&goal_pos| terrain_map.are_connected(current_pos, goal_pos, size.value, &movement_configs))
+                .map(|goal_pos| (region, goal_pos))
+        });
+        let (region, goal_pos) = match destination {
+            Some(destination) => destination,
+            None => continue,
+        };
+
+        println!("{} is migrating away from its territory for lack of food ({} < {})", pawn.pawn_type, home_food, migrate.min_food);
+        let migrate_behaviour = BehaviourConfig::Simple(BehaviourType::Migrate);
+        current_behavior.state = migrate_behaviour.name().to_string();
+        commands.entity(entity)
+            .remove::<(HomeDen, PawnTarget, PathfindingRequest)>()
+            .insert((
+                Migrating { destination: Vec2::new(goal_pos.0, goal_pos.1), region },
+                ActiveBehaviour { action: migrate_behaviour },
+            ));
+        request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, goal_pos, size.value);
+    }
+}
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use bevy::image::ImageSampler;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::RelativeCursorPosition;
+use crate::systems::camera::{CameraController, CameraFollow};
+use crate::systems::camera_bookmarks::CameraBookmarks;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::pawn::Pawn;
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::statistics::is_predator;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs, TerrainChanges};
+
+/// Length in pixels of the minimap's longer side
+const MINIMAP_SIZE: f32 = 192.0;
+/// Width and height in pixels of a pawn's dot
+const DOT_SIZE: f32 = 3.0;
+const PREDATOR_DOT_COLOR: Color = Color::srgb(0.95, 0.25, 0.2);
+const PREY_DOT_COLOR: Color = Color::WHITE;
+
+/// The texture the minimap shows, one pixel per tile
+#[derive(Resource)]
+pub struct MinimapImage(pub Handle<Image>);
+
+/// The minimap panel in the bottom-left corner
+#[derive(Component)]
+pub struct Minimap;
+
+/// A pawn's dot on the minimap
+#[derive(Component)]
+pub struct MinimapDot {
+    pub pawn: Entity,
+}
+
+/// Outline of what the camera can see
+#[derive(Component)]
+pub struct MinimapViewport;
+
+/// Size in pixels of the minimap, keeping the map's proportions
+pub fn minimap_size(terrain_map: &TerrainMap) -> Vec2 {
+    let longest = terrain_map.width.max(terrain_map.height).max(1) as f32;
+    Vec2::new(terrain_map.width as f32, terrain_map.height as f32) / longest * MINIMAP_SIZE
+}
+
+/// Where a tile's pixel starts in the minimap's RGBA data. Rows run top to bottom and the world's
+/// y runs upwards, so the last row of tiles comes first.
+fn pixel_offset(terrain_map: &TerrainMap, tile: (u32, u32)) -> usize {
+    let row = terrain_map.height - 1 - tile.1;
+    ((row * terrain_map.width + tile.0) * 4) as usize
+}
+
+fn paint_tile(data: &mut [u8], terrain_map: &TerrainMap, ground_configs: &GroundConfigs, tile: (u32, u32), terrain_type: usize) {
+    let offset = pixel_offset(terrain_map, tile);
+    let [r, g, b] = ground_configs.minimap_color(terrain_type);
+    data[offset..offset + 4].copy_from_slice(&[r, g, b, 255]);
+}
+
+/// RGBA pixels for the whole map, each tile colored by its ground
+pub fn minimap_pixels(terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> Vec<u8> {
+    let mut data = vec![0; (terrain_map.width * terrain_map.height * 4) as usize];
+    for x in 0..terrain_map.width {
+        for y in 0..terrain_map.height {
+            let terrain_type = terrain_map.tiles[x as usize][y as usize];
+            paint_tile(&mut data, terrain_map, ground_configs, (x, y), terrain_type);
+        }
+    }
+    data
+}
+
+/// A world position as a fraction of the minimap, from (0, 0) in the top-left corner to (1, 1)
+/// in the bottom-right
+pub fn world_to_minimap(position: Vec2, terrain_map: &TerrainMap) -> Vec2 {
+    let map_size = Vec2::new(terrain_map.width as f32, terrain_map.height as f32) * terrain_map.tile_size;
+    let fraction = (position + map_size / 2.0) / map_size;
+    Vec2::new(fraction.x, 1.0 - fraction.y)
+}
+
+/// The world position at a fraction of the minimap
+pub fn minimap_to_world(fraction: Vec2, terrain_map: &TerrainMap) -> Vec2 {
+    let map_size = Vec2::new(terrain_map.width as f32, terrain_map.height as f32) * terrain_map.tile_size;
+    Vec2::new(fraction.x, 1.0 - fraction.y) * map_size - map_size / 2.0
+}
+
+/// The part of the minimap a view `half_view` across each way from `center` covers, as fractions
+/// of the minimap and cut off at its edges
+pub fn viewport_on_minimap(center: Vec2, half_view: Vec2, terrain_map: &TerrainMap) -> Rect {
+    let corner = world_to_minimap(center - half_view, terrain_map);
+    let opposite = world_to_minimap(center + half_view, terrain_map);
+    Rect::from_corners(corner, opposite).intersect(Rect::new(0.0, 0.0, 1.0, 1.0))
+}
+
+/// Paint the map once it's generated and put the minimap in the corner
+pub fn setup_minimap(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let mut image = Image::new(
+        Extent3d { width: terrain_map.width, height: terrain_map.height, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        minimap_pixels(&terrain_map, &ground_configs),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.sampler = ImageSampler::nearest(); // Crisp tiles rather than a blur
+    let handle = images.add(image);
+
+    let size = minimap_size(&terrain_map);
+    commands
+        .spawn((
+            ImageNode::new(handle.clone()),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                width: Val::Px(size.x),
+                height: Val::Px(size.y),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+            RelativeCursorPosition::default(),
+            Minimap,
+        ))
+        .with_children(|minimap| {
+            minimap.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor(Color::WHITE),
+                MinimapViewport,
+            ));
+        });
+    commands.insert_resource(MinimapImage(handle));
+}
+
+/// Repaint the tiles whose ground changed. Runs before the changes are cleared by
+/// update_terrain_visuals.
+pub fn update_minimap_terrain(
+    terrain_changes: Res<TerrainChanges>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    minimap_image: Option<Res<MinimapImage>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if terrain_changes.changed_tiles.is_empty() {
+        return;
+    }
+    let image = match minimap_image.and_then(|minimap_image| images.get_mut(&minimap_image.0)) {
+        Some(image) => image,
+        None => return,
+    };
+    for &(x, y, terrain_type) in &terrain_changes.changed_tiles {
+        if x < terrain_map.width && y < terrain_map.height {
+            paint_tile(&mut image.data, &terrain_map, &ground_configs, (x, y), terrain_type);
+        }
+    }
+}
+
+/// Move the pawns' dots and the camera's outline to match the world
+pub fn update_minimap_markers(
+    terrain_map: Res<TerrainMap>,
+    pawn_config: Res<PawnConfig>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Transform, &OrthographicProjection), (With<Camera>, With<CameraController>)>,
+    pawn_query: Query<(Entity, &Transform, &Pawn)>,
+    minimap_query: Query<Entity, With<Minimap>>,
+    mut dot_query: Query<(Entity, &MinimapDot, &mut Node), Without<MinimapViewport>>,
+    mut viewport_query: Query<&mut Node, (With<MinimapViewport>, Without<MinimapDot>)>,
+    mut commands: Commands,
+) {
+    let minimap = match minimap_query.get_single() {
+        Ok(minimap) => minimap,
+        Err(_) => return,
+    };
+    let size = minimap_size(&terrain_map);
+    let dot_position = |transform: &Transform| {
+        world_to_minimap(transform.translation.truncate(), &terrain_map) * size - DOT_SIZE / 2.0
+    };
+
+    let mut drawn = Vec::new();
+    for (dot, minimap_dot, mut node) in dot_query.iter_mut() {
+        match pawn_query.get(minimap_dot.pawn) {
+            Ok((_, transform, _)) => {
+                let position = dot_position(transform);
+                node.left = Val::Px(position.x);
+                node.top = Val::Px(position.y);
+                drawn.push(minimap_dot.pawn);
+            }
+            Err(_) => commands.entity(dot).despawn_recursive(),
+        }
+    }
+    for (pawn, transform, pawn_info) in pawn_query.iter() {
+        if drawn.contains(&pawn) {
+            continue;
+        }
+        let position = dot_position(transform);
+        let color = if is_predator(&pawn_config, &pawn_info.pawn_type) { PREDATOR_DOT_COLOR } else { PREY_DOT_COLOR };
+        commands.entity(minimap).with_children(|minimap| {
+            minimap.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(position.x),
+                    top: Val::Px(position.y),
+                    width: Val::Px(DOT_SIZE),
+                    height: Val::Px(DOT_SIZE),
+                    ..default()
+                },
+                BackgroundColor(color),
+                MinimapDot { pawn },
+            ));
+        });
+    }
+
+    let (window, (camera_transform, projection)) = match (windows.get_single(), camera_query.get_single()) {
+        (Ok(window), Ok(camera)) => (window, camera),
+        _ => return,
+    };
+    let half_view = window.size() * projection.scale / 2.0;
+    let viewport = viewport_on_minimap(camera_transform.translation.truncate(), half_view, &terrain_map);
+    for mut node in viewport_query.iter_mut() {
+        node.left = Val::Px(viewport.min.x * size.x);
+        node.top = Val::Px(viewport.min.y * size.y);
+        node.width = Val::Px(viewport.width() * size.x);
+        node.height = Val::Px(viewport.height() * size.y);
+    }
+}
+
+/// Whether the cursor is over the minimap, so clicks there don't reach the world underneath
+pub fn cursor_over_minimap(minimap_query: &Query<&RelativeCursorPosition, With<Minimap>>) -> bool {
+    minimap_query.iter().any(|cursor| cursor.mouse_over())
+}
+
+/// Clicking the minimap centres the camera on that spot
+pub fn minimap_click(
+    actions: ActionInput,
+    terrain_map: Res<TerrainMap>,
+    minimap_query: Query<&RelativeCursorPosition, With<Minimap>>,
+    mut follow: ResMut<CameraFollow>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+) {
+    if !actions.just_pressed(Action::Select) {
+        return;
+    }
+    let fraction = match minimap_query.iter().find(|cursor| cursor.mouse_over()).and_then(|cursor| cursor.normalized) {
+        Some(fraction) => fraction,
+        None => return,
+    };
+    let target = minimap_to_world(fraction, &terrain_map);
+    follow.target = None;
+    bookmarks.travel = None;
+    for mut transform in camera_query.iter_mut() {
+        transform.translation = target.extend(transform.translation.z);
+    }
+}
@@ -1,13 +1,82 @@
+pub mod aging;
 pub mod ai;
+pub mod ai_tick;
+pub mod ambush;
+pub mod animation;
+pub mod attachment;
 pub mod async_pathfinding;
+pub mod attack;
+pub mod audio;
+pub mod behaviour_icons;
+pub mod bestiary;
 pub mod camera;
+pub mod camera_bookmarks;
+pub mod charge;
+pub mod combat;
+pub mod combat_feedback;
+pub mod config_files;
+pub mod config_validation;
+pub mod control;
+pub mod culling;
 pub mod debug_display;
+pub mod den;
+pub mod display;
+pub mod eating;
+pub mod faction;
+pub mod fog_of_war;
+pub mod follow;
+pub mod footsteps;
 pub mod fps_counter;
+pub mod game_state;
+pub mod gamepad;
+pub mod grazing;
+pub mod growth;
+pub mod herd;
+pub mod group_pathfinding;
+pub mod infusion;
 pub mod input;
+pub mod interpolation;
+pub mod inventory;
+pub mod keybinds;
+pub mod level_of_detail;
+pub mod lighting;
+pub mod memory;
+pub mod menu;
+pub mod migration;
+pub mod minimap;
+pub mod needs;
+pub mod pack_hunt;
+pub mod panic;
+pub mod passability_overlay;
 pub mod pawn;
 pub mod pawn_config;
 pub mod pathfinding_cache;
+pub mod pathfinding_heatmap;
+pub mod pathfinding_stats;
+pub mod perception;
+pub mod population;
+pub mod render_stats;
+pub mod replay;
+pub mod save;
+pub mod scent;
+pub mod scripting;
+pub mod seasons;
+pub mod selection;
+pub mod shadows;
+pub mod soundscape;
+pub mod sim_rng;
+pub mod sim_tick;
+pub mod spatial_grid;
 pub mod spawn;
+pub mod statistics;
+pub mod status_effects;
+pub mod taming;
+pub mod terrain_effects;
+pub mod tile_tooltip;
 pub mod tilemap;
-pub mod water_shader;
-pub mod world_gen;
\ No newline at end of file
+pub mod utility;
+pub mod vegetation;
+pub mod weather;
+pub mod world_clock;
+pub mod world_gen;
+pub mod y_sort;
\ No newline at end of file
@@ -0,0 +1,333 @@
+use bevy::prelude::*;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingTask, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::den::{Den, HomeDen, DEN_SLEEP_RADIUS_TILES};
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Endurance, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, PawnDefinition, BehaviourConfig, BehaviourType, NeedKind};
+use crate::systems::world_clock::WorldClock;
+use crate::systems::seasons::Seasons;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// Once urgent, a need keeps the pawn busy until it's this much (as a fraction of max) above its threshold
+pub const NEED_SATISFIED_MARGIN: f32 = 0.2;
+/// Fraction of max thirst quenched per second of drinking
+pub const DRINK_RATE: f32 = 0.25;
+/// Fraction of max energy recovered per second of sleep
+pub const SLEEP_RECOVERY_RATE: f32 = 0.1;
+/// Seconds between a thirsty pawn's searches for water
+pub const DRINK_SEARCH_INTERVAL: f32 = 1.0;
+/// How far (in tiles) thirsty pawns look for water
+pub const DRINK_SEARCH_RADIUS_TILES: i32 = 12;
+
+#[derive(Component)]
+pub struct Thirst {
+    pub current: f32,
+    pub max: f32,
+}
+
+#[derive(Component)]
+pub struct Energy {
+    pub current: f32,
+    pub max: f32,
+}
+
+/// Pawn is sleeping - it stays put while its energy and endurance recover, but defends itself poorly
+#[derive(Component)]
+pub struct Asleep;
+
+#[derive(Component)]
+pub struct DrinkingAI {
+    pub search_timer: f32,
+}
+
+impl DrinkingAI {
+    pub fn new() -> Self {
+        Self {
+            search_timer: DRINK_SEARCH_INTERVAL, // Look straight away
+        }
+    }
+}
+
+/// How full each of a pawn's needs is, as a fraction of its max
+pub fn need_fractions(endurance: &Endurance, thirst: Option<&Thirst>, energy: Option<&Energy>) -> Vec<(NeedKind, f32)> {
+    let mut fractions = vec![(NeedKind::Hunger, endurance.current / endurance.max)];
+    if let Some(thirst) = thirst {
+        fractions.push((NeedKind::Thirst, thirst.current / thirst.max));
+    }
+    if let Some(energy) = energy {
+        fractions.push((NeedKind::Energy, energy.current / energy.max));
+    }
+    fractions
+}
+
+/// The need a behaviour satisfies
+pub fn need_tended_by(action: &BehaviourConfig) -> Option<NeedKind> {
+    match action {
+        BehaviourConfig::Simple(BehaviourType::HuntSolo | BehaviourType::HuntPack | BehaviourType::Eat) => Some(NeedKind::Hunger),
+        BehaviourConfig::Simple(BehaviourType::Drink) => Some(NeedKind::Thirst),
+        BehaviourConfig::Simple(BehaviourType::Sleep) => Some(NeedKind::Energy),
+        _ => None,
+    }
+}
+
+/// The need a pawn should see to: the one it's already tending until that's satisfied,
+/// otherwise the lowest of the needs at or below their threshold
+pub fn urgent_need(definition: &PawnDefinition, fractions: &[(NeedKind, f32)], tending: Option<NeedKind>) -> Option<NeedKind> {
+    if let Some(need) = tending {
+        let unsatisfied = fractions
+            .iter()
+            .any(|&(other, fraction)| other == need && fraction <= definition.need_threshold(need) + NEED_SATISFIED_MARGIN);
+        if unsatisfied {
+            return Some(need);
+        }
+    }
+
+    fractions
+        .iter()
+        .filter(|&&(need, fraction)| fraction <= definition.need_threshold(need))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|&(need, _)| need)
+}
+
+/// Needs run down over time - sleeping pawns don't tire
+pub fn need_decay_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    seasons: Option<Res<Seasons>>,
+    mut pawn_query: Query<(&Pawn, &mut Endurance, Option<&mut Thirst>, Option<&mut Energy>, Has<Asleep>)>,
+) {
+    let delta = time.delta_secs();
+    let hunger_pace = seasons.map_or(1.0, |seasons| seasons.endurance_decay);
+
+    for (pawn, mut endurance, thirst, energy, asleep) in pawn_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+
+        if let Some(hunger) = definition.need_config(NeedKind::Hunger) {
+            endurance.current = (endurance.current - hunger.decay * hunger_pace * delta).max(0.0);
+        }
+        if let (Some(mut thirst), Some(config)) = (thirst, definition.need_config(NeedKind::Thirst)) {
+            thirst.current = (thirst.current - config.decay * delta).max(0.0);
+        }
+        if let (Some(mut energy), Some(config)) = (energy, definition.need_config(NeedKind::Energy)) {
+            if !asleep {
+                energy.current = (energy.current - config.decay * delta).max(0.0);
+            }
+        }
+    }
+}
+
+fn has_behaviour_for(pawn_config: &PawnConfig, pawn: &Pawn, state: &str) -> bool {
+    pawn_config
+        .get_behaviour_config(&pawn.pawn_type, state)
+        .is_some_and(|behaviour| !matches!(behaviour, BehaviourConfig::Simple(BehaviourType::Null)))
+}
+
+/// State-driven pawns switch to the behaviour state of their most urgent need (looking_for_food,
+/// thirsty or tired) and back to idle once it's satisfied. Outside their scheduled active hours
+/// they switch to resting instead of idle. States without a behaviour configured are ignored, as
+/// are pawns with a `utility` entry.
+pub fn needs_behaviour_switching_system(
+    pawn_config: Res<PawnConfig>,
+    world_clock: Option<Res<WorldClock>>,
+    mut pawn_query: Query<(&Pawn, &Endurance, Option<&Thirst>, Option<&Energy>, &mut CurrentBehavior), Without<ActiveBehaviour>>, // Behaviour trees switch on their own conditions
+) {
+    for (pawn, endurance, thirst, energy, mut current_behavior) in pawn_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+
+        // Utility-driven pawns weigh their needs up themselves
+        if definition.utility.is_some() {
+            continue;
+        }
+        // Running for its life comes first - perception switches the pawn back once it's safe
+        if current_behavior.state == "hunted" {
+            continue;
+        }
+
+        let fractions: Vec<(NeedKind, f32)> = need_fractions(endurance, thirst, energy)
+            .into_iter()
+            .filter(|(need, _)| has_behaviour_for(&pawn_config, pawn, need.state()))
+            .collect();
+        let tending = NeedKind::ALL.into_iter().find(|need| need.state() == current_behavior.state);
+        let resting = current_behavior.state == "resting";
+        let rest_time = world_clock.as_ref().is_some_and(|clock| definition.schedule.rests_during(clock.phase()))
+            && has_behaviour_for(&pawn_config, pawn, "resting");
+
+        match urgent_need(definition, &fractions, tending) {
+            Some(need) if need.state() != current_behavior.state => {
+                let fraction = fractions.iter().find(|(other, _)| *other == need).map_or(0.0, |(_, fraction)| *fraction);
+                println!("{} switching to {} behavior ({:?}: {:.1}%)",
+                         pawn.pawn_type, need.state(), need, fraction * 100.0);
+                current_behavior.state = need.state().to_string();
+            }
+            None if rest_time && !resting => {
+                println!("{} switching to resting behavior", pawn.pawn_type);
+                current_behavior.state = "resting".to_string();
+            }
+            None if !rest_time && (tending.is_some() || resting) => {
+                println!("{} switching back to idle behavior", pawn.pawn_type);
+                current_behavior.state = "idle".to_string();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_drinking(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::Drink))
+    )
+}
+
+/// Whether a pawn at this tile can reach drinkable ground - on it or right next to it
+pub fn can_drink_at(terrain_map: &TerrainMap, ground_configs: &GroundConfigs, tile: (i32, i32)) -> bool {
+    for dx in -1..=1 {
+        for dy in -1..=1 {
+            let (x, y) = (tile.0 + dx, tile.1 + dy);
+            if x >= 0 && y >= 0 && x < terrain_map.width as i32 && y < terrain_map.height as i32
+                && ground_configs.is_drinkable(terrain_map.tiles[x as usize][y as usize])
+            {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// System to add DrinkingAI component to thirsty pawns with drink behavior
+pub fn setup_drinking_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    drinker_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), (With<Thirst>, Without<DrinkingAI>)>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in drinker_query.iter() {
+        if is_drinking(&pawn_config, pawn, current_behavior, active_behaviour) {
+            commands.entity(entity).insert(DrinkingAI::new());
+        }
+    }
+}
+
+/// Drinking pawns walk to the nearest spot next to drinkable ground and drink there
+pub fn drink_ai_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut commands: Commands,
+    mut drinker_query: Query<(
+        Entity, &Transform, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>, &Size, &mut DrinkingAI, &mut Thirst,
+        Has<PawnTarget>, Has<PathfindingRequest>, Has<PathfindingTask>, Has<Swimmer>,
+    )>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, transform, pawn, current_behavior, active_behaviour, size, mut drinking_ai, mut thirst,
+         has_target, has_request, has_task, is_swimmer) in drinker_query.iter_mut() {
+        if !is_drinking(&pawn_config, pawn, current_behavior, active_behaviour) {
+            continue;
+        }
+        // Still on the way somewhere
+        if has_target || has_request || has_task {
+            continue;
+        }
+
+        let position = (transform.translation.x, transform.translation.y);
+        let current_tile = match terrain_map.world_to_tile_coords(position.0, position.1) {
+            Some(tile) => tile,
+            None => continue,
+        };
+
+        if can_drink_at(&terrain_map, &ground_configs, current_tile) {
+            thirst.current = (thirst.current + DRINK_RATE * thirst.max * delta).min(thirst.max);
+            continue;
+        }
+
+        drinking_ai.search_timer += delta;
+        if drinking_ai.search_timer < DRINK_SEARCH_INTERVAL {
+            continue;
+        }
+        drinking_ai.search_timer = 0.0;
+
+        let movement_configs = ground_configs.for_movement(is_swimmer);
+        let spot = terrain_map.find_nearest_reachable_tile(position, DRINK_SEARCH_RADIUS_TILES, size.value, &movement_configs, |tile| {
+            can_drink_at(&terrain_map, &ground_configs, tile)
+        });
+        if let Some((tile_x, tile_y)) = spot {
+            let target_pos = terrain_map.tile_to_world_coords(tile_x, tile_y);
+            commands.entity(entity).insert(PathfindingRequest::new(position, target_pos, size.value));
+        }
+    }
+}
+
+/// Sleeping pawns drop what they were doing, stay put and recover energy and endurance; they
+/// wake as soon as their behaviour changes. Pawns with a den make their way home first.
+pub fn sleep_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut sleeper_query: Query<(
+        Entity, &Transform, &Pawn, &Size, &CurrentBehavior, Option<&ActiveBehaviour>, &mut Endurance, Option<&mut Energy>,
+        Option<&HomeDen>, Option<&PawnTarget>, Has<Asleep>, Has<Swimmer>, Has<PathfindingRequest>,
+    )>,
+    den_query: Query<&Transform, With<Den>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, transform, pawn, size, current_behavior, active_behaviour, mut endurance, energy, home, pawn_target, asleep, is_swimmer, has_path_request) in sleeper_query.iter_mut() {
+        let sleeping = matches!(
+            resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour),
+            Some(BehaviourConfig::Simple(BehaviourType::Sleep))
+        );
+
+        if !sleeping {
+            if asleep {
+                commands.entity(entity).remove::<Asleep>();
+                println!("{} woke up", pawn.pawn_type);
+            }
+            continue;
+        }
+
+        if !asleep {
+            // Head home to sleep, as long as the den can be reached
+            let den_pos = home.and_then(|home| den_query.get(home.den).ok()).map(|den| den.translation.truncate());
+            if let Some(den_pos) = den_pos {
+                let position = transform.translation.truncate();
+                let movement_configs = ground_configs.for_movement(is_swimmer);
+                let current_pos = (position.x, position.y);
+                let den = (den_pos.x, den_pos.y);
+                if position.distance(den_pos) > DEN_SLEEP_RADIUS_TILES * terrain_map.tile_size
+                    && terrain_map.are_connected(current_pos, den, size.value, &movement_configs)
+                {
+                    let heading_home = pawn_target.is_some_and(|target| {
+                        !target.path.is_empty() && target.target_position.truncate().distance(den_pos) <= terrain_map.tile_size
+                    });
+                    if !heading_home && !has_path_request {
+                        request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, den, size.value);
+                    }
+                    continue;
+                }
+            }
+
+            commands.entity(entity)
+                .remove::<(PawnTarget, PathfindingRequest, PathfindingTask)>()
+                .insert(Asleep);
+            println!("{} fell asleep", pawn.pawn_type);
+        }
+        if let Some(mut energy) = energy {
+            energy.current = (energy.current + SLEEP_RECOVERY_RATE * energy.max * delta).min(energy.max);
+        }
+        if let Some(definition) = pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            let regen = definition.rest_config().endurance_regen;
+            endurance.current = (endurance.current + regen * endurance.max * delta).min(endurance.max);
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::aging::Age;
+use crate::systems::growth::Grown;
+use crate::systems::attack::{Attacking, blow_damage};
+use crate::systems::needs::Asleep;
+use crate::systems::perception::Vision;
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour};
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingPriority};
+use crate::systems::combat::CombatEvent;
+use crate::systems::eating::start_eating;
+use crate::systems::group_pathfinding::{FormationLeader, can_follow, request_group_pathfinding};
+use crate::systems::charge::{Charge, Stunned};
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// Seconds between a pack leader's searches for new prey
+pub const PACK_SEARCH_INTERVAL: f32 = 2.0;
+/// Pack hunters close in to this fraction of their reach, so they end up ringed around the prey
+pub const APPROACH_REACH_FRACTION: f32 = 0.75;
+/// Packs travel in formation behind their leader until it's this close (in tiles) to the prey,
+/// then spread out to surround it
+pub const SURROUND_DISTANCE_TILES: f32 = 6.0;
+
+#[derive(Component)]
+pub struct HuntPackAI {
+    pub leader: Option<Entity>,        // The pack leader - itself for leaders, None until a pack forms
+    pub target_entity: Option<Entity>, // Only used by leaders - members hunt their leader's target
+    pub last_attack_time: f32,
+    pub search_timer: f32,
+    pub unreachable_target: Option<Entity>, // Last pack target we couldn't path to
+}
+
+impl HuntPackAI {
+    pub fn new() -> Self {
+        Self {
+            leader: None,
+            target_entity: None,
+            last_attack_time: 0.0,
+            search_timer: 0.0,
+            unreachable_target: None,
+        }
+    }
+}
+
+fn is_hunting_in_pack(pawn_config: &PawnConfig, pawn: &Pawn, current_behavior: &CurrentBehavior, active_behaviour: Option<&ActiveBehaviour>) -> bool {
+    matches!(
+        resolve_behaviour(pawn_config, pawn, current_behavior, active_behaviour),
+        Some(BehaviourConfig::Simple(BehaviourType::HuntPack))
+    )
+}
+
+// System to add HuntPackAI component to pawns with hunt_pack behavior
+pub fn setup_hunt_pack_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    hunt_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), (With<Pawn>, Without<HuntPackAI>)>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in hunt_query.iter() {
+        if is_hunting_in_pack(&pawn_config, pawn, current_behavior, active_behaviour) {
+            commands.entity(entity).insert(HuntPackAI::new());
+        }
+    }
+}
+
+/// Group pack hunters of the same type into packs around a leader. Members stay with their
+/// leader while they're within its coordination radius; everyone else joins the nearest pack
+/// with room or starts a new one.
+pub fn form_hunting_packs(
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    mut hunter_query: Query<(Entity, &Transform, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>, &mut HuntPackAI)>,
+) {
+    let mut hunters: Vec<(Entity, Vec3, String, Option<Entity>)> = Vec::new();
+    for (entity, transform, pawn, current_behavior, active_behaviour, mut pack_ai) in hunter_query.iter_mut() {
+        if is_hunting_in_pack(&pawn_config, pawn, current_behavior, active_behaviour) {
+            hunters.push((entity, transform.translation, pawn.pawn_type.clone(), pack_ai.leader));
+        } else if pack_ai.leader.is_some() {
+            pack_ai.leader = None;
+            pack_ai.target_entity = None;
+        }
+    }
+    hunters.sort_by_key(|(entity, _, _, _)| *entity);
+
+    let within_radius = |pawn_type: &str, a: Vec3, b: Vec3| {
+        let pack = pawn_config.get_pawn_definition(pawn_type).map(|def| def.pack_config()).unwrap_or_default();
+        a.distance(b) <= pack.coordination_radius as f32 * config.tile_size
+    };
+    let max_size = |pawn_type: &str| {
+        pawn_config.get_pawn_definition(pawn_type).map(|def| def.pack_config()).unwrap_or_default().max_size.max(1) as usize
+    };
+
+    // Leaders that are still hunting keep their packs
+    let mut pack_sizes: HashMap<Entity, (usize, Vec3, String)> = hunters
+        .iter()
+        .filter(|(entity, _, _, leader)| *leader == Some(*entity))
+        .map(|(entity, position, pawn_type, _)| (*entity, (1, *position, pawn_type.clone())))
+        .collect();
+    let mut assignments: HashMap<Entity, Entity> = pack_sizes.keys().map(|&leader| (leader, leader)).collect();
+
+    // Members that are still close to their leader, while there's room
+    for (entity, position, pawn_type, leader) in &hunters {
+        if let Some(leader) = leader {
+            if *leader == *entity {
+                continue;
+            }
+            if let Some((size, leader_position, _)) = pack_sizes.get_mut(leader) {
+                if *size < max_size(pawn_type) && within_radius(pawn_type, *position, *leader_position) {
+                    *size += 1;
+                    assignments.insert(*entity, *leader);
+                }
+            }
+        }
+    }
+
+    // Everyone else joins the nearest pack of their kind with room, or leads a new one
+    for (entity, position, pawn_type, _) in &hunters {
+        if assignments.contains_key(entity) {
+            continue;
+        }
+        let nearest_pack = pack_sizes
+            .iter()
+            .filter(|(_, (size, leader_position, leader_type))| {
+                leader_type == pawn_type && *size < max_size(pawn_type) && within_radius(pawn_type, *position, *leader_position)
+            })
+            .min_by(|a, b| position.distance(a.1 .1).total_cmp(&position.distance(b.1 .1)))
+            .map(|(leader, _)| *leader);
+
+        let leader = match nearest_pack {
+            Some(leader) => leader,
+            None => {
+                pack_sizes.insert(*entity, (0, *position, pawn_type.clone()));
+                *entity
+            }
+        };
+        if let Some((size, _, _)) = pack_sizes.get_mut(&leader) {
+            *size += 1;
+        }
+        assignments.insert(*entity, leader);
+    }
+
+    for (entity, _, _, _, _, mut pack_ai) in hunter_query.iter_mut() {
+        if let Some(&leader) = assignments.get(&entity) {
+            if pack_ai.leader != Some(leader) {
+                pack_ai.leader = Some(leader);
+                pack_ai.target_entity = None;
+            }
+        }
+    }
+}
+
+pub fn hunt_pack_ai_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut commands: Commands,
+    mut hunter_query: Query<(Entity, &Transform, &Pawn, &Size, &mut HuntPackAI, Option<&PawnTarget>, Has<PathfindingRequest>, Has<Swimmer>, Option<&mut Attacking>, Has<FormationLeader>), (With<Pawn>, Without<Charge>, Without<Stunned>)>,
+    mut prey_query: Query<(Entity, &Transform, &Pawn, &mut Health), (With<Pawn>, Without<HuntPackAI>)>,
+    age_query: Query<&Age>,
+    grown_query: Query<&Grown>,
+    sleeper_query: Query<(), With<Asleep>>,
+    vision_query: Query<&Vision>,
+    spatial_grid: Res<SpatialGrid>,
+) {
+    // Pack rosters (in a stable order, for approach angles), where everyone is and who the leaders
+    // are after, as of the start of the frame
+    let mut packs: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    let mut leader_positions: HashMap<Entity, Vec3> = HashMap::new();
+    let mut leader_targets: HashMap<Entity, Option<Entity>> = HashMap::new();
+    let mut walkers: HashMap<Entity, (Vec2, f32, bool)> = HashMap::new();
+    for (entity, transform, _, size, pack_ai, _, _, is_swimmer, _, _) in hunter_query.iter() {
+        if let Some(leader) = pack_ai.leader {
+            packs.entry(leader).or_default().push(entity);
+            walkers.insert(entity, (transform.translation.truncate(), size.value, is_swimmer));
+            if leader == entity {
+                leader_positions.insert(entity, transform.translation);
+                leader_targets.insert(entity, pack_ai.target_entity);
+            }
+        }
+    }
+    for members in packs.values_mut() {
+        members.sort();
+    }
+
+    for (hunter_entity, hunter_transform, hunter_pawn, hunter_size, mut pack_ai, current_target, awaiting_path, is_swimmer, attacking, leads_formation) in hunter_query.iter_mut() {
+        let leader = match pack_ai.leader {
+            Some(leader) => leader,
+            None => continue,
+        };
+        let is_leader = leader == hunter_entity;
+
+        let hunter_def = match pawn_config.get_pawn_definition(&hunter_pawn.pawn_type) {
+            Some(def) => def,
+            None => continue,
+        };
+        let reach_distance = hunter_def.reach as f32 * config.tile_size;
+
+        pack_ai.last_attack_time += time.delta_secs();
+        pack_ai.search_timer += time.delta_secs();
+
+        // Leaders pick the prey for the whole pack
+        if is_leader {
+            if let Some(target) = pack_ai.target_entity {
+                if !prey_query.get(target).is_ok_and(|(_, _, _, health)| health.current > 0.0) {
+                    pack_ai.target_entity = None;
+                }
+            }
+
+            if pack_ai.target_entity.is_none() && pack_ai.search_timer >= PACK_SEARCH_INTERVAL {
+                pack_ai.search_timer = 0.0;
+
+                let hunter_pos = (hunter_transform.translation.x, hunter_transform.translation.y);
+                let movement_configs = ground_configs.for_movement(is_swimmer);
+                let vision = vision_query.get(hunter_entity).ok();
+                let unreachable_target = pack_ai.unreachable_target;
+                let closest_target = spatial_grid.nearest(hunter_transform.translation.truncate(), f32::INFINITY, |prey_entity, _| {
+                    let (_, prey_transform, prey_pawn, prey_health) = match prey_query.get(prey_entity) {
+                        Ok(prey) => prey,
+                        Err(_) => return false,
+                    };
+                    if prey_health.current <= 0.0 || unreachable_target == Some(prey_entity) {
+                        return false;
+                    }
+                    let prey_pos = (prey_transform.translation.x, prey_transform.translation.y);
+                    pawn_config.will_attack(&hunter_pawn.pawn_type, &prey_pawn.pawn_type)
+                        && terrain_map.are_connected(hunter_pos, prey_pos, hunter_size.value, &movement_configs)
+                        && vision.is_none_or(|vision| {
+                            vision.can_see(hunter_transform.translation.truncate(), prey_transform.translation.truncate(), &terrain_map, &ground_configs)
+                        })
+                });
+                pack_ai.target_entity = closest_target.map(|(prey_entity, _)| prey_entity);
+            }
+        }
+
+        let target_entity = if is_leader {
+            pack_ai.target_entity
+        } else {
+            leader_targets.get(&leader).copied().flatten()
+        };
+        let target_entity = match target_entity {
+            Some(target) if pack_ai.unreachable_target != Some(target) => target,
+            _ => continue,
+        };
+
+        if let Ok((_, target_transform, target_pawn, mut target_health)) = prey_query.get_mut(target_entity) {
+            if target_health.current <= 0.0 {
+                continue;
+            }
+            let distance = hunter_transform.translation.distance(target_transform.translation);
+
+            // The leader may have moved on to other prey mid-attack
+            if attacking.as_ref().is_some_and(|attack| attack.target != target_entity) {
+                commands.entity(hunter_entity).remove::<Attacking>();
+            }
+            let mut attacking = attacking.filter(|attack| attack.target == target_entity);
+
+            // Wind up a new attack once the last one has had time to land
+            let in_reach = distance <= reach_distance;
+            let mut new_attack = None;
+            if in_reach && attacking.is_none() && pack_ai.last_attack_time >= 1.0 / hunter_def.attack_speed {
+                new_attack = Some(Attacking::new(target_entity));
+                pack_ai.last_attack_time = 0.0;
+            }
+            let delta = if new_attack.is_some() { 0.0 } else { time.delta_secs() };
+
+            if let Some(attack) = attacking.as_deref_mut().or(new_attack.as_mut()) {
+                let step = attack.advance(delta, &hunter_def.attack_config());
+                if step.struck && !in_reach {
+                    println!("{} dodged {}'s attack", target_pawn.pawn_type, hunter_pawn.pawn_type);
+                } else if step.struck {
+                    let target_def = pawn_config.get_pawn_definition(&target_pawn.pawn_type).unwrap();
+                    let hunter_stage = age_query.get(hunter_entity).ok().map(|age| age.stage);
+                    let target_stage = age_query.get(target_entity).ok().map(|age| age.stage);
+                    let damage = blow_damage(hunter_def, hunter_stage, grown_query.get(hunter_entity).ok(), target_def, target_stage, sleeper_query.contains(target_entity));
+
+                    target_health.current = (target_health.current - damage).max(0.0);
+                    combat_events.send(CombatEvent { attacker: hunter_entity, victim: target_entity, damage });
+
+                    // The kill feeds the whole pack
+                    if target_health.current <= 0.0 {
+                        let members = packs.get(&leader).cloned().unwrap_or_else(|| vec![hunter_entity]);
+                        let share = target_def.max_health as f32 / members.len() as f32;
+                        for member in members {
+                            start_eating(&mut commands, member, &target_pawn.pawn_type, share, &hunter_def.eats.meal_config());
+                        }
+                        println!("{} pack killed {} and settles down to share it",
+                                hunter_pawn.pawn_type, target_pawn.pawn_type);
+                        if is_leader {
+                            pack_ai.target_entity = None;
+                        }
+                    }
+                }
+
+                if step.finished {
+                    if new_attack.is_none() {
+                        commands.entity(hunter_entity).remove::<Attacking>();
+                    }
+                } else if let Some(new_attack) = new_attack {
+                    commands.entity(hunter_entity).insert(new_attack);
+                }
+                continue; // Attackers stand their ground
+            }
+            if in_reach {
+                continue; // Don't move between attacks
+            }
+
+            if awaiting_path {
+                continue;
+            }
+
+            // Far from the prey the pack follows its leader's path in formation, so only the
+            // leader searches; close in, everyone finds their own way to their side of it
+            let leader_position = leader_positions.get(&leader).copied().unwrap_or(hunter_transform.translation);
+            let travelling = leader_position.distance(target_transform.translation) > SURROUND_DISTANCE_TILES * config.tile_size;
+            let follows_leader = |member: &Entity| {
+                match (walkers.get(member), walkers.get(&leader)) {
+                    (Some(&walker), Some(&leader_walker)) => *member != leader && can_follow(walker, leader_walker, config.tile_size),
+                    _ => false,
+                }
+            };
+            if travelling && follows_leader(&hunter_entity) {
+                continue;
+            }
+            if !travelling && leads_formation {
+                commands.entity(hunter_entity).remove::<FormationLeader>();
+            }
+
+            // Surround the prey: each member closes in from its own angle around it,
+            // starting from the side the leader is coming from
+            let members = packs.get(&leader).map(|members| members.as_slice()).unwrap_or(&[]);
+            let slot = members.iter().position(|&member| member == hunter_entity).unwrap_or(0);
+            let base_angle = (leader_position - target_transform.translation).truncate().to_angle();
+            let angle = base_angle + slot as f32 * std::f32::consts::TAU / members.len().max(1) as f32;
+            let approach = target_transform.translation.truncate() + Vec2::from_angle(angle) * reach_distance * APPROACH_REACH_FRACTION;
+            let movement_configs = ground_configs.for_movement(is_swimmer);
+            let approach = if terrain_map.is_position_passable_for_size(approach.x, approach.y, hunter_size.value, &movement_configs) {
+                approach
+            } else {
+                target_transform.translation.truncate()
+            };
+
+            let needs_new_path = match current_target {
+                Some(pawn_target) => {
+                    pawn_target.target_position.truncate().distance(approach) > 5.0 || pawn_target.path.is_empty()
+                }
+                None => true,
+            };
+            if needs_new_path {
+                let current_pos = (hunter_transform.translation.x, hunter_transform.translation.y);
+                let request = PathfindingRequest::new(current_pos, (approach.x, approach.y), hunter_size.value)
+                    .with_priority(PathfindingPriority::High);
+                if travelling && is_leader {
+                    let followers: Vec<Entity> = members.iter().copied().filter(follows_leader).collect();
+                    request_group_pathfinding(&mut commands, hunter_entity, &followers, request);
+                } else {
+                    commands.entity(hunter_entity).insert(request);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::pawn::{Pawn, CurrentBehavior};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::perception::Threatened;
+use crate::systems::spatial_grid::SpatialGrid;
+
+/// Each pawn a panic passes through hands on this fraction of what it has left, so it dies out
+/// a few pawns away from whoever actually saw the predator
+pub const PANIC_FALLOFF: f32 = 0.5;
+/// Panics with less than this many seconds left aren't passed on
+pub const MIN_PANIC_SECS: f32 = 0.5;
+
+/// Pawn has caught a fleeing neighbour's panic without seeing the predator itself. It runs from
+/// where the predator was until `remaining` runs out.
+#[derive(Component, Debug, Clone)]
+pub struct Panicked {
+    pub from: Vec2,
+    pub remaining: f32,
+}
+
+/// Threatened pawns with a `perception: { panic }` entry panic the pawns of their type within the
+/// panic radius for its duration, and panicked pawns pass on PANIC_FALLOFF of what they have left.
+/// State-driven pawns with a hunted behaviour switch to it while panicked and back to idle once
+/// the panic wears off, unless they've spotted the predator themselves by then.
+pub fn panic_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    spatial_grid: Res<SpatialGrid>,
+    mut commands: Commands,
+    mut pawn_query: Query<(Entity, &Transform, &Pawn, &mut CurrentBehavior, Option<&Threatened>, Option<&mut Panicked>, Has<ActiveBehaviour>)>,
+    predator_query: Query<&Transform>,
+) {
+    let delta = time.delta_secs();
+
+    // Who is spreading panic, how long it lasts and where the predator they're running from is
+    let mut spreaders: Vec<(Entity, Vec2, String, f32, f32, Vec2)> = Vec::new();
+    for (entity, transform, pawn, _, threatened, panicked, _) in pawn_query.iter() {
+        let panic = match pawn_config
+            .get_pawn_definition(&pawn.pawn_type)
+            .and_then(|definition| definition.perception.as_ref())
+            .and_then(|perception| perception.panic.as_ref())
+        {
+            Some(panic) => panic,
+            None => continue,
+        };
+        let spread = match (threatened, panicked) {
+            (Some(threatened), _) => match predator_query.get(threatened.predator) {
+                Ok(predator_transform) => Some((panic.duration, predator_transform.translation.truncate())),
+                Err(_) => None,
+            },
+            (None, Some(panicked)) => Some((panicked.remaining * PANIC_FALLOFF, panicked.from)),
+            (None, None) => None,
+        };
+        if let Some((duration, from)) = spread {
+            if duration >= MIN_PANIC_SECS {
+                let radius = panic.radius as f32 * config.tile_size;
+                spreaders.push((entity, transform.translation.truncate(), pawn.pawn_type.clone(), radius, duration, from));
+            }
+        }
+    }
+
+    // Each pawn catches the longest panic around it
+    let mut caught: HashMap<Entity, (f32, Vec2)> = HashMap::new();
+    for (spreader, position, pawn_type, radius, duration, from) in spreaders {
+        for (neighbour, _) in spatial_grid.within(position, radius) {
+            if neighbour == spreader {
+                continue;
+            }
+            let same_type = pawn_query.get(neighbour).is_ok_and(|(_, _, pawn, _, _, _, _)| pawn.pawn_type == pawn_type);
+            if same_type && caught.get(&neighbour).is_none_or(|(longest, _)| duration > *longest) {
+                caught.insert(neighbour, (duration, from));
+            }
+        }
+    }
+
+    for (entity, _, pawn, mut current_behavior, threatened, panicked, has_tree) in pawn_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        // Behaviour trees and utility scoring react to the panic themselves
+        let only_flag = has_tree || definition.utility.is_some();
+        let has_hunted_behaviour = pawn_config
+            .get_behaviour_config(&pawn.pawn_type, "hunted")
+            .is_some_and(|behaviour| !matches!(behaviour, BehaviourConfig::Simple(BehaviourType::Null)));
+
+        // Pawns that have seen the predator themselves are already running from it
+        let catching = caught.get(&entity).filter(|_| threatened.is_none());
+        match panicked {
+            Some(mut panicked) => {
+                panicked.remaining -= delta;
+                if let Some(&(duration, from)) = catching {
+                    if duration > panicked.remaining {
+                        panicked.remaining = duration;
+                        panicked.from = from;
+                    }
+                }
+                if panicked.remaining <= 0.0 {
+                    commands.entity(entity).remove::<Panicked>();
+                    if !only_flag && threatened.is_none() && current_behavior.state == "hunted" {
+                        println!("{} has calmed down, switching back to idle behavior", pawn.pawn_type);
+                        current_behavior.state = "idle".to_string();
+                    }
+                }
+            }
+            None => {
+                if let Some(&(duration, from)) = catching {
+                    commands.entity(entity).insert(Panicked { from, remaining: duration });
+                    if !only_flag && has_hunted_behaviour && current_behavior.state != "hunted" && current_behavior.state != "controlled" {
+                        println!("{} panics, switching to hunted behavior", pawn.pawn_type);
+                        current_behavior.state = "hunted".to_string();
+                    }
+                }
+            }
+        }
+    }
+}
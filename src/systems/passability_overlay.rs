@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use crate::systems::debug_display::{DebugDisplayState, DebugMode, INSPECTED_SIZES};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs, TerrainChanges};
+
+/// Opacity of the overlay's tint, enough to see the ground through it
+const OVERLAY_ALPHA: f32 = 0.35;
+/// Above terrain and props, below pawns - level with the heatmap, which is never shown with it
+const OVERLAY_Z: f32 = 50.0;
+
+/// Which of the inspected pawn sizes the passability overlay is showing
+#[derive(Resource, Default)]
+pub struct PassabilityOverlay {
+    pub size_index: usize,
+}
+
+impl PassabilityOverlay {
+    pub fn size(&self) -> f32 {
+        INSPECTED_SIZES[self.size_index]
+    }
+
+    pub fn next_size(&mut self) {
+        self.size_index = (self.size_index + 1) % INSPECTED_SIZES.len();
+    }
+}
+
+/// Tint over one tile of the passability overlay
+#[derive(Component)]
+pub struct PassabilityTile {
+    pub tile: (i32, i32),
+}
+
+/// Whether a walking pawn of `size` can stand centered on a tile, asked the same way A* asks it
+pub fn tile_passable_for_size(terrain_map: &TerrainMap, ground_configs: &GroundConfigs, tile: (i32, i32), size: f32) -> bool {
+    let (world_x, world_y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+    terrain_map.is_position_passable_for_size(world_x, world_y, size, ground_configs)
+}
+
+/// Green where the pawn fits, red where it doesn't
+pub fn passability_color(passable: bool) -> Color {
+    if passable {
+        Color::srgba(0.2, 0.9, 0.2, OVERLAY_ALPHA)
+    } else {
+        Color::srgba(0.9, 0.1, 0.1, OVERLAY_ALPHA)
+    }
+}
+
+/// Step through the pawn sizes while the passability overlay is on
+pub fn cycle_passability_size(
+    actions: ActionInput,
+    debug_state: Res<DebugDisplayState>,
+    mut overlay: ResMut<PassabilityOverlay>,
+) {
+    if debug_state.mode == DebugMode::Passability && actions.just_pressed(Action::CyclePassabilitySize) {
+        overlay.next_size();
+        println!("Passability overlay: size {}", overlay.size());
+    }
+}
+
+/// Tint every tile green or red by whether a pawn of the chosen size fits there. Tiles are
+/// recolored when the size changes, and around any tile whose ground changed.
+pub fn update_passability_overlay(
+    debug_state: Res<DebugDisplayState>,
+    overlay: Res<PassabilityOverlay>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    terrain_changes: Res<TerrainChanges>,
+    mut drawn_size: Local<Option<usize>>,
+    mut commands: Commands,
+    mut tile_query: Query<(Entity, &PassabilityTile, &mut Sprite)>,
+) {
+    if debug_state.mode != DebugMode::Passability {
+        for (entity, _, _) in tile_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        *drawn_size = None;
+        return;
+    }
+
+    let size = overlay.size();
+    let passable = |tile: (i32, i32)| tile_passable_for_size(&terrain_map, &ground_configs, tile, size);
+
+    if drawn_size.is_none() {
+        for x in 0..terrain_map.width as i32 {
+            for y in 0..terrain_map.height as i32 {
+                let (world_x, world_y) = terrain_map.tile_to_world_coords(x, y);
+                commands.spawn((
+                    Sprite::from_color(passability_color(passable((x, y))), Vec2::splat(terrain_map.tile_size)),
+                    Transform::from_translation(Vec3::new(world_x, world_y, OVERLAY_Z)),
+                    PassabilityTile { tile: (x, y) },
+                ));
+            }
+        }
+    } else if *drawn_size != Some(overlay.size_index) {
+        for (_, overlay_tile, mut sprite) in tile_query.iter_mut() {
+            sprite.color = passability_color(passable(overlay_tile.tile));
+        }
+    } else if !terrain_changes.changed_tiles.is_empty() {
+        // A tile's footing depends on every tile a pawn centered on it reaches
+        let reach = (size / 2.0).ceil() as i32;
+        for (_, overlay_tile, mut sprite) in tile_query.iter_mut() {
+            let (x, y) = overlay_tile.tile;
+            let near_change = terrain_changes.changed_tiles.iter().any(|&(changed_x, changed_y, _)| {
+                (x - changed_x as i32).abs() <= reach && (y - changed_y as i32).abs() <= reach
+            });
+            if near_change {
+                sprite.color = passability_color(passable(overlay_tile.tile));
+            }
+        }
+    }
+    *drawn_size = Some(overlay.size_index);
+}
@@ -1,7 +1,19 @@
 use bevy::prelude::*;
 use bevy::utils::{HashMap, HashSet};
+use std::collections::BTreeMap;
 use std::time::{Duration, Instant};
-use crate::systems::world_gen::{TerrainChanges, TerrainMap};
+use crate::systems::world_gen::{TerrainChanges, TerrainMap, update_terrain_visuals};
+use crate::systems::async_pathfinding::GlobalPathfindingCache;
+
+pub const DEFAULT_MAX_PATH_ENTRIES: usize = 2048;
+pub const DEFAULT_MAX_PASSABILITY_ENTRIES: usize = 8192;
+
+/// Width/height in tiles of a versioning region - edits only stale entries in nearby chunks
+pub const CACHE_CHUNK_SIZE: i32 = 16;
+/// Tiles around an edit whose passability may change (large pawns overlap neighbours)
+const INVALIDATION_RADIUS: i32 = 3;
+/// Seconds between sweeps of expired cache entries
+const CLEANUP_INTERVAL_SECS: f32 = 5.0;
 
 /// High-performance pathfinding cache with event-driven invalidation
 #[derive(Resource)]
@@ -10,10 +22,20 @@ pub struct PathfindingCache {
     path_cache: HashMap<PathCacheKey, CachedPathResult>,
     // Passability cache for expensive position checks
     passability_cache: HashMap<PassabilityCacheKey, CachedPassability>,
-    // Current terrain version - incremented when terrain changes
+    // Current terrain version - incremented when terrain changes anywhere
     pub terrain_version: u64,
+    // Per-chunk versions - incremented only for chunks near an edit
+    chunk_versions: HashMap<(i32, i32), u64>,
     // Spatial index for efficient cache invalidation
     spatial_index: HashMap<(u32, u32), Vec<PathCacheKey>>, // tile -> affected cache keys
+    // Size caps - least recently used entries are evicted once reached
+    pub max_path_entries: usize,
+    pub max_passability_entries: usize,
+    // Monotonic counter used to order entries by recency of use
+    access_counter: u64,
+    // Entries by when they were last used, so the least recent is found without a scan
+    path_recency: BTreeMap<u64, PathCacheKey>,
+    passability_recency: BTreeMap<u64, PassabilityCacheKey>,
     // Performance metrics
     pub stats: CacheStats,
 }
@@ -34,16 +56,19 @@ struct PassabilityCacheKey {
 
 struct CachedPathResult {
     path: Option<Vec<(f32, f32)>>,
-    terrain_version: u64, // Version when computed
+    terrain_version: u64, // Global version when computed - only used to validate "no path" results
+    chunk_versions: Vec<((i32, i32), u64)>, // Versions of every chunk the path touches
     last_accessed: Instant,
+    last_used: u64,
     // Store tiles this path crosses for invalidation
     affected_tiles: HashSet<(u32, u32)>,
 }
 
 struct CachedPassability {
     is_passable: bool,
-    terrain_version: u64,
+    chunk_version: u64,
     last_accessed: Instant,
+    last_used: u64,
 }
 
 #[derive(Default)]
@@ -53,20 +78,75 @@ pub struct CacheStats {
     pub passability_cache_hits: u64,
     pub passability_cache_misses: u64,
     pub terrain_invalidations: u64,
+    pub path_evictions: u64,
+    pub passability_evictions: u64,
     pub cache_size: usize,
 }
 
 impl PathfindingCache {
     pub fn new() -> Self {
+        Self::with_limits(DEFAULT_MAX_PATH_ENTRIES, DEFAULT_MAX_PASSABILITY_ENTRIES)
+    }
+
+    pub fn with_limits(max_path_entries: usize, max_passability_entries: usize) -> Self {
         Self {
-            path_cache: HashMap::with_capacity(512),
-            passability_cache: HashMap::with_capacity(1024),
+            path_cache: HashMap::with_capacity(max_path_entries.min(512)),
+            passability_cache: HashMap::with_capacity(max_passability_entries.min(1024)),
             terrain_version: 1,
+            chunk_versions: HashMap::new(),
             spatial_index: HashMap::new(),
+            max_path_entries,
+            max_passability_entries,
+            access_counter: 0,
+            path_recency: BTreeMap::new(),
+            passability_recency: BTreeMap::new(),
             stats: CacheStats::default(),
         }
     }
 
+    pub fn path_entry_count(&self) -> usize {
+        self.path_cache.len()
+    }
+
+    pub fn passability_entry_count(&self) -> usize {
+        self.passability_cache.len()
+    }
+
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+
+    pub fn chunk_of(tile_x: i32, tile_y: i32) -> (i32, i32) {
+        (tile_x.div_euclid(CACHE_CHUNK_SIZE), tile_y.div_euclid(CACHE_CHUNK_SIZE))
+    }
+
+    pub fn chunk_version(&self, chunk: (i32, i32)) -> u64 {
+        self.chunk_versions.get(&chunk).copied().unwrap_or(0)
+    }
+
+    /// Bump the version of every chunk whose passability an edit at this tile can affect
+    fn bump_chunks_around_tile(&mut self, tile_x: i32, tile_y: i32) {
+        let (min_cx, min_cy) = Self::chunk_of(tile_x - INVALIDATION_RADIUS, tile_y - INVALIDATION_RADIUS);
+        let (max_cx, max_cy) = Self::chunk_of(tile_x + INVALIDATION_RADIUS, tile_y + INVALIDATION_RADIUS);
+
+        for cx in min_cx..=max_cx {
+            for cy in min_cy..=max_cy {
+                *self.chunk_versions.entry((cx, cy)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    fn is_path_entry_valid(&self, cached: &CachedPathResult) -> bool {
+        if cached.path.is_none() {
+            // An edit anywhere could open up a route, so "no path" needs the global version
+            return cached.terrain_version == self.terrain_version;
+        }
+        cached.chunk_versions
+            .iter()
+            .all(|(chunk, version)| self.chunk_version(*chunk) == *version)
+    }
+
     /// Update cache based on terrain changes - called when terrain is modified
     pub fn invalidate_from_terrain_changes(&mut self, terrain_changes: &TerrainChanges) {
         if terrain_changes.changed_tiles.is_empty() {
@@ -81,6 +161,9 @@ impl PathfindingCache {
 
         // Invalidate paths that cross changed tiles
         for (x, y, _terrain_type) in &terrain_changes.changed_tiles {
+            // Stale only the chunks around this edit
+            self.bump_chunks_around_tile(*x as i32, *y as i32);
+
             // Remove passability cache entries for this tile and nearby tiles
             self.invalidate_passability_around_tile(*x as i32, *y as i32);
 
@@ -94,17 +177,7 @@ impl PathfindingCache {
 
         // Remove invalidated path cache entries
         for key in keys_to_remove {
-            if let Some(cached_path) = self.path_cache.remove(&key) {
-                // Clean up spatial index
-                for tile in &cached_path.affected_tiles {
-                    if let Some(keys) = self.spatial_index.get_mut(tile) {
-                        keys.retain(|k| k != &key);
-                        if keys.is_empty() {
-                            self.spatial_index.remove(tile);
-                        }
-                    }
-                }
-            }
+            self.remove_path(&key);
         }
 
         self.update_stats();
@@ -113,12 +186,15 @@ impl PathfindingCache {
     fn invalidate_passability_around_tile(&mut self, center_x: i32, center_y: i32) {
         // Remove passability cache in a radius around the changed tile
         // (since large pawns can be affected by changes in nearby tiles)
-        const INVALIDATION_RADIUS: i32 = 3;
-        
-        self.passability_cache.retain(|key, _| {
+        let recency = &mut self.passability_recency;
+        self.passability_cache.retain(|key, cached| {
             let dx = (key.tile_x - center_x).abs();
             let dy = (key.tile_y - center_y).abs();
-            dx > INVALIDATION_RADIUS || dy > INVALIDATION_RADIUS
+            let keep = dx > INVALIDATION_RADIUS || dy > INVALIDATION_RADIUS;
+            if !keep {
+                recency.remove(&cached.last_used);
+            }
+            keep
         });
     }
 
@@ -132,13 +208,17 @@ impl PathfindingCache {
 
         // Check if entry exists and is valid
         let should_remove = if let Some(cached) = self.path_cache.get(&key) {
-            if cached.terrain_version == self.terrain_version {
+            if self.is_path_entry_valid(cached) {
                 self.stats.path_cache_hits += 1;
                 // Update access time in a separate call to avoid borrowing issues
                 let result = cached.path.clone();
                 // Update last accessed time
+                let access = self.next_access();
                 if let Some(cached_mut) = self.path_cache.get_mut(&key) {
                     cached_mut.last_accessed = Instant::now();
+                    self.path_recency.remove(&cached_mut.last_used);
+                    cached_mut.last_used = access;
+                    self.path_recency.insert(access, key);
                 }
                 return Some(result);
             } else {
@@ -150,9 +230,7 @@ impl PathfindingCache {
 
         // Remove stale entry if needed
         if should_remove {
-            if let Some(old_entry) = self.path_cache.remove(&key) {
-                self.cleanup_spatial_index(&key, &old_entry.affected_tiles);
-            }
+            self.remove_path(&key);
         }
 
         self.stats.path_cache_misses += 1;
@@ -174,6 +252,15 @@ impl PathfindingCache {
             HashSet::new()
         };
 
+        // Make room before inserting a new entry
+        if !self.path_cache.contains_key(&key) {
+            while self.path_cache.len() >= self.max_path_entries.max(1) {
+                self.evict_least_recent_path();
+            }
+        } else {
+            self.remove_path(&key);
+        }
+
         // Update spatial index
         for tile in &affected_tiles {
             self.spatial_index
@@ -182,17 +269,56 @@ impl PathfindingCache {
                 .push(key.clone());
         }
 
+        let mut touched_chunks: HashSet<(i32, i32)> = affected_tiles
+            .iter()
+            .map(|(x, y)| Self::chunk_of(*x as i32, *y as i32))
+            .collect();
+        touched_chunks.insert(Self::chunk_of(start.0, start.1));
+        touched_chunks.insert(Self::chunk_of(goal.0, goal.1));
+        let chunk_versions = touched_chunks
+            .into_iter()
+            .map(|chunk| (chunk, self.chunk_version(chunk)))
+            .collect();
+
+        let last_used = self.next_access();
         let cached_result = CachedPathResult {
             path,
             terrain_version: self.terrain_version,
+            chunk_versions,
             last_accessed: Instant::now(),
+            last_used,
             affected_tiles,
         };
 
+        self.path_recency.insert(last_used, key.clone());
         self.path_cache.insert(key, cached_result);
         self.update_stats();
     }
 
+    /// Drop a path entry along with its place in the spatial and recency indexes
+    fn remove_path(&mut self, key: &PathCacheKey) -> Option<CachedPathResult> {
+        let removed = self.path_cache.remove(key)?;
+        self.path_recency.remove(&removed.last_used);
+        self.cleanup_spatial_index(key, &removed.affected_tiles);
+        Some(removed)
+    }
+
+    fn evict_least_recent_path(&mut self) {
+        if let Some((_, key)) = self.path_recency.pop_first() {
+            if let Some(evicted) = self.path_cache.remove(&key) {
+                self.cleanup_spatial_index(&key, &evicted.affected_tiles);
+            }
+            self.stats.path_evictions += 1;
+        }
+    }
+
+    fn evict_least_recent_passability(&mut self) {
+        if let Some((_, key)) = self.passability_recency.pop_first() {
+            self.passability_cache.remove(&key);
+            self.stats.passability_evictions += 1;
+        }
+    }
+
     /// Get cached passability result
     pub fn get_passability(&mut self, tile_x: i32, tile_y: i32, size: f32) -> Option<bool> {
         let key = PassabilityCacheKey {
@@ -201,9 +327,14 @@ impl PathfindingCache {
             size_tier: self.quantize_size(size),
         };
 
+        let access = self.next_access();
+        let current_chunk_version = self.chunk_version(Self::chunk_of(tile_x, tile_y));
         if let Some(cached) = self.passability_cache.get_mut(&key) {
-            if cached.terrain_version == self.terrain_version {
+            self.passability_recency.remove(&cached.last_used);
+            if cached.chunk_version == current_chunk_version {
                 cached.last_accessed = Instant::now();
+                cached.last_used = access;
+                self.passability_recency.insert(access, key);
                 self.stats.passability_cache_hits += 1;
                 return Some(cached.is_passable);
             } else {
@@ -224,12 +355,26 @@ impl PathfindingCache {
             size_tier: self.quantize_size(size),
         };
 
+        match self.passability_cache.remove(&key) {
+            Some(old_entry) => {
+                self.passability_recency.remove(&old_entry.last_used);
+            }
+            None => {
+                while self.passability_cache.len() >= self.max_passability_entries.max(1) {
+                    self.evict_least_recent_passability();
+                }
+            }
+        }
+
+        let last_used = self.next_access();
         let cached = CachedPassability {
             is_passable,
-            terrain_version: self.terrain_version,
+            chunk_version: self.chunk_version(Self::chunk_of(tile_x, tile_y)),
             last_accessed: Instant::now(),
+            last_used,
         };
 
+        self.passability_recency.insert(last_used, key.clone());
         self.passability_cache.insert(key, cached);
     }
 
@@ -239,29 +384,35 @@ impl PathfindingCache {
         let now = Instant::now();
 
         // Clean up old path cache entries
-        let mut expired_keys = Vec::new();
-        self.path_cache.retain(|key, cached| {
-            if now.duration_since(cached.last_accessed) > expiry_time {
-                expired_keys.push((key.clone(), cached.affected_tiles.clone()));
-                false
-            } else {
-                true
-            }
-        });
-
-        // Clean up spatial index for expired entries
-        for (key, affected_tiles) in expired_keys {
-            self.cleanup_spatial_index(&key, &affected_tiles);
+        let expired_keys: Vec<PathCacheKey> = self.path_cache
+            .iter()
+            .filter(|(_, cached)| now.duration_since(cached.last_accessed) > expiry_time)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired_keys {
+            self.remove_path(&key);
         }
 
         // Clean up old passability cache entries
+        let recency = &mut self.passability_recency;
         self.passability_cache.retain(|_, cached| {
-            now.duration_since(cached.last_accessed) <= expiry_time
+            let keep = now.duration_since(cached.last_accessed) <= expiry_time;
+            if !keep {
+                recency.remove(&cached.last_used);
+            }
+            keep
         });
 
         self.update_stats();
     }
 
+    /// Start tile, goal tile and (quantized) pawn size of every cached path crossing a tile
+    pub fn paths_over_tile(&self, tile_x: u32, tile_y: u32) -> Vec<((i32, i32), (i32, i32), f32)> {
+        self.spatial_index.get(&(tile_x, tile_y)).map_or_else(Vec::new, |keys| {
+            keys.iter().map(|key| (key.start_tile, key.goal_tile, key.size_tier as f32 / 8.0)).collect()
+        })
+    }
+
     fn quantize_size(&self, size: f32) -> u8 {
         // Quantize to reduce cache fragmentation while maintaining accuracy
         (size * 8.0).round().min(255.0) as u8
@@ -321,28 +472,58 @@ impl Default for PathfindingCache {
     }
 }
 
+/// Owns the global pathfinding cache and the systems that keep it fresh
+pub struct CachePlugin {
+    pub max_path_entries: usize,
+    pub max_passability_entries: usize,
+}
+
+impl Default for CachePlugin {
+    fn default() -> Self {
+        Self {
+            max_path_entries: DEFAULT_MAX_PATH_ENTRIES,
+            max_passability_entries: DEFAULT_MAX_PASSABILITY_ENTRIES,
+        }
+    }
+}
+
+impl Plugin for CachePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GlobalPathfindingCache::with_limits(self.max_path_entries, self.max_passability_entries))
+            // Must see terrain changes before update_terrain_visuals drains them
+            .add_systems(Update, (
+                update_pathfinding_cache.before(update_terrain_visuals),
+                cleanup_pathfinding_cache,
+            ));
+    }
+}
+
+/// Repeating timer for cleanup_pathfinding_cache
+pub struct CleanupTimer(pub Timer);
+
+impl Default for CleanupTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(CLEANUP_INTERVAL_SECS, TimerMode::Repeating))
+    }
+}
+
 /// System to update pathfinding cache when terrain changes
 pub fn update_pathfinding_cache(
-    mut cache: ResMut<PathfindingCache>,
+    mut global_cache: ResMut<GlobalPathfindingCache>,
     terrain_changes: Res<TerrainChanges>,
 ) {
     if terrain_changes.is_changed() {
-        cache.invalidate_from_terrain_changes(&terrain_changes);
+        global_cache.cache_mut().invalidate_from_terrain_changes(&terrain_changes);
     }
 }
 
 /// System to periodically clean up expired cache entries
 pub fn cleanup_pathfinding_cache(
-    mut cache: ResMut<PathfindingCache>,
+    time: Res<Time>,
+    mut timer: Local<CleanupTimer>,
+    mut global_cache: ResMut<GlobalPathfindingCache>,
 ) {
-    // Run cleanup every 5 seconds
-    static mut LAST_CLEANUP: Option<Instant> = None;
-    
-    unsafe {
-        let now = Instant::now();
-        if LAST_CLEANUP.map_or(true, |last| now.duration_since(last) >= Duration::from_secs(5)) {
-            cache.cleanup_expired_entries();
-            LAST_CLEANUP = Some(now);
-        }
+    if timer.0.tick(time.delta()).just_finished() {
+        global_cache.cache_mut().cleanup_expired_entries();
     }
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+use crate::systems::world_gen::TerrainMap;
+
+/// Heat added to a tile each time A* expands it or a cached path is served from it
+pub const HEAT_PER_EVENT: f32 = 1.0;
+/// Heat lost per second, so the overlay only shows recent pathfinding work
+pub const HEAT_DECAY_PER_SEC: f32 = 2.0;
+/// Heat at which a tile is drawn fully saturated
+const HEAT_SATURATION: f32 = 10.0;
+/// Opacity of a fully saturated tile
+const MAX_OVERLAY_ALPHA: f32 = 0.6;
+/// Above terrain and props, below pawns and waypoint lines
+const OVERLAY_Z: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeatmapLayer {
+    Expanded, // Tiles A* had to expand
+    CacheHit, // Tiles where a request was answered from the path cache
+}
+
+impl HeatmapLayer {
+    fn color(&self, alpha: f32) -> Color {
+        match self {
+            HeatmapLayer::Expanded => Color::srgba(1.0, 0.3, 0.0, alpha),
+            HeatmapLayer::CacheHit => Color::srgba(0.0, 0.8, 1.0, alpha),
+        }
+    }
+}
+
+/// Recent pathfinding activity per tile. Only recorded while the heatmap overlay is shown,
+/// since tracing expansions costs an allocation per search.
+#[derive(Resource, Default)]
+pub struct PathfindingHeatmap {
+    pub recording: bool,
+    heat: HashMap<(HeatmapLayer, (i32, i32)), f32>,
+}
+
+impl PathfindingHeatmap {
+    pub fn heat(&self, layer: HeatmapLayer, tile: (i32, i32)) -> f32 {
+        self.heat.get(&(layer, tile)).copied().unwrap_or(0.0)
+    }
+
+    pub fn record_expanded(&mut self, tiles: &[(i32, i32)]) {
+        for &tile in tiles {
+            *self.heat.entry((HeatmapLayer::Expanded, tile)).or_insert(0.0) += HEAT_PER_EVENT;
+        }
+    }
+
+    pub fn record_cache_hit(&mut self, tile: (i32, i32)) {
+        *self.heat.entry((HeatmapLayer::CacheHit, tile)).or_insert(0.0) += HEAT_PER_EVENT;
+    }
+
+    pub fn decay(&mut self, amount: f32) {
+        self.heat.retain(|_, value| {
+            *value -= amount;
+            *value > 0.0
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.heat.clear();
+    }
+}
+
+/// Translucent overlay sprite for one tile of one heatmap layer
+#[derive(Component)]
+pub struct HeatmapTile {
+    pub layer: HeatmapLayer,
+    pub tile: (i32, i32),
+}
+
+/// Record only while the heatmap overlay is active, and forget everything when it's turned off
+pub fn sync_heatmap_recording(
+    debug_state: Res<DebugDisplayState>,
+    mut heatmap: ResMut<PathfindingHeatmap>,
+) {
+    let recording = debug_state.mode == DebugMode::PathfindingHeatmap;
+    if heatmap.recording != recording {
+        heatmap.recording = recording;
+        if !recording {
+            heatmap.clear();
+        }
+    }
+}
+
+pub fn decay_pathfinding_heatmap(
+    time: Res<Time>,
+    mut heatmap: ResMut<PathfindingHeatmap>,
+) {
+    heatmap.decay(HEAT_DECAY_PER_SEC * time.delta_secs());
+}
+
+/// Keep one overlay sprite per hot tile, fading with its heat
+pub fn update_heatmap_overlay(
+    mut commands: Commands,
+    heatmap: Res<PathfindingHeatmap>,
+    terrain_map: Res<TerrainMap>,
+    mut overlay_query: Query<(Entity, &HeatmapTile, &mut Sprite)>,
+) {
+    let mut drawn = std::collections::HashSet::new();
+
+    for (entity, overlay_tile, mut sprite) in overlay_query.iter_mut() {
+        let heat = heatmap.heat(overlay_tile.layer, overlay_tile.tile);
+        if heat <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite.color = overlay_tile.layer.color(heat_alpha(heat));
+        drawn.insert((overlay_tile.layer, overlay_tile.tile));
+    }
+
+    for (&(layer, tile), &heat) in heatmap.heat.iter() {
+        if drawn.contains(&(layer, tile)) {
+            continue;
+        }
+        let (world_x, world_y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+        commands.spawn((
+            Sprite {
+                color: layer.color(heat_alpha(heat)),
+                custom_size: Some(Vec2::splat(terrain_map.tile_size)),
+                ..default()
+            },
+            Transform::from_translation(Vec3::new(world_x, world_y, OVERLAY_Z)),
+            HeatmapTile { layer, tile },
+        ));
+    }
+}
+
+fn heat_alpha(heat: f32) -> f32 {
+    (heat / HEAT_SATURATION).min(1.0) * MAX_OVERLAY_ALPHA
+}
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use crate::systems::async_pathfinding::{GlobalPathfindingCache, PathfindingTask};
+use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+use crate::systems::pathfinding_cache::PathfindingCache;
+
+/// Seconds between refreshes of the panel, so the numbers can be read
+pub const STATS_REFRESH_SECS: f32 = 1.0;
+
+/// Pathfinding cache statistics, shown alongside the heatmap overlay
+#[derive(Component)]
+pub struct PathfindingStatsPanel;
+
+/// Repeating timer for update_pathfinding_stats_panel
+pub struct StatsRefreshTimer(pub Timer);
+
+impl Default for StatsRefreshTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(STATS_REFRESH_SECS, TimerMode::Repeating))
+    }
+}
+
+fn hit_ratio_line(name: &str, hits: u64, misses: u64) -> String {
+    let total = hits + misses;
+    let percent = if total > 0 { hits as f32 / total as f32 * 100.0 } else { 0.0 };
+    format!("{} hits: {:.0}% ({} of {})", name, percent, hits, total)
+}
+
+/// The panel's text: hit ratios, how full the cache is, searches still running and how often
+/// entries have been thrown away
+pub fn describe_cache_stats(cache: &PathfindingCache, tasks_in_flight: usize) -> String {
+    let stats = &cache.stats;
+    [
+        "Pathfinding cache".to_string(),
+        hit_ratio_line("Path", stats.path_cache_hits, stats.path_cache_misses),
+        hit_ratio_line("Passability", stats.passability_cache_hits, stats.passability_cache_misses),
+        format!("Paths cached: {}/{}", cache.path_entry_count(), cache.max_path_entries),
+        format!("Passability cached: {}/{}", cache.passability_entry_count(), cache.max_passability_entries),
+        format!("Searches in flight: {}", tasks_in_flight),
+        format!("Terrain invalidations: {}", stats.terrain_invalidations),
+        format!("Evictions: {} paths, {} passability", stats.path_evictions, stats.passability_evictions),
+    ]
+    .join("\n")
+}
+
+/// Show the cache statistics in the corner while the heatmap overlay is on, refreshed once a second
+pub fn update_pathfinding_stats_panel(
+    time: Res<Time<Real>>, // Keeps refreshing while the game is paused
+    mut timer: Local<StatsRefreshTimer>,
+    debug_state: Res<DebugDisplayState>,
+    global_cache: Res<GlobalPathfindingCache>,
+    task_query: Query<(), With<PathfindingTask>>,
+    mut commands: Commands,
+    mut panel_query: Query<(Entity, &mut Text), With<PathfindingStatsPanel>>,
+) {
+    if debug_state.mode != DebugMode::PathfindingHeatmap {
+        for (panel, _) in panel_query.iter() {
+            commands.entity(panel).despawn();
+        }
+        return;
+    }
+
+    let refresh = timer.0.tick(time.delta()).just_finished();
+    match panel_query.get_single_mut() {
+        Ok((_, mut text)) => {
+            if refresh {
+                text.0 = describe_cache_stats(global_cache.cache(), task_query.iter().count());
+            }
+        }
+        Err(_) => {
+            timer.0.reset();
+            commands.spawn((
+                Text::new(describe_cache_stats(global_cache.cache(), task_query.iter().count())),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+                PathfindingStatsPanel,
+            ));
+        }
+    }
+}
@@ -1,9 +1,29 @@
 use bevy::prelude::*;
 use crate::systems::world_gen::{TerrainMap, GroundConfigs};
-use crate::systems::pawn_config::{PawnConfig, PawnType, BehaviourConfig, BehaviourType};
+use crate::systems::pawn_config::{PawnConfig, PawnType, NeedKind, LifeStage};
 use crate::resources::GameConfig;
+use crate::systems::charge::{Charge, Stunned};
+use crate::systems::async_pathfinding::PathfindingRequest;
+use crate::systems::aging::Age;
+use crate::systems::combat::{CombatEvent, DeathEvent};
+use crate::systems::needs::{Thirst, Energy};
+use crate::systems::memory::Memory;
+use crate::systems::perception::{Threatened, Vision};
+use crate::systems::panic::Panicked;
+use crate::systems::status_effects::StatusEffects;
+use crate::systems::inventory::Inventory;
+use crate::systems::animation::{SpriteAnimation, WalkAnimation};
+use crate::systems::weather::Weather;
+use crate::systems::y_sort::{YSort, DepthLayer};
+use crate::systems::selection::selection_ring;
+use crate::systems::shadows::pawn_shadow;
+use crate::systems::inventory::ItemConfig;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::TextureAtlasBuilderError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SpriteInfo {
@@ -21,29 +41,50 @@ struct TilesetIndex {
     tile_size: u32,
     tiles_per_row: u32,
     total_tiles: u32,
+    #[serde(default)]
+    padding: u32, // Pixels between tiles
     sprites: Vec<SpriteInfo>,
+    #[serde(default)]
+    animations: HashMap<String, SpriteAnimation>,
 }
 
+/// Largest the atlas sprites given as direct image paths are packed into can grow, in pixels
+const PACKED_ATLAS_MAX_SIZE: u32 = 4096;
+
 #[derive(Resource)]
 pub struct TilesetManager {
     tilesets: HashMap<String, TilesetIndex>,
-    atlases: HashMap<String, Handle<TextureAtlasLayout>>,
+    atlases: HashMap<String, Handle<TextureAtlasLayout>>, // One layout per tileset, shared by all its sprites
+    packed: HashMap<String, usize>, // Direct image paths packed into packed_atlas, and their index in it
+    packed_atlas: Option<(Handle<Image>, Handle<TextureAtlasLayout>)>,
+    assets_dir: PathBuf, // Where tileset indexes and sprite images are read from
 }
 
 impl Default for TilesetManager {
     fn default() -> Self {
+        Self::new(PathBuf::from("assets"))
+    }
+}
+
+impl TilesetManager {
+    pub fn new(assets_dir: PathBuf) -> Self {
         Self {
             tilesets: HashMap::new(),
             atlases: HashMap::new(),
+            packed: HashMap::new(),
+            packed_atlas: None,
+            assets_dir,
         }
     }
-}
 
-impl TilesetManager {
     pub fn load_tileset(&mut self, tileset_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let yaml_path = format!("assets/tilesets/{}.yaml", tileset_name);
+        let yaml_path = self.assets_dir.join("tilesets").join(format!("{}.yaml", tileset_name));
         let yaml_content = std::fs::read_to_string(&yaml_path)?;
-        let tileset_index: TilesetIndex = serde_yaml::from_str(&yaml_content)?;
+        self.load_tileset_from_str(tileset_name, &yaml_content)
+    }
+
+    pub fn load_tileset_from_str(&mut self, tileset_name: &str, yaml_content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let tileset_index: TilesetIndex = serde_yaml::from_str(yaml_content)?;
         self.tilesets.insert(tileset_name.to_string(), tileset_index);
         Ok(())
     }
@@ -54,8 +95,16 @@ impl TilesetManager {
             .find(|sprite| sprite.name == sprite_name)
             .map(|sprite| sprite.index)
     }
+
+    pub fn get_animation(&self, tileset_name: &str, sprite_name: &str) -> Option<&SpriteAnimation> {
+        self.tilesets.get(tileset_name)?.animations.get(sprite_name)
+    }
     
-    pub fn create_atlas_layout(&self, tileset_name: &str, texture_atlas_layouts: &mut Assets<TextureAtlasLayout>) -> Option<Handle<TextureAtlasLayout>> {
+    /// Atlas layout for a tileset, made the first time it's asked for and shared after that
+    pub fn create_atlas_layout(&mut self, tileset_name: &str, texture_atlas_layouts: &mut Assets<TextureAtlasLayout>) -> Option<Handle<TextureAtlasLayout>> {
+        if let Some(layout) = self.atlases.get(tileset_name) {
+            return Some(layout.clone());
+        }
         let tileset = self.tilesets.get(tileset_name)?;
         
         // Create atlas layout based on tileset configuration
@@ -63,11 +112,98 @@ impl TilesetManager {
             UVec2::new(tileset.tile_size, tileset.tile_size),
             tileset.tiles_per_row,
             (tileset.total_tiles + tileset.tiles_per_row - 1) / tileset.tiles_per_row,
-            None,
+            Some(UVec2::splat(tileset.padding)),
             None
         );
         
-        Some(texture_atlas_layouts.add(layout))
+        let handle = texture_atlas_layouts.add(layout);
+        self.atlases.insert(tileset_name.to_string(), handle.clone());
+        Some(handle)
+    }
+
+    /// Pack sprites given as direct image paths into one atlas, so pawns drawn with them share a
+    /// texture and are batched together like tileset sprites
+    pub fn pack_sprites(
+        &mut self,
+        sprites: &[(String, Image)],
+        images: &mut Assets<Image>,
+        texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Result<(), TextureAtlasBuilderError> {
+        if sprites.is_empty() {
+            return Ok(());
+        }
+        let mut builder = TextureAtlasBuilder::default();
+        builder.max_size(UVec2::splat(PACKED_ATLAS_MAX_SIZE)).padding(UVec2::ONE);
+        for (_, image) in sprites {
+            builder.add_texture(None, image);
+        }
+        // Indices follow the order the sprites were added in
+        let (layout, _, image) = builder.build()?;
+        self.packed = sprites.iter().enumerate().map(|(index, (path, _))| (path.clone(), index)).collect();
+        self.packed_atlas = Some((images.add(image), texture_atlas_layouts.add(layout)));
+        Ok(())
+    }
+
+    /// Index of a direct image path in the packed atlas
+    pub fn packed_index(&self, sprite_path: &str) -> Option<usize> {
+        self.packed.get(sprite_path).copied()
+    }
+
+    /// Sprite for a direct image path from the packed atlas, if it was packed
+    pub fn packed_sprite(&self, sprite_path: &str) -> Option<Sprite> {
+        let (image, layout) = self.packed_atlas.as_ref()?;
+        let index = self.packed_index(sprite_path)?;
+        Some(Sprite::from_atlas_image(image.clone(), TextureAtlas { layout: layout.clone(), index }))
+    }
+
+    /// Whether a sprite is drawn from the packed atlas
+    pub fn is_packed(&self, sprite: &Sprite) -> bool {
+        self.packed_atlas.as_ref().is_some_and(|(image, _)| *image == sprite.image)
+    }
+}
+
+/// Every sprite pawns and items are drawn with that's a direct image path rather than a tileset
+/// reference, each once
+pub fn direct_sprite_paths(pawn_config: &PawnConfig, item_config: &ItemConfig) -> Vec<String> {
+    let stages = [None, Some(LifeStage::Juvenile), Some(LifeStage::Adult), Some(LifeStage::Elderly)];
+    let pawn_sprites = pawn_config.pawns.values()
+        .flat_map(|definition| stages.map(|stage| definition.sprite_for(stage).to_string()));
+    let item_sprites = item_config.items.values().map(|item| item.sprite.clone());
+    let paths: BTreeSet<String> = pawn_sprites.chain(item_sprites)
+        .filter(|path| !path.starts_with("tileset::"))
+        .collect();
+    paths.into_iter().collect()
+}
+
+/// Read the direct image sprites from the assets folder and pack them into one atlas at startup.
+/// Any that can't be read are left to load on their own when they're first drawn.
+pub fn pack_direct_sprites(
+    pawn_config: Res<PawnConfig>,
+    item_config: Res<ItemConfig>,
+    mut tileset_manager: ResMut<TilesetManager>,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let mut sprites = Vec::new();
+    for path in direct_sprite_paths(&pawn_config, &item_config) {
+        let extension = std::path::Path::new(&path).extension().and_then(|extension| extension.to_str()).unwrap_or("png").to_string();
+        let image = std::fs::read(tileset_manager.assets_dir.join(&path))
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| Image::from_buffer(
+                &bytes,
+                ImageType::Extension(&extension),
+                CompressedImageFormats::NONE,
+                true,
+                ImageSampler::Default,
+                RenderAssetUsages::default(),
+            ).map_err(|e| e.to_string()));
+        match image {
+            Ok(image) => sprites.push((path, image)),
+            Err(e) => eprintln!("Could not pack sprite {}: {}", path, e),
+        }
+    }
+    if let Err(e) = tileset_manager.pack_sprites(&sprites, &mut images, &mut texture_atlas_layouts) {
+        eprintln!("Could not pack {} sprites into an atlas: {}", sprites.len(), e);
     }
 }
 
@@ -81,6 +217,10 @@ pub struct Size {
     pub value: f32,
 }
 
+/// Pawn can swim - swimmable grounds are passable for its paths and movement
+#[derive(Component)]
+pub struct Swimmer;
+
 #[derive(Component)]
 pub struct CurrentBehavior {
     pub state: String,
@@ -131,6 +271,13 @@ pub struct PawnTarget {
     pub current_waypoint_index: usize,
 }
 
+/// How far (in tiles) a pawn may drift from its path segment before its route is re-checked
+pub const OFF_PATH_THRESHOLD_TILES: f32 = 1.0;
+/// Health lost per second by a pawn in water that can't swim (or is too exhausted to)
+pub const DROWNING_DAMAGE_PER_SEC: f32 = 10.0;
+/// How far below its centre a pawn's feet are, in tiles - where its shadow and selection ring go
+pub const FEET_OFFSET_TILES: f32 = 0.35;
+
 impl PawnTarget {
     pub fn new(target_position: Vec3) -> Self {
         Self {
@@ -161,6 +308,29 @@ impl PawnTarget {
         }
     }
 
+    /// Distance from a position to the segment the pawn should currently be walking
+    /// (previous waypoint -> current waypoint, or just the first waypoint at the start)
+    pub fn deviation_from_path(&self, position: Vec3) -> f32 {
+        let current = match self.get_current_waypoint() {
+            Some(waypoint) => waypoint.truncate(),
+            None => return 0.0,
+        };
+        let position = position.truncate();
+
+        if self.current_waypoint_index == 0 {
+            return position.distance(current);
+        }
+
+        let previous = self.path[self.current_waypoint_index - 1].truncate();
+        let segment = current - previous;
+        let t = if segment.length_squared() > 0.0 {
+            ((position - previous).dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        position.distance(previous + segment * t)
+    }
+
     pub fn is_at_destination(&self) -> bool {
         self.current_waypoint_index >= self.path.len() - 1
     }
@@ -172,36 +342,17 @@ impl PawnTarget {
     }
 }
 
-pub fn spawn_pawn(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    terrain_map: &Res<TerrainMap>,
-    ground_configs: &Res<GroundConfigs>,
-    pawn_config: &Res<PawnConfig>,
-    tileset_manager: &mut ResMut<TilesetManager>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    pawn: Pawn,
-    spawn_position: Option<(f32, f32)>,
-) -> Entity {
-    let position = if let Some(pos) = spawn_position {
-        pos
-    } else {
-        // Find a passable spawn position
-        let initial_center = (0.0, 0.0);
-        if let Some(passable_pos) = terrain_map.find_nearest_passable_tile(initial_center, ground_configs) {
-            passable_pos
-        } else {
-            (0.0, 0.0) // Fallback
-        }
-    };
-
-    let pawn_def = pawn_config.get_pawn_definition(&pawn.pawn_type)
-        .expect("Pawn definition not found in config");
-
+/// Sprite for a "tileset::name::sprite" reference or a direct image path
+pub fn create_sprite(
+    asset_server: &AssetServer,
+    tileset_manager: &mut TilesetManager,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    sprite_path: &str,
+) -> Sprite {
     // Parse sprite reference - check if it's a tileset reference or direct file
-    let sprite_bundle = if pawn_def.sprite.starts_with("tileset::") {
+    if sprite_path.starts_with("tileset::") {
         // Parse tileset reference: "tileset::tileset_name::sprite_name"
-        let parts: Vec<&str> = pawn_def.sprite.split("::").collect();
+        let parts: Vec<&str> = sprite_path.split("::").collect();
         if parts.len() == 3 && parts[0] == "tileset" {
             let tileset_name = parts[1];
             let sprite_name = parts[2];
@@ -211,7 +362,7 @@ pub fn spawn_pawn(
                 if let Err(e) = tileset_manager.load_tileset(tileset_name) {
                     eprintln!("Failed to load tileset {}: {}", tileset_name, e);
                     // Fallback to direct sprite loading
-                    Sprite::from_image(asset_server.load(&pawn_def.sprite))
+                    Sprite::from_image(asset_server.load(sprite_path))
                 } else {
                     // Create sprite with atlas
                     let texture_handle = asset_server.load(format!("tilesets/{}.png", tileset_name));
@@ -242,41 +393,134 @@ pub fn spawn_pawn(
             }
         } else {
             // Invalid tileset format, fallback to direct loading
-            Sprite::from_image(asset_server.load(&pawn_def.sprite))
+            Sprite::from_image(asset_server.load(sprite_path))
         }
     } else {
-        // Direct sprite file
-        Sprite::from_image(asset_server.load(&pawn_def.sprite))
+        // Direct sprite file, from the packed atlas unless it couldn't be packed at startup
+        tileset_manager.packed_sprite(sprite_path)
+            .unwrap_or_else(|| Sprite::from_image(asset_server.load(sprite_path)))
+    }
+}
+
+pub fn spawn_pawn(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    terrain_map: &Res<TerrainMap>,
+    ground_configs: &Res<GroundConfigs>,
+    pawn_config: &Res<PawnConfig>,
+    tileset_manager: &mut ResMut<TilesetManager>,
+    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
+    pawn: Pawn,
+    spawn_position: Option<(f32, f32)>,
+) -> Entity {
+    let position = if let Some(pos) = spawn_position {
+        pos
+    } else {
+        // Find a passable spawn position
+        let initial_center = (0.0, 0.0);
+        if let Some(passable_pos) = terrain_map.find_nearest_passable_tile(initial_center, ground_configs) {
+            passable_pos
+        } else {
+            (0.0, 0.0) // Fallback
+        }
     };
 
-    commands.spawn((
+    let pawn_def = pawn_config.get_pawn_definition(&pawn.pawn_type)
+        .expect("Pawn definition not found in config");
+    // Pawns that age start out as young adults
+    let age = pawn_def.aging.as_ref().map(|aging| Age::new(aging.adult_age(), aging));
+    let stage = age.as_ref().map(|age| age.stage);
+    let sprite_path = pawn_def.sprite_for(stage);
+
+    let sprite_bundle = create_sprite(asset_server, tileset_manager, texture_atlas_layouts, sprite_path);
+    let size = pawn_def.size_for(stage);
+
+    let mut entity_commands = commands.spawn((
         sprite_bundle,
         Transform::from_translation(Vec3::new(position.0, position.1, 100.0)),
         pawn,
-        Size { value: pawn_def.size },
+        Size { value: size },
         Health::new(pawn_def.max_health),
         Endurance::new(pawn_def.max_endurance),
         CurrentBehavior { state: "idle".to_string() },
-    )).id()
+        YSort(DepthLayer::Pawns),
+    ));
+    // Children, so they're drawn wherever the pawn is
+    entity_commands.with_children(|parent| {
+        parent.spawn(pawn_shadow(asset_server, size, terrain_map.tile_size));
+        parent.spawn(selection_ring(asset_server, size, terrain_map.tile_size));
+    });
+    if pawn_def.can_swim {
+        entity_commands.insert(Swimmer);
+    }
+    if let Some(age) = age {
+        entity_commands.insert(age);
+    }
+    if let Some(walk_animation) = WalkAnimation::for_sprite(sprite_path, tileset_manager) {
+        entity_commands.insert(walk_animation);
+    }
+    if pawn_def.can_carry {
+        entity_commands.insert(Inventory::default());
+    }
+    if let Some(vision) = pawn_def.vision.as_ref() {
+        entity_commands.insert((Vision::new(vision), Memory::new(vision.memory)));
+    }
+    if let Some(thirst) = pawn_def.need_config(NeedKind::Thirst) {
+        entity_commands.insert(Thirst { current: thirst.max, max: thirst.max });
+    }
+    if let Some(energy) = pawn_def.need_config(NeedKind::Energy) {
+        entity_commands.insert(Energy { current: energy.max, max: energy.max });
+    }
+    entity_commands.id()
 }
 
 pub fn move_pawn_to_target(
     time: Res<Time>,
     pawn_config: Res<PawnConfig>,
     config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    weather: Res<Weather>,
     mut commands: Commands,
-    mut pawn_query: Query<(Entity, &mut Transform, &mut PawnTarget, &Pawn, &mut Endurance)>,
+    mut pawn_query: Query<(Entity, &mut Transform, &mut PawnTarget, &Pawn, &Size, &mut Endurance, Has<Swimmer>, Option<&Age>, Option<&StatusEffects>), (Without<Charge>, Without<Stunned>)>,
 ) {
-    for (entity, mut transform, mut target, pawn, mut endurance) in pawn_query.iter_mut() {
+    for (entity, mut transform, mut target, pawn, size, mut endurance, is_swimmer, age, status_effects) in pawn_query.iter_mut() {
         if let Some(current_waypoint) = target.get_current_waypoint() {
+            // Pushed or spawned off the path: keep going only if the straight line to the waypoint is still clear
+            if target.deviation_from_path(transform.translation) > OFF_PATH_THRESHOLD_TILES * config.tile_size {
+                let position = (transform.translation.x, transform.translation.y);
+                let waypoint = (current_waypoint.x, current_waypoint.y);
+                if !terrain_map.is_path_segment_clear(position, waypoint, size.value, &ground_configs.for_movement(is_swimmer)) {
+                    let goal = (target.target_position.x, target.target_position.y);
+                    println!("{} is off its path, re-pathing", pawn.pawn_type);
+                    commands.entity(entity)
+                        .remove::<PawnTarget>()
+                        .insert(PathfindingRequest::new(position, goal, size.value));
+                    continue;
+                }
+            }
+
             let distance = transform.translation.distance(current_waypoint);
             
             if distance > 2.0 { // Close enough threshold for waypoints
                 let pawn_def = pawn_config.get_pawn_definition(&pawn.pawn_type)
                     .expect("Pawn definition not found in config");
                 
+                // Swimmers move at their swim speed and tire faster while in water
+                let swimming = is_swimmer && is_in_swimmable_ground(&transform, &terrain_map, &ground_configs);
+                let (speed, endurance_multiplier) = if swimming {
+                    (pawn_def.swim_speed(), pawn_def.swim_endurance_multiplier())
+                } else {
+                    (pawn_def.move_speed, 1.0)
+                };
+                let ground = terrain_map.get_terrain_at_world_pos(transform.translation.x, transform.translation.y);
+                let speed = speed
+                    * pawn_def.speed_multiplier(age.map(|age| age.stage))
+                    * status_effects.map_or(1.0, |effects| effects.speed_multiplier())
+                    * ground.map_or(1.0, |ground| weather.speed_multiplier(ground, &ground_configs));
+
                 let direction = (current_waypoint - transform.translation).normalize();
-                let movement = direction * pawn_def.move_speed * time.delta_secs();
+                let movement = direction * speed * time.delta_secs();
                 
                 let actual_movement_distance = if movement.length() > distance {
                     // Don't overshoot the waypoint
@@ -291,7 +535,7 @@ pub fn move_pawn_to_target(
                 
                 // Reduce endurance based on distance moved
                 let cells_moved = actual_movement_distance / config.tile_size;
-                let endurance_cost = cells_moved * config.endurance_cost_per_cell;
+                let endurance_cost = cells_moved * config.endurance_cost_per_cell * endurance_multiplier;
                 endurance.current = (endurance.current - endurance_cost).max(0.0);
             } else {
                 // Reached current waypoint, advance to next
@@ -309,13 +553,14 @@ pub fn move_pawn_to_target(
     }
 }
 
+/// Starving (out of endurance) or dehydrated pawns slowly lose health
 pub fn endurance_health_loss_system(
     time: Res<Time>,
     config: Res<GameConfig>,
-    mut pawn_query: Query<(Entity, &mut Health, &mut Endurance, &Pawn)>,
+    mut pawn_query: Query<(Entity, &mut Health, &mut Endurance, &Pawn, Option<&Thirst>)>,
 ) {
-    for (_entity, mut health, mut endurance, _pawn) in pawn_query.iter_mut() {
-        if endurance.current <= 0.0 {
+    for (_entity, mut health, mut endurance, _pawn, thirst) in pawn_query.iter_mut() {
+        if endurance.current <= 0.0 || thirst.is_some_and(|thirst| thirst.current <= 0.0) {
             // Update health loss timer
             endurance.health_loss_timer += time.delta_secs();
             
@@ -332,41 +577,89 @@ pub fn endurance_health_loss_system(
     }
 }
 
-pub fn pawn_death_system(
-    mut commands: Commands,
-    pawn_query: Query<(Entity, &Health, &Pawn)>,
+/// Pawns with a `health_regen` entry heal at its rate while their endurance is above
+/// `min_endurance`, nothing is after them and they've been out of the fight for `combat_cooldown`
+pub fn health_regen_system(
+    time: Res<Time>,
+    pawn_config: Res<PawnConfig>,
+    mut last_combat: Local<HashMap<Entity, f32>>,
+    mut combat_events: EventReader<CombatEvent>,
+    mut pawn_query: Query<(Entity, &Pawn, &mut Health, &Endurance, &CurrentBehavior, Has<Threatened>, Has<Panicked>)>,
 ) {
-    for (entity, health, pawn) in pawn_query.iter() {
-        if health.current <= 0.0 {
-            println!("{} has died!", pawn.pawn_type);
-            commands.entity(entity).despawn();
+    let now = time.elapsed_secs();
+    for event in combat_events.read() {
+        last_combat.insert(event.attacker, now);
+        last_combat.insert(event.victim, now);
+    }
+    last_combat.retain(|entity, _| pawn_query.contains(*entity));
+
+    for (entity, pawn, mut health, endurance, current_behavior, threatened, panicked) in pawn_query.iter_mut() {
+        if health.current <= 0.0 || health.current >= health.max {
+            continue;
         }
+        let regen = match pawn_config.get_pawn_definition(&pawn.pawn_type).and_then(|def| def.health_regen.as_ref()) {
+            Some(regen) => regen,
+            None => continue,
+        };
+        let fleeing = threatened || panicked || current_behavior.state == "hunted" || current_behavior.state == "flee";
+        let fighting = last_combat.get(&entity).is_some_and(|&at| now - at < regen.combat_cooldown);
+        if fleeing || fighting || endurance.current <= endurance.max * regen.min_endurance {
+            continue;
+        }
+        health.current = (health.current + regen.rate * time.delta_secs()).min(health.max);
     }
 }
 
-pub fn endurance_behavior_switching_system(
-    pawn_config: Res<PawnConfig>,
-    mut pawn_query: Query<(&Pawn, &Endurance, &mut CurrentBehavior)>,
+fn is_in_swimmable_ground(transform: &Transform, terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> bool {
+    terrain_map
+        .get_terrain_at_world_pos(transform.translation.x, transform.translation.y)
+        .is_some_and(|terrain_type| ground_configs.is_swimmable(terrain_type))
+}
+
+/// Pawns in water drown unless they can swim and still have endurance left
+pub fn drowning_system(
+    time: Res<Time>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut pawn_query: Query<(&Transform, &mut Health, &Endurance, &Pawn, Has<Swimmer>)>,
 ) {
-    for (pawn, endurance, mut current_behavior) in pawn_query.iter_mut() {
-        let endurance_percentage = endurance.current / endurance.max;
-        
-        // Switch to looking_for_food when endurance is 30% or below
-        if endurance_percentage <= 0.3 && current_behavior.state != "looking_for_food" {
-            // Check if this pawn has a looking_for_food behavior defined
-            if let Some(behavior_config) = pawn_config.get_behaviour_config(&pawn.pawn_type, "looking_for_food") {
-                if !matches!(behavior_config, BehaviourConfig::Simple(BehaviourType::Null)) {
-                    println!("{} switching to looking_for_food behavior (endurance: {:.1}%)", 
-                             pawn.pawn_type, endurance_percentage * 100.0);
-                    current_behavior.state = "looking_for_food".to_string();
-                }
-            }
+    for (transform, mut health, endurance, pawn, is_swimmer) in pawn_query.iter_mut() {
+        if !is_in_swimmable_ground(transform, &terrain_map, &ground_configs) {
+            continue;
         }
-        // Switch back to idle when endurance is above 50% (hysteresis to prevent flapping)
-        else if endurance_percentage > 0.5 && current_behavior.state == "looking_for_food" {
-            println!("{} switching back to idle behavior (endurance: {:.1}%)", 
-                     pawn.pawn_type, endurance_percentage * 100.0);
-            current_behavior.state = "idle".to_string();
+        if is_swimmer && endurance.current > 0.0 {
+            continue;
+        }
+
+        let was_alive = health.current > 0.0;
+        health.current = (health.current - DROWNING_DAMAGE_PER_SEC * time.delta_secs()).max(0.0);
+        if was_alive && health.current <= 0.0 {
+            println!("{} drowned", pawn.pawn_type);
+        }
+    }
+}
+
+/// Despawn pawns with no health left, crediting the kill to whoever struck them last
+pub fn pawn_death_system(
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatEvent>,
+    mut death_events: EventWriter<DeathEvent>,
+    pawn_query: Query<(Entity, &Health, &Pawn, Option<&Transform>)>,
+) {
+    // Blows since the last run, so a killing blow dealt after this ran last frame still counts
+    let last_attackers: HashMap<Entity, Entity> = combat_events
+        .read()
+        .map(|event| (event.victim, event.attacker))
+        .collect();
+
+    for (entity, health, pawn, transform) in pawn_query.iter() {
+        if health.current <= 0.0 {
+            death_events.send(DeathEvent {
+                victim_type: pawn.pawn_type.clone(),
+                position: transform.map(|transform| transform.translation.truncate()).unwrap_or_default(),
+                killer: last_attackers.get(&entity).copied(),
+            });
+            commands.entity(entity).despawn_recursive();
         }
     }
-}
\ No newline at end of file
+}
@@ -1,19 +1,123 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
 use std::collections::HashMap;
-use std::fs;
+use crate::systems::faction::{FactionConfig, Stance};
+use crate::systems::world_clock::DayPhase;
 
 pub type PawnType = String;
 
+/// Swim speed as a fraction of move speed when a swimmer doesn't set swim_speed
+pub const DEFAULT_SWIM_SPEED_FACTOR: f32 = 0.5;
+/// Endurance cost multiplier for swimming when a swimmer doesn't set swim_endurance_multiplier
+pub const DEFAULT_SWIM_ENDURANCE_MULTIPLIER: f32 = 2.0;
+/// Most hunters in one pack when a pawn doesn't configure `pack`
+pub const DEFAULT_PACK_SIZE: u32 = 4;
+/// How close (in tiles) pack hunters must be to band together when a pawn doesn't configure `pack`
+pub const DEFAULT_PACK_COORDINATION_RADIUS: u32 = 10;
+/// Grazing defaults for pawns that eat grounds without a `graze` entry
+pub const DEFAULT_GRAZE_DURATION: f32 = 2.0;
+pub const DEFAULT_GRAZE_ENDURANCE: f32 = 5.0;
+/// Seconds a hunter spends eating a kill when its `eats` has no `meal` entry
+pub const DEFAULT_MEAL_DURATION: f32 = 5.0;
+/// Size of the thirst and energy meters when a need doesn't set `max`
+pub const DEFAULT_NEED_MAX: f32 = 100.0;
+/// Fraction of a need's max at which it becomes urgent when the need doesn't set `threshold`
+pub const DEFAULT_NEED_THRESHOLD: f32 = 0.3;
+/// How far (in tiles) pawns with a `perception` entry spot predators when it doesn't set `detection_radius`
+pub const DEFAULT_DETECTION_RADIUS: u32 = 6;
+/// Panic defaults for a `panic` entry that leaves them out - tiles and seconds
+pub const DEFAULT_PANIC_RADIUS: u32 = 5;
+pub const DEFAULT_PANIC_DURATION: f32 = 4.0;
+/// Seconds pawns with vision remember where they last saw someone when it doesn't set `memory`
+pub const DEFAULT_MEMORY_SECS: f32 = 10.0;
+/// Resting defaults for pawns without a `rest` entry
+pub const DEFAULT_REST_ENDURANCE_REGEN: f32 = 0.05;
+pub const DEFAULT_REST_DEFENCE: f32 = 0.5;
+/// Herding defaults for herd pawns without a `herd` entry
+pub const DEFAULT_HERD_RADIUS: u32 = 8;
+pub const DEFAULT_HERD_COHESION: f32 = 1.0;
+pub const DEFAULT_HERD_ALIGNMENT: f32 = 0.5;
+pub const DEFAULT_HERD_SEPARATION: f32 = 1.5;
+pub const DEFAULT_HERD_MOVE_INTERVAL_MIN: f32 = 2.0;
+pub const DEFAULT_HERD_MOVE_INTERVAL_MAX: f32 = 5.0;
+pub const DEFAULT_HERD_MOVE_RANGE: u32 = 5;
+/// Feedings it takes to tame a tameable pawn whose `tame` entry doesn't set `feedings`
+pub const DEFAULT_TAME_FEEDINGS: u32 = 3;
+/// Dens placed per species when a `den` entry doesn't set `count`
+pub const DEFAULT_DEN_COUNT: u32 = 1;
+/// How many food regions away a migrating pawn's new territory has to be when `migrate` doesn't set `min_distance`
+pub const DEFAULT_MIGRATION_MIN_DISTANCE: u32 = 2;
+/// Following defaults (in tiles) for pawns without a `follow` entry
+pub const DEFAULT_FOLLOW_MIN_DISTANCE: f32 = 1.0;
+pub const DEFAULT_FOLLOW_MAX_DISTANCE: f32 = 3.0;
+pub const DEFAULT_FOLLOW_REPATH_DISTANCE: f32 = 2.0;
+/// Ambush defaults for pawns without an `ambush` entry - tiles, tiles, pixels per second and seconds
+pub const DEFAULT_AMBUSH_SEARCH_RADIUS: u32 = 8;
+pub const DEFAULT_AMBUSH_STRIKE_RANGE: u32 = 3;
+pub const DEFAULT_AMBUSH_BURST_SPEED: f32 = 300.0;
+pub const DEFAULT_AMBUSH_PATIENCE: f32 = 30.0;
+/// Health regen defaults for a `health_regen` entry that only sets `rate` - fraction of max endurance and seconds
+pub const DEFAULT_REGEN_MIN_ENDURANCE: f32 = 0.5;
+pub const DEFAULT_REGEN_COMBAT_COOLDOWN: f32 = 5.0;
+/// Fraction of the gap to `max_size` closed by each feeding when `growth` doesn't set `per_feeding`
+pub const DEFAULT_GROWTH_PER_FEEDING: f32 = 0.1;
+/// Simulated seconds between new arrivals of a dwindling species when `population` doesn't set `interval`
+pub const DEFAULT_RESPAWN_INTERVAL: f32 = 10.0;
+/// Score bonus for the current state of utility-driven pawns that don't set `hysteresis`
+pub const DEFAULT_UTILITY_HYSTERESIS: f32 = 0.1;
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BehaviourType {
     Null,
     Flee,
     HuntSolo,
+    HuntPack, // Hunt alongside nearby pawns of the same type, sharing prey and kills
+    Eat, // Attack prey already within reach, without chasing it
+    Drink, // Walk to the nearest drinkable ground and drink until no longer thirsty
+    Sleep, // Stay put and recover energy
+    Herd, // Wander, but stay with nearby pawns of the same type
+    Follow, // Keep within a distance band of a leader - its owner, pack leader or parent
+    Migrate, // Travel to a new territory with more food
+    Ambush, // Hide where prey passes most and burst out at anything that comes within strike range
     PlayerInput,
 }
 
+impl BehaviourType {
+    pub const ALL: [BehaviourType; 12] = [
+        BehaviourType::Null,
+        BehaviourType::Flee,
+        BehaviourType::HuntSolo,
+        BehaviourType::HuntPack,
+        BehaviourType::Eat,
+        BehaviourType::Drink,
+        BehaviourType::Sleep,
+        BehaviourType::Herd,
+        BehaviourType::Follow,
+        BehaviourType::Migrate,
+        BehaviourType::Ambush,
+        BehaviourType::PlayerInput,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BehaviourType::Null => "null",
+            BehaviourType::Flee => "flee",
+            BehaviourType::HuntSolo => "hunt_solo",
+            BehaviourType::HuntPack => "hunt_pack",
+            BehaviourType::Eat => "eat",
+            BehaviourType::Drink => "drink",
+            BehaviourType::Sleep => "sleep",
+            BehaviourType::Herd => "herd",
+            BehaviourType::Follow => "follow",
+            BehaviourType::Migrate => "migrate",
+            BehaviourType::Ambush => "ambush",
+            BehaviourType::PlayerInput => "player_input",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WanderingConfig {
     pub move_interval_min: f32,
@@ -26,9 +130,63 @@ pub struct WanderingConfig {
 pub enum BehaviourConfig {
     Simple(BehaviourType),
     Wandering { wandering: WanderingConfig },
+    Script { script: String }, // Path to a script run by the `scripting` feature
+}
+
+impl BehaviourConfig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BehaviourConfig::Simple(behaviour) => behaviour.as_str(),
+            BehaviourConfig::Wandering { .. } => "wandering",
+            BehaviourConfig::Script { .. } => "script",
+        }
+    }
 }
 
+/// Checks a behaviour tree can make about a pawn and its surroundings
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviourCondition {
+    EnduranceBelow(f32), // Fraction of max endurance
+    EnduranceAbove(f32),
+    HealthBelow(f32),    // Fraction of max health
+    PredatorWithin(u32), // Tiles to the nearest pawn that eats this one
+    PreyWithin(u32),     // Tiles to the nearest pawn this one eats
+    ThirstBelow(f32),    // Fraction of max thirst - never true for pawns that don't get thirsty
+    EnergyBelow(f32),    // Fraction of max energy - never true for pawns that don't get tired
+    UrgentNeed(NeedKind), // This need is the most urgent one, or the one being tended and not yet satisfied
+    RestTime,            // The pawn's schedule has it resting at this time of day
+    Night,               // Between dusk and dawn, whatever the pawn's schedule
+    Threatened,          // Perception has spotted a predator that's still too close for comfort
+    Not(Box<BehaviourCondition>),
+}
+
+/// Data-driven behaviour tree node. Actions are long-running behaviours rather than one-shot
+/// tasks, so every tick the tree picks the first action it reaches and the pawn keeps doing it
+/// until a later tick picks another.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviourNode {
+    Selector(Vec<BehaviourNode>), // Succeeds with the first child that succeeds
+    Sequence(Vec<BehaviourNode>), // Fails at the first child that fails
+    Condition(BehaviourCondition),
+    Action(BehaviourConfig),
+}
+
+impl BehaviourNode {
+    /// Every action reachable from this node, in tree order
+    pub fn actions(&self) -> Vec<&BehaviourConfig> {
+        match self {
+            BehaviourNode::Selector(children) | BehaviourNode::Sequence(children) => {
+                children.iter().flat_map(|child| child.actions()).collect()
+            }
+            BehaviourNode::Condition(_) => Vec::new(),
+            BehaviourNode::Action(action) => vec![action],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct PawnBehaviours {
     pub idle: Option<BehaviourConfig>,
     pub hunted: Option<BehaviourConfig>,
@@ -36,11 +194,576 @@ pub struct PawnBehaviours {
     pub eat: Option<BehaviourConfig>,
     pub controlled: Option<BehaviourConfig>,
     pub flee: Option<BehaviourConfig>,
+    pub thirsty: Option<BehaviourConfig>,
+    pub tired: Option<BehaviourConfig>,
+    pub resting: Option<BehaviourConfig>, // Outside the pawn's active hours
+}
+
+/// Names of the behaviour states above, as used by utility options and get_behaviour_config
+pub const BEHAVIOUR_STATES: [&str; 9] = [
+    "idle", "hunted", "looking_for_food", "eat", "controlled", "flee", "thirsty", "tired", "resting",
+];
+
+/// Needs that decay over time and drive behaviour once they run low.
+/// Hunger is the pawn's endurance; thirst and energy are separate meters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NeedKind {
+    Hunger,
+    Thirst,
+    Energy,
+}
+
+impl NeedKind {
+    pub const ALL: [NeedKind; 3] = [NeedKind::Hunger, NeedKind::Thirst, NeedKind::Energy];
+
+    /// Behaviour state a state-driven pawn switches to while this need is urgent
+    pub fn state(&self) -> &'static str {
+        match self {
+            NeedKind::Hunger => "looking_for_food",
+            NeedKind::Thirst => "thirsty",
+            NeedKind::Energy => "tired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NeedConfig {
+    #[serde(default = "default_need_max")]
+    pub max: f32, // Ignored for hunger, which uses max_endurance
+    #[serde(default)]
+    pub decay: f32, // Lost per second
+    #[serde(default = "default_need_threshold")]
+    pub threshold: f32, // Fraction of max at or below which the need becomes urgent
+}
+
+fn default_need_max() -> f32 {
+    DEFAULT_NEED_MAX
+}
+
+fn default_need_threshold() -> f32 {
+    DEFAULT_NEED_THRESHOLD
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PawnNeeds {
+    #[serde(default)]
+    pub hunger: Option<NeedConfig>,
+    #[serde(default)]
+    pub thirst: Option<NeedConfig>, // Pawns without it never get thirsty
+    #[serde(default)]
+    pub energy: Option<NeedConfig>, // Pawns without it never get tired
+}
+
+impl PawnNeeds {
+    pub fn get(&self, need: NeedKind) -> Option<&NeedConfig> {
+        match need {
+            NeedKind::Hunger => self.hunger.as_ref(),
+            NeedKind::Thirst => self.thirst.as_ref(),
+            NeedKind::Energy => self.energy.as_ref(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PawnEats {
     pub pawns: Vec<PawnType>,
+    #[serde(default)]
+    pub grounds: Vec<String>, // Ground names from grounds.yaml this pawn grazes on
+    #[serde(default)]
+    pub graze: Option<GrazeConfig>,
+    #[serde(default)]
+    pub meal: Option<MealConfig>,
+}
+
+impl PawnEats {
+    pub fn graze_config(&self) -> GrazeConfig {
+        self.graze.clone().unwrap_or_default()
+    }
+
+    pub fn meal_config(&self) -> MealConfig {
+        self.meal.clone().unwrap_or_default()
+    }
+}
+
+/// How long a hunter stays at a kill eating it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MealConfig {
+    pub duration: f32, // Seconds to get all of the kill's endurance - it trickles in meanwhile
+}
+
+impl Default for MealConfig {
+    fn default() -> Self {
+        Self { duration: DEFAULT_MEAL_DURATION }
+    }
+}
+
+/// How long grazing takes, what it's worth and what it does to the ground
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GrazeConfig {
+    pub duration: f32,  // Seconds spent eating one tile
+    pub endurance: f32, // Endurance restored per tile eaten
+    #[serde(default)]
+    pub leaves: Option<String>, // Ground an eaten tile turns into (e.g. dirt) - untouched if unset
+}
+
+impl Default for GrazeConfig {
+    fn default() -> Self {
+        Self {
+            duration: DEFAULT_GRAZE_DURATION,
+            endurance: DEFAULT_GRAZE_ENDURANCE,
+            leaves: None,
+        }
+    }
+}
+
+/// Terrain a pawn slowly converts the ground it occupies into (e.g. fire elementals scorch the land)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TerrainInfusion {
+    pub terrain: String, // Ground name from grounds.yaml
+    pub rate: f32,       // Influence added per second spent on a tile
+}
+
+/// Straight-line charge attack - damage and knockback scale with the speed reached
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChargeConfig {
+    pub max_speed: f32,    // Pixels per second
+    pub acceleration: f32, // Pixels per second squared
+    pub range: u32,        // Maximum charge distance in tiles
+    pub cooldown: f32,     // Seconds before the pawn can charge again
+}
+
+/// How hunt_pack hunters of this type group up
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PackConfig {
+    pub max_size: u32,
+    pub coordination_radius: u32, // Tiles from the pack leader
+}
+
+impl Default for PackConfig {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_PACK_SIZE,
+            coordination_radius: DEFAULT_PACK_COORDINATION_RADIUS,
+        }
+    }
+}
+
+/// How herd pawns of this type move together. Each move heads off in a random direction pulled
+/// towards the herd (cohesion), along with it (alignment) and away from herd-mates that are too
+/// close (separation), weighted as configured.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HerdConfig {
+    pub radius: u32, // Tiles within which other pawns of the same type count as the herd
+    pub cohesion: f32,
+    pub alignment: f32,
+    pub separation: f32,
+    pub move_interval_min: f32,
+    pub move_interval_max: f32,
+    pub move_range: u32,
+}
+
+impl Default for HerdConfig {
+    fn default() -> Self {
+        Self {
+            radius: DEFAULT_HERD_RADIUS,
+            cohesion: DEFAULT_HERD_COHESION,
+            alignment: DEFAULT_HERD_ALIGNMENT,
+            separation: DEFAULT_HERD_SEPARATION,
+            move_interval_min: DEFAULT_HERD_MOVE_INTERVAL_MIN,
+            move_interval_max: DEFAULT_HERD_MOVE_INTERVAL_MAX,
+            move_range: DEFAULT_HERD_MOVE_RANGE,
+        }
+    }
+}
+
+/// How long each phase of an attack takes, in seconds - pawns without an `attack` entry strike instantly
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AttackConfig {
+    #[serde(default)]
+    pub windup: f32, // Telegraphing the attack - prey that gets out of reach in time dodges it
+    #[serde(default)]
+    pub strike: f32, // Lunging at the target once the damage lands
+    #[serde(default)]
+    pub recover: f32, // Standing still afterwards
+}
+
+/// Stage of a pawn's life - see AgingConfig
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeStage {
+    Juvenile,
+    Adult,
+    Elderly,
+}
+
+/// Lifespan and life stages of a pawn type - ages are in in-game days
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AgingConfig {
+    pub max_age: f32, // Pawns die of old age on reaching it
+    #[serde(default)]
+    pub juvenile: Option<LifeStageConfig>, // Until its age
+    #[serde(default)]
+    pub elderly: Option<LifeStageConfig>, // From its age
+}
+
+impl AgingConfig {
+    pub fn stage_at(&self, age_days: f32) -> LifeStage {
+        if self.juvenile.as_ref().is_some_and(|juvenile| age_days < juvenile.age) {
+            LifeStage::Juvenile
+        } else if self.elderly.as_ref().is_some_and(|elderly| age_days >= elderly.age) {
+            LifeStage::Elderly
+        } else {
+            LifeStage::Adult
+        }
+    }
+
+    /// Age at which pawns are fully grown
+    pub fn adult_age(&self) -> f32 {
+        self.juvenile.as_ref().map_or(0.0, |juvenile| juvenile.age)
+    }
+
+    pub fn stage_config(&self, stage: LifeStage) -> Option<&LifeStageConfig> {
+        match stage {
+            LifeStage::Juvenile => self.juvenile.as_ref(),
+            LifeStage::Adult => None,
+            LifeStage::Elderly => self.elderly.as_ref(),
+        }
+    }
+}
+
+/// Multipliers on the adult stats while a pawn is in a life stage
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LifeStageConfig {
+    pub age: f32,
+    #[serde(default = "default_stage_multiplier")]
+    pub size: f32,
+    #[serde(default = "default_stage_multiplier")]
+    pub speed: f32,
+    #[serde(default = "default_stage_multiplier")]
+    pub strength: f32,
+    #[serde(default = "default_stage_multiplier")]
+    pub defence: f32,
+    #[serde(default)]
+    pub sprite: Option<String>, // Another sprite from the pawn's tileset, e.g. "tileset::pawns::rabbit_young"
+}
+
+fn default_stage_multiplier() -> f32 {
+    1.0
+}
+
+/// How a pawn notices predators
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PerceptionConfig {
+    #[serde(default = "default_detection_radius")]
+    pub detection_radius: u32, // Tiles
+    #[serde(default)]
+    pub line_of_sight: bool, // Predators behind sight-blocking ground (e.g. stone) go unnoticed
+    #[serde(default)]
+    pub panic: Option<PanicConfig>, // Pawns without it only flee from predators they spot themselves
+}
+
+/// How a fleeing pawn's panic spreads to others of its kind
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PanicConfig {
+    pub radius: u32,   // Tiles - pawns of the same type this close catch the panic
+    pub duration: f32, // Seconds a caught panic lasts without being renewed
+}
+
+impl Default for PanicConfig {
+    fn default() -> Self {
+        Self {
+            radius: DEFAULT_PANIC_RADIUS,
+            duration: DEFAULT_PANIC_DURATION,
+        }
+    }
+}
+
+fn default_detection_radius() -> u32 {
+    DEFAULT_DETECTION_RADIUS
+}
+
+/// What a pawn can see - hunters only pick out prey they can see
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VisionConfig {
+    pub range: u32, // Tiles
+    #[serde(default)]
+    pub fov: Option<f32>, // Degrees, centred on the way the pawn last moved; all round when unset
+    #[serde(default = "default_memory_secs")]
+    pub memory: f32, // Seconds a pawn remembers where it last saw someone after losing sight of them
+}
+
+fn default_memory_secs() -> f32 {
+    DEFAULT_MEMORY_SECS
+}
+
+/// When a pawn is up and about - it rests the rest of the day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivitySchedule {
+    #[default]
+    Always,
+    Diurnal,   // Active by day, rests at night
+    Nocturnal, // Active by night, rests by day
+}
+
+impl ActivitySchedule {
+    pub fn rests_during(&self, phase: DayPhase) -> bool {
+        match self {
+            ActivitySchedule::Always => false,
+            ActivitySchedule::Diurnal => phase == DayPhase::Night,
+            ActivitySchedule::Nocturnal => phase == DayPhase::Day,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TameConfig {
+    #[serde(default = "default_tame_feedings")]
+    pub feedings: u32, // Times the player has to feed it before it's tamed
+}
+
+fn default_tame_feedings() -> u32 {
+    DEFAULT_TAME_FEEDINGS
+}
+
+/// When a pawn gives up on its home region for a richer one
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MigrateConfig {
+    pub min_food: u32, // Food (prey pawns plus edible tiles) below which its home region is too poor
+    #[serde(default = "default_migration_min_distance")]
+    pub min_distance: u32, // Food regions away the new territory has to be
+}
+
+fn default_migration_min_distance() -> u32 {
+    DEFAULT_MIGRATION_MIN_DISTANCE
+}
+
+/// Where a species' dens go at world generation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DenConfig {
+    pub count: u32,
+    pub grounds: Vec<String>, // Ground names from grounds.yaml to dig dens in - any passable ground when empty
+}
+
+impl Default for DenConfig {
+    fn default() -> Self {
+        Self {
+            count: DEFAULT_DEN_COUNT,
+            grounds: Vec::new(),
+        }
+    }
+}
+
+/// The distance band (in tiles) a following pawn keeps from its leader
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FollowConfig {
+    pub min_distance: f32,    // Closer than this it stops and waits
+    pub max_distance: f32,    // Further than this it sets off after the leader
+    pub repath_distance: f32, // How far the leader may move from where it's heading before it re-paths
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            min_distance: DEFAULT_FOLLOW_MIN_DISTANCE,
+            max_distance: DEFAULT_FOLLOW_MAX_DISTANCE,
+            repath_distance: DEFAULT_FOLLOW_REPATH_DISTANCE,
+        }
+    }
+}
+
+/// Where an `ambush` pawn hides and how it springs its trap
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AmbushConfig {
+    pub search_radius: u32, // Tiles around the hunter it looks for the most scent-trodden spot in
+    pub strike_range: u32,  // Tiles - prey this close sets off the burst
+    pub burst_speed: f32,   // Pixels per second, hitting like a charge at that speed
+    pub patience: f32,      // Seconds hidden without prey before trying another spot
+}
+
+impl Default for AmbushConfig {
+    fn default() -> Self {
+        Self {
+            search_radius: DEFAULT_AMBUSH_SEARCH_RADIUS,
+            strike_range: DEFAULT_AMBUSH_STRIKE_RANGE,
+            burst_speed: DEFAULT_AMBUSH_BURST_SPEED,
+            patience: DEFAULT_AMBUSH_PATIENCE,
+        }
+    }
+}
+
+/// How a pawn heals on its own
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HealthRegenConfig {
+    pub rate: f32, // Health per second
+    #[serde(default = "default_regen_min_endurance")]
+    pub min_endurance: f32, // Fraction (0-1) of max endurance it has to be above to heal
+    #[serde(default = "default_regen_combat_cooldown")]
+    pub combat_cooldown: f32, // Seconds after landing or taking a blow before it starts healing
+}
+
+fn default_regen_min_endurance() -> f32 {
+    DEFAULT_REGEN_MIN_ENDURANCE
+}
+
+fn default_regen_combat_cooldown() -> f32 {
+    DEFAULT_REGEN_COMBAT_COOLDOWN
+}
+
+/// How big a pawn can grow by feeding
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GrowthConfig {
+    pub max_size: f32,
+    #[serde(default = "default_growth_per_feeding")]
+    pub per_feeding: f32, // Fraction (0-1) of the gap between its size and max_size each feeding closes
+}
+
+fn default_growth_per_feeding() -> f32 {
+    DEFAULT_GROWTH_PER_FEEDING
+}
+
+/// Numbers a species is kept at by new pawns arriving, as nothing else replaces the dead
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PopulationConfig {
+    pub min: u32, // Below this, new pawns start arriving
+    #[serde(default)]
+    pub max: Option<u32>, // ...and keep arriving until there are this many again - min when unset
+    #[serde(default = "default_respawn_interval")]
+    pub interval: f32, // Simulated seconds between arrivals
+}
+
+impl PopulationConfig {
+    /// How many there are once the species has been topped up
+    pub fn target(&self) -> u32 {
+        self.max.unwrap_or(self.min).max(self.min)
+    }
+}
+
+fn default_respawn_interval() -> f32 {
+    DEFAULT_RESPAWN_INTERVAL
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RestConfig {
+    #[serde(default = "default_rest_endurance_regen")]
+    pub endurance_regen: f32, // Fraction of max endurance recovered per second asleep
+    #[serde(default = "default_rest_defence")]
+    pub defence: f32, // Defence multiplier while asleep
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            endurance_regen: DEFAULT_REST_ENDURANCE_REGEN,
+            defence: DEFAULT_REST_DEFENCE,
+        }
+    }
+}
+
+fn default_rest_endurance_regen() -> f32 {
+    DEFAULT_REST_ENDURANCE_REGEN
+}
+
+fn default_rest_defence() -> f32 {
+    DEFAULT_REST_DEFENCE
+}
+
+/// What a utility consideration looks at - every input is a value from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UtilityInput {
+    Hunger,    // Fraction of max endurance missing
+    Thirst,    // Fraction of max thirst missing - 0 for pawns that don't get thirsty
+    Tiredness, // Fraction of max energy missing - 0 for pawns that don't get tired
+    Threat,    // 1 with a spotted predator right there, falling to 0 at the detection radius
+    Health,    // Fraction of max health
+    RestTime,  // 1 while the pawn's schedule has it resting, otherwise 0
+    Darkness,  // 0 in full day, rising over dusk to 1 at night
+}
+
+/// Maps a consideration's input onto a score, clamped to 0-1
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseCurve {
+    Linear {
+        #[serde(default = "default_curve_slope")]
+        slope: f32,
+        #[serde(default)]
+        intercept: f32,
+    },
+    Power { exponent: f32 },                    // Above 1 only picks up once the input gets high
+    Logistic { midpoint: f32, steepness: f32 }, // S-curve flipping over around the midpoint
+    Step { threshold: f32 },                    // 0 below the threshold, 1 from it
+}
+
+impl Default for ResponseCurve {
+    fn default() -> Self {
+        ResponseCurve::Linear { slope: 1.0, intercept: 0.0 }
+    }
+}
+
+impl ResponseCurve {
+    pub fn evaluate(&self, input: f32) -> f32 {
+        let score = match *self {
+            ResponseCurve::Linear { slope, intercept } => slope * input + intercept,
+            ResponseCurve::Power { exponent } => input.max(0.0).powf(exponent),
+            ResponseCurve::Logistic { midpoint, steepness } => 1.0 / (1.0 + (-steepness * (input - midpoint)).exp()),
+            ResponseCurve::Step { threshold } => if input >= threshold { 1.0 } else { 0.0 },
+        };
+        score.clamp(0.0, 1.0)
+    }
+}
+
+fn default_curve_slope() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UtilityConsideration {
+    pub input: UtilityInput,
+    #[serde(default)]
+    pub curve: ResponseCurve, // The input as-is when unset
+}
+
+/// A behaviour state a utility-driven pawn can pick, and how much it wants to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UtilityOption {
+    pub state: String, // Behaviour state to switch to, e.g. looking_for_food
+    #[serde(default = "default_utility_weight")]
+    pub weight: f32,
+    #[serde(default)]
+    pub considerations: Vec<UtilityConsideration>, // Multiplied together - with none the option scores its weight
+}
+
+impl UtilityOption {
+    pub fn score(&self, input: impl Fn(UtilityInput) -> f32) -> f32 {
+        self.considerations
+            .iter()
+            .map(|consideration| consideration.curve.evaluate(input(consideration.input)))
+            .product::<f32>()
+            * self.weight
+    }
+}
+
+fn default_utility_weight() -> f32 {
+    1.0
+}
+
+/// Scores every option each tick and switches to the best - replaces the fixed need thresholds
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UtilityConfig {
+    #[serde(default = "default_utility_hysteresis")]
+    pub hysteresis: f32, // Added to the current state's score so close calls don't flip back and forth
+    pub options: Vec<UtilityOption>,
+}
+
+fn default_utility_hysteresis() -> f32 {
+    DEFAULT_UTILITY_HYSTERESIS
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -54,22 +777,238 @@ pub struct PawnDefinition {
     pub defence: u32,
     pub attack_speed: f32,
     pub reach: u32,
+    #[serde(default)]
+    pub attack: Option<AttackConfig>,
     pub size: f32,
     pub spawn_count: u32,
+    #[serde(default)]
+    pub faction: Option<String>, // Faction from factions.yaml - pawns without one are neutral to everyone
+    #[serde(default)]
     pub behaviours: PawnBehaviours,
+    #[serde(default)]
+    pub behaviour_tree: Option<BehaviourNode>, // Takes over from the state-based behaviours when set
+    #[serde(default)]
+    pub pack: Option<PackConfig>,
+    #[serde(default)]
+    pub herd: Option<HerdConfig>,
     pub eats: PawnEats,
+    #[serde(default)]
+    pub infuses: Option<TerrainInfusion>,
+    #[serde(default)]
+    pub charge: Option<ChargeConfig>,
+    #[serde(default)]
+    pub can_swim: bool, // Swimmable grounds (water) are passable for this pawn
+    #[serde(default)]
+    pub swim_speed: Option<f32>,
+    #[serde(default)]
+    pub swim_endurance_multiplier: Option<f32>, // Endurance cost per cell swum, relative to walking
+    #[serde(default)]
+    pub aging: Option<AgingConfig>, // Pawns without it never age
+    #[serde(default)]
+    pub needs: Option<PawnNeeds>,
+    #[serde(default)]
+    pub schedule: ActivitySchedule,
+    #[serde(default)]
+    pub rest: Option<RestConfig>,
+    #[serde(default)]
+    pub perception: Option<PerceptionConfig>, // Pawns without it never switch to hunted
+    #[serde(default)]
+    pub vision: Option<VisionConfig>, // Pawns without it see everything
+    #[serde(default)]
+    pub utility: Option<UtilityConfig>, // Picks the behaviour state by score instead of need thresholds
+    #[serde(default)]
+    pub tame: Option<TameConfig>, // Pawns without it can't be tamed
+    #[serde(default)]
+    pub follow: Option<FollowConfig>,
+    #[serde(default)]
+    pub den: Option<DenConfig>, // Species without one have no home and spawn anywhere
+    #[serde(default)]
+    pub migrate: Option<MigrateConfig>, // Pawns without it stay put however little food is left
+    #[serde(default)]
+    pub stealth: f32, // Fraction (0-1) of prey's detection radius lost while this pawn lies in ambush
+    #[serde(default)]
+    pub ambush: Option<AmbushConfig>,
+    #[serde(default)]
+    pub inflicts: Vec<String>, // Status effects from status_effects.yaml put on whatever this pawn's blows land on
+    #[serde(default)]
+    pub health_regen: Option<HealthRegenConfig>, // Pawns without it only heal from effects like regen
+    #[serde(default)]
+    pub growth: Option<GrowthConfig>, // Pawns without it stay the size of their species
+    #[serde(default)]
+    pub population: Option<PopulationConfig>, // Species without it can die out
+    #[serde(default)]
+    pub can_carry: bool, // Picks up items from items.yaml that it walks over, as much as its size lets it carry
+}
+
+impl PawnDefinition {
+    pub fn swim_speed(&self) -> f32 {
+        self.swim_speed.unwrap_or(self.move_speed * DEFAULT_SWIM_SPEED_FACTOR)
+    }
+
+    pub fn swim_endurance_multiplier(&self) -> f32 {
+        self.swim_endurance_multiplier.unwrap_or(DEFAULT_SWIM_ENDURANCE_MULTIPLIER)
+    }
+
+    pub fn pack_config(&self) -> PackConfig {
+        self.pack.clone().unwrap_or_default()
+    }
+
+    pub fn attack_config(&self) -> AttackConfig {
+        self.attack.clone().unwrap_or_default()
+    }
+
+    pub fn herd_config(&self) -> HerdConfig {
+        self.herd.clone().unwrap_or_default()
+    }
+
+    pub fn follow_config(&self) -> FollowConfig {
+        self.follow.clone().unwrap_or_default()
+    }
+
+    pub fn ambush_config(&self) -> AmbushConfig {
+        self.ambush.clone().unwrap_or_default()
+    }
+
+    pub fn need_config(&self, need: NeedKind) -> Option<&NeedConfig> {
+        self.needs.as_ref().and_then(|needs| needs.get(need))
+    }
+
+    pub fn need_threshold(&self, need: NeedKind) -> f32 {
+        self.need_config(need).map_or(DEFAULT_NEED_THRESHOLD, |config| config.threshold)
+    }
+
+    pub fn rest_config(&self) -> RestConfig {
+        self.rest.clone().unwrap_or_default()
+    }
+
+    fn stage_multiplier(&self, stage: Option<LifeStage>, multiplier: impl Fn(&LifeStageConfig) -> f32) -> f32 {
+        match (&self.aging, stage) {
+            (Some(aging), Some(stage)) => aging.stage_config(stage).map_or(1.0, multiplier),
+            _ => 1.0,
+        }
+    }
+
+    pub fn size_for(&self, stage: Option<LifeStage>) -> f32 {
+        self.size * self.stage_multiplier(stage, |config| config.size)
+    }
+
+    pub fn speed_multiplier(&self, stage: Option<LifeStage>) -> f32 {
+        self.stage_multiplier(stage, |config| config.speed)
+    }
+
+    pub fn strength_for(&self, stage: Option<LifeStage>) -> f32 {
+        self.strength as f32 * self.stage_multiplier(stage, |config| config.strength)
+    }
+
+    /// Defence for a life stage - sleeping pawns are easier to hurt
+    pub fn defence_for(&self, stage: Option<LifeStage>, asleep: bool) -> f32 {
+        let rest_multiplier = if asleep { self.rest_config().defence } else { 1.0 };
+        self.defence as f32 * self.stage_multiplier(stage, |config| config.defence) * rest_multiplier
+    }
+
+    /// Sprite for a life stage, falling back to the pawn's own
+    pub fn sprite_for(&self, stage: Option<LifeStage>) -> &str {
+        match (&self.aging, stage) {
+            (Some(aging), Some(stage)) => aging
+                .stage_config(stage)
+                .and_then(|config| config.sprite.as_deref())
+                .unwrap_or(&self.sprite),
+            _ => &self.sprite,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Resource, Deserialize, Serialize)]
 pub struct PawnConfig {
     #[serde(flatten)]
     pub pawns: HashMap<PawnType, PawnDefinition>,
+    #[serde(skip)]
+    pub factions: FactionConfig, // Loaded from factions.yaml alongside pawns.yaml
+}
+
+/// Fill in every pawn that `extends` another from that one's definition, then drop the entries
+/// marked `template: true`, which are only there to be extended and can leave fields out. Fields
+/// a pawn sets replace the ones it inherits, with sections like `eats` or `behaviours` merged key
+/// by key - except `behaviour_tree`, which is replaced whole.
+pub fn resolve_pawn_templates(pawns: Value) -> Result<Value, String> {
+    let entries = match pawns {
+        Value::Mapping(entries) => entries,
+        other => return Ok(other),
+    };
+    let mut resolved: HashMap<String, Value> = HashMap::new();
+    for name in entries.keys().filter_map(Value::as_str) {
+        resolve_pawn_template(name, &entries, &mut resolved, &mut Vec::new())?;
+    }
+
+    let mut pawns = Mapping::new();
+    for name in entries.keys().filter_map(Value::as_str) {
+        let definition = resolved.remove(name).unwrap_or_default();
+        if definition.get("template").and_then(Value::as_bool) != Some(true) {
+            pawns.insert(Value::from(name), definition);
+        }
+    }
+    Ok(Value::Mapping(pawns))
+}
+
+fn resolve_pawn_template(
+    name: &str,
+    entries: &Mapping,
+    resolved: &mut HashMap<String, Value>,
+    chain: &mut Vec<String>,
+) -> Result<Value, String> {
+    if let Some(definition) = resolved.get(name) {
+        return Ok(definition.clone());
+    }
+    if chain.iter().any(|link| link == name) {
+        chain.push(name.to_string());
+        return Err(format!("pawns extend each other in a loop: {}", chain.join(" -> ")));
+    }
+    let mut definition = entries.get(name).cloned().unwrap_or_default();
+    let parent = match definition.as_mapping_mut().and_then(|fields| fields.remove("extends")) {
+        None => None,
+        Some(Value::String(parent)) => Some(parent),
+        Some(_) => return Err(format!("{}.extends should be the name of another pawn", name)),
+    };
+    if let Some(parent) = parent {
+        if !entries.contains_key(parent.as_str()) {
+            return Err(format!("{} extends {}, which isn't in pawns.yaml", name, parent));
+        }
+        chain.push(name.to_string());
+        let mut inherited = resolve_pawn_template(&parent, entries, resolved, chain)?;
+        chain.pop();
+        if let Some(fields) = inherited.as_mapping_mut() {
+            fields.remove("template"); // Extending a template doesn't make this one
+        }
+        definition = merge_pawn_fields(inherited, definition);
+    }
+    resolved.insert(name.to_string(), definition.clone());
+    Ok(definition)
+}
+
+fn merge_pawn_fields(inherited: Value, overrides: Value) -> Value {
+    match (inherited, overrides) {
+        (Value::Mapping(mut fields), Value::Mapping(overrides)) => {
+            for (key, value) in overrides {
+                match fields.get_mut(&key) {
+                    Some(existing) if key.as_str() != Some("behaviour_tree") => {
+                        *existing = merge_pawn_fields(std::mem::take(existing), value);
+                    }
+                    _ => {
+                        fields.insert(key, value);
+                    }
+                }
+            }
+            Value::Mapping(fields)
+        }
+        (_, overrides) => overrides,
+    }
 }
 
 impl PawnConfig {
-    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let contents = fs::read_to_string(path)?;
-        let config: PawnConfig = serde_yaml::from_str(&contents)?;
+    /// Pawn definitions from YAML, with templates resolved
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let pawns: Value = serde_yaml::from_str(contents)?;
+        let config: PawnConfig = serde_yaml::from_value(resolve_pawn_templates(pawns)?)?;
         Ok(config)
     }
 
@@ -77,8 +1016,11 @@ impl PawnConfig {
         self.pawns.get(pawn_type)
     }
 
+    /// Pawn types in name order, so going through them draws from the RNG the same way each time
     pub fn get_pawn_types(&self) -> Vec<String> {
-        self.pawns.keys().cloned().collect()
+        let mut pawn_types: Vec<String> = self.pawns.keys().cloned().collect();
+        pawn_types.sort();
+        pawn_types
     }
 
     pub fn get_behaviour_config(&self, pawn_type: &str, state: &str) -> Option<&BehaviourConfig> {
@@ -90,18 +1032,13 @@ impl PawnConfig {
             "eat" => def.behaviours.eat.as_ref(),
             "controlled" => def.behaviours.controlled.as_ref(),
             "flee" => def.behaviours.flee.as_ref(),
+            "thirsty" => def.behaviours.thirsty.as_ref(),
+            "tired" => def.behaviours.tired.as_ref(),
+            "resting" => def.behaviours.resting.as_ref(),
             _ => None,
         }
     }
 
-    pub fn get_wandering_config(&self, pawn_type: &str, state: &str) -> Option<&WanderingConfig> {
-        if let Some(BehaviourConfig::Wandering { wandering }) = self.get_behaviour_config(pawn_type, state) {
-            Some(wandering)
-        } else {
-            None
-        }
-    }
-
     pub fn can_eat(&self, predator: &PawnType, prey: &PawnType) -> bool {
         if let Some(def) = self.get_pawn_definition(predator) {
             def.eats.pawns.contains(prey)
@@ -110,6 +1047,31 @@ impl PawnConfig {
         }
     }
 
+    /// Whether any kind of pawn goes after this one
+    pub fn is_prey(&self, pawn_type: &str) -> bool {
+        let pawn_type = pawn_type.to_string();
+        self.pawns.keys().any(|predator| self.will_attack(predator, &pawn_type))
+    }
+
+    /// Stance of one pawn type's faction towards another's - neutral unless both have a faction
+    pub fn stance_between(&self, from_type: &str, to_type: &str) -> Stance {
+        let faction = |pawn_type: &str| self.get_pawn_definition(pawn_type).and_then(|def| def.faction.as_deref());
+        match (faction(from_type), faction(to_type)) {
+            (Some(from), Some(to)) => self.factions.stance(from, to),
+            _ => Stance::Neutral,
+        }
+    }
+
+    /// Whether hunters of one type go after another: never their allies, always hostile
+    /// factions, and otherwise only what they eat
+    pub fn will_attack(&self, attacker_type: &PawnType, target_type: &PawnType) -> bool {
+        match self.stance_between(attacker_type, target_type) {
+            Stance::Allied => false,
+            Stance::Hostile => true,
+            Stance::Neutral => self.can_eat_by_tags(attacker_type, target_type),
+        }
+    }
+
     pub fn can_eat_by_tags(&self, predator_type: &PawnType, prey_type: &PawnType) -> bool {
         let predator_def = match self.get_pawn_definition(predator_type) {
             Some(def) => def,
@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::pawn::{Pawn, CurrentBehavior, Health};
+use crate::systems::pawn_config::{PawnConfig, PerceptionConfig, VisionConfig, BehaviourConfig, BehaviourType};
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::ambush::Hidden;
+use crate::systems::panic::Panicked;
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// How often pawns look around for predators unless settings.yaml says otherwise
+pub const PERCEPTION_TICK_SECS: f32 = 0.25;
+/// A spotted predator has to get this many tiles beyond the detection radius before its prey feels safe
+pub const SAFE_MARGIN_TILES: f32 = 2.0;
+
+/// Pawn has spotted a predator and hasn't got clear of it yet
+#[derive(Component, Debug, Clone)]
+pub struct Threatened {
+    pub predator: Entity,
+}
+
+/// What a pawn can see: everything within range, inside its field of view and not behind
+/// sight-blocking ground. The pawn faces the way it last moved.
+#[derive(Component, Debug, Clone)]
+pub struct Vision {
+    pub range: f32,       // Tiles, as far as the weather lets it see
+    pub base_range: f32,  // Tiles in clear weather
+    pub fov: Option<f32>, // Degrees, all round when None
+    pub facing: Vec2,
+    pub last_position: Option<Vec2>,
+}
+
+impl Vision {
+    pub fn new(config: &VisionConfig) -> Self {
+        Self {
+            range: config.range as f32,
+            base_range: config.range as f32,
+            fov: config.fov,
+            facing: Vec2::X,
+            last_position: None,
+        }
+    }
+
+    pub fn can_see(&self, from: Vec2, to: Vec2, terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> bool {
+        let offset = to - from;
+        if offset.length() > self.range * terrain_map.tile_size {
+            return false;
+        }
+        if let Some(fov) = self.fov {
+            // Something right on top of the pawn is always in view
+            if offset != Vec2::ZERO && self.facing.angle_to(offset).abs().to_degrees() > fov / 2.0 {
+                return false;
+            }
+        }
+        terrain_map.has_line_of_sight((from.x, from.y), (to.x, to.y), ground_configs)
+    }
+}
+
+/// Whether a pawn with this perception notices something at `to` from `from`
+pub fn can_perceive(
+    perception: &PerceptionConfig,
+    from: Vec2,
+    to: Vec2,
+    range: f32,
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+) -> bool {
+    from.distance(to) <= range
+        && (!perception.line_of_sight || terrain_map.has_line_of_sight((from.x, from.y), (to.x, to.y), ground_configs))
+}
+
+/// Pawns with a `perception` entry watch for predators that can eat them. A spotted predator
+/// stays a threat until it's dead or SAFE_MARGIN_TILES beyond the detection radius. State-driven
+/// pawns with a hunted behaviour switch to it while threatened and back to idle once safe, unless
+/// they pick their state by utility score.
+pub fn threat_perception_system(
+    time: Res<Time>,
+    mut ticker: Local<AiTicker>,
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut commands: Commands,
+    mut prey_query: Query<(Entity, &Transform, &Pawn, &mut CurrentBehavior, Option<&Threatened>, Has<ActiveBehaviour>, Has<Panicked>)>,
+    predator_query: Query<(Entity, &Transform, &Pawn, &Health, Option<&Hidden>)>,
+    spatial_grid: Res<SpatialGrid>,
+) {
+    ticker.advance(time.delta_secs());
+
+    for (entity, transform, pawn, mut current_behavior, threatened, has_tree, panicked) in prey_query.iter_mut() {
+        if !ticker.is_due(entity, config.ai_ticks.perception) {
+            continue;
+        }
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let perception = match &definition.perception {
+            Some(perception) => perception,
+            None => continue,
+        };
+        // Behaviour trees and utility scoring react to the threat themselves
+        let only_flag = has_tree || definition.utility.is_some();
+        let position = transform.translation.truncate();
+        let detection_range = perception.detection_radius as f32 * config.tile_size;
+        let safe_range = detection_range + SAFE_MARGIN_TILES * config.tile_size;
+
+        // Keep an eye on the predator already spotted, otherwise look for the nearest new one
+        let still_threatening = threatened
+            .and_then(|threatened| predator_query.get(threatened.predator).ok())
+            .filter(|(_, predator_transform, _, health, _)| {
+                health.current > 0.0 && position.distance(predator_transform.translation.truncate()) <= safe_range
+            })
+            .map(|(predator, _, _, _, _)| predator);
+        let threat = still_threatening.or_else(|| {
+            spatial_grid
+                .within(position, detection_range)
+                .filter_map(|(predator, _)| predator_query.get(predator).ok())
+                .filter(|(predator, predator_transform, predator_pawn, health, hidden)| {
+                    // Predators lying in ambush have to be that much closer to be noticed
+                    let range = hidden.map_or(detection_range, |hidden| detection_range * (1.0 - hidden.stealth));
+                    *predator != entity
+                        && health.current > 0.0
+                        && pawn_config.will_attack(&predator_pawn.pawn_type, &pawn.pawn_type)
+                        && can_perceive(perception, position, predator_transform.translation.truncate(), range, &terrain_map, &ground_configs)
+                })
+                .min_by(|a, b| {
+                    let distance_a = position.distance(a.1.translation.truncate());
+                    let distance_b = position.distance(b.1.translation.truncate());
+                    distance_a.total_cmp(&distance_b)
+                })
+                .map(|(predator, _, _, _, _)| predator)
+        });
+
+        let has_hunted_behaviour = pawn_config
+            .get_behaviour_config(&pawn.pawn_type, "hunted")
+            .is_some_and(|behaviour| !matches!(behaviour, BehaviourConfig::Simple(BehaviourType::Null)));
+
+        match threat {
+            Some(predator) => {
+                if threatened.is_none_or(|threatened| threatened.predator != predator) {
+                    commands.entity(entity).insert(Threatened { predator });
+                }
+                if !only_flag && has_hunted_behaviour && current_behavior.state != "hunted" && current_behavior.state != "controlled" {
+                    println!("{} spotted a predator, switching to hunted behavior", pawn.pawn_type);
+                    current_behavior.state = "hunted".to_string();
+                }
+            }
+            None => {
+                if threatened.is_some() {
+                    commands.entity(entity).remove::<Threatened>();
+                }
+                // Panicked pawns keep running until the panic wears off
+                if !only_flag && !panicked && current_behavior.state == "hunted" {
+                    println!("{} is safe, switching back to idle behavior", pawn.pawn_type);
+                    current_behavior.state = "idle".to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Turn pawns with vision to face the way they're moving
+pub fn update_vision_facing(mut vision_query: Query<(&Transform, &mut Vision)>) {
+    for (transform, mut vision) in vision_query.iter_mut() {
+        let position = transform.translation.truncate();
+        if let Some(last_position) = vision.last_position {
+            if let Some(direction) = (position - last_position).try_normalize() {
+                vision.facing = direction;
+            }
+        }
+        vision.last_position = Some(position);
+    }
+}
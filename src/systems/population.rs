@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use std::collections::HashMap;
+use crate::systems::den::{Den, HomeDen};
+use crate::systems::pawn::{Pawn, spawn_pawn, TilesetManager};
+use crate::systems::pawn_config::{PawnConfig, PawnType};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::sim_rng::SimRng;
+
+/// Species being topped up, with when each one's next pawn arrives. A species is added once it
+/// drops below its population `min` and dropped again once it's back up to `max`.
+#[derive(Resource, Debug, Default)]
+pub struct PopulationManager {
+    pub arrivals: HashMap<PawnType, f32>,
+}
+
+/// Pawn types due a new arrival at `now`, in name order. The first one arrives an interval after
+/// a species drops below its min, so a pack killed off together isn't replaced the same instant.
+pub fn due_arrivals(manager: &mut PopulationManager, counts: &HashMap<PawnType, u32>, pawn_config: &PawnConfig, now: f32) -> Vec<PawnType> {
+    let mut due = Vec::new();
+    for pawn_type in pawn_config.get_pawn_types() {
+        let population = match pawn_config.get_pawn_definition(&pawn_type).and_then(|def| def.population.as_ref()) {
+            Some(population) => population,
+            None => continue,
+        };
+        let count = counts.get(&pawn_type).copied().unwrap_or(0);
+        match manager.arrivals.get(&pawn_type).copied() {
+            Some(_) if count >= population.target() => {
+                manager.arrivals.remove(&pawn_type);
+            }
+            Some(next) if now >= next => {
+                manager.arrivals.insert(pawn_type.clone(), now + population.interval);
+                due.push(pawn_type);
+            }
+            Some(_) => {}
+            None if count < population.min => {
+                manager.arrivals.insert(pawn_type, now + population.interval);
+            }
+            None => {}
+        }
+    }
+    due
+}
+
+/// A random tile on the edge of the map a pawn can stand on, as if it had wandered in from
+/// beyond it. None when the whole edge is impassable.
+pub fn pick_edge_tile(terrain_map: &TerrainMap, ground_configs: &GroundConfigs, rng: &mut impl Rng) -> Option<(i32, i32)> {
+    let width = terrain_map.width as i32;
+    let height = terrain_map.height as i32;
+    let mut edge: Vec<(i32, i32)> = Vec::new();
+    for x in 0..width {
+        for y in 0..height {
+            let on_edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if on_edge && terrain_map.is_tile_passable(x, y, ground_configs) {
+                edge.push((x, y));
+            }
+        }
+    }
+    edge.choose(rng).copied()
+}
+
+/// Bring in a new pawn of each species that has dropped below its population min, one every
+/// `interval` until it's back up to its max. Species with dens get them at a den, the rest at the
+/// edge of the map.
+pub fn population_manager_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    pawn_config: Res<PawnConfig>,
+    mut manager: ResMut<PopulationManager>,
+    mut tileset_manager: ResMut<TilesetManager>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    pawn_query: Query<&Pawn>,
+    den_query: Query<(Entity, &Den, &Transform)>,
+    mut rng: ResMut<SimRng>,
+) {
+    let mut counts: HashMap<PawnType, u32> = HashMap::new();
+    for pawn in pawn_query.iter() {
+        *counts.entry(pawn.pawn_type.clone()).or_insert(0) += 1;
+    }
+
+    let rng = &mut *rng;
+    for pawn_type in due_arrivals(&mut manager, &counts, &pawn_config, time.elapsed_secs()) {
+        let dens: Vec<(Entity, Vec3)> = den_query
+            .iter()
+            .filter(|(_, den, _)| den.species == pawn_type)
+            .map(|(entity, _, transform)| (entity, transform.translation))
+            .collect();
+        let home = dens.choose(rng).copied();
+        let position = match home {
+            Some((_, position)) => (position.x, position.y),
+            None => {
+                let can_swim = pawn_config.get_pawn_definition(&pawn_type).is_some_and(|def| def.can_swim);
+                match pick_edge_tile(&terrain_map, &ground_configs.for_movement(can_swim), rng) {
+                    Some((tile_x, tile_y)) => terrain_map.tile_to_world_coords(tile_x, tile_y),
+                    None => continue,
+                }
+            }
+        };
+
+        let pawn = Pawn::new(pawn_type.clone());
+        let entity = spawn_pawn(&mut commands, &asset_server, &terrain_map, &ground_configs, &pawn_config, &mut tileset_manager, &mut texture_atlas_layouts, pawn, Some(position));
+        if let Some((den, _)) = home {
+            commands.entity(entity).insert(HomeDen { den });
+        }
+        println!("A {} arrived to keep its numbers up ({} left)", pawn_type, counts.get(&pawn_type).copied().unwrap_or(0));
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+use crate::systems::pathfinding_stats::StatsRefreshTimer;
+
+/// Sprites drawn and the draw calls they take, shown alongside the pawn stats overlay
+#[derive(Component)]
+pub struct RenderStatsPanel;
+
+/// Draw calls sprites at these depths with these images take. Sprites are drawn back to front
+/// and a run of them sharing an image goes in one call, so this is a close estimate of what the
+/// renderer does rather than a count from it.
+pub fn sprite_batches(mut sprites: Vec<(f32, AssetId<Image>)>) -> usize {
+    sprites.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    sprites.iter()
+        .enumerate()
+        .filter(|(index, (_, image))| *index == 0 || sprites[index - 1].1 != *image)
+        .count()
+}
+
+/// The panel's text: sprites on screen, the images they're drawn from and their draw calls
+pub fn describe_render_stats(sprites: &[(f32, AssetId<Image>)]) -> String {
+    let images: HashSet<AssetId<Image>> = sprites.iter().map(|(_, image)| *image).collect();
+    [
+        "Rendering".to_string(),
+        format!("Sprites drawn: {}", sprites.len()),
+        format!("Sprite images: {}", images.len()),
+        format!("Sprite draw calls: {}", sprite_batches(sprites.to_vec())),
+    ]
+    .join("\n")
+}
+
+/// Show sprite counts and draw calls in the corner while the pawn stats overlay is on, refreshed
+/// once a second
+pub fn update_render_stats_panel(
+    time: Res<Time<Real>>, // Keeps refreshing while the game is paused
+    mut timer: Local<StatsRefreshTimer>,
+    debug_state: Res<DebugDisplayState>,
+    sprite_query: Query<(&GlobalTransform, &Sprite, &ViewVisibility)>,
+    mut commands: Commands,
+    mut panel_query: Query<(Entity, &mut Text), With<RenderStatsPanel>>,
+) {
+    if debug_state.mode != DebugMode::Pawns {
+        for (panel, _) in panel_query.iter() {
+            commands.entity(panel).despawn();
+        }
+        return;
+    }
+
+    let refresh = timer.0.tick(time.delta()).just_finished();
+    let drawn = || -> Vec<(f32, AssetId<Image>)> {
+        sprite_query.iter()
+            .filter(|(_, _, visibility)| visibility.get())
+            .map(|(transform, sprite, _)| (transform.translation().z, sprite.image.id()))
+            .collect()
+    };
+    match panel_query.get_single_mut() {
+        Ok((_, mut text)) => {
+            if refresh {
+                text.0 = describe_render_stats(&drawn());
+            }
+        }
+        Err(_) => {
+            timer.0.reset();
+            commands.spawn((
+                Text::new(describe_render_stats(&drawn())),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+                RenderStatsPanel,
+            ));
+        }
+    }
+}
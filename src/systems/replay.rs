@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::systems::game_state::GameState;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::save::WorldMetadata;
+use crate::systems::sim_tick::SimTick;
+
+/// Command line option naming a replay to play back instead of starting at the main menu
+pub const REPLAY_ARG: &str = "--replay";
+pub const REPLAY_DIR: &str = "replays";
+
+/// Something the player told the world to do. Input systems give these instead of acting on the
+/// world themselves, and they're carried out on the next simulation tick, so a session can be
+/// recorded and played back tick for tick.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerCommand {
+    /// A click (from and to the same place) or a box dragged between two corners
+    Select { from: (f32, f32), to: (f32, f32) },
+    SelectAll,
+    ClearSelection,
+    /// Send the controlled pawns to a tile, or after the pawn on it
+    Order { position: (f32, f32) },
+    Hold,
+    /// Walk the controlled pawns this way until the next steer, stopping them if it's zero
+    Steer { direction: (f32, f32) },
+    Charge { towards: (f32, f32) },
+    Feed,
+    EditTerrain { position: (f32, f32) },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TimedCommand {
+    pub tick: u64,
+    pub command: PlayerCommand,
+}
+
+/// A session played from a new world: what the world was made from, and every command given
+/// up to `end_tick`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Replay {
+    pub metadata: WorldMetadata,
+    pub end_tick: u64,
+    pub commands: Vec<TimedCommand>,
+}
+
+impl Replay {
+    pub fn new(metadata: &WorldMetadata) -> Self {
+        Self {
+            metadata: metadata.clone(),
+            end_tick: 0,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let replay: Replay = serde_yaml::from_str(&content)?;
+        Ok(replay)
+    }
+
+    /// Commands due on `tick`, starting the search at `next`, and where to start it next time
+    pub fn commands_due(&self, next: usize, tick: u64) -> (Vec<PlayerCommand>, usize) {
+        let due: Vec<PlayerCommand> = self.commands[next.min(self.commands.len())..]
+            .iter()
+            .take_while(|timed| timed.tick <= tick)
+            .map(|timed| timed.command.clone())
+            .collect();
+        let next = next + due.len();
+        (due, next)
+    }
+}
+
+pub fn replay_path(dir: &Path, created_at: u64) -> PathBuf {
+    dir.join(format!("replay_{}.yaml", created_at))
+}
+
+/// Commands given since the last simulation tick, and those being carried out on this one
+#[derive(Resource, Debug, Default)]
+pub struct PlayerCommands {
+    pub queued: Vec<PlayerCommand>,
+    pub this_tick: Vec<PlayerCommand>,
+}
+
+impl PlayerCommands {
+    pub fn give(&mut self, command: PlayerCommand) {
+        self.queued.push(command);
+    }
+}
+
+/// Whether the session is being recorded, played back from a replay, or neither
+#[derive(Resource, Debug, Default)]
+pub enum ReplayMode {
+    #[default]
+    Off,
+    Recording(Replay),
+    Playing { replay: Replay, next: usize },
+}
+
+/// Runs on FixedUpdate after the tick advances - hands the tick the commands given since the
+/// last one, stamping them into the recording, or the replay's for it while one is playing
+pub fn take_player_commands(
+    sim_tick: Res<SimTick>,
+    mut player_commands: ResMut<PlayerCommands>,
+    mut mode: ResMut<ReplayMode>,
+) {
+    let queued = std::mem::take(&mut player_commands.queued);
+    let mut finished = false;
+    player_commands.this_tick = match &mut *mode {
+        ReplayMode::Off => queued,
+        ReplayMode::Recording(replay) => {
+            replay.commands.extend(queued.iter().map(|command| TimedCommand { tick: sim_tick.tick, command: command.clone() }));
+            queued
+        }
+        // The replay has the controls until it runs out
+        ReplayMode::Playing { replay, next } => {
+            let (due, after) = replay.commands_due(*next, sim_tick.tick);
+            *next = after;
+            finished = sim_tick.tick >= replay.end_tick;
+            due
+        }
+    };
+    if finished {
+        println!("Replay finished at tick {}, the controls are yours", sim_tick.tick);
+        *mode = ReplayMode::Off;
+    }
+}
+
+/// Every new world is recorded from the start, unless it's a replay being played
+pub fn start_recording(metadata: Res<WorldMetadata>, mut mode: ResMut<ReplayMode>) {
+    if matches!(*mode, ReplayMode::Off) {
+        *mode = ReplayMode::Recording(Replay::new(&metadata));
+    }
+}
+
+/// A replay given on the command line skips the main menu and starts straight away
+pub fn start_replay(mode: Res<ReplayMode>, mut next_state: ResMut<NextState<GameState>>) {
+    if let ReplayMode::Playing { replay, .. } = &*mode {
+        println!("Playing back a replay of seed {} to tick {}", replay.metadata.seed, replay.end_tick);
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// Write out the session so far, to attach to a bug report or play back with --replay
+pub fn save_replay(
+    actions: ActionInput,
+    sim_tick: Res<SimTick>,
+    mode: Res<ReplayMode>,
+) {
+    if !actions.just_pressed(Action::SaveReplay) {
+        return;
+    }
+    let mut replay = match &*mode {
+        ReplayMode::Recording(replay) => replay.clone(),
+        ReplayMode::Playing { .. } => {
+            println!("A replay is playing, there's nothing new to save");
+            return;
+        }
+        ReplayMode::Off => {
+            println!("Nothing has been recorded since the world was loaded");
+            return;
+        }
+    };
+    replay.end_tick = sim_tick.tick;
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = replay_path(Path::new(REPLAY_DIR), created_at);
+    match replay.save_to_file(&path) {
+        Ok(()) => println!("Saved a replay of {} commands over {} ticks to {:?}", replay.commands.len(), replay.end_tick, path),
+        Err(e) => eprintln!("Failed to save replay to {:?}: {}", path, e),
+    }
+}
@@ -0,0 +1,415 @@
+use bevy::prelude::*;
+use bevy::image::ImageSampler;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::resources::GameConfig;
+use crate::systems::config_files::ConfigPaths;
+use crate::systems::bestiary::BestiaryProgress;
+use crate::systems::camera::CameraFollow;
+use crate::systems::camera_bookmarks::{CameraBookmark, CameraBookmarks};
+use crate::systems::den::Den;
+use crate::systems::fog_of_war::{Exploration, TileBits};
+use crate::systems::infusion::TerrainInfluence;
+use crate::systems::inventory::Item;
+use crate::systems::migration::RegionFood;
+use crate::systems::minimap::minimap_pixels;
+use crate::systems::pawn::Pawn;
+use crate::systems::population::PopulationManager;
+use crate::systems::scent::ScentMap;
+use crate::systems::seasons::Seasons;
+use crate::systems::statistics::Statistics;
+use crate::systems::vegetation::VegetationGrowth;
+use crate::systems::weather::Weather;
+use crate::systems::world_clock::WorldClock;
+use crate::systems::sim_tick::SimTick;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, TerrainType, GroundConfigs};
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::sim_rng::SimRng;
+use crate::systems::replay::ReplayMode;
+
+pub const SAVE_DIR: &str = "saves";
+pub const QUICKSAVE_NAME: &str = "quicksave";
+/// Length in pixels of the longer side of a save's thumbnail
+pub const THUMBNAIL_SIZE: u32 = 64;
+
+/// Config files whose contents are fingerprinted into every save
+pub const CONFIG_FILES: [&str; 8] = ["settings.yaml", "grounds.yaml", "pawns.yaml", "factions.yaml", "props.yaml", "status_effects.yaml", "items.yaml", "spawns.yaml"];
+
+/// Per-world options fixed at generation time
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WorldOptions {
+    pub map_width: u32,
+    pub map_height: u32,
+    pub tile_size: f32,
+}
+
+/// Metadata describing a generated world, stored at the top of every save file
+#[derive(Debug, Clone, Resource, Deserialize, Serialize)]
+pub struct WorldMetadata {
+    pub name: String,
+    pub seed: u32,
+    pub created_at: u64, // Unix timestamp in seconds
+    pub playtime_secs: f64,
+    #[serde(default)]
+    pub sim_tick: u64,
+    pub options: WorldOptions,
+    #[serde(default)]
+    pub mods: Vec<String>,
+    #[serde(default)]
+    pub config_hashes: BTreeMap<String, u64>,
+}
+
+impl WorldMetadata {
+    pub fn new(name: &str, seed: u32, config: &GameConfig, config_paths: &ConfigPaths) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            name: name.to_string(),
+            seed,
+            created_at,
+            playtime_secs: 0.0,
+            sim_tick: 0,
+            options: WorldOptions {
+                map_width: config.map_width,
+                map_height: config.map_height,
+                tile_size: config.tile_size,
+            },
+            mods: config_paths.mods.clone(),
+            config_hashes: hash_config_files(&CONFIG_FILES, config_paths),
+        }
+    }
+
+    /// Why this save can't be loaded over a world of the given size, if it can't
+    pub fn map_size_mismatch(&self, width: u32, height: u32) -> Option<String> {
+        if self.options.map_width == width && self.options.map_height == height {
+            return None;
+        }
+        Some(format!("Saved map is {}x{} but the current map is {}x{}",
+                     self.options.map_width, self.options.map_height, width, height))
+    }
+
+    /// Describe every way the running game's mods/configs differ from this save
+    pub fn compatibility_warnings(&self, current_mods: &[String], current_hashes: &BTreeMap<String, u64>) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (file, saved_hash) in &self.config_hashes {
+            match current_hashes.get(file) {
+                Some(hash) if hash == saved_hash => {}
+                Some(_) => warnings.push(format!("{} has changed since this world was saved", file)),
+                None => warnings.push(format!("{} is missing but was present when this world was saved", file)),
+            }
+        }
+        for file in current_hashes.keys() {
+            if !self.config_hashes.contains_key(file) {
+                warnings.push(format!("{} was not present when this world was saved", file));
+            }
+        }
+
+        for saved_mod in &self.mods {
+            if !current_mods.contains(saved_mod) {
+                warnings.push(format!("Mod '{}' used by this save is not loaded", saved_mod));
+            }
+        }
+        for current_mod in current_mods {
+            if !self.mods.contains(current_mod) {
+                warnings.push(format!("Mod '{}' is loaded but was not used by this save", current_mod));
+            }
+        }
+
+        warnings
+    }
+}
+
+/// A small copy of the world's minimap, shown next to the save on the load screen
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>, // RGB, rows top to bottom like the minimap's
+}
+
+impl Thumbnail {
+    /// Shrink the minimap's RGBA pixels until the longer side fits in THUMBNAIL_SIZE, keeping its
+    /// proportions. Small maps keep one pixel per tile.
+    pub fn from_minimap(rgba: &[u8], width: u32, height: u32) -> Self {
+        let longest = width.max(height);
+        if longest == 0 || rgba.len() != (width * height * 4) as usize {
+            return Self::default();
+        }
+        let scale = |length: u32| (length * THUMBNAIL_SIZE / longest).clamp(1, length);
+        let (thumb_width, thumb_height) = if longest > THUMBNAIL_SIZE { (scale(width), scale(height)) } else { (width, height) };
+
+        let mut pixels = Vec::with_capacity((thumb_width * thumb_height * 3) as usize);
+        for row in 0..thumb_height {
+            for column in 0..thumb_width {
+                let x = column * width / thumb_width;
+                let y = row * height / thumb_height;
+                let offset = ((y * width + x) * 4) as usize;
+                pixels.extend_from_slice(&rgba[offset..offset + 3]);
+            }
+        }
+        Self { width: thumb_width, height: thumb_height, pixels }
+    }
+
+    /// The thumbnail as an image for the UI, unless the save was made before thumbnails
+    pub fn to_image(&self) -> Option<Image> {
+        if self.pixels.is_empty() || self.pixels.len() != (self.width * self.height * 3) as usize {
+            return None;
+        }
+        let data = self.pixels.chunks(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+        let mut image = Image::new(
+            Extent3d { width: self.width, height: self.height, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::nearest();
+        Some(image)
+    }
+}
+
+/// Complete on-disk representation of a saved world
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaveFile {
+    pub metadata: WorldMetadata,
+    pub tiles: Vec<Vec<TerrainType>>,
+    #[serde(default)]
+    pub bestiary: BestiaryProgress,
+    #[serde(default)]
+    pub camera_bookmarks: [Option<CameraBookmark>; 9],
+    #[serde(default)]
+    pub explored: TileBits,
+    #[serde(default)]
+    pub thumbnail: Thumbnail,
+}
+
+/// Only the header of a save file - lets save listings skip parsing the tile data
+#[derive(Deserialize)]
+struct SaveHeader {
+    metadata: WorldMetadata,
+    #[serde(default)]
+    thumbnail: Thumbnail,
+}
+
+impl SaveFile {
+    pub fn new(metadata: &WorldMetadata, terrain_map: &TerrainMap, bestiary: &BestiaryProgress) -> Self {
+        Self {
+            metadata: metadata.clone(),
+            tiles: terrain_map.tiles.clone(),
+            bestiary: bestiary.clone(),
+            camera_bookmarks: Default::default(),
+            explored: TileBits::default(),
+            thumbnail: Thumbnail::default(),
+        }
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let save: SaveFile = serde_yaml::from_str(&content)?;
+        Ok(save)
+    }
+}
+
+pub fn save_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.yaml", name))
+}
+
+/// Read the metadata and thumbnail of every save in a directory, newest first, for a load-game listing
+pub fn list_saves(dir: &Path) -> Vec<(PathBuf, WorldMetadata, Thumbnail)> {
+    let mut saves = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("yaml") {
+                continue;
+            }
+            let header = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_yaml::from_str::<SaveHeader>(&content).ok());
+            if let Some(header) = header {
+                saves.push((path, header.metadata, header.thumbnail));
+            }
+        }
+    }
+
+    saves.sort_by_key(|(_, metadata, _)| std::cmp::Reverse(metadata.created_at));
+    saves
+}
+
+/// Stable FNV-1a hash so fingerprints survive compiler/toolchain upgrades
+pub fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Fingerprint each config file the game is running with, wherever it was found
+pub fn hash_config_files(names: &[&str], config_paths: &ConfigPaths) -> BTreeMap<String, u64> {
+    names
+        .iter()
+        .filter_map(|name| {
+            config_paths.read(name)
+                .ok()
+                .map(|content| (name.to_string(), fnv1a_hash(content.as_bytes())))
+        })
+        .collect()
+}
+
+/// Save the world to the quicksave slot, as the quick save key does
+#[derive(Event)]
+pub struct SaveRequest;
+
+/// Load a save file over the current world, as the quick load key does with the quicksave
+#[derive(Event)]
+pub struct LoadRequest(pub PathBuf);
+
+/// Sent once a save's ground has been laid over the world, for its pawns, dens and items to be
+/// placed afresh as they are in a new world
+#[derive(Event)]
+pub struct WorldLoaded;
+
+pub fn track_playtime(
+    time: Res<Time>,
+    mut metadata: ResMut<WorldMetadata>,
+) {
+    metadata.playtime_secs += time.delta_secs_f64();
+}
+
+pub fn quick_save(
+    actions: ActionInput,
+    metadata: Res<WorldMetadata>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    bestiary: Res<BestiaryProgress>,
+    bookmarks: Res<CameraBookmarks>,
+    exploration: Res<Exploration>,
+    sim_tick: Res<SimTick>,
+    mut save_requests: EventReader<SaveRequest>,
+) {
+    let requested = save_requests.read().count() > 0;
+    if !actions.just_pressed(Action::QuickSave) && !requested {
+        return;
+    }
+
+    let path = save_path(Path::new(SAVE_DIR), QUICKSAVE_NAME);
+    let mut save = SaveFile::new(&metadata, &terrain_map, &bestiary);
+    save.metadata.sim_tick = sim_tick.tick;
+    save.camera_bookmarks = bookmarks.slots;
+    save.explored = exploration.explored.clone();
+    save.thumbnail = Thumbnail::from_minimap(&minimap_pixels(&terrain_map, &ground_configs), terrain_map.width, terrain_map.height);
+    match save.save_to_file(&path) {
+        Ok(()) => println!("Saved world '{}' (day {}) to {:?}", metadata.name, sim_tick.day(), path),
+        Err(e) => eprintln!("Failed to save world to {:?}: {}", path, e),
+    }
+}
+
+pub fn quick_load(
+    actions: ActionInput,
+    mut metadata: ResMut<WorldMetadata>,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut terrain_changes: ResMut<TerrainChanges>,
+    mut bestiary: ResMut<BestiaryProgress>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut exploration: ResMut<Exploration>,
+    mut sim_tick: ResMut<SimTick>,
+    mut load_requests: EventReader<LoadRequest>,
+    config_paths: Res<ConfigPaths>,
+    mut rng: ResMut<SimRng>,
+    mut replay_mode: ResMut<ReplayMode>,
+    mut loaded_events: EventWriter<WorldLoaded>,
+) {
+    let requested = load_requests.read().last().map(|request| request.0.clone());
+    let path = match requested {
+        Some(path) => path,
+        None if actions.just_pressed(Action::QuickLoad) => save_path(Path::new(SAVE_DIR), QUICKSAVE_NAME),
+        None => return,
+    };
+    let save = match SaveFile::load_from_file(&path) {
+        Ok(save) => save,
+        Err(e) => {
+            eprintln!("Failed to load world from {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    // The load screen doesn't offer these, but the quick load key can still find one
+    if let Some(mismatch) = save.metadata.map_size_mismatch(terrain_map.width, terrain_map.height) {
+        eprintln!("Cannot load {:?}: {}", path, mismatch);
+        return;
+    }
+
+    for warning in save.metadata.compatibility_warnings(&config_paths.mods, &hash_config_files(&CONFIG_FILES, &config_paths)) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    for (x, column) in save.tiles.iter().enumerate() {
+        for (y, &terrain_type) in column.iter().enumerate() {
+            if terrain_map.tiles[x][y] != terrain_type {
+                terrain_map.set_tile(x as u32, y as u32, terrain_type);
+                terrain_changes.add_change(x as u32, y as u32, terrain_type);
+            }
+        }
+    }
+
+    sim_tick.tick = save.metadata.sim_tick;
+    *rng = SimRng::at_tick(save.metadata.seed, save.metadata.sim_tick);
+    *metadata = save.metadata;
+    *bestiary = save.bestiary;
+    bookmarks.slots = save.camera_bookmarks;
+    // Saves from before fog of war start out unexplored
+    *exploration = Exploration::new(terrain_map.width, terrain_map.height);
+    if save.explored.width == terrain_map.width && save.explored.height == terrain_map.height {
+        exploration.explored = save.explored;
+    }
+    // Replays only play out from when a world was made
+    if !matches!(*replay_mode, ReplayMode::Off) {
+        println!("Stopped the replay, it can't carry on from a loaded save");
+        *replay_mode = ReplayMode::Off;
+    }
+    println!("Loaded world '{}' (seed {}, {:.0}s played)", metadata.name, metadata.seed, metadata.playtime_secs);
+    loaded_events.send(WorldLoaded);
+}
+
+/// Clear away the pawns, dens and items of the world a save was loaded over, along with
+/// everything the simulation was keeping track of for them, ready to restock from the loaded
+/// world's seed
+pub fn clear_world_for_load(
+    mut commands: Commands,
+    sim_tick: Res<SimTick>,
+    world_query: Query<Entity, Or<(With<Pawn>, With<Den>, With<Item>)>>,
+) {
+    for entity in world_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.insert_resource(WorldClock::from_tick(&sim_tick));
+    commands.insert_resource(VegetationGrowth::default());
+    commands.insert_resource(ScentMap::default());
+    commands.insert_resource(TerrainInfluence::default());
+    commands.insert_resource(RegionFood::default());
+    commands.insert_resource(PopulationManager::default());
+    commands.insert_resource(Weather::default());
+    commands.insert_resource(Seasons::default());
+    commands.insert_resource(Statistics::default());
+    commands.insert_resource(CameraFollow::default());
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::systems::pawn::{Pawn, Health};
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::world_gen::TerrainMap;
+
+/// Scent left on a tile by prey standing on it
+pub const SCENT_STRENGTH: f32 = 1.0;
+/// Scent lost per second - trails go cold after SCENT_STRENGTH / SCENT_DECAY_PER_SECOND seconds
+pub const SCENT_DECAY_PER_SECOND: f32 = 0.05;
+/// How far (in tiles) a tracking hunter sniffs around for a fresher bit of trail
+pub const SCENT_SNIFF_RADIUS_TILES: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scent {
+    pub pawn: Entity,
+    pub strength: f32,
+}
+
+/// Decaying scent trails, per tile and per pawn that left them
+#[derive(Resource, Debug, Default)]
+pub struct ScentMap {
+    pub tiles: HashMap<(i32, i32), Vec<Scent>>,
+}
+
+impl ScentMap {
+    /// Freshen a pawn's scent on a tile
+    pub fn deposit(&mut self, tile: (i32, i32), pawn: Entity) {
+        let scents = self.tiles.entry(tile).or_default();
+        match scents.iter_mut().find(|scent| scent.pawn == pawn) {
+            Some(scent) => scent.strength = SCENT_STRENGTH,
+            None => scents.push(Scent { pawn, strength: SCENT_STRENGTH }),
+        }
+    }
+
+    pub fn strength_at(&self, tile: (i32, i32), pawn: Entity) -> f32 {
+        self.tiles
+            .get(&tile)
+            .and_then(|scents| scents.iter().find(|scent| scent.pawn == pawn))
+            .map_or(0.0, |scent| scent.strength)
+    }
+
+    /// All the scent left on a tile - how much prey passes through it
+    pub fn traffic_at(&self, tile: (i32, i32)) -> f32 {
+        self.tiles.get(&tile).map_or(0.0, |scents| scents.iter().map(|scent| scent.strength).sum())
+    }
+
+    /// Fade every trail, forgetting scents that have gone cold
+    pub fn decay(&mut self, amount: f32) {
+        self.tiles.retain(|_, scents| {
+            scents.retain_mut(|scent| {
+                scent.strength -= amount;
+                scent.strength > 0.0
+            });
+            !scents.is_empty()
+        });
+    }
+
+    /// The tile near `from` where this pawn's trail is freshest, if it's fresher than at `from`.
+    /// Following it leads up the trail towards where the pawn is now.
+    pub fn follow_trail(&self, from: (i32, i32), pawn: Entity) -> Option<(i32, i32)> {
+        let mut best = (from, self.strength_at(from, pawn));
+        for dx in -SCENT_SNIFF_RADIUS_TILES..=SCENT_SNIFF_RADIUS_TILES {
+            for dy in -SCENT_SNIFF_RADIUS_TILES..=SCENT_SNIFF_RADIUS_TILES {
+                let tile = (from.0 + dx, from.1 + dy);
+                let strength = self.strength_at(tile, pawn);
+                if strength > best.1 {
+                    best = (tile, strength);
+                }
+            }
+        }
+        (best.0 != from).then_some(best.0)
+    }
+}
+
+/// Living prey leave their scent on the tile they're on
+pub fn scent_deposit_system(
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    mut scent_map: ResMut<ScentMap>,
+    pawn_query: Query<(Entity, &Transform, &Pawn, &Health)>,
+) {
+    for (entity, transform, pawn, health) in pawn_query.iter() {
+        if health.current <= 0.0 || !pawn_config.is_prey(&pawn.pawn_type) {
+            continue;
+        }
+        if let Some(tile) = terrain_map.world_to_tile_coords(transform.translation.x, transform.translation.y) {
+            scent_map.deposit(tile, entity);
+        }
+    }
+}
+
+/// Trails fade continuously, so older stretches are always fainter than newer ones
+pub fn scent_decay_system(time: Res<Time>, mut scent_map: ResMut<ScentMap>) {
+    scent_map.decay(SCENT_DECAY_PER_SECOND * time.delta_secs());
+}
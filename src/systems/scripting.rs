@@ -0,0 +1,355 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use crate::resources::GameConfig;
+use crate::systems::aging::Age;
+use crate::systems::growth::Grown;
+use crate::systems::combat::CombatEvent;
+use crate::systems::eating::start_eating;
+use crate::systems::ai::{ActiveBehaviour, resolve_behaviour, behaviour_tree_system, handle_pathfinding_failures};
+use crate::systems::ai_tick::AiTicker;
+use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingRequestCounter, request_pathfinding};
+use crate::systems::attack::{Attacking, blow_damage};
+use crate::systems::needs::Asleep;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Size, Swimmer};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig};
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// How often scripted pawns get a turn unless settings.yaml says otherwise
+pub const SCRIPT_TICK_SECS: f32 = 0.25;
+/// How far around itself a script is told about other pawns, in tiles
+pub const SCRIPT_SIGHT_TILES: f32 = 8.0;
+/// Most steps a script may take in one turn before it's stopped, so a runaway loop can't
+/// freeze the game
+#[cfg(feature = "scripting")]
+pub const SCRIPT_MAX_OPERATIONS: u64 = 50_000;
+
+/// What a script is told about a pawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptPawn {
+    pub id: u64, // Entity bits, to hand back to `attack`
+    pub pawn_type: String,
+    pub x: f32,
+    pub y: f32,
+    pub health: f32,
+    pub distance: f32, // Tiles from the scripted pawn
+    pub prey: bool,    // The scripted pawn would attack it
+    pub threat: bool,  // It would attack the scripted pawn
+}
+
+/// Everything a script gets to look at on its turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptTurn {
+    pub me: ScriptPawn,
+    pub state: String,
+    pub nearby: Vec<ScriptPawn>, // Living pawns within SCRIPT_SIGHT_TILES
+}
+
+/// What a script asked its pawn to do. Orders are checked before they're carried out -
+/// a script can't move a pawn anywhere it couldn't walk, or attack what's out of reach.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))] // Only scripts give orders
+pub enum ScriptOrder {
+    MoveTo(Vec2),     // Path to a world position
+    Attack(Entity),   // Attack a pawn in reach, at the pawn's usual attack speed
+    SetState(String), // Switch to another of the pawn's behaviour states
+}
+
+/// Runs the rhai scripts named by `script` behaviours. Scripts are compiled the first time
+/// they're used and see `me`, `state` and `nearby` (maps with the ScriptPawn fields, `type`
+/// for the pawn type), and can call `move_to(x, y)`, `attack(id)` and `set_state(name)`.
+/// Nothing else is exposed - no file, network or world access beyond that.
+#[cfg(feature = "scripting")]
+#[derive(Resource)]
+pub struct ScriptRunner {
+    engine: rhai::Engine,
+    scripts: std::collections::HashMap<String, rhai::AST>,
+    orders: std::sync::Arc<std::sync::Mutex<Vec<ScriptOrder>>>,
+}
+
+#[cfg(feature = "scripting")]
+impl Default for ScriptRunner {
+    fn default() -> Self {
+        let orders = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut engine = rhai::Engine::new();
+        engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+        engine.set_max_call_levels(32);
+        engine.set_max_string_size(4096);
+        engine.set_max_array_size(1024);
+        engine.set_max_map_size(256);
+        engine.disable_symbol("eval");
+
+        let sink = orders.clone();
+        engine.register_fn("move_to", move |x: f64, y: f64| {
+            sink.lock().unwrap().push(ScriptOrder::MoveTo(Vec2::new(x as f32, y as f32)));
+        });
+        let sink = orders.clone();
+        engine.register_fn("attack", move |id: i64| {
+            if let Ok(target) = Entity::try_from_bits(id as u64) {
+                sink.lock().unwrap().push(ScriptOrder::Attack(target));
+            }
+        });
+        let sink = orders.clone();
+        engine.register_fn("set_state", move |state: &str| {
+            sink.lock().unwrap().push(ScriptOrder::SetState(state.to_string()));
+        });
+
+        Self {
+            engine,
+            scripts: std::collections::HashMap::new(),
+            orders,
+        }
+    }
+}
+
+#[cfg(feature = "scripting")]
+impl ScriptRunner {
+    /// Run the script at `path` for one turn and collect the orders it gave
+    pub fn run(&mut self, path: &str, turn: &ScriptTurn) -> Result<Vec<ScriptOrder>, String> {
+        if !self.scripts.contains_key(path) {
+            let ast = self.engine.compile_file(path.into()).map_err(|error| error.to_string())?;
+            self.scripts.insert(path.to_string(), ast);
+        }
+
+        let mut scope = rhai::Scope::new();
+        scope.push("me", script_pawn_map(&turn.me));
+        scope.push("state", turn.state.clone());
+        scope.push("nearby", turn.nearby.iter().map(|pawn| rhai::Dynamic::from(script_pawn_map(pawn))).collect::<rhai::Array>());
+
+        self.orders.lock().unwrap().clear();
+        self.engine.run_ast_with_scope(&mut scope, &self.scripts[path]).map_err(|error| error.to_string())?;
+        Ok(std::mem::take(&mut *self.orders.lock().unwrap()))
+    }
+}
+
+#[cfg(feature = "scripting")]
+fn script_pawn_map(pawn: &ScriptPawn) -> rhai::Map {
+    let mut map = rhai::Map::new();
+    map.insert("id".into(), (pawn.id as i64).into());
+    map.insert("type".into(), pawn.pawn_type.clone().into());
+    map.insert("x".into(), (pawn.x as f64).into());
+    map.insert("y".into(), (pawn.y as f64).into());
+    map.insert("health".into(), (pawn.health as f64).into());
+    map.insert("distance".into(), (pawn.distance as f64).into());
+    map.insert("prey".into(), pawn.prey.into());
+    map.insert("threat".into(), pawn.threat.into());
+    map
+}
+
+/// Stands in for the script engine when the game is built without the `scripting` feature -
+/// every script fails, so scripted pawns just stand still
+#[cfg(not(feature = "scripting"))]
+#[derive(Resource, Default)]
+pub struct ScriptRunner;
+
+#[cfg(not(feature = "scripting"))]
+impl ScriptRunner {
+    pub fn run(&mut self, path: &str, _turn: &ScriptTurn) -> Result<Vec<ScriptOrder>, String> {
+        Err(format!("can't run {} - built without the `scripting` feature", path))
+    }
+}
+
+#[derive(Component)]
+pub struct ScriptedAI {
+    pub last_attack_time: f32,
+    pub failed: bool, // The script errored - it isn't run again, so the error is only reported once
+}
+
+impl ScriptedAI {
+    /// Ready to strike straight away, as a hunter is by the time it has run its prey down
+    pub fn new(attack_interval: f32) -> Self {
+        Self {
+            last_attack_time: attack_interval,
+            failed: false,
+        }
+    }
+}
+
+// System to add ScriptedAI component to pawns with a script behaviour
+pub fn setup_scripted_ai(
+    mut commands: Commands,
+    pawn_config: Res<PawnConfig>,
+    script_query: Query<(Entity, &Pawn, &CurrentBehavior, Option<&ActiveBehaviour>), (With<Pawn>, Without<ScriptedAI>)>,
+) {
+    for (entity, pawn, current_behavior, active_behaviour) in script_query.iter() {
+        if let Some(BehaviourConfig::Script { .. }) = resolve_behaviour(&pawn_config, pawn, current_behavior, active_behaviour) {
+            let attack_interval = pawn_config.get_pawn_definition(&pawn.pawn_type).map_or(0.0, |def| 1.0 / def.attack_speed);
+            commands.entity(entity).insert(ScriptedAI::new(attack_interval));
+        }
+    }
+}
+
+/// Give scripted pawns their turn and carry out the orders their scripts give. Attacks
+/// a script starts wind up and land every frame, like a hunter's.
+pub fn script_ai_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    spatial_grid: Res<SpatialGrid>,
+    mut runner: ResMut<ScriptRunner>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut ticker: Local<AiTicker>,
+    mut commands: Commands,
+    mut request_counter: ResMut<PathfindingRequestCounter>,
+    mut scripted_query: Query<(Entity, &Pawn, &Size, &mut CurrentBehavior, &mut ScriptedAI, Option<&ActiveBehaviour>, Option<&mut Attacking>, Option<&PawnTarget>, Has<Swimmer>, Has<PathfindingRequest>)>,
+    mut pawn_query: Query<(&Transform, &Pawn, &mut Health)>,
+    age_query: Query<&Age>,
+    grown_query: Query<&Grown>,
+    sleeper_query: Query<(), With<Asleep>>,
+) {
+    ticker.advance(time.delta_secs());
+
+    for (entity, pawn, size, mut current_behavior, mut scripted_ai, active_behaviour, mut attacking, pawn_target, is_swimmer, has_path_request) in scripted_query.iter_mut() {
+        let script = match resolve_behaviour(&pawn_config, pawn, &current_behavior, active_behaviour) {
+            Some(BehaviourConfig::Script { script }) => script.clone(),
+            _ => continue,
+        };
+        let def = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(def) => def,
+            None => continue,
+        };
+        let position = match pawn_query.get(entity) {
+            Ok((transform, _, _)) => transform.translation.truncate(),
+            Err(_) => continue,
+        };
+        let reach_distance = def.reach as f32 * config.tile_size;
+        scripted_ai.last_attack_time += time.delta_secs();
+
+        let mut new_attack = None;
+        if attacking.is_none() && !scripted_ai.failed && ticker.is_due(entity, config.ai_ticks.script) {
+            let nearby = spatial_grid
+                .within(position, SCRIPT_SIGHT_TILES * config.tile_size)
+                .filter(|(other, _)| *other != entity)
+                .filter_map(|(other, other_position)| {
+                    let (_, other_pawn, other_health) = pawn_query.get(other).ok()?;
+                    if other_health.current <= 0.0 {
+                        return None;
+                    }
+                    Some(ScriptPawn {
+                        id: other.to_bits(),
+                        pawn_type: other_pawn.pawn_type.clone(),
+                        x: other_position.x,
+                        y: other_position.y,
+                        health: other_health.current,
+                        distance: position.distance(other_position) / config.tile_size,
+                        prey: pawn_config.will_attack(&pawn.pawn_type, &other_pawn.pawn_type),
+                        threat: pawn_config.will_attack(&other_pawn.pawn_type, &pawn.pawn_type),
+                    })
+                })
+                .collect();
+            let turn = ScriptTurn {
+                me: ScriptPawn {
+                    id: entity.to_bits(),
+                    pawn_type: pawn.pawn_type.clone(),
+                    x: position.x,
+                    y: position.y,
+                    health: pawn_query.get(entity).map_or(0.0, |(_, _, health)| health.current),
+                    distance: 0.0,
+                    prey: false,
+                    threat: false,
+                },
+                state: current_behavior.state.clone(),
+                nearby,
+            };
+
+            let orders = match runner.run(&script, &turn) {
+                Ok(orders) => orders,
+                Err(error) => {
+                    println!("{}'s script {} failed: {}", pawn.pawn_type, script, error);
+                    scripted_ai.failed = true;
+                    continue;
+                }
+            };
+
+            for order in orders {
+                match order {
+                    ScriptOrder::MoveTo(goal) => {
+                        let already_heading_there = pawn_target.is_some_and(|target| target.target_position.truncate().distance(goal) <= 5.0 && !target.path.is_empty());
+                        if has_path_request || already_heading_there {
+                            continue;
+                        }
+                        let movement_configs = ground_configs.for_movement(is_swimmer);
+                        let current_pos = (position.x, position.y);
+                        let goal_pos = (goal.x, goal.y);
+                        if terrain_map.is_position_passable_for_size(goal.x, goal.y, size.value, &movement_configs)
+                            && terrain_map.are_connected(current_pos, goal_pos, size.value, &movement_configs)
+                        {
+                            request_pathfinding(&mut commands, &mut request_counter, entity, current_pos, goal_pos, size.value);
+                        }
+                    }
+                    ScriptOrder::Attack(target) => {
+                        let in_reach = pawn_query.get(target).is_ok_and(|(target_transform, _, target_health)| {
+                            target_health.current > 0.0 && position.distance(target_transform.translation.truncate()) <= reach_distance
+                        });
+                        if target != entity && in_reach && new_attack.is_none() && scripted_ai.last_attack_time >= 1.0 / def.attack_speed {
+                            new_attack = Some(Attacking::new(target));
+                            scripted_ai.last_attack_time = 0.0;
+                        }
+                    }
+                    ScriptOrder::SetState(state) => {
+                        if pawn_config.get_behaviour_config(&pawn.pawn_type, &state).is_some() {
+                            current_behavior.state = state;
+                        } else {
+                            println!("{}'s script {} asked for unknown state {}", pawn.pawn_type, script, state);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Wind up, land and recover from the script's attack
+        let delta = if new_attack.is_some() { 0.0 } else { time.delta_secs() };
+        if let Some(attack) = attacking.as_deref_mut().or(new_attack.as_mut()) {
+            let target_entity = attack.target;
+            let step = attack.advance(delta, &def.attack_config());
+            if step.struck {
+                if let Ok((target_transform, target_pawn, mut target_health)) = pawn_query.get_mut(target_entity) {
+                    if position.distance(target_transform.translation.truncate()) > reach_distance {
+                        println!("{} dodged {}'s attack", target_pawn.pawn_type, pawn.pawn_type);
+                    } else if let Some(target_def) = pawn_config.get_pawn_definition(&target_pawn.pawn_type) {
+                        let attacker_stage = age_query.get(entity).ok().map(|age| age.stage);
+                        let target_stage = age_query.get(target_entity).ok().map(|age| age.stage);
+                        let damage = blow_damage(def, attacker_stage, grown_query.get(entity).ok(), target_def, target_stage, sleeper_query.contains(target_entity));
+                        target_health.current = (target_health.current - damage).max(0.0);
+                        combat_events.send(CombatEvent { attacker: entity, victim: target_entity, damage });
+
+                        if target_health.current <= 0.0 {
+                            println!("{} killed {} and settles down to eat", pawn.pawn_type, target_pawn.pawn_type);
+                            start_eating(&mut commands, entity, &target_pawn.pawn_type, target_def.max_health as f32, &def.eats.meal_config());
+                        }
+                    }
+                }
+            }
+
+            if step.finished {
+                if new_attack.is_none() {
+                    commands.entity(entity).remove::<Attacking>();
+                }
+            } else if let Some(new_attack) = new_attack {
+                commands.entity(entity).insert(new_attack);
+            }
+        }
+    }
+}
+
+pub struct ScriptingPlugin {
+    pub schedule: InternedScheduleLabel, // Wherever the other AI systems run - FixedUpdate in the game
+}
+
+impl Default for ScriptingPlugin {
+    fn default() -> Self {
+        Self { schedule: Update.intern() }
+    }
+}
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScriptRunner>()
+            .add_systems(self.schedule, (
+                setup_scripted_ai.after(behaviour_tree_system),
+                script_ai_system.after(setup_scripted_ai).after(handle_pathfinding_failures),
+            ));
+    }
+}
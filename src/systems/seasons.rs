@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::resources::GameConfig;
+use crate::systems::world_clock::WorldClock;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, GroundConfigs};
+
+/// Tiles handed to the terrain layer to redraw each frame when the season turns, so a big map
+/// changes colour over a few frames rather than all in one
+const REPAINT_BATCH: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [Season::Spring, Season::Summer, Season::Autumn, Season::Winter];
+
+    /// The season on a day of the world - a new world starts on the first day of spring
+    pub fn for_day(day: u64, days_per_season: u64) -> Self {
+        Self::ALL[((day / days_per_season.max(1)) % 4) as usize]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+}
+
+/// How long seasons last and what they do, from the `seasons` section of settings.yaml.
+/// Seasons left out of a table go at the usual pace.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SeasonSettings {
+    pub days_per_season: u64,
+    pub regrowth: BTreeMap<Season, f32>,        // How fast vegetation grows back, as a multiple of its regrow_secs pace
+    pub endurance_decay: BTreeMap<Season, f32>, // How fast pawns get hungry, as a multiple of their hunger decay
+}
+
+impl Default for SeasonSettings {
+    fn default() -> Self {
+        Self {
+            days_per_season: 7,
+            regrowth: BTreeMap::from([(Season::Spring, 1.5), (Season::Summer, 1.0), (Season::Autumn, 0.75), (Season::Winter, 0.25)]),
+            endurance_decay: BTreeMap::from([(Season::Spring, 1.0), (Season::Summer, 1.0), (Season::Autumn, 1.1), (Season::Winter, 1.5)]),
+        }
+    }
+}
+
+impl SeasonSettings {
+    pub fn regrowth(&self, season: Season) -> f32 {
+        self.regrowth.get(&season).copied().unwrap_or(1.0).max(0.0)
+    }
+
+    pub fn endurance_decay(&self, season: Season) -> f32 {
+        self.endurance_decay.get(&season).copied().unwrap_or(1.0).max(0.0)
+    }
+}
+
+/// The season now and what it's doing to the world
+#[derive(Resource, Debug, Clone)]
+pub struct Seasons {
+    pub current: Option<Season>, // None until the first tick of a world, while the ground has its usual sprites
+    pub regrowth: f32,
+    pub endurance_decay: f32,
+    pub repaint: Vec<(u32, u32)>, // Tiles still to be redrawn for the season
+}
+
+impl Default for Seasons {
+    fn default() -> Self {
+        Self {
+            current: None,
+            regrowth: 1.0,
+            endurance_decay: 1.0,
+            repaint: Vec::new(),
+        }
+    }
+}
+
+/// A new or loaded world is drawn with the grounds' usual sprites, whatever season the last one
+/// was left in
+pub fn reset_seasons(mut seasons: ResMut<Seasons>) {
+    *seasons = Seasons::default();
+}
+
+/// Runs on FixedUpdate after update_world_clock - turns the season with the calendar, and queues
+/// up the grounds with seasonal sprites to be redrawn
+pub fn update_season(
+    config: Res<GameConfig>,
+    world_clock: Res<WorldClock>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut seasons: ResMut<Seasons>,
+) {
+    let season = Season::for_day(world_clock.day, config.seasons.days_per_season);
+    if seasons.current == Some(season) {
+        return;
+    }
+    if seasons.current.is_some() {
+        println!("{} has come", season.label());
+    }
+
+    seasons.current = Some(season);
+    seasons.regrowth = config.seasons.regrowth(season);
+    seasons.endurance_decay = config.seasons.endurance_decay(season);
+    seasons.repaint.clear();
+    for x in 0..terrain_map.width {
+        for y in 0..terrain_map.height {
+            if ground_configs.is_seasonal(terrain_map.tiles[x as usize][y as usize]) {
+                seasons.repaint.push((x, y));
+            }
+        }
+    }
+}
+
+/// Hand the terrain layer the next batch of tiles to redraw for the season
+pub fn repaint_for_season(mut seasons: ResMut<Seasons>, mut terrain_changes: ResMut<TerrainChanges>) {
+    if seasons.repaint.is_empty() {
+        return;
+    }
+    let batch = seasons.repaint.len().saturating_sub(REPAINT_BATCH);
+    for (x, y) in seasons.repaint.drain(batch..) {
+        terrain_changes.add_repaint(x, y);
+    }
+}
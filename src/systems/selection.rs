@@ -0,0 +1,331 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use crate::resources::GameConfig;
+use crate::systems::aging::Age;
+use crate::systems::control::is_controllable;
+use crate::systems::inventory::Inventory;
+use crate::systems::minimap::{Minimap, cursor_over_minimap};
+use crate::systems::needs::{Thirst, Energy};
+use crate::systems::pawn::{Pawn, Size, FEET_OFFSET_TILES, Health, Endurance, CurrentBehavior, PawnTarget};
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::status_effects::StatusEffects;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
+
+/// Drags shorter than this (in tiles) are clicks rather than box selections
+pub const BOX_SELECT_MIN_TILES: f32 = 0.5;
+/// Just below the pawn it's under, but over its shadow - less than the step between y-sorted
+/// layers, so it stays over anything else at the pawn's height
+pub const RING_BELOW: f32 = 0.0004;
+/// Above pawns, so the box shows what it's over
+const SELECTION_BOX_Z: f32 = 300.0;
+
+/// A pawn the player has clicked on or boxed in
+#[derive(Component, Debug)]
+pub struct Selected;
+
+/// Ring around the feet of a pawn, spawned hidden with it and shown while it's selected
+#[derive(Component)]
+pub struct SelectionRing;
+
+#[derive(Component)]
+pub struct SelectionPanel;
+
+#[derive(Component)]
+pub struct SelectionBox;
+
+/// Where a left-button drag started, while it's held
+#[derive(Resource, Default)]
+pub struct SelectionDrag {
+    pub start: Option<Vec2>,
+}
+
+/// Nearest pawn whose body (its size in tiles, centred on it) is under `position`
+pub fn pawn_at_position<'a>(
+    position: Vec2,
+    tile_size: f32,
+    pawns: impl Iterator<Item = (Entity, &'a Transform, &'a Size)>,
+) -> Option<Entity> {
+    pawns
+        .map(|(entity, transform, size)| (entity, transform.translation.truncate().distance(position), size))
+        .filter(|(_, distance, size)| *distance <= size.value.max(1.0) * tile_size / 2.0)
+        .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b))
+        .map(|(entity, _, _)| entity)
+}
+
+/// Pawns whose position is inside the box between two corners
+pub fn pawns_in_box<'a>(
+    corner: Vec2,
+    other_corner: Vec2,
+    pawns: impl Iterator<Item = (Entity, &'a Transform)>,
+) -> Vec<Entity> {
+    let rect = Rect::from_corners(corner, other_corner);
+    pawns
+        .filter(|(_, transform)| rect.contains(transform.translation.truncate()))
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+/// World position under the mouse cursor, if it's over the window
+pub fn cursor_world_position(
+    windows: &Query<&Window>,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<Camera>>,
+) -> Option<Vec2> {
+    let window = windows.get_single().ok()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    let cursor_position = window.cursor_position()?;
+    camera.viewport_to_world_2d(camera_transform, cursor_position).ok()
+}
+
+/// Left-click selects the pawn under the cursor, or clears the selection when there isn't one.
+/// Dragging instead selects every controllable pawn inside the box.
+pub fn handle_selection_input(
+    actions: ActionInput,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    mut drag: ResMut<SelectionDrag>,
+    mut player_commands: ResMut<PlayerCommands>,
+    minimap_query: Query<&RelativeCursorPosition, With<Minimap>>,
+) {
+    if actions.just_pressed(Action::Select) {
+        // Clicks on the minimap move the camera instead
+        if !cursor_over_minimap(&minimap_query) {
+            drag.start = cursor_world_position(&windows, &camera_query);
+        }
+        return;
+    }
+    if !actions.just_released(Action::Select) {
+        return;
+    }
+    if let (Some(start), Some(world_position)) = (drag.start.take(), cursor_world_position(&windows, &camera_query)) {
+        player_commands.give(PlayerCommand::Select { from: start.into(), to: world_position.into() });
+    }
+}
+
+/// Select every controllable pawn, dropping anything else that was selected
+pub fn handle_select_all_input(
+    actions: ActionInput,
+    mut player_commands: ResMut<PlayerCommands>,
+) {
+    if actions.just_pressed(Action::SelectAll) {
+        player_commands.give(PlayerCommand::SelectAll);
+    }
+}
+
+/// Carry out this tick's selection commands. A click selects just the pawn under it, a box
+/// every controllable pawn inside it, and either clears the selection when it finds nothing.
+pub fn apply_selection_commands(
+    player_commands: Res<PlayerCommands>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    mut commands: Commands,
+    pawn_query: Query<(Entity, &Transform, &Size, &Pawn)>,
+    selected_query: Query<Entity, With<Selected>>,
+) {
+    if player_commands.this_tick.is_empty() {
+        return;
+    }
+    let controllable = |entity: &Entity| {
+        pawn_query.get(*entity).is_ok_and(|(_, _, _, pawn)| is_controllable(&pawn_config, &pawn.pawn_type))
+    };
+
+    // Worked out here rather than read back, as the commands before it on the tick haven't landed yet
+    let mut selection: Vec<Entity> = selected_query.iter().collect();
+    for command in &player_commands.this_tick {
+        selection = match *command {
+            PlayerCommand::Select { from, to } => {
+                let (start, end) = (Vec2::from(from), Vec2::from(to));
+                if start.distance(end) >= BOX_SELECT_MIN_TILES * config.tile_size {
+                    let boxed = pawns_in_box(start, end, pawn_query.iter().map(|(entity, transform, _, _)| (entity, transform)));
+                    boxed.into_iter().filter(controllable).collect()
+                } else {
+                    let pawns = pawn_query.iter().map(|(entity, transform, size, _)| (entity, transform, size));
+                    pawn_at_position(end, config.tile_size, pawns).into_iter().collect()
+                }
+            }
+            PlayerCommand::SelectAll => pawn_query.iter().map(|(entity, ..)| entity).filter(controllable).collect(),
+            PlayerCommand::ClearSelection => Vec::new(),
+            _ => continue,
+        };
+    }
+
+    for selected in selected_query.iter() {
+        if !selection.contains(&selected) {
+            commands.entity(selected).remove::<Selected>();
+        }
+    }
+    for pawn in selection {
+        commands.entity(pawn).insert(Selected);
+    }
+}
+
+/// Draw the box being dragged out
+pub fn update_selection_box(
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    drag: Res<SelectionDrag>,
+    mut commands: Commands,
+    mut box_query: Query<(Entity, &mut Transform, &mut Sprite), With<SelectionBox>>,
+) {
+    let corners = drag.start.zip(cursor_world_position(&windows, &camera_query));
+    match (corners, box_query.get_single_mut()) {
+        (Some((start, end)), Ok((_, mut transform, mut sprite))) => {
+            let rect = Rect::from_corners(start, end);
+            transform.translation = rect.center().extend(SELECTION_BOX_Z);
+            sprite.custom_size = Some(rect.size());
+        }
+        (Some((start, end)), Err(_)) => {
+            let rect = Rect::from_corners(start, end);
+            commands.spawn((
+                Sprite::from_color(Color::srgba(0.4, 0.8, 1.0, 0.2), rect.size()),
+                Transform::from_translation(rect.center().extend(SELECTION_BOX_Z)),
+                SelectionBox,
+            ));
+        }
+        (None, _) => {
+            for (selection_box, _, _) in box_query.iter() {
+                commands.entity(selection_box).despawn();
+            }
+        }
+    }
+}
+
+/// Ring the feet of a pawn, sized to it
+pub fn selection_ring(asset_server: &AssetServer, size: f32, tile_size: f32) -> impl Bundle {
+    let mut sprite = Sprite::from_image(asset_server.load("selection_ring.png"));
+    sprite.color = Color::srgba(1.0, 0.9, 0.2, 0.9);
+    sprite.custom_size = Some(ring_size(size, tile_size));
+    (
+        sprite,
+        Transform::from_xyz(0.0, -FEET_OFFSET_TILES * tile_size, -RING_BELOW),
+        Visibility::Hidden,
+        SelectionRing,
+    )
+}
+
+fn ring_size(size: f32, tile_size: f32) -> Vec2 {
+    let width = size.max(1.0) * tile_size;
+    Vec2::new(width, width / 2.0)
+}
+
+/// Show the rings of selected pawns and hide the rest, keeping them sized to the pawns as they grow
+pub fn update_selection_rings(
+    config: Res<GameConfig>,
+    pawn_query: Query<(Ref<Size>, Has<Selected>)>,
+    mut ring_query: Query<(&Parent, &mut Visibility, &mut Sprite), With<SelectionRing>>,
+) {
+    for (parent, mut visibility, mut sprite) in ring_query.iter_mut() {
+        let Ok((size, selected)) = pawn_query.get(parent.get()) else {
+            continue;
+        };
+        let shown = if selected { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+        if size.is_changed() {
+            sprite.custom_size = Some(ring_size(size.value, config.tile_size));
+        }
+    }
+}
+
+/// Show the selected pawn's stats, behaviour and path in a panel down the left of the screen, or
+/// what's selected when there's more than one
+pub fn update_selection_panel(
+    mut commands: Commands,
+    selected_query: Query<(
+        &Pawn,
+        &Transform,
+        &Size,
+        &Health,
+        &Endurance,
+        &CurrentBehavior,
+        Option<&PawnTarget>,
+        Option<&Age>,
+        Option<&Thirst>,
+        Option<&Energy>,
+        Option<&StatusEffects>,
+        Option<&Inventory>,
+    ), With<Selected>>,
+    mut panel_query: Query<(Entity, &mut Text), With<SelectionPanel>>,
+) {
+    let selected_count = selected_query.iter().count();
+    let description = if selected_count > 1 {
+        let mut pawn_types: Vec<&str> = selected_query.iter().map(|(pawn, ..)| pawn.pawn_type.as_str()).collect();
+        pawn_types.sort();
+        Some(format!("{} pawns selected\n{}", selected_count, pawn_types.join(", ")))
+    } else {
+        selected_query.iter().next().map(
+            |(pawn, transform, size, health, endurance, behavior, target, age, thirst, energy, effects, inventory)| {
+                let mut lines = vec![
+                    pawn.pawn_type.clone(),
+                    format!("Health: {:.0}/{:.0}", health.current, health.max),
+                    format!("Endurance: {:.0}/{:.0}", endurance.current, endurance.max),
+                    format!("Size: {:.2}", size.value),
+                    format!("Behaviour: {}", behavior.state),
+                    format!("Position: ({:.0}, {:.0})", transform.translation.x, transform.translation.y),
+                ];
+                if let Some(age) = age {
+                    lines.push(format!("Age: {:.1} days ({:?})", age.days(), age.stage));
+                }
+                if let Some(thirst) = thirst {
+                    lines.push(format!("Thirst: {:.0}/{:.0}", thirst.current, thirst.max));
+                }
+                if let Some(energy) = energy {
+                    lines.push(format!("Energy: {:.0}/{:.0}", energy.current, energy.max));
+                }
+                if let Some(effects) = effects {
+                    for effect in &effects.effects {
+                        lines.push(format!("Effect: {} x{} ({:.1}s)", effect.name, effect.stacks, effect.remaining));
+                    }
+                }
+                if let Some(inventory) = inventory {
+                    lines.push(format!("Carrying: {}", if inventory.items.is_empty() { "nothing".to_string() } else { inventory.items.join(", ") }));
+                }
+                match target {
+                    Some(target) => {
+                        lines.push(format!("Target: ({:.0}, {:.0})", target.target_position.x, target.target_position.y));
+                        let remaining = target.path.iter().skip(target.current_waypoint_index);
+                        for waypoint in remaining {
+                            lines.push(format!("  -> ({:.0}, {:.0})", waypoint.x, waypoint.y));
+                        }
+                    }
+                    None => lines.push("Target: none".to_string()),
+                }
+                lines.join("\n")
+            },
+        )
+    };
+
+    match (description, panel_query.get_single_mut()) {
+        (Some(description), Ok((_, mut text))) => {
+            if text.0 != description {
+                text.0 = description;
+            }
+        }
+        (Some(description), Err(_)) => {
+            commands.spawn((
+                Text::new(description),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(40.0),
+                    left: Val::Px(10.0),
+                    max_width: Val::Px(300.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+                SelectionPanel,
+            ));
+        }
+        (None, _) => {
+            for (panel, _) in panel_query.iter() {
+                commands.entity(panel).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::pawn::{Size, FEET_OFFSET_TILES};
+
+/// Under the pawn and its selection ring - less than the step between y-sorted layers, so it stays
+/// over anything else at the pawn's height
+const SHADOW_BELOW: f32 = 0.0008;
+/// Width of the shadow of a pawn of size 1, in tiles
+const SHADOW_WIDTH_TILES: f32 = 0.8;
+
+/// Soft blob on the ground under a pawn, spawned as a child of it
+#[derive(Component)]
+pub struct PawnShadow;
+
+/// A shadow for the feet of a pawn of `size`
+pub fn pawn_shadow(asset_server: &AssetServer, size: f32, tile_size: f32) -> impl Bundle {
+    let mut sprite = Sprite::from_image(asset_server.load("pawn_shadow.png"));
+    sprite.color = Color::srgba(0.0, 0.0, 0.0, 0.35);
+    sprite.custom_size = Some(shadow_size(size, tile_size));
+    (
+        sprite,
+        Transform::from_xyz(0.0, -FEET_OFFSET_TILES * tile_size, -SHADOW_BELOW),
+        PawnShadow,
+    )
+}
+
+/// Twice as wide as it's tall, and as wide as the pawn's body
+pub fn shadow_size(size: f32, tile_size: f32) -> Vec2 {
+    let width = size * SHADOW_WIDTH_TILES * tile_size;
+    Vec2::new(width, width / 2.0)
+}
+
+/// Grow and shrink shadows along with their pawns
+pub fn update_pawn_shadows(
+    config: Res<GameConfig>,
+    pawn_query: Query<&Size, Changed<Size>>,
+    mut shadow_query: Query<(&Parent, &mut Sprite), With<PawnShadow>>,
+) {
+    for (parent, mut sprite) in shadow_query.iter_mut() {
+        if let Ok(size) = pawn_query.get(parent.get()) {
+            sprite.custom_size = Some(shadow_size(size.value, config.tile_size));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The one source of randomness for everything that plays out in the world, from generating it to
+/// where pawns wander. Seeded from the world seed, so the same seed plays out the same way.
+#[derive(Resource, Debug, Clone)]
+pub struct SimRng(ChaCha8Rng);
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+impl SimRng {
+    pub fn from_seed(seed: u32) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed as u64))
+    }
+
+    /// The world seed picked up partway through, as when a save is loaded: each tick has its own
+    /// stream, so loading the same save plays out the same way
+    pub fn at_tick(seed: u32, tick: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed as u64);
+        rng.set_stream(tick);
+        Self(rng)
+    }
+}
+
+impl RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use crate::systems::keybinds::{Action, ActionInput};
+
+pub const DEFAULT_TICKS_PER_SECOND: u32 = 20;
+/// Length of an in-game day in simulated seconds, unless settings.yaml says otherwise. Ages are
+/// always counted in days this long.
+pub const SECONDS_PER_DAY: u64 = 20 * 60;
+/// Multipliers the speed keys pick between, slowest first
+pub const SIMULATION_SPEEDS: [f32; 3] = [1.0, 2.0, 4.0];
+
+/// Periods that systems can subscribe to instead of keeping private timers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickPeriod {
+    Second,
+    Minute,
+    Day,
+}
+
+impl TickPeriod {
+    pub const ALL: [TickPeriod; 3] = [TickPeriod::Second, TickPeriod::Minute, TickPeriod::Day];
+}
+
+/// Canonical simulation tick count, advanced once per fixed update
+#[derive(Resource, Debug, Clone)]
+pub struct SimTick {
+    pub tick: u64,
+    pub ticks_per_second: u32,
+    pub seconds_per_day: u64,
+}
+
+impl Default for SimTick {
+    fn default() -> Self {
+        Self::new(DEFAULT_TICKS_PER_SECOND)
+    }
+}
+
+impl SimTick {
+    pub fn new(ticks_per_second: u32) -> Self {
+        Self {
+            tick: 0,
+            ticks_per_second: ticks_per_second.max(1),
+            seconds_per_day: SECONDS_PER_DAY,
+        }
+    }
+
+    pub fn with_day_length(mut self, seconds_per_day: u64) -> Self {
+        self.seconds_per_day = seconds_per_day.max(1);
+        self
+    }
+
+    pub fn ticks_per(&self, period: TickPeriod) -> u64 {
+        let seconds = match period {
+            TickPeriod::Second => 1,
+            TickPeriod::Minute => 60,
+            TickPeriod::Day => self.seconds_per_day,
+        };
+        seconds * self.ticks_per_second as u64
+    }
+
+    /// Whether the current tick lands on a boundary of the given period
+    pub fn is_boundary(&self, period: TickPeriod) -> bool {
+        self.tick > 0 && self.tick.is_multiple_of(self.ticks_per(period))
+    }
+
+    pub fn day(&self) -> u64 {
+        self.tick / self.ticks_per(TickPeriod::Day)
+    }
+}
+
+/// Sent whenever the tick counter crosses a second/minute/day boundary
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationTick {
+    pub tick: u64,
+    pub period: TickPeriod,
+}
+
+/// Runs on FixedUpdate - advances the tick and emits one event per period boundary crossed
+pub fn advance_sim_tick(
+    mut sim_tick: ResMut<SimTick>,
+    mut tick_events: EventWriter<SimulationTick>,
+) {
+    sim_tick.tick += 1;
+
+    for period in TickPeriod::ALL {
+        if sim_tick.is_boundary(period) {
+            tick_events.send(SimulationTick {
+                tick: sim_tick.tick,
+                period,
+            });
+        }
+    }
+}
+
+/// How fast gameplay runs against real time. It drives the virtual clock, so everything timed by
+/// `Time` or run on FixedUpdate speeds up, slows down or stops with it, while the camera and UI,
+/// which go by real time, stay responsive.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SimulationSpeed {
+    pub paused: bool,
+    pub multiplier: f32,
+}
+
+impl Default for SimulationSpeed {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            multiplier: SIMULATION_SPEEDS[0],
+        }
+    }
+}
+
+/// Pause toggles the simulation; the speed keys pick a speed, unpausing if need be
+pub fn simulation_speed_input(
+    actions: ActionInput,
+    mut speed: ResMut<SimulationSpeed>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        speed.paused = !speed.paused;
+        println!("{}", if speed.paused { "Paused" } else { "Unpaused" });
+    }
+
+    // The number row is also for camera bookmarks, held with their keys
+    if actions.pressed(Action::StoreBookmark) || actions.pressed(Action::RecallBookmark) {
+        return;
+    }
+    let speed_actions = [Action::SpeedNormal, Action::SpeedFast, Action::SpeedFastest];
+    for (action, multiplier) in speed_actions.into_iter().zip(SIMULATION_SPEEDS) {
+        if actions.just_pressed(action) {
+            speed.paused = false;
+            speed.multiplier = multiplier;
+            println!("Simulation speed: {}x", multiplier);
+        }
+    }
+}
+
+/// Keep the virtual clock in step with the simulation speed
+pub fn apply_simulation_speed(
+    speed: Res<SimulationSpeed>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !speed.is_changed() {
+        return;
+    }
+    time.set_relative_speed(speed.multiplier);
+    if speed.paused {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+}
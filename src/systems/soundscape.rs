@@ -0,0 +1,240 @@
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::audio::{SoundConfig, load_sound};
+use crate::systems::camera::CameraController;
+use crate::systems::world_clock::WorldClock;
+use crate::systems::world_gen::{GroundConfigs, TerrainMap};
+
+/// Seconds an ambient loop takes to fade fully in or out as the view moves or the day turns
+const AMBIENCE_FADE_SECS: f32 = 2.0;
+
+/// When in the day an ambient loop plays
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbientTime {
+    #[default]
+    Always,
+    Day,   // Fading out over dusk
+    Night, // Fading in over dusk
+}
+
+/// An `ambience` entry in sounds.yaml - a loop that plays while the view is over its grounds at
+/// its time of day
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AmbientLoopConfig {
+    pub sound: String,
+    #[serde(default)]
+    pub grounds: Vec<String>, // Louder the more of the ground around the view is these, everywhere when left out
+    #[serde(default)]
+    pub time: AmbientTime,
+    #[serde(default = "full_volume")]
+    pub volume: f32, // 0-1
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+/// The `music` section of sounds.yaml - tracks played in turn, each crossfading into the next
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MusicConfig {
+    pub tracks: Vec<String>,
+    pub track_secs: f32,     // How long each track plays, looping if it's shorter
+    pub crossfade_secs: f32, // How long one track takes to fade into the next
+}
+
+impl Default for MusicConfig {
+    fn default() -> Self {
+        Self {
+            tracks: Vec::new(),
+            track_secs: 180.0,
+            crossfade_secs: 4.0,
+        }
+    }
+}
+
+/// The ambient loops and music tracks from sounds.yaml whose files were found
+#[derive(Resource, Debug, Default)]
+pub struct Soundscape {
+    pub ambience: Vec<(AmbientLoopConfig, Handle<AudioSource>)>,
+    pub tracks: Vec<Handle<AudioSource>>,
+    pub current_track: usize,
+    pub track_playing_secs: f32,
+}
+
+impl Soundscape {
+    pub fn load(sound_config: &SoundConfig, asset_server: &AssetServer, missing: &mut Vec<String>) -> Self {
+        Self {
+            ambience: sound_config.ambience.iter()
+                .filter_map(|ambient| Some((ambient.clone(), load_sound(asset_server, &ambient.sound, missing)?)))
+                .collect(),
+            tracks: sound_config.music.tracks.iter()
+                .filter_map(|track| load_sound(asset_server, track, missing))
+                .collect(),
+            current_track: 0,
+            track_playing_secs: 0.0,
+        }
+    }
+}
+
+/// A playing ambient loop, and how far it's faded in
+#[derive(Component, Debug)]
+pub struct AmbientLoop {
+    pub index: usize, // Into Soundscape::ambience
+    pub level: f32,   // 0-1
+}
+
+/// A playing music track, and how far it's faded in
+#[derive(Component, Debug)]
+pub struct MusicTrack {
+    pub level: f32, // 0-1
+    pub fading_out: bool,
+}
+
+/// How much of the ground within a radius of a point is each ground, by name
+pub fn ground_shares(terrain_map: &TerrainMap, ground_configs: &GroundConfigs, centre: Vec2, radius_tiles: f32) -> HashMap<String, f32> {
+    let mut counts: HashMap<String, f32> = HashMap::new();
+    let Some((centre_x, centre_y)) = terrain_map.world_to_tile_coords(centre.x, centre.y) else {
+        return counts;
+    };
+    let reach = radius_tiles.ceil() as i32;
+    let mut total = 0.0;
+    for x in (centre_x - reach).max(0)..=(centre_x + reach).min(terrain_map.width as i32 - 1) {
+        for y in (centre_y - reach).max(0)..=(centre_y + reach).min(terrain_map.height as i32 - 1) {
+            if ((x - centre_x).pow(2) + (y - centre_y).pow(2)) as f32 > radius_tiles * radius_tiles {
+                continue;
+            }
+            if let Some(ground) = ground_configs.name_of(terrain_map.tiles[x as usize][y as usize]) {
+                *counts.entry(ground.to_string()).or_default() += 1.0;
+            }
+            total += 1.0;
+        }
+    }
+    counts.values_mut().for_each(|count| *count /= total);
+    counts
+}
+
+/// How loud an ambient loop should be, from 0 to 1, for the ground around the view and the
+/// daylight
+pub fn ambient_level(ambient: &AmbientLoopConfig, shares: &HashMap<String, f32>, daylight: f32) -> f32 {
+    let ground = if ambient.grounds.is_empty() {
+        1.0
+    } else {
+        ambient.grounds.iter().filter_map(|ground| shares.get(ground)).sum::<f32>().min(1.0)
+    };
+    let time = match ambient.time {
+        AmbientTime::Always => 1.0,
+        AmbientTime::Day => daylight,
+        AmbientTime::Night => 1.0 - daylight,
+    };
+    ground * time
+}
+
+/// Step a fade towards a level over a number of seconds for the whole way
+fn fade_towards(level: f32, target: f32, delta: f32, fade_secs: f32) -> f32 {
+    let step = if fade_secs > 0.0 { delta / fade_secs } else { 1.0 };
+    if level < target { (level + step).min(target) } else { (level - step).max(target) }
+}
+
+/// Start every ambient loop silent, for update_ambience to fade in where it belongs
+pub fn spawn_ambient_loops(mut commands: Commands, soundscape: Option<Res<Soundscape>>) {
+    let Some(soundscape) = soundscape else {
+        return;
+    };
+    for (index, (_, sound)) in soundscape.ambience.iter().enumerate() {
+        commands.spawn((
+            AudioPlayer::new(sound.clone()),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            AmbientLoop { index, level: 0.0 },
+        ));
+    }
+}
+
+/// Fade the ambient loops in and out with the ground around the middle of the view and the time
+/// of day. Nothing plays until there's a world to listen to.
+pub fn update_ambience(
+    time: Res<Time<Real>>, // Keeps fading while the game is paused
+    config: Res<GameConfig>,
+    sound_config: Res<SoundConfig>,
+    soundscape: Option<Res<Soundscape>>,
+    world_clock: Res<WorldClock>,
+    world: (Option<Res<TerrainMap>>, Option<Res<GroundConfigs>>),
+    camera_query: Query<&Transform, With<CameraController>>,
+    mut loop_query: Query<(&mut AmbientLoop, Option<&AudioSink>)>,
+) {
+    let Some(soundscape) = soundscape else {
+        return;
+    };
+    let shares = match (world, camera_query.get_single()) {
+        ((Some(terrain_map), Some(ground_configs)), Ok(camera)) => {
+            Some(ground_shares(&terrain_map, &ground_configs, camera.translation.truncate(), sound_config.ambience_radius))
+        }
+        _ => None,
+    };
+    let daylight = world_clock.daylight();
+    for (mut ambient_loop, sink) in loop_query.iter_mut() {
+        let Some((ambient, _)) = soundscape.ambience.get(ambient_loop.index) else {
+            continue;
+        };
+        let target = shares.as_ref().map_or(0.0, |shares| ambient_level(ambient, shares, daylight));
+        ambient_loop.level = fade_towards(ambient_loop.level, target, time.delta_secs(), AMBIENCE_FADE_SECS);
+        // A sink's volume replaces the global volume, so the master volume is applied here
+        if let Some(sink) = sink {
+            sink.set_volume(ambient_loop.level * ambient.volume * config.sfx_volume * config.volume);
+        }
+    }
+}
+
+/// Play the music tracks in turn, each for track_secs, crossfading from one to the next
+pub fn update_music(
+    time: Res<Time<Real>>, // Music plays on while the game is paused
+    config: Res<GameConfig>,
+    sound_config: Res<SoundConfig>,
+    soundscape: Option<ResMut<Soundscape>>,
+    mut commands: Commands,
+    mut track_query: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let Some(mut soundscape) = soundscape else {
+        return;
+    };
+    if soundscape.tracks.is_empty() {
+        return;
+    }
+    let music = &sound_config.music;
+    let delta = time.delta_secs();
+
+    soundscape.track_playing_secs += delta;
+    let playing = track_query.iter().any(|(_, track, _)| !track.fading_out);
+    let next_due = soundscape.tracks.len() > 1 && soundscape.track_playing_secs >= music.track_secs;
+    if !playing || next_due {
+        if playing {
+            for (_, mut track, _) in track_query.iter_mut() {
+                track.fading_out = true;
+            }
+            soundscape.current_track = (soundscape.current_track + 1) % soundscape.tracks.len();
+        }
+        soundscape.track_playing_secs = 0.0;
+        commands.spawn((
+            AudioPlayer::new(soundscape.tracks[soundscape.current_track].clone()),
+            PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            MusicTrack { level: 0.0, fading_out: false },
+        ));
+    }
+
+    for (entity, mut track, sink) in track_query.iter_mut() {
+        let target = if track.fading_out { 0.0 } else { 1.0 };
+        track.level = fade_towards(track.level, target, delta, music.crossfade_secs);
+        if track.fading_out && track.level <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // A sink's volume replaces the global volume, so the master volume is applied here
+        if let Some(sink) = sink {
+            sink.set_volume(track.level * config.music_volume * config.volume);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use std::collections::HashMap;
+use crate::resources::GameConfig;
+use crate::systems::pawn::Pawn;
+
+/// Width of a spatial grid cell, in tiles
+pub const SPATIAL_CELL_TILES: f32 = 8.0;
+
+/// Pawns bucketed into square cells by position, so proximity queries only look at nearby
+/// cells rather than every pawn. Rebuilt from Transforms at the start of every frame.
+#[derive(Resource, Debug)]
+pub struct SpatialGrid {
+    pub cell_size: f32,
+    pub cells: HashMap<(i32, i32), Vec<(Entity, Vec2)>>,
+    bounds: Option<((i32, i32), (i32, i32))>, // Lowest and highest occupied cells
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(GameConfig::default().tile_size * SPATIAL_CELL_TILES)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    pub fn cell(&self, position: Vec2) -> (i32, i32) {
+        ((position.x / self.cell_size).floor() as i32, (position.y / self.cell_size).floor() as i32)
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push((entity, position));
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => ((min.0.min(cell.0), min.1.min(cell.1)), (max.0.max(cell.0), max.1.max(cell.1))),
+            None => (cell, cell),
+        });
+    }
+
+    /// Everyone within `radius` of `center`
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let low = self.cell(center - Vec2::splat(radius));
+        let high = self.cell(center + Vec2::splat(radius));
+        // Only the occupied part of the square around the circle needs looking at
+        self.bounds
+            .into_iter()
+            .flat_map(move |(min, max)| {
+                let cells_y = low.1.max(min.1)..=high.1.min(max.1);
+                (low.0.max(min.0)..=high.0.min(max.0)).flat_map(move |x| cells_y.clone().map(move |y| (x, y)))
+            })
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, position)| center.distance(*position) <= radius)
+    }
+
+    /// The closest pawn within `max_radius` of `center` that `accept` approves, and its distance.
+    /// Cells are searched in rings outwards from `center`, stopping once no further ring could
+    /// hold anything closer, and `accept` is only asked about pawns closer than the best so far.
+    pub fn nearest(&self, center: Vec2, max_radius: f32, mut accept: impl FnMut(Entity, Vec2) -> bool) -> Option<(Entity, f32)> {
+        let (min, max) = self.bounds?;
+        let (center_x, center_y) = self.cell(center);
+        let furthest_ring = [center_x - min.0, max.0 - center_x, center_y - min.1, max.1 - center_y]
+            .into_iter()
+            .max()
+            .unwrap_or(0);
+        let radius_rings = (max_radius / self.cell_size).ceil();
+        let last_ring = if radius_rings < furthest_ring as f32 { radius_rings as i32 } else { furthest_ring };
+
+        let mut best: Option<(Entity, f32)> = None;
+        for ring in 0..=last_ring {
+            // Nothing in this ring or beyond is nearer than (ring - 1) cells
+            if best.is_some_and(|(_, distance)| distance <= (ring - 1) as f32 * self.cell_size) {
+                break;
+            }
+            for cell in ring_cells((center_x, center_y), ring) {
+                let entries = match self.cells.get(&cell) {
+                    Some(entries) => entries,
+                    None => continue,
+                };
+                for &(entity, position) in entries {
+                    let distance = center.distance(position);
+                    if distance <= max_radius && best.is_none_or(|(_, nearest)| distance < nearest) && accept(entity, position) {
+                        best = Some((entity, distance));
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+/// The cells `ring` steps (in a square) out from `center`
+fn ring_cells(center: (i32, i32), ring: i32) -> Vec<(i32, i32)> {
+    if ring == 0 {
+        return vec![center];
+    }
+    let mut cells = Vec::with_capacity(8 * ring as usize);
+    for dx in -ring..=ring {
+        cells.push((center.0 + dx, center.1 - ring));
+        cells.push((center.0 + dx, center.1 + ring));
+    }
+    for dy in (1 - ring)..ring {
+        cells.push((center.0 - ring, center.1 + dy));
+        cells.push((center.0 + ring, center.1 + dy));
+    }
+    cells
+}
+
+/// Re-bucket every pawn by where it is now
+pub fn rebuild_spatial_grid(
+    config: Res<GameConfig>,
+    mut spatial_grid: ResMut<SpatialGrid>,
+    pawn_query: Query<(Entity, &Transform), With<Pawn>>,
+) {
+    spatial_grid.clear();
+    spatial_grid.cell_size = config.tile_size * SPATIAL_CELL_TILES;
+    for (entity, transform) in pawn_query.iter() {
+        spatial_grid.insert(entity, transform.translation.truncate());
+    }
+}
+
+/// Rebuilds the grid at the start of `schedule` so every AI system after it sees the same
+/// snapshot - FixedFirst in the game, ahead of each simulation step
+pub struct SpatialGridPlugin {
+    pub schedule: InternedScheduleLabel,
+}
+
+impl Default for SpatialGridPlugin {
+    fn default() -> Self {
+        Self { schedule: PreUpdate.intern() }
+    }
+}
+
+impl Plugin for SpatialGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialGrid>()
+            .add_systems(self.schedule, rebuild_spatial_grid);
+    }
+}
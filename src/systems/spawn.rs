@@ -1,7 +1,145 @@
 use bevy::prelude::*;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::systems::den::{Den, HomeDen};
 use crate::systems::pawn::{Pawn, spawn_pawn, TilesetManager};
-use crate::systems::pawn_config::PawnConfig;
+use crate::systems::pawn_config::{PawnConfig, PawnType};
 use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+use crate::systems::sim_rng::SimRng;
+
+/// Tiles around a clustered group's centre its pawns are spread over when it doesn't set `cluster_radius`
+pub const DEFAULT_CLUSTER_RADIUS: u32 = 3;
+
+/// How a spawn group's pawns are spread out
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpawnPlacement {
+    #[default]
+    Clustered, // Together around one spot, like a herd or a pack
+    Scattered, // Each on its own anywhere on the group's grounds
+}
+
+/// Pawns of one type placed together when the world is generated
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SpawnGroup {
+    pub pawn: PawnType,
+    #[serde(default)]
+    pub count: Option<u32>,
+    #[serde(default)]
+    pub density: Option<f32>, // Pawns per 100 tiles of the group's grounds - used when count isn't set
+    #[serde(default)]
+    pub grounds: Vec<String>, // Ground names from grounds.yaml to spawn on - any passable ground when empty
+    #[serde(default)]
+    pub min_distance: u32, // Tiles every pawn keeps from pawns of the groups placed before this one
+    #[serde(default)]
+    pub placement: SpawnPlacement,
+    #[serde(default = "default_cluster_radius")]
+    pub cluster_radius: u32,
+}
+
+fn default_cluster_radius() -> u32 {
+    DEFAULT_CLUSTER_RADIUS
+}
+
+/// Spawn groups from spawns.yaml by name. Pawn types without a group spawn `spawn_count` from
+/// pawns.yaml at their dens, or the middle of the map.
+#[derive(Debug, Clone, Default, Resource, Deserialize, Serialize)]
+pub struct SpawnConfig {
+    #[serde(flatten)]
+    pub groups: HashMap<String, SpawnGroup>,
+}
+
+impl SpawnConfig {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: SpawnConfig = serde_yaml::from_str(contents)?;
+        Ok(config)
+    }
+
+    /// Groups in name order, so they're placed the same way every time
+    pub fn sorted_groups(&self) -> Vec<(&String, &SpawnGroup)> {
+        let mut groups: Vec<_> = self.groups.iter().collect();
+        groups.sort_by(|a, b| a.0.cmp(b.0));
+        groups
+    }
+
+    pub fn has_groups_for(&self, pawn_type: &str) -> bool {
+        self.groups.values().any(|group| group.pawn == pawn_type)
+    }
+}
+
+/// Whether a group's pawns can stand on a tile, going by its grounds alone
+fn on_group_grounds(group: &SpawnGroup, tile: (i32, i32), terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> bool {
+    if !terrain_map.is_tile_passable(tile.0, tile.1, ground_configs) {
+        return false;
+    }
+    let terrain_type = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+    group.grounds.is_empty()
+        || group.grounds.iter().any(|ground| ground_configs.terrain_mapping.get(ground) == Some(&terrain_type))
+}
+
+/// How many pawns a group spawns: its count, or its density over however much of its grounds
+/// the map has
+pub fn spawn_group_count(group: &SpawnGroup, terrain_map: &TerrainMap, ground_configs: &GroundConfigs) -> u32 {
+    if let Some(count) = group.count {
+        return count;
+    }
+    let density = match group.density {
+        Some(density) => density,
+        None => return 0,
+    };
+    let mut tiles = 0;
+    for x in 0..terrain_map.width as i32 {
+        for y in 0..terrain_map.height as i32 {
+            if on_group_grounds(group, (x, y), terrain_map, ground_configs) {
+                tiles += 1;
+            }
+        }
+    }
+    (tiles as f32 * density / 100.0).round() as u32
+}
+
+/// Tiles for `count` of a group's pawns on its grounds and at least `min_distance` from every
+/// tile in `taken`. Clustered groups share out the free tiles around one spot, doubling up once
+/// they run out; scattered ones get a tile each from anywhere. Empty if there's nowhere to go.
+pub fn pick_spawn_tiles(
+    group: &SpawnGroup,
+    count: u32,
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+    taken: &[(i32, i32)],
+    rng: &mut impl Rng,
+) -> Vec<(i32, i32)> {
+    let min_distance_squared = (group.min_distance * group.min_distance) as i32;
+    let far_enough = |tile: (i32, i32)| {
+        taken.iter().all(|other| (other.0 - tile.0).pow(2) + (other.1 - tile.1).pow(2) >= min_distance_squared)
+    };
+    let mut free: Vec<(i32, i32)> = Vec::new();
+    for x in 0..terrain_map.width as i32 {
+        for y in 0..terrain_map.height as i32 {
+            if on_group_grounds(group, (x, y), terrain_map, ground_configs) && far_enough((x, y)) {
+                free.push((x, y));
+            }
+        }
+    }
+    if free.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    match group.placement {
+        SpawnPlacement::Scattered => free.choose_multiple(rng, count as usize).copied().collect(),
+        SpawnPlacement::Clustered => {
+            let centre = *free.choose(rng).expect("Checked there are free tiles");
+            let radius_squared = (group.cluster_radius * group.cluster_radius) as i32;
+            let mut around: Vec<(i32, i32)> = free
+                .into_iter()
+                .filter(|tile| (tile.0 - centre.0).pow(2) + (tile.1 - centre.1).pow(2) <= radius_squared)
+                .collect();
+            around.shuffle(rng);
+            around.iter().cycle().take(count as usize).copied().collect()
+        }
+    }
+}
 
 pub fn spawn_all_pawns(
     mut commands: Commands,
@@ -9,17 +147,72 @@ pub fn spawn_all_pawns(
     terrain_map: Res<TerrainMap>,
     ground_configs: Res<GroundConfigs>,
     pawn_config: Res<PawnConfig>,
+    spawn_config: Res<SpawnConfig>,
     mut tileset_manager: ResMut<TilesetManager>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    den_query: Query<(Entity, &Den, &Transform)>,
+    mut rng: ResMut<SimRng>,
 ) {
-    // Loop through all pawn types defined in pawns.yaml
+    // Species with dens are shared out between them
+    let dens_of = |pawn_type: &str| -> Vec<(Entity, Vec3)> {
+        den_query
+            .iter()
+            .filter(|(_, den, _)| den.species == pawn_type)
+            .map(|(entity, _, transform)| (entity, transform.translation))
+            .collect()
+    };
+
+    // Groups from spawns.yaml, each kept clear of the ones before it. Their pawns call the
+    // nearest of their species' dens home.
+    let rng = &mut *rng;
+    let mut taken: Vec<(i32, i32)> = Vec::new();
+    for (name, group) in spawn_config.sorted_groups() {
+        let definition = match pawn_config.get_pawn_definition(&group.pawn) {
+            Some(definition) => definition,
+            None => {
+                println!("Spawn group {} is for unknown pawn type {}", name, group.pawn);
+                continue;
+            }
+        };
+        let movement = ground_configs.for_movement(definition.can_swim);
+        let count = spawn_group_count(group, &terrain_map, &movement);
+        let tiles = pick_spawn_tiles(group, count, &terrain_map, &movement, &taken, rng);
+        if tiles.len() < count as usize {
+            println!("Only found room for {} of {} pawns in spawn group {}", tiles.len(), count, name);
+        }
+        let dens = dens_of(&group.pawn);
+        for &(tile_x, tile_y) in &tiles {
+            let (x, y) = terrain_map.tile_to_world_coords(tile_x, tile_y);
+            let pawn = Pawn::new(group.pawn.clone());
+            let entity = spawn_pawn(&mut commands, &asset_server, &terrain_map, &ground_configs, &pawn_config, &mut tileset_manager, &mut texture_atlas_layouts, pawn, Some((x, y)));
+            let nearest_den = dens.iter().min_by(|a, b| {
+                a.1.truncate().distance_squared(Vec2::new(x, y)).total_cmp(&b.1.truncate().distance_squared(Vec2::new(x, y)))
+            });
+            if let Some(&(den, _)) = nearest_den {
+                commands.entity(entity).insert(HomeDen { den });
+            }
+        }
+        taken.extend(tiles);
+    }
+
+    // Loop through the rest of the pawn types defined in pawns.yaml
     for pawn_type in pawn_config.get_pawn_types() {
+        if spawn_config.has_groups_for(&pawn_type) {
+            continue;
+        }
         if let Some(definition) = pawn_config.get_pawn_definition(&pawn_type) {
+            let dens = dens_of(&pawn_type);
+
             // Spawn the specified number of each pawn type
-            for _ in 0..definition.spawn_count {
+            for index in 0..definition.spawn_count as usize {
                 let pawn = Pawn::new(pawn_type.clone());
-                spawn_pawn(&mut commands, &asset_server, &terrain_map, &ground_configs, &pawn_config, &mut tileset_manager, &mut texture_atlas_layouts, pawn, None);
+                let home = dens.get(index % dens.len().max(1)).copied();
+                let position = home.map(|(_, position)| (position.x, position.y));
+                let entity = spawn_pawn(&mut commands, &asset_server, &terrain_map, &ground_configs, &pawn_config, &mut tileset_manager, &mut texture_atlas_layouts, pawn, position);
+                if let Some((den, _)) = home {
+                    commands.entity(entity).insert(HomeDen { den });
+                }
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,324 @@
+use bevy::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
+use crate::systems::combat::{CombatEvent, DeathEvent};
+use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+use crate::systems::pawn::{Pawn, Health, Endurance};
+use crate::systems::pawn_config::PawnConfig;
+
+/// Simulated seconds the per-minute rates are counted over
+pub const RATE_WINDOW_SECS: f64 = 60.0;
+/// Simulated seconds between population samples
+pub const POPULATION_SAMPLE_SECS: f64 = 5.0;
+/// Population samples kept - ten minutes' worth
+pub const POPULATION_HISTORY_LEN: usize = 120;
+/// Size in pixels of the population graph's plot
+const GRAPH_SIZE: Vec2 = Vec2::new(240.0, 100.0);
+/// Thickness in pixels of the graph's lines
+const GRAPH_LINE_THICKNESS: f32 = 2.0;
+const PREDATOR_COLOR: Color = Color::srgb(0.9, 0.3, 0.25);
+const PREY_COLOR: Color = Color::srgb(0.4, 0.85, 0.35);
+
+/// Births, deaths and blows landed across the whole world. Births are pawns that turn up after
+/// the starting population was placed.
+#[derive(Resource, Default)]
+pub struct Statistics {
+    births: VecDeque<f64>, // When each birth in the last minute happened, in simulated seconds
+    deaths: VecDeque<f64>,
+    blows: VecDeque<f64>,
+    pub total_births: u64,
+    pub total_deaths: u64,
+    started: bool, // Whether the starting population has been seen, so it isn't counted as born
+    history: VecDeque<BTreeMap<String, usize>>, // Live count per species, oldest sample first
+    pub samples_taken: u64,
+    next_sample_at: f64,
+}
+
+impl Statistics {
+    pub fn record_birth(&mut self, now: f64) {
+        self.births.push_back(now);
+        self.total_births += 1;
+    }
+
+    pub fn record_death(&mut self, now: f64) {
+        self.deaths.push_back(now);
+        self.total_deaths += 1;
+    }
+
+    pub fn record_blow(&mut self, now: f64) {
+        self.blows.push_back(now);
+    }
+
+    /// Drop anything older than the rate window
+    pub fn forget_before(&mut self, cutoff: f64) {
+        for times in [&mut self.births, &mut self.deaths, &mut self.blows] {
+            while times.front().is_some_and(|time| *time < cutoff) {
+                times.pop_front();
+            }
+        }
+    }
+
+    pub fn births_per_minute(&self) -> usize {
+        self.births.len()
+    }
+
+    pub fn deaths_per_minute(&self) -> usize {
+        self.deaths.len()
+    }
+
+    pub fn blows_per_minute(&self) -> usize {
+        self.blows.len()
+    }
+
+    /// Add a sample of the live count per species, dropping the oldest once the history is full
+    pub fn record_sample(&mut self, counts: BTreeMap<String, usize>) {
+        if self.history.len() >= POPULATION_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(counts);
+        self.samples_taken += 1;
+    }
+
+    /// Total population of the species `include` picks out in each sample, oldest first
+    pub fn history_of(&self, include: impl Fn(&str) -> bool) -> Vec<usize> {
+        self.history
+            .iter()
+            .map(|counts| counts.iter().filter(|(pawn_type, _)| include(pawn_type)).map(|(_, count)| count).sum())
+            .collect()
+    }
+}
+
+/// Pawns that eat other pawns - the rest count as prey on the population graph
+pub fn is_predator(pawn_config: &PawnConfig, pawn_type: &str) -> bool {
+    pawn_config.get_pawn_definition(pawn_type).is_some_and(|definition| !definition.eats.pawns.is_empty())
+}
+
+/// Thin vertical strips that trace a series as a line across a graph `size` pixels big, each
+/// joining a sample to the one before it. Each is (left, top, height) in pixels from the top-left
+/// corner, with `max` at the top and one strip per slot of the full history.
+pub fn line_strips(series: &[usize], max: usize, size: Vec2) -> Vec<(f32, f32, f32)> {
+    let step = size.x / POPULATION_HISTORY_LEN as f32;
+    let max = max.max(1) as f32;
+    let y = |value: usize| (size.y - GRAPH_LINE_THICKNESS) * (1.0 - value as f32 / max);
+    series
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            let previous = if index > 0 { series[index - 1] } else { value };
+            let (from, to) = (y(previous), y(value));
+            (index as f32 * step, from.min(to), (from - to).abs() + GRAPH_LINE_THICKNESS)
+        })
+        .collect()
+}
+
+/// How one species is doing
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SpeciesSummary {
+    pub count: usize,
+    pub average_health: f32,    // Fraction of max, 0-1
+    pub average_endurance: f32, // Fraction of max, 0-1
+}
+
+/// Live count and average health and endurance of every species present
+pub fn summarize_population<'a>(pawns: impl Iterator<Item = (&'a Pawn, &'a Health, &'a Endurance)>) -> BTreeMap<String, SpeciesSummary> {
+    let mut totals: BTreeMap<String, SpeciesSummary> = BTreeMap::new();
+    for (pawn, health, endurance) in pawns {
+        let summary = totals.entry(pawn.pawn_type.clone()).or_default();
+        summary.count += 1;
+        summary.average_health += if health.max > 0.0 { health.current / health.max } else { 0.0 };
+        summary.average_endurance += if endurance.max > 0.0 { endurance.current / endurance.max } else { 0.0 };
+    }
+    for summary in totals.values_mut() {
+        summary.average_health /= summary.count as f32;
+        summary.average_endurance /= summary.count as f32;
+    }
+    totals
+}
+
+/// The population overlay's text
+pub fn describe_population(statistics: &Statistics, species: &BTreeMap<String, SpeciesSummary>) -> String {
+    let total: usize = species.values().map(|summary| summary.count).sum();
+    let mut lines = vec![
+        format!("Population: {}", total),
+        format!(
+            "Per minute: {} born, {} died, {} blows",
+            statistics.births_per_minute(), statistics.deaths_per_minute(), statistics.blows_per_minute()
+        ),
+        format!("All time: {} born, {} died", statistics.total_births, statistics.total_deaths),
+    ];
+    for (pawn_type, summary) in species {
+        lines.push(format!(
+            "{}: {} (health {:.0}%, endurance {:.0}%)",
+            pawn_type, summary.count, summary.average_health * 100.0, summary.average_endurance * 100.0
+        ));
+    }
+    lines.join("\n")
+}
+
+#[derive(Component)]
+pub struct PopulationPanel;
+
+/// Predator and prey populations over the last ten minutes, shown with the population overlay
+#[derive(Component)]
+pub struct PopulationGraph;
+
+/// Every few simulated seconds, note how many of each species are alive
+pub fn sample_population(
+    time: Res<Time>,
+    mut statistics: ResMut<Statistics>,
+    pawn_query: Query<&Pawn>,
+) {
+    let now = time.elapsed_secs_f64();
+    if now < statistics.next_sample_at {
+        return;
+    }
+    statistics.next_sample_at = now + POPULATION_SAMPLE_SECS;
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for pawn in pawn_query.iter() {
+        *counts.entry(pawn.pawn_type.clone()).or_insert(0) += 1;
+    }
+    statistics.record_sample(counts);
+}
+
+/// Count births, deaths and blows as they happen
+pub fn record_population_events(
+    time: Res<Time>,
+    mut statistics: ResMut<Statistics>,
+    mut death_events: EventReader<DeathEvent>,
+    mut combat_events: EventReader<CombatEvent>,
+    new_pawns: Query<(), Added<Pawn>>,
+) {
+    let now = time.elapsed_secs_f64();
+    if statistics.started {
+        for _ in new_pawns.iter() {
+            statistics.record_birth(now);
+        }
+    } else {
+        statistics.started = true;
+    }
+    for _ in death_events.read() {
+        statistics.record_death(now);
+    }
+    for _ in combat_events.read() {
+        statistics.record_blow(now);
+    }
+    statistics.forget_before(now - RATE_WINDOW_SECS);
+}
+
+/// Show the population statistics while the population overlay is on
+pub fn update_population_panel(
+    debug_state: Res<DebugDisplayState>,
+    statistics: Res<Statistics>,
+    pawn_query: Query<(&Pawn, &Health, &Endurance)>,
+    mut commands: Commands,
+    mut panel_query: Query<(Entity, &mut Text), With<PopulationPanel>>,
+) {
+    if debug_state.mode != DebugMode::Population {
+        for (panel, _) in panel_query.iter() {
+            commands.entity(panel).despawn();
+        }
+        return;
+    }
+
+    let description = describe_population(&statistics, &summarize_population(pawn_query.iter()));
+    match panel_query.get_single_mut() {
+        Ok((_, mut text)) => {
+            if text.0 != description {
+                text.0 = description;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Text::new(description),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(40.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+                PopulationPanel,
+            ));
+        }
+    }
+}
+
+/// Redraw the population graph whenever a sample is taken while the population overlay is on
+pub fn update_population_graph(
+    debug_state: Res<DebugDisplayState>,
+    statistics: Res<Statistics>,
+    pawn_config: Res<PawnConfig>,
+    mut samples_drawn: Local<Option<u64>>,
+    mut commands: Commands,
+    graph_query: Query<Entity, With<PopulationGraph>>,
+) {
+    let showing = debug_state.mode == DebugMode::Population;
+    let drawn = if showing { Some(statistics.samples_taken) } else { None };
+    if *samples_drawn == drawn {
+        return;
+    }
+    *samples_drawn = drawn;
+    for graph in graph_query.iter() {
+        commands.entity(graph).despawn_recursive();
+    }
+    if !showing {
+        return;
+    }
+
+    let predators = statistics.history_of(|pawn_type| is_predator(&pawn_config, pawn_type));
+    let prey = statistics.history_of(|pawn_type| !is_predator(&pawn_config, pawn_type));
+    let max = predators.iter().chain(prey.iter()).copied().max().unwrap_or(0);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+            PopulationGraph,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new(format!("Predators vs prey, last 10 minutes (peak {})", max)),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            panel
+                .spawn(Node {
+                    width: Val::Px(GRAPH_SIZE.x),
+                    height: Val::Px(GRAPH_SIZE.y),
+                    ..default()
+                })
+                .with_children(|plot| {
+                    for (series, color) in [(&prey, PREY_COLOR), (&predators, PREDATOR_COLOR)] {
+                        for (left, top, height) in line_strips(series, max, GRAPH_SIZE) {
+                            plot.spawn((
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px(left),
+                                    top: Val::Px(top),
+                                    width: Val::Px(GRAPH_SIZE.x / POPULATION_HISTORY_LEN as f32),
+                                    height: Val::Px(height),
+                                    ..default()
+                                },
+                                BackgroundColor(color),
+                            ));
+                        }
+                    }
+                });
+        });
+}
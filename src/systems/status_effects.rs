@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::systems::combat::CombatEvent;
+use crate::systems::pawn::{Pawn, Health};
+use crate::systems::pawn_config::PawnConfig;
+use crate::systems::sim_tick::{SimulationTick, TickPeriod};
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// What happens when an effect is applied to a pawn that already has it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stacking {
+    #[default]
+    Refresh, // Start the duration over
+    Stack,   // Add a stack (up to `max_stacks`) and start the duration over
+    Ignore,  // Leave the running one be
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatusEffectDefinition {
+    pub duration: f32, // Seconds
+    #[serde(default = "default_speed_multiplier")]
+    pub speed_multiplier: f32, // Per stack
+    #[serde(default)]
+    pub damage_per_second: f32, // Per stack
+    #[serde(default)]
+    pub heal_per_second: f32, // Per stack
+    #[serde(default)]
+    pub stacking: Stacking,
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u32,
+}
+
+fn default_speed_multiplier() -> f32 {
+    1.0
+}
+
+fn default_max_stacks() -> u32 {
+    1
+}
+
+/// Status effects from status_effects.yaml by name. Pawns inflict them with `inflicts` in
+/// pawns.yaml and grounds with `status_effect` in grounds.yaml.
+#[derive(Debug, Clone, Default, Resource, Deserialize, Serialize)]
+pub struct StatusEffectConfig {
+    #[serde(flatten)]
+    pub effects: HashMap<String, StatusEffectDefinition>,
+}
+
+impl StatusEffectConfig {
+    pub fn from_yaml(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: StatusEffectConfig = serde_yaml::from_str(contents)?;
+        Ok(config)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StatusEffectDefinition> {
+        self.effects.get(name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveStatusEffect {
+    pub name: String,
+    pub definition: StatusEffectDefinition,
+    pub remaining: f32,
+    pub stacks: u32,
+}
+
+/// The effects running on a pawn. Removed once the last one wears off.
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<ActiveStatusEffect>,
+}
+
+impl StatusEffects {
+    /// Add an effect, or follow its stacking rule if it's already running
+    pub fn apply(&mut self, name: &str, definition: &StatusEffectDefinition) {
+        match self.effects.iter_mut().find(|effect| effect.name == name) {
+            Some(effect) => match definition.stacking {
+                Stacking::Refresh => effect.remaining = definition.duration,
+                Stacking::Stack => {
+                    effect.stacks = (effect.stacks + 1).min(definition.max_stacks.max(1));
+                    effect.remaining = definition.duration;
+                }
+                Stacking::Ignore => {}
+            },
+            None => self.effects.push(ActiveStatusEffect {
+                name: name.to_string(),
+                definition: definition.clone(),
+                remaining: definition.duration,
+                stacks: 1,
+            }),
+        }
+    }
+
+    /// What the pawn's move speed is multiplied by, every stack of every effect compounded
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .map(|effect| effect.definition.speed_multiplier.powi(effect.stacks as i32))
+            .product()
+    }
+}
+
+/// Ask for a named effect from status_effects.yaml to be put on a pawn
+#[derive(Event, Debug, Clone)]
+pub struct ApplyStatusEffect {
+    pub target: Entity,
+    pub effect: String,
+}
+
+/// Blows from pawns with `inflicts` put those effects on their victim
+pub fn inflict_attack_effects(
+    pawn_config: Res<PawnConfig>,
+    mut combat_events: EventReader<CombatEvent>,
+    mut apply_events: EventWriter<ApplyStatusEffect>,
+    attacker_query: Query<&Pawn>,
+) {
+    for event in combat_events.read() {
+        let definition = match attacker_query.get(event.attacker).ok().and_then(|pawn| pawn_config.get_pawn_definition(&pawn.pawn_type)) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        for effect in &definition.inflicts {
+            apply_events.send(ApplyStatusEffect { target: event.victim, effect: effect.clone() });
+        }
+    }
+}
+
+/// Pawns standing on ground with a `status_effect` get it (re)applied once a second of simulated time
+pub fn ground_status_effects(
+    mut tick_events: EventReader<SimulationTick>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut apply_events: EventWriter<ApplyStatusEffect>,
+    pawn_query: Query<(Entity, &Transform), With<Pawn>>,
+) {
+    if !tick_events.read().any(|event| event.period == TickPeriod::Second) {
+        return;
+    }
+
+    for (entity, transform) in pawn_query.iter() {
+        let effect = terrain_map
+            .get_terrain_at_world_pos(transform.translation.x, transform.translation.y)
+            .and_then(|terrain_type| ground_configs.status_effect(terrain_type));
+        if let Some(effect) = effect {
+            apply_events.send(ApplyStatusEffect { target: entity, effect: effect.clone() });
+        }
+    }
+}
+
+/// Put requested effects on their pawns, following each effect's stacking rule
+pub fn apply_status_effects(
+    effect_config: Res<StatusEffectConfig>,
+    mut apply_events: EventReader<ApplyStatusEffect>,
+    mut commands: Commands,
+    mut effects_query: Query<&mut StatusEffects>,
+    pawn_query: Query<&Pawn>,
+) {
+    // Pawns that don't have any effects yet get theirs all at once
+    let mut new_effects: HashMap<Entity, StatusEffects> = HashMap::new();
+    for event in apply_events.read() {
+        let definition = match effect_config.get(&event.effect) {
+            Some(definition) => definition,
+            None => {
+                println!("Unknown status effect {}", event.effect);
+                continue;
+            }
+        };
+        if let Ok(mut effects) = effects_query.get_mut(event.target) {
+            effects.apply(&event.effect, definition);
+        } else if pawn_query.get(event.target).is_ok() {
+            new_effects.entry(event.target).or_default().apply(&event.effect, definition);
+        }
+    }
+
+    for (entity, effects) in new_effects {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(effects);
+        }
+    }
+}
+
+/// Run every effect's damage and healing, and take off the ones that have worn off
+pub fn status_effect_system(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pawn_query: Query<(Entity, &Pawn, &mut StatusEffects, &mut Health)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, pawn, mut effects, mut health) in pawn_query.iter_mut() {
+        for effect in effects.effects.iter_mut() {
+            let active = delta.min(effect.remaining.max(0.0));
+            let stacks = effect.stacks as f32;
+            let change = (effect.definition.heal_per_second - effect.definition.damage_per_second) * stacks * active;
+            health.current = (health.current + change).clamp(0.0, health.max);
+            effect.remaining -= delta;
+        }
+
+        effects.effects.retain(|effect| {
+            if effect.remaining <= 0.0 {
+                println!("{} is no longer affected by {}", pawn.pawn_type, effect.name);
+            }
+            effect.remaining > 0.0
+        });
+        if effects.effects.is_empty() {
+            commands.entity(entity).remove::<StatusEffects>();
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::{ActiveBehaviour, HuntSoloAI};
+use crate::systems::async_pathfinding::PathfindingRequest;
+use crate::systems::attack::Attacking;
+use crate::systems::bestiary::BestiaryProgress;
+use crate::systems::control::Controlled;
+use crate::systems::follow::FollowTarget;
+use crate::systems::growth::PawnFed;
+use crate::systems::pack_hunt::HuntPackAI;
+use crate::systems::pawn::{Pawn, PawnTarget, CurrentBehavior, Health, Endurance};
+use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+use crate::systems::spatial_grid::SpatialGrid;
+use crate::systems::keybinds::{Action, ActionInput};
+use crate::systems::replay::{PlayerCommand, PlayerCommands};
+
+/// How close (in tiles) the player has to be to feed a creature
+pub const FEED_REACH_TILES: f32 = 2.0;
+/// Endurance each feeding passes from the player to the creature. Stands in for a food
+/// item until there's an inventory to take one from.
+pub const FEED_ENDURANCE: f32 = 5.0;
+/// How close (in tiles) to its owner an attacker has to be for a tamed pawn to step in
+pub const DEFEND_RADIUS_TILES: f32 = 6.0;
+
+/// A request for `feeder` to feed `pawn`, sent by the feed key
+#[derive(Event, Debug, Clone)]
+pub struct FeedPawn {
+    pub feeder: Entity,
+    pub pawn: Entity,
+}
+
+/// How many times a tameable pawn has been fed on its way to being tamed
+#[derive(Component, Debug, Default)]
+pub struct Tameness {
+    pub feedings: u32,
+}
+
+/// The pawn that tamed this one
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Owner {
+    pub owner: Entity,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct FollowOwnerAI {
+    pub defending: Option<Entity>, // Attacker it's hunting down on its owner's behalf
+}
+
+/// The player feeds the closest tameable pawn within reach on F
+pub fn handle_feed_input(
+    actions: ActionInput,
+    mut player_commands: ResMut<PlayerCommands>,
+) {
+    if actions.just_pressed(Action::Feed) {
+        player_commands.give(PlayerCommand::Feed);
+    }
+}
+
+/// Carry out this tick's feed commands, sending a FeedPawn for the closest tameable pawn to
+/// each player
+pub fn apply_feed_commands(
+    player_commands: Res<PlayerCommands>,
+    config: Res<GameConfig>,
+    pawn_config: Res<PawnConfig>,
+    mut feed_events: EventWriter<FeedPawn>,
+    pawn_query: Query<(Entity, &Transform, &Pawn, &Health), Without<Owner>>,
+) {
+    if !player_commands.this_tick.contains(&PlayerCommand::Feed) {
+        return;
+    }
+
+    let reach = FEED_REACH_TILES * config.tile_size;
+    for (player, player_transform, player_pawn, _) in pawn_query.iter() {
+        if player_pawn.pawn_type != "player" {
+            continue;
+        }
+        let closest = pawn_query
+            .iter()
+            .filter(|(entity, _, pawn, health)| {
+                *entity != player
+                    && health.current > 0.0
+                    && pawn_config.get_pawn_definition(&pawn.pawn_type).is_some_and(|def| def.tame.is_some())
+            })
+            .map(|(entity, transform, _, _)| (entity, player_transform.translation.distance(transform.translation)))
+            .filter(|(_, distance)| *distance <= reach)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        match closest {
+            Some((pawn, _)) => {
+                feed_events.send(FeedPawn { feeder: player, pawn });
+            }
+            None => println!("Nothing tameable within reach to feed"),
+        }
+    }
+}
+
+/// Feeding passes some of the feeder's endurance to the pawn; once it has been fed enough
+/// times it's tamed and starts following the feeder around
+pub fn feeding_system(
+    pawn_config: Res<PawnConfig>,
+    mut bestiary: Option<ResMut<BestiaryProgress>>,
+    mut feed_events: EventReader<FeedPawn>,
+    mut fed_events: EventWriter<PawnFed>,
+    mut commands: Commands,
+    mut pawn_query: Query<(&Pawn, &mut Endurance, &mut CurrentBehavior, Option<&mut Tameness>, Has<Owner>)>,
+) {
+    for event in feed_events.read() {
+        let feeder_type = match pawn_query.get_mut(event.feeder) {
+            Ok((feeder_pawn, mut feeder_endurance, _, _, _)) => {
+                if feeder_endurance.current < FEED_ENDURANCE {
+                    println!("{} has no food to spare", feeder_pawn.pawn_type);
+                    continue;
+                }
+                feeder_endurance.current -= FEED_ENDURANCE;
+                feeder_pawn.pawn_type.clone()
+            }
+            Err(_) => continue,
+        };
+
+        let (pawn, mut endurance, mut current_behavior, tameness, owned) = match pawn_query.get_mut(event.pawn) {
+            Ok(pawn) => pawn,
+            Err(_) => continue,
+        };
+        let tame_config = match pawn_config.get_pawn_definition(&pawn.pawn_type).and_then(|def| def.tame.as_ref()) {
+            Some(tame_config) => tame_config,
+            None => continue,
+        };
+        endurance.current = (endurance.current + FEED_ENDURANCE).min(endurance.max);
+        fed_events.send(PawnFed { pawn: event.pawn });
+        if owned {
+            continue; // Already tamed - just a treat
+        }
+
+        let feedings = tameness.map_or(0, |tameness| tameness.feedings) + 1;
+        if feedings < tame_config.feedings {
+            println!("{} fed {} ({}/{})", feeder_type, pawn.pawn_type, feedings, tame_config.feedings);
+            commands.entity(event.pawn).insert(Tameness { feedings });
+            continue;
+        }
+
+        println!("{} tamed {}", feeder_type, pawn.pawn_type);
+        if feeder_type == "player" {
+            if let Some(bestiary) = bestiary.as_mut() {
+                bestiary.record_tame(&pawn.pawn_type);
+            }
+        }
+        let follow = BehaviourConfig::Simple(BehaviourType::Follow);
+        current_behavior.state = follow.name().to_string();
+        commands.entity(event.pawn)
+            .remove::<(Tameness, PawnTarget, PathfindingRequest, Attacking)>()
+            .insert((
+                Owner { owner: event.feeder },
+                FollowOwnerAI::default(),
+                FollowTarget { leader: event.feeder },
+                ActiveBehaviour { action: follow },
+            ));
+    }
+}
+
+/// Tamed pawns follow their owner around (see `follow_ai_system`) and turn on anyone who
+/// attacks it, hunting the attacker down before coming back to heel. If the owner dies
+/// they go back to the wild.
+pub fn follow_owner_system(
+    config: Res<GameConfig>,
+    spatial_grid: Res<SpatialGrid>,
+    mut commands: Commands,
+    mut follower_query: Query<(Entity, &Pawn, &Owner, &mut FollowOwnerAI, &mut CurrentBehavior, Option<&HuntSoloAI>), Without<Controlled>>,
+    owner_query: Query<(&Transform, &Health)>,
+    attacker_query: Query<(Option<&HuntSoloAI>, Option<&HuntPackAI>, Option<&Attacking>), Without<Owner>>,
+) {
+    for (entity, pawn, owner, mut follow_ai, mut current_behavior, hunt_ai) in follower_query.iter_mut() {
+        let owner_pos = match owner_query.get(owner.owner) {
+            Ok((owner_transform, owner_health)) if owner_health.current > 0.0 => owner_transform.translation.truncate(),
+            _ => {
+                println!("{} lost its owner and went back to the wild", pawn.pawn_type);
+                current_behavior.state = "idle".to_string();
+                commands.entity(entity).remove::<(Owner, FollowOwnerAI, FollowTarget, ActiveBehaviour)>();
+                continue;
+            }
+        };
+
+        if let Some(attacker) = follow_ai.defending {
+            if hunt_ai.is_some_and(|hunt_ai| hunt_ai.target_entity == Some(attacker)) {
+                continue; // Still seeing the attacker off
+            }
+            // The attacker is dead or got away
+            follow_ai.defending = None;
+            let follow = BehaviourConfig::Simple(BehaviourType::Follow);
+            current_behavior.state = follow.name().to_string();
+            commands.entity(entity).insert(ActiveBehaviour { action: follow });
+        }
+
+        // Anyone going for the owner gets hunted down
+        let attacker = spatial_grid.nearest(owner_pos, DEFEND_RADIUS_TILES * config.tile_size, |other, _| {
+            other != entity && attacker_query.get(other).is_ok_and(|(solo, pack, attacking)| {
+                solo.is_some_and(|solo| solo.target_entity == Some(owner.owner))
+                    || pack.is_some_and(|pack| pack.target_entity == Some(owner.owner))
+                    || attacking.is_some_and(|attacking| attacking.target == owner.owner)
+            })
+        });
+        if let Some((attacker, _)) = attacker {
+            println!("{} leaps to defend its owner", pawn.pawn_type);
+            follow_ai.defending = Some(attacker);
+            let defend = BehaviourConfig::Simple(BehaviourType::HuntSolo);
+            current_behavior.state = defend.name().to_string();
+            commands.entity(entity).insert((
+                ActiveBehaviour { action: defend },
+                HuntSoloAI { target_entity: Some(attacker), ..HuntSoloAI::new() },
+            ));
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::sprite::{Material2d, Material2dPlugin, MeshMaterial2d};
+use noise::{NoiseFn, Perlin};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use crate::resources::GameConfig;
+use crate::systems::culling::OverlayChunks;
+use crate::systems::game_state::{GameSet, GameState};
+use crate::systems::save::WorldMetadata;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, TerrainType, GroundConfigs, update_terrain_visuals};
+use crate::systems::world_clock::WorldClock;
+
+/// Tiles in from the edge of a patch of ground at which its effect is drawn deepest
+const DEEP_TILES: f32 = 6.0;
+/// Steps depth is rounded to, so tiles about as deep share a material
+const DEPTH_LEVELS: f32 = 8.0;
+/// How far noise can push a tile's depth either way, so deep water isn't drawn in neat rings
+const DEPTH_NOISE: f32 = 0.3;
+const DEPTH_NOISE_SCALE: f64 = 0.2;
+
+/// Bits of TerrainEffectMaterial::edge_mask, one for each neighbour that's a different ground
+pub const EDGE_N: u32 = 1;
+pub const EDGE_NE: u32 = 2;
+pub const EDGE_E: u32 = 4;
+pub const EDGE_SE: u32 = 8;
+pub const EDGE_S: u32 = 16;
+pub const EDGE_SW: u32 = 32;
+pub const EDGE_W: u32 = 64;
+pub const EDGE_NW: u32 = 128;
+
+const NEIGHBOURS: [((i32, i32), u32); 8] = [
+    ((0, 1), EDGE_N),
+    ((1, 1), EDGE_NE),
+    ((1, 0), EDGE_E),
+    ((1, -1), EDGE_SE),
+    ((0, -1), EDGE_S),
+    ((-1, -1), EDGE_SW),
+    ((-1, 0), EDGE_W),
+    ((-1, 1), EDGE_NW),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerrainEffectKind {
+    Water, // Shimmering waves, foam at the shore and darker deep water
+    Lava,  // A glowing flow that pulses, with a cooler crust at its edges
+    Sway,  // Gusts of wind rolling across, as over grass
+}
+
+impl TerrainEffectKind {
+    /// What the shader branches on
+    fn shader_id(self) -> u32 {
+        match self {
+            TerrainEffectKind::Water => 0,
+            TerrainEffectKind::Lava => 1,
+            TerrainEffectKind::Sway => 2,
+        }
+    }
+
+    /// The tint drawn when a ground leaves out `color` - the deep water colour for water
+    fn default_color(self) -> [u8; 3] {
+        match self {
+            TerrainEffectKind::Water => [0, 8, 31],
+            TerrainEffectKind::Lava => [255, 90, 13],
+            TerrainEffectKind::Sway => [230, 255, 153],
+        }
+    }
+}
+
+/// An animated overlay drawn over a ground, from its `effect` entry in grounds.yaml
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TerrainEffect {
+    pub kind: TerrainEffectKind,
+    #[serde(default = "default_effect_speed")]
+    pub speed: f32, // How fast it animates, as a multiple of the usual
+    #[serde(default = "default_effect_strength")]
+    pub strength: f32, // How strongly it's drawn over the ground, 0 to 1
+    #[serde(default)]
+    pub color: Option<[u8; 3]>, // RGB it's tinted with, each kind having its own if left out
+}
+
+fn default_effect_speed() -> f32 {
+    1.0
+}
+
+fn default_effect_strength() -> f32 {
+    1.0
+}
+
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct TerrainEffectMaterial {
+    #[uniform(0)]
+    pub time: f32,
+    #[uniform(0)]
+    pub daylight: f32, // WorldClock::daylight, dimming the effect to moonlight at night
+    #[uniform(0)]
+    pub edge_mask: u32, // Which neighbours are a different ground, e.g. foaming water next to land - see EDGE_N
+    #[uniform(0)]
+    pub depth: f32, // 0 at the edge of the patch to 1 well inside it, where water is drawn darkest
+    #[uniform(0)]
+    pub kind: u32, // TerrainEffectKind::shader_id
+    #[uniform(0)]
+    pub speed: f32,
+    #[uniform(0)]
+    pub strength: f32,
+    #[uniform(0)]
+    pub color: Vec4,
+}
+
+impl TerrainEffectMaterial {
+    fn new(effect: &TerrainEffect, edge_mask: u32, depth_level: u32) -> Self {
+        let [r, g, b] = effect.color.unwrap_or_else(|| effect.kind.default_color());
+        Self {
+            time: 0.0,
+            daylight: 1.0,
+            edge_mask,
+            depth: depth_level as f32 / (DEPTH_LEVELS - 1.0),
+            kind: effect.kind.shader_id(),
+            speed: effect.speed,
+            strength: effect.strength,
+            color: Vec4::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0),
+        }
+    }
+}
+
+impl Material2d for TerrainEffectMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/terrain_effect.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> bevy::sprite::AlphaMode2d {
+        bevy::sprite::AlphaMode2d::Blend
+    }
+}
+
+pub struct TerrainEffectPlugin;
+
+impl Plugin for TerrainEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<TerrainEffectMaterial>::default())
+            .add_systems(Update, update_effect_time)
+            .add_systems(Update, update_terrain_effects.before(update_terrain_visuals).in_set(GameSet::World))
+            .add_systems(OnExit(GameState::MainMenu), spawn_terrain_effects.after(crate::systems::world_gen::generate_world));
+    }
+}
+
+/// Effects follow the virtual clock so they freeze while paused and speed up in fast-forward,
+/// unless settings ask for purely cosmetic real-time animation. They darken with the time of day.
+fn update_effect_time(
+    config: Res<GameConfig>,
+    virtual_time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+    world_clock: Res<WorldClock>,
+    mut materials: ResMut<Assets<TerrainEffectMaterial>>,
+) {
+    let elapsed = if config.water_animation_real_time {
+        real_time.elapsed_secs()
+    } else {
+        virtual_time.elapsed_secs()
+    };
+
+    for (_, material) in materials.iter_mut() {
+        material.time = elapsed;
+        material.daylight = world_clock.daylight();
+    }
+}
+
+/// Which of a tile's eight neighbours are a different ground. Off the edge of the map counts as
+/// the same, so there's no foam along it.
+pub fn edge_mask(terrain_map: &TerrainMap, x: u32, y: u32) -> u32 {
+    let terrain_type = terrain_map.tiles[x as usize][y as usize];
+    let mut mask = 0;
+    for ((dx, dy), bit) in NEIGHBOURS {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        if nx < 0 || ny < 0 || nx >= terrain_map.width as i32 || ny >= terrain_map.height as i32 {
+            continue;
+        }
+        if terrain_map.tiles[nx as usize][ny as usize] != terrain_type {
+            mask |= bit;
+        }
+    }
+    mask
+}
+
+/// Steps from each tile from `min` up to `max` to the nearest one that isn't `terrain_type`,
+/// diagonals included: 0 off it, 1 along its edge and so on in - for water, the distance to the
+/// shore. Indexed from `min`, and only looking for other grounds inside the window, so it's right
+/// for any tile whose nearest is at least as close as the window's edge; tiles with none in it
+/// are u32::MAX.
+pub fn edge_distances_within(terrain_map: &TerrainMap, terrain_type: TerrainType, min: (u32, u32), max: (u32, u32)) -> Vec<Vec<u32>> {
+    let (width, height) = ((max.0 - min.0) as usize, (max.1 - min.1) as usize);
+    let mut distances = vec![vec![u32::MAX; height]; width];
+    let mut queue = VecDeque::new();
+    for (x, column) in terrain_map.tiles[min.0 as usize..max.0 as usize].iter().enumerate() {
+        for (y, &tile) in column[min.1 as usize..max.1 as usize].iter().enumerate() {
+            if tile != terrain_type {
+                distances[x][y] = 0;
+                queue.push_back((x, y));
+            }
+        }
+    }
+    while let Some((x, y)) = queue.pop_front() {
+        let next = distances[x][y] + 1;
+        for ((dx, dy), _) in NEIGHBOURS {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                continue;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            if distances[nx][ny] > next {
+                distances[nx][ny] = next;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    distances
+}
+
+/// How deep into its patch a tile is drawn, from its distance to the edge roughened by noise in
+/// [-1, 1], as one of DEPTH_LEVELS steps from 0 to DEPTH_LEVELS - 1
+pub fn depth_level(edge_distance: u32, noise: f32) -> u32 {
+    let from_edge = (edge_distance.saturating_sub(1) as f32 / DEEP_TILES).min(1.0);
+    let depth = (from_edge * (1.0 + DEPTH_NOISE * noise.clamp(-1.0, 1.0))).clamp(0.0, 1.0);
+    (depth * (DEPTH_LEVELS - 1.0)).round() as u32
+}
+
+#[derive(Component)]
+pub struct TerrainEffectTile;
+
+/// What a tile's overlay is drawn with: its ground, edge mask and depth level
+pub type EffectKey = (TerrainType, u32, u32);
+
+/// The overlay on every tile of a ground with an effect, drawn with a material for its edges
+/// and depth. Tiles that look alike share one.
+#[derive(Resource)]
+pub struct TerrainEffectOverlays {
+    pub noise: Perlin,
+    pub mesh: Handle<Mesh>,
+    pub materials: HashMap<EffectKey, Handle<TerrainEffectMaterial>>,
+    pub tiles: HashMap<(u32, u32), (Entity, EffectKey)>, // Overlay on each tile, and its material's key
+    pub chunks: OverlayChunks,
+}
+
+impl TerrainEffectOverlays {
+    pub fn new(seed: u32, mesh: Handle<Mesh>) -> Self {
+        Self {
+            noise: Perlin::new(seed),
+            mesh,
+            materials: HashMap::new(),
+            tiles: HashMap::new(),
+            chunks: OverlayChunks::default(),
+        }
+    }
+
+    /// Give every tile from `min` up to `max` whose ground has an effect an overlay drawn for how
+    /// it sits now, looking for the edges of its patch no further out than `window_min` and
+    /// `window_max`
+    fn refresh(
+        &mut self,
+        commands: &mut Commands,
+        terrain_map: &TerrainMap,
+        ground_configs: &GroundConfigs,
+        materials: &mut Assets<TerrainEffectMaterial>,
+        overlay_query: &mut Query<&mut MeshMaterial2d<TerrainEffectMaterial>>,
+        (min, max): ((u32, u32), (u32, u32)),
+        (window_min, window_max): ((u32, u32), (u32, u32)),
+    ) {
+        let mut distances: HashMap<TerrainType, Vec<Vec<u32>>> = HashMap::new();
+        for x in min.0..max.0 {
+            for y in min.1..max.1 {
+                let terrain_type = terrain_map.tiles[x as usize][y as usize];
+                let effect = match ground_configs.effect(terrain_type) {
+                    Some(effect) => effect,
+                    None => continue,
+                };
+                let edge_distance = distances
+                    .entry(terrain_type)
+                    .or_insert_with(|| edge_distances_within(terrain_map, terrain_type, window_min, window_max))
+                    [(x - window_min.0) as usize][(y - window_min.1) as usize];
+                let noise = self.noise.get([x as f64 * DEPTH_NOISE_SCALE, y as f64 * DEPTH_NOISE_SCALE]) as f32;
+                let key = (terrain_type, edge_mask(terrain_map, x, y), depth_level(edge_distance, noise));
+
+                match self.tiles.get(&(x, y)).copied() {
+                    Some((_, old_key)) if old_key == key => {}
+                    Some((entity, _)) => {
+                        let material = self.material(key, effect, materials);
+                        if let Ok(mut handle) = overlay_query.get_mut(entity) {
+                            handle.0 = material;
+                        }
+                        self.tiles.insert((x, y), (entity, key));
+                    }
+                    None => {
+                        let (world_x, world_y) = terrain_map.tile_to_world_coords(x as i32, y as i32);
+                        let chunk = self.chunks.chunk_for(commands, terrain_map, x, y);
+                        let entity = commands.spawn((
+                            Mesh2d::from(self.mesh.clone()),
+                            MeshMaterial2d(self.material(key, effect, materials)),
+                            Transform::from_translation(Vec3::new(world_x, world_y, 1.0)),
+                            TerrainEffectTile,
+                        )).set_parent(chunk).id();
+                        self.tiles.insert((x, y), (entity, key));
+                    }
+                }
+            }
+        }
+    }
+
+    fn material(&mut self, key: EffectKey, effect: &TerrainEffect, materials: &mut Assets<TerrainEffectMaterial>) -> Handle<TerrainEffectMaterial> {
+        self.materials
+            .entry(key)
+            .or_insert_with(|| materials.add(TerrainEffectMaterial::new(effect, key.1, key.2)))
+            .clone()
+    }
+}
+
+pub fn spawn_terrain_effects(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    metadata: Res<WorldMetadata>,
+    mut materials: ResMut<Assets<TerrainEffectMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut overlay_query: Query<&mut MeshMaterial2d<TerrainEffectMaterial>>,
+) {
+    let mesh = meshes.add(Rectangle::new(terrain_map.tile_size, terrain_map.tile_size));
+    let mut overlays = TerrainEffectOverlays::new(metadata.seed, mesh);
+    let whole_map = ((0, 0), (terrain_map.width, terrain_map.height));
+    overlays.refresh(&mut commands, &terrain_map, &ground_configs, &mut materials, &mut overlay_query, whole_map, whole_map);
+    commands.insert_resource(overlays);
+}
+
+/// When ground is dug or filled in, take overlays off tiles that changed ground and redraw the
+/// effects around them. Runs before the terrain changes are cleared.
+pub fn update_terrain_effects(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    terrain_changes: Res<TerrainChanges>,
+    ground_configs: Res<GroundConfigs>,
+    overlays: Option<ResMut<TerrainEffectOverlays>>,
+    mut materials: ResMut<Assets<TerrainEffectMaterial>>,
+    mut overlay_query: Query<&mut MeshMaterial2d<TerrainEffectMaterial>>,
+) {
+    let mut overlays = match overlays {
+        Some(overlays) => overlays,
+        None => return,
+    };
+
+    // Only a change to or from a ground with an effect can change how one is drawn...
+    let mut edited = Vec::new();
+    for &(x, y, terrain_type) in &terrain_changes.changed_tiles {
+        let drawn_as = overlays.tiles.get(&(x, y)).map(|&(_, key)| key.0);
+        let wanted = ground_configs.effect(terrain_type).map(|_| terrain_type);
+        if drawn_as == wanted {
+            continue;
+        }
+        if let Some((entity, _)) = overlays.tiles.remove(&(x, y)) {
+            commands.entity(entity).despawn_recursive(); // Taking it out of its chunk
+        }
+        edited.push((x, y));
+    }
+    if edited.is_empty() {
+        return;
+    }
+
+    // ...and only out to DEEP_TILES around it. Distance to the edge of a patch only matters up to
+    // there, so other grounds twice as far out from the edits are all that's looked at.
+    let reach = DEEP_TILES as u32 + 1;
+    let (mut min, mut max) = ((u32::MAX, u32::MAX), (0, 0));
+    for &(x, y) in &edited {
+        min = (min.0.min(x.saturating_sub(reach)), min.1.min(y.saturating_sub(reach)));
+        max = (max.0.max((x + reach + 1).min(terrain_map.width)), max.1.max((y + reach + 1).min(terrain_map.height)));
+    }
+    let window_min = (min.0.saturating_sub(reach), min.1.saturating_sub(reach));
+    let window_max = ((max.0 + reach).min(terrain_map.width), (max.1 + reach).min(terrain_map.height));
+    overlays.refresh(&mut commands, &terrain_map, &ground_configs, &mut materials, &mut overlay_query, (min, max), (window_min, window_max));
+}
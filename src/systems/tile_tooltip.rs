@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::infusion::{TerrainInfluence, INFUSION_THRESHOLD};
+use crate::systems::input::{tile_center, world_to_tile};
+use crate::systems::pawn::Pawn;
+use crate::systems::selection::cursor_world_position;
+use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+
+/// Seconds the cursor has to rest on a tile before its tooltip shows
+pub const TOOLTIP_DELAY_SECS: f32 = 0.5;
+/// Just above the ground, under dens, items and pawns
+const TILE_HIGHLIGHT_Z: f32 = 5.0;
+/// How far (in pixels) the tooltip sits from the cursor, so it doesn't cover the tile
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(16.0, 16.0);
+
+/// The tile under the cursor and how long it's been there
+#[derive(Resource, Default)]
+pub struct TileHover {
+    pub tile: Option<(i32, i32)>,
+    pub hovered_secs: f32,
+}
+
+/// Square drawn over the tile under the cursor
+#[derive(Component)]
+pub struct TileHighlight;
+
+#[derive(Component)]
+pub struct TileTooltip;
+
+/// What the tooltip says about a tile: its ground, whether pawns can cross it, what crossing it
+/// costs a path, who's standing on it and how far along any infusion is
+pub fn describe_tile(
+    tile: (i32, i32),
+    terrain_map: &TerrainMap,
+    ground_configs: &GroundConfigs,
+    influence: &TerrainInfluence,
+    occupants: &[&str],
+) -> String {
+    let terrain_type = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+    let ground = ground_configs.name_of(terrain_type).unwrap_or("unknown");
+    let (passable, move_cost) = if ground_configs.is_passable(terrain_type) {
+        // Paths cost the same on every passable ground, more for diagonal steps
+        ("yes", "1 (1.4 diagonally)")
+    } else if ground_configs.is_swimmable(terrain_type) {
+        ("swimmers only", "1 (1.4 diagonally) for swimmers")
+    } else {
+        ("no", "-")
+    };
+    let pawns = if occupants.is_empty() { "none".to_string() } else { occupants.join(", ") };
+    let mut description = format!(
+        "Tile ({}, {})\nGround: {}\nPassable: {}\nMove cost: {}\nPawns: {}",
+        tile.0, tile.1, ground, passable, move_cost, pawns
+    );
+
+    // Sorted so the lines don't swap places between frames
+    let mut infusions: Vec<(&str, f32)> = ground_configs.terrain_mapping
+        .iter()
+        .map(|(name, &terrain)| (name.as_str(), influence.influence((tile.0 as u32, tile.1 as u32), terrain)))
+        .filter(|(_, amount)| *amount > 0.0)
+        .collect();
+    infusions.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, amount) in infusions {
+        description.push_str(&format!("\nInfusing: {} {:.0}%", name, amount / INFUSION_THRESHOLD * 100.0));
+    }
+    description
+}
+
+/// Keep track of the tile under the cursor and draw a highlight over it
+pub fn track_hovered_tile(
+    time: Res<Time<Real>>, // The tooltip keeps working while the game is paused
+    config: Res<GameConfig>,
+    terrain_map: Res<TerrainMap>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera>>,
+    mut hover: ResMut<TileHover>,
+    mut commands: Commands,
+    mut highlight_query: Query<(Entity, &mut Transform), With<TileHighlight>>,
+) {
+    let tile = cursor_world_position(&windows, &camera_query)
+        .map(|world_position| world_to_tile(world_position, &config))
+        .filter(|(x, y)| *x >= 0 && *y >= 0 && (*x as u32) < terrain_map.width && (*y as u32) < terrain_map.height);
+
+    if tile == hover.tile {
+        hover.hovered_secs += time.delta_secs();
+    } else {
+        hover.tile = tile;
+        hover.hovered_secs = 0.0;
+    }
+
+    match (tile, highlight_query.get_single_mut()) {
+        (Some(tile), Ok((_, mut transform))) => {
+            transform.translation = tile_center(tile, &config).extend(TILE_HIGHLIGHT_Z);
+        }
+        (Some(tile), Err(_)) => {
+            commands.spawn((
+                Sprite::from_color(Color::srgba(1.0, 1.0, 1.0, 0.15), Vec2::splat(config.tile_size)),
+                Transform::from_translation(tile_center(tile, &config).extend(TILE_HIGHLIGHT_Z)),
+                TileHighlight,
+            ));
+        }
+        (None, _) => {
+            for (highlight, _) in highlight_query.iter() {
+                commands.entity(highlight).despawn();
+            }
+        }
+    }
+}
+
+/// Once the cursor has rested on a tile for a moment, show what's there next to it
+pub fn update_tile_tooltip(
+    config: Res<GameConfig>,
+    hover: Res<TileHover>,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    influence: Res<TerrainInfluence>,
+    windows: Query<&Window>,
+    pawn_query: Query<(&Transform, &Pawn)>,
+    mut commands: Commands,
+    mut tooltip_query: Query<(Entity, &mut Text, &mut Node), With<TileTooltip>>,
+) {
+    let cursor = windows.get_single().ok().and_then(|window| window.cursor_position());
+    let shown = match (hover.tile, cursor) {
+        (Some(tile), Some(cursor)) if hover.hovered_secs >= TOOLTIP_DELAY_SECS => Some((tile, cursor)),
+        _ => None,
+    };
+    let (tile, cursor) = match shown {
+        Some(shown) => shown,
+        None => {
+            for (tooltip, _, _) in tooltip_query.iter() {
+                commands.entity(tooltip).despawn();
+            }
+            return;
+        }
+    };
+
+    let occupants: Vec<&str> = pawn_query
+        .iter()
+        .filter(|(transform, _)| world_to_tile(transform.translation.truncate(), &config) == tile)
+        .map(|(_, pawn)| pawn.pawn_type.as_str())
+        .collect();
+    let description = describe_tile(tile, &terrain_map, &ground_configs, &influence, &occupants);
+    let position = cursor + TOOLTIP_OFFSET;
+
+    match tooltip_query.get_single_mut() {
+        Ok((_, mut text, mut node)) => {
+            if text.0 != description {
+                text.0 = description;
+            }
+            node.left = Val::Px(position.x);
+            node.top = Val::Px(position.y);
+        }
+        Err(_) => {
+            commands.spawn((
+                Text::new(description),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(position.x),
+                    top: Val::Px(position.y),
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.85)),
+                TileTooltip,
+            ));
+        }
+    }
+}
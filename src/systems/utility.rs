@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use crate::resources::GameConfig;
+use crate::systems::ai::ActiveBehaviour;
+use crate::systems::needs::{Thirst, Energy};
+use crate::systems::pawn::{Pawn, CurrentBehavior, Health, Endurance};
+use crate::systems::pawn_config::{PawnConfig, UtilityConfig, UtilityInput};
+use crate::systems::perception::Threatened;
+use crate::systems::world_clock::WorldClock;
+
+/// Everything a utility-driven pawn's considerations can look at, each from 0 to 1
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UtilityInputs {
+    pub hunger: f32,
+    pub thirst: f32,
+    pub tiredness: f32,
+    pub threat: f32,
+    pub health: f32,
+    pub rest_time: f32,
+    pub darkness: f32,
+}
+
+impl UtilityInputs {
+    pub fn get(&self, input: UtilityInput) -> f32 {
+        match input {
+            UtilityInput::Hunger => self.hunger,
+            UtilityInput::Thirst => self.thirst,
+            UtilityInput::Tiredness => self.tiredness,
+            UtilityInput::Threat => self.threat,
+            UtilityInput::Health => self.health,
+            UtilityInput::RestTime => self.rest_time,
+            UtilityInput::Darkness => self.darkness,
+        }
+    }
+}
+
+/// The state with the best score, counting the current state's hysteresis bonus.
+/// Ties go to the option listed first.
+pub fn best_utility_state<'a>(utility: &'a UtilityConfig, inputs: &UtilityInputs, current_state: &str) -> Option<(&'a str, f32)> {
+    let mut best: Option<(&str, f32)> = None;
+    for option in &utility.options {
+        let mut score = option.score(|input| inputs.get(input));
+        if option.state == current_state {
+            score += utility.hysteresis;
+        }
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((&option.state, score));
+        }
+    }
+    best
+}
+
+/// State-driven pawns with a `utility` entry score each of their options from its curves and
+/// switch to the best one. Player-controlled pawns are left alone.
+pub fn utility_behaviour_system(
+    pawn_config: Res<PawnConfig>,
+    config: Res<GameConfig>,
+    world_clock: Option<Res<WorldClock>>,
+    mut pawn_query: Query<(
+        &Transform, &Pawn, &Health, &Endurance, Option<&Thirst>, Option<&Energy>, Option<&Threatened>, &mut CurrentBehavior,
+    ), Without<ActiveBehaviour>>, // Behaviour trees switch on their own conditions
+    predator_query: Query<&Transform, With<Pawn>>,
+) {
+    for (transform, pawn, health, endurance, thirst, energy, threatened, mut current_behavior) in pawn_query.iter_mut() {
+        let definition = match pawn_config.get_pawn_definition(&pawn.pawn_type) {
+            Some(definition) => definition,
+            None => continue,
+        };
+        let utility = match &definition.utility {
+            Some(utility) => utility,
+            None => continue,
+        };
+        if current_behavior.state == "controlled" {
+            continue;
+        }
+
+        let threat = match (threatened, &definition.perception) {
+            (Some(threatened), Some(perception)) => match predator_query.get(threatened.predator) {
+                Ok(predator_transform) => {
+                    let distance = transform.translation.truncate().distance(predator_transform.translation.truncate());
+                    let detection_range = perception.detection_radius as f32 * config.tile_size;
+                    (1.0 - distance / detection_range).clamp(0.0, 1.0)
+                }
+                Err(_) => 0.0,
+            },
+            _ => 0.0,
+        };
+        let inputs = UtilityInputs {
+            hunger: 1.0 - endurance.current / endurance.max,
+            thirst: thirst.map_or(0.0, |thirst| 1.0 - thirst.current / thirst.max),
+            tiredness: energy.map_or(0.0, |energy| 1.0 - energy.current / energy.max),
+            threat,
+            health: health.current / health.max,
+            rest_time: if world_clock.as_ref().is_some_and(|clock| definition.schedule.rests_during(clock.phase())) { 1.0 } else { 0.0 },
+            darkness: world_clock.as_ref().map_or(0.0, |clock| 1.0 - clock.daylight()),
+        };
+
+        if let Some((state, score)) = best_utility_state(utility, &inputs, &current_behavior.state) {
+            if state != current_behavior.state {
+                println!("{} switching to {} behavior (utility {:.2})", pawn.pawn_type, state, score);
+                current_behavior.state = state.to_string();
+            }
+        }
+    }
+}
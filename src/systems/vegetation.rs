@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use crate::systems::seasons::Seasons;
+use crate::systems::sim_tick::{SimTick, SimulationTick, TickPeriod};
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, TerrainType, GroundConfigs};
+
+/// A vegetation tile that was eaten or cleared, waiting to grow back
+#[derive(Debug, Clone)]
+pub struct RegrowingTile {
+    pub tile: (u32, u32),
+    pub grows_back: TerrainType,
+    pub cleared_to: TerrainType,
+    pub regrow_tick: u64,
+}
+
+/// Tracks cleared vegetation (grounds with `regrow_secs` in grounds.yaml) until it grows back
+#[derive(Resource, Default)]
+pub struct VegetationGrowth {
+    pub tiles: Vec<RegrowingTile>,
+}
+
+impl VegetationGrowth {
+    /// Turn a tile into another ground, scheduling it to grow back if it was vegetation.
+    /// Returns false if the tile is out of bounds or already that ground.
+    pub fn clear_tile(
+        &mut self,
+        tile: (u32, u32),
+        cleared_to: TerrainType,
+        terrain_map: &mut TerrainMap,
+        terrain_changes: &mut TerrainChanges,
+        ground_configs: &GroundConfigs,
+        sim_tick: &SimTick,
+    ) -> bool {
+        if tile.0 >= terrain_map.width || tile.1 >= terrain_map.height {
+            return false;
+        }
+        let previous = terrain_map.tiles[tile.0 as usize][tile.1 as usize];
+        if previous == cleared_to {
+            return false;
+        }
+
+        terrain_map.set_tile(tile.0, tile.1, cleared_to);
+        terrain_changes.add_change(tile.0, tile.1, cleared_to);
+
+        // A tile cleared again before growing back keeps its original vegetation
+        if let Some(regrowing) = self.tiles.iter_mut().find(|regrowing| regrowing.tile == tile) {
+            regrowing.cleared_to = cleared_to;
+            return true;
+        }
+        if let Some(regrow_secs) = ground_configs.regrow_secs(previous) {
+            self.tiles.push(RegrowingTile {
+                tile,
+                grows_back: previous,
+                cleared_to,
+                regrow_tick: sim_tick.tick + regrow_secs * sim_tick.ticks_per(TickPeriod::Second),
+            });
+        }
+        true
+    }
+}
+
+/// Once a second of simulated time, grow back vegetation whose time has come.
+/// Tiles that have since turned into something else are left alone. The season speeds growth up
+/// or slows it down, bringing each tile's regrow tick closer or putting it off.
+pub fn vegetation_growth_system(
+    sim_tick: Res<SimTick>,
+    seasons: Option<Res<Seasons>>,
+    mut tick_events: EventReader<SimulationTick>,
+    mut vegetation: ResMut<VegetationGrowth>,
+    mut terrain_map: ResMut<TerrainMap>,
+    mut terrain_changes: ResMut<TerrainChanges>,
+) {
+    if !tick_events.read().any(|event| event.period == TickPeriod::Second) {
+        return;
+    }
+
+    let regrowth = seasons.map_or(1.0, |seasons| seasons.regrowth);
+    let shift = ((regrowth - 1.0) * sim_tick.ticks_per(TickPeriod::Second) as f32).round() as i64;
+    vegetation.tiles.retain_mut(|regrowing| {
+        regrowing.regrow_tick = (regrowing.regrow_tick as i64 - shift).max(0) as u64;
+        if regrowing.regrow_tick > sim_tick.tick {
+            return true;
+        }
+        let (x, y) = regrowing.tile;
+        if terrain_map.tiles[x as usize][y as usize] == regrowing.cleared_to {
+            terrain_map.set_tile(x, y, regrowing.grows_back);
+            terrain_changes.add_change(x, y, regrowing.grows_back);
+        }
+        false
+    });
+}
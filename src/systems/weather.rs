@@ -0,0 +1,352 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use crate::resources::GameConfig;
+use crate::systems::camera::CameraController;
+use crate::systems::culling::OverlayChunks;
+use crate::systems::perception::Vision;
+use crate::systems::sim_rng::SimRng;
+use crate::systems::sim_tick::{SimTick, SimulationTick, TickPeriod};
+use crate::systems::world_gen::{TerrainMap, TerrainChanges, GroundConfigs};
+
+/// Above pawns and items, under the night tint so rain darkens with everything else
+const PARTICLE_Z: f32 = 140.0;
+const FOG_Z: f32 = 145.0;
+/// Just over the ground, under props and water
+const SNOW_Z: f32 = 0.5;
+/// How white fully settled snow gets over the grass under it
+const SNOW_MAX_ALPHA: f32 = 0.85;
+/// How thick fog gets, and how fast it rolls in or lifts (opacity a second)
+const FOG_ALPHA: f32 = 0.45;
+const FOG_FADE_SPEED: f32 = 0.15;
+/// How far past the edge of the view the fog reaches, as with the night tint
+const FOG_MARGIN: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Rain,
+    Storm,
+    Snow,
+    Fog,
+}
+
+impl WeatherKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "Clear",
+            WeatherKind::Rain => "Rain",
+            WeatherKind::Storm => "Storm",
+            WeatherKind::Snow => "Snow",
+            WeatherKind::Fog => "Fog",
+        }
+    }
+
+    /// Rain and storms both turn grounds with a `rain_speed` to mud
+    pub fn is_wet(self) -> bool {
+        matches!(self, WeatherKind::Rain | WeatherKind::Storm)
+    }
+
+    /// How many particles fill the view, how fast they fall (world units a second), how big they
+    /// are and their colour - None for weather without any
+    fn particles(self) -> Option<(usize, Vec2, Vec2, Color)> {
+        match self {
+            WeatherKind::Rain => Some((150, Vec2::new(-40.0, -420.0), Vec2::new(1.0, 8.0), Color::srgba(0.6, 0.7, 0.9, 0.5))),
+            WeatherKind::Storm => Some((350, Vec2::new(-160.0, -600.0), Vec2::new(1.5, 10.0), Color::srgba(0.55, 0.6, 0.8, 0.6))),
+            WeatherKind::Snow => Some((160, Vec2::new(-15.0, -60.0), Vec2::new(3.0, 3.0), Color::srgba(1.0, 1.0, 1.0, 0.8))),
+            WeatherKind::Clear | WeatherKind::Fog => None,
+        }
+    }
+}
+
+/// How the weather behaves, from the `weather` section of settings.yaml
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WeatherSettings {
+    pub biome: String,         // Which of `biomes` the world's weather comes from
+    pub min_secs: u64,         // Simulated seconds a spell of weather lasts at the least
+    pub max_secs: u64,         // ...and at the most
+    pub fog_vision: f32,       // Vision range in fog, as a fraction of the usual
+    pub snow_cover_secs: u64,  // Simulated seconds of snow to bury snow_cover grounds, and to melt off them again
+    pub biomes: BTreeMap<String, BTreeMap<WeatherKind, u32>>, // How often each weather comes up, relative to the others
+}
+
+impl Default for WeatherSettings {
+    fn default() -> Self {
+        let biome = |frequencies: &[(WeatherKind, u32)]| frequencies.iter().copied().collect();
+        Self {
+            biome: "temperate".to_string(),
+            min_secs: 120,
+            max_secs: 600,
+            fog_vision: 0.5,
+            snow_cover_secs: 120,
+            biomes: BTreeMap::from([
+                ("temperate".to_string(), biome(&[(WeatherKind::Clear, 6), (WeatherKind::Rain, 3), (WeatherKind::Storm, 1), (WeatherKind::Fog, 2)])),
+                ("tundra".to_string(), biome(&[(WeatherKind::Clear, 4), (WeatherKind::Snow, 5), (WeatherKind::Storm, 1), (WeatherKind::Fog, 2)])),
+                ("arid".to_string(), biome(&[(WeatherKind::Clear, 12), (WeatherKind::Storm, 1)])),
+            ]),
+        }
+    }
+}
+
+impl WeatherSettings {
+    /// The next spell of weather, picked by the biome's frequencies - always clear for a biome
+    /// that isn't listed or has none
+    pub fn roll_weather(&self, rng: &mut impl Rng) -> WeatherKind {
+        let frequencies = match self.biomes.get(&self.biome) {
+            Some(frequencies) => frequencies,
+            None => return WeatherKind::Clear,
+        };
+        let total: u32 = frequencies.values().sum();
+        if total == 0 {
+            return WeatherKind::Clear;
+        }
+        let mut roll = rng.gen_range(0..total);
+        for (&kind, &frequency) in frequencies {
+            if roll < frequency {
+                return kind;
+            }
+            roll -= frequency;
+        }
+        WeatherKind::Clear
+    }
+
+    /// Simulated seconds the next spell of weather lasts
+    pub fn roll_duration(&self, rng: &mut impl Rng) -> u64 {
+        rng.gen_range(self.min_secs..=self.max_secs.max(self.min_secs))
+    }
+}
+
+/// The weather now, when it next changes and how much snow is lying
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    pub changes_at: u64, // Tick the next spell is rolled on - 0 rolls one on a world's first second
+    pub snow_cover: f32, // 0 for bare ground to 1 for snow_cover grounds buried white
+}
+
+impl Weather {
+    /// Speed for a pawn on this ground as a multiple of the usual: the ground's `rain_speed` in
+    /// rain or storms, and unchanged otherwise
+    pub fn speed_multiplier(&self, terrain_type: usize, ground_configs: &GroundConfigs) -> f32 {
+        if self.kind.is_wet() {
+            ground_configs.rain_speed(terrain_type)
+        } else {
+            1.0
+        }
+    }
+
+    /// Vision range as a multiple of the usual
+    pub fn vision_multiplier(&self, settings: &WeatherSettings) -> f32 {
+        if self.kind == WeatherKind::Fog {
+            settings.fog_vision.clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Once a second of simulated time: roll new weather when the spell runs out, and let snow
+/// settle while it snows and melt while it doesn't
+pub fn weather_system(
+    config: Res<GameConfig>,
+    sim_tick: Res<SimTick>,
+    mut tick_events: EventReader<SimulationTick>,
+    mut weather: ResMut<Weather>,
+    mut rng: ResMut<SimRng>,
+) {
+    if !tick_events.read().any(|event| event.period == TickPeriod::Second) {
+        return;
+    }
+
+    if sim_tick.tick >= weather.changes_at {
+        let kind = config.weather.roll_weather(&mut *rng);
+        let secs = config.weather.roll_duration(&mut *rng);
+        if kind != weather.kind {
+            println!("The weather turns to {} for {} seconds", kind.label().to_lowercase(), secs);
+        }
+        weather.kind = kind;
+        weather.changes_at = sim_tick.tick + secs.max(1) * sim_tick.ticks_per(TickPeriod::Second);
+    }
+
+    let step = 1.0 / config.weather.snow_cover_secs.max(1) as f32;
+    let cover = if weather.kind == WeatherKind::Snow {
+        weather.snow_cover + step
+    } else {
+        weather.snow_cover - step
+    };
+    weather.snow_cover = cover.clamp(0.0, 1.0);
+}
+
+/// Pawns see as far as the weather lets them
+pub fn apply_weather_to_vision(
+    config: Res<GameConfig>,
+    weather: Res<Weather>,
+    mut vision_query: Query<&mut Vision>,
+) {
+    let multiplier = weather.vision_multiplier(&config.weather);
+    for mut vision in vision_query.iter_mut() {
+        vision.range = vision.base_range * multiplier;
+    }
+}
+
+/// A raindrop or snowflake, somewhere in the view
+#[derive(Component)]
+pub struct WeatherParticle {
+    pub offset: Vec2, // Where in the view it is, from (0, 0) at the bottom left to (1, 1) at the top right
+}
+
+/// Grey haze over the view while it's foggy
+#[derive(Component)]
+pub struct FogHaze;
+
+/// Fill the view with falling rain or snow for the weather, and roll fog in over it. Particles
+/// are spread evenly through the view rather than at random, so drawing them takes nothing from
+/// the simulation's randomness.
+pub fn update_weather_overlays(
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut commands: Commands,
+    mut shown: Local<Option<WeatherKind>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), (With<CameraController>, Without<WeatherParticle>, Without<FogHaze>)>,
+    mut particle_query: Query<(Entity, &mut Transform, &mut Sprite, &mut WeatherParticle), Without<FogHaze>>,
+    mut fog_query: Query<(&mut Transform, &mut Sprite), (With<FogHaze>, Without<WeatherParticle>)>,
+) {
+    let (camera_transform, projection) = match camera_query.get_single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    let view = projection.area.size();
+    let camera_position = camera_transform.translation.truncate();
+    let particles = weather.kind.particles();
+
+    if *shown != Some(weather.kind) {
+        *shown = Some(weather.kind);
+        for (entity, ..) in particle_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        if let Some((count, _, size, color)) = particles {
+            for index in 0..count {
+                // Golden ratio steps scatter the particles evenly without bunching
+                let offset = Vec2::new((index as f32 * 0.618_034).fract(), (index as f32 * 0.754_878).fract());
+                commands.spawn((
+                    Sprite::from_color(color, size),
+                    Transform::from_translation((camera_position + (offset - 0.5) * view).extend(PARTICLE_Z)),
+                    WeatherParticle { offset },
+                ));
+            }
+        }
+    } else if let Some((_, velocity, size, _)) = particles {
+        let step = velocity * time.delta_secs() / view.max(Vec2::ONE);
+        for (_, mut transform, mut sprite, mut particle) in particle_query.iter_mut() {
+            particle.offset = (particle.offset + step).rem_euclid(Vec2::ONE);
+            transform.translation = (camera_position + (particle.offset - 0.5) * view).extend(PARTICLE_Z);
+            // The same size on screen however far the camera is zoomed out
+            sprite.custom_size = Some(size * projection.scale);
+        }
+    }
+
+    let target = if weather.kind == WeatherKind::Fog { FOG_ALPHA } else { 0.0 };
+    let translation = camera_position.extend(FOG_Z);
+    match fog_query.get_single_mut() {
+        Ok((mut transform, mut sprite)) => {
+            let alpha = sprite.color.alpha();
+            let fade = FOG_FADE_SPEED * time.delta_secs();
+            sprite.color.set_alpha(alpha + (target - alpha).clamp(-fade, fade));
+            sprite.custom_size = Some(view * FOG_MARGIN);
+            transform.translation = translation;
+        }
+        Err(_) => {
+            commands.spawn((
+                Sprite::from_color(Color::srgba(0.75, 0.78, 0.8, 0.0), view * FOG_MARGIN),
+                Transform::from_translation(translation),
+                FogHaze,
+            ));
+        }
+    }
+}
+
+/// The white layer snow settles into over grounds with `snow_cover`, one tile at a time, all
+/// sharing a material so the whole layer thickens together
+#[derive(Resource)]
+pub struct SnowCover {
+    pub material: Handle<ColorMaterial>,
+    pub mesh: Handle<Mesh>,
+    pub tiles: HashMap<(u32, u32), Entity>,
+    pub chunks: OverlayChunks,
+}
+
+fn spawn_snow_tile(commands: &mut Commands, snow: &mut SnowCover, terrain_map: &TerrainMap, x: u32, y: u32) -> Entity {
+    let (world_x, world_y) = terrain_map.tile_to_world_coords(x as i32, y as i32);
+    let chunk = snow.chunks.chunk_for(commands, terrain_map, x, y);
+    commands.spawn((
+        Mesh2d(snow.mesh.clone()),
+        MeshMaterial2d(snow.material.clone()),
+        Transform::from_translation(Vec3::new(world_x, world_y, SNOW_Z)),
+    )).set_parent(chunk).id()
+}
+
+pub fn spawn_snow_overlays(
+    mut commands: Commands,
+    terrain_map: Res<TerrainMap>,
+    ground_configs: Res<GroundConfigs>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let mut snow = SnowCover {
+        material: materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 0.0))),
+        mesh: meshes.add(Rectangle::new(terrain_map.tile_size, terrain_map.tile_size)),
+        tiles: HashMap::new(),
+        chunks: OverlayChunks::default(),
+    };
+    for x in 0..terrain_map.width {
+        for y in 0..terrain_map.height {
+            if ground_configs.takes_snow(terrain_map.tiles[x as usize][y as usize]) {
+                let entity = spawn_snow_tile(&mut commands, &mut snow, &terrain_map, x, y);
+                snow.tiles.insert((x, y), entity);
+            }
+        }
+    }
+    commands.insert_resource(snow);
+}
+
+/// Thicken or thin the snow with the weather, and keep it to the tiles that take snow as the
+/// terrain changes under it. Runs before the terrain changes are cleared.
+pub fn update_snow_overlays(
+    mut commands: Commands,
+    weather: Res<Weather>,
+    terrain_map: Res<TerrainMap>,
+    terrain_changes: Res<TerrainChanges>,
+    ground_configs: Res<GroundConfigs>,
+    snow: Option<ResMut<SnowCover>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let mut snow = match snow {
+        Some(snow) => snow,
+        None => return,
+    };
+
+    if weather.is_changed() {
+        if let Some(material) = materials.get_mut(&snow.material) {
+            material.color.set_alpha(weather.snow_cover * SNOW_MAX_ALPHA);
+        }
+    }
+
+    for &(x, y, terrain_type) in &terrain_changes.changed_tiles {
+        let takes_snow = ground_configs.takes_snow(terrain_type);
+        match snow.tiles.get(&(x, y)).copied() {
+            Some(entity) if !takes_snow => {
+                commands.entity(entity).despawn_recursive();
+                snow.tiles.remove(&(x, y));
+            }
+            None if takes_snow => {
+                let entity = spawn_snow_tile(&mut commands, &mut snow, &terrain_map, x, y);
+                snow.tiles.insert((x, y), entity);
+            }
+            _ => {}
+        }
+    }
+}
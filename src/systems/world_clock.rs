@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use crate::systems::sim_tick::{SimTick, TickPeriod};
+
+/// Fraction of the day at which the sun rises
+pub const DAWN: f32 = 0.25;
+/// Fraction of the day at which the sun sets
+pub const DUSK: f32 = 0.75;
+/// Fraction of the day it takes to get light around dawn, or dark around dusk
+pub const TWILIGHT: f32 = 0.04;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPhase {
+    Day,
+    Night,
+}
+
+/// In-game calendar derived from the simulation tick. A new world starts at dawn of day 0.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct WorldClock {
+    pub day: u64,
+    pub time_of_day: f32, // Fraction of the day, 0.0 is midnight
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self::from_tick(&SimTick::default())
+    }
+}
+
+impl WorldClock {
+    pub fn from_tick(sim_tick: &SimTick) -> Self {
+        let ticks_per_day = sim_tick.ticks_per(TickPeriod::Day);
+        let ticks = sim_tick.tick + (DAWN * ticks_per_day as f32) as u64;
+        Self {
+            day: ticks / ticks_per_day,
+            time_of_day: (ticks % ticks_per_day) as f32 / ticks_per_day as f32,
+        }
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        if (DAWN..DUSK).contains(&self.time_of_day) {
+            DayPhase::Day
+        } else {
+            DayPhase::Night
+        }
+    }
+
+    /// How light it is, from 0 at night to 1 in the day, easing between them over twilight
+    pub fn daylight(&self) -> f32 {
+        let since_dawn = (self.time_of_day - DAWN) / TWILIGHT + 0.5;
+        let until_dusk = (DUSK - self.time_of_day) / TWILIGHT + 0.5;
+        let light = since_dawn.min(until_dusk).clamp(0.0, 1.0);
+        light * light * (3.0 - 2.0 * light)
+    }
+
+    /// Hour and minute on a 24 hour clock
+    pub fn hour_minute(&self) -> (u32, u32) {
+        let minutes = (self.time_of_day * 24.0 * 60.0) as u32;
+        (minutes / 60, minutes % 60)
+    }
+}
+
+/// Runs on FixedUpdate after advance_sim_tick - keeps the clock in step with the tick
+pub fn update_world_clock(sim_tick: Res<SimTick>, mut clock: ResMut<WorldClock>) {
+    let updated = WorldClock::from_tick(&sim_tick);
+    if *clock != updated {
+        if updated.phase() != clock.phase() {
+            println!("Day {}: {:?} begins", updated.day, updated.phase());
+        }
+        *clock = updated;
+    }
+}
@@ -2,16 +2,49 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 use crate::components::TerrainLayer;
 use crate::resources::GameConfig;
+use crate::systems::config_files::ConfigPaths;
+use crate::systems::footsteps::FootstepEffect;
+use crate::systems::save::WorldMetadata;
+use crate::systems::seasons::{Season, Seasons};
+use crate::systems::sim_rng::SimRng;
+use crate::systems::terrain_effects::TerrainEffect;
+use crate::systems::y_sort::{YSort, DepthLayer};
 use noise::{NoiseFn, Perlin, Simplex};
 use pathfinding::prelude::astar;
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GroundConfig {
     pub sprite: String,
     pub passable: bool,
+    #[serde(default)]
+    pub swimmable: bool, // Passable for pawns that can swim
+    #[serde(default)]
+    pub drinkable: bool, // Thirsty pawns drink from tiles next to (or in) this ground
+    #[serde(default)]
+    pub blocks_sight: bool, // Pawns can't see past it, e.g. stone
+    #[serde(default)]
+    pub regrow_secs: Option<u64>, // Vegetation - grows back this many simulated seconds after being eaten or cleared
+    #[serde(default)]
+    pub status_effect: Option<String>, // From status_effects.yaml - given to pawns standing on it, e.g. burn on lava
+    #[serde(default)]
+    pub rain_speed: Option<f32>, // Speed on it while it rains, as a multiple of the usual - e.g. dirt turning to mud
+    #[serde(default)]
+    pub snow_cover: bool, // Snow settles on it while it snows
+    #[serde(default)]
+    pub seasonal_sprites: HashMap<Season, String>, // Drawn instead of `sprite` in these seasons, e.g. autumn grass
+    #[serde(default)]
+    pub effect: Option<TerrainEffect>, // Animated overlay drawn over it - water waves, lava glow, swaying grass
+    #[serde(default)]
+    pub footstep: Option<FootstepEffect>, // Particles pawns kick up walking on it - dust, splashes
+    #[serde(default)]
+    pub minimap_color: Option<[u8; 3]>, // RGB the ground is shown in on the minimap, gray if left out
     pub height_min: f32,
     pub height_max: f32,
 }
@@ -32,6 +65,8 @@ pub struct TilesetIndex {
     pub tile_size: u32,
     pub tiles_per_row: u32,
     pub total_tiles: u32,
+    #[serde(default)]
+    pub padding: u32, // Pixels between tiles
     pub sprites: Vec<SpriteInfo>,
 }
 
@@ -47,6 +82,36 @@ pub struct PropsSpawn {
     pub floor: Option<HashMap<String, String>>,  // alternate naming
 }
 
+/// Above the ground and its terrain effects, while props aren't y-sorted
+const PROP_Z: f32 = 2.0;
+
+/// Every tileset index in a directory, by file name. Ones that can't be read are left out, and
+/// their sprites drawn without a tileset.
+pub fn load_tileset_indices(tilesets_dir: &Path) -> HashMap<String, TilesetIndex> {
+    let mut tileset_indices = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(tilesets_dir) else {
+        return tileset_indices;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|s| s.to_str()) != Some("yaml") {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            let tileset_index = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|yaml_content| serde_yaml::from_str::<TilesetIndex>(&yaml_content).ok());
+            if let Some(tileset_index) = tileset_index {
+                tileset_indices.insert(stem.to_string(), tileset_index);
+            }
+        }
+    }
+    tileset_indices
+}
+
+/// A tree, mushroom or the like scattered over the ground when the world is made
+#[derive(Component, Debug)]
+pub struct Prop;
+
 #[derive(Debug, Clone, Resource)]
 pub struct PropsConfigs {
     pub configs: HashMap<String, PropsConfig>,
@@ -96,6 +161,7 @@ pub struct GroundConfigs {
     pub configs: HashMap<String, GroundConfig>,
     pub terrain_mapping: HashMap<String, usize>, // Maps config names to terrain type indices
     pub tileset_indices: HashMap<String, TilesetIndex>, // Maps tileset names to their sprite indices
+    pub swimming: bool, // Passability as seen by pawns that can swim - see for_swimmers
 }
 
 impl GroundConfigs {
@@ -111,40 +177,31 @@ impl GroundConfigs {
             .map(|(i, name)| ((*name).clone(), i))
             .collect();
 
-        // Load tileset indices from assets/tilesets/*.yaml files
-        let tileset_indices = Self::load_tileset_indices()?;
-            
         Ok(Self {
             configs,
             terrain_mapping,
-            tileset_indices,
+            tileset_indices: HashMap::new(), // Filled in from the tilesets once the world is made
+            swimming: false,
         })
     }
 
-    fn load_tileset_indices() -> Result<HashMap<String, TilesetIndex>, Box<dyn std::error::Error>> {
-        let mut tileset_indices = HashMap::new();
-        
-        // Load known tileset index files
-        let tilesets_path = std::path::Path::new("assets/tilesets");
-        if tilesets_path.exists() {
-            for entry in std::fs::read_dir(tilesets_path)? {
-                let entry = entry?;
-                let path = entry.path();
-                
-                if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
-                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        let yaml_content = std::fs::read_to_string(&path)?;
-                        if let Ok(tileset_index) = serde_yaml::from_str::<TilesetIndex>(&yaml_content) {
-                            tileset_indices.insert(stem.to_string(), tileset_index);
-                        }
-                    }
-                }
-            }
+    /// Copy of these configs in which swimmable grounds are passable
+    pub fn for_swimmers(&self) -> Self {
+        Self {
+            swimming: true,
+            ..self.clone()
         }
-        
-        Ok(tileset_indices)
     }
-    
+
+    /// Passability for a pawn that can or can't swim - only copies the configs for swimmers
+    pub fn for_movement(&self, can_swim: bool) -> Cow<'_, Self> {
+        if can_swim {
+            Cow::Owned(self.for_swimmers())
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
     pub fn get_terrain_type_for_height(&self, height: f32) -> Option<usize> {
         // Find the terrain type that matches the height range
         for (name, config) in &self.configs {
@@ -160,13 +217,84 @@ impl GroundConfigs {
         for (name, config) in &self.configs {
             if let Some(&index) = self.terrain_mapping.get(name) {
                 if index == terrain_type {
-                    return config.passable;
+                    return config.passable || (self.swimming && config.swimmable);
                 }
             }
         }
         false // Default to impassable if not found
     }
 
+    /// RGB the ground is painted on the minimap
+    pub fn minimap_color(&self, terrain_type: usize) -> [u8; 3] {
+        self.configs
+            .iter()
+            .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+            .and_then(|(_, config)| config.minimap_color)
+            .unwrap_or([128, 128, 128])
+    }
+
+    /// The ground's name in grounds.yaml, e.g. "grass"
+    pub fn name_of(&self, terrain_type: usize) -> Option<&str> {
+        self.terrain_mapping
+            .iter()
+            .find(|(_, index)| **index == terrain_type)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn is_swimmable(&self, terrain_type: usize) -> bool {
+        self.configs
+            .iter()
+            .any(|(name, config)| config.swimmable && self.terrain_mapping.get(name) == Some(&terrain_type))
+    }
+
+    pub fn blocks_sight(&self, terrain_type: usize) -> bool {
+        self.configs
+            .iter()
+            .any(|(name, config)| config.blocks_sight && self.terrain_mapping.get(name) == Some(&terrain_type))
+    }
+
+    pub fn is_drinkable(&self, terrain_type: usize) -> bool {
+        self.configs
+            .iter()
+            .any(|(name, config)| config.drinkable && self.terrain_mapping.get(name) == Some(&terrain_type))
+    }
+
+    pub fn regrow_secs(&self, terrain_type: usize) -> Option<u64> {
+        self.configs
+            .iter()
+            .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+            .and_then(|(_, config)| config.regrow_secs)
+    }
+
+    /// Speed on the ground while it rains, as a multiple of the usual
+    pub fn rain_speed(&self, terrain_type: usize) -> f32 {
+        self.configs
+            .iter()
+            .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+            .and_then(|(_, config)| config.rain_speed)
+            .unwrap_or(1.0)
+    }
+
+    pub fn effect(&self, terrain_type: usize) -> Option<&TerrainEffect> {
+        self.configs
+            .iter()
+            .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+            .and_then(|(_, config)| config.effect.as_ref())
+    }
+
+    pub fn takes_snow(&self, terrain_type: usize) -> bool {
+        self.configs
+            .iter()
+            .any(|(name, config)| config.snow_cover && self.terrain_mapping.get(name) == Some(&terrain_type))
+    }
+
+    pub fn status_effect(&self, terrain_type: usize) -> Option<&String> {
+        self.configs
+            .iter()
+            .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+            .and_then(|(_, config)| config.status_effect.as_ref())
+    }
+
     /// Resolves a sprite path like "tileset::grounds::water" to a texture index
     pub fn resolve_sprite_path_to_index(&self, terrain_type: usize) -> Option<u32> {
         // Find the config by terrain type index
@@ -180,6 +308,28 @@ impl GroundConfigs {
         None
     }
 
+    /// Whether the ground looks different in some season
+    pub fn is_seasonal(&self, terrain_type: usize) -> bool {
+        self.configs
+            .iter()
+            .any(|(name, config)| !config.seasonal_sprites.is_empty() && self.terrain_mapping.get(name) == Some(&terrain_type))
+    }
+
+    /// The texture index the ground is drawn with in a season - its usual sprite if it has none
+    /// for that season, or before the first season has begun
+    pub fn resolve_seasonal_sprite_index(&self, terrain_type: usize, season: Option<Season>) -> Option<u32> {
+        let seasonal = season.and_then(|season| {
+            self.configs
+                .iter()
+                .find(|(name, _)| self.terrain_mapping.get(*name) == Some(&terrain_type))
+                .and_then(|(_, config)| config.seasonal_sprites.get(&season))
+        });
+        match seasonal {
+            Some(sprite_path) => self.parse_sprite_path(sprite_path),
+            None => self.resolve_sprite_path_to_index(terrain_type),
+        }
+    }
+
     fn parse_sprite_path(&self, sprite_path: &str) -> Option<u32> {
         // Parse sprite path format: "tileset::tileset_name::sprite_name"
         let parts: Vec<&str> = sprite_path.split("::").collect();
@@ -206,12 +356,60 @@ impl GroundConfigs {
 
 pub type TerrainType = usize;
 
+/// Label for tiles a pawn of the given size can't stand on
+const NO_REGION: u32 = u32::MAX;
+
+/// Connected-region labels per size tier (and swimming or not), computed lazily and dropped whenever a tile changes
+#[derive(Default)]
+pub struct RegionCache {
+    labels: Mutex<HashMap<(u8, bool), Arc<Vec<u32>>>>,
+}
+
+impl Clone for RegionCache {
+    fn clone(&self) -> Self {
+        Self {
+            labels: Mutex::new(self.labels.lock().unwrap().clone()),
+        }
+    }
+}
+
+/// Neighbour offsets in the order A* explores them; bit `i` of a nav grid cell is the step to `NEIGHBOUR_OFFSETS[i]`
+const NEIGHBOUR_OFFSETS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+/// Side length in tiles of a nav grid chunk - the unit nav grids are built and rebuilt in
+const NAV_CHUNK_SIZE: i32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct NavChunkKey {
+    size_tier: u8,
+    swimming: bool,
+    prevent_corner_cutting: bool,
+    chunk: (i32, i32),
+}
+
+/// Precomputed A* steps out of every tile of one chunk, tagged with the latest edit it accounts for
+struct NavChunk {
+    stamp: u64,
+    edges: Arc<Vec<u8>>,
+}
+
+/// Nav grid chunks shared by a map and all of its clones. Pathfinding tasks search on clones,
+/// so sharing lets them reuse (and contribute) chunks instead of rebuilding them per search.
+#[derive(Default)]
+struct NavGridStore {
+    last_edit: AtomicU64,
+    chunks: Mutex<HashMap<NavChunkKey, NavChunk>>,
+}
+
 #[derive(Resource, Clone)]
 pub struct TerrainMap {
     pub width: u32,
     pub height: u32,
     pub tile_size: f32,
     pub tiles: Vec<Vec<TerrainType>>,
+    pub prevent_corner_cutting: bool, // Diagonal steps need both adjacent cardinal tiles passable
+    regions: RegionCache,
+    nav_store: Arc<NavGridStore>,
+    chunk_edits: HashMap<(i32, i32), u64>, // Latest edit per nav chunk, so shared nav chunks can be checked for staleness
 }
 
 impl TerrainMap {
@@ -221,13 +419,266 @@ impl TerrainMap {
             height,
             tile_size,
             tiles: vec![vec![0; height as usize]; width as usize], // Default to first terrain type
+            prevent_corner_cutting: true,
+            regions: RegionCache::default(),
+            nav_store: Arc::new(NavGridStore::default()),
+            chunk_edits: HashMap::new(),
         }
     }
 
     pub fn set_tile(&mut self, x: u32, y: u32, terrain_type: TerrainType) {
-        if x < self.width && y < self.height {
+        if x < self.width && y < self.height && self.tiles[x as usize][y as usize] != terrain_type {
             self.tiles[x as usize][y as usize] = terrain_type;
+            self.invalidate_regions();
+            let edit = self.nav_store.last_edit.fetch_add(1, Ordering::Relaxed) + 1;
+            self.chunk_edits.insert(Self::nav_chunk_of((x as i32, y as i32)), edit);
+        }
+    }
+
+    fn nav_chunk_of(tile: (i32, i32)) -> (i32, i32) {
+        (tile.0.div_euclid(NAV_CHUNK_SIZE), tile.1.div_euclid(NAV_CHUNK_SIZE))
+    }
+
+    fn radius_in_tiles(size: f32) -> i32 {
+        (size / 2.0).ceil() as i32
+    }
+
+    /// Latest edit that could change a step out of this chunk for the given size tier
+    fn nav_chunk_stamp(&self, chunk: (i32, i32), size_tier: u8) -> u64 {
+        // A step samples up to one tile past its origin, and each sample checks the pawn's radius
+        let reach = Self::radius_in_tiles(size_tier as f32 / 8.0) + 2;
+        let chunk_reach = (reach + NAV_CHUNK_SIZE - 1) / NAV_CHUNK_SIZE;
+
+        let mut stamp = 0;
+        for dx in -chunk_reach..=chunk_reach {
+            for dy in -chunk_reach..=chunk_reach {
+                if let Some(&edit) = self.chunk_edits.get(&(chunk.0 + dx, chunk.1 + dy)) {
+                    stamp = stamp.max(edit);
+                }
+            }
+        }
+        stamp
+    }
+
+    /// Steps out of every tile in a chunk, built on first use and again only after nearby edits.
+    /// Sizes share chunks per 1/8 tier, the same tiers the path cache uses.
+    fn nav_chunk(&self, chunk: (i32, i32), size_tier: u8, ground_configs: &GroundConfigs) -> Arc<Vec<u8>> {
+        let key = NavChunkKey {
+            size_tier,
+            swimming: ground_configs.swimming,
+            prevent_corner_cutting: self.prevent_corner_cutting,
+            chunk,
+        };
+        let stamp = self.nav_chunk_stamp(chunk, size_tier);
+
+        let cached = self.nav_store.chunks.lock().unwrap()
+            .get(&key)
+            .filter(|existing| existing.stamp == stamp)
+            .map(|existing| existing.edges.clone());
+        if let Some(edges) = cached {
+            return edges;
+        }
+
+        // Build outside the lock so parallel pathfinding tasks don't wait on each other
+        let edges = Arc::new(self.compute_nav_chunk(chunk, size_tier as f32 / 8.0, ground_configs));
+
+        let mut chunks = self.nav_store.chunks.lock().unwrap();
+        if chunks.get(&key).is_none_or(|existing| existing.stamp <= stamp) {
+            chunks.insert(key, NavChunk { stamp, edges: edges.clone() });
+        }
+        edges
+    }
+
+    fn compute_nav_chunk(&self, chunk: (i32, i32), size: f32, ground_configs: &GroundConfigs) -> Vec<u8> {
+        let mut edges = Vec::with_capacity((NAV_CHUNK_SIZE * NAV_CHUNK_SIZE) as usize);
+        for local_x in 0..NAV_CHUNK_SIZE {
+            for local_y in 0..NAV_CHUNK_SIZE {
+                let tile = (chunk.0 * NAV_CHUNK_SIZE + local_x, chunk.1 * NAV_CHUNK_SIZE + local_y);
+                if self.is_in_bounds(tile) {
+                    edges.push(self.compute_tile_nav_edges(tile, size, ground_configs));
+                } else {
+                    edges.push(0);
+                }
+            }
+        }
+        edges
+    }
+
+    /// The same checks A* used to run per expansion: destination standable, no corner cutting, segment clear
+    fn compute_tile_nav_edges(&self, (x, y): (i32, i32), size: f32, ground_configs: &GroundConfigs) -> u8 {
+        let from_world = self.tile_to_world_coords(x, y);
+
+        // Every segment check samples its origin first, so a tile the pawn can't stand on has no steps out
+        if !self.is_position_passable_for_size(from_world.0, from_world.1, size, ground_configs) {
+            return 0;
+        }
+
+        let mut edges = 0;
+        for (i, &(dx, dy)) in NEIGHBOUR_OFFSETS.iter().enumerate() {
+            let to = (x + dx, y + dy);
+            let to_world = self.tile_to_world_coords(to.0, to.1);
+            if self.is_position_passable_for_size(to_world.0, to_world.1, size, ground_configs)
+                && self.is_step_allowed((x, y), to, ground_configs)
+                && self.is_path_segment_clear(from_world, to_world, size, ground_configs)
+            {
+                edges |= 1 << i;
+            }
+        }
+        edges
+    }
+
+    /// Drop cached region labels - done automatically by set_tile
+    pub fn invalidate_regions(&mut self) {
+        self.regions.labels.get_mut().unwrap().clear();
+    }
+
+    /// Whether no sight-blocking ground (e.g. stone) lies between two world positions. Casts a ray
+    /// through every tile the segment crosses; the tiles at either end never block.
+    pub fn has_line_of_sight(&self, from: (f32, f32), to: (f32, f32), ground_configs: &GroundConfigs) -> bool {
+        let (mut tile, end_tile) = match (self.world_to_tile_coords(from.0, from.1), self.world_to_tile_coords(to.0, to.1)) {
+            (Some(start_tile), Some(end_tile)) => (start_tile, end_tile),
+            _ => return false,
+        };
+
+        // Ray position in tile units, relative to the bottom-left corner of the map
+        let half_width = self.width as f32 * self.tile_size / 2.0;
+        let half_height = self.height as f32 * self.tile_size / 2.0;
+        let start = Vec2::new((from.0 + half_width) / self.tile_size, (from.1 + half_height) / self.tile_size);
+        let end = Vec2::new((to.0 + half_width) / self.tile_size, (to.1 + half_height) / self.tile_size);
+        let direction = end - start;
+        let step = (direction.x.signum() as i32, direction.y.signum() as i32);
+
+        // Ray distance (as a fraction of the segment) to the next tile boundary on each axis, and between boundaries
+        let boundary_distance = |position: f32, tile: i32, delta: f32| {
+            if delta > 0.0 {
+                ((tile + 1) as f32 - position) / delta
+            } else if delta < 0.0 {
+                (tile as f32 - position) / delta
+            } else {
+                f32::INFINITY
+            }
+        };
+        let mut next = Vec2::new(boundary_distance(start.x, tile.0, direction.x), boundary_distance(start.y, tile.1, direction.y));
+        let stride = Vec2::new((1.0 / direction.x).abs(), (1.0 / direction.y).abs());
+
+        while tile != end_tile {
+            if next.x < next.y {
+                tile.0 += step.0;
+                next.x += stride.x;
+            } else {
+                tile.1 += step.1;
+                next.y += stride.y;
+            }
+            if tile == end_tile {
+                break;
+            }
+            // Guard against float drift walking off the map
+            if tile.0 < 0 || tile.1 < 0 || tile.0 >= self.width as i32 || tile.1 >= self.height as i32 {
+                return false;
+            }
+            if ground_configs.blocks_sight(self.tiles[tile.0 as usize][tile.1 as usize]) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Cheap reachability check: false means no path can exist for a pawn of this size.
+    /// Regions are a superset of what A* can walk, so true still needs a real path request.
+    pub fn are_connected(&self, a: (f32, f32), b: (f32, f32), size: f32, ground_configs: &GroundConfigs) -> bool {
+        let (tile_a, tile_b) = match (self.world_to_tile_coords(a.0, a.1), self.world_to_tile_coords(b.0, b.1)) {
+            (Some(tile_a), Some(tile_b)) => (tile_a, tile_b),
+            _ => return false,
+        };
+
+        let labels = self.region_labels(size, ground_configs);
+        let label_b = labels[self.tile_index(tile_b)];
+        if label_b == NO_REGION {
+            return false;
+        }
+
+        let label_a = labels[self.tile_index(tile_a)];
+        if label_a != NO_REGION {
+            return label_a == label_b;
+        }
+
+        // A pawn may stand slightly off-centre on a tile it couldn't be placed at;
+        // it can still step into any neighbouring region
+        (-1..=1).any(|dx| {
+            (-1..=1).any(|dy| {
+                let neighbour = (tile_a.0 + dx, tile_a.1 + dy);
+                self.is_in_bounds(neighbour)
+                    && labels[self.tile_index(neighbour)] == label_b
+                    && self.is_step_allowed(tile_a, neighbour, ground_configs)
+            })
+        })
+    }
+
+    fn is_in_bounds(&self, tile: (i32, i32)) -> bool {
+        tile.0 >= 0 && tile.0 < self.width as i32 && tile.1 >= 0 && tile.1 < self.height as i32
+    }
+
+    fn tile_index(&self, tile: (i32, i32)) -> usize {
+        tile.0 as usize * self.height as usize + tile.1 as usize
+    }
+
+    fn region_labels(&self, size: f32, ground_configs: &GroundConfigs) -> Arc<Vec<u32>> {
+        // Round size down so a tier never reports fewer connections than A* could find
+        let size_tier = (size * 8.0).floor().clamp(0.0, 255.0) as u8;
+
+        let mut cache = self.regions.labels.lock().unwrap();
+        cache
+            .entry((size_tier, ground_configs.swimming))
+            .or_insert_with(|| Arc::new(self.compute_region_labels(size_tier as f32 / 8.0, ground_configs)))
+            .clone()
+    }
+
+    /// Flood fill the map into 8-connected regions of tiles a pawn of this size can stand on
+    fn compute_region_labels(&self, size: f32, ground_configs: &GroundConfigs) -> Vec<u32> {
+        let standable: Vec<bool> = (0..self.width as i32)
+            .flat_map(|x| (0..self.height as i32).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let (world_x, world_y) = self.tile_to_world_coords(x, y);
+                self.is_position_passable_for_size(world_x, world_y, size, ground_configs)
+            })
+            .collect();
+
+        let mut labels = vec![NO_REGION; standable.len()];
+        let mut next_label = 0;
+        let mut queue = VecDeque::new();
+
+        for x in 0..self.width as i32 {
+            for y in 0..self.height as i32 {
+                let index = self.tile_index((x, y));
+                if !standable[index] || labels[index] != NO_REGION {
+                    continue;
+                }
+
+                labels[index] = next_label;
+                queue.push_back((x, y));
+                while let Some(tile) = queue.pop_front() {
+                    for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            let neighbour = (tile.0 + dx, tile.1 + dy);
+                            if !self.is_in_bounds(neighbour) {
+                                continue;
+                            }
+                            let neighbour_index = self.tile_index(neighbour);
+                            if standable[neighbour_index]
+                                && labels[neighbour_index] == NO_REGION
+                                && self.is_step_allowed(tile, neighbour, ground_configs)
+                            {
+                                labels[neighbour_index] = next_label;
+                                queue.push_back(neighbour);
+                            }
+                        }
+                    }
+                }
+                next_label += 1;
+            }
         }
+
+        labels
     }
 
     pub fn get_terrain_at_world_pos(&self, world_x: f32, world_y: f32) -> Option<TerrainType> {
@@ -284,6 +735,15 @@ impl TerrainMap {
         }
     }
 
+    /// Whether a step between neighbouring tiles is allowed by the corner cutting rule.
+    /// Straight steps are always allowed; diagonal ones may not squeeze between two blocked tiles.
+    pub fn is_step_allowed(&self, from: (i32, i32), to: (i32, i32), ground_configs: &GroundConfigs) -> bool {
+        if !self.prevent_corner_cutting || from.0 == to.0 || from.1 == to.1 {
+            return true;
+        }
+        self.is_tile_passable(to.0, from.1, ground_configs) && self.is_tile_passable(from.0, to.1, ground_configs)
+    }
+
     pub fn find_nearest_passable_tile(&self, start_world: (f32, f32), ground_configs: &GroundConfigs) -> Option<(f32, f32)> {
         // First check if the starting position is already passable
         if let Some((start_tile_x, start_tile_y)) = self.world_to_tile_coords(start_world.0, start_world.1) {
@@ -316,10 +776,42 @@ impl TerrainMap {
         None // No passable tile found within reasonable distance
     }
 
+    /// Nearest tile within `radius` tiles that matches, can be stood on by a pawn of this size
+    /// and is connected to its position - candidates are tried closest first
+    pub fn find_nearest_reachable_tile(
+        &self,
+        position: (f32, f32),
+        radius: i32,
+        size: f32,
+        ground_configs: &GroundConfigs,
+        matches: impl Fn((i32, i32)) -> bool,
+    ) -> Option<(i32, i32)> {
+        let (start_x, start_y) = self.world_to_tile_coords(position.0, position.1)?;
+        let mut candidates: Vec<((i32, i32), i32)> = Vec::new();
+        for dx in -radius..=radius {
+            for dy in -radius..=radius {
+                let (x, y) = (start_x + dx, start_y + dy);
+                if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+                    continue;
+                }
+                if matches((x, y)) {
+                    candidates.push(((x, y), dx * dx + dy * dy));
+                }
+            }
+        }
+        candidates.sort_by_key(|&(tile, distance)| (distance, tile));
+
+        candidates.into_iter().map(|(tile, _)| tile).find(|&(x, y)| {
+            let tile_pos = self.tile_to_world_coords(x, y);
+            self.is_position_passable_for_size(tile_pos.0, tile_pos.1, size, ground_configs)
+                && self.are_connected(position, tile_pos, size, ground_configs)
+        })
+    }
+
     pub fn set_tile_at_world_pos(&mut self, world_x: f32, world_y: f32, terrain_type: TerrainType, terrain_changes: &mut TerrainChanges) -> bool {
         if let Some((tile_x, tile_y)) = self.world_to_tile_coords(world_x, world_y) {
             if tile_x >= 0 && tile_x < self.width as i32 && tile_y >= 0 && tile_y < self.height as i32 {
-                self.tiles[tile_x as usize][tile_y as usize] = terrain_type;
+                self.set_tile(tile_x as u32, tile_y as u32, terrain_type);
                 terrain_changes.add_change(tile_x as u32, tile_y as u32, terrain_type);
                 return true;
             }
@@ -430,7 +922,7 @@ impl TerrainMap {
                 
                 neighbors
                     .into_iter()
-                    .filter(|&(nx, ny)| self.is_tile_passable(nx, ny, ground_configs))
+                    .filter(|&(nx, ny)| self.is_tile_passable(nx, ny, ground_configs) && self.is_step_allowed((x, y), (nx, ny), ground_configs))
                     .map(|pos| {
                         // Diagonal moves cost more (approximately sqrt(2) ≈ 1.414)
                         let cost = if pos.0 != x && pos.1 != y { 14 } else { 10 };
@@ -459,7 +951,7 @@ impl TerrainMap {
         }
     }
 
-    fn is_path_segment_clear(&self, from_world: (f32, f32), to_world: (f32, f32), size: f32, ground_configs: &GroundConfigs) -> bool {
+    pub fn is_path_segment_clear(&self, from_world: (f32, f32), to_world: (f32, f32), size: f32, ground_configs: &GroundConfigs) -> bool {
         self.is_path_segment_clear_with_cache(from_world, to_world, size, ground_configs, &mut None)
     }
 
@@ -520,7 +1012,7 @@ impl TerrainMap {
         }
 
         // Compute path if not cached
-        let result = self.find_path_for_size_internal(start_world, goal_world, size, ground_configs, Some(cache));
+        let result = self.find_path_for_size_internal(start_world, goal_world, size, ground_configs, Some(cache), None);
         
         // Cache the result
         cache.cache_path(start_tile, goal_tile, size, result.clone(), self);
@@ -530,10 +1022,15 @@ impl TerrainMap {
 
     /// Original pathfinding method (kept for compatibility)
     pub fn find_path_for_size(&self, start_world: (f32, f32), goal_world: (f32, f32), size: f32, ground_configs: &GroundConfigs) -> Option<Vec<(f32, f32)>> {
-        self.find_path_for_size_internal(start_world, goal_world, size, ground_configs, None)
+        self.find_path_for_size_internal(start_world, goal_world, size, ground_configs, None, None)
     }
 
-    fn find_path_for_size_internal(&self, start_world: (f32, f32), goal_world: (f32, f32), size: f32, ground_configs: &GroundConfigs, mut cache: Option<&mut crate::systems::pathfinding_cache::PathfindingCache>) -> Option<Vec<(f32, f32)>> {
+    /// Same as find_path_for_size, but also records every tile A* expanded (for the debug heatmap)
+    pub fn find_path_for_size_traced(&self, start_world: (f32, f32), goal_world: (f32, f32), size: f32, ground_configs: &GroundConfigs, expanded: &mut Vec<(i32, i32)>) -> Option<Vec<(f32, f32)>> {
+        self.find_path_for_size_internal(start_world, goal_world, size, ground_configs, None, Some(expanded))
+    }
+
+    fn find_path_for_size_internal(&self, start_world: (f32, f32), goal_world: (f32, f32), size: f32, ground_configs: &GroundConfigs, mut cache: Option<&mut crate::systems::pathfinding_cache::PathfindingCache>, mut expanded: Option<&mut Vec<(i32, i32)>>) -> Option<Vec<(f32, f32)>> {
         // Convert world coordinates to tile coordinates
         let start_tile = self.world_to_tile_coords(start_world.0, start_world.1)?;
         let goal_tile = self.world_to_tile_coords(goal_world.0, goal_world.1)?;
@@ -548,36 +1045,28 @@ impl TerrainMap {
             return None; // Can't path to position that's impassable for this size
         }
 
-        // A* pathfinding with size awareness  
-        // Note: We can't pass mutable cache into the closure, so we'll use the uncached version in A*
+        // A* pathfinding with size awareness - steps come from the precomputed nav grid
+        let size_tier = (size * 8.0).round().clamp(0.0, 255.0) as u8;
+        let mut nav_chunks: HashMap<(i32, i32), Arc<Vec<u8>>> = HashMap::new();
         let result = astar(
             &start_tile,
             |&(x, y)| {
-                // Generate neighbors (8-directional movement with diagonal support)
-                let neighbors = vec![
-                    (x + 1, y),     // Right
-                    (x - 1, y),     // Left
-                    (x, y + 1),     // Up
-                    (x, y - 1),     // Down
-                    (x + 1, y + 1), // Up-Right (diagonal)
-                    (x + 1, y - 1), // Down-Right (diagonal)
-                    (x - 1, y + 1), // Up-Left (diagonal)
-                    (x - 1, y - 1), // Down-Left (diagonal)
-                ];
-                
-                neighbors
-                    .into_iter()
-                    .filter(|&(nx, ny)| {
-                        // Check if destination position is passable for the given size
-                        let to_world = self.tile_to_world_coords(nx, ny);
-                        if !self.is_position_passable_for_size(to_world.0, to_world.1, size, ground_configs) {
-                            return false;
-                        }
-                        
-                        // Check if the entire path segment from current position to neighbor is clear
-                        let from_world = self.tile_to_world_coords(x, y);
-                        self.is_path_segment_clear(from_world, to_world, size, ground_configs)
-                    })
+                // Successors are generated once per expanded node
+                if let Some(expanded) = expanded.as_mut() {
+                    expanded.push((x, y));
+                }
+
+                // 8-directional movement with diagonal support
+                let chunk = Self::nav_chunk_of((x, y));
+                let chunk_edges = nav_chunks
+                    .entry(chunk)
+                    .or_insert_with(|| self.nav_chunk(chunk, size_tier, ground_configs));
+                let edges = chunk_edges[((x - chunk.0 * NAV_CHUNK_SIZE) * NAV_CHUNK_SIZE + (y - chunk.1 * NAV_CHUNK_SIZE)) as usize];
+                NEIGHBOUR_OFFSETS
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| edges & (1 << i) != 0)
+                    .map(|(_, &(dx, dy))| (x + dx, y + dy))
                     .map(|pos| {
                         // Diagonal moves cost more (approximately sqrt(2) ≈ 1.414)
                         let cost = if pos.0 != x && pos.1 != y { 14 } else { 10 };
@@ -610,15 +1099,21 @@ impl TerrainMap {
 #[derive(Resource, Default)]
 pub struct TerrainChanges {
     pub changed_tiles: Vec<(u32, u32, TerrainType)>, // (x, y, new_terrain_type)
+    pub repainted_tiles: Vec<(u32, u32)>, // Same ground, different look (e.g. for the season) - only redrawn
 }
 
 impl TerrainChanges {
     pub fn add_change(&mut self, x: u32, y: u32, terrain_type: TerrainType) {
         self.changed_tiles.push((x, y, terrain_type));
     }
+
+    pub fn add_repaint(&mut self, x: u32, y: u32) {
+        self.repainted_tiles.push((x, y));
+    }
     
     pub fn clear(&mut self) {
         self.changed_tiles.clear();
+        self.repainted_tiles.clear();
     }
 }
 
@@ -669,29 +1164,38 @@ impl TerrainNoise {
     }
 }
 
+/// Seed for the next world to be generated, picked at random if there isn't one
+#[derive(Resource, Debug, Default)]
+pub struct NextWorldSeed(pub Option<u32>);
+
 pub fn generate_world(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     config: Res<GameConfig>,
+    config_paths: Res<ConfigPaths>,
+    next_seed: Option<Res<NextWorldSeed>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut rng: ResMut<SimRng>,
 ) {
     // Load ground configuration from YAML
-    let grounds_yaml = std::fs::read_to_string("grounds.yaml")
+    let grounds_yaml = config_paths.read("grounds.yaml")
         .expect("Failed to read grounds.yaml file");
-    let ground_configs = GroundConfigs::load_from_yaml(&grounds_yaml)
+    let mut ground_configs = GroundConfigs::load_from_yaml(&grounds_yaml)
         .expect("Failed to parse grounds.yaml");
     
     // Load props configuration from YAML
-    let props_yaml = std::fs::read_to_string("props.yaml")
+    let props_yaml = config_paths.read("props.yaml")
         .expect("Failed to read props.yaml file");
     let mut props_configs = PropsConfigs::load_from_yaml(&props_yaml)
         .expect("Failed to parse props.yaml");
     
-    // Load props tileset configuration
-    let props_tileset_yaml = std::fs::read_to_string("assets/tilesets/props.yaml")
-        .expect("Failed to read assets/tilesets/props.yaml file");
-    let props_tileset: TilesetIndex = serde_yaml::from_str(&props_tileset_yaml)
-        .expect("Failed to parse props tileset yaml");
-    props_configs.add_tileset(props_tileset);
+    // Tilesets sit in the assets folder, wherever the game was started from
+    let tilesets_dir = config_paths.data_dir().join("assets").join("tilesets");
+    ground_configs.tileset_indices = load_tileset_indices(&tilesets_dir);
+    match ground_configs.tileset_indices.get("props") {
+        Some(props_tileset) => props_configs.add_tileset(props_tileset.clone()),
+        None => eprintln!("Warning: No props tileset in {:?}, props will be left out", tilesets_dir),
+    }
     
     let map_size = TilemapSize { 
         x: config.map_width, 
@@ -706,12 +1210,20 @@ pub fn generate_world(
 
     // Create and populate terrain map
     let mut terrain_map = TerrainMap::new(config.map_width, config.map_height, config.tile_size);
+    terrain_map.prevent_corner_cutting = config.prevent_corner_cutting;
+    
+    // Use the seed asked for, or pick a random one, and remember it in the world's save metadata
+    let seed = next_seed
+        .and_then(|next_seed| next_seed.0)
+        .unwrap_or_else(|| rand::thread_rng().next_u32());
+    commands.insert_resource(WorldMetadata::new("New World", seed, &config, &config_paths));
+    *rng = SimRng::from_seed(seed);
     
     // Generate ground layer and populate terrain map
-    generate_ground_layer(&mut commands, &asset_server, &map_size, &tile_size, &grid_size, &map_type, &mut terrain_map, &ground_configs);
+    generate_ground_layer(&mut commands, &asset_server, &map_size, &tile_size, &grid_size, &map_type, &mut terrain_map, &ground_configs, seed);
     
-    // Generate props layer
-    generate_props_layer(&mut commands, &asset_server, &map_size, &tile_size, &grid_size, &map_type, &terrain_map, &ground_configs, &props_configs);
+    // Scatter props over it
+    generate_props_layer(&mut commands, &asset_server, &mut texture_atlas_layouts, &terrain_map, &ground_configs, &props_configs, &mut rng);
     
     // Insert the populated terrain map and configs as resources
     commands.insert_resource(terrain_map);
@@ -719,7 +1231,7 @@ pub fn generate_world(
     commands.insert_resource(props_configs);
     
     // Generate decoration layer (if needed)
-    // generate_decoration_layer(&mut commands, &asset_server, &map_size, &tile_size, &grid_size, &map_type);
+    // generate_decoration_layer(&mut commands, &asset_server, &map_size, &tile_size, &grid_size, &map_type, &mut rng);
 }
 
 fn generate_ground_layer(
@@ -731,14 +1243,14 @@ fn generate_ground_layer(
     map_type: &TilemapType,
     terrain_map: &mut TerrainMap,
     ground_configs: &GroundConfigs,
+    seed: u32,
 ) {
     let texture_handle: Handle<Image> = asset_server.load("tilesets/grounds.png");
+    let padding = ground_configs.tileset_indices.get("grounds").map_or(0.0, |tileset| tileset.padding as f32);
     let tilemap_entity = commands.spawn_empty().id();
     let mut tile_storage = TileStorage::empty(*map_size);
-    let mut rng = rand::thread_rng();
     
-    // Create noise generator with random seed
-    let seed: u32 = rng.next_u32();
+    // Create noise generator from the world seed
     let noise = TerrainNoise::new(seed);
 
     for x in 0..map_size.x {
@@ -780,6 +1292,7 @@ fn generate_ground_layer(
         storage: tile_storage,
         texture: TilemapTexture::Single(texture_handle),
         tile_size: *tile_size,
+        spacing: TilemapSpacing { x: padding, y: padding },
         transform: get_tilemap_center_transform(map_size, grid_size, map_type, 0.0),
         ..Default::default()
     })
@@ -792,24 +1305,30 @@ fn generate_ground_layer(
 fn generate_props_layer(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    map_size: &TilemapSize,
-    tile_size: &TilemapTileSize,
-    grid_size: &TilemapGridSize,
-    map_type: &TilemapType,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
     terrain_map: &TerrainMap,
     ground_configs: &GroundConfigs,
     props_configs: &PropsConfigs,
+    rng: &mut SimRng,
 ) {
+    // Props are sprites rather than a tilemap layer, so they can be drawn in depth order with pawns
     let texture_handle: Handle<Image> = asset_server.load("tilesets/props.png");
-    let tilemap_entity = commands.spawn_empty().id();
-    let mut tile_storage = TileStorage::empty(*map_size);
-    let mut rng = rand::thread_rng();
-
+    let atlas_layout = props_configs.tileset_indices.get("props").map(|tileset| {
+        texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(tileset.tile_size),
+            tileset.tiles_per_row,
+            tileset.total_tiles.div_ceil(tileset.tiles_per_row),
+            Some(UVec2::splat(tileset.padding)),
+            None,
+        ))
+    });
 
-    for x in 1..map_size.x - 1 { // Skip borders
-        for y in 1..map_size.y - 1 {
-            let tile_pos = TilePos { x, y };
+    // Props in name order, so the same seed rolls for them the same way
+    let mut props: Vec<_> = props_configs.configs.iter().collect();
+    props.sort_by(|a, b| a.0.cmp(b.0));
 
+    for x in 1..terrain_map.width - 1 { // Skip borders
+        for y in 1..terrain_map.height - 1 {
             // Get the terrain type at this position
             let terrain_type = terrain_map.tiles[x as usize][y as usize];
 
@@ -820,7 +1339,7 @@ fn generate_props_layer(
 
             if let Some(terrain_name) = terrain_name {
                 // Check each prop type to see if it should spawn on this terrain
-                for (_prop_name, prop_config) in &props_configs.configs {
+                for &(_prop_name, prop_config) in &props {
                     let spawn_floors = prop_config.spawn.floors.as_ref().or(prop_config.spawn.floor.as_ref());
 
                     if let Some(floors) = spawn_floors {
@@ -830,16 +1349,19 @@ fn generate_props_layer(
                                 if let (Ok(num), Ok(den)) = (numerator.parse::<u32>(), denominator.parse::<u32>()) {
                                     if rng.gen_ratio(num, den) {
                                         // Spawn this prop
-                                        if let Some(texture_index) = props_configs.resolve_sprite_path_to_index(&prop_config.sprite) {
-                                            let tile_entity = commands
-                                                .spawn(TileBundle {
-                                                    position: tile_pos,
-                                                    tilemap_id: TilemapId(tilemap_entity),
-                                                    texture_index: TileTextureIndex(texture_index),
-                                                    ..Default::default()
-                                                })
-                                                .id();
-                                            tile_storage.set(&tile_pos, tile_entity);
+                                        if let (Some(texture_index), Some(layout)) = (props_configs.resolve_sprite_path_to_index(&prop_config.sprite), atlas_layout.as_ref()) {
+                                            let mut sprite = Sprite::from_atlas_image(texture_handle.clone(), TextureAtlas {
+                                                layout: layout.clone(),
+                                                index: texture_index as usize,
+                                            });
+                                            sprite.custom_size = Some(Vec2::splat(terrain_map.tile_size));
+                                            let (world_x, world_y) = terrain_map.tile_to_world_coords(x as i32, y as i32);
+                                            commands.spawn((
+                                                Prop,
+                                                sprite,
+                                                Transform::from_xyz(world_x, world_y, PROP_Z),
+                                                YSort(DepthLayer::Props),
+                                            ));
                                             break; // Only spawn one prop per tile
                                         }
                                     }
@@ -851,21 +1373,6 @@ fn generate_props_layer(
             }
         }
     }
-
-    commands.entity(tilemap_entity).insert(TilemapBundle {
-        grid_size: *grid_size,
-        map_type: *map_type,
-        size: *map_size,
-        storage: tile_storage,
-        texture: TilemapTexture::Single(texture_handle),
-        tile_size: *tile_size,
-        transform: get_tilemap_center_transform(map_size, grid_size, map_type, 1.0),
-        ..Default::default()
-    })
-    .insert(TerrainLayer {
-        layer_id: 1,
-        z_index: 1.0,
-    });
 }
 
 fn generate_decoration_layer(
@@ -875,11 +1382,11 @@ fn generate_decoration_layer(
     tile_size: &TilemapTileSize,
     grid_size: &TilemapGridSize,
     map_type: &TilemapType,
+    rng: &mut SimRng,
 ) {
     let texture_handle: Handle<Image> = asset_server.load("decoration_tileset.png");
     let tilemap_entity = commands.spawn_empty().id();
     let mut tile_storage = TileStorage::empty(*map_size);
-    let mut rng = rand::thread_rng();
 
     for x in 1..map_size.x - 1 { // Skip borders
         for y in 1..map_size.y - 1 {
@@ -926,23 +1433,30 @@ fn generate_decoration_layer(
 pub fn update_terrain_visuals(
     mut terrain_changes: ResMut<TerrainChanges>,
     mut tile_query: Query<&mut TileTextureIndex>,
-    tile_storage_query: Query<&TileStorage, With<TerrainLayer>>,
+    tile_storage_query: Query<(&TileStorage, &TerrainLayer)>,
+    terrain_map: Res<TerrainMap>,
     ground_configs: Res<GroundConfigs>,
+    seasons: Option<Res<Seasons>>,
 ) {
-    if terrain_changes.changed_tiles.is_empty() {
+    if terrain_changes.changed_tiles.is_empty() && terrain_changes.repainted_tiles.is_empty() {
         return;
     }
+    let season = seasons.and_then(|seasons| seasons.current);
     
-    // Find the terrain layer tile storage
-    if let Ok(tile_storage) = tile_storage_query.get_single() {
-        for (x, y, terrain_type) in terrain_changes.changed_tiles.drain(..) {
+    // Find the ground layer's tile storage, under the props
+    if let Some((tile_storage, _)) = tile_storage_query.iter().find(|(_, layer)| layer.layer_id == 0) {
+        let repainted = terrain_changes.repainted_tiles
+            .iter()
+            .filter(|(x, y)| *x < terrain_map.width && *y < terrain_map.height)
+            .map(|&(x, y)| (x, y, terrain_map.tiles[x as usize][y as usize]));
+        for (x, y, terrain_type) in terrain_changes.changed_tiles.iter().copied().chain(repainted) {
             let tile_pos = TilePos { x, y };
             
             if let Some(tile_entity) = tile_storage.get(&tile_pos) {
                 if let Ok(mut texture_index) = tile_query.get_mut(tile_entity) {
                     // Resolve sprite path to texture index
                     let resolved_index = ground_configs
-                        .resolve_sprite_path_to_index(terrain_type)
+                        .resolve_seasonal_sprite_index(terrain_type, season)
                         .unwrap_or(terrain_type as u32); // Fallback to terrain_type if resolution fails
                     texture_index.0 = resolved_index;
                 }
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::resources::GameConfig;
+use crate::systems::interpolation::SmoothMotion;
+use crate::systems::pawn::Pawn;
+
+/// Depth of whatever's at the far (top) edge of the map - above the ground overlays
+pub const Y_SORT_Z: f32 = 60.0;
+/// How much nearer the near (bottom) edge of the map is drawn - staying under the night tint
+const Y_SORT_DEPTH: f32 = 40.0;
+/// Between layers at the same height, so later layers in the settings are drawn over earlier ones.
+/// Well under the depth a pixel of height makes on the largest maps.
+const LAYER_STEP: f32 = 0.001;
+
+/// The kinds of things in the world that can be drawn in depth order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepthLayer {
+    Props,
+    Dens,
+    Items,
+    Pawns,
+}
+
+/// Which layers are drawn in depth order, from the `y_sort` section of settings.yaml. Things on
+/// them are drawn in front of anything higher up the screen; layers left out keep their usual
+/// depth.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct YSortSettings {
+    pub layers: Vec<DepthLayer>, // Later ones are drawn over earlier ones at the same height
+}
+
+impl Default for YSortSettings {
+    fn default() -> Self {
+        Self {
+            layers: vec![DepthLayer::Props, DepthLayer::Dens, DepthLayer::Items, DepthLayer::Pawns],
+        }
+    }
+}
+
+impl YSortSettings {
+    /// Where the layer comes in the tie-break, if it's sorted at all
+    pub fn layer_index(&self, layer: DepthLayer) -> Option<usize> {
+        self.layers.iter().position(|&sorted| sorted == layer)
+    }
+}
+
+/// Drawn at a depth set by how far down the map it is, if its layer is sorted
+#[derive(Component, Debug, Clone, Copy)]
+pub struct YSort(pub DepthLayer);
+
+/// The depth to draw something at height `y` on a map `world_height` tall - nearer the bottom
+/// is nearer the viewer
+pub fn sorted_z(y: f32, world_height: f32, layer_index: usize) -> f32 {
+    let down_the_map = (0.5 - y / world_height.max(1.0)).clamp(0.0, 1.0);
+    Y_SORT_Z + down_the_map * Y_SORT_DEPTH + layer_index as f32 * LAYER_STEP
+}
+
+/// Runs every frame once pawns are drawn where they'll be seen - sets the depth of everything
+/// that's moved. Pawns are left alone until they're drawn between simulation steps, so the depth
+/// never finds its way back into where the simulation has them.
+pub fn y_sort_system(
+    config: Res<GameConfig>,
+    mut query: Query<(&YSort, &mut Transform), (Changed<Transform>, Or<(Without<Pawn>, With<SmoothMotion>)>)>,
+) {
+    let world_height = config.map_height as f32 * config.tile_size;
+    for (y_sort, mut transform) in query.iter_mut() {
+        if let Some(layer_index) = config.y_sort.layer_index(y_sort.0) {
+            let z = sorted_z(transform.translation.y, world_height, layer_index);
+            if transform.translation.z != z {
+                transform.translation.z = z;
+            }
+        }
+    }
+}
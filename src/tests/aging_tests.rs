@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::aging::{Age, aging_system};
+    use crate::systems::pawn::{Pawn, PawnTarget, Size, Health, Endurance, move_pawn_to_target};
+    use crate::systems::pawn_config::{PawnConfig, LifeStage};
+    use crate::systems::sim_tick::{SimulationTick, TickPeriod, SECONDS_PER_DAY};
+    use crate::tests::{setup_test_app, create_test_terrain_map, create_test_ground_configs};
+
+    const AGING_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 10
+  strength: 8
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 5
+  eats:
+    pawns: []
+  aging:
+    max_age: 4
+    juvenile:
+      age: 1
+      size: 0.5
+      speed: 0.5
+    elderly:
+      age: 3
+      strength: 0.5
+      defence: 0.5
+"#;
+
+    fn create_aging_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(AGING_PAWNS_YAML).expect("Aging config should parse")
+    }
+
+    fn setup_aging_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_aging_pawn_config());
+        app.add_event::<SimulationTick>();
+        app.add_systems(Update, aging_system);
+        app
+    }
+
+    fn spawn_aged_rabbit(app: &mut App, age_days: f32) -> Entity {
+        let config = create_aging_pawn_config();
+        let aging = config.get_pawn_definition("rabbit").unwrap().aging.clone().unwrap();
+        let age = Age::new(age_days, &aging);
+        let size = config.get_pawn_definition("rabbit").unwrap().size_for(Some(age.stage));
+        app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            age,
+            Size { value: size },
+            Health::new(25),
+            Endurance::new(10),
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id()
+    }
+
+    fn tick_seconds(app: &mut App, seconds: u64) {
+        for tick in 0..seconds {
+            app.world_mut().send_event(SimulationTick { tick, period: TickPeriod::Second });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn test_life_stages_scale_stats() {
+        let config = create_aging_pawn_config();
+        let rabbit = config.get_pawn_definition("rabbit").unwrap();
+        let aging = rabbit.aging.as_ref().unwrap();
+
+        assert_eq!(aging.stage_at(0.5), LifeStage::Juvenile);
+        assert_eq!(aging.stage_at(1.0), LifeStage::Adult);
+        assert_eq!(aging.stage_at(3.5), LifeStage::Elderly);
+
+        assert_eq!(rabbit.size_for(Some(LifeStage::Juvenile)), 0.5);
+        assert_eq!(rabbit.speed_multiplier(Some(LifeStage::Juvenile)), 0.5);
+        assert_eq!(rabbit.strength_for(Some(LifeStage::Juvenile)), 8.0, "Unset multipliers leave stats alone");
+        assert_eq!(rabbit.strength_for(Some(LifeStage::Elderly)), 4.0);
+        assert_eq!(rabbit.defence_for(Some(LifeStage::Elderly), false), 5.0);
+        assert_eq!(rabbit.defence_for(None, false), 10.0, "Pawns without an age fight at full strength");
+        assert_eq!(rabbit.sprite_for(Some(LifeStage::Elderly)), "tileset::pawns::rabbit", "Stages without a sprite keep the pawn's own");
+    }
+
+    #[test]
+    fn test_juvenile_grows_up() {
+        let mut app = setup_aging_app();
+        let rabbit = spawn_aged_rabbit(&mut app, 0.0);
+        assert_eq!(app.world().get::<Size>(rabbit).unwrap().value, 0.5);
+
+        tick_seconds(&mut app, SECONDS_PER_DAY - 1);
+        assert_eq!(app.world().get::<Age>(rabbit).unwrap().stage, LifeStage::Juvenile);
+
+        tick_seconds(&mut app, 1);
+        let age = app.world().get::<Age>(rabbit).unwrap();
+        assert_eq!(age.seconds, SECONDS_PER_DAY);
+        assert_eq!(age.stage, LifeStage::Adult);
+        assert_eq!(app.world().get::<Size>(rabbit).unwrap().value, 1.0, "Adults are full size");
+    }
+
+    #[test]
+    fn test_pawns_die_of_old_age() {
+        let mut app = setup_aging_app();
+        let rabbit = spawn_aged_rabbit(&mut app, 3.9);
+        tick_seconds(&mut app, 10);
+        assert!(app.world().get::<Health>(rabbit).unwrap().current > 0.0);
+
+        tick_seconds(&mut app, SECONDS_PER_DAY / 10);
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 0.0, "Reaching max_age is fatal");
+    }
+
+    #[test]
+    fn test_juveniles_move_slower() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.insert_resource(create_aging_pawn_config());
+        app.insert_resource(GameConfig { tile_size: 16.0, ..GameConfig::default() });
+        app.insert_resource(create_test_terrain_map(20, 20, 16.0));
+        app.insert_resource(create_test_ground_configs());
+        app.add_systems(Update, move_pawn_to_target);
+
+        // Both walk east along the grass border row
+        let start = Vec3::new(-120.0, -152.0, 100.0);
+        let goal = Vec3::new(120.0, -152.0, 100.0);
+        let juvenile = spawn_aged_rabbit(&mut app, 0.0);
+        let adult = spawn_aged_rabbit(&mut app, 2.0);
+        for rabbit in [juvenile, adult] {
+            let mut target = PawnTarget::new(goal);
+            target.set_path(vec![(goal.x, goal.y)]);
+            app.world_mut().entity_mut(rabbit).insert((target, Transform::from_translation(start)));
+        }
+
+        app.update();
+        app.update();
+
+        let juvenile_moved = app.world().get::<Transform>(juvenile).unwrap().translation.distance(start);
+        let adult_moved = app.world().get::<Transform>(adult).unwrap().translation.distance(start);
+        assert!(adult_moved > 0.0);
+        assert!((juvenile_moved - adult_moved * 0.5).abs() < 0.01, "Juvenile moved {} vs adult {}", juvenile_moved, adult_moved);
+    }
+}
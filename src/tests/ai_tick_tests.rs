@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{WanderingAI, wandering_ai_system};
+    use crate::systems::ai_tick::{AiTicker, AiTickIntervals, stagger_offset};
+    use crate::systems::pawn::{Pawn, CurrentBehavior, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::perception::PERCEPTION_TICK_SECS;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const WANDER_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle:
+      wandering:
+        move_interval_min: 100.0
+        move_interval_max: 100.0
+        move_range: 5
+  eats:
+    pawns: []
+"#;
+
+    #[test]
+    fn test_tick_interval_settings() {
+        let partial: AiTickIntervals = serde_yaml::from_str("wander: 0.2").expect("Partial AI settings should parse");
+        assert_eq!(partial.wander, 0.2);
+        assert_eq!(partial.perception, PERCEPTION_TICK_SECS, "Unset intervals keep their defaults");
+        assert_eq!(GameConfig::default().ai_ticks.wander, 0.0, "Wandering thinks every simulation step unless configured");
+    }
+
+    #[test]
+    fn test_every_pawn_gets_one_turn_per_interval() {
+        let pawns: Vec<Entity> = (0..50).map(Entity::from_raw).collect();
+        let mut ticker = AiTicker::default();
+        let mut turns = vec![0; pawns.len()];
+        let mut busiest_frame = 0;
+
+        // One second at 60 fps with a 0.2 second interval
+        for _ in 0..60 {
+            ticker.advance(1.0 / 60.0);
+            let due: Vec<usize> = (0..pawns.len()).filter(|&index| ticker.is_due(pawns[index], 0.2)).collect();
+            busiest_frame = busiest_frame.max(due.len());
+            for index in due {
+                turns[index] += 1;
+            }
+        }
+
+        assert!(turns.iter().all(|&count| (4..=5).contains(&count)), "About five turns each: {:?}", turns);
+        assert!(busiest_frame < pawns.len() / 4, "Turns are spread out, at most {} of 50 in one frame", busiest_frame);
+    }
+
+    #[test]
+    fn test_zero_interval_runs_every_frame() {
+        let mut ticker = AiTicker::default();
+        ticker.advance(0.001);
+        assert!(ticker.is_due(Entity::from_raw(7), 0.0));
+        assert!((0..100).map(Entity::from_raw).all(|entity| (0.0..=1.0).contains(&stagger_offset(entity))));
+    }
+
+    #[test]
+    fn test_wanderers_only_think_on_their_turn() {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(WANDER_PAWNS_YAML).expect("Wander config should parse");
+        let ai_ticks = AiTickIntervals { wander: 0.2, ..AiTickIntervals::default() };
+        app.insert_resource(config);
+        app.insert_resource(GameConfig { ai_ticks, ..GameConfig::default() });
+        app.insert_resource(TerrainMap::new(20, 20, 16.0));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+        app.add_systems(Update, wandering_ai_system);
+
+        let mut wandering_ai = WanderingAI::new();
+        wandering_ai.next_move_time = 10.0;
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Size { value: 1.0 },
+            wandering_ai,
+            Transform::from_xyz(100.0, 100.0, 100.0),
+        )).id();
+
+        // The first update has no time in it, then a second of 50ms frames
+        for _ in 0..21 {
+            app.update();
+        }
+        let next_move_time = app.world().get::<WanderingAI>(rabbit).unwrap().next_move_time;
+        assert!((next_move_time - 9.0).abs() < 0.001, "Five turns of 0.2s each, got {}", next_move_time);
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ambush::{AmbushAI, Hidden, pick_ambush_spot, setup_ambush_ai, ambush_ai_system};
+    use crate::systems::charge::Charge;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_AMBUSH_BURST_SPEED, DEFAULT_AMBUSH_PATIENCE};
+    use crate::systems::perception::{Threatened, threat_perception_system};
+    use crate::systems::scent::ScentMap;
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const AMBUSH_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: flee
+  eats:
+    pawns: []
+  perception:
+    detection_radius: 6
+lynx:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 60
+  max_endurance: 10
+  defence: 5
+  strength: 20
+  attack_speed: 2
+  reach: 1
+  size: 1
+  spawn_count: 1
+  stealth: 0.5
+  ambush:
+    strike_range: 3
+  behaviours:
+    idle: ambush
+  eats:
+    pawns: [small]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_ambush_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(AMBUSH_PAWNS_YAML).expect("Ambush config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app
+    }
+
+    fn spawn_at(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(30),
+            Endurance::new(10),
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_ambush_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(AMBUSH_PAWNS_YAML).expect("Ambush config should parse");
+        let lynx = config.get_pawn_definition("lynx").unwrap();
+        assert_eq!(lynx.stealth, 0.5);
+        assert_eq!(lynx.ambush_config().strike_range, 3);
+        assert_eq!(lynx.ambush_config().burst_speed, DEFAULT_AMBUSH_BURST_SPEED);
+        assert_eq!(lynx.ambush_config().patience, DEFAULT_AMBUSH_PATIENCE);
+        assert_eq!(config.get_pawn_definition("deer").unwrap().stealth, 0.0, "Pawns aren't stealthy unless configured");
+    }
+
+    #[test]
+    fn test_ambush_spot_is_where_prey_passes_most() {
+        let terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        let ground_configs = create_test_ground_configs();
+        let (x, y) = terrain_map.tile_to_world_coords(5, 5);
+        let position = Vec2::new(x, y);
+
+        assert_eq!(pick_ambush_spot(position, 8, 1.0, &terrain_map, &ground_configs, None), Some((5, 5)), "No trails, so it hides where it is");
+
+        let mut scent_map = ScentMap::default();
+        scent_map.deposit((3, 3), Entity::from_raw(1));
+        for pawn in 1..4 {
+            scent_map.deposit((9, 6), Entity::from_raw(pawn));
+        }
+        for pawn in 5..9 {
+            scent_map.deposit((18, 18), Entity::from_raw(pawn)); // Busier, but out of range
+        }
+        assert_eq!(scent_map.traffic_at((9, 6)), 3.0);
+        assert_eq!(
+            pick_ambush_spot(position, 8, 1.0, &terrain_map, &ground_configs, Some(&scent_map)),
+            Some((9, 6)),
+            "The busiest tile in range wins"
+        );
+    }
+
+    #[test]
+    fn test_hidden_predators_are_noticed_closer() {
+        for (stealth, noticed) in [(None, true), (Some(0.5), false)] {
+            let mut app = setup_ambush_app();
+            app.add_systems(Update, threat_perception_system);
+            let deer = spawn_at(&mut app, "deer", (5, 5));
+            let lynx = spawn_at(&mut app, "lynx", (9, 5));
+            if let Some(stealth) = stealth {
+                app.world_mut().entity_mut(lynx).insert(Hidden { stealth });
+            }
+            for _ in 0..5 {
+                app.update();
+            }
+
+            assert_eq!(
+                app.world().get::<Threatened>(deer).is_some(),
+                noticed,
+                "Four tiles away against a detection radius of 6, halved while hidden"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ambusher_hides_then_bursts_at_prey() {
+        let mut app = setup_ambush_app();
+        app.add_systems(Update, (setup_ambush_ai, ambush_ai_system.after(setup_ambush_ai)));
+        let lynx = spawn_at(&mut app, "lynx", (5, 5));
+        let deer = spawn_at(&mut app, "deer", (12, 5));
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<AmbushAI>(lynx).is_some());
+        assert_eq!(app.world().get::<Hidden>(lynx).map(|hidden| hidden.stealth), Some(0.5), "It lies in wait on its own tile");
+        assert!(app.world().get::<Charge>(lynx).is_none(), "The deer is out of strike range");
+
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(7, 5);
+        app.world_mut().get_mut::<Transform>(deer).unwrap().translation = Vec3::new(x, y, 100.0);
+        app.update();
+        app.update();
+
+        let charge = app.world().get::<Charge>(lynx).expect("The lynx should burst out at the deer");
+        assert_eq!(charge.direction, Vec2::X);
+        assert_eq!(charge.speed, DEFAULT_AMBUSH_BURST_SPEED, "Bursts start at full speed");
+        assert!(app.world().get::<Hidden>(lynx).is_none(), "It gives itself away");
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::animation::{Facing, WalkAnimation, walk_animation_system};
+    use crate::systems::level_of_detail::{Detail, LevelOfDetail};
+    use crate::systems::pawn::TilesetManager;
+    use crate::tests::setup_test_app;
+
+    const ANIMATED_TILESET_YAML: &str = r#"
+tileset_name: pawns
+tile_size: 16
+tiles_per_row: 16
+total_tiles: 7
+sprites:
+- { name: rabbit, index: 0, x: 0, y: 0, width: 16, height: 16 }
+- { name: rabbit_down_0, index: 1, x: 16, y: 0, width: 16, height: 16 }
+- { name: rabbit_down_1, index: 2, x: 32, y: 0, width: 16, height: 16 }
+- { name: rabbit_right_0, index: 3, x: 48, y: 0, width: 16, height: 16 }
+- { name: rabbit_right_1, index: 4, x: 64, y: 0, width: 16, height: 16 }
+- { name: rabbit_right_2, index: 5, x: 80, y: 0, width: 16, height: 16 }
+- { name: wolf, index: 6, x: 96, y: 0, width: 16, height: 16 }
+animations:
+  rabbit:
+    down: [rabbit_down_0, rabbit_down_1]
+    right: [rabbit_right_0, rabbit_right_1, rabbit_right_2]
+    frame_distance: 4
+"#;
+
+    fn create_tileset_manager() -> TilesetManager {
+        let mut tileset_manager = TilesetManager::default();
+        tileset_manager.load_tileset_from_str("pawns", ANIMATED_TILESET_YAML).expect("Tileset should parse");
+        tileset_manager
+    }
+
+    fn setup_animation_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_tileset_manager());
+        app.init_resource::<LevelOfDetail>();
+        app.add_systems(Update, walk_animation_system);
+        app
+    }
+
+    fn spawn_animated_pawn(app: &mut App) -> Entity {
+        let walk_animation = WalkAnimation::for_sprite("tileset::pawns::rabbit", &create_tileset_manager())
+            .expect("Rabbit should have a walk cycle");
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, 0.0, 100.0),
+            Sprite {
+                texture_atlas: Some(TextureAtlas { layout: Handle::default(), index: 0 }),
+                ..default()
+            },
+            walk_animation,
+        )).id()
+    }
+
+    fn move_by(app: &mut App, entity: Entity, offset: Vec2) {
+        let mut transform = app.world_mut().get_mut::<Transform>(entity).unwrap();
+        transform.translation += offset.extend(0.0);
+        app.update();
+    }
+
+    fn atlas_index(app: &App, entity: Entity) -> usize {
+        app.world().get::<Sprite>(entity).unwrap().texture_atlas.as_ref().unwrap().index
+    }
+
+    #[test]
+    fn test_only_sprites_with_animations_get_a_walk_cycle() {
+        let tileset_manager = create_tileset_manager();
+        assert!(WalkAnimation::for_sprite("tileset::pawns::rabbit", &tileset_manager).is_some());
+        assert!(WalkAnimation::for_sprite("tileset::pawns::wolf", &tileset_manager).is_none());
+        assert!(WalkAnimation::for_sprite("sprites/rabbit.png", &tileset_manager).is_none());
+    }
+
+    #[test]
+    fn test_standing_pawn_shows_first_frame() {
+        let mut app = setup_animation_app();
+        let pawn = spawn_animated_pawn(&mut app);
+
+        app.update();
+        app.update();
+
+        assert_eq!(atlas_index(&app, pawn), 1, "Standing pawn should show rabbit_down_0");
+    }
+
+    #[test]
+    fn test_walk_cycles_stop_when_zoomed_out() {
+        let mut app = setup_animation_app();
+        let pawn = spawn_animated_pawn(&mut app);
+        app.update();
+
+        app.world_mut().resource_mut::<LevelOfDetail>().detail = Detail::Reduced;
+        move_by(&mut app, pawn, Vec2::new(2.0, 0.5));
+        assert_eq!(app.world().get::<WalkAnimation>(pawn).unwrap().facing, Facing::Down);
+        assert_eq!(atlas_index(&app, pawn), 1, "Left standing as it was");
+
+        app.world_mut().resource_mut::<LevelOfDetail>().detail = Detail::Full;
+        move_by(&mut app, pawn, Vec2::new(2.0, 0.5));
+        assert_eq!(app.world().get::<WalkAnimation>(pawn).unwrap().facing, Facing::Right);
+    }
+
+    #[test]
+    fn test_pawn_faces_the_way_it_moves() {
+        let mut app = setup_animation_app();
+        let pawn = spawn_animated_pawn(&mut app);
+        app.update();
+
+        move_by(&mut app, pawn, Vec2::new(2.0, 0.5));
+        assert_eq!(app.world().get::<WalkAnimation>(pawn).unwrap().facing, Facing::Right);
+        assert_eq!(atlas_index(&app, pawn), 3, "Pawn walking right should show rabbit_right_0");
+
+        // Up has no frames of its own, so it falls back to down
+        move_by(&mut app, pawn, Vec2::new(0.0, 2.0));
+        assert_eq!(app.world().get::<WalkAnimation>(pawn).unwrap().facing, Facing::Up);
+        assert_eq!(atlas_index(&app, pawn), 1);
+    }
+
+    #[test]
+    fn test_frames_advance_with_distance_walked() {
+        let mut app = setup_animation_app();
+        let slow = spawn_animated_pawn(&mut app);
+        let fast = spawn_animated_pawn(&mut app);
+        app.update();
+
+        // Four frames of walking at 1 and 4 pixels a frame, with a frame every 4 pixels
+        let mut slow_indices = Vec::new();
+        let mut fast_indices = Vec::new();
+        for _ in 0..4 {
+            app.world_mut().get_mut::<Transform>(slow).unwrap().translation.x += 1.0;
+            move_by(&mut app, fast, Vec2::new(4.0, 0.0));
+            slow_indices.push(atlas_index(&app, slow));
+            fast_indices.push(atlas_index(&app, fast));
+        }
+
+        assert_eq!(slow_indices, vec![3, 3, 3, 4], "Slow pawn should step once in 4 pixels");
+        assert_eq!(fast_indices, vec![4, 5, 3, 4], "Fast pawn should step every frame and loop the cycle");
+    }
+
+    #[test]
+    fn test_stopping_returns_to_first_frame() {
+        let mut app = setup_animation_app();
+        let pawn = spawn_animated_pawn(&mut app);
+        app.update();
+
+        move_by(&mut app, pawn, Vec2::new(4.0, 0.0));
+        assert_eq!(atlas_index(&app, pawn), 4);
+
+        app.update();
+        assert_eq!(atlas_index(&app, pawn), 3, "Stopped pawn should stand facing the way it was walking");
+    }
+}
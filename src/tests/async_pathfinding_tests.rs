@@ -3,11 +3,13 @@ mod tests {
     use bevy::prelude::*;
     use crate::systems::async_pathfinding::{
         PathfindingRequest, PathfindingPriority, PathfindingTask, 
-        spawn_cached_pathfinding_tasks,
-        PathfindingRequestCounter, GlobalPathfindingCache
+        spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding,
+        PathfindingRequestCounter, GlobalPathfindingCache,
+        PathfindingCompleted, PathfindingFailed, PathfindFailure, request_pathfinding
     };
+    use crate::systems::pathfinding_heatmap::PathfindingHeatmap;
     use crate::systems::world_gen::TerrainMap;
-    use crate::systems::pawn::{Size, Pawn};
+    use crate::systems::pawn::{Size, Pawn, PawnTarget};
     use crate::tests::{setup_test_app, create_test_ground_configs};
 
     fn create_simple_terrain() -> TerrainMap {
@@ -58,6 +60,9 @@ mod tests {
         app.insert_resource(create_test_ground_configs());
         app.insert_resource(PathfindingRequestCounter::default());
         app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
         
         // Add only the spawning system (not completion handling to avoid async complexity)
         app.add_systems(Update, spawn_cached_pathfinding_tasks);
@@ -100,6 +105,9 @@ mod tests {
         
         // Access the terrain map to populate cache (simplified test)
         app.insert_resource(cache);
+        app.insert_resource(PathfindingHeatmap::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
         app.add_systems(Update, spawn_cached_pathfinding_tasks);
 
         // Create entity with pathfinding request that should hit cache
@@ -138,4 +146,131 @@ mod tests {
         assert_eq!(id_max, u64::MAX);
         assert_eq!(id_wrapped, 0);
     }
-}
\ No newline at end of file
+
+    #[derive(Resource, Default)]
+    struct PathfindingOutcomes {
+        completed: Vec<PathfindingCompleted>,
+        failed: Vec<PathfindingFailed>,
+    }
+
+    fn collect_pathfinding_outcomes(
+        mut outcomes: ResMut<PathfindingOutcomes>,
+        mut completed_events: EventReader<PathfindingCompleted>,
+        mut failed_events: EventReader<PathfindingFailed>,
+    ) {
+        outcomes.completed.extend(completed_events.read().cloned());
+        outcomes.failed.extend(failed_events.read().cloned());
+    }
+
+    fn setup_outcome_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_simple_terrain());
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(PathfindingRequestCounter::default());
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.insert_resource(PathfindingOutcomes::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, (
+            spawn_cached_pathfinding_tasks,
+            handle_completed_cached_pathfinding.after(spawn_cached_pathfinding_tasks),
+            collect_pathfinding_outcomes.after(handle_completed_cached_pathfinding),
+        ));
+        app
+    }
+
+    fn run_until_resolved(app: &mut App, entity: Entity) {
+        for _ in 0..200 {
+            app.update();
+            if app.world().get::<PathfindingRequest>(entity).is_none() && app.world().get::<PathfindingTask>(entity).is_none() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        panic!("Pathfinding request never resolved");
+    }
+
+    #[test]
+    fn test_completed_event_names_the_entity() {
+        let mut app = setup_outcome_app();
+        let entity = app.world_mut().spawn((
+            Transform::from_xyz(-32.0, -32.0, 0.0),
+            PathfindingRequest::new((-32.0, -32.0), (32.0, 32.0), 1.0),
+        )).id();
+
+        run_until_resolved(&mut app, entity);
+        app.update();
+
+        let outcomes = app.world().resource::<PathfindingOutcomes>();
+        assert!(outcomes.failed.is_empty());
+        assert_eq!(outcomes.completed.len(), 1);
+        assert_eq!(outcomes.completed[0].entity, entity);
+        assert!(app.world().get::<PawnTarget>(entity).is_some_and(|target| !target.path.is_empty()));
+    }
+
+    #[test]
+    fn test_events_carry_the_id_request_pathfinding_returned() {
+        let mut app = setup_outcome_app();
+        // The second search for the same route is answered from the cache
+        for answered in 1..=2 {
+            let entity = app.world_mut().spawn(Transform::from_xyz(-32.0, -32.0, 0.0)).id();
+            let request_id = app.world_mut().resource_scope(|world, mut request_counter: Mut<PathfindingRequestCounter>| {
+                request_pathfinding(&mut world.commands(), &mut request_counter, entity, (-32.0, -32.0), (32.0, 32.0), 1.0)
+            });
+            run_until_resolved(&mut app, entity);
+            app.update();
+
+            let outcomes = app.world().resource::<PathfindingOutcomes>();
+            assert_eq!(outcomes.completed.len(), answered);
+            let completed = &outcomes.completed[answered - 1];
+            assert_eq!((completed.entity, completed.request_id), (entity, request_id));
+            assert_eq!(completed.path_length, app.world().get::<PawnTarget>(entity).unwrap().path.len());
+        }
+        let ids: Vec<u64> = app.world().resource::<PathfindingOutcomes>().completed.iter().map(|completed| completed.request_id).collect();
+        assert_ne!(ids[0], ids[1], "Each request gets its own id");
+    }
+
+    #[test]
+    fn test_failed_event_reports_out_of_bounds() {
+        let mut app = setup_outcome_app();
+        let entity = app.world_mut().spawn((
+            Transform::from_xyz(-32.0, -32.0, 0.0),
+            PathfindingRequest::new((-32.0, -32.0), (5000.0, 5000.0), 1.0),
+        )).id();
+
+        run_until_resolved(&mut app, entity);
+        app.update();
+
+        let outcomes = app.world().resource::<PathfindingOutcomes>();
+        assert!(outcomes.completed.is_empty());
+        assert_eq!(outcomes.failed.len(), 1);
+        assert_eq!(outcomes.failed[0].entity, entity);
+        assert_eq!(outcomes.failed[0].reason, PathfindFailure::OutOfBounds);
+    }
+
+    #[test]
+    fn test_cached_no_path_still_reports_failure() {
+        let mut app = setup_outcome_app();
+        let ground_configs = create_test_ground_configs();
+        let stone_type = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+        // Wall off the goal tile so there is no path to it
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(4, 4, stone_type);
+        let (goal_x, goal_y) = app.world().resource::<TerrainMap>().tile_to_world_coords(4, 4);
+
+        // The first request computes "no path", the second is answered from the cache
+        for expected_failures in 1..=2 {
+            let entity = app.world_mut().spawn((
+                Transform::from_xyz(-32.0, -32.0, 0.0),
+                PathfindingRequest::new((-32.0, -32.0), (goal_x, goal_y), 1.0),
+            )).id();
+            run_until_resolved(&mut app, entity);
+            app.update();
+
+            let outcomes = app.world().resource::<PathfindingOutcomes>();
+            assert_eq!(outcomes.failed.len(), expected_failures);
+            assert_eq!(outcomes.failed[expected_failures - 1].entity, entity);
+            assert_eq!(outcomes.failed[expected_failures - 1].reason, PathfindFailure::NoPath);
+        }
+    }
+}
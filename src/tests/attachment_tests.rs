@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::components::AttachedTo;
+    use crate::systems::attachment::despawn_detached_entities;
+    use crate::tests::setup_test_app;
+
+    #[test]
+    fn test_attached_entities_follow_owner_despawn() {
+        let mut app = setup_test_app();
+        app.add_systems(Update, despawn_detached_entities);
+
+        let owner = app.world_mut().spawn_empty().id();
+        let survivor_owner = app.world_mut().spawn_empty().id();
+
+        let attached = app.world_mut().spawn(AttachedTo(owner)).id();
+        let attached_child = app.world_mut().spawn_empty().id();
+        app.world_mut().entity_mut(attached).add_child(attached_child);
+        let unrelated = app.world_mut().spawn(AttachedTo(survivor_owner)).id();
+
+        app.update();
+        assert!(app.world().get_entity(attached).is_ok(), "Attachments of live owners must be kept");
+
+        app.world_mut().despawn(owner);
+        app.update();
+
+        assert!(app.world().get_entity(attached).is_err(), "Attachment should be despawned with its owner");
+        assert!(app.world().get_entity(attached_child).is_err(), "Attachment children should be despawned too");
+        assert!(app.world().get_entity(unrelated).is_ok(), "Other attachments should be untouched");
+    }
+}
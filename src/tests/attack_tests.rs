@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::sprite::Anchor;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::attack::{Attacking, AttackPhase, WINDUP_TINT, cancel_abandoned_attacks, attack_visual_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, AttackConfig};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const ATTACK_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 1
+  reach: 1
+  attack:
+    windup: 0.5
+    strike: 0.2
+    recover: 0.3
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    looking_for_food: eat
+  eats:
+    pawns: [small, animal]
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 100
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_attack_app() -> (App, Entity, Entity) {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(ATTACK_PAWNS_YAML).expect("Attack config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, (
+            cancel_abandoned_attacks.before(hunt_solo_ai_system),
+            hunt_solo_ai_system,
+            attack_visual_system.after(hunt_solo_ai_system),
+        ));
+
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(100),
+            Sprite::default(),
+            Transform::from_xyz(10.0, 0.0, 100.0),
+        )).id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(rabbit);
+        hunt_ai.last_attack_time = 1.0;
+        let wolf = app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            hunt_ai,
+            Sprite::default(),
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id();
+        (app, wolf, rabbit)
+    }
+
+    fn rabbit_health(app: &App, rabbit: Entity) -> f32 {
+        app.world().get::<Health>(rabbit).unwrap().current
+    }
+
+    #[test]
+    fn test_attack_phases() {
+        let config = AttackConfig { windup: 0.5, strike: 0.2, recover: 0.3 };
+        let mut attack = Attacking::new(Entity::from_raw(1));
+
+        assert!(!attack.advance(0.3, &config).struck);
+        assert_eq!(attack.phase, AttackPhase::Windup);
+        let step = attack.advance(0.3, &config);
+        assert!(step.struck && !step.finished);
+        assert_eq!(attack.phase, AttackPhase::Strike);
+        attack.advance(0.2, &config);
+        assert_eq!(attack.phase, AttackPhase::Recover);
+        let step = attack.advance(0.3, &config);
+        assert!(!step.struck && step.finished);
+
+        let step = Attacking::new(Entity::from_raw(1)).advance(0.0, &AttackConfig::default());
+        assert!(step.struck && step.finished, "Without durations an attack lands and ends at once");
+    }
+
+    #[test]
+    fn test_damage_lands_after_the_windup() {
+        let (mut app, wolf, rabbit) = setup_attack_app();
+
+        app.update();
+        assert_eq!(rabbit_health(&app, rabbit), 100.0, "Still winding up");
+        assert_eq!(app.world().get::<Attacking>(wolf).unwrap().phase, AttackPhase::Windup);
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(rabbit_health(&app, rabbit), 75.0, "30 strength against 5 defence");
+        assert_eq!(app.world().get::<Attacking>(wolf).unwrap().phase, AttackPhase::Strike);
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.world().get::<Attacking>(wolf).is_none(), "Recovered");
+        assert_eq!(rabbit_health(&app, rabbit), 75.0);
+    }
+
+    #[test]
+    fn test_prey_escapes_during_windup() {
+        let (mut app, wolf, rabbit) = setup_attack_app();
+
+        app.update();
+        app.update();
+        app.world_mut().get_mut::<Transform>(rabbit).unwrap().translation.x = 40.0;
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(rabbit_health(&app, rabbit), 100.0, "The rabbit got out of reach in time");
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, Some(rabbit), "The wolf is still recovering");
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, None, "Eaters don't give chase");
+    }
+
+    #[test]
+    fn test_abandoned_attacks_are_dropped() {
+        let (mut app, wolf, rabbit) = setup_attack_app();
+        app.update();
+        assert!(app.world().get::<Attacking>(wolf).is_some());
+
+        app.world_mut().get_mut::<CurrentBehavior>(wolf).unwrap().state = "idle".to_string();
+        app.update();
+        assert!(app.world().get::<Attacking>(wolf).is_none(), "Stopped hunting");
+        assert_eq!(rabbit_health(&app, rabbit), 100.0);
+    }
+
+    #[test]
+    fn test_attackers_tint_and_lunge() {
+        let (mut app, wolf, _) = setup_attack_app();
+        app.update();
+        app.update();
+        let sprite = app.world().get::<Sprite>(wolf).unwrap();
+        assert_ne!(sprite.color, Color::WHITE, "Reddening as the windup builds");
+        assert_ne!(sprite.color, WINDUP_TINT);
+
+        for _ in 0..4 {
+            app.update();
+        }
+        let sprite = app.world().get::<Sprite>(wolf).unwrap();
+        assert_eq!(sprite.color, Color::WHITE);
+        assert_eq!(sprite.anchor, Anchor::Custom(Vec2::new(-0.3, 0.0)), "Lunging right, towards the rabbit");
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<Sprite>(wolf).unwrap().anchor, Anchor::Center);
+    }
+}
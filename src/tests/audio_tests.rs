@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::audio::{SoundConfig, SoundEffects, attenuation, play_event_sounds, play_footsteps};
+    use crate::systems::camera::CameraController;
+    use crate::systems::footsteps::{Footstep, track_footsteps};
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::pawn::{Pawn, FEET_OFFSET_TILES};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    fn setup_audio_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.insert_resource(SoundConfig::default());
+        app.insert_resource(SoundEffects {
+            attack_hit: Some(Handle::default()),
+            footsteps: [("dirt".to_string(), Handle::default())].into_iter().collect(),
+            ..default()
+        });
+        app.insert_resource(TerrainMap::new(100, 100, 32.0)); // All dirt
+        app.insert_resource(create_test_ground_configs());
+        app.world_mut().spawn((Transform::default(), CameraController));
+        app.add_event::<Footstep>();
+        app.add_systems(Update, (play_event_sounds, track_footsteps, play_footsteps.after(track_footsteps)));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut().spawn((
+            Pawn { pawn_type: "rabbit".to_string() },
+            Transform::from_translation(position.extend(100.0)),
+        )).id()
+    }
+
+    fn sound_volumes(app: &mut App) -> Vec<f32> {
+        let mut query = app.world_mut().query_filtered::<&PlaybackSettings, With<AudioPlayer>>();
+        query.iter(app.world()).map(|settings| settings.volume.get()).collect()
+    }
+
+    #[test]
+    fn test_sounds_fade_with_distance() {
+        assert_eq!(attenuation(0.0, 100.0), 1.0);
+        assert_eq!(attenuation(25.0, 100.0), 0.75);
+        assert_eq!(attenuation(100.0, 100.0), 0.0);
+        assert_eq!(attenuation(250.0, 100.0), 0.0);
+        assert_eq!(attenuation(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_sound_config_defaults() {
+        let config = SoundConfig::from_yaml("death: sounds/death.ogg\nfootsteps:\n  grass: sounds/grass.ogg\n").unwrap();
+        assert_eq!(config.hearing_distance, 30.0);
+        assert_eq!(config.footstep_distance, 1.0);
+        assert_eq!(config.death.as_deref(), Some("sounds/death.ogg"));
+        assert!(config.attack_hit.is_none());
+        assert_eq!(config.footsteps.len(), 1);
+    }
+
+    #[test]
+    fn test_footsteps_sound_as_pawns_walk_within_hearing() {
+        let mut app = setup_audio_app();
+        let tile_size = app.world().resource::<GameConfig>().tile_size;
+        let near = spawn_pawn(&mut app, Vec2::ZERO);
+        let far = spawn_pawn(&mut app, Vec2::new(40.0 * tile_size, 0.0));
+        app.update();
+
+        for pawn in [near, far] {
+            app.world_mut().get_mut::<Transform>(pawn).unwrap().translation.x += 0.5 * tile_size;
+        }
+        app.update();
+        assert!(sound_volumes(&mut app).is_empty(), "Half a tile isn't a step yet");
+
+        for pawn in [near, far] {
+            app.world_mut().get_mut::<Transform>(pawn).unwrap().translation.x += 0.5 * tile_size;
+        }
+        app.update();
+        let volumes = sound_volumes(&mut app);
+        assert_eq!(volumes.len(), 1, "Only the pawn near the middle of the view is heard");
+        let feet = Vec2::new(tile_size, -FEET_OFFSET_TILES * tile_size);
+        assert!((volumes[0] - attenuation(feet.length(), 30.0 * tile_size)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_hits_are_heard_unless_hidden() {
+        let mut app = setup_audio_app();
+        let attacker = spawn_pawn(&mut app, Vec2::ZERO);
+        let seen = spawn_pawn(&mut app, Vec2::ZERO);
+        let hidden = spawn_pawn(&mut app, Vec2::ZERO);
+        app.world_mut().entity_mut(hidden).insert(Visibility::Hidden);
+
+        app.world_mut().send_event(CombatEvent { attacker, victim: seen, damage: 5.0 });
+        app.world_mut().send_event(CombatEvent { attacker, victim: hidden, damage: 5.0 });
+        app.world_mut().send_event(CombatEvent { attacker, victim: seen, damage: 0.0 });
+        app.update();
+
+        assert_eq!(sound_volumes(&mut app), vec![1.0], "One blow that did damage, seen");
+    }
+}
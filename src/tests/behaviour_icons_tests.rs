@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use crate::systems::behaviour_icons::behaviour_icon;
+    use crate::systems::pawn_config::{BehaviourConfig, BehaviourType};
+
+    #[test]
+    fn test_behaviours_map_to_icons() {
+        let icon = |behaviour: BehaviourType| behaviour_icon(Some(&BehaviourConfig::Simple(behaviour)), false);
+        assert_eq!(icon(BehaviourType::Sleep), Some("sleep"));
+        assert_eq!(icon(BehaviourType::HuntSolo), Some("hunt"));
+        assert_eq!(icon(BehaviourType::HuntPack), Some("hunt"));
+        assert_eq!(icon(BehaviourType::Ambush), Some("hunt"));
+        assert_eq!(icon(BehaviourType::Flee), Some("flee"));
+        assert_eq!(icon(BehaviourType::Eat), None);
+        assert_eq!(icon(BehaviourType::Herd), None);
+
+        let script = BehaviourConfig::Script { script: "scripts/dance.rhai".to_string() };
+        assert_eq!(behaviour_icon(Some(&script), false), None);
+        assert_eq!(behaviour_icon(None, false), None);
+    }
+
+    #[test]
+    fn test_asleep_pawns_show_sleep_whatever_their_behaviour() {
+        let flee = BehaviourConfig::Simple(BehaviourType::Flee);
+        assert_eq!(behaviour_icon(Some(&flee), true), Some("sleep"));
+        assert_eq!(behaviour_icon(None, true), Some("sleep"));
+    }
+}
@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{
+        ActiveBehaviour, BehaviourContext, HuntSoloAI, WanderingAI, evaluate_behaviour_tree, behaviour_tree_system,
+        setup_wandering_ai, setup_hunt_solo_ai, flee_ai_system, hunt_solo_ai_system,
+    };
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::needs::needs_behaviour_switching_system;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, PawnTarget, Size};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType, BehaviourNode, BehaviourCondition};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::tests::{setup_test_app, create_test_terrain_map, create_test_ground_configs};
+
+    const TREE_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 5
+  behaviour_tree:
+    selector:
+      - sequence:
+          - condition:
+              predator_within: 5
+          - action: flee
+      - action:
+          wandering:
+            move_interval_min: 1.5
+            move_interval_max: 4.0
+            move_range: 6
+  eats:
+    pawns: []
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 110
+  max_endurance: 10
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviour_tree:
+    selector:
+      - sequence:
+          - condition:
+              not:
+                endurance_above: 0.3
+          - action: hunt_solo
+      - sequence:
+          - condition:
+              prey_within: 1
+          - action: eat
+      - action:
+          wandering:
+            move_interval_min: 3.0
+            move_interval_max: 8.0
+            move_range: 10
+  eats:
+    pawns: [small, animal]
+"#;
+
+    fn create_tree_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(TREE_PAWNS_YAML).expect("Behaviour tree config should parse")
+    }
+
+    fn setup_tree_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_tree_pawn_config());
+        app.insert_resource(GameConfig { tile_size: 16.0, ..GameConfig::default() });
+        app.insert_resource(create_test_terrain_map(20, 20, 16.0));
+        app.insert_resource(create_test_ground_configs());
+        app.add_plugins(SpatialGridPlugin::default());
+        app
+    }
+
+    fn spawn_tree_pawn(app: &mut App, pawn_type: &str, position: Vec3, endurance: f32) -> Entity {
+        let max = if pawn_type == "wolf" { 110 } else { 25 };
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(max),
+            Endurance { current: endurance, max: 10.0, health_loss_timer: 0.0 },
+            Size { value: 1.0 },
+            Transform::from_translation(position),
+        )).id()
+    }
+
+    fn active_action(app: &App, entity: Entity) -> Option<&'static str> {
+        app.world().get::<ActiveBehaviour>(entity).map(|active| active.action.name())
+    }
+
+    #[test]
+    fn test_behaviour_tree_parses_from_yaml() {
+        let config = create_tree_pawn_config();
+        let wolf = config.get_pawn_definition("wolf").unwrap();
+        let tree = wolf.behaviour_tree.as_ref().expect("Wolf should have a behaviour tree");
+
+        let actions: Vec<&str> = tree.actions().iter().map(|action| action.name()).collect();
+        assert_eq!(actions, vec!["hunt_solo", "eat", "wandering"]);
+        assert!(wolf.behaviours.idle.is_none(), "State behaviours are optional once a tree is set");
+    }
+
+    #[test]
+    fn test_tree_picks_first_succeeding_branch() {
+        let config = create_tree_pawn_config();
+        let wolf_tree = config.get_pawn_definition("wolf").unwrap().behaviour_tree.as_ref().unwrap();
+        let rabbit_tree = config.get_pawn_definition("rabbit").unwrap().behaviour_tree.as_ref().unwrap();
+
+        let rested = BehaviourContext { endurance: 1.0, health: 1.0, ..Default::default() };
+        let hungry = BehaviourContext { endurance: 0.2, ..rested.clone() };
+        let prey_adjacent = BehaviourContext { nearest_prey: Some(0.5), ..rested.clone() };
+        let predator_close = BehaviourContext { nearest_predator: Some(3.0), ..rested.clone() };
+        let predator_far = BehaviourContext { nearest_predator: Some(9.0), ..rested.clone() };
+
+        let pick = |tree, context: &BehaviourContext| evaluate_behaviour_tree(tree, context).map(|action| action.name());
+        assert_eq!(pick(wolf_tree, &rested), Some("wandering"));
+        assert_eq!(pick(wolf_tree, &hungry), Some("hunt_solo"));
+        assert_eq!(pick(wolf_tree, &prey_adjacent), Some("eat"));
+        assert_eq!(pick(rabbit_tree, &predator_close), Some("flee"));
+        assert_eq!(pick(rabbit_tree, &predator_far), Some("wandering"));
+    }
+
+    #[test]
+    fn test_sequence_without_action_leaves_pawn_idle() {
+        let tree = BehaviourNode::Selector(vec![
+            BehaviourNode::Sequence(vec![BehaviourNode::Condition(BehaviourCondition::HealthBelow(0.5))]),
+            BehaviourNode::Action(BehaviourConfig::Simple(BehaviourType::Flee)),
+        ]);
+        let hurt = BehaviourContext { endurance: 1.0, health: 0.4, ..Default::default() };
+        let healthy = BehaviourContext { endurance: 1.0, health: 1.0, ..Default::default() };
+
+        assert!(evaluate_behaviour_tree(&tree, &hurt).is_none(), "A succeeding branch with no action should stop the selector");
+        assert!(matches!(evaluate_behaviour_tree(&tree, &healthy), Some(BehaviourConfig::Simple(BehaviourType::Flee))));
+    }
+
+    #[test]
+    fn test_tree_system_drives_existing_behaviours() {
+        let mut app = setup_tree_app();
+        app.add_systems(Update, (
+            behaviour_tree_system,
+            setup_wandering_ai.after(behaviour_tree_system),
+            setup_hunt_solo_ai.after(behaviour_tree_system),
+            needs_behaviour_switching_system.after(behaviour_tree_system),
+        ));
+
+        let hungry_wolf = spawn_tree_pawn(&mut app, "wolf", Vec3::new(-100.0, -100.0, 100.0), 1.0);
+        let rabbit = spawn_tree_pawn(&mut app, "rabbit", Vec3::new(-100.0 + 3.0 * 16.0, -100.0, 100.0), 10.0);
+        let lone_rabbit = spawn_tree_pawn(&mut app, "rabbit", Vec3::new(100.0, 100.0, 100.0), 10.0);
+        app.update();
+
+        assert_eq!(active_action(&app, hungry_wolf), Some("hunt_solo"));
+        assert!(app.world().get::<HuntSoloAI>(hungry_wolf).is_some(), "Hunting reuses the hunt_solo AI");
+        assert_eq!(app.world().get::<CurrentBehavior>(hungry_wolf).unwrap().state, "hunt_solo",
+                   "Endurance switching shouldn't override tree-driven pawns");
+
+        assert_eq!(active_action(&app, rabbit), Some("flee"));
+        assert_eq!(active_action(&app, lone_rabbit), Some("wandering"));
+        assert!(app.world().get::<WanderingAI>(lone_rabbit).is_some(), "Wandering reuses the wandering AI");
+    }
+
+    #[test]
+    fn test_switching_action_drops_previous_target() {
+        let mut app = setup_tree_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(300)));
+        app.add_systems(Update, behaviour_tree_system);
+
+        let wolf = spawn_tree_pawn(&mut app, "wolf", Vec3::new(-100.0, -100.0, 100.0), 10.0);
+        app.update();
+        assert_eq!(active_action(&app, wolf), Some("wandering"));
+
+        app.world_mut().entity_mut(wolf).insert(PawnTarget::new(Vec3::new(0.0, 0.0, 100.0)));
+        app.world_mut().get_mut::<Endurance>(wolf).unwrap().current = 1.0;
+        app.update();
+
+        assert_eq!(active_action(&app, wolf), Some("hunt_solo"));
+        assert!(app.world().get::<PawnTarget>(wolf).is_none(), "The wander target should be dropped when the wolf starts hunting");
+    }
+
+    #[test]
+    fn test_fleeing_pawn_runs_away_from_predator() {
+        let mut app = setup_tree_app();
+        app.add_systems(Update, (behaviour_tree_system, flee_ai_system.after(behaviour_tree_system)));
+
+        // Grass border row of the test map, rabbit east of the wolf
+        let wolf_position = Vec3::new(-40.0, -152.0, 100.0);
+        let rabbit_position = Vec3::new(8.0, -152.0, 100.0);
+        spawn_tree_pawn(&mut app, "wolf", wolf_position, 10.0);
+        let rabbit = spawn_tree_pawn(&mut app, "rabbit", rabbit_position, 10.0);
+        app.update();
+
+        let request = app.world().get::<PathfindingRequest>(rabbit).expect("Fleeing rabbit should request a path");
+        let away = Vec2::new(request.goal.0, request.goal.1) - rabbit_position.truncate();
+        let towards_wolf = (wolf_position - rabbit_position).truncate();
+        assert!(away.dot(towards_wolf) <= 0.0, "Rabbit should run away from the wolf, not towards it");
+        assert!(away.length() > 16.0 * 5.0, "Rabbit should flee several tiles");
+    }
+
+    #[test]
+    fn test_eating_pawn_attacks_in_reach_but_never_chases() {
+        let mut app = setup_tree_app();
+        app.add_systems(Update, hunt_solo_ai_system);
+
+        let eat = ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Eat) };
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.search_timer = 2.0;
+        hunt_ai.last_attack_time = 10.0;
+        let wolf = spawn_tree_pawn(&mut app, "wolf", Vec3::new(-100.0, -100.0, 100.0), 10.0);
+        app.world_mut().entity_mut(wolf).insert((eat.clone(), hunt_ai));
+        let far_rabbit = spawn_tree_pawn(&mut app, "rabbit", Vec3::new(-100.0 + 5.0 * 16.0, -100.0, 100.0), 10.0);
+
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, None, "Eaters don't pick prey out of reach");
+        assert!(app.world().get::<PathfindingRequest>(wolf).is_none(), "Eaters never chase");
+
+        let near_rabbit = spawn_tree_pawn(&mut app, "rabbit", Vec3::new(-100.0 + 16.0, -100.0, 100.0), 10.0);
+        app.world_mut().get_mut::<HuntSoloAI>(wolf).unwrap().search_timer = 2.0;
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<Health>(near_rabbit).unwrap().current < 25.0, "Prey within reach gets eaten");
+        assert_eq!(app.world().get::<Health>(far_rabbit).unwrap().current, 25.0);
+        assert!(app.world().get::<PathfindingRequest>(wolf).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::bestiary::{BestiaryProgress, DiscoveryLevel, describe_species, behaviour_summary, record_bestiary_encounters};
+    use crate::systems::pawn::Pawn;
+    use crate::systems::pawn_config::{PawnDefinition, PawnBehaviours, PawnEats, ActivitySchedule, BehaviourConfig, BehaviourType, WanderingConfig};
+    use crate::resources::GameConfig;
+    use crate::tests::setup_test_app;
+
+    fn create_wolf_definition() -> PawnDefinition {
+        PawnDefinition {
+            sprite: "tileset::pawns::wolf".to_string(),
+            tags: vec!["medium".to_string(), "animal".to_string()],
+            move_speed: 120.0,
+            max_health: 110,
+            max_endurance: 10,
+            strength: 30,
+            defence: 10,
+            attack_speed: 3.0,
+            reach: 1,
+            attack: None,
+            size: 1.0,
+            spawn_count: 1,
+            faction: None,
+            behaviours: PawnBehaviours {
+                idle: Some(BehaviourConfig::Wandering {
+                    wandering: WanderingConfig {
+                        move_interval_min: 3.0,
+                        move_interval_max: 8.0,
+                        move_range: 10,
+                    },
+                }),
+                hunted: Some(BehaviourConfig::Simple(BehaviourType::Null)),
+                looking_for_food: Some(BehaviourConfig::Simple(BehaviourType::HuntSolo)),
+                eat: None,
+                controlled: None,
+                flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
+            },
+            eats: PawnEats { pawns: vec!["small".to_string(), "animal".to_string()], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim: false,
+            swim_speed: None,
+            swim_endurance_multiplier: None,
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
+        }
+    }
+
+    #[test]
+    fn test_discovery_levels_only_increase() {
+        let mut progress = BestiaryProgress::default();
+        assert_eq!(progress.level("wolf"), DiscoveryLevel::Unknown);
+
+        assert!(progress.record_kill("wolf"));
+        assert_eq!(progress.level("wolf"), DiscoveryLevel::Killed);
+
+        // Encountering again must not downgrade what we already know
+        assert!(!progress.record_encounter("wolf"));
+        assert_eq!(progress.level("wolf"), DiscoveryLevel::Killed);
+
+        assert!(progress.unlock("wolf", DiscoveryLevel::Tamed));
+        assert_eq!(progress.level("wolf"), DiscoveryLevel::Tamed);
+    }
+
+    #[test]
+    fn test_describe_species_reveals_detail_by_level() {
+        let wolf = create_wolf_definition();
+
+        let unknown = describe_species("wolf", &wolf, DiscoveryLevel::Unknown);
+        assert!(!unknown.contains("wolf"), "Unknown species should not reveal its name");
+
+        let encountered = describe_species("wolf", &wolf, DiscoveryLevel::Encountered);
+        assert!(encountered.contains("wolf [medium, animal]"));
+        assert!(!encountered.contains("STR 30"));
+
+        let killed = describe_species("wolf", &wolf, DiscoveryLevel::Killed);
+        assert!(killed.contains("STR 30"));
+        assert!(!killed.contains("Eats: small + animal"));
+
+        let tamed = describe_species("wolf", &wolf, DiscoveryLevel::Tamed);
+        assert!(tamed.contains("Eats: small + animal"));
+        assert!(tamed.contains("looking_for_food: hunt_solo"));
+    }
+
+    #[test]
+    fn test_behaviour_summary_skips_null_behaviours() {
+        let summary = behaviour_summary(&create_wolf_definition());
+        assert_eq!(summary, vec!["idle: wandering".to_string(), "looking_for_food: hunt_solo".to_string()]);
+    }
+
+    #[test]
+    fn test_encounters_recorded_near_player() {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.insert_resource(BestiaryProgress::default());
+        app.add_systems(Update, record_bestiary_encounters);
+
+        let tile_size = GameConfig::default().tile_size;
+        app.world_mut().spawn((Pawn::new("player".to_string()), Transform::from_xyz(0.0, 0.0, 100.0)));
+        app.world_mut().spawn((Pawn::new("rabbit".to_string()), Transform::from_xyz(2.0 * tile_size, 0.0, 100.0)));
+        app.world_mut().spawn((Pawn::new("wolf".to_string()), Transform::from_xyz(50.0 * tile_size, 0.0, 100.0)));
+
+        app.update();
+
+        let progress = app.world().resource::<BestiaryProgress>();
+        assert_eq!(progress.level("rabbit"), DiscoveryLevel::Encountered);
+        assert_eq!(progress.level("wolf"), DiscoveryLevel::Unknown, "Distant species should stay unknown");
+        assert_eq!(progress.level("player"), DiscoveryLevel::Unknown, "The player is not a bestiary entry");
+    }
+}
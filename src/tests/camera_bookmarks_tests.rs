@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::camera::{CameraController, CameraFollow};
+    use crate::systems::camera_bookmarks::{CameraBookmark, CameraBookmarks, camera_bookmark_input, camera_bookmark_travel};
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::sim_tick::{SimulationSpeed, simulation_speed_input};
+    use crate::tests::setup_test_app;
+
+    fn setup_bookmarks_app() -> (App, Entity) {
+        let mut app = setup_test_app();
+        app.insert_resource(InputMap::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.insert_resource(CameraFollow::default());
+        app.insert_resource(CameraBookmarks::default());
+        app.insert_resource(SimulationSpeed::default());
+        app.add_systems(Update, (simulation_speed_input, camera_bookmark_input, camera_bookmark_travel).chain());
+        let camera = app.world_mut().spawn((
+            Camera2d,
+            CameraController,
+            Transform::default(),
+            OrthographicProjection::default_2d(),
+        )).id();
+        app.update(); // Real time only starts counting after the first frame
+        (app, camera)
+    }
+
+    /// Press `key` for a frame with `modifier` held
+    fn press_with(app: &mut App, modifier: KeyCode, key: KeyCode) {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(modifier);
+        keys.press(key);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release_all();
+        keys.clear();
+    }
+
+    fn place_camera(app: &mut App, camera: Entity, position: Vec2, zoom: f32) {
+        app.world_mut().get_mut::<Transform>(camera).unwrap().translation = position.extend(0.0);
+        app.world_mut().get_mut::<OrthographicProjection>(camera).unwrap().scale = zoom;
+    }
+
+    fn camera_view(app: &App, camera: Entity) -> (Vec2, f32) {
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        let projection = app.world().get::<OrthographicProjection>(camera).unwrap();
+        (transform.translation.truncate(), projection.scale)
+    }
+
+    #[test]
+    fn test_ctrl_and_a_number_stores_the_view() {
+        let (mut app, camera) = setup_bookmarks_app();
+        place_camera(&mut app, camera, Vec2::new(120.0, -40.0), 2.0);
+        press_with(&mut app, KeyCode::ControlLeft, KeyCode::Digit3);
+
+        let bookmarks = app.world().resource::<CameraBookmarks>();
+        assert_eq!(bookmarks.slots[2], Some(CameraBookmark { x: 120.0, y: -40.0, zoom: 2.0 }));
+        assert!(bookmarks.slots.iter().enumerate().all(|(slot, bookmark)| slot == 2 || bookmark.is_none()));
+        assert_eq!(app.world().resource::<SimulationSpeed>().multiplier, 1.0, "Storing a bookmark shouldn't change the speed");
+    }
+
+    #[test]
+    fn test_alt_and_a_number_glides_back() {
+        let (mut app, camera) = setup_bookmarks_app();
+        place_camera(&mut app, camera, Vec2::new(200.0, 100.0), 2.0);
+        press_with(&mut app, KeyCode::ControlLeft, KeyCode::Digit2);
+        place_camera(&mut app, camera, Vec2::ZERO, 1.0);
+        app.world_mut().resource_mut::<CameraFollow>().target = Some(camera);
+
+        press_with(&mut app, KeyCode::AltLeft, KeyCode::Digit2);
+        assert_eq!(app.world().resource::<CameraFollow>().target, None, "Going to a bookmark stops following");
+        assert_eq!(app.world().resource::<SimulationSpeed>().multiplier, 1.0, "Recalling a bookmark shouldn't change the speed");
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        app.update();
+        let (position, zoom) = camera_view(&app, camera);
+        assert!(position.x > 0.0 && position.x < 200.0, "The camera should glide, not jump: {:?}", position);
+        assert!(zoom > 1.0 && zoom < 2.0);
+
+        std::thread::sleep(std::time::Duration::from_millis(450));
+        app.update();
+        assert_eq!(camera_view(&app, camera), (Vec2::new(200.0, 100.0), 2.0));
+        assert!(app.world().resource::<CameraBookmarks>().travel.is_none());
+    }
+
+    #[test]
+    fn test_empty_slots_and_plain_numbers_leave_the_camera_alone() {
+        let (mut app, camera) = setup_bookmarks_app();
+        place_camera(&mut app, camera, Vec2::new(50.0, 50.0), 1.0);
+        press_with(&mut app, KeyCode::AltLeft, KeyCode::Digit5);
+        assert!(app.world().resource::<CameraBookmarks>().travel.is_none());
+
+        press_with(&mut app, KeyCode::ShiftLeft, KeyCode::Digit2);
+        assert!(app.world().resource::<CameraBookmarks>().slots.iter().all(Option::is_none));
+        assert_eq!(app.world().resource::<SimulationSpeed>().multiplier, 2.0, "The number row alone still sets the speed");
+        assert_eq!(camera_view(&app, camera), (Vec2::new(50.0, 50.0), 1.0));
+    }
+}
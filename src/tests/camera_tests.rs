@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::camera::{CameraController, CameraFollow, camera_movement, clamp_to_map, follow_camera, keep_camera_over_map, toggle_camera_follow};
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::pawn::Pawn;
+    use crate::systems::selection::Selected;
+    use crate::tests::setup_test_app;
+
+    fn setup_camera_app(camera_bounds_padding: f32) -> (App, Entity) {
+        let mut app = setup_test_app();
+        // A 32x32 map of 16 pixel tiles is 512 pixels across
+        app.insert_resource(GameConfig { camera_bounds_padding, ..GameConfig::default() });
+        app.add_systems(Update, keep_camera_over_map);
+        app.world_mut().spawn(Window {
+            resolution: (200.0, 100.0).into(),
+            ..default()
+        });
+        let camera = app.world_mut().spawn((
+            Camera2d,
+            CameraController,
+            Transform::default(),
+            OrthographicProjection::default_2d(),
+        )).id();
+        (app, camera)
+    }
+
+    fn move_camera(app: &mut App, camera: Entity, position: Vec2, scale: f32) -> Vec2 {
+        app.world_mut().get_mut::<Transform>(camera).unwrap().translation = position.extend(0.0);
+        app.world_mut().get_mut::<OrthographicProjection>(camera).unwrap().scale = scale;
+        app.update();
+        app.world().get::<Transform>(camera).unwrap().translation.truncate()
+    }
+
+    #[test]
+    fn test_clamp_keeps_the_view_inside_the_map_and_padding() {
+        let half_map = Vec2::new(256.0, 256.0);
+        let half_view = Vec2::new(100.0, 50.0);
+        assert_eq!(clamp_to_map(Vec2::new(10.0, -20.0), half_view, half_map, 0.0), Vec2::new(10.0, -20.0));
+        assert_eq!(clamp_to_map(Vec2::new(1000.0, -1000.0), half_view, half_map, 0.0), Vec2::new(156.0, -206.0));
+        assert_eq!(clamp_to_map(Vec2::new(1000.0, 0.0), half_view, half_map, 32.0), Vec2::new(188.0, 0.0));
+        // Zoomed out past the whole map, the view stays centred on it
+        assert_eq!(clamp_to_map(Vec2::new(50.0, 50.0), Vec2::splat(400.0), half_map, 0.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_camera_is_pulled_back_over_the_map() {
+        let (mut app, camera) = setup_camera_app(0.0);
+        assert_eq!(move_camera(&mut app, camera, Vec2::new(5000.0, 0.0), 1.0), Vec2::new(156.0, 0.0));
+        // Zooming out shows more, so the camera has to come further in
+        assert_eq!(move_camera(&mut app, camera, Vec2::new(5000.0, 0.0), 2.0), Vec2::new(56.0, 0.0));
+        assert_eq!(move_camera(&mut app, camera, Vec2::new(-30.0, 40.0), 1.0), Vec2::new(-30.0, 40.0), "Inside the map it's left alone");
+    }
+
+    #[test]
+    fn test_padding_lets_the_view_past_the_edge() {
+        let (mut app, camera) = setup_camera_app(2.0);
+        assert_eq!(move_camera(&mut app, camera, Vec2::new(0.0, -5000.0), 1.0), Vec2::new(0.0, -238.0));
+    }
+
+    fn setup_follow_app() -> (App, Entity) {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.insert_resource(InputMap::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.insert_resource(CameraFollow::default());
+        app.add_systems(Update, (camera_movement, toggle_camera_follow, follow_camera).chain());
+        let camera = app.world_mut().spawn((Camera2d, CameraController, Transform::default())).id();
+        app.update(); // Real time only starts counting after the first frame
+        (app, camera)
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        run_frames(app, 1);
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+    }
+
+    fn run_frames(app: &mut App, frames: usize) {
+        for _ in 0..frames {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            app.update();
+        }
+    }
+
+    fn camera_position(app: &App, camera: Entity) -> Vec2 {
+        app.world().get::<Transform>(camera).unwrap().translation.truncate()
+    }
+
+    #[test]
+    fn test_camera_eases_towards_the_followed_pawn() {
+        let (mut app, camera) = setup_follow_app();
+        let wolf = app.world_mut().spawn((Pawn::new("wolf".to_string()), Transform::from_xyz(100.0, 0.0, 100.0), Selected)).id();
+
+        press(&mut app, KeyCode::KeyL);
+        assert_eq!(app.world().resource::<CameraFollow>().target, Some(wolf));
+        let first_step = camera_position(&app, camera).x;
+        assert!(first_step > 0.0 && first_step < 100.0, "The camera should ease over, not jump: {}", first_step);
+
+        run_frames(&mut app, 30);
+        let closer = camera_position(&app, camera).x;
+        assert!(closer > first_step && closer < 100.0, "The camera should keep closing on the wolf: {}", closer);
+
+        app.world_mut().despawn(wolf);
+        run_frames(&mut app, 1);
+        assert_eq!(app.world().resource::<CameraFollow>().target, None, "Following stops when the pawn is gone");
+    }
+
+    #[test]
+    fn test_panning_or_toggling_stops_following() {
+        let (mut app, camera) = setup_follow_app();
+        app.world_mut().spawn((Pawn::new("wolf".to_string()), Transform::from_xyz(100.0, 0.0, 100.0), Selected));
+
+        press(&mut app, KeyCode::KeyL);
+        press(&mut app, KeyCode::KeyL);
+        assert_eq!(app.world().resource::<CameraFollow>().target, None, "The key toggles following off again");
+
+        press(&mut app, KeyCode::KeyL);
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::ArrowLeft);
+        run_frames(&mut app, 1);
+        assert_eq!(app.world().resource::<CameraFollow>().target, None, "Panning by hand breaks the follow");
+        let after_pan = camera_position(&app, camera).x;
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().release(KeyCode::ArrowLeft);
+        run_frames(&mut app, 5);
+        assert_eq!(camera_position(&app, camera).x, after_pan, "The camera stays where it was panned to");
+    }
+}
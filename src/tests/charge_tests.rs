@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::charge::{Charge, Stunned, segment_circle_hit, sweep_terrain, charge_impact_damage, charge_movement_system, stun_recovery_system};
+    use crate::systems::pawn::{Pawn, Health, Size};
+    use crate::systems::pawn_config::{PawnConfig, ChargeConfig};
+    use crate::systems::faction::FactionConfig;
+    use crate::resources::GameConfig;
+    use crate::tests::{setup_test_app, create_test_ground_configs, create_test_terrain_map};
+
+    fn create_charge_config() -> ChargeConfig {
+        ChargeConfig {
+            max_speed: 320.0,
+            acceleration: 3200.0,
+            range: 10,
+            cooldown: 1.0,
+        }
+    }
+
+    fn setup_charge_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+        app.insert_resource(GameConfig {
+            tile_size: 32.0,
+            map_width: 20,
+            map_height: 20,
+            ..GameConfig::default()
+        });
+        app.insert_resource(create_test_terrain_map(20, 20, 32.0));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(PawnConfig { pawns: std::collections::HashMap::new(), factions: FactionConfig::default() });
+        app.add_systems(Update, (charge_movement_system, stun_recovery_system));
+        app
+    }
+
+    #[test]
+    fn test_segment_circle_hit() {
+        let start = Vec2::new(0.0, 0.0);
+        let end = Vec2::new(100.0, 0.0);
+
+        let t = segment_circle_hit(start, end, Vec2::new(50.0, 0.0), 10.0).expect("Should hit circle on the path");
+        assert!((t - 0.4).abs() < 0.001, "Should touch the circle edge at 40%, got {}", t);
+
+        assert!(segment_circle_hit(start, end, Vec2::new(50.0, 30.0), 10.0).is_none(), "Circle beside the path should be missed");
+        assert!(segment_circle_hit(start, end, Vec2::new(150.0, 0.0), 10.0).is_none(), "Circle beyond the segment should be missed");
+        assert_eq!(segment_circle_hit(start, end, Vec2::new(5.0, 0.0), 10.0), Some(0.0), "Overlapping circle should hit immediately");
+    }
+
+    #[test]
+    fn test_sweep_terrain_stops_before_impassable() {
+        let terrain_map = create_test_terrain_map(20, 20, 32.0);
+        let ground_configs = create_test_ground_configs();
+
+        // The stone obstacle is at the map centre (10, 10)
+        let start = Vec2::from(terrain_map.tile_to_world_coords(13, 10));
+        let end = Vec2::from(terrain_map.tile_to_world_coords(7, 10));
+        let t = sweep_terrain(start, end, 1.0, &terrain_map, &ground_configs).expect("Sweep should hit the stone");
+        assert!(t > 0.0 && t < 0.5, "Should stop before reaching the stone, got {}", t);
+
+        let clear_end = Vec2::from(terrain_map.tile_to_world_coords(13, 16));
+        assert!(sweep_terrain(start, clear_end, 1.0, &terrain_map, &ground_configs).is_none());
+    }
+
+    #[test]
+    fn test_charge_impact_damage_scales_with_speed_and_size() {
+        let slow = charge_impact_damage(100.0, 1.0, 32.0);
+        let fast = charge_impact_damage(200.0, 1.0, 32.0);
+        let big = charge_impact_damage(100.0, 2.0, 32.0);
+        assert!((fast - slow * 2.0).abs() < 0.001);
+        assert!((big - slow * 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_charge_damages_and_knocks_back_pawn() {
+        let mut app = setup_charge_app();
+        let (start_x, start_y) = app.world().resource::<crate::systems::world_gen::TerrainMap>().tile_to_world_coords(12, 15);
+        let (target_x, target_y) = app.world().resource::<crate::systems::world_gen::TerrainMap>().tile_to_world_coords(16, 15);
+
+        let charger = app.world_mut().spawn((
+            Pawn::new("charger".to_string()),
+            Size { value: 1.0 },
+            Transform::from_xyz(start_x, start_y, 100.0),
+            Charge::new(Vec2::X, &create_charge_config(), 32.0),
+        )).id();
+        let target = app.world_mut().spawn((
+            Pawn::new("target".to_string()),
+            Size { value: 1.0 },
+            Health::new(100),
+            Transform::from_xyz(target_x, target_y, 100.0),
+        )).id();
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world().get::<Charge>(charger).is_none(), "Charge should end on impact");
+        let health = app.world().get::<Health>(target).unwrap();
+        assert!(health.current < 100.0, "Target should take charge damage");
+        let target_transform = app.world().get::<Transform>(target).unwrap();
+        assert!(target_transform.translation.x > target_x, "Target should be knocked back along the charge");
+    }
+
+    #[test]
+    fn test_charge_into_wall_stuns_charger() {
+        let mut app = setup_charge_app();
+        let (start_x, start_y) = app.world().resource::<crate::systems::world_gen::TerrainMap>().tile_to_world_coords(14, 10);
+
+        let charger = app.world_mut().spawn((
+            Pawn::new("charger".to_string()),
+            Size { value: 1.0 },
+            Transform::from_xyz(start_x, start_y, 100.0),
+            Charge::new(Vec2::NEG_X, &create_charge_config(), 32.0),
+        )).id();
+
+        let mut stunned = false;
+        for _ in 0..20 {
+            app.update();
+            if app.world().get::<Stunned>(charger).is_some() {
+                stunned = true;
+                break;
+            }
+        }
+
+        assert!(stunned, "Charging into the stone should stun the charger");
+        assert!(app.world().get::<Charge>(charger).is_none());
+        let stone_x = app.world().resource::<crate::systems::world_gen::TerrainMap>().tile_to_world_coords(10, 10).0;
+        assert!(app.world().get::<Transform>(charger).unwrap().translation.x > stone_x, "Charger should stop before the stone");
+    }
+}
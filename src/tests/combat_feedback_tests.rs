@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::combat_feedback::{DamageNumber, HitFlash, HIT_FLASH_SECS, spawn_combat_feedback, update_combat_feedback};
+    use crate::systems::pawn::{Pawn, Size};
+    use crate::tests::setup_test_app;
+
+    fn setup_feedback_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.insert_resource(GameConfig::default());
+        app.add_systems(Update, (spawn_combat_feedback, update_combat_feedback.after(spawn_combat_feedback)));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut().spawn((Pawn::new("wolf".to_string()), Size { value: 1.0 }, Transform::from_translation(position.extend(100.0)))).id()
+    }
+
+    fn damage_numbers(app: &mut App) -> Vec<(String, Vec3, f32)> {
+        let mut numbers = app.world_mut().query_filtered::<(&Text2d, &Transform, &TextColor), With<DamageNumber>>();
+        numbers.iter(app.world()).map(|(text, transform, color)| (text.0.clone(), transform.translation, color.0.alpha())).collect()
+    }
+
+    #[test]
+    fn test_hits_float_a_number_and_flash_the_victim() {
+        let mut app = setup_feedback_app();
+        let wolf = spawn_pawn(&mut app, Vec2::ZERO);
+        let rabbit = spawn_pawn(&mut app, Vec2::new(64.0, 0.0));
+        app.world_mut().send_event(CombatEvent { attacker: wolf, victim: rabbit, damage: 7.6 });
+        app.world_mut().send_event(CombatEvent { attacker: rabbit, victim: wolf, damage: 0.0 });
+        app.update();
+
+        let mut numbers = damage_numbers(&mut app);
+        numbers.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(numbers.iter().map(|(text, ..)| text.as_str()).collect::<Vec<_>>(), vec!["0", "8"]);
+        let (_, position, _) = numbers[1];
+        assert_eq!(position.x, 64.0, "Over the victim");
+        assert!(position.y > 0.0);
+        assert!(app.world().get::<HitFlash>(rabbit).is_some());
+        assert!(app.world().get::<HitFlash>(wolf).is_none(), "Blows that do no damage don't flash");
+
+        // Numbers rise and fade until they're gone, and the flash dies away first
+        for _ in 0..3 {
+            app.update();
+        }
+        let (_, risen, alpha) = damage_numbers(&mut app)[0];
+        assert!(risen.y > position.y && alpha < 1.0);
+        assert!(app.world().get::<HitFlash>(rabbit).is_none(), "Over after {}s", HIT_FLASH_SECS);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(damage_numbers(&mut app).is_empty());
+    }
+
+    #[test]
+    fn test_combat_feedback_can_be_switched_off() {
+        let mut app = setup_feedback_app();
+        app.world_mut().resource_mut::<GameConfig>().show_damage_numbers = false;
+        let wolf = spawn_pawn(&mut app, Vec2::ZERO);
+        let rabbit = spawn_pawn(&mut app, Vec2::new(64.0, 0.0));
+        app.world_mut().send_event(CombatEvent { attacker: wolf, victim: rabbit, damage: 5.0 });
+        app.update();
+        assert!(damage_numbers(&mut app).is_empty());
+        assert!(app.world().get::<HitFlash>(rabbit).is_none());
+
+        let config = GameConfig::from_yaml(r#"
+world: { map_width: 32, map_height: 32, tile_size: 16.0 }
+camera: { movement_speed: 200.0, zoom_min: 0.1, zoom_max: 10.0, mouse_sensitivity: 1.0 }
+game: { window_title: "Test", target_fps: 60, show_fps: false, show_damage_numbers: false }
+"#).expect("Failed to parse settings");
+        assert!(!config.show_damage_numbers);
+        assert!(GameConfig::default().show_damage_numbers);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::bestiary::{BestiaryProgress, DiscoveryLevel, record_bestiary_kills};
+    use crate::systems::combat::{CombatEvent, DeathEvent};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size, pawn_death_system};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const COMBAT_PAWNS_YAML: &str = r#"
+player:
+  sprite: "tileset::pawns::player"
+  tags: [medium, humanoid]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: eat
+  eats:
+    pawns: [small]
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 40
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_combat_app() -> (App, Entity, Entity) {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(COMBAT_PAWNS_YAML).expect("Combat config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(BestiaryProgress::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, (
+            hunt_solo_ai_system,
+            pawn_death_system.after(hunt_solo_ai_system),
+            record_bestiary_kills.after(pawn_death_system),
+        ));
+
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(40),
+            Transform::from_xyz(10.0, 0.0, 100.0),
+        )).id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(rabbit);
+        hunt_ai.last_attack_time = 1.0;
+        let player = app.world_mut().spawn((
+            Pawn::new("player".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            hunt_ai,
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id();
+        (app, player, rabbit)
+    }
+
+    fn sent<E: Event + Clone>(app: &App) -> Vec<E> {
+        let events = app.world().resource::<Events<E>>();
+        events.get_cursor().read(events).cloned().collect()
+    }
+
+    #[test]
+    fn test_blows_send_combat_events() {
+        let (mut app, player, rabbit) = setup_combat_app();
+        app.update();
+
+        let blows = sent::<CombatEvent>(&app);
+        assert_eq!(blows.len(), 1);
+        assert_eq!((blows[0].attacker, blows[0].victim, blows[0].damage), (player, rabbit, 25.0), "30 strength against 5 defence");
+        assert!(sent::<DeathEvent>(&app).is_empty(), "The rabbit is still standing");
+    }
+
+    #[test]
+    fn test_killing_blow_is_credited() {
+        let (mut app, player, rabbit) = setup_combat_app();
+        for _ in 0..12 {
+            app.update();
+        }
+
+        assert!(app.world().get_entity(rabbit).is_err(), "Two blows finish the rabbit off");
+        let deaths = sent::<DeathEvent>(&app);
+        assert_eq!(deaths.len(), 1);
+        assert_eq!(deaths[0].killer, Some(player));
+        assert_eq!(deaths[0].victim_type, "rabbit");
+        assert_eq!(app.world().resource::<BestiaryProgress>().level("rabbit"), DiscoveryLevel::Killed, "Player kills fill in the bestiary");
+    }
+
+    #[test]
+    fn test_deaths_without_a_blow_have_no_killer() {
+        let mut app = setup_test_app();
+        app.add_systems(Update, pawn_death_system);
+        let starved = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health { current: 0.0, max: 40.0 },
+        )).id();
+        app.update();
+
+        let deaths = sent::<DeathEvent>(&app);
+        assert_eq!(deaths.len(), 1);
+        assert!(app.world().get_entity(starved).is_err());
+        assert_eq!(deaths[0].killer, None);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use crate::resources::{GameConfig, SETTINGS_FILE};
+    use crate::systems::audio::SoundConfig;
+    use crate::systems::config_files::{ConfigPaths, EMBEDDED_CONFIGS, config_dir_arg, embedded_config};
+    use crate::systems::faction::FactionConfig;
+    use crate::systems::inventory::ItemConfig;
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::spawn::SpawnConfig;
+    use crate::systems::status_effects::StatusEffectConfig;
+    use crate::systems::world_gen::{GroundConfigs, PropsConfigs};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_config_dir_from_the_command_line() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(config_dir_arg(args(&["--config-dir", "mods/winter"])), Some(PathBuf::from("mods/winter")));
+        assert_eq!(config_dir_arg(args(&["--fullscreen", "--config-dir=conf"])), Some(PathBuf::from("conf")));
+        assert_eq!(config_dir_arg(args(&["--config-dir"])), None);
+        assert_eq!(config_dir_arg(args(&["--config-directory", "x"])), None);
+    }
+
+    #[test]
+    fn test_search_order_skips_missing_and_repeated_dirs() {
+        let paths = ConfigPaths::search_order(None, Some(PathBuf::from("/env")), Some(PathBuf::from("/game")), Some(PathBuf::from("/game")));
+        assert_eq!(paths.dirs, vec![PathBuf::from("/env"), PathBuf::from("/game")]);
+        assert!(paths.embedded);
+    }
+
+    #[test]
+    fn test_config_dirs_from_the_command_line_and_environment_are_mods() {
+        let paths = ConfigPaths::search_order(Some(PathBuf::from("/mods/winter")), Some(PathBuf::from("/game")), Some(PathBuf::from("/game")), None);
+        assert_eq!(paths.mods, vec!["winter".to_string()], "The game's own directory isn't a mod");
+        assert!(ConfigPaths::search_order(None, None, Some(PathBuf::from("/game")), None).mods.is_empty());
+    }
+
+    #[test]
+    fn test_each_file_comes_from_the_first_dir_that_has_it() {
+        let first = temp_dir("config_files_first");
+        let second = temp_dir("config_files_second");
+        std::fs::create_dir_all(second.join("assets")).unwrap();
+        std::fs::write(first.join("factions.yaml"), "first: {}\n").unwrap();
+        std::fs::write(second.join("factions.yaml"), "second: {}\n").unwrap();
+        std::fs::write(second.join("items.yaml"), "items: {}\n").unwrap();
+
+        let paths = ConfigPaths::search_order(Some(first.clone()), None, None, Some(second.clone()));
+        let factions = paths.read("factions.yaml").unwrap();
+        let items = paths.read("items.yaml").unwrap();
+        let grounds = paths.read("grounds.yaml").unwrap();
+        let only_first = ConfigPaths { dirs: vec![first.clone()], embedded: false, mods: Vec::new() };
+        let missing = only_first.read("grounds.yaml");
+        let data_dir = paths.data_dir();
+        let settings_path = paths.write_path(SETTINGS_FILE);
+        std::fs::remove_dir_all(&first).unwrap();
+        std::fs::remove_dir_all(&second).unwrap();
+
+        assert_eq!(factions, "first: {}\n");
+        assert_eq!(items, "items: {}\n");
+        assert_eq!(grounds, embedded_config("grounds.yaml").unwrap(), "Built-in when it isn't on disk");
+        assert!(missing.is_err(), "Nothing built in to fall back on");
+        assert!(!only_first.has("grounds.yaml"));
+        assert_eq!(data_dir, second, "The one with the assets");
+        assert_eq!(settings_path, second.join(SETTINGS_FILE), "New files go with the assets");
+    }
+
+    #[test]
+    fn test_embedded_configs_all_load() {
+        let paths = ConfigPaths { dirs: Vec::new(), embedded: true, mods: Vec::new() };
+        assert_eq!(EMBEDDED_CONFIGS.len(), 10);
+        paths.load(SETTINGS_FILE, GameConfig::from_yaml).expect("settings.yaml");
+        paths.load("pawns.yaml", PawnConfig::from_yaml).expect("pawns.yaml");
+        paths.load("factions.yaml", FactionConfig::from_yaml).expect("factions.yaml");
+        paths.load("status_effects.yaml", StatusEffectConfig::from_yaml).expect("status_effects.yaml");
+        paths.load("items.yaml", ItemConfig::from_yaml).expect("items.yaml");
+        paths.load("spawns.yaml", SpawnConfig::from_yaml).expect("spawns.yaml");
+        paths.load("keybinds.yaml", InputMap::from_yaml).expect("keybinds.yaml");
+        paths.load("sounds.yaml", SoundConfig::from_yaml).expect("sounds.yaml");
+        paths.load("grounds.yaml", GroundConfigs::load_from_yaml).expect("grounds.yaml");
+        paths.load("props.yaml", PropsConfigs::load_from_yaml).expect("props.yaml");
+    }
+}
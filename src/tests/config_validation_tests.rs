@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+    use crate::systems::audio::SoundConfig;
+    use crate::systems::config_validation::{ConfigProblem, check_behaviour_references, check_grounds, check_height_ranges, check_pawns, check_sounds, check_spawns, sprite_problem, validate_configs};
+    use crate::systems::config_files::ConfigPaths;
+    use crate::systems::spawn::SpawnConfig;
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::status_effects::StatusEffectConfig;
+    use crate::systems::world_gen::{GroundConfig, TilesetIndex};
+
+    fn grounds(yaml: &str) -> HashMap<String, GroundConfig> {
+        serde_yaml::from_str(yaml).expect("Failed to parse grounds")
+    }
+
+    fn ground(name: &str, min: f32, max: f32) -> String {
+        format!("{}:\n  sprite: \"tileset::grounds::{}\"\n  passable: true\n  height_min: {}\n  height_max: {}\n", name, name, min, max)
+    }
+
+    fn tilesets() -> HashMap<String, TilesetIndex> {
+        let tileset: TilesetIndex = serde_yaml::from_str(r#"
+tileset_name: grounds
+tile_size: 16
+tiles_per_row: 16
+total_tiles: 2
+sprites:
+- { name: grass, index: 0, x: 0, y: 0, width: 16, height: 16 }
+- { name: dirt, index: 1, x: 16, y: 0, width: 16, height: 16 }
+"#).expect("Failed to parse tileset");
+        HashMap::from([("grounds".to_string(), tileset)])
+    }
+
+    fn fields(problems: &[ConfigProblem]) -> Vec<&str> {
+        problems.iter().map(|problem| problem.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_height_ranges_must_cover_zero_to_one() {
+        let covered = grounds(&(ground("water", 0.0, 0.3) + ground("grass", 0.3, 1.0).as_str()));
+        assert!(check_height_ranges(&covered).is_empty());
+
+        let gap = grounds(&(ground("water", 0.0, 0.3) + ground("grass", 0.4, 1.0).as_str()));
+        let problems = check_height_ranges(&gap);
+        assert_eq!(fields(&problems), vec!["grass.height_min"]);
+        assert!(problems[0].message.contains("0.3 to 0.4"), "{}", problems[0].message);
+
+        let overlap = grounds(&(ground("water", 0.0, 0.5) + ground("grass", 0.4, 1.0).as_str()));
+        assert_eq!(fields(&check_height_ranges(&overlap)), vec!["grass.height_min"]);
+
+        let short = grounds(&(ground("water", 0.1, 0.3) + ground("grass", 0.3, 0.9).as_str()));
+        let problems = check_height_ranges(&short);
+        assert_eq!(problems.len(), 2, "Missing both ends: {:?}", problems);
+        assert_eq!(problems[1].to_string(), "grounds.yaml: no ground covers heights 0.9 to 1");
+    }
+
+    #[test]
+    fn test_sprites_must_be_in_a_tileset() {
+        let tilesets = tilesets();
+        let assets = Path::new("no_such_assets");
+        assert_eq!(sprite_problem("tileset::grounds::grass", &tilesets, assets), None);
+        assert!(sprite_problem("tileset::grounds::lava", &tilesets, assets).unwrap().contains("no sprite named lava"));
+        assert!(sprite_problem("tileset::props::tree", &tilesets, assets).unwrap().contains("no tileset named props"));
+        assert!(sprite_problem("tileset::grass", &tilesets, assets).is_some());
+        assert!(sprite_problem("rabbit.png", &tilesets, assets).is_some(), "Plain images have to be in assets");
+    }
+
+    #[test]
+    fn test_footsteps_must_kick_something_up() {
+        let dust = ground("dirt", 0.0, 0.5) + "  footstep: { kind: dust }\n";
+        let none = ground("grass", 0.5, 1.0) + "  footstep: { kind: dust, particles: 0 }\n";
+        let problems = check_grounds(&grounds(&(dust + none.as_str())), &tilesets(), &StatusEffectConfig::default(), Path::new("no_such_assets"));
+        assert_eq!(fields(&problems), vec!["grass.footstep.particles"]);
+    }
+
+    #[test]
+    fn test_unknown_behaviours_are_named() {
+        let pawns: serde_yaml::Value = serde_yaml::from_str(r#"
+wolf:
+  behaviours:
+    idle: { wandering: { move_interval_min: 1.0, move_interval_max: 2.0, move_range: 3 } }
+    hunted: flee
+    looking_for_food: hunt_solos
+    sleepy: sleep
+    controlled: { script: "ai/missing.rhai" }
+  behaviour_tree:
+    selector:
+      - sequence:
+          - condition: threatened
+          - action: flea
+      - action: herd
+"#).unwrap();
+        let problems = check_behaviour_references(&pawns, Path::new("."));
+        let mut found = fields(&problems);
+        found.sort();
+        assert_eq!(found, vec![
+            "wolf.behaviour_tree.selector[0].sequence[1].action",
+            "wolf.behaviours.controlled.script",
+            "wolf.behaviours.looking_for_food",
+            "wolf.behaviours.sleepy",
+        ]);
+        let misspelt = problems.iter().find(|problem| problem.field == "wolf.behaviours.looking_for_food").unwrap();
+        assert!(misspelt.message.starts_with("unknown behaviour hunt_solos, expected one of null, flee, hunt_solo"));
+    }
+
+    #[test]
+    fn test_pawn_references_must_exist() {
+        let pawn_config: PawnConfig = serde_yaml::from_str(r#"
+rabbit:
+  sprite: "tileset::grounds::grass"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 1
+  faction: wildlife
+  eats:
+    pawns: []
+    grounds: [grass, clover]
+  den:
+    grounds: [dirt]
+wolf:
+  sprite: "tileset::grounds::wolf"
+  tags: [medium]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 1
+  inflicts: [poison]
+  eats:
+    pawns: [small, furry]
+  utility:
+    options:
+      - state: looking_for_food
+      - state: napping
+"#).unwrap();
+        let grounds = grounds(&(ground("dirt", 0.0, 0.5) + ground("grass", 0.5, 1.0).as_str()));
+        let problems = check_pawns(&pawn_config, &grounds, &tilesets(), &StatusEffectConfig::default(), Path::new("."));
+        let mut found: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+        found.sort();
+        assert_eq!(found, vec![
+            "pawns.yaml: rabbit.eats.grounds: no ground named clover in grounds.yaml",
+            "pawns.yaml: rabbit.faction: no faction named wildlife in factions.yaml",
+            "pawns.yaml: wolf.eats.pawns: no pawn is tagged furry",
+            "pawns.yaml: wolf.inflicts: no status effect named poison in status_effects.yaml",
+            "pawns.yaml: wolf.sprite: no sprite named wolf in the grounds tileset",
+            "pawns.yaml: wolf.utility.options[1].state: unknown behaviour state napping, expected one of idle, hunted, looking_for_food, eat, controlled, flee, thirsty, tired, resting",
+        ]);
+    }
+
+    #[test]
+    fn test_spawn_groups_must_say_how_many_of_what() {
+        let spawns: SpawnConfig = serde_yaml::from_str(r#"
+warren: { pawn: rabbit, count: 5, grounds: [grass] }
+pack: { pawn: wolves, density: 1.0, grounds: [tundra] }
+herd: { pawn: rabbit }
+crowd: { pawn: rabbit, count: 1, density: 1.0 }
+"#).unwrap();
+        let grounds = grounds(&ground("grass", 0.0, 1.0));
+        let mut found: Vec<String> = check_spawns(&spawns, &["rabbit".to_string()], &grounds).iter().map(|problem| problem.to_string()).collect();
+        found.sort();
+        assert_eq!(found, vec![
+            "spawns.yaml: crowd: has both a count and a density - pick one",
+            "spawns.yaml: herd: needs a count or a density",
+            "spawns.yaml: pack.grounds: no ground named tundra in grounds.yaml",
+            "spawns.yaml: pack.pawn: no pawn type named wolves in pawns.yaml",
+        ]);
+    }
+
+    #[test]
+    fn test_sounds_must_be_for_grounds_that_exist() {
+        let sounds = SoundConfig::from_yaml(r#"
+hearing_distance: 0
+footsteps: { grass: sounds/grass.ogg, sand: sounds/sand.ogg }
+ambience:
+  - { sound: sounds/birds.ogg, grounds: [grass], time: day }
+  - { sound: sounds/waves.ogg, grounds: [sea], volume: 2 }
+music: { tracks: [music/a.ogg], track_secs: 3, crossfade_secs: 4 }
+"#).unwrap();
+        let grounds = grounds(&ground("grass", 0.0, 1.0));
+        let mut found: Vec<String> = check_sounds(&sounds, &grounds).iter().map(|problem| problem.to_string()).collect();
+        found.sort();
+        assert_eq!(found, vec![
+            "sounds.yaml: ambience[1].grounds: no ground named sea in grounds.yaml",
+            "sounds.yaml: ambience[1].volume: 2 is outside 0 to 1",
+            "sounds.yaml: footsteps.sand: no ground named sand in grounds.yaml",
+            "sounds.yaml: hearing_distance: should be more than 0",
+            "sounds.yaml: music.track_secs: should be longer than crossfade_secs",
+        ]);
+    }
+
+    #[test]
+    fn test_every_file_is_checked_before_giving_up() {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "config_validation", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("grounds.yaml"), ground("grass", 0.0, 0.8)).unwrap();
+        std::fs::write(dir.join("factions.yaml"), "wolves:\n  stances: [oops\n").unwrap();
+
+        let problems = validate_configs(&ConfigPaths { dirs: vec![dir.clone()], embedded: false, mods: Vec::new() });
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let files: Vec<&str> = problems.iter().map(|problem| problem.file.as_str()).collect();
+        assert!(files.contains(&"factions.yaml"), "Malformed YAML is reported: {:?}", problems);
+        let malformed = problems.iter().find(|problem| problem.file == "factions.yaml").unwrap();
+        assert!(malformed.message.contains("line"), "With where it went wrong: {}", malformed.message);
+        assert!(problems.iter().any(|problem| problem.to_string() == "grounds.yaml: no ground covers heights 0.8 to 1"));
+        assert!(problems.iter().any(|problem| problem.to_string() == "grounds.yaml: grass.sprite: no tileset named grounds in assets/tilesets"));
+        assert!(problems.iter().any(|problem| problem.to_string() == "pawns.yaml: file not found"));
+        assert!(problems.iter().any(|problem| problem.to_string() == "props.yaml: file not found"));
+        assert!(!files.contains(&"settings.yaml"), "Optional files can be left out");
+    }
+
+    #[test]
+    fn test_pawns_with_behaviour_trees_parse() {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "config_validation_trees", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pawns.yaml"), r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 1
+  eats:
+    pawns: []
+  behaviour_tree:
+    selector:
+      - sequence:
+          - condition:
+              urgent_need: thirst
+          - action: drink
+      - action: herd
+"#).unwrap();
+
+        let problems = validate_configs(&ConfigPaths { dirs: vec![dir.clone()], embedded: false, mods: Vec::new() });
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let pawn_problems: Vec<String> = problems.iter().filter(|problem| problem.file == "pawns.yaml").map(|problem| problem.to_string()).collect();
+        assert_eq!(pawn_problems, vec!["pawns.yaml: rabbit.sprite: no tileset named pawns in assets/tilesets"]);
+    }
+}
@@ -0,0 +1,508 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::ActiveBehaviour;
+    use crate::systems::ai::{WanderingAI, wandering_ai_system};
+    use crate::systems::async_pathfinding::{
+        PathfindingRequest, PathfindingPriority, PathfindingCompleted, PathfindingFailed, GlobalPathfindingCache,
+        spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding,
+    };
+    use crate::systems::input::apply_order_commands;
+    use crate::systems::pathfinding_heatmap::PathfindingHeatmap;
+    use crate::systems::sim_rng::SimRng;
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::control::{Controlled, HoldPosition, AttackOrder, is_controllable, can_be_ordered_to_attack, fan_out_goal_tiles, take_control_of_selected, release_control, handle_hold_input, steer_controlled_pawns, apply_control_commands, attack_order_system};
+    use crate::systems::pawn::{Pawn, PawnTarget, Size, Health, Endurance, CurrentBehavior, move_pawn_to_target};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+    use crate::systems::replay::{PlayerCommand, PlayerCommands, ReplayMode, Replay, TimedCommand, take_player_commands};
+    use crate::systems::selection::{Selected, handle_select_all_input, apply_selection_commands};
+    use crate::systems::sim_tick::SimTick;
+    use crate::systems::world_gen::{TerrainMap, TerrainChanges};
+    use crate::systems::config_files::ConfigPaths;
+    use crate::systems::save::WorldMetadata;
+    use crate::tests::{setup_test_app, create_test_terrain_map, create_test_ground_configs};
+
+    const CONTROL_PAWNS_YAML: &str = r#"
+player:
+  sprite: "tileset::pawns::player"
+  tags: [medium]
+  move_speed: 150.0
+  max_health: 80
+  max_endurance: 150
+  defence: 0
+  strength: 10
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    controlled: player_input
+  eats:
+    pawns: []
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 2
+  strength: 8
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle:
+      wandering:
+        move_interval_min: 0.1
+        move_interval_max: 0.1
+        move_range: 3
+    hunted: flee
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 32.0;
+
+    fn create_control_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(CONTROL_PAWNS_YAML).expect("Control config should parse")
+    }
+
+    fn setup_control_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.insert_resource(create_control_pawn_config());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(create_test_terrain_map(10, 10, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.insert_resource(InputMap::default());
+        app.insert_resource(SimTick::default());
+        app.insert_resource(PlayerCommands::default());
+        app.insert_resource(ReplayMode::default());
+        app.add_systems(Update, (
+            handle_select_all_input,
+            steer_controlled_pawns,
+            handle_hold_input,
+            take_player_commands.after(handle_select_all_input).after(steer_controlled_pawns).after(handle_hold_input),
+            apply_selection_commands.after(take_player_commands),
+            take_control_of_selected.after(apply_selection_commands),
+            release_control.after(apply_selection_commands),
+            apply_control_commands.after(take_control_of_selected).after(release_control),
+            attack_order_system.after(apply_control_commands),
+            move_pawn_to_target.after(attack_order_system),
+            wandering_ai_system.after(apply_control_commands),
+        ));
+        app
+    }
+
+    fn spawn_at_tile(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            Transform::from_xyz(x, y, 100.0),
+            Size { value: 1.0 },
+            Endurance::new(150),
+            CurrentBehavior { state: "idle".to_string() },
+        )).id()
+    }
+
+    fn keys(app: &mut App) -> Mut<'_, ButtonInput<KeyCode>> {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>()
+    }
+
+    fn position(app: &App, entity: Entity) -> Vec2 {
+        app.world().get::<Transform>(entity).unwrap().translation.truncate()
+    }
+
+    #[test]
+    fn test_only_player_input_pawns_are_controllable() {
+        let config = create_control_pawn_config();
+        assert!(is_controllable(&config, "player"));
+        assert!(!is_controllable(&config, "rabbit"));
+    }
+
+    #[test]
+    fn test_selecting_a_controllable_pawn_takes_control() {
+        let mut app = setup_control_app();
+        let player = spawn_at_tile(&mut app, "player", (7, 7));
+        let rabbit = spawn_at_tile(&mut app, "rabbit", (7, 2));
+        app.world_mut().entity_mut(player).insert((Selected, PawnTarget::new(Vec3::new(0.0, 0.0, 100.0))));
+        app.world_mut().entity_mut(rabbit).insert(Selected);
+        app.update();
+
+        assert!(app.world().get::<Controlled>(player).is_some());
+        assert_eq!(app.world().get::<CurrentBehavior>(player).unwrap().state, "controlled");
+        assert!(matches!(
+            app.world().get::<ActiveBehaviour>(player).unwrap().action,
+            BehaviourConfig::Simple(BehaviourType::PlayerInput)
+        ));
+        assert!(app.world().get::<PawnTarget>(player).is_none(), "Whatever the pawn was doing is dropped");
+
+        assert!(app.world().get::<Controlled>(rabbit).is_none(), "Pawns without player_input stay under AI");
+        assert_eq!(app.world().get::<CurrentBehavior>(rabbit).unwrap().state, "idle");
+    }
+
+    #[test]
+    fn test_wasd_steers_the_controlled_pawn() {
+        let mut app = setup_control_app();
+        let player = spawn_at_tile(&mut app, "player", (7, 7));
+        app.world_mut().entity_mut(player).insert(Selected);
+        app.update();
+        let start = position(&app, player);
+
+        keys(&mut app).press(KeyCode::KeyD);
+        for _ in 0..5 {
+            app.update();
+        }
+        let moved = position(&app, player) - start;
+        assert!(moved.x > TILE_SIZE, "Holding D should walk the pawn right: {:?}", moved);
+        assert!(moved.y.abs() < 0.01);
+
+        keys(&mut app).release(KeyCode::KeyD);
+        app.update();
+        keys(&mut app).clear();
+        let stopped_at = position(&app, player);
+        app.update();
+        app.update();
+        assert!(app.world().get::<PawnTarget>(player).is_none());
+        assert_eq!(position(&app, player), stopped_at, "Letting go of the keys stops the pawn");
+    }
+
+    #[test]
+    fn test_steering_into_a_wall_goes_nowhere() {
+        let mut app = setup_control_app();
+        // The test map has stone in the middle tile, right of this one
+        let player = spawn_at_tile(&mut app, "player", (4, 5));
+        app.world_mut().entity_mut(player).insert(Selected);
+        app.update();
+        let start = position(&app, player);
+
+        keys(&mut app).press(KeyCode::KeyD);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(position(&app, player), start);
+    }
+
+    #[test]
+    fn test_group_goals_fan_out_over_passable_tiles() {
+        let terrain_map = create_test_terrain_map(10, 10, TILE_SIZE);
+        let ground_configs = create_test_ground_configs();
+
+        let goals = fan_out_goal_tiles(&terrain_map, &ground_configs, (7, 7), 5);
+        assert_eq!(goals.len(), 5);
+        assert_eq!(goals[0], (7, 7), "The closest pawn gets the clicked tile");
+        assert!(goals[1..].iter().all(|(x, y)| (x - 7).abs() <= 1 && (y - 7).abs() <= 1), "The rest crowd round it: {:?}", goals);
+        let mut unique = goals.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 5, "No two pawns share a goal");
+
+        // Clicking the stone in the middle puts nobody on it
+        let goals = fan_out_goal_tiles(&terrain_map, &ground_configs, (5, 5), 3);
+        assert_eq!(goals.len(), 3);
+        assert!(!goals.contains(&(5, 5)));
+        assert!(goals.iter().all(|(x, y)| terrain_map.is_tile_passable(*x, *y, &ground_configs)));
+    }
+
+    #[test]
+    fn test_selecting_several_pawns_controls_them_all() {
+        let mut app = setup_control_app();
+        let players: Vec<Entity> = [(7, 7), (8, 7)].into_iter().map(|tile| spawn_at_tile(&mut app, "player", tile)).collect();
+        for player in &players {
+            app.world_mut().entity_mut(*player).insert(Selected);
+        }
+        app.update();
+        let starts: Vec<Vec2> = players.iter().map(|player| position(&app, *player)).collect();
+
+        keys(&mut app).press(KeyCode::KeyW);
+        for _ in 0..3 {
+            app.update();
+        }
+        for (player, start) in players.iter().zip(starts) {
+            assert!(app.world().get::<Controlled>(*player).is_some());
+            assert!(position(&app, *player).y > start.y, "Every controlled pawn walks");
+        }
+    }
+
+    #[test]
+    fn test_select_all_takes_control_of_every_controllable_pawn() {
+        let mut app = setup_control_app();
+        let players: Vec<Entity> = [(7, 7), (2, 2)].into_iter().map(|tile| spawn_at_tile(&mut app, "player", tile)).collect();
+        let rabbit = spawn_at_tile(&mut app, "rabbit", (7, 2));
+        app.world_mut().entity_mut(rabbit).insert(Selected);
+
+        keys(&mut app).press(KeyCode::Tab);
+        app.update();
+
+        for player in players {
+            assert!(app.world().get::<Selected>(player).is_some());
+            assert!(app.world().get::<Controlled>(player).is_some());
+        }
+        assert!(app.world().get::<Selected>(rabbit).is_none(), "The rabbit can't be controlled, so it's dropped");
+    }
+
+    #[test]
+    fn test_hold_stops_selected_pawns_and_keeps_them_from_wandering() {
+        let mut app = setup_control_app();
+        let rabbit = spawn_at_tile(&mut app, "rabbit", (7, 2));
+        app.world_mut().entity_mut(rabbit).insert((
+            Selected,
+            WanderingAI::new(),
+            PawnTarget::new(Vec3::new(0.0, 0.0, 100.0)),
+            PathfindingRequest::new((0.0, 0.0), (10.0, 10.0), 1.0),
+        ));
+
+        keys(&mut app).press(KeyCode::KeyH);
+        app.update();
+        keys(&mut app).clear();
+
+        assert!(app.world().get::<HoldPosition>(rabbit).is_some());
+        assert!(app.world().get::<PawnTarget>(rabbit).is_none(), "The rabbit stops where it is");
+        assert!(app.world().get::<PathfindingRequest>(rabbit).is_none(), "Its pending path is dropped");
+        let held_at = position(&app, rabbit);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get::<PathfindingRequest>(rabbit).is_none(), "A holding rabbit doesn't wander off");
+        assert_eq!(position(&app, rabbit), held_at);
+
+        // Without the hold it would have picked somewhere to go by now
+        app.world_mut().entity_mut(rabbit).remove::<HoldPosition>();
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world().get::<PathfindingRequest>(rabbit).is_some());
+    }
+
+    #[test]
+    fn test_steering_ends_a_hold() {
+        let mut app = setup_control_app();
+        let player = spawn_at_tile(&mut app, "player", (7, 7));
+        app.world_mut().entity_mut(player).insert(Selected);
+        keys(&mut app).press(KeyCode::KeyH);
+        app.update();
+        keys(&mut app).clear();
+        assert!(app.world().get::<HoldPosition>(player).is_some());
+
+        keys(&mut app).press(KeyCode::KeyW);
+        app.update();
+        assert!(app.world().get::<HoldPosition>(player).is_none());
+        assert!(app.world().get::<PawnTarget>(player).is_some());
+    }
+
+    fn blows(app: &App) -> Vec<(Entity, f32)> {
+        let events = app.world().resource::<Events<CombatEvent>>();
+        events.get_cursor().read(events).map(|event| (event.victim, event.damage)).collect()
+    }
+
+    fn order_attack(app: &mut App, attacker_tile: (i32, i32), target_tile: (i32, i32)) -> (Entity, Entity) {
+        let player = spawn_at_tile(app, "player", attacker_tile);
+        let rabbit = spawn_at_tile(app, "rabbit", target_tile);
+        app.world_mut().entity_mut(rabbit).insert(Health::new(25));
+        app.world_mut().entity_mut(player).insert(Selected);
+        app.update();
+        app.world_mut().entity_mut(player).insert(AttackOrder::new(rabbit));
+        (player, rabbit)
+    }
+
+    #[test]
+    fn test_ordered_pawn_strikes_its_target_in_reach() {
+        let mut app = setup_control_app();
+        let (player, rabbit) = order_attack(&mut app, (7, 7), (8, 7));
+
+        app.update();
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 17.0, "10 strength against 2 defence");
+        assert_eq!(blows(&app), vec![(rabbit, 8.0)]);
+        assert!(app.world().get::<AttackOrder>(player).is_some());
+
+        // One attack a second
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 17.0);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 9.0);
+    }
+
+    #[test]
+    fn test_attack_order_ends_when_the_target_dies() {
+        let mut app = setup_control_app();
+        let (player, rabbit) = order_attack(&mut app, (7, 7), (8, 7));
+        app.world_mut().get_mut::<Health>(rabbit).unwrap().current = 5.0;
+
+        app.update();
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 0.0);
+        app.update();
+        assert!(app.world().get::<AttackOrder>(player).is_none());
+        assert!(app.world().get::<Controlled>(player).is_some(), "The pawn stays under control");
+    }
+
+    #[test]
+    fn test_ordered_pawn_chases_a_target_out_of_reach() {
+        let mut app = setup_control_app();
+        let (player, rabbit) = order_attack(&mut app, (7, 7), (7, 2));
+
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(player).expect("The pawn should path to its target");
+        assert_eq!(request.priority, PathfindingPriority::High);
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(7, 2);
+        assert_eq!(request.goal, (x, y));
+        assert!(blows(&app).is_empty());
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 25.0);
+    }
+
+    #[test]
+    fn test_allies_cannot_be_ordered_to_attack_each_other() {
+        let mut config = create_control_pawn_config();
+        assert!(can_be_ordered_to_attack(&config, "player", "rabbit"));
+        assert!(can_be_ordered_to_attack(&config, "player", "player"), "Pawns without a faction are fair game");
+
+        config.factions = serde_yaml::from_str("camp: {}").expect("Factions should parse");
+        config.pawns.get_mut("player").unwrap().faction = Some("camp".to_string());
+        assert!(!can_be_ordered_to_attack(&config, "player", "player"));
+        assert!(can_be_ordered_to_attack(&config, "player", "rabbit"));
+    }
+
+    #[test]
+    fn test_steering_calls_off_an_attack() {
+        let mut app = setup_control_app();
+        let (player, _) = order_attack(&mut app, (7, 7), (7, 2));
+
+        keys(&mut app).press(KeyCode::KeyW);
+        app.update();
+        assert!(app.world().get::<AttackOrder>(player).is_none());
+    }
+
+    #[test]
+    fn test_deselecting_releases_control_back_to_idle() {
+        let mut app = setup_control_app();
+        let player = spawn_at_tile(&mut app, "player", (7, 7));
+        app.world_mut().entity_mut(player).insert(Selected);
+        app.update();
+        assert!(app.world().get::<Controlled>(player).is_some());
+
+        app.world_mut().entity_mut(player).remove::<Selected>();
+        app.update();
+
+        assert!(app.world().get::<Controlled>(player).is_none());
+        assert!(app.world().get::<ActiveBehaviour>(player).is_none());
+        assert_eq!(app.world().get::<CurrentBehavior>(player).unwrap().state, "idle");
+    }
+
+    /// Tab, then W for a few frames, then H, on the frames a player would have pressed them
+    fn press_script_keys(app: &mut App, frame: usize) {
+        let mut keys = keys(app);
+        keys.clear();
+        match frame {
+            0 => keys.press(KeyCode::Tab),
+            2 => keys.press(KeyCode::KeyW),
+            6 => keys.release(KeyCode::KeyW),
+            8 => keys.press(KeyCode::KeyH),
+            _ => {}
+        }
+    }
+
+    fn run_ticks(app: &mut App, frames: usize, script: Option<fn(&mut App, usize)>) {
+        for frame in 0..frames {
+            app.world_mut().resource_mut::<SimTick>().tick += 1;
+            if let Some(script) = script {
+                script(app, frame);
+            }
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_a_recorded_session_plays_back_the_same() {
+        let metadata = WorldMetadata::new("Replayed", 7, &GameConfig::default(), &ConfigPaths { dirs: Vec::new(), embedded: false, mods: Vec::new() });
+        let mut recording = setup_control_app();
+        recording.insert_resource(ReplayMode::Recording(Replay::new(&metadata)));
+        let recorded: Vec<Entity> = [(7, 2), (8, 2)].into_iter().map(|tile| spawn_at_tile(&mut recording, "player", tile)).collect();
+        let starts: Vec<Vec2> = recorded.iter().map(|pawn| position(&recording, *pawn)).collect();
+        run_ticks(&mut recording, 12, Some(press_script_keys));
+
+        let mut replay = match recording.world_mut().remove_resource::<ReplayMode>() {
+            Some(ReplayMode::Recording(replay)) => replay,
+            other => panic!("Should still be recording: {:?}", other),
+        };
+        replay.end_tick = 12;
+        assert_eq!(replay.commands.len(), 4, "Select all, steer, stop and hold: {:?}", replay.commands);
+
+        let mut playback = setup_control_app();
+        playback.insert_resource(ReplayMode::Playing { replay, next: 0 });
+        let played: Vec<Entity> = [(7, 2), (8, 2)].into_iter().map(|tile| spawn_at_tile(&mut playback, "player", tile)).collect();
+        run_ticks(&mut playback, 12, None);
+
+        for ((recorded, played), start) in recorded.into_iter().zip(played).zip(starts) {
+            assert!(position(&recording, recorded).y > start.y, "W walked the pawns up while recording");
+            assert_eq!(position(&playback, played), position(&recording, recorded));
+            assert!(playback.world().get::<HoldPosition>(played).is_some());
+        }
+        assert!(matches!(playback.world().resource::<ReplayMode>(), ReplayMode::Off), "The controls are handed back at the end");
+    }
+
+    /// Play a seeded world of wandering rabbits with the player ordered across it, giving back
+    /// where every pawn ended up. Pausing between steps lets each search finish long before its
+    /// path is due.
+    fn play_seeded_world(replay: &Replay, pause_between_steps: bool) -> Vec<Vec2> {
+        let mut app = setup_control_app();
+        app.insert_resource(SimRng::from_seed(replay.metadata.seed));
+        app.insert_resource(ReplayMode::Playing { replay: replay.clone(), next: 0 });
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.insert_resource(TerrainChanges::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, (
+            apply_order_commands.after(take_control_of_selected).after(release_control),
+            spawn_cached_pathfinding_tasks.after(apply_order_commands).after(wandering_ai_system),
+            handle_completed_cached_pathfinding.after(spawn_cached_pathfinding_tasks).before(move_pawn_to_target),
+        ));
+
+        let player = spawn_at_tile(&mut app, "player", (1, 1));
+        let mut pawns = vec![player];
+        for tile in [(5, 5), (7, 3), (3, 8), (8, 7)] {
+            let rabbit = spawn_at_tile(&mut app, "rabbit", tile);
+            app.world_mut().entity_mut(rabbit).insert(WanderingAI::new());
+            pawns.push(rabbit);
+        }
+
+        for _ in 0..replay.end_tick {
+            app.world_mut().resource_mut::<SimTick>().tick += 1;
+            app.update();
+            if pause_between_steps {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        pawns.into_iter().map(|pawn| position(&app, pawn)).collect()
+    }
+
+    #[test]
+    fn test_a_seeded_world_plays_back_the_same_however_long_searches_take() {
+        let metadata = WorldMetadata::new("Seeded", 11, &GameConfig::default(), &ConfigPaths { dirs: Vec::new(), embedded: false, mods: Vec::new() });
+        let mut replay = Replay::new(&metadata);
+        let goal = create_test_terrain_map(10, 10, TILE_SIZE).tile_to_world_coords(8, 8);
+        replay.commands = vec![
+            TimedCommand { tick: 1, command: PlayerCommand::SelectAll },
+            TimedCommand { tick: 2, command: PlayerCommand::Order { position: goal } },
+        ];
+        replay.end_tick = 30;
+
+        let hurried = play_seeded_world(&replay, false);
+        let unhurried = play_seeded_world(&replay, true);
+        let start = create_test_terrain_map(10, 10, TILE_SIZE).tile_to_world_coords(1, 1);
+        assert!(hurried[0].distance(Vec2::from(start)) > TILE_SIZE, "The player walked off as ordered");
+        assert_eq!(hurried, unhurried);
+    }
+}
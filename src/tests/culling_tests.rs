@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::camera::CameraController;
+    use crate::systems::culling::{OverlayChunk, OverlayChunks, cull_overlay_chunks, CHUNK_TILES};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::setup_test_app;
+
+    #[derive(Resource, Default)]
+    struct TestChunks(OverlayChunks);
+
+    /// A chunk for every tile of the map, with an 800x600 view over the middle of it
+    fn setup_culling_app(size: u32) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(TerrainMap::new(size, size, 16.0));
+        app.init_resource::<TestChunks>();
+        let mut projection = OrthographicProjection::default_2d();
+        projection.area = Rect::new(-400.0, -300.0, 400.0, 300.0);
+        app.world_mut().spawn((Transform::default(), projection, CameraController));
+        app.add_systems(Startup, |mut commands: Commands, terrain_map: Res<TerrainMap>, mut chunks: ResMut<TestChunks>| {
+            for x in 0..terrain_map.width {
+                for y in 0..terrain_map.height {
+                    chunks.0.chunk_for(&mut commands, &terrain_map, x, y);
+                }
+            }
+        });
+        app.add_systems(Update, cull_overlay_chunks);
+        app
+    }
+
+    fn shown_chunks(app: &mut App) -> usize {
+        let mut query = app.world_mut().query::<(&OverlayChunk, &Visibility)>();
+        query.iter(app.world()).filter(|(_, visibility)| **visibility != Visibility::Hidden).count()
+    }
+
+    #[test]
+    fn test_tiles_share_chunks() {
+        let mut app = setup_culling_app(40);
+        app.update();
+
+        let chunks = &app.world().resource::<TestChunks>().0.chunks;
+        assert_eq!(chunks.len(), 9, "40 tiles across takes 3 chunks of {}", CHUNK_TILES);
+    }
+
+    #[test]
+    fn test_only_chunks_in_view_are_shown_on_a_large_map() {
+        let mut app = setup_culling_app(512);
+        app.update();
+
+        // 1024 chunks of 256 pixels, with the 800x600 view and its margin reaching into 4 by 4 of them
+        assert_eq!(app.world().resource::<TestChunks>().0.chunks.len(), 1024);
+        assert_eq!(shown_chunks(&mut app), 16);
+
+        // Zooming out shows more of them
+        let mut projections = app.world_mut().query::<&mut OrthographicProjection>();
+        projections.single_mut(app.world_mut()).area = Rect::new(-1600.0, -1200.0, 1600.0, 1200.0);
+        app.update();
+        assert_eq!(shown_chunks(&mut app), 14 * 10);
+    }
+}
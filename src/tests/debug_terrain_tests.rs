@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use crate::systems::debug_display::inspect_tile;
+    use crate::systems::pathfinding_cache::PathfindingCache;
     use crate::systems::world_gen::{TerrainMap, TerrainChanges};
-    use crate::tests::create_test_ground_configs;
+    use crate::tests::{create_test_ground_configs, create_test_terrain_map};
 
     #[test]
     fn test_terrain_modification_tracking() {
@@ -64,4 +66,26 @@ mod tests {
         assert_eq!(new_terrain_from_passable, stone_type);
         assert_eq!(new_terrain_from_impassable, dirt_type);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_inspecting_a_tile_reports_footing_and_cached_paths() {
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_test_terrain_map(9, 9, 32.0);
+        let mut cache = PathfindingCache::new();
+        let path = vec![terrain_map.tile_to_world_coords(3, 1), terrain_map.tile_to_world_coords(4, 1), terrain_map.tile_to_world_coords(5, 1)];
+        cache.cache_path((3, 1), (5, 1), 1.0, Some(path), &terrain_map);
+
+        // Open grass next to the stone in the middle of the map
+        let report = inspect_tile((4, 3), &terrain_map, &ground_configs, &cache);
+        assert!(report.starts_with("Tile (4, 3): grass"), "{}", report);
+        assert!(report.contains("Passable by size - 0.5: yes, 1: yes, 1.5: yes, 2: no"), "The biggest pawns brush the stone: {}", report);
+        assert!(report.contains("Cached paths over it: none"), "{}", report);
+
+        let report = inspect_tile((4, 1), &terrain_map, &ground_configs, &cache);
+        assert!(report.contains("Cached paths over it: 1\n  (3, 1) -> (5, 1) for size 1"), "{}", report);
+
+        let report = inspect_tile((4, 4), &terrain_map, &ground_configs, &cache);
+        assert!(report.starts_with("Tile (4, 4): stone"), "{}", report);
+        assert!(report.contains("0.5: no"), "{}", report);
+    }
+}
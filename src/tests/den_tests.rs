@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::den::{Den, HomeDen, pick_den_tiles, release_homeless_pawns};
+    use crate::systems::needs::{Asleep, sleep_system};
+    use crate::systems::pawn::{Pawn, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, DenConfig};
+    use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DEN_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  den:
+    count: 2
+    grounds: [grass]
+  behaviours:
+    idle: sleep
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    /// Dirt map with a patch of grass in the corner
+    fn create_den_terrain(ground_configs: &GroundConfigs) -> TerrainMap {
+        let dirt = ground_configs.terrain_mapping["dirt"];
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for x in 0..20 {
+            for y in 0..20 {
+                terrain_map.set_tile(x, y, if x < 5 && y < 5 { grass } else { dirt });
+            }
+        }
+        terrain_map
+    }
+
+    #[test]
+    fn test_den_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(DEN_PAWNS_YAML).expect("Den config should parse");
+        assert_eq!(config.get_pawn_definition("rabbit").unwrap().den.as_ref().unwrap().count, 2);
+        assert_eq!(DenConfig::default().count, 1);
+        assert!(DenConfig::default().grounds.is_empty());
+    }
+
+    #[test]
+    fn test_dens_are_dug_in_their_grounds() {
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_den_terrain(&ground_configs);
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let den = DenConfig { count: 3, grounds: vec!["grass".to_string()] };
+        let tiles = pick_den_tiles(&den, &terrain_map, &ground_configs, &mut rng);
+        assert_eq!(tiles.len(), 3);
+        for (index, tile) in tiles.iter().enumerate() {
+            assert_eq!(terrain_map.tiles[tile.0 as usize][tile.1 as usize], grass, "Den {:?} should be on grass", tile);
+            assert!(tile.0 >= 1 && tile.1 >= 1, "Dens keep off the map edge");
+            assert!(!tiles[..index].contains(tile), "Dens get a tile each");
+        }
+
+        let anywhere = pick_den_tiles(&DenConfig::default(), &terrain_map, &ground_configs, &mut rng);
+        assert_eq!(anywhere.len(), 1);
+        let nowhere = DenConfig { count: 1, grounds: vec!["water".to_string()] };
+        assert!(pick_den_tiles(&nowhere, &terrain_map, &ground_configs, &mut rng).is_empty());
+    }
+
+    fn setup_den_app() -> (App, Entity, Entity) {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(DEN_PAWNS_YAML).expect("Den config should parse");
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_den_terrain(&ground_configs);
+        let (den_x, den_y) = terrain_map.tile_to_world_coords(2, 2);
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(ground_configs);
+        app.add_systems(Update, (release_homeless_pawns, sleep_system.after(release_homeless_pawns)));
+
+        let den = app.world_mut().spawn((Den { species: "rabbit".to_string() }, Transform::from_xyz(den_x, den_y, 10.0))).id();
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Endurance::new(10),
+            Size { value: 1.0 },
+            HomeDen { den },
+            Transform::from_xyz(den_x + 10.0 * TILE_SIZE, den_y, 100.0),
+        )).id();
+        (app, den, rabbit)
+    }
+
+    #[test]
+    fn test_tired_pawns_go_home_to_sleep() {
+        let (mut app, den, rabbit) = setup_den_app();
+        app.update();
+        assert!(app.world().get::<Asleep>(rabbit).is_none(), "Not home yet");
+        let request = app.world().get::<PathfindingRequest>(rabbit).expect("The rabbit should head for its den");
+        let den_pos = app.world().get::<Transform>(den).unwrap().translation;
+        assert_eq!(request.goal, (den_pos.x, den_pos.y));
+
+        // Once home it falls asleep
+        app.world_mut().entity_mut(rabbit).remove::<PathfindingRequest>();
+        app.world_mut().get_mut::<Transform>(rabbit).unwrap().translation = den_pos.with_z(100.0);
+        app.update();
+        assert!(app.world().get::<Asleep>(rabbit).is_some());
+    }
+
+    #[test]
+    fn test_destroyed_den_leaves_pawns_homeless() {
+        let (mut app, den, rabbit) = setup_den_app();
+        app.world_mut().despawn(den);
+        app.update();
+        assert!(app.world().get::<HomeDen>(rabbit).is_none());
+        app.update();
+        assert!(app.world().get::<Asleep>(rabbit).is_some(), "Homeless pawns sleep where they are");
+    }
+}
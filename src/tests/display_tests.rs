@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use bevy::window::{MonitorSelection, PresentMode, WindowMode};
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::display::{frame_duration, window_from_config};
+
+    #[test]
+    fn test_window_follows_the_settings() {
+        let config = GameConfig {
+            window_title: "Test".to_string(),
+            window_width: 800,
+            window_height: 600,
+            fullscreen: true,
+            vsync: false,
+            ..GameConfig::default()
+        };
+        let window = window_from_config(&config);
+        assert_eq!(window.title, "Test");
+        assert_eq!((window.resolution.width(), window.resolution.height()), (800.0, 600.0));
+        assert_eq!(window.present_mode, PresentMode::AutoNoVsync);
+        assert_eq!(window.mode, WindowMode::BorderlessFullscreen(MonitorSelection::Current));
+
+        let window = window_from_config(&GameConfig::default());
+        assert_eq!(window.present_mode, PresentMode::AutoVsync);
+        assert_eq!(window.mode, WindowMode::Windowed);
+    }
+
+    #[test]
+    fn test_frame_duration_from_target_fps() {
+        assert_eq!(frame_duration(50), Some(Duration::from_millis(20)));
+        assert_eq!(frame_duration(0), None, "No limit");
+    }
+}
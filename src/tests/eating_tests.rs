@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{ActiveBehaviour, HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::eating::{Eating, eating_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size, pawn_death_system};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_MEAL_DURATION};
+    use crate::systems::perception::Threatened;
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const EATING_PAWNS_YAML: &str = r#"
+fox:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 60
+  max_endurance: 100
+  defence: 5
+  strength: 50
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: eat
+  eats:
+    pawns: [small]
+    meal:
+      duration: 1.0
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 40
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_eating_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(EATING_PAWNS_YAML).expect("Eating config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, eating_system);
+        app
+    }
+
+    fn spawn_eater(app: &mut App, endurance: f32) -> Entity {
+        let entity = app.world_mut().spawn((
+            Pawn::new("fox".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(60),
+            Endurance { current: endurance, max: 100.0, health_loss_timer: 0.0 },
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id();
+        app.world_mut().entity_mut(entity).insert(Eating {
+            prey: "rabbit".to_string(),
+            remaining: 1.0,
+            endurance_left: 40.0,
+        });
+        entity
+    }
+
+    #[test]
+    fn test_meal_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(EATING_PAWNS_YAML).expect("Eating config should parse");
+        assert_eq!(config.get_pawn_definition("fox").unwrap().eats.meal_config().duration, 1.0);
+        assert_eq!(config.get_pawn_definition("rabbit").unwrap().eats.meal_config().duration, DEFAULT_MEAL_DURATION);
+    }
+
+    #[test]
+    fn test_kill_starts_a_meal() {
+        let mut app = setup_eating_app();
+        app.add_systems(Update, (hunt_solo_ai_system.before(eating_system), pawn_death_system.after(hunt_solo_ai_system)));
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(40),
+            Transform::from_xyz(10.0, 0.0, 100.0),
+        )).id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(rabbit);
+        hunt_ai.last_attack_time = 1.0;
+        let fox = app.world_mut().spawn((
+            Pawn::new("fox".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(60),
+            Endurance { current: 10.0, max: 100.0, health_loss_timer: 0.0 },
+            Size { value: 1.0 },
+            hunt_ai,
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id();
+        app.update();
+
+        let eating = app.world().get::<Eating>(fox).expect("The fox should sit down to eat its kill");
+        assert_eq!((eating.prey.as_str(), eating.remaining, eating.endurance_left), ("rabbit", 1.0, 40.0));
+        assert!(app.world().get::<ActiveBehaviour>(fox).is_some(), "Eating overrides the fox's behaviour");
+        assert_eq!(app.world().get::<Endurance>(fox).unwrap().current, 10.0, "None of it is eaten yet");
+
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(fox).unwrap().state, "eat");
+    }
+
+    #[test]
+    fn test_meal_is_eaten_over_its_duration() {
+        let mut app = setup_eating_app();
+        let fox = spawn_eater(&mut app, 10.0);
+        app.update();
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let endurance = app.world().get::<Endurance>(fox).unwrap().current;
+        assert!((endurance - 30.0).abs() < 0.01, "Half way through the meal it has half the kill, got {}", endurance);
+        assert!(app.world().get::<Eating>(fox).is_some());
+
+        for _ in 0..5 {
+            app.update();
+        }
+        let endurance = app.world().get::<Endurance>(fox).unwrap().current;
+        assert!((endurance - 50.0).abs() < 0.01, "It gets the whole kill by the end, got {}", endurance);
+        assert!(app.world().get::<Eating>(fox).is_none(), "It gets up once it's done");
+        assert_eq!(app.world().get::<CurrentBehavior>(fox).unwrap().state, "idle");
+    }
+
+    #[test]
+    fn test_disturbed_eaters_leave_the_rest() {
+        for threatened in [false, true] {
+            let mut app = setup_eating_app();
+            let fox = spawn_eater(&mut app, 10.0);
+            app.update();
+            for _ in 0..5 {
+                app.update();
+            }
+            let attacker = app.world_mut().spawn_empty().id();
+            if threatened {
+                app.world_mut().entity_mut(fox).insert(Threatened { predator: attacker });
+            } else {
+                app.world_mut().send_event(CombatEvent { attacker, victim: fox, damage: 5.0 });
+            }
+            app.update();
+            app.update();
+
+            assert!(app.world().get::<Eating>(fox).is_none(), "The fox is driven off its kill");
+            assert!(app.world().get::<ActiveBehaviour>(fox).is_none());
+            let endurance = app.world().get::<Endurance>(fox).unwrap().current;
+            assert!((endurance - 30.0).abs() < 0.01, "It only keeps what it ate, got {}", endurance);
+        }
+    }
+}
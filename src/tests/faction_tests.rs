@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::faction::{FactionConfig, Stance};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const FACTIONS_YAML: &str = r#"
+pack: {}
+wildlife: {}
+camp:
+  stances:
+    pack: hostile
+"#;
+
+    const FACTION_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  faction: pack
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    looking_for_food: hunt_solo
+  eats:
+    pawns: [small, animal]
+pup:
+  sprite: "tileset::pawns::wolf"
+  faction: pack
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 40
+  max_endurance: 20
+  defence: 5
+  strength: 10
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  faction: wildlife
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+golem:
+  sprite: "tileset::pawns::wolf"
+  faction: camp
+  tags: [large, construct]
+  move_speed: 60.0
+  max_health: 200
+  max_endurance: 100
+  defence: 20
+  strength: 40
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    looking_for_food: hunt_solo
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn create_faction_config() -> PawnConfig {
+        let mut config: PawnConfig = serde_yaml::from_str(FACTION_PAWNS_YAML).expect("Faction pawns should parse");
+        config.factions = serde_yaml::from_str(FACTIONS_YAML).expect("Factions should parse");
+        config
+    }
+
+    fn setup_hunt_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_faction_config());
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, hunt_solo_ai_system);
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, x: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(100),
+            Size { value: 1.0 },
+            Transform::from_xyz(x, 0.0, 100.0),
+        )).id()
+    }
+
+    fn spawn_hunter(app: &mut App, pawn_type: &str) -> Entity {
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.search_timer = 2.0; // Search on the first update
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            hunt_ai,
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_faction_stances() {
+        let factions: FactionConfig = serde_yaml::from_str(FACTIONS_YAML).expect("Factions should parse");
+        assert_eq!(factions.stance("pack", "pack"), Stance::Allied, "Members of a faction are allied by default");
+        assert_eq!(factions.stance("camp", "pack"), Stance::Hostile);
+        assert_eq!(factions.stance("pack", "camp"), Stance::Neutral, "Stances are one-way");
+        assert_eq!(factions.stance("wildlife", "bandits"), Stance::Neutral);
+
+        let config = create_faction_config();
+        assert_eq!(config.stance_between("wolf", "pup"), Stance::Allied);
+        assert_eq!(config.stance_between("golem", "wolf"), Stance::Hostile);
+    }
+
+    #[test]
+    fn test_will_attack() {
+        let config = create_faction_config();
+        let will_attack = |attacker: &str, target: &str| config.will_attack(&attacker.to_string(), &target.to_string());
+
+        assert!(config.can_eat_by_tags(&"wolf".to_string(), &"pup".to_string()));
+        assert!(!will_attack("wolf", "pup"), "Wolves never go after their own pack, food or not");
+        assert!(will_attack("wolf", "rabbit"), "Neutral pawns are hunted when they're food");
+        assert!(will_attack("golem", "wolf"), "Hostile factions are attacked though they aren't food");
+        assert!(!will_attack("golem", "rabbit"));
+        assert!(config.is_prey("wolf"), "Nothing eats wolves, but the camp hunts them");
+    }
+
+    #[test]
+    fn test_hunters_skip_allies() {
+        let mut app = setup_hunt_app();
+        spawn_pawn(&mut app, "pup", 16.0);
+        let rabbit = spawn_pawn(&mut app, "rabbit", 48.0);
+        let wolf = spawn_hunter(&mut app, "wolf");
+
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, Some(rabbit), "The closer pup is pack");
+    }
+
+    #[test]
+    fn test_hostile_factions_are_hunted() {
+        let mut app = setup_hunt_app();
+        spawn_pawn(&mut app, "rabbit", 16.0);
+        let wolf = spawn_pawn(&mut app, "wolf", 48.0);
+        let golem = spawn_hunter(&mut app, "golem");
+
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(golem).unwrap().target_entity, Some(wolf), "Camps attack the pack on sight");
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::components::AttachedTo;
+    use crate::resources::GameConfig;
+    use crate::systems::fog_of_war::{Exploration, FogOfWarSettings, TileBits, TileFog, fog_pixels, tiles_in_view, update_fog_of_war};
+    use crate::systems::pawn::Pawn;
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::tests::{setup_test_app, create_test_terrain_map};
+
+    const TILE_SIZE: f32 = 16.0;
+
+    const FOG_PAWNS_YAML: &str = r#"
+player:
+  sprite: "tileset::pawns::player"
+  tags: [medium]
+  move_speed: 150.0
+  max_health: 80
+  max_endurance: 150
+  defence: 0
+  strength: 10
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    controlled: player_input
+  eats:
+    pawns: []
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 2
+  strength: 8
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+  eats:
+    pawns: []
+"#;
+
+    fn setup_fog_app() -> App {
+        let mut app = setup_test_app();
+        let fog_of_war = FogOfWarSettings { enabled: true, reveal_radius: 3.0, ..FogOfWarSettings::default() };
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, fog_of_war, ..GameConfig::default() });
+        app.insert_resource(serde_yaml::from_str::<PawnConfig>(FOG_PAWNS_YAML).expect("Fog config should parse"));
+        app.insert_resource(create_test_terrain_map(32, 32, TILE_SIZE));
+        app.insert_resource(Exploration::new(32, 32));
+        app.init_resource::<Assets<Image>>();
+        app.add_systems(Update, update_fog_of_war);
+        app
+    }
+
+    /// World position of the middle of a tile on the 32x32 test map
+    fn tile_center(x: u32, y: u32) -> Vec3 {
+        Vec3::new((x as f32 - 15.5) * TILE_SIZE, (y as f32 - 15.5) * TILE_SIZE, 100.0)
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, tile: (u32, u32)) -> Entity {
+        app.world_mut().spawn((Pawn::new(pawn_type.to_string()), Transform::from_translation(tile_center(tile.0, tile.1)), Visibility::default())).id()
+    }
+
+    fn is_hidden(app: &App, entity: Entity) -> bool {
+        app.world().get::<Visibility>(entity) == Some(&Visibility::Hidden)
+    }
+
+    #[test]
+    fn test_tiles_within_range_of_a_viewer_are_in_view() {
+        let terrain_map = create_test_terrain_map(32, 32, TILE_SIZE);
+        let visible = tiles_in_view(&terrain_map, [(tile_center(10, 10).truncate(), 3.0)].into_iter());
+        assert!(visible.get(10, 10) && visible.get(13, 10) && visible.get(10, 7));
+        assert!(visible.get(12, 12), "Just under three tiles away diagonally");
+        assert!(!visible.get(13, 13) && !visible.get(14, 10));
+
+        let corner = tiles_in_view(&terrain_map, [(tile_center(0, 0).truncate(), 2.0)].into_iter());
+        assert!(corner.get(0, 2) && !corner.get(31, 31), "Cut off at the edge of the map");
+        assert!(!TileBits::new(4, 4).get(5, 0), "Off the map is never seen");
+    }
+
+    #[test]
+    fn test_fog_is_dimmed_where_the_map_has_been_seen() {
+        let mut exploration = Exploration::new(2, 2);
+        exploration.explored.set(0, 0);
+        exploration.explored.set(1, 1);
+        exploration.visible.set(1, 1);
+        assert_eq!(exploration.fog_at(0, 0), TileFog::Remembered);
+        assert_eq!(exploration.fog_at(1, 1), TileFog::Visible);
+        assert_eq!(exploration.fog_at(1, 0), TileFog::Unexplored);
+
+        // Rows run from the top of the map down
+        let alphas: Vec<u8> = fog_pixels(&exploration, 0.5).chunks(4).map(|pixel| pixel[3]).collect();
+        assert_eq!(alphas, vec![255, 0, 128, 255]);
+    }
+
+    #[test]
+    fn test_pawns_are_revealed_and_hidden_by_what_the_player_can_see() {
+        let mut app = setup_fog_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let rabbit = spawn_pawn(&mut app, "rabbit", (12, 5));
+        let icon = app.world_mut().spawn((AttachedTo(rabbit), Visibility::default())).id();
+        app.update();
+
+        let exploration = app.world().resource::<Exploration>();
+        assert_eq!(exploration.fog_at(5, 8), TileFog::Visible);
+        assert_eq!(exploration.fog_at(12, 5), TileFog::Unexplored);
+        assert!(!is_hidden(&app, player), "The player's own pawns are always drawn");
+        assert!(is_hidden(&app, rabbit) && is_hidden(&app, icon), "Out of sight along with its icon");
+
+        app.world_mut().get_mut::<Transform>(player).unwrap().translation = tile_center(10, 5);
+        app.update();
+        assert!(!is_hidden(&app, rabbit) && !is_hidden(&app, icon));
+
+        // Walking off leaves the ground remembered, but not who was on it
+        app.world_mut().get_mut::<Transform>(player).unwrap().translation = tile_center(20, 20);
+        app.update();
+        let exploration = app.world().resource::<Exploration>();
+        assert_eq!(exploration.fog_at(12, 5), TileFog::Remembered);
+        assert_eq!(exploration.fog_at(5, 5), TileFog::Remembered);
+        assert_eq!(exploration.fog_at(20, 20), TileFog::Visible);
+        assert!(is_hidden(&app, rabbit));
+    }
+
+    #[test]
+    fn test_fog_of_war_is_off_unless_enabled() {
+        let mut app = setup_fog_app();
+        app.world_mut().resource_mut::<GameConfig>().fog_of_war.enabled = false;
+        let rabbit = spawn_pawn(&mut app, "rabbit", (12, 5));
+        app.update();
+        assert!(!is_hidden(&app, rabbit));
+        assert_eq!(app.world().resource::<Exploration>().fog_at(12, 5), TileFog::Unexplored);
+        assert!(!GameConfig::default().fog_of_war.enabled);
+    }
+}
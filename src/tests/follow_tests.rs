@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingPriority};
+    use crate::systems::follow::{FollowTarget, setup_follow_ai, follow_ai_system};
+    use crate::systems::pack_hunt::HuntPackAI;
+    use crate::systems::pawn::{Pawn, PawnTarget, Health, CurrentBehavior, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const FOLLOW_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  follow:
+    min_distance: 1
+    max_distance: 3
+    repath_distance: 2
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: follow
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_follow_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(FOLLOW_PAWNS_YAML).expect("Follow config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(30, 30, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_systems(Update, (setup_follow_ai, follow_ai_system.after(setup_follow_ai)));
+        app
+    }
+
+    fn tile(app: &App, tile: (i32, i32)) -> Vec3 {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        Vec3::new(x, y, 100.0)
+    }
+
+    fn spawn_wolf(app: &mut App, at: (i32, i32)) -> Entity {
+        let position = tile(app, at);
+        app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(100),
+            Size { value: 1.0 },
+            Transform::from_translation(position),
+        )).id()
+    }
+
+    #[test]
+    fn test_follower_keeps_to_its_distance_band() {
+        let mut app = setup_follow_app();
+        let leader = spawn_wolf(&mut app, (5, 5));
+        let follower = spawn_wolf(&mut app, (7, 5));
+        app.world_mut().entity_mut(follower).insert(FollowTarget { leader });
+
+        // Two tiles off is inside the band - no need to move
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(follower).is_none());
+
+        // The leader wanders off past max_distance
+        let far = tile(&app, (12, 5));
+        app.world_mut().get_mut::<Transform>(leader).unwrap().translation = far;
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(follower).expect("The follower should set off");
+        assert_eq!(request.goal, (far.x, far.y));
+        assert_eq!(request.priority, PathfindingPriority::Normal);
+    }
+
+    #[test]
+    fn test_follower_repaths_only_when_the_leader_moves_far_enough() {
+        let mut app = setup_follow_app();
+        let leader = spawn_wolf(&mut app, (12, 5));
+        let follower = spawn_wolf(&mut app, (5, 5));
+        let heading = tile(&app, (12, 5));
+        app.world_mut().entity_mut(follower).insert((FollowTarget { leader }, PawnTarget { path: vec![heading], ..PawnTarget::new(heading) }));
+
+        // One tile's drift is within repath_distance
+        app.world_mut().get_mut::<Transform>(leader).unwrap().translation = tile(&app, (13, 5));
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(follower).is_none());
+
+        app.world_mut().get_mut::<Transform>(leader).unwrap().translation = tile(&app, (15, 5));
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(follower).is_some(), "Three tiles off where it was heading");
+    }
+
+    #[test]
+    fn test_follower_waits_once_close() {
+        let mut app = setup_follow_app();
+        let leader = spawn_wolf(&mut app, (5, 5));
+        let follower = spawn_wolf(&mut app, (5, 5));
+        let heading = tile(&app, (12, 5));
+        app.world_mut().entity_mut(follower).insert((FollowTarget { leader }, PawnTarget { path: vec![heading], ..PawnTarget::new(heading) }));
+
+        app.update();
+        assert!(app.world().get::<PawnTarget>(follower).is_none(), "Within min_distance the follower stops");
+        assert!(app.world().get::<PathfindingRequest>(follower).is_none());
+    }
+
+    #[test]
+    fn test_followers_pick_their_pack_leader_and_let_go_when_it_dies() {
+        let mut app = setup_follow_app();
+        let leader = spawn_wolf(&mut app, (5, 5));
+        let follower = spawn_wolf(&mut app, (6, 5));
+        let loner = spawn_wolf(&mut app, (20, 20));
+        app.world_mut().entity_mut(follower).insert(HuntPackAI { leader: Some(leader), ..HuntPackAI::new() });
+
+        app.update();
+        assert_eq!(app.world().get::<FollowTarget>(follower), Some(&FollowTarget { leader }));
+        assert!(app.world().get::<FollowTarget>(loner).is_none(), "Nobody to follow");
+
+        app.world_mut().get_mut::<Health>(leader).unwrap().current = 0.0;
+        app.update();
+        assert!(app.world().get::<FollowTarget>(follower).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::audio::SoundConfig;
+    use crate::systems::footsteps::{Footstep, FootstepParticle, track_footsteps, spawn_footstep_particles, update_footstep_particles};
+    use crate::systems::level_of_detail::{Detail, LevelOfDetail};
+    use crate::systems::pawn::Pawn;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+    const WATER: usize = 3;
+
+    #[derive(Resource, Default)]
+    struct HeardSteps(Vec<Footstep>);
+
+    fn collect_steps(mut footstep_events: EventReader<Footstep>, mut heard: ResMut<HeardSteps>) {
+        heard.0.extend(footstep_events.read().cloned());
+    }
+
+    fn setup_footsteps_app(ground: usize) -> App {
+        let mut app = setup_test_app();
+        let config = GameConfig::default();
+        let mut terrain_map = TerrainMap::new(100, 100, config.tile_size);
+        terrain_map.tiles.iter_mut().for_each(|column| column.fill(ground));
+        app.insert_resource(terrain_map);
+        app.insert_resource(config);
+        app.insert_resource(SoundConfig::default());
+        app.insert_resource(create_test_ground_configs());
+        app.init_resource::<LevelOfDetail>();
+        app.init_resource::<HeardSteps>();
+        app.add_event::<Footstep>();
+        app.add_systems(Update, (
+            track_footsteps,
+            collect_steps.after(track_footsteps),
+            spawn_footstep_particles.after(track_footsteps),
+        ));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut().spawn((
+            Pawn { pawn_type: "rabbit".to_string() },
+            Transform::from_translation(position.extend(100.0)),
+        )).id()
+    }
+
+    fn walk(app: &mut App, pawn: Entity, tiles: f32) {
+        let tile_size = app.world().resource::<GameConfig>().tile_size;
+        app.world_mut().get_mut::<Transform>(pawn).unwrap().translation.x += tiles * tile_size;
+    }
+
+    fn particle_count(app: &mut App) -> usize {
+        let mut query = app.world_mut().query::<&FootstepParticle>();
+        query.iter(app.world()).count()
+    }
+
+    #[test]
+    fn test_faster_pawns_step_more_often() {
+        let mut app = setup_footsteps_app(DIRT);
+        let tile_size = app.world().resource::<GameConfig>().tile_size;
+        let slow = spawn_pawn(&mut app, Vec2::new(0.0, 10.0 * tile_size));
+        let fast = spawn_pawn(&mut app, Vec2::new(0.0, -10.0 * tile_size));
+        app.update();
+
+        for _ in 0..8 {
+            walk(&mut app, slow, 0.25);
+            walk(&mut app, fast, 0.5);
+            app.update();
+        }
+
+        let steps = &app.world().resource::<HeardSteps>().0;
+        let slow_steps = steps.iter().filter(|step| step.position.y > 0.0).count();
+        let fast_steps = steps.iter().filter(|step| step.position.y < 0.0).count();
+        assert_eq!(slow_steps, 2, "A step for every tile walked");
+        assert_eq!(fast_steps, 4, "Twice the speed, twice the steps");
+        assert!(steps.iter().all(|step| step.terrain_type == DIRT));
+    }
+
+    #[test]
+    fn test_pawns_hidden_in_fog_leave_no_footsteps() {
+        let mut app = setup_footsteps_app(DIRT);
+        let hidden = spawn_pawn(&mut app, Vec2::ZERO);
+        app.world_mut().entity_mut(hidden).insert(Visibility::Hidden);
+        app.update();
+
+        walk(&mut app, hidden, 1.0);
+        app.update();
+
+        assert!(app.world().resource::<HeardSteps>().0.is_empty());
+        assert_eq!(particle_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_footsteps_kick_up_what_their_ground_is_set_to() {
+        for (ground, particles) in [(DIRT, 3), (WATER, 4), (GRASS, 0)] {
+            let mut app = setup_footsteps_app(ground);
+            let pawn = spawn_pawn(&mut app, Vec2::ZERO);
+            app.update();
+
+            walk(&mut app, pawn, 1.0);
+            app.update();
+
+            assert_eq!(app.world().resource::<HeardSteps>().0.len(), 1);
+            assert_eq!(particle_count(&mut app), particles, "Particles on ground {}", ground);
+        }
+    }
+
+    #[test]
+    fn test_no_particles_when_zoomed_out() {
+        let mut app = setup_footsteps_app(DIRT);
+        app.world_mut().resource_mut::<LevelOfDetail>().detail = Detail::Reduced;
+        let pawn = spawn_pawn(&mut app, Vec2::ZERO);
+        app.update();
+
+        walk(&mut app, pawn, 1.0);
+        app.update();
+
+        assert_eq!(app.world().resource::<HeardSteps>().0.len(), 1, "Still heard");
+        assert_eq!(particle_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_particles_spread_fade_and_go() {
+        let mut app = setup_footsteps_app(DIRT);
+        app.add_systems(Update, update_footstep_particles.before(track_footsteps));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        let pawn = spawn_pawn(&mut app, Vec2::ZERO);
+        app.update();
+
+        walk(&mut app, pawn, 1.0);
+        app.update();
+        let start: Vec<Vec3> = {
+            let mut query = app.world_mut().query_filtered::<&Transform, With<FootstepParticle>>();
+            query.iter(app.world()).map(|transform| transform.translation).collect()
+        };
+        assert_eq!(start.len(), 3);
+        assert!(start.iter().all(|position| *position == start[0]), "All from the foot");
+
+        app.update();
+        let mut query = app.world_mut().query_filtered::<(&Transform, &Sprite), With<FootstepParticle>>();
+        let moved: Vec<(Vec3, f32)> = query.iter(app.world())
+            .map(|(transform, sprite)| (transform.translation, sprite.color.alpha()))
+            .collect();
+        assert!(moved.iter().all(|(position, _)| *position != start[0]), "Spread out from the foot");
+        assert!(moved.iter().all(|(_, alpha)| *alpha < 1.0), "Fading");
+        assert!(moved[0].0 != moved[1].0, "Each its own way");
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(particle_count(&mut app), 0, "Gone once their lifetime's up");
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::camera::{CameraController, CameraFollow};
+    use crate::systems::gamepad::{apply_stick_dead_zone, apply_trigger_dead_zone, gamepad_camera, gamepad_selection};
+    use crate::systems::pawn::{Pawn, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::replay::{PlayerCommands, ReplayMode, take_player_commands};
+    use crate::systems::selection::{Selected, apply_selection_commands};
+    use crate::systems::sim_tick::SimTick;
+    use crate::tests::setup_test_app;
+
+    fn setup_gamepad_app() -> (App, Entity, Entity) {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.insert_resource(CameraFollow::default());
+        app.insert_resource(serde_yaml::from_str::<PawnConfig>("{}").unwrap());
+        app.insert_resource(SimTick::default());
+        app.insert_resource(PlayerCommands::default());
+        app.insert_resource(ReplayMode::default());
+        app.add_systems(Update, (
+            gamepad_camera,
+            gamepad_selection,
+            take_player_commands.after(gamepad_selection),
+            apply_selection_commands.after(take_player_commands),
+        ));
+        let camera = app.world_mut().spawn((
+            Camera2d,
+            CameraController,
+            Transform::default(),
+            OrthographicProjection::default_2d(),
+        )).id();
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+        (app, camera, gamepad)
+    }
+
+    fn gamepad(app: &mut App, entity: Entity) -> Mut<'_, Gamepad> {
+        app.world_mut().get_mut::<Gamepad>(entity).unwrap()
+    }
+
+    fn run_frames(app: &mut App, frames: usize) {
+        for _ in 0..frames {
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            app.update();
+        }
+    }
+
+    fn press(app: &mut App, entity: Entity, button: GamepadButton) {
+        gamepad(app, entity).digital_mut().press(button);
+        app.update();
+        let mut pad = gamepad(app, entity);
+        pad.digital_mut().release(button);
+        pad.digital_mut().clear();
+    }
+
+    #[test]
+    fn test_dead_zones_cut_out_drift_and_rescale_the_rest() {
+        assert_eq!(apply_stick_dead_zone(Vec2::new(0.1, 0.05), 0.15), Vec2::ZERO);
+        let half = apply_stick_dead_zone(Vec2::new(0.6, 0.0), 0.2);
+        assert!((half.x - 0.5).abs() < 1e-5, "Halfway between the dead zone and the edge reads as half: {:?}", half);
+        assert_eq!(apply_stick_dead_zone(Vec2::new(0.0, -1.0), 0.2), Vec2::new(0.0, -1.0));
+
+        assert_eq!(apply_trigger_dead_zone(0.05, 0.1), 0.0);
+        assert!((apply_trigger_dead_zone(0.55, 0.1) - 0.5).abs() < 1e-5);
+        assert_eq!(apply_trigger_dead_zone(1.0, 0.1), 1.0);
+    }
+
+    #[test]
+    fn test_left_stick_pans_the_camera() {
+        let (mut app, camera, pad) = setup_gamepad_app();
+        app.update();
+
+        // Resting drift inside the dead zone leaves the camera alone
+        gamepad(&mut app, pad).analog_mut().set(GamepadAxis::LeftStickX, 0.1);
+        run_frames(&mut app, 3);
+        assert_eq!(app.world().get::<Transform>(camera).unwrap().translation, Vec3::ZERO);
+
+        gamepad(&mut app, pad).analog_mut().set(GamepadAxis::LeftStickX, 1.0);
+        run_frames(&mut app, 3);
+        let moved = app.world().get::<Transform>(camera).unwrap().translation;
+        assert!(moved.x > 0.0 && moved.y == 0.0, "Camera should pan right: {:?}", moved);
+    }
+
+    #[test]
+    fn test_triggers_zoom_within_the_limits() {
+        let (mut app, camera, pad) = setup_gamepad_app();
+        app.update();
+        let scale = |app: &App| app.world().get::<OrthographicProjection>(camera).unwrap().scale;
+
+        gamepad(&mut app, pad).analog_mut().set(GamepadButton::RightTrigger2, 1.0);
+        run_frames(&mut app, 3);
+        assert!(scale(&app) < 1.0, "The right trigger zooms in");
+
+        gamepad(&mut app, pad).analog_mut().set(GamepadButton::RightTrigger2, 0.0);
+        gamepad(&mut app, pad).analog_mut().set(GamepadButton::LeftTrigger2, 1.0);
+        app.world_mut().get_mut::<OrthographicProjection>(camera).unwrap().scale = 9.99;
+        run_frames(&mut app, 3);
+        assert_eq!(scale(&app), GameConfig::default().zoom_max, "The left trigger zooms out, up to the limit");
+    }
+
+    #[test]
+    fn test_a_selects_the_pawn_in_the_middle_and_b_clears_it() {
+        let (mut app, _, pad) = setup_gamepad_app();
+        let wolf = app.world_mut().spawn((Pawn::new("wolf".to_string()), Transform::from_xyz(2.0, 0.0, 100.0), Size { value: 1.0 })).id();
+        let rabbit = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Transform::from_xyz(200.0, 0.0, 100.0), Size { value: 1.0 }, Selected)).id();
+
+        press(&mut app, pad, GamepadButton::South);
+        assert!(app.world().get::<Selected>(wolf).is_some());
+        assert!(app.world().get::<Selected>(rabbit).is_none(), "A replaces the selection, like a click");
+
+        press(&mut app, pad, GamepadButton::East);
+        assert!(app.world().get::<Selected>(wolf).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::ai::ActiveBehaviour;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::grazing::{GrazingAI, find_nearest_edible_tile, setup_grazing_ai, grazing_ai_system};
+    use crate::systems::pawn::{Pawn, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType, DEFAULT_GRAZE_DURATION};
+    use crate::systems::sim_tick::{SimTick, TickPeriod};
+    use crate::systems::vegetation::VegetationGrowth;
+    use crate::systems::world_gen::{TerrainMap, TerrainChanges};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const GRAZER_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 5
+  eats:
+    pawns: []
+    grounds: [grass]
+    graze:
+      duration: 1.0
+      endurance: 4.0
+      leaves: dirt
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [medium, animal]
+  move_speed: 100.0
+  max_health: 50
+  max_endurance: 20
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+    grounds: [grass]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+    const STONE: usize = 2;
+
+    /// Dirt map with the given grass and stone tiles
+    fn create_grazing_terrain(grass: &[(u32, u32)], stone: &[(u32, u32)]) -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for &(x, y) in grass {
+            terrain_map.set_tile(x, y, GRASS);
+        }
+        for &(x, y) in stone {
+            terrain_map.set_tile(x, y, STONE);
+        }
+        terrain_map
+    }
+
+    fn setup_grazing_app(terrain_map: TerrainMap) -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(GRAZER_PAWNS_YAML).expect("Grazer config should parse");
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(TerrainChanges::default());
+        app.insert_resource(VegetationGrowth::default());
+        app.insert_resource(SimTick::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(300)));
+        app.add_systems(Update, (setup_grazing_ai, grazing_ai_system.after(setup_grazing_ai)));
+        app
+    }
+
+    fn spawn_grazer(app: &mut App, tile: (i32, i32), endurance: f32) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Eat) },
+            Endurance { current: endurance, max: 10.0, health_loss_timer: 0.0 },
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_graze_config_parses_with_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(GRAZER_PAWNS_YAML).unwrap();
+        let rabbit = config.get_pawn_definition("rabbit").unwrap();
+        assert_eq!(rabbit.eats.grounds, vec!["grass".to_string()]);
+        let graze = rabbit.eats.graze_config();
+        assert_eq!(graze.leaves.as_deref(), Some("dirt"));
+        assert_eq!(graze.duration, 1.0);
+
+        let deer_graze = config.get_pawn_definition("deer").unwrap().eats.graze_config();
+        assert!(deer_graze.leaves.is_none(), "Grazing leaves the ground alone unless configured");
+        assert_eq!(deer_graze.duration, DEFAULT_GRAZE_DURATION);
+    }
+
+    #[test]
+    fn test_nearest_edible_tile_skips_unreachable_grass() {
+        // Grass at (6, 5) is walled in by stone, so the farther patch at (2, 5) wins
+        let walls = [(5, 4), (6, 4), (7, 4), (5, 5), (7, 5), (5, 6), (6, 6), (7, 6)];
+        let terrain_map = create_grazing_terrain(&[(6, 5), (2, 5)], &walls);
+        let ground_configs = create_test_ground_configs();
+        let (x, y) = terrain_map.tile_to_world_coords(4, 8);
+
+        let tile = find_nearest_edible_tile(&terrain_map, &ground_configs, &[GRASS], Vec2::new(x, y), 1.0);
+        assert_eq!(tile, Some((2, 5)));
+        assert_eq!(find_nearest_edible_tile(&terrain_map, &ground_configs, &[], Vec2::new(x, y), 1.0), None);
+    }
+
+    #[test]
+    fn test_hungry_grazer_heads_for_grass() {
+        let mut app = setup_grazing_app(create_grazing_terrain(&[(8, 3)], &[]));
+        let rabbit = spawn_grazer(&mut app, (3, 3), 2.0);
+        app.update();
+        app.update();
+
+        let request = app.world().get::<PathfindingRequest>(rabbit).expect("Grazer should path to the grass");
+        let grass_position = app.world().resource::<TerrainMap>().tile_to_world_coords(8, 3);
+        assert_eq!(request.goal, grass_position);
+    }
+
+    #[test]
+    fn test_grazing_restores_endurance_and_leaves_dirt() {
+        let mut app = setup_grazing_app(create_grazing_terrain(&[(4, 4)], &[]));
+        let rabbit = spawn_grazer(&mut app, (4, 4), 2.0);
+        app.update();
+        app.update();
+        assert!(app.world().get::<GrazingAI>(rabbit).unwrap().eating.is_some(), "Grazer standing on grass starts eating");
+        assert_eq!(app.world().get::<Endurance>(rabbit).unwrap().current, 2.0, "Endurance only comes back once the tile is eaten");
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<Endurance>(rabbit).unwrap().current, 6.0);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[4][4], DIRT, "Eaten grass turns to dirt");
+        assert!(app.world().resource::<TerrainChanges>().changed_tiles.contains(&(4, 4, DIRT)));
+        let regrowing = &app.world().resource::<VegetationGrowth>().tiles;
+        assert_eq!(regrowing.len(), 1, "Eaten grass is left to grow back");
+        assert_eq!(regrowing[0].grows_back, GRASS);
+        assert_eq!(regrowing[0].regrow_tick, 60 * app.world().resource::<SimTick>().ticks_per(TickPeriod::Second));
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::async_pathfinding::{
+        PathfindingRequest, PathfindingTask, PathfindingRequestCounter, GlobalPathfindingCache,
+        PathfindingCompleted, PathfindingFailed, spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding,
+    };
+    use crate::systems::group_pathfinding::{
+        FormationLeader, FormationFollower, GroupPathfindingPlugin, formation_offset, formation_path,
+        request_group_pathfinding, formation_avoidance_system, can_follow,
+    };
+    use crate::systems::pathfinding_heatmap::PathfindingHeatmap;
+    use crate::systems::pawn::{PawnTarget, Size};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    /// Open grass map, optionally with a stone wall filling every row from `wall_from_y` up
+    fn create_open_terrain(width: u32, height: u32, wall_from_y: Option<u32>) -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(width, height, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap();
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap();
+        for x in 0..width {
+            for y in 0..height {
+                let walled = wall_from_y.is_some_and(|wall_y| y >= wall_y);
+                terrain_map.set_tile(x, y, if walled { stone } else { grass });
+            }
+        }
+        terrain_map
+    }
+
+    fn setup_group_app(terrain_map: TerrainMap) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(PathfindingRequestCounter::default());
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, (
+            spawn_cached_pathfinding_tasks,
+            handle_completed_cached_pathfinding.after(spawn_cached_pathfinding_tasks),
+        ));
+        app.add_plugins(GroupPathfindingPlugin::default());
+        app
+    }
+
+    fn spawn_member(app: &mut App, position: (f32, f32)) -> Entity {
+        app.world_mut().spawn((
+            Transform::from_xyz(position.0, position.1, 100.0),
+            Size { value: 1.0 },
+        )).id()
+    }
+
+    fn run_until_settled(app: &mut App, leader: Entity) {
+        for _ in 0..200 {
+            app.update();
+            let pending = app.world().get::<PathfindingRequest>(leader).is_some()
+                || app.world().get::<PathfindingTask>(leader).is_some();
+            if !pending {
+                // One more frame for the formation systems to react
+                app.update();
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("Leader's pathfinding never finished");
+    }
+
+    #[test]
+    fn test_formation_offsets_fill_rows_behind_leader() {
+        let offsets: Vec<Vec2> = (0..4).map(formation_offset).collect();
+        assert_eq!(offsets, vec![
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(-2.0, 2.0),
+            Vec2::new(2.0, 2.0),
+        ]);
+    }
+
+    #[test]
+    fn test_formation_path_falls_back_to_leader_waypoint_when_slot_is_blocked() {
+        let terrain_map = create_open_terrain(12, 8, Some(5));
+        let ground_configs = create_test_ground_configs();
+        let leader_path: Vec<Vec3> = (2..10)
+            .map(|x| {
+                let (world_x, world_y) = terrain_map.tile_to_world_coords(x, 3);
+                Vec3::new(world_x, world_y, 100.0)
+            })
+            .collect();
+
+        // Heading east: slot 0 is one tile north and behind, slot 1 one tile south and behind
+        let left = formation_path(&leader_path, formation_offset(0), 1.0, 1.0, &terrain_map, &ground_configs);
+        let right = formation_path(&leader_path, formation_offset(1), 1.0, 1.0, &terrain_map, &ground_configs);
+        assert_eq!(left.len(), leader_path.len());
+        assert_eq!(*left.last().unwrap(), terrain_map.tile_to_world_coords(8, 4));
+        assert_eq!(*right.last().unwrap(), terrain_map.tile_to_world_coords(8, 2));
+
+        // Slot 2 would put the follower inside the wall, so it walks the leader's own route
+        let blocked = formation_path(&leader_path, formation_offset(2), 1.0, 1.0, &terrain_map, &ground_configs);
+        for (waypoint, leader_waypoint) in blocked.iter().zip(&leader_path) {
+            assert_eq!(*waypoint, (leader_waypoint.x, leader_waypoint.y));
+        }
+    }
+
+    #[test]
+    fn test_group_request_runs_one_search_for_the_whole_group() {
+        let terrain_map = create_open_terrain(16, 16, None);
+        let leader_start = terrain_map.tile_to_world_coords(3, 8);
+        let goal = terrain_map.tile_to_world_coords(12, 8);
+        let follower_starts = [terrain_map.tile_to_world_coords(2, 9), terrain_map.tile_to_world_coords(2, 7)];
+        let mut app = setup_group_app(terrain_map);
+
+        let leader = spawn_member(&mut app, leader_start);
+        let followers = [spawn_member(&mut app, follower_starts[0]), spawn_member(&mut app, follower_starts[1])];
+        let mut commands = app.world_mut().commands();
+        request_group_pathfinding(&mut commands, leader, &followers, PathfindingRequest::new(leader_start, goal, 1.0));
+
+        run_until_settled(&mut app, leader);
+
+        assert_eq!(app.world().resource::<PathfindingRequestCounter>().next_id, 1, "Only the leader should have searched");
+        assert!(app.world().get::<PawnTarget>(leader).is_some());
+
+        let mut follower_goals = Vec::new();
+        for follower in followers {
+            let target = app.world().get::<PawnTarget>(follower).expect("Follower should have a formation target");
+            let end = target.path.last().unwrap().truncate();
+            assert!(end.distance(Vec2::new(goal.0, goal.1)) > 1.0, "Follower should stop in its slot, not on the goal");
+            assert!(end.distance(Vec2::new(goal.0, goal.1)) <= 2.0 * 32.0, "Follower should stop near the goal");
+            follower_goals.push(end);
+        }
+        assert_ne!(follower_goals[0], follower_goals[1], "Followers should have different slots");
+    }
+
+    #[test]
+    fn test_unreachable_goal_disbands_formation() {
+        let terrain_map = create_open_terrain(12, 8, Some(5));
+        let leader_start = terrain_map.tile_to_world_coords(3, 2);
+        let goal = terrain_map.tile_to_world_coords(8, 6);
+        let follower_start = terrain_map.tile_to_world_coords(2, 2);
+        let mut app = setup_group_app(terrain_map);
+
+        let leader = spawn_member(&mut app, leader_start);
+        let follower = spawn_member(&mut app, follower_start);
+        let mut commands = app.world_mut().commands();
+        request_group_pathfinding(&mut commands, leader, &[follower], PathfindingRequest::new(leader_start, goal, 1.0));
+
+        run_until_settled(&mut app, leader);
+
+        assert!(app.world().get::<FormationLeader>(leader).is_none());
+        assert!(app.world().get::<FormationFollower>(follower).is_none());
+        assert!(app.world().get::<PawnTarget>(follower).is_none());
+    }
+
+    #[test]
+    fn test_formation_ends_once_the_leader_arrives() {
+        let terrain_map = create_open_terrain(16, 16, None);
+        let leader_start = terrain_map.tile_to_world_coords(3, 8);
+        let goal = terrain_map.tile_to_world_coords(12, 8);
+        let follower_start = terrain_map.tile_to_world_coords(2, 9);
+        let mut app = setup_group_app(terrain_map);
+
+        let leader = spawn_member(&mut app, leader_start);
+        let follower = spawn_member(&mut app, follower_start);
+        let mut commands = app.world_mut().commands();
+        request_group_pathfinding(&mut commands, leader, &[follower], PathfindingRequest::new(leader_start, goal, 1.0));
+
+        run_until_settled(&mut app, leader);
+        assert!(app.world().get::<FormationLeader>(leader).is_some(), "Still on its way");
+
+        app.world_mut().entity_mut(leader).remove::<PawnTarget>();
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<FormationLeader>(leader).is_none());
+        assert!(app.world().get::<FormationFollower>(follower).is_none());
+        assert!(app.world().get::<PawnTarget>(follower).is_some(), "The follower still finishes its walk");
+    }
+
+    #[test]
+    fn test_only_nearby_pawns_that_fit_the_path_can_follow() {
+        let leader = (Vec2::ZERO, 1.0, false);
+        assert!(can_follow((Vec2::new(64.0, 0.0), 1.0, false), leader, 32.0));
+        assert!(!can_follow((Vec2::new(200.0, 0.0), 1.0, false), leader, 32.0), "Too far behind");
+        assert!(!can_follow((Vec2::new(64.0, 0.0), 2.0, false), leader, 32.0), "Too big for the path");
+        assert!(can_follow((Vec2::new(64.0, 0.0), 1.0, true), leader, 32.0), "Swimmers can walk too");
+        assert!(!can_follow((Vec2::new(64.0, 0.0), 1.0, false), (Vec2::ZERO, 1.0, true), 32.0), "Walkers can't swim after a swimmer");
+    }
+
+    #[test]
+    fn test_stacked_followers_slide_apart() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+        let terrain_map = create_open_terrain(12, 8, None);
+        let leader_position = terrain_map.tile_to_world_coords(2, 2);
+        let stacked = terrain_map.tile_to_world_coords(6, 4);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.add_systems(Update, formation_avoidance_system);
+
+        let leader = spawn_member(&mut app, leader_position);
+        app.world_mut().entity_mut(leader).insert(FormationLeader { followers: vec![], spacing: 1.0 });
+        let first = spawn_member(&mut app, stacked);
+        let second = spawn_member(&mut app, stacked);
+        for follower in [first, second] {
+            app.world_mut().entity_mut(follower).insert(FormationFollower { leader, offset: Vec2::ZERO });
+        }
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let first_position = app.world().get::<Transform>(first).unwrap().translation;
+        let second_position = app.world().get::<Transform>(second).unwrap().translation;
+        assert!(first_position.distance(second_position) > 16.0,
+                "Followers should have separated, still {} apart", first_position.distance(second_position));
+        assert_eq!(app.world().get::<Transform>(leader).unwrap().translation.truncate(), Vec2::new(leader_position.0, leader_position.1),
+                   "Avoidance never moves the leader");
+    }
+}
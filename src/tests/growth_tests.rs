@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::eating::{Eating, eating_system};
+    use crate::systems::growth::{Grown, PawnFed, growth_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_GROWTH_PER_FEEDING};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const GROWTH_PAWNS_YAML: &str = r#"
+bear:
+  sprite: "tileset::pawns::wolf"
+  tags: [large, animal]
+  move_speed: 100.0
+  max_health: 100
+  max_endurance: 100
+  defence: 5
+  strength: 20
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  growth:
+    max_size: 2.0
+    per_feeding: 0.5
+  eats:
+    pawns: [small]
+fox:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 60
+  max_endurance: 100
+  defence: 5
+  strength: 20
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  growth:
+    max_size: 1.5
+  behaviours:
+    idle: eat
+  eats:
+    pawns: [small]
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 40
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    fn setup_growth_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(GROWTH_PAWNS_YAML).expect("Growth config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_systems(Update, growth_system);
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, max_health: u32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(max_health),
+            Endurance::new(100),
+            Size { value: 1.0 },
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id()
+    }
+
+    fn feed(app: &mut App, pawn: Entity) {
+        app.world_mut().send_event(PawnFed { pawn });
+        app.update();
+    }
+
+    #[test]
+    fn test_growth_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(GROWTH_PAWNS_YAML).expect("Growth config should parse");
+        let fox = config.get_pawn_definition("fox").unwrap().growth.clone().unwrap();
+        assert_eq!((fox.max_size, fox.per_feeding), (1.5, DEFAULT_GROWTH_PER_FEEDING));
+        assert!(config.get_pawn_definition("rabbit").unwrap().growth.is_none());
+    }
+
+    #[test]
+    fn test_feeding_grows_pawns_towards_max_size() {
+        let mut app = setup_growth_app();
+        let bear = spawn_pawn(&mut app, "bear", 100);
+        let rabbit = spawn_pawn(&mut app, "rabbit", 40);
+        app.world_mut().get_mut::<Health>(bear).unwrap().current = 50.0;
+        feed(&mut app, bear);
+        feed(&mut app, rabbit);
+
+        assert_eq!(app.world().get::<Grown>(bear).unwrap().scale, 1.5, "Half way from size 1 to 2");
+        assert_eq!(app.world().get::<Size>(bear).unwrap().value, 1.5);
+        assert_eq!(app.world().get::<Transform>(bear).unwrap().scale, Vec3::new(1.5, 1.5, 1.0), "The sprite grows too");
+        let health = app.world().get::<Health>(bear).unwrap();
+        assert_eq!((health.current, health.max), (100.0, 150.0), "Max health grows, and current health by as much");
+        assert!(app.world().get::<Grown>(rabbit).is_none(), "Rabbits don't grow");
+        assert_eq!(app.world().get::<Size>(rabbit).unwrap().value, 1.0);
+
+        for _ in 0..20 {
+            feed(&mut app, bear);
+        }
+        let size = app.world().get::<Size>(bear).unwrap().value;
+        assert!(size > 1.99 && size <= 2.0, "It never outgrows max_size, got {}", size);
+    }
+
+    #[test]
+    fn test_finished_meals_grow_the_eater() {
+        let mut app = setup_growth_app();
+        app.add_systems(Update, eating_system.before(growth_system));
+        let fox = spawn_pawn(&mut app, "fox", 60);
+        app.world_mut().entity_mut(fox).insert(Eating { prey: "rabbit".to_string(), remaining: 0.5, endurance_left: 10.0 });
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world().get::<Grown>(fox).is_none(), "Still eating");
+
+        for _ in 0..5 {
+            app.update();
+        }
+        let scale = app.world().get::<Grown>(fox).expect("The fox should have grown from its meal").scale;
+        assert!((scale - 1.05).abs() < 0.001, "A tenth of the way to 1.5, got {}", scale);
+    }
+
+    #[test]
+    fn test_grown_hunters_hit_harder() {
+        for (grown, damage) in [(None, 15.0), (Some(1.5), 25.0)] {
+            let mut app = setup_growth_app();
+            app.insert_resource(TerrainMap::new(20, 20, 16.0));
+            app.insert_resource(create_test_ground_configs());
+            app.insert_resource(GameConfig { tile_size: 16.0, ..GameConfig::default() });
+            app.add_plugins(SpatialGridPlugin::default());
+            app.add_systems(Update, hunt_solo_ai_system);
+            let rabbit = spawn_pawn(&mut app, "rabbit", 40);
+            app.world_mut().get_mut::<Transform>(rabbit).unwrap().translation.x = 10.0;
+            let fox = spawn_pawn(&mut app, "fox", 60);
+            let mut hunt_ai = HuntSoloAI::new();
+            hunt_ai.target_entity = Some(rabbit);
+            hunt_ai.last_attack_time = 1.0;
+            app.world_mut().entity_mut(fox).insert(hunt_ai);
+            if let Some(scale) = grown {
+                app.world_mut().entity_mut(fox).insert(Grown { scale });
+            }
+            app.update();
+
+            let events = app.world().resource::<Events<CombatEvent>>();
+            let blows: Vec<f32> = events.get_cursor().read(events).map(|event| event.damage).collect();
+            assert_eq!(blows, vec![damage], "20 strength (scaled by growth) against 5 defence");
+        }
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, health_regen_system};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_REGEN_MIN_ENDURANCE};
+    use crate::systems::perception::Threatened;
+    use crate::tests::setup_test_app;
+
+    const REGEN_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  health_regen:
+    rate: 2.0
+    combat_cooldown: 1.0
+  eats:
+    pawns: []
+mole:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    fn setup_regen_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(REGEN_PAWNS_YAML).expect("Regen config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_systems(Update, health_regen_system);
+        app
+    }
+
+    fn spawn_wounded(app: &mut App, pawn_type: &str, endurance: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health { current: 10.0, max: 30.0 },
+            Endurance { current: endurance, max: 10.0, health_loss_timer: 0.0 },
+        )).id()
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Health>(entity).unwrap().current
+    }
+
+    fn run(app: &mut App, updates: usize) {
+        for _ in 0..updates {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_regen_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(REGEN_PAWNS_YAML).expect("Regen config should parse");
+        let regen = config.get_pawn_definition("deer").unwrap().health_regen.clone().unwrap();
+        assert_eq!((regen.rate, regen.min_endurance, regen.combat_cooldown), (2.0, DEFAULT_REGEN_MIN_ENDURANCE, 1.0));
+        assert!(config.get_pawn_definition("mole").unwrap().health_regen.is_none());
+    }
+
+    #[test]
+    fn test_fed_pawns_heal_up_to_max() {
+        let mut app = setup_regen_app();
+        let fed = spawn_wounded(&mut app, "deer", 8.0);
+        let hungry = spawn_wounded(&mut app, "deer", 4.0);
+        let mole = spawn_wounded(&mut app, "mole", 8.0);
+        run(&mut app, 11);
+
+        assert!((health(&app, fed) - 12.0).abs() < 0.01, "Two health a second for a second, got {}", health(&app, fed));
+        assert_eq!(health(&app, hungry), 10.0, "Below half endurance it doesn't heal");
+        assert_eq!(health(&app, mole), 10.0, "No health_regen entry");
+
+        run(&mut app, 200);
+        assert_eq!(health(&app, fed), 30.0, "It stops at max health");
+    }
+
+    #[test]
+    fn test_no_regen_while_fleeing_or_fighting() {
+        let mut app = setup_regen_app();
+        let threatened = spawn_wounded(&mut app, "deer", 8.0);
+        let predator = app.world_mut().spawn_empty().id();
+        app.world_mut().entity_mut(threatened).insert(Threatened { predator });
+        let hunted = spawn_wounded(&mut app, "deer", 8.0);
+        app.world_mut().get_mut::<CurrentBehavior>(hunted).unwrap().state = "hunted".to_string();
+        let bitten = spawn_wounded(&mut app, "deer", 8.0);
+        app.update();
+        app.world_mut().send_event(CombatEvent { attacker: predator, victim: bitten, damage: 1.0 });
+        run(&mut app, 5);
+
+        assert_eq!(health(&app, threatened), 10.0);
+        assert_eq!(health(&app, hunted), 10.0);
+        assert_eq!(health(&app, bitten), 10.0, "Still within the combat cooldown");
+
+        run(&mut app, 10);
+        assert!(health(&app, bitten) > 10.0, "It heals once the fight is a second behind it");
+    }
+}
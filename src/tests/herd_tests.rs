@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::herd::{HerdingAI, herd_direction, setup_herding_ai, herding_ai_system};
+    use crate::systems::pawn::{Pawn, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, HerdConfig, BehaviourConfig, BehaviourType};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const HERD_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: herd
+  herd:
+    radius: 16
+    cohesion: 5.0
+    alignment: 0.0
+    separation: 0.0
+    move_interval_min: 0.0
+    move_interval_max: 0.0
+    move_range: 4
+  eats:
+    pawns: []
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: herd
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_herd_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(HERD_PAWNS_YAML).expect("Herd config should parse");
+        app.insert_resource(config);
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+
+        let mut terrain_map = TerrainMap::new(40, 40, TILE_SIZE);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap();
+        for x in 0..40 {
+            for y in 0..40 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+        app.insert_resource(terrain_map);
+        app.insert_resource(ground_configs);
+        app.add_systems(Update, (setup_herding_ai, herding_ai_system.after(setup_herding_ai)));
+        app
+    }
+
+    fn spawn_at(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_herd_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(HERD_PAWNS_YAML).expect("Herd config should parse");
+        assert!(matches!(config.get_behaviour_config("rabbit", "idle"), Some(BehaviourConfig::Simple(BehaviourType::Herd))));
+        let rabbit_herd = config.get_pawn_definition("rabbit").unwrap().herd_config();
+        let defaults = HerdConfig::default();
+        assert_eq!(rabbit_herd.radius, defaults.radius);
+        assert_eq!(rabbit_herd.cohesion, defaults.cohesion);
+
+        let partial: HerdConfig = serde_yaml::from_str("cohesion: 3.0").expect("Partial herd config should parse");
+        assert_eq!(partial.cohesion, 3.0);
+        assert_eq!(partial.separation, defaults.separation, "Unset weights fall back to the defaults");
+    }
+
+    #[test]
+    fn test_herd_steering() {
+        let herd = HerdConfig { cohesion: 1.0, alignment: 1.0, separation: 1.0, ..HerdConfig::default() };
+        let position = Vec2::ZERO;
+        assert_eq!(herd_direction(position, Vec2::X, &[], &herd, TILE_SIZE), Vec2::X, "Alone, a herd pawn just wanders");
+
+        let far_herd = [(Vec2::new(0.0, 100.0), Vec2::ZERO), (Vec2::new(20.0, 100.0), Vec2::ZERO)];
+        assert!(herd_direction(position, Vec2::X, &far_herd, &herd, TILE_SIZE).y > 0.0, "Cohesion pulls towards the herd");
+
+        let crowded = [(Vec2::new(0.0, 8.0), Vec2::ZERO)];
+        assert!(herd_direction(position, Vec2::X, &crowded, &herd, TILE_SIZE).y < 0.0, "Separation pushes away from a close herd-mate");
+
+        let heading_south = HerdConfig { cohesion: 0.0, ..herd.clone() };
+        let moving = [(Vec2::new(100.0, 0.0), Vec2::NEG_Y), (Vec2::new(-100.0, 0.0), Vec2::NEG_Y)];
+        assert!(herd_direction(position, Vec2::X, &moving, &heading_south, TILE_SIZE).y < 0.0, "Alignment follows the herd's heading");
+    }
+
+    #[test]
+    fn test_stragglers_head_back_to_the_herd() {
+        let mut app = setup_herd_app();
+        spawn_at(&mut app, "deer", (20, 20));
+        spawn_at(&mut app, "deer", (21, 20));
+        spawn_at(&mut app, "rabbit", (8, 8)); // Not the straggler's kind
+        let straggler = spawn_at(&mut app, "deer", (10, 20));
+
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(straggler).expect("Straggler should move");
+        let centroid = Vec2::from(app.world().resource::<TerrainMap>().tile_to_world_coords(20, 20)) + Vec2::new(TILE_SIZE / 2.0, 0.0);
+        let start = Vec2::from(request.start);
+        let goal = Vec2::from(request.goal);
+        assert!(goal.distance(centroid) < start.distance(centroid), "Heads towards the herd rather than off at random");
+        assert!(app.world().get::<HerdingAI>(straggler).is_some());
+    }
+
+    #[test]
+    fn test_herders_track_their_heading() {
+        let mut app = setup_herd_app();
+        let deer = spawn_at(&mut app, "deer", (20, 20));
+        app.update();
+        app.world_mut().entity_mut(deer).remove::<PathfindingRequest>();
+
+        app.world_mut().get_mut::<Transform>(deer).unwrap().translation.y -= 10.0;
+        app.update();
+        assert_eq!(app.world().get::<HerdingAI>(deer).unwrap().heading, Vec2::NEG_Y);
+
+        app.world_mut().get_mut::<CurrentBehavior>(deer).unwrap().state = "hunted".to_string();
+        app.world_mut().entity_mut(deer).remove::<PathfindingRequest>();
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(deer).is_none(), "Only pawns currently herding move with the herd");
+    }
+}
@@ -2,10 +2,13 @@
 mod tests {
     use bevy::prelude::*;
     use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
-    use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnBehaviours, PawnEats, BehaviourConfig, BehaviourType};
-    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system, setup_hunt_solo_ai};
+    use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnBehaviours, PawnEats, ActivitySchedule, BehaviourConfig, BehaviourType};
+    use crate::systems::faction::FactionConfig;
+    use crate::systems::ai::{HuntSoloAI, WanderingAI, WANDER_RETRY_DELAY, hunt_solo_ai_system, setup_hunt_solo_ai, handle_pathfinding_failures};
+    use crate::systems::async_pathfinding::{PathfindingFailed, PathfindFailure};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
     use crate::resources::GameConfig;
-    use crate::tests::{setup_test_app, create_test_terrain_map};
+    use crate::tests::{setup_test_app, create_test_terrain_map, create_test_ground_configs};
 
     fn create_test_hunter_config() -> PawnConfig {
         let mut pawns = std::collections::HashMap::new();
@@ -21,8 +24,10 @@ mod tests {
             defence: 10,
             attack_speed: 3.0,
             reach: 1,
+            attack: None,
             size: 1.0,
             spawn_count: 1,
+            faction: None,
             behaviours: PawnBehaviours {
                 idle: None,
                 hunted: None,
@@ -30,8 +35,37 @@ mod tests {
                 eat: None,
                 controlled: None,
                 flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
             },
-            eats: PawnEats { pawns: vec!["small".to_string(), "animal".to_string()] },
+            eats: PawnEats { pawns: vec!["small".to_string(), "animal".to_string()], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim: false,
+            swim_speed: None,
+            swim_endurance_multiplier: None,
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
         });
         
         // Create prey (rabbit)
@@ -45,8 +79,10 @@ mod tests {
             defence: 5,
             attack_speed: 3.0,
             reach: 1,
+            attack: None,
             size: 0.8,
             spawn_count: 1,
+            faction: None,
             behaviours: PawnBehaviours {
                 idle: None,
                 hunted: None,
@@ -54,8 +90,37 @@ mod tests {
                 eat: None,
                 controlled: None,
                 flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
             },
-            eats: PawnEats { pawns: vec![] },
+            eats: PawnEats { pawns: vec![], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim: false,
+            swim_speed: None,
+            swim_endurance_multiplier: None,
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
         });
         
         // Create non-prey (stone golem - not small or animal)
@@ -69,8 +134,10 @@ mod tests {
             defence: 20,
             attack_speed: 1.0,
             reach: 1,
+            attack: None,
             size: 2.0,
             spawn_count: 1,
+            faction: None,
             behaviours: PawnBehaviours {
                 idle: None,
                 hunted: None,
@@ -78,11 +145,40 @@ mod tests {
                 eat: None,
                 controlled: None,
                 flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
             },
-            eats: PawnEats { pawns: vec![] },
+            eats: PawnEats { pawns: vec![], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim: false,
+            swim_speed: None,
+            swim_endurance_multiplier: None,
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
         });
         
-        PawnConfig { pawns }
+        PawnConfig { pawns, factions: FactionConfig::default() }
     }
 
     fn create_test_config() -> GameConfig {
@@ -99,6 +195,7 @@ mod tests {
             show_fps: false,
             endurance_cost_per_cell: 1.0,
             health_loss_interval: 5.0,
+            ..GameConfig::default()
         }
     }
 
@@ -171,6 +268,7 @@ mod tests {
         app.insert_resource(config);
         app.insert_resource(game_config);
         app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
         
         // Spawn hunter at (0, 0)
         let hunter_entity = app.world_mut().spawn((
@@ -201,6 +299,7 @@ mod tests {
             Transform::from_translation(Vec3::new(16.0, 0.0, 100.0)),
         )).id();
 
+        app.add_plugins(SpatialGridPlugin::default());
         app.add_systems(Update, hunt_solo_ai_system);
         
         // Fast-forward time to trigger search (search happens every 2 seconds)
@@ -225,6 +324,7 @@ mod tests {
         app.insert_resource(config);
         app.insert_resource(game_config);
         app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
         
         // Spawn hunter
         let hunter_entity = app.world_mut().spawn((
@@ -246,6 +346,7 @@ mod tests {
             Transform::from_translation(Vec3::new(16.0, 0.0, 100.0)),
         )).id();
 
+        app.add_plugins(SpatialGridPlugin::default());
         app.add_systems(Update, hunt_solo_ai_system);
         
         // Fast-forward time to trigger search
@@ -349,4 +450,59 @@ mod tests {
         let rabbit_hunt_behavior = config.get_behaviour_config("rabbit", "looking_for_food");
         assert!(rabbit_hunt_behavior.is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_failed_pathfinding_drops_prey_and_retries_wander() {
+        let mut app = setup_test_app();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, handle_pathfinding_failures);
+
+        let prey = app.world_mut().spawn_empty().id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(prey);
+        let hunter = app.world_mut().spawn(hunt_ai).id();
+
+        let mut wandering_ai = WanderingAI::new();
+        wandering_ai.next_move_time = 6.0;
+        let wanderer = app.world_mut().spawn(wandering_ai).id();
+
+        for entity in [hunter, wanderer] {
+            app.world_mut().send_event(PathfindingFailed {
+                entity,
+                request_id: 0,
+                reason: PathfindFailure::NoPath,
+            });
+        }
+        app.update();
+
+        let hunt_ai = app.world().get::<HuntSoloAI>(hunter).unwrap();
+        assert_eq!(hunt_ai.target_entity, None, "Unreachable prey should be dropped");
+        assert_eq!(hunt_ai.unreachable_target, Some(prey), "Unreachable prey should be skipped in the next search");
+
+        let wandering_ai = app.world().get::<WanderingAI>(wanderer).unwrap();
+        assert!(wandering_ai.next_move_time <= WANDER_RETRY_DELAY, "Wanderer should pick a new destination soon");
+    }
+
+    #[test]
+    fn test_prey_off_the_map_is_dropped_but_not_skipped() {
+        let mut app = setup_test_app();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, handle_pathfinding_failures);
+
+        let prey = app.world_mut().spawn_empty().id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(prey);
+        let hunter = app.world_mut().spawn(hunt_ai).id();
+
+        app.world_mut().send_event(PathfindingFailed {
+            entity: hunter,
+            request_id: 0,
+            reason: PathfindFailure::OutOfBounds,
+        });
+        app.update();
+
+        let hunt_ai = app.world().get::<HuntSoloAI>(hunter).unwrap();
+        assert_eq!(hunt_ai.target_entity, None);
+        assert_eq!(hunt_ai.unreachable_target, None, "Prey can be hunted again once it's back on the map");
+    }
+}
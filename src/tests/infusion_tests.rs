@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::systems::infusion::{TerrainInfluence, INFUSION_THRESHOLD};
+    use crate::systems::pawn_config::PawnConfig;
+
+    #[test]
+    fn test_influence_converts_at_threshold() {
+        let mut influence = TerrainInfluence::default();
+
+        assert!(!influence.accumulate((3, 4), 0, INFUSION_THRESHOLD * 0.6));
+        assert_eq!(influence.influence((3, 4), 0), INFUSION_THRESHOLD * 0.6);
+
+        assert!(influence.accumulate((3, 4), 0, INFUSION_THRESHOLD * 0.6), "Crossing the threshold should convert the tile");
+        assert_eq!(influence.influence((3, 4), 0), 0.0, "Converted tile should start from scratch");
+    }
+
+    #[test]
+    fn test_competing_influences_are_tracked_separately() {
+        let mut influence = TerrainInfluence::default();
+
+        influence.accumulate((1, 1), 0, 40.0);
+        influence.accumulate((1, 1), 3, 70.0);
+        assert_eq!(influence.influence((1, 1), 0), 40.0);
+        assert_eq!(influence.influence((1, 1), 3), 70.0);
+
+        // The first terrain to reach the threshold wins and clears its rival
+        assert!(influence.accumulate((1, 1), 3, 30.0));
+        assert_eq!(influence.influence((1, 1), 0), 0.0);
+    }
+
+    #[test]
+    fn test_influence_decays_away() {
+        let mut influence = TerrainInfluence::default();
+
+        influence.accumulate((0, 0), 0, 10.0);
+        influence.accumulate((5, 5), 0, 2.0);
+
+        influence.decay(5.0);
+        assert_eq!(influence.influence((0, 0), 0), 5.0);
+        assert_eq!(influence.influence((5, 5), 0), 0.0, "Briefly visited tiles should forget their influence");
+    }
+
+    #[test]
+    fn test_infuses_is_optional_in_pawn_yaml() {
+        let yaml = r#"
+fire_elemental:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, elemental]
+  move_speed: 80.0
+  max_health: 200
+  max_endurance: 100
+  defence: 5
+  strength: 20
+  attack_speed: 2
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: null
+    looking_for_food: null
+  eats:
+    pawns: []
+  infuses:
+    terrain: dirt
+    rate: 5.0
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 5
+  behaviours:
+    idle: null
+    hunted: null
+    looking_for_food: null
+  eats:
+    pawns: []
+"#;
+        let config: PawnConfig = serde_yaml::from_str(yaml).expect("Pawn config should parse");
+
+        let infusion = config.get_pawn_definition("fire_elemental").unwrap().infuses.as_ref().expect("Elemental should infuse");
+        assert_eq!(infusion.terrain, "dirt");
+        assert_eq!(infusion.rate, 5.0);
+        assert!(config.get_pawn_definition("rabbit").unwrap().infuses.is_none());
+    }
+}
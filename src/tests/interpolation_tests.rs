@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::app::RunFixedMainLoopSystem;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::interpolation::{SmoothMotion, restore_simulated_positions, record_previous_positions, interpolate_drawn_positions};
+    use crate::systems::pawn::Pawn;
+    use crate::tests::setup_test_app;
+
+    /// x positions the simulation saw at each step
+    #[derive(Resource, Default)]
+    struct SeenBySimulation(Vec<f32>);
+
+    fn walk_right(mut seen: ResMut<SeenBySimulation>, mut query: Query<&mut Transform, With<Pawn>>) {
+        for mut transform in query.iter_mut() {
+            seen.0.push(transform.translation.x);
+            transform.translation.x += 10.0;
+        }
+    }
+
+    fn setup_interpolation_app(frame_millis: u64) -> (App, Entity) {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(frame_millis)));
+        app.insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(100)));
+        app.init_resource::<SeenBySimulation>();
+        app.add_systems(RunFixedMainLoop, (
+            restore_simulated_positions.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            interpolate_drawn_positions.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+        ));
+        app.add_systems(FixedFirst, record_previous_positions);
+        app.add_systems(FixedUpdate, walk_right);
+        let pawn = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Transform::default())).id();
+        (app, pawn)
+    }
+
+    #[test]
+    fn test_pawns_are_drawn_between_simulation_steps() {
+        let (mut app, pawn) = setup_interpolation_app(50);
+
+        let mut drawn = Vec::new();
+        for _ in 0..10 {
+            app.update();
+            drawn.push(app.world().get::<Transform>(pawn).unwrap().translation.x);
+        }
+
+        let seen = &app.world().resource::<SeenBySimulation>().0;
+        let steps: Vec<f32> = (0..seen.len()).map(|step| step as f32 * 10.0).collect();
+        assert_eq!(seen, &steps, "The simulation only ever sees where it left the pawn");
+
+        // Steps come every other frame; the frames in between draw the pawn halfway
+        assert!(drawn.windows(2).all(|pair| pair[1] >= pair[0]), "The pawn never jumps back: {:?}", drawn);
+        assert!(drawn.iter().any(|x| x % 10.0 == 5.0), "Some frames should land between steps: {:?}", drawn);
+        let motion = app.world().get::<SmoothMotion>(pawn).unwrap();
+        let last = *drawn.last().unwrap();
+        assert!(last >= motion.previous.x && last <= motion.current.x);
+    }
+
+    #[test]
+    fn test_simulation_goes_at_the_same_pace_whatever_the_frame_rate() {
+        let (mut fast, fast_pawn) = setup_interpolation_app(20);
+        let (mut slow, slow_pawn) = setup_interpolation_app(100);
+
+        // The first frame starts the clock - a second follows at each rate
+        for _ in 0..=50 {
+            fast.update();
+        }
+        for _ in 0..=10 {
+            slow.update();
+        }
+
+        assert_eq!(fast.world().resource::<SeenBySimulation>().0.len(), 10);
+        assert_eq!(slow.world().resource::<SeenBySimulation>().0.len(), 10);
+        assert_eq!(
+            fast.world().get::<SmoothMotion>(fast_pawn).unwrap().current,
+            slow.world().get::<SmoothMotion>(slow_pawn).unwrap().current,
+        );
+    }
+}
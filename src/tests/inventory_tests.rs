@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::inventory::{ItemConfig, Item, Inventory, carry_capacity, spawn_item, item_pickup_system, drop_inventory_on_death};
+    use crate::systems::pawn::{Pawn, Health, Size, pawn_death_system};
+    use crate::tests::setup_test_app;
+
+    const ITEMS_YAML: &str = r#"
+mushroom:
+  sprite: "tileset::props::mushroom"
+  spawn_count: 12
+stone:
+  sprite: "tileset::props::stone"
+  weight: 4.0
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_inventory_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(serde_yaml::from_str::<ItemConfig>(ITEMS_YAML).expect("Items should parse"));
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_systems(Update, (
+            item_pickup_system,
+            drop_inventory_on_death.after(item_pickup_system),
+            pawn_death_system.after(drop_inventory_on_death),
+        ));
+        app
+    }
+
+    fn spawn_carrier(app: &mut App, size: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new("player".to_string()),
+            Health::new(80),
+            Size { value: size },
+            Inventory::default(),
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id()
+    }
+
+    fn place(app: &mut App, item_type: &str, position: Vec2) -> Entity {
+        let mut commands = app.world_mut().commands();
+        let item = spawn_item(&mut commands, item_type, position, None);
+        app.world_mut().flush();
+        item
+    }
+
+    fn items_on_ground(app: &mut App) -> Vec<(String, Vec2)> {
+        let mut query = app.world_mut().query::<(&Item, &Transform)>();
+        query.iter(app.world()).map(|(item, transform)| (item.item_type.clone(), transform.translation.truncate())).collect()
+    }
+
+    #[test]
+    fn test_item_config_defaults() {
+        let config: ItemConfig = serde_yaml::from_str(ITEMS_YAML).expect("Items should parse");
+        let mushroom = config.get("mushroom").unwrap();
+        assert_eq!((mushroom.weight, mushroom.spawn_count), (1.0, 12));
+        assert_eq!((config.weight("stone"), config.get("stone").unwrap().spawn_count), (4.0, 0));
+        assert_eq!(carry_capacity(&Size { value: 1.5 }), 15.0);
+    }
+
+    #[test]
+    fn test_carriers_pick_up_items_they_walk_over() {
+        let mut app = setup_inventory_app();
+        let carrier = spawn_carrier(&mut app, 1.0);
+        place(&mut app, "mushroom", Vec2::new(4.0, 0.0));
+        place(&mut app, "mushroom", Vec2::new(3.0 * TILE_SIZE, 0.0));
+        app.update();
+
+        assert_eq!(app.world().get::<Inventory>(carrier).unwrap().items, vec!["mushroom".to_string()]);
+        assert_eq!(items_on_ground(&mut app).len(), 1, "The far mushroom is left where it is");
+    }
+
+    #[test]
+    fn test_carry_capacity_depends_on_size() {
+        let mut app = setup_inventory_app();
+        let small = spawn_carrier(&mut app, 1.0);
+        for _ in 0..3 {
+            place(&mut app, "stone", Vec2::ZERO);
+        }
+        app.update();
+        assert_eq!(app.world().get::<Inventory>(small).unwrap().items.len(), 2, "Two stones make 8 of a capacity of 10");
+        assert_eq!(items_on_ground(&mut app).len(), 1);
+
+        let mut app = setup_inventory_app();
+        let big = spawn_carrier(&mut app, 1.5);
+        for _ in 0..3 {
+            place(&mut app, "stone", Vec2::ZERO);
+        }
+        app.update();
+        assert_eq!(app.world().get::<Inventory>(big).unwrap().items.len(), 3, "Bigger pawns carry more");
+    }
+
+    #[test]
+    fn test_items_are_dropped_on_death() {
+        let mut app = setup_inventory_app();
+        let carrier = spawn_carrier(&mut app, 1.0);
+        app.world_mut().get_mut::<Inventory>(carrier).unwrap().items = vec!["mushroom".to_string(), "stone".to_string()];
+        app.world_mut().get_mut::<Transform>(carrier).unwrap().translation = Vec3::new(50.0, 20.0, 100.0);
+        app.world_mut().get_mut::<Health>(carrier).unwrap().current = 0.0;
+        app.update();
+
+        assert!(app.world().get_entity(carrier).is_err());
+        let mut dropped = items_on_ground(&mut app);
+        dropped.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(dropped, vec![
+            ("mushroom".to_string(), Vec2::new(50.0, 20.0)),
+            ("stone".to_string(), Vec2::new(50.0, 20.0)),
+        ]);
+    }
+}
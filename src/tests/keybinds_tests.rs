@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::debug_display::{DebugDisplayState, DebugMode, toggle_debug_display};
+    use crate::systems::keybinds::{Action, Binding, InputMap};
+    use crate::tests::setup_test_app;
+
+    fn setup_keybinds_app(input_map: InputMap) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(input_map);
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.insert_resource(DebugDisplayState::default());
+        app.add_systems(Update, toggle_debug_display);
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+    }
+
+    #[test]
+    fn test_bindings_are_written_by_name() {
+        let binding = |name: &str| Binding::try_from(name.to_string());
+        assert_eq!(binding("KeyW"), Ok(Binding::Key(KeyCode::KeyW)));
+        assert_eq!(binding("F12"), Ok(Binding::Key(KeyCode::F12)));
+        assert_eq!(binding("ArrowUp"), Ok(Binding::Key(KeyCode::ArrowUp)));
+        assert_eq!(binding("MouseLeft"), Ok(Binding::Mouse(MouseButton::Left)));
+        assert!(binding("Banana").is_err());
+        assert!(binding("MouseBanana").is_err());
+
+        assert_eq!(String::from(Binding::Key(KeyCode::Space)), "Space");
+        assert_eq!(String::from(Binding::Mouse(MouseButton::Middle)), "MouseMiddle");
+    }
+
+    #[test]
+    fn test_bindings_are_labelled_as_the_player_knows_them() {
+        assert_eq!(Binding::Key(KeyCode::KeyB).label(), "B");
+        assert_eq!(Binding::Key(KeyCode::Digit1).label(), "1");
+        assert_eq!(Binding::Key(KeyCode::F5).label(), "F5");
+        assert_eq!(Binding::Mouse(MouseButton::Right).label(), "MouseRight");
+    }
+
+    #[test]
+    fn test_actions_left_out_keep_their_defaults() {
+        let input_map = InputMap::from_yaml("toggle_debug: [F3, MouseRight]\npause: []\n").expect("Keybinds should parse");
+        assert_eq!(input_map.bindings(Action::ToggleDebug), &[Binding::Key(KeyCode::F3), Binding::Mouse(MouseButton::Right)]);
+        assert!(input_map.bindings(Action::Pause).is_empty(), "An empty list unbinds the action");
+        assert_eq!(input_map.bindings(Action::CameraUp), InputMap::default().bindings(Action::CameraUp));
+
+        let empty = InputMap::from_yaml("").expect("An empty file should parse");
+        assert_eq!(empty.bindings(Action::QuickSave), &[Binding::Key(KeyCode::F5)]);
+    }
+
+    #[test]
+    fn test_unknown_actions_and_keys_are_rejected() {
+        assert!(InputMap::from_yaml("dance: [KeyD]\n").is_err());
+        assert!(InputMap::from_yaml("pause: [Spacebar]\n").is_err());
+    }
+
+    #[test]
+    fn test_rebound_action_follows_its_new_key() {
+        let input_map = InputMap::from_yaml("toggle_debug: [F3]\n").unwrap();
+        let mut app = setup_keybinds_app(input_map);
+
+        press(&mut app, KeyCode::F12);
+        assert_eq!(app.world().resource::<DebugDisplayState>().mode, DebugMode::Off, "F12 no longer toggles the debug display");
+
+        press(&mut app, KeyCode::F3);
+        assert_ne!(app.world().resource::<DebugDisplayState>().mode, DebugMode::Off);
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::camera::CameraController;
+    use crate::systems::level_of_detail::{Detail, LevelOfDetail, LevelOfDetailSettings, PawnMarker, update_level_of_detail, apply_level_of_detail};
+    use crate::systems::pawn::Pawn;
+    use crate::systems::shadows::PawnShadow;
+    use crate::tests::setup_test_app;
+
+    fn setup_lod_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.init_resource::<LevelOfDetail>();
+        app.add_systems(Update, (update_level_of_detail, apply_level_of_detail.after(update_level_of_detail)));
+        app.world_mut().spawn((OrthographicProjection::default_2d(), CameraController));
+        app
+    }
+
+    fn zoom_to(app: &mut App, scale: f32) {
+        let mut projections = app.world_mut().query::<&mut OrthographicProjection>();
+        projections.single_mut(app.world_mut()).scale = scale;
+        app.update();
+        app.update(); // Markers are swapped in by commands, so they settle a frame later
+    }
+
+    fn spawn_pawn(app: &mut App) -> (Entity, Entity) {
+        let sprite = Sprite {
+            texture_atlas: Some(TextureAtlas { layout: Handle::default(), index: 4 }),
+            ..default()
+        };
+        let mut shadow = Entity::PLACEHOLDER;
+        let pawn = app.world_mut().spawn((Pawn::new("rabbit".to_string()), sprite, Transform::default())).with_children(|parent| {
+            shadow = parent.spawn((Sprite::default(), PawnShadow)).id();
+        }).id();
+        (pawn, shadow)
+    }
+
+    #[test]
+    fn test_detail_drops_as_the_view_zooms_out() {
+        let settings = LevelOfDetailSettings::default();
+        assert_eq!(Detail::for_scale(1.0, &settings), Detail::Full);
+        assert_eq!(Detail::for_scale(settings.overlays_scale, &settings), Detail::Reduced);
+        assert_eq!(Detail::for_scale(settings.markers_scale + 1.0, &settings), Detail::Markers);
+        assert!(LevelOfDetail { detail: Detail::Markers, scale: 8.0 }.hides_overlays());
+        assert!(!LevelOfDetail::default().hides_overlays());
+    }
+
+    #[test]
+    fn test_pawns_turn_to_dots_and_back() {
+        let mut app = setup_lod_app();
+        let (pawn, shadow) = spawn_pawn(&mut app);
+        zoom_to(&mut app, 1.0);
+        assert_eq!(app.world().get::<Visibility>(shadow), Some(&Visibility::Inherited));
+
+        zoom_to(&mut app, 4.0);
+        assert_eq!(app.world().resource::<LevelOfDetail>().detail, Detail::Reduced);
+        assert_eq!(app.world().get::<Visibility>(shadow), Some(&Visibility::Hidden));
+        assert!(app.world().get::<PawnMarker>(pawn).is_none());
+
+        zoom_to(&mut app, 8.0);
+        let sprite = app.world().get::<Sprite>(pawn).unwrap();
+        assert!(sprite.texture_atlas.is_none(), "Drawn as a plain dot");
+        assert_eq!(sprite.custom_size, Some(Vec2::splat(16.0)), "A couple of pixels across on screen");
+        zoom_to(&mut app, 10.0);
+        assert_eq!(app.world().get::<Sprite>(pawn).unwrap().custom_size, Some(Vec2::splat(20.0)));
+
+        // Pawns that turn up while zoomed out are drawn as dots too
+        let (newcomer, _) = spawn_pawn(&mut app);
+        app.update();
+        assert!(app.world().get::<PawnMarker>(newcomer).is_some());
+
+        zoom_to(&mut app, 1.0);
+        let sprite = app.world().get::<Sprite>(pawn).unwrap();
+        assert_eq!(sprite.texture_atlas.as_ref().map(|atlas| atlas.index), Some(4), "Its own sprite back");
+        assert!(app.world().get::<PawnMarker>(pawn).is_none());
+        assert_eq!(app.world().get::<Visibility>(shadow), Some(&Visibility::Inherited));
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system, flee_ai_system};
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::memory::{Memory, memory_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, VisionConfig};
+    use crate::systems::perception::Vision;
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const MEMORY_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    looking_for_food: hunt_solo
+  eats:
+    pawns: [small, animal]
+  vision:
+    range: 12
+    memory: 1.0
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    hunted: flee
+  eats:
+    pawns: []
+  vision:
+    range: 3
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const STONE: usize = 2;
+
+    /// Dirt map with a stone ridge down column 10, open at the top
+    fn create_ridge_terrain() -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for y in 0..15 {
+            terrain_map.set_tile(10, y, STONE);
+        }
+        terrain_map
+    }
+
+    fn world(terrain_map: &TerrainMap, tile: (i32, i32)) -> Vec2 {
+        let (x, y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+        Vec2::new(x, y)
+    }
+
+    fn setup_memory_app(terrain_map: TerrainMap) -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(MEMORY_PAWNS_YAML).expect("Memory config should parse");
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(250)));
+        app
+    }
+
+    fn vision_config(app: &App, pawn_type: &str) -> VisionConfig {
+        app.world().resource::<PawnConfig>().get_pawn_definition(pawn_type).unwrap().vision.clone().unwrap()
+    }
+
+    fn spawn_rabbit(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(25),
+            Size { value: 1.0 },
+            Transform::from_translation(position.extend(100.0)),
+        )).id()
+    }
+
+    fn spawn_wolf(app: &mut App, position: Vec2, memory: Memory) -> Entity {
+        let vision = vision_config(app, "wolf");
+        app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            Vision::new(&vision),
+            memory,
+            HuntSoloAI::new(),
+            Transform::from_translation(position.extend(100.0)),
+        )).id()
+    }
+
+    #[test]
+    fn test_memory_recall_and_decay() {
+        let mut memory = Memory::new(2.0);
+        let rabbit = Entity::from_raw(1);
+        assert_eq!(memory.recall(rabbit), None);
+
+        memory.remember(rabbit, Vec2::new(3.0, 4.0), 1.0);
+        memory.forget_stale(3.0);
+        assert_eq!(memory.recall(rabbit), Some(Vec2::new(3.0, 4.0)), "Still within the memory span");
+        memory.forget_stale(3.5);
+        assert_eq!(memory.recall(rabbit), None);
+
+        memory.remember(rabbit, Vec2::ZERO, 4.0);
+        memory.forget(rabbit);
+        assert_eq!(memory.recall(rabbit), None);
+    }
+
+    #[test]
+    fn test_pawns_remember_where_they_last_saw_someone() {
+        let terrain_map = create_ridge_terrain();
+        let seen_pos = world(&terrain_map, (8, 5));
+        let hidden_pos = world(&terrain_map, (14, 5));
+        let wolf_pos = world(&terrain_map, (5, 5));
+        let mut app = setup_memory_app(terrain_map);
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, memory_system);
+
+        let span = vision_config(&app, "wolf").memory;
+        let wolf = spawn_wolf(&mut app, wolf_pos, Memory::new(span));
+        let rabbit = spawn_rabbit(&mut app, seen_pos);
+        let doomed = spawn_rabbit(&mut app, seen_pos);
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().get::<Memory>(wolf).unwrap().recall(rabbit), Some(seen_pos));
+
+        app.world_mut().get_mut::<Transform>(rabbit).unwrap().translation = hidden_pos.extend(100.0);
+        app.world_mut().get_mut::<Health>(doomed).unwrap().current = 0.0;
+        app.update();
+        let memory = app.world().get::<Memory>(wolf).unwrap();
+        assert_eq!(memory.recall(rabbit), Some(seen_pos), "Behind the ridge, the rabbit is where it was last seen");
+        assert_eq!(memory.recall(doomed), None, "The dead are forgotten");
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<Memory>(wolf).unwrap().recall(rabbit), None, "Forgotten after the memory span");
+    }
+
+    #[test]
+    fn test_hunters_head_for_the_last_sighting() {
+        let terrain_map = create_ridge_terrain();
+        let last_seen = world(&terrain_map, (8, 5));
+        let hidden_pos = world(&terrain_map, (14, 5));
+        let wolf_pos = world(&terrain_map, (5, 5));
+        let mut app = setup_memory_app(terrain_map);
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, hunt_solo_ai_system);
+
+        let rabbit = spawn_rabbit(&mut app, hidden_pos);
+        let mut memory = Memory::new(10.0);
+        memory.remember(rabbit, last_seen, 0.0);
+        let wolf = spawn_wolf(&mut app, wolf_pos, memory);
+        app.world_mut().get_mut::<HuntSoloAI>(wolf).unwrap().search_timer = 2.0;
+
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, Some(rabbit), "Remembered prey can be picked out of sight");
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(wolf).expect("The hunter should set off");
+        assert_eq!(request.goal, (last_seen.x, last_seen.y), "No scent to follow, so it heads for the last sighting");
+
+        app.world_mut().entity_mut(wolf).remove::<PathfindingRequest>();
+        app.world_mut().get_mut::<Transform>(wolf).unwrap().translation = last_seen.extend(100.0);
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, None, "Nothing there, so the hunter gives up");
+        assert_eq!(app.world().get::<Memory>(wolf).unwrap().recall(rabbit), None);
+    }
+
+    #[test]
+    fn test_prey_flees_from_remembered_predators() {
+        let terrain_map = TerrainMap::new(40, 40, TILE_SIZE);
+        let rabbit_pos = world(&terrain_map, (20, 20));
+        let wolf_pos = world(&terrain_map, (25, 20));
+        let remembered_pos = world(&terrain_map, (22, 20));
+        let mut app = setup_memory_app(terrain_map);
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, flee_ai_system);
+
+        let wolf = spawn_wolf(&mut app, wolf_pos, Memory::new(10.0));
+        let vision = vision_config(&app, "rabbit");
+        let rabbit = spawn_rabbit(&mut app, rabbit_pos);
+        app.world_mut().entity_mut(rabbit).insert((
+            CurrentBehavior { state: "hunted".to_string() },
+            Vision::new(&vision),
+            Memory::new(vision.memory),
+        ));
+
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(rabbit).is_none(), "The wolf is out of sight and was never seen");
+
+        app.world_mut().get_mut::<Memory>(rabbit).unwrap().remember(wolf, remembered_pos, 0.0);
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(rabbit).expect("The rabbit should run");
+        assert!(request.goal.0 < rabbit_pos.x, "Runs away from where the wolf was last seen");
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::input::keyboard::Key;
+    use bevy::state::app::StatesPlugin;
+    use crate::systems::game_state::{GameState, GameSet, GameStatePlugin};
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::menu::{MenuPage, menu_back_input, parse_seed, step_map_size, type_seed};
+    use crate::systems::sim_tick::SimulationSpeed;
+    use crate::tests::setup_test_app;
+
+    #[derive(Resource, Default)]
+    struct Runs {
+        simulation: u32,
+        world: u32,
+    }
+
+    fn setup_state_app() -> App {
+        let mut app = setup_test_app();
+        app.add_plugins(StatesPlugin)
+            .add_plugins(GameStatePlugin)
+            .insert_resource(SimulationSpeed::default())
+            .insert_resource(InputMap::default())
+            .insert_resource(ButtonInput::<KeyCode>::default())
+            .insert_resource(ButtonInput::<MouseButton>::default())
+            .insert_resource(MenuPage::default())
+            .insert_resource(Runs::default())
+            .add_systems(Update, (
+                (|mut runs: ResMut<Runs>| runs.simulation += 1).in_set(GameSet::Simulation),
+                (|mut runs: ResMut<Runs>| runs.world += 1).in_set(GameSet::World),
+                menu_back_input,
+            ));
+        app.update();
+        app
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+        app.update(); // The state changes on the frame after
+    }
+
+    fn state(app: &App) -> GameState {
+        *app.world().resource::<State<GameState>>().get()
+    }
+
+    #[test]
+    fn test_seed_takes_digits_only() {
+        let mut seed = String::new();
+        for key in ["4", "x", "2"] {
+            type_seed(&mut seed, &Key::Character(key.into()));
+        }
+        assert_eq!(seed, "42");
+        type_seed(&mut seed, &Key::Backspace);
+        assert_eq!(seed, "4");
+
+        for _ in 0..12 {
+            type_seed(&mut seed, &Key::Character("9".into()));
+        }
+        assert_eq!(seed.len(), 10, "Seeds stop at ten digits");
+
+        assert_eq!(parse_seed("4"), Some(4));
+        assert_eq!(parse_seed(""), None, "An empty seed means a random one");
+        assert_eq!(parse_seed("9999999999"), None, "Too big for a seed");
+    }
+
+    #[test]
+    fn test_map_sizes_step_and_stop_at_the_ends() {
+        assert_eq!(step_map_size(128, true), 256);
+        assert_eq!(step_map_size(128, false), 64);
+        assert_eq!(step_map_size(512, true), 512);
+        assert_eq!(step_map_size(64, false), 64);
+        assert_eq!(step_map_size(100, true), 128, "Sizes from settings.yaml step to the next one");
+    }
+
+    #[test]
+    fn test_gameplay_only_runs_while_playing() {
+        let mut app = setup_state_app();
+        assert_eq!(state(&app), GameState::MainMenu);
+        assert!(app.world().resource::<Time<Virtual>>().is_paused(), "Nothing is simulated on the main menu");
+        let runs = app.world().resource::<Runs>();
+        assert_eq!((runs.simulation, runs.world), (0, 0));
+
+        set_state(&mut app, GameState::Playing);
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+        let runs = app.world().resource::<Runs>();
+        assert_eq!((runs.simulation, runs.world), (1, 1));
+
+        set_state(&mut app, GameState::Paused);
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+        let runs = app.world().resource::<Runs>();
+        assert_eq!((runs.simulation, runs.world), (1, 2), "The world is still drawn under the pause menu");
+    }
+
+    #[test]
+    fn test_menu_key_opens_and_closes_the_pause_menu() {
+        let mut app = setup_state_app();
+        press(&mut app, KeyCode::Escape);
+        assert_eq!(state(&app), GameState::MainMenu, "There's nothing to pause on the main menu");
+
+        set_state(&mut app, GameState::Playing);
+        press(&mut app, KeyCode::Escape);
+        assert_eq!(state(&app), GameState::Paused);
+
+        *app.world_mut().resource_mut::<MenuPage>() = MenuPage::Settings;
+        press(&mut app, KeyCode::Escape);
+        assert_eq!(state(&app), GameState::Paused);
+        assert_eq!(*app.world().resource::<MenuPage>(), MenuPage::Main, "Goes back a screen first");
+
+        press(&mut app, KeyCode::Escape);
+        assert_eq!(state(&app), GameState::Playing);
+    }
+
+    #[test]
+    fn test_unpausing_the_menu_keeps_a_paused_simulation_paused() {
+        let mut app = setup_state_app();
+        app.world_mut().resource_mut::<SimulationSpeed>().paused = true;
+        set_state(&mut app, GameState::Playing);
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+    }
+}
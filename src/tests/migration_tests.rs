@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::ActiveBehaviour;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::den::HomeDen;
+    use crate::systems::migration::{RegionFood, Territory, Migrating, update_region_food, migration_system, REGION_TILES};
+    use crate::systems::pawn::{Pawn, Health, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_MIGRATION_MIN_DISTANCE};
+    use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const MIGRATION_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  migrate:
+    min_food: 40
+  eats:
+    pawns: []
+    grounds: [grass]
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [large, animal]
+  move_speed: 100.0
+  max_health: 50
+  max_endurance: 10
+  defence: 5
+  strength: 10
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: [small]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    /// Three regions side by side: bare dirt, dirt and a region of grass
+    fn create_migration_terrain(ground_configs: &GroundConfigs) -> TerrainMap {
+        let dirt = ground_configs.terrain_mapping["dirt"];
+        let grass = ground_configs.terrain_mapping["grass"];
+        let width = REGION_TILES as u32 * 3;
+        let mut terrain_map = TerrainMap::new(width, REGION_TILES as u32, TILE_SIZE);
+        for x in 0..width {
+            for y in 0..REGION_TILES as u32 {
+                terrain_map.set_tile(x, y, if x >= REGION_TILES as u32 * 2 { grass } else { dirt });
+            }
+        }
+        terrain_map
+    }
+
+    fn setup_migration_app() -> App {
+        let mut app = setup_test_app();
+        let ground_configs = create_test_ground_configs();
+        let config: PawnConfig = serde_yaml::from_str(MIGRATION_PAWNS_YAML).expect("Migration config should parse");
+        app.insert_resource(create_migration_terrain(&ground_configs));
+        app.insert_resource(ground_configs);
+        app.insert_resource(config);
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(RegionFood::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_systems(Update, (update_region_food, migration_system.after(update_region_food)));
+        app
+    }
+
+    fn spawn_rabbit(app: &mut App, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(25),
+            CurrentBehavior { state: "idle".to_string() },
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_migrate_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(MIGRATION_PAWNS_YAML).expect("Migration config should parse");
+        let migrate = config.get_pawn_definition("rabbit").unwrap().migrate.as_ref().unwrap();
+        assert_eq!(migrate.min_food, 40);
+        assert_eq!(migrate.min_distance, DEFAULT_MIGRATION_MIN_DISTANCE);
+        assert!(config.get_pawn_definition("wolf").unwrap().migrate.is_none());
+    }
+
+    #[test]
+    fn test_region_food_counts_prey_and_grazing() {
+        let mut app = setup_migration_app();
+        spawn_rabbit(&mut app, (2, 2));
+        spawn_rabbit(&mut app, (3, 3));
+        spawn_rabbit(&mut app, (REGION_TILES * 2 + 1, 1));
+        app.update();
+
+        let region_food = app.world().resource::<RegionFood>();
+        let config = app.world().resource::<PawnConfig>();
+        let ground_configs = app.world().resource::<GroundConfigs>();
+        let rabbit = "rabbit".to_string();
+        let wolf = "wolf".to_string();
+        assert_eq!(region_food.food_for((0, 0), &rabbit, config, ground_configs), 0, "Nothing to graze on dirt");
+        assert_eq!(region_food.food_for((2, 0), &rabbit, config, ground_configs), (REGION_TILES * REGION_TILES) as u32);
+        assert_eq!(region_food.food_for((0, 0), &wolf, config, ground_configs), 2, "Wolves count the rabbits");
+        assert_eq!(region_food.food_for((1, 0), &wolf, config, ground_configs), 0);
+        assert_eq!(RegionFood::region((-1, REGION_TILES)), (-1, 1));
+    }
+
+    #[test]
+    fn test_starving_pawn_migrates_to_richer_region() {
+        let mut app = setup_migration_app();
+        let rabbit = spawn_rabbit(&mut app, (4, 8));
+        app.world_mut().entity_mut(rabbit).insert(HomeDen { den: Entity::from_raw(999) });
+
+        // One check per pawn every five seconds, so it gets a turn within six
+        for _ in 0..60 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<Territory>(rabbit), Some(&Territory { region: (0, 0) }));
+        let migrating = app.world().get::<Migrating>(rabbit).expect("The rabbit should be migrating");
+        assert_eq!(migrating.region, (2, 0), "The grass region is the only one with food");
+        let centre = app.world().resource::<TerrainMap>().tile_to_world_coords(REGION_TILES * 2 + REGION_TILES / 2, REGION_TILES / 2);
+        assert_eq!(migrating.destination, Vec2::new(centre.0, centre.1), "It heads for the middle of the region");
+        assert!(app.world().get::<PathfindingRequest>(rabbit).is_some(), "It should be finding its way there");
+        assert!(app.world().get::<HomeDen>(rabbit).is_none(), "Its den is left behind");
+        assert_eq!(app.world().get::<CurrentBehavior>(rabbit).unwrap().state, "migrate");
+    }
+
+    #[test]
+    fn test_fed_pawn_stays_put() {
+        let mut app = setup_migration_app();
+        let rabbit = spawn_rabbit(&mut app, (REGION_TILES * 2 + 8, 8));
+
+        for _ in 0..60 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<Territory>(rabbit), Some(&Territory { region: (2, 0) }));
+        assert!(app.world().get::<Migrating>(rabbit).is_none());
+        assert_eq!(app.world().get::<CurrentBehavior>(rabbit).unwrap().state, "idle");
+    }
+
+    #[test]
+    fn test_migrating_pawn_settles_on_arrival() {
+        let mut app = setup_migration_app();
+        let rabbit = spawn_rabbit(&mut app, (REGION_TILES * 2 + 8, 8));
+        let destination = app.world().get::<Transform>(rabbit).unwrap().translation.truncate() + Vec2::new(TILE_SIZE, 0.0);
+        app.world_mut().entity_mut(rabbit).insert((
+            Territory { region: (0, 0) },
+            Migrating { destination, region: (2, 0) },
+        ));
+        app.world_mut().get_mut::<CurrentBehavior>(rabbit).unwrap().state = "migrate".to_string();
+        app.update();
+
+        assert_eq!(app.world().get::<Territory>(rabbit), Some(&Territory { region: (2, 0) }));
+        assert!(app.world().get::<Migrating>(rabbit).is_none());
+        assert!(app.world().get::<ActiveBehaviour>(rabbit).is_none());
+        assert_eq!(app.world().get::<CurrentBehavior>(rabbit).unwrap().state, "idle");
+    }
+}
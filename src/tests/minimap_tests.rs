@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::render::render_asset::RenderAssetUsages;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+    use crate::systems::minimap::{MinimapImage, minimap_pixels, minimap_size, minimap_to_world, update_minimap_terrain, viewport_on_minimap, world_to_minimap};
+    use crate::systems::world_gen::{GroundConfigs, TerrainChanges};
+    use crate::tests::{setup_test_app, create_test_ground_configs, create_test_terrain_map};
+
+    fn colored_ground_configs() -> GroundConfigs {
+        GroundConfigs::load_from_yaml(r#"
+grass:
+  sprite: "tileset::grounds::grass"
+  minimap_color: [0, 200, 0]
+  passable: true
+  height_min: 0.0
+  height_max: 0.5
+stone:
+  sprite: "tileset::grounds::stone"
+  passable: false
+  height_min: 0.5
+  height_max: 1.0
+"#).expect("Failed to load ground configs")
+    }
+
+    #[test]
+    fn test_grounds_without_a_color_are_gray() {
+        let ground_configs = colored_ground_configs();
+        let grass = ground_configs.terrain_mapping["grass"];
+        let stone = ground_configs.terrain_mapping["stone"];
+        assert_eq!(ground_configs.minimap_color(grass), [0, 200, 0]);
+        assert_eq!(ground_configs.minimap_color(stone), [128, 128, 128]);
+    }
+
+    #[test]
+    fn test_pixels_put_the_top_row_of_tiles_first() {
+        let ground_configs = colored_ground_configs();
+        let mut terrain_map = create_test_terrain_map(3, 2, 16.0);
+        for x in 0..3 {
+            for y in 0..2 {
+                terrain_map.set_tile(x, y, ground_configs.terrain_mapping["grass"]);
+            }
+        }
+        terrain_map.set_tile(2, 1, ground_configs.terrain_mapping["stone"]);
+
+        let pixels = minimap_pixels(&terrain_map, &ground_configs);
+        assert_eq!(pixels.len(), 3 * 2 * 4);
+        assert_eq!(&pixels[0..4], &[0, 200, 0, 255]);
+        assert_eq!(&pixels[8..12], &[128, 128, 128, 255], "Tile (2, 1) is the end of the first row");
+        assert_eq!(&pixels[20..24], &[0, 200, 0, 255]);
+    }
+
+    #[test]
+    fn test_world_positions_map_onto_the_minimap_and_back() {
+        let terrain_map = create_test_terrain_map(10, 5, 32.0);
+        assert_eq!(minimap_size(&terrain_map), Vec2::new(192.0, 96.0));
+
+        // The map spans -160..160 across and -80..80 up
+        assert_eq!(world_to_minimap(Vec2::new(-160.0, 80.0), &terrain_map), Vec2::new(0.0, 0.0));
+        assert_eq!(world_to_minimap(Vec2::new(160.0, -80.0), &terrain_map), Vec2::new(1.0, 1.0));
+        assert_eq!(world_to_minimap(Vec2::ZERO, &terrain_map), Vec2::new(0.5, 0.5));
+        assert_eq!(minimap_to_world(Vec2::new(0.25, 0.75), &terrain_map), Vec2::new(-80.0, -40.0));
+    }
+
+    #[test]
+    fn test_viewport_is_cut_off_at_the_edges() {
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let inside = viewport_on_minimap(Vec2::ZERO, Vec2::new(80.0, 40.0), &terrain_map);
+        assert_eq!(inside, Rect::new(0.25, 0.375, 0.75, 0.625));
+
+        let past_the_corner = viewport_on_minimap(Vec2::new(-160.0, 160.0), Vec2::new(80.0, 80.0), &terrain_map);
+        assert_eq!(past_the_corner, Rect::new(0.0, 0.0, 0.25, 0.25));
+    }
+
+    #[test]
+    fn test_changed_tiles_are_repainted() {
+        let mut app = setup_test_app();
+        let terrain_map = create_test_terrain_map(4, 4, 16.0);
+        let ground_configs = create_test_ground_configs();
+        let mut images = Assets::<Image>::default();
+        let handle = images.add(Image::new(
+            Extent3d { width: 4, height: 4, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            vec![0; 4 * 4 * 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ));
+        let stone = ground_configs.terrain_mapping["stone"];
+        let mut terrain_changes = TerrainChanges::default();
+        terrain_changes.add_change(1, 3, stone);
+        app.insert_resource(terrain_map);
+        app.insert_resource(ground_configs);
+        app.insert_resource(terrain_changes);
+        app.insert_resource(images);
+        app.insert_resource(MinimapImage(handle.clone()));
+        app.add_systems(Update, update_minimap_terrain);
+        app.update();
+
+        let images = app.world().resource::<Assets<Image>>();
+        let data = &images.get(&handle).unwrap().data;
+        assert_eq!(&data[4..8], &[128, 128, 128, 255], "Tile (1, 3) is second in the top row");
+        assert_eq!(&data[0..4], &[0, 0, 0, 0], "Unchanged tiles are left alone");
+    }
+}
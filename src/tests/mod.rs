@@ -1,14 +1,96 @@
 pub mod movement_tests;
 pub mod world_gen_tests;
 pub mod pawn_tests;
+pub mod pawn_template_tests;
+pub mod population_tests;
+pub mod replay_tests;
 pub mod hunt_solo_tests;
 pub mod debug_terrain_tests;
 pub mod size_pathfinding_tests;
+pub mod spawn_tests;
 pub mod path_segment_regression_tests;
 pub mod pathfinding_cache_tests;
 pub mod async_pathfinding_tests;
+pub mod save_tests;
+pub mod bestiary_tests;
+pub mod infusion_tests;
+pub mod charge_tests;
+pub mod sim_rng_tests;
+pub mod sim_tick_tests;
+pub mod attachment_tests;
+pub mod pathfinding_heatmap_tests;
+pub mod swim_tests;
+pub mod group_pathfinding_tests;
+pub mod behaviour_tree_tests;
+pub mod pack_hunt_tests;
+pub mod grazing_tests;
+pub mod vegetation_tests;
+pub mod aging_tests;
+pub mod needs_tests;
+pub mod world_clock_tests;
+pub mod weather_tests;
+pub mod season_tests;
+pub mod terrain_effect_tests;
+pub mod y_sort_tests;
+pub mod perception_tests;
+pub mod vision_tests;
+pub mod scent_tests;
+pub mod utility_tests;
+pub mod herd_tests;
+pub mod attack_tests;
+pub mod audio_tests;
+pub mod faction_tests;
+pub mod memory_tests;
+pub mod spatial_grid_tests;
+pub mod ai_tick_tests;
+pub mod scripting_tests;
+pub mod taming_tests;
+pub mod fog_of_war_tests;
+pub mod follow_tests;
+pub mod footsteps_tests;
+pub mod den_tests;
+pub mod display_tests;
+pub mod migration_tests;
+pub mod combat_tests;
+pub mod combat_feedback_tests;
+pub mod ambush_tests;
+pub mod eating_tests;
+pub mod panic_tests;
+pub mod status_effect_tests;
+pub mod health_regen_tests;
+pub mod growth_tests;
+pub mod inventory_tests;
+pub mod animation_tests;
+pub mod selection_tests;
+pub mod shadow_tests;
+pub mod soundscape_tests;
+pub mod control_tests;
+pub mod culling_tests;
+pub mod keybinds_tests;
+pub mod level_of_detail_tests;
+pub mod gamepad_tests;
+pub mod interpolation_tests;
+pub mod camera_tests;
+pub mod camera_bookmarks_tests;
+pub mod tile_tooltip_tests;
+pub mod pathfinding_stats_tests;
+pub mod render_stats_tests;
+pub mod statistics_tests;
+pub mod behaviour_icons_tests;
+pub mod passability_overlay_tests;
+pub mod minimap_tests;
+pub mod menu_tests;
+pub mod config_files_tests;
+pub mod config_validation_tests;
+pub mod settings_tests;
 
 use bevy::prelude::*;
+use crate::systems::async_pathfinding::PathfindingRequestCounter;
+use crate::systems::combat::{CombatEvent, DeathEvent};
+use crate::systems::growth::PawnFed;
+use crate::systems::sim_rng::SimRng;
+use crate::systems::status_effects::ApplyStatusEffect;
+use crate::systems::weather::Weather;
 use crate::systems::world_gen::{TerrainMap, GroundConfigs};
 
 // Test utilities
@@ -17,21 +99,34 @@ pub fn create_test_ground_configs() -> GroundConfigs {
 water:
   sprite: "tileset::grounds::water"
   passable: false
+  swimmable: true
+  drinkable: true
+  effect: { kind: water }
+  footstep: { kind: splash, particles: 4 }
   height_min: 0.0
   height_max: 0.15
 dirt:
   sprite: "tileset::grounds::dirt"
   passable: true
+  rain_speed: 0.7
+  footstep: { kind: dust }
   height_min: 0.15
   height_max: 0.3
 grass:
   sprite: "tileset::grounds::grass"
   passable: true
+  regrow_secs: 60
+  snow_cover: true
+  seasonal_sprites:
+    autumn: "tileset::grounds::grass_autumn"
+    winter: "tileset::grounds::grass_winter"
+  effect: { kind: sway, strength: 0.6 }
   height_min: 0.3
   height_max: 0.7
 stone:
   sprite: "tileset::grounds::stone"
   passable: false
+  blocks_sight: true
   height_min: 0.7
   height_max: 1.0
 "#;
@@ -68,6 +163,13 @@ pub fn setup_test_app() -> App {
     let mut app = App::new();
     app.add_plugins(MinimalPlugins)
         .add_plugins(TransformPlugin)
-        .add_plugins(HierarchyPlugin);
+        .add_plugins(HierarchyPlugin)
+        .add_event::<CombatEvent>()
+        .add_event::<DeathEvent>()
+        .add_event::<ApplyStatusEffect>()
+        .add_event::<PawnFed>()
+        .insert_resource(SimRng::default())
+        .insert_resource(PathfindingRequestCounter::default())
+        .insert_resource(Weather::default());
     app
-}
\ No newline at end of file
+}
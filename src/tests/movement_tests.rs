@@ -213,4 +213,43 @@ mod tests {
         assert!(!terrain_map.is_passable_at_world_pos(-1000.0, -1000.0, &ground_configs));
         assert_eq!(terrain_map.get_terrain_at_world_pos(-1000.0, -1000.0), None);
     }
-}
\ No newline at end of file
+
+    fn create_corner_terrain() -> TerrainMap {
+        let mut terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap_or(&2);
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+
+        for x in 0..10 {
+            for y in 0..10 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+        // Two stones touching only at a corner between (6, 6) and (7, 7)
+        terrain_map.set_tile(7, 6, stone);
+        terrain_map.set_tile(6, 7, stone);
+        terrain_map
+    }
+
+    #[test]
+    fn test_diagonal_corner_cutting_prevented() {
+        let mut terrain_map = create_corner_terrain();
+        let ground_configs = create_test_ground_configs();
+        let start = terrain_map.tile_to_world_coords(6, 6);
+        let goal = terrain_map.tile_to_world_coords(7, 7);
+
+        assert!(!terrain_map.is_step_allowed((6, 6), (7, 7), &ground_configs));
+        assert!(terrain_map.is_step_allowed((6, 6), (5, 5), &ground_configs), "Open diagonals stay allowed");
+
+        let path = terrain_map.find_path(start, goal, &ground_configs).expect("Should path around the corner");
+        assert!(path.len() > 2, "Path should not squeeze diagonally between the stones");
+
+        let sized_path = terrain_map.find_path_for_size(start, goal, 0.5, &ground_configs).expect("Should path around the corner");
+        assert!(sized_path.len() > 2, "Size-aware path should not squeeze diagonally between the stones");
+
+        // With the rule disabled the diagonal squeeze is allowed again
+        terrain_map.prevent_corner_cutting = false;
+        let path = terrain_map.find_path(start, goal, &ground_configs).unwrap();
+        assert_eq!(path.len(), 2);
+    }
+}
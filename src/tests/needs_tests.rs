@@ -0,0 +1,331 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::ActiveBehaviour;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::needs::{
+        Thirst, Energy, Asleep, DrinkingAI, need_fractions, urgent_need, can_drink_at,
+        need_decay_system, needs_behaviour_switching_system, setup_drinking_ai, drink_ai_system, sleep_system,
+    };
+    use crate::systems::pawn::{Pawn, PawnTarget, Health, Endurance, CurrentBehavior, Size, endurance_health_loss_system};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType, NeedKind, DEFAULT_NEED_MAX, DEFAULT_REST_DEFENCE};
+    use crate::systems::seasons::{Season, Seasons};
+    use crate::systems::sim_tick::{SimTick, SECONDS_PER_DAY};
+    use crate::systems::world_clock::{WorldClock, DayPhase};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const NEEDY_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [medium, animal]
+  move_speed: 100.0
+  max_health: 50
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    looking_for_food: eat
+    thirsty: drink
+    tired: sleep
+    resting: sleep
+  eats:
+    pawns: []
+  schedule: diurnal
+  rest:
+    endurance_regen: 0.1
+  needs:
+    hunger:
+      decay: 1.0
+      threshold: 0.5
+    thirst:
+      max: 50
+      decay: 5.0
+    energy:
+      decay: 2.0
+      threshold: 0.2
+fox:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    looking_for_food: eat
+  eats:
+    pawns: []
+  needs:
+    thirst:
+      decay: 1.0
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const WATER: usize = 3;
+
+    fn create_needs_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(NEEDY_PAWNS_YAML).expect("Needs config should parse")
+    }
+
+    /// Dirt map with a pond in columns 0-2
+    fn create_pond_terrain() -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for x in 0..3 {
+            for y in 0..20 {
+                terrain_map.set_tile(x, y, WATER);
+            }
+        }
+        terrain_map
+    }
+
+    fn setup_needs_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_needs_pawn_config());
+        app.insert_resource(create_pond_terrain());
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { health_loss_interval: 1.0, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(500)));
+        app
+    }
+
+    fn spawn_needy_pawn(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(50),
+            Endurance::new(10),
+            Thirst { current: 50.0, max: 50.0 },
+            Energy { current: DEFAULT_NEED_MAX, max: DEFAULT_NEED_MAX },
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    #[test]
+    fn test_most_urgent_need_wins() {
+        let config = create_needs_pawn_config();
+        let deer = config.get_pawn_definition("deer").unwrap();
+        assert_eq!(deer.need_config(NeedKind::Thirst).unwrap().max, 50.0);
+        assert_eq!(deer.need_threshold(NeedKind::Thirst), 0.3, "Unset thresholds use the default");
+
+        let endurance = Endurance { current: 4.0, max: 10.0, health_loss_timer: 0.0 };
+        let thirst = Thirst { current: 5.0, max: 50.0 };
+        let energy = Energy { current: 30.0, max: 100.0 };
+        let fractions = need_fractions(&endurance, Some(&thirst), Some(&energy));
+        assert_eq!(urgent_need(deer, &fractions, None), Some(NeedKind::Thirst), "Lowest need below its threshold");
+
+        let fractions = need_fractions(&endurance, None, Some(&energy));
+        assert_eq!(urgent_need(deer, &fractions, None), Some(NeedKind::Hunger), "Energy isn't below its 0.2 threshold");
+
+        let endurance = Endurance { current: 6.0, max: 10.0, health_loss_timer: 0.0 };
+        let fractions = need_fractions(&endurance, None, None);
+        assert_eq!(urgent_need(deer, &fractions, None), None);
+        assert_eq!(urgent_need(deer, &fractions, Some(NeedKind::Hunger)), Some(NeedKind::Hunger),
+                   "A need being tended stays urgent until it's comfortably satisfied");
+
+        let endurance = Endurance { current: 8.0, max: 10.0, health_loss_timer: 0.0 };
+        let fractions = need_fractions(&endurance, None, None);
+        assert_eq!(urgent_need(deer, &fractions, Some(NeedKind::Hunger)), None);
+    }
+
+    #[test]
+    fn test_needs_decay_and_sleep_stops_tiring() {
+        let mut app = setup_needs_app();
+        app.add_systems(Update, need_decay_system);
+        let awake = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        let sleeping = spawn_needy_pawn(&mut app, "deer", (12, 10));
+        app.world_mut().entity_mut(sleeping).insert(Asleep);
+
+        app.update();
+        app.update();
+
+        let thirst = app.world().get::<Thirst>(awake).unwrap().current;
+        let energy = app.world().get::<Energy>(awake).unwrap().current;
+        let endurance = app.world().get::<Endurance>(awake).unwrap().current;
+        assert!(thirst < 50.0 && energy < DEFAULT_NEED_MAX && endurance < 10.0, "Every configured need runs down");
+        assert!((50.0 - thirst - 5.0 * (DEFAULT_NEED_MAX - energy) / 2.0).abs() < 0.01, "Needs decay at their own rates");
+        assert_eq!(app.world().get::<Energy>(sleeping).unwrap().current, DEFAULT_NEED_MAX);
+        assert!(app.world().get::<Thirst>(sleeping).unwrap().current < 50.0, "Sleepers still get thirsty");
+    }
+
+    #[test]
+    fn test_hunger_keeps_the_seasons_pace() {
+        let mut app = setup_needs_app();
+        app.insert_resource(Seasons { current: Some(Season::Winter), endurance_decay: 2.0, ..Default::default() });
+        app.add_systems(Update, need_decay_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+
+        app.update();
+        app.update();
+
+        let thirst_lost = 50.0 - app.world().get::<Thirst>(deer).unwrap().current;
+        let endurance_lost = 10.0 - app.world().get::<Endurance>(deer).unwrap().current;
+        assert!(endurance_lost > 0.0);
+        assert!((thirst_lost / endurance_lost - 2.5).abs() < 0.01, "Hunger at twice its decay of 1 against thirst's 5");
+    }
+
+    #[test]
+    fn test_state_follows_most_urgent_need() {
+        let mut app = setup_needs_app();
+        app.add_systems(Update, needs_behaviour_switching_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        let fox = spawn_needy_pawn(&mut app, "fox", (12, 10));
+        app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 10.0;
+        app.world_mut().get_mut::<Energy>(deer).unwrap().current = 10.0;
+        app.world_mut().get_mut::<Thirst>(fox).unwrap().current = 0.0;
+
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "tired", "Energy at 10% beats thirst at 20%");
+        assert_eq!(app.world().get::<CurrentBehavior>(fox).unwrap().state, "idle",
+                   "Needs without a behaviour for their state are ignored");
+
+        app.world_mut().get_mut::<Energy>(deer).unwrap().current = 35.0;
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "tired", "Still recovering");
+
+        app.world_mut().get_mut::<Energy>(deer).unwrap().current = 60.0;
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "thirsty");
+
+        app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 50.0;
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "idle");
+    }
+
+    #[test]
+    fn test_thirsty_pawn_heads_for_water_and_drinks() {
+        let terrain_map = create_pond_terrain();
+        let ground_configs = create_test_ground_configs();
+        assert!(can_drink_at(&terrain_map, &ground_configs, (3, 5)));
+        assert!(!can_drink_at(&terrain_map, &ground_configs, (4, 5)));
+
+        let mut app = setup_needs_app();
+        app.add_systems(Update, (setup_drinking_ai, drink_ai_system.after(setup_drinking_ai)));
+        let far = spawn_needy_pawn(&mut app, "deer", (10, 5));
+        let near = spawn_needy_pawn(&mut app, "deer", (3, 5));
+        for deer in [far, near] {
+            app.world_mut().entity_mut(deer).insert(ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Drink) });
+            app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 10.0;
+        }
+
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<DrinkingAI>(far).is_some());
+        let request = app.world().get::<PathfindingRequest>(far).expect("Far pawn should path to the water");
+        let goal = app.world().resource::<TerrainMap>().world_to_tile_coords(request.goal.0, request.goal.1);
+        assert_eq!(goal, Some((3, 5)), "Drinkers stand beside water they can't walk into");
+        assert_eq!(app.world().get::<Thirst>(far).unwrap().current, 10.0);
+
+        assert!(app.world().get::<PathfindingRequest>(near).is_none());
+        assert!(app.world().get::<Thirst>(near).unwrap().current > 10.0, "Pawn next to water drinks");
+    }
+
+    #[test]
+    fn test_sleeping_pawn_stays_put_and_recovers() {
+        let mut app = setup_needs_app();
+        app.add_systems(Update, sleep_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        app.world_mut().entity_mut(deer).insert((
+            ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Sleep) },
+            PawnTarget::new(Vec3::new(0.0, 0.0, 100.0)),
+        ));
+        app.world_mut().get_mut::<Energy>(deer).unwrap().current = 10.0;
+
+        app.update();
+        app.update();
+        assert!(app.world().get::<Asleep>(deer).is_some());
+        assert!(app.world().get::<PawnTarget>(deer).is_none(), "Sleepers drop where they were going");
+        assert!(app.world().get::<Energy>(deer).unwrap().current > 10.0);
+
+        app.world_mut().entity_mut(deer).insert(ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Null) });
+        app.update();
+        assert!(app.world().get::<Asleep>(deer).is_none(), "Pawns wake once their behaviour changes");
+    }
+
+    #[test]
+    fn test_dehydration_costs_health() {
+        let mut app = setup_needs_app();
+        app.add_systems(Update, endurance_health_loss_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 0.0;
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get::<Health>(deer).unwrap().current < 50.0, "Dehydrated pawns lose health despite a full stomach");
+    }
+
+    #[test]
+    fn test_sleep_restores_endurance() {
+        let mut app = setup_needs_app();
+        app.add_systems(Update, sleep_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        app.world_mut().entity_mut(deer).insert(ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Sleep) });
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 2.0;
+        app.world_mut().get_mut::<Energy>(deer).unwrap().current = 10.0;
+
+        app.update();
+        app.update();
+        let endurance_gained = app.world().get::<Endurance>(deer).unwrap().current - 2.0;
+        let energy_gained = app.world().get::<Energy>(deer).unwrap().current - 10.0;
+        assert!(endurance_gained > 0.0);
+        assert!((endurance_gained / 10.0 - energy_gained / DEFAULT_NEED_MAX).abs() < 0.001,
+                "rest.endurance_regen matches the 10% of max per second energy comes back at");
+    }
+
+    #[test]
+    fn test_sleepers_defend_poorly() {
+        let config = create_needs_pawn_config();
+        let deer = config.get_pawn_definition("deer").unwrap();
+        assert_eq!(deer.defence_for(None, false), 5.0);
+        assert_eq!(deer.defence_for(None, true), 5.0 * DEFAULT_REST_DEFENCE, "Unset rest defence uses the default");
+    }
+
+    #[test]
+    fn test_scheduled_pawns_rest_outside_active_hours() {
+        let mut app = setup_needs_app();
+        app.insert_resource(WorldClock::default());
+        app.add_systems(Update, needs_behaviour_switching_system);
+        let deer = spawn_needy_pawn(&mut app, "deer", (10, 10));
+        let fox = spawn_needy_pawn(&mut app, "fox", (12, 10));
+
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "idle", "Diurnal pawns are up by day");
+
+        let mut dusk = SimTick::new(1);
+        dusk.tick = SECONDS_PER_DAY / 2;
+        app.insert_resource(WorldClock::from_tick(&dusk));
+        assert_eq!(app.world().resource::<WorldClock>().phase(), DayPhase::Night);
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "resting");
+        assert_eq!(app.world().get::<CurrentBehavior>(fox).unwrap().state, "idle", "Pawns without a schedule never rest");
+
+        app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 5.0;
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "thirsty", "Urgent needs wake resting pawns");
+
+        app.world_mut().get_mut::<Thirst>(deer).unwrap().current = 50.0;
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "resting");
+
+        app.insert_resource(WorldClock::default());
+        app.update();
+        assert_eq!(app.world().get::<CurrentBehavior>(deer).unwrap().state, "idle");
+    }
+}
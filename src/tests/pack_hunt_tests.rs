@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::handle_pathfinding_failures;
+    use crate::systems::async_pathfinding::{PathfindingRequest, PathfindingFailed, PathfindFailure};
+    use crate::systems::eating::Eating;
+    use crate::systems::group_pathfinding::{FormationLeader, FormationFollower};
+    use crate::systems::pack_hunt::{HuntPackAI, setup_hunt_pack_ai, form_hunting_packs, hunt_pack_ai_system, PACK_SEARCH_INTERVAL};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+
+    const PACK_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 110
+  max_endurance: 100
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 3
+  behaviours:
+    looking_for_food: hunt_pack
+  pack:
+    max_size: 3
+    coordination_radius: 5
+  eats:
+    pawns: [small, animal]
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 60
+  max_endurance: 10
+  defence: 0
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 5
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_pack_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(PACK_PAWNS_YAML).expect("Pack config should parse");
+        app.insert_resource(config);
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+
+        let mut terrain_map = TerrainMap::new(40, 40, TILE_SIZE);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap();
+        for x in 0..40 {
+            for y in 0..40 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+        app.insert_resource(terrain_map);
+        app.insert_resource(ground_configs);
+        app.add_event::<PathfindingFailed>();
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, (
+            setup_hunt_pack_ai,
+            form_hunting_packs.after(setup_hunt_pack_ai),
+            hunt_pack_ai_system.after(form_hunting_packs),
+        ));
+        app
+    }
+
+    fn spawn_wolf(app: &mut App, tile: (f32, f32)) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(110),
+            Endurance { current: 10.0, max: 100.0, health_loss_timer: 0.0 },
+            Size { value: 1.0 },
+            Transform::from_xyz(tile.0 * TILE_SIZE, tile.1 * TILE_SIZE, 100.0),
+        )).id()
+    }
+
+    fn spawn_rabbit(app: &mut App, tile: (f32, f32)) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(60),
+            Size { value: 1.0 },
+            Transform::from_xyz(tile.0 * TILE_SIZE, tile.1 * TILE_SIZE, 100.0),
+        )).id()
+    }
+
+    fn leader_of(app: &App, wolf: Entity) -> Option<Entity> {
+        app.world().get::<HuntPackAI>(wolf).and_then(|pack_ai| pack_ai.leader)
+    }
+
+    #[test]
+    fn test_packs_respect_size_and_radius() {
+        let mut app = setup_pack_app();
+        let close: Vec<Entity> = (0..4).map(|i| spawn_wolf(&mut app, (i as f32, 0.0))).collect();
+        let far = spawn_wolf(&mut app, (15.0, 15.0));
+        app.update();
+        app.update();
+
+        let mut pack_sizes = std::collections::HashMap::new();
+        for &wolf in close.iter().chain([far].iter()) {
+            let leader = leader_of(&app, wolf).expect("Every pack hunter should be in a pack");
+            *pack_sizes.entry(leader).or_insert(0) += 1;
+            assert_eq!(leader_of(&app, leader), Some(leader), "Pack leaders lead themselves");
+        }
+        assert!(pack_sizes.values().all(|&size| size <= 3), "Packs are capped at max_size: {:?}", pack_sizes);
+        assert_eq!(leader_of(&app, far), Some(far), "A wolf out of everyone's coordination radius leads its own pack");
+        assert_eq!(pack_sizes.len(), 3, "Four close wolves make a full pack plus one more, the far wolf another");
+    }
+
+    #[test]
+    fn test_pack_shares_target_and_spreads_out() {
+        let mut app = setup_pack_app();
+        let wolves: Vec<Entity> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)].iter().map(|&tile| spawn_wolf(&mut app, tile)).collect();
+        let rabbit = spawn_rabbit(&mut app, (5.0, 0.0));
+        app.update();
+
+        let leader = leader_of(&app, wolves[0]).unwrap();
+        app.world_mut().get_mut::<HuntPackAI>(leader).unwrap().search_timer = PACK_SEARCH_INTERVAL;
+        app.update();
+        assert_eq!(app.world().get::<HuntPackAI>(leader).unwrap().target_entity, Some(rabbit));
+        app.update();
+
+        let rabbit_position = Vec2::new(5.0 * TILE_SIZE, 0.0);
+        let mut goals: Vec<Vec2> = Vec::new();
+        for &wolf in &wolves {
+            let request = app.world().get::<PathfindingRequest>(wolf).expect("Every pack member should close in on the shared prey");
+            let goal = Vec2::new(request.goal.0, request.goal.1);
+            assert!(goal.distance(rabbit_position) <= TILE_SIZE, "Members approach to within reach of the prey");
+            goals.push(goal);
+        }
+        for i in 0..goals.len() {
+            for j in (i + 1)..goals.len() {
+                assert!(goals[i].distance(goals[j]) > 1.0, "Members should come at the prey from different angles");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_follows_its_leader_to_distant_prey() {
+        let mut app = setup_pack_app();
+        let wolves: Vec<Entity> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)].iter().map(|&tile| spawn_wolf(&mut app, tile)).collect();
+        spawn_rabbit(&mut app, (15.0, 0.0));
+        app.update();
+
+        let leader = leader_of(&app, wolves[0]).unwrap();
+        app.world_mut().get_mut::<HuntPackAI>(leader).unwrap().search_timer = PACK_SEARCH_INTERVAL;
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<PathfindingRequest>(leader).is_some(), "The leader searches for the whole pack");
+        let mut followers = app.world().get::<FormationLeader>(leader).expect("The pack travels in formation").followers.clone();
+        followers.sort();
+        let mut members: Vec<Entity> = wolves.iter().copied().filter(|&wolf| wolf != leader).collect();
+        members.sort();
+        assert_eq!(followers, members);
+        for member in members {
+            assert!(app.world().get::<PathfindingRequest>(member).is_none(), "Members don't search for themselves");
+            assert_eq!(app.world().get::<FormationFollower>(member).map(|slot| slot.leader), Some(leader));
+        }
+    }
+
+    #[test]
+    fn test_kill_reward_is_split_across_pack() {
+        let mut app = setup_pack_app();
+        let leader = spawn_wolf(&mut app, (0.0, 0.0));
+        let member = spawn_wolf(&mut app, (2.0, 0.0));
+        let rabbit = spawn_rabbit(&mut app, (1.0, 0.0));
+        app.world_mut().get_mut::<Health>(rabbit).unwrap().current = 1.0;
+        app.update();
+        assert_eq!(leader_of(&app, member), Some(leader));
+
+        app.world_mut().get_mut::<HuntPackAI>(leader).unwrap().search_timer = PACK_SEARCH_INTERVAL;
+        app.world_mut().get_mut::<HuntPackAI>(leader).unwrap().last_attack_time = 10.0;
+        app.update();
+
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 0.0);
+        assert_eq!(app.world().get::<Eating>(leader).unwrap().endurance_left, 30.0, "Killer gets half of the rabbit's 60 max health");
+        assert_eq!(app.world().get::<Eating>(member).unwrap().endurance_left, 30.0, "The rest of the pack gets the other half");
+        assert_eq!(app.world().get::<Endurance>(leader).unwrap().current, 10.0, "It comes in as they eat, not all at once");
+    }
+
+    #[test]
+    fn test_unreachable_prey_is_dropped_by_the_pack() {
+        let mut app = setup_test_app();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, handle_pathfinding_failures);
+
+        let prey = app.world_mut().spawn_empty().id();
+        let leader = app.world_mut().spawn_empty().id();
+        let mut leader_ai = HuntPackAI::new();
+        leader_ai.leader = Some(leader);
+        leader_ai.target_entity = Some(prey);
+        app.world_mut().entity_mut(leader).insert(leader_ai);
+        let mut member_ai = HuntPackAI::new();
+        member_ai.leader = Some(leader);
+        let member = app.world_mut().spawn(member_ai).id();
+
+        app.world_mut().send_event(PathfindingFailed { entity: member, request_id: 0, reason: PathfindFailure::NoPath });
+        app.update();
+        assert_eq!(app.world().get::<HuntPackAI>(member).unwrap().unreachable_target, Some(prey), "Member stops chasing prey it can't reach");
+        assert_eq!(app.world().get::<HuntPackAI>(leader).unwrap().target_entity, Some(prey), "The rest of the pack keeps hunting");
+
+        app.world_mut().send_event(PathfindingFailed { entity: leader, request_id: 0, reason: PathfindFailure::NoPath });
+        app.update();
+        let leader_ai = app.world().get::<HuntPackAI>(leader).unwrap();
+        assert_eq!(leader_ai.target_entity, None, "Leader drops prey it can't reach");
+        assert_eq!(leader_ai.unreachable_target, Some(prey));
+    }
+}
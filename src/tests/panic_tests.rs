@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::flee_ai_system;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::panic::{Panicked, panic_system};
+    use crate::systems::pawn::{Pawn, Health, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, DEFAULT_PANIC_RADIUS, DEFAULT_PANIC_DURATION};
+    use crate::systems::perception::{Threatened, threat_perception_system};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const PANIC_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: flee
+  eats:
+    pawns: []
+  perception:
+    detection_radius: 5
+    panic:
+      radius: 4
+      duration: 2.0
+hare:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: flee
+  eats:
+    pawns: []
+  perception:
+    panic: {}
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 60
+  max_endurance: 10
+  defence: 5
+  strength: 20
+  attack_speed: 2
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: [small]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_panic_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(PANIC_PAWNS_YAML).expect("Panic config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(30, 30, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app
+    }
+
+    fn spawn_at(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(30),
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    fn state(app: &App, entity: Entity) -> String {
+        app.world().get::<CurrentBehavior>(entity).unwrap().state.clone()
+    }
+
+    #[test]
+    fn test_panic_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(PANIC_PAWNS_YAML).expect("Panic config should parse");
+        let hare = config.get_pawn_definition("hare").unwrap().perception.as_ref().unwrap().panic.clone().unwrap();
+        assert_eq!((hare.radius, hare.duration), (DEFAULT_PANIC_RADIUS, DEFAULT_PANIC_DURATION));
+        let deer = config.get_pawn_definition("deer").unwrap().perception.as_ref().unwrap().panic.clone().unwrap();
+        assert_eq!((deer.radius, deer.duration), (4, 2.0));
+        assert!(config.get_pawn_definition("wolf").unwrap().perception.is_none());
+    }
+
+    #[test]
+    fn test_panic_spreads_to_the_same_type() {
+        let mut app = setup_panic_app();
+        app.add_systems(Update, (threat_perception_system, panic_system.after(threat_perception_system)));
+        spawn_at(&mut app, "wolf", (14, 10));
+        let spotter = spawn_at(&mut app, "deer", (10, 10));
+        let neighbour = spawn_at(&mut app, "deer", (7, 10));
+        let hare = spawn_at(&mut app, "hare", (9, 10));
+        let far_off = spawn_at(&mut app, "deer", (7, 20));
+        for _ in 0..5 {
+            app.update();
+        }
+
+        assert!(app.world().get::<Threatened>(spotter).is_some());
+        assert!(app.world().get::<Threatened>(neighbour).is_none(), "Seven tiles from the wolf is out of sight");
+        let panicked = app.world().get::<Panicked>(neighbour).expect("The neighbour should catch the panic");
+        let (wolf_x, wolf_y) = app.world().resource::<TerrainMap>().tile_to_world_coords(14, 10);
+        assert_eq!(panicked.from, Vec2::new(wolf_x, wolf_y), "It runs from where the wolf was spotted");
+        assert_eq!(state(&app, neighbour), "hunted");
+        assert!(app.world().get::<Panicked>(hare).is_none(), "Hares don't panic at deer");
+        assert!(app.world().get::<Panicked>(far_off).is_none(), "Too far away to notice");
+        assert_eq!(state(&app, far_off), "idle");
+    }
+
+    #[test]
+    fn test_panic_fades_down_the_line() {
+        let mut app = setup_panic_app();
+        app.add_systems(Update, panic_system);
+        let spotter = spawn_at(&mut app, "deer", (10, 10));
+        let wolf = spawn_at(&mut app, "wolf", (14, 10));
+        app.world_mut().entity_mut(spotter).insert(Threatened { predator: wolf });
+        let first = spawn_at(&mut app, "deer", (7, 10));
+        let second = spawn_at(&mut app, "deer", (4, 10));
+        app.update();
+        app.update();
+        app.update();
+
+        let first_panic = app.world().get::<Panicked>(first).expect("In range of the spotter").remaining;
+        let second_panic = app.world().get::<Panicked>(second).expect("In range of the first deer").remaining;
+        assert!(second_panic < first_panic, "Each deer passes on less than it has, got {} and {}", first_panic, second_panic);
+
+        // With the spotter calm again the panic runs out
+        app.world_mut().entity_mut(spotter).remove::<Threatened>();
+        for _ in 0..25 {
+            app.update();
+        }
+        for deer in [first, second] {
+            assert!(app.world().get::<Panicked>(deer).is_none());
+            assert_eq!(state(&app, deer), "idle", "Calmed down");
+        }
+    }
+
+    #[test]
+    fn test_panicked_pawns_flee_from_the_panic() {
+        let mut app = setup_panic_app();
+        app.add_systems(Update, flee_ai_system);
+        let deer = spawn_at(&mut app, "deer", (10, 10));
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(25, 10);
+        app.world_mut().entity_mut(deer).insert((
+            CurrentBehavior { state: "hunted".to_string() },
+            Panicked { from: Vec2::new(x, y), remaining: 2.0 },
+        ));
+        app.update();
+
+        let request = app.world().get::<PathfindingRequest>(deer).expect("The deer should run even with no wolf in sight");
+        assert!(request.goal.0 < request.start.0, "It runs away from where the panic came from");
+    }
+}
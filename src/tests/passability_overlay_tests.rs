@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+    use crate::systems::passability_overlay::{PassabilityOverlay, PassabilityTile, passability_color, tile_passable_for_size, update_passability_overlay};
+    use crate::systems::world_gen::{TerrainMap, TerrainChanges};
+    use crate::tests::{setup_test_app, create_test_ground_configs, create_test_terrain_map};
+
+    fn setup_overlay_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_test_terrain_map(9, 9, 32.0));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(TerrainChanges::default());
+        app.insert_resource(DebugDisplayState { mode: DebugMode::Passability });
+        app.insert_resource(PassabilityOverlay::default());
+        app.add_systems(Update, update_passability_overlay);
+        app
+    }
+
+    fn tile_color(app: &mut App, tile: (i32, i32)) -> Color {
+        let mut query = app.world_mut().query::<(&PassabilityTile, &Sprite)>();
+        query
+            .iter(app.world())
+            .find(|(overlay_tile, _)| overlay_tile.tile == tile)
+            .map(|(_, sprite)| sprite.color)
+            .expect("Every tile should have an overlay")
+    }
+
+    #[test]
+    fn test_bigger_pawns_dont_fit_next_to_stone() {
+        let terrain_map = create_test_terrain_map(9, 9, 32.0);
+        let ground_configs = create_test_ground_configs();
+
+        assert!(tile_passable_for_size(&terrain_map, &ground_configs, (4, 5), 1.0));
+        assert!(!tile_passable_for_size(&terrain_map, &ground_configs, (4, 5), 2.0));
+        assert!(!tile_passable_for_size(&terrain_map, &ground_configs, (4, 4), 0.5), "Stone itself is never passable");
+    }
+
+    #[test]
+    fn test_overlay_follows_size_and_terrain_changes() {
+        let mut app = setup_overlay_app();
+        app.update();
+
+        let mut query = app.world_mut().query::<&PassabilityTile>();
+        assert_eq!(query.iter(app.world()).count(), 81);
+        assert_eq!(tile_color(&mut app, (4, 5)), passability_color(true));
+        assert_eq!(tile_color(&mut app, (4, 4)), passability_color(false));
+
+        app.world_mut().resource_mut::<PassabilityOverlay>().size_index = 3; // Size 2
+        app.update();
+        assert_eq!(tile_color(&mut app, (4, 5)), passability_color(false));
+
+        app.world_mut().resource_mut::<PassabilityOverlay>().size_index = 0;
+        app.update();
+        let stone_type = *create_test_ground_configs().terrain_mapping.get("stone").unwrap();
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(7, 7, stone_type);
+        app.world_mut().resource_mut::<TerrainChanges>().add_change(7, 7, stone_type);
+        app.update();
+        assert_eq!(tile_color(&mut app, (7, 7)), passability_color(false), "Edited ground is redrawn");
+        assert_eq!(tile_color(&mut app, (4, 5)), passability_color(true));
+    }
+
+    #[test]
+    fn test_overlay_is_removed_with_the_mode() {
+        let mut app = setup_overlay_app();
+        app.update();
+        app.world_mut().resource_mut::<DebugDisplayState>().mode = DebugMode::Off;
+        app.update();
+
+        let mut query = app.world_mut().query::<&PassabilityTile>();
+        assert_eq!(query.iter(app.world()).count(), 0);
+    }
+}
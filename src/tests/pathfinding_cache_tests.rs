@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod tests {
     use crate::systems::world_gen::{TerrainMap, TerrainChanges};
-    use crate::systems::pathfinding_cache::PathfindingCache;
+    use crate::systems::pathfinding_cache::{PathfindingCache, CachePlugin};
+    use crate::systems::async_pathfinding::GlobalPathfindingCache;
+    use bevy::prelude::*;
     use crate::tests::create_test_ground_configs;
     use std::time::Instant;
 
@@ -554,4 +556,192 @@ mod tests {
         
         terrain_changes.clear();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_path_cache_lru_eviction() {
+        let terrain_map = create_test_terrain();
+        let mut cache = PathfindingCache::with_limits(2, 1024);
+        let size = 1.0;
+
+        let start = terrain_map.tile_to_world_coords(1, 1);
+        let goal_a = terrain_map.tile_to_world_coords(8, 8);
+        let goal_b = terrain_map.tile_to_world_coords(8, 4);
+        let goal_c = terrain_map.tile_to_world_coords(4, 8);
+
+        let _ = find_path_cached_test(&terrain_map, start, goal_a, size, &mut cache);
+        let _ = find_path_cached_test(&terrain_map, start, goal_b, size, &mut cache);
+        assert_eq!(cache.path_entry_count(), 2);
+
+        // Touch A so that B becomes the least recently used entry
+        let _ = find_path_cached_test(&terrain_map, start, goal_a, size, &mut cache);
+        assert_eq!(cache.stats.path_cache_hits, 1);
+
+        // Inserting C must evict B, not A
+        let _ = find_path_cached_test(&terrain_map, start, goal_c, size, &mut cache);
+        assert_eq!(cache.path_entry_count(), 2, "Path cache should never exceed its cap");
+        assert_eq!(cache.stats.path_evictions, 1);
+
+        let _ = find_path_cached_test(&terrain_map, start, goal_a, size, &mut cache);
+        assert_eq!(cache.stats.path_cache_hits, 2, "Recently used path should survive eviction");
+
+        let misses_before = cache.stats.path_cache_misses;
+        let _ = find_path_cached_test(&terrain_map, start, goal_b, size, &mut cache);
+        assert_eq!(cache.stats.path_cache_misses, misses_before + 1, "Evicted path should miss");
+    }
+
+    #[test]
+    fn test_passability_cache_lru_eviction() {
+        let mut cache = PathfindingCache::with_limits(16, 3);
+
+        cache.cache_passability(0, 0, 1.0, true);
+        cache.cache_passability(1, 0, 1.0, true);
+        cache.cache_passability(2, 0, 1.0, false);
+
+        // Use (0, 0) so (1, 0) is the oldest
+        assert_eq!(cache.get_passability(0, 0, 1.0), Some(true));
+
+        cache.cache_passability(3, 0, 1.0, true);
+        assert_eq!(cache.passability_entry_count(), 3, "Passability cache should never exceed its cap");
+        assert_eq!(cache.stats.passability_evictions, 1);
+
+        assert_eq!(cache.get_passability(0, 0, 1.0), Some(true));
+        assert_eq!(cache.get_passability(1, 0, 1.0), None, "Least recently used entry should be evicted");
+        assert_eq!(cache.get_passability(2, 0, 1.0), Some(false));
+        assert_eq!(cache.get_passability(3, 0, 1.0), Some(true));
+    }
+
+    #[test]
+    fn test_recaching_same_path_does_not_evict() {
+        let terrain_map = create_test_terrain();
+        let mut cache = PathfindingCache::with_limits(1, 1024);
+
+        let start = (1, 1);
+        let goal = (8, 8);
+        let path = Some(vec![terrain_map.tile_to_world_coords(1, 1), terrain_map.tile_to_world_coords(8, 8)]);
+
+        cache.cache_path(start, goal, 1.0, path.clone(), &terrain_map);
+        cache.cache_path(start, goal, 1.0, path, &terrain_map);
+
+        assert_eq!(cache.path_entry_count(), 1);
+        assert_eq!(cache.stats.path_evictions, 0, "Replacing an entry should not count as an eviction");
+    }
+
+    #[test]
+    fn test_invalidated_entries_leave_the_eviction_order() {
+        let terrain_map = create_test_terrain();
+        let mut cache = PathfindingCache::with_limits(2, 2);
+        let mut terrain_changes = TerrainChanges::default();
+        let straight = |from: (i32, i32), to: (i32, i32)| Some(vec![terrain_map.tile_to_world_coords(from.0, from.1), terrain_map.tile_to_world_coords(to.0, to.1)]);
+
+        // The older path crosses the edit; the newer one is far away from it
+        cache.cache_path((1, 1), (1, 8), 1.0, straight((1, 1), (1, 8)), &terrain_map);
+        cache.cache_path((8, 1), (8, 8), 1.0, straight((8, 1), (8, 8)), &terrain_map);
+        cache.cache_passability(1, 4, 1.0, true);
+        cache.cache_passability(8, 8, 1.0, true);
+        terrain_changes.add_change(1, 2, 0);
+        cache.invalidate_from_terrain_changes(&terrain_changes);
+        assert_eq!((cache.path_entry_count(), cache.passability_entry_count()), (1, 1));
+
+        // There's room again, so nothing that's still cached gets pushed out
+        cache.cache_path((1, 1), (4, 1), 1.0, straight((1, 1), (4, 1)), &terrain_map);
+        cache.cache_passability(4, 1, 1.0, true);
+        assert_eq!((cache.stats.path_evictions, cache.stats.passability_evictions), (0, 0));
+
+        // Full now: the next ones evict the oldest that's still there
+        cache.cache_path((1, 8), (4, 8), 1.0, straight((1, 8), (4, 8)), &terrain_map);
+        cache.cache_passability(5, 1, 1.0, true);
+        assert_eq!((cache.stats.path_evictions, cache.stats.passability_evictions), (1, 1));
+        assert_eq!(cache.get_path((8, 1), (8, 8), 1.0), None);
+        assert!(cache.get_path((1, 1), (4, 1), 1.0).is_some());
+        assert_eq!(cache.get_passability(8, 8, 1.0), None);
+        assert_eq!(cache.get_passability(4, 1, 1.0), Some(true));
+    }
+
+    fn create_large_grass_terrain() -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(48, 48, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass_type = *ground_configs.terrain_mapping.get("grass").unwrap_or(&2);
+        for x in 0..48 {
+            for y in 0..48 {
+                terrain_map.set_tile(x, y, grass_type);
+            }
+        }
+        terrain_map
+    }
+
+    #[test]
+    fn test_distant_edit_keeps_cached_path() {
+        let terrain_map = create_large_grass_terrain();
+        let mut cache = PathfindingCache::new();
+        let mut terrain_changes = TerrainChanges::default();
+        let stone_type = *create_test_ground_configs().terrain_mapping.get("stone").unwrap_or(&3);
+
+        let start = terrain_map.tile_to_world_coords(1, 1);
+        let goal = terrain_map.tile_to_world_coords(10, 10);
+        let _ = find_path_cached_test(&terrain_map, start, goal, 1.0, &mut cache);
+
+        // Edit far away from the path - only the chunks around (40, 40) are staled
+        terrain_changes.add_change(40, 40, stone_type);
+        cache.invalidate_from_terrain_changes(&terrain_changes);
+
+        let _ = find_path_cached_test(&terrain_map, start, goal, 1.0, &mut cache);
+        assert_eq!(cache.stats.path_cache_hits, 1, "Distant edit should not invalidate the path");
+
+        // Edit in a chunk the path passes through (but not on the path itself)
+        terrain_changes.clear();
+        terrain_changes.add_change(12, 2, stone_type);
+        cache.invalidate_from_terrain_changes(&terrain_changes);
+
+        let _ = find_path_cached_test(&terrain_map, start, goal, 1.0, &mut cache);
+        assert_eq!(cache.stats.path_cache_misses, 2, "Edit in a chunk the path touches should invalidate it");
+    }
+
+    #[test]
+    fn test_distant_edit_keeps_passability() {
+        let mut cache = PathfindingCache::new();
+        let mut terrain_changes = TerrainChanges::default();
+
+        cache.cache_passability(2, 2, 1.0, true);
+        cache.cache_passability(14, 14, 1.0, true);
+        cache.cache_passability(40, 40, 1.0, true);
+
+        terrain_changes.add_change(12, 12, 3);
+        cache.invalidate_from_terrain_changes(&terrain_changes);
+
+        assert_eq!(cache.get_passability(2, 2, 1.0), None, "Same-chunk passability should be invalidated");
+        assert_eq!(cache.get_passability(14, 14, 1.0), None, "Nearby passability should be invalidated");
+        assert_eq!(cache.get_passability(40, 40, 1.0), Some(true), "Distant passability should survive");
+    }
+
+    #[test]
+    fn test_no_path_invalidated_by_any_edit() {
+        let terrain_map = create_large_grass_terrain();
+        let mut cache = PathfindingCache::new();
+        let mut terrain_changes = TerrainChanges::default();
+
+        cache.cache_path((1, 1), (5, 5), 1.0, None, &terrain_map);
+        assert_eq!(cache.get_path((1, 1), (5, 5), 1.0), Some(None));
+
+        // Any edit might open a route, no matter how far away
+        terrain_changes.add_change(40, 40, 2);
+        cache.invalidate_from_terrain_changes(&terrain_changes);
+        assert_eq!(cache.get_path((1, 1), (5, 5), 1.0), None, "Cached 'no path' should be recomputed after any edit");
+    }
+
+    #[test]
+    fn test_cache_plugin_invalidates_global_cache_on_terrain_change() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TerrainChanges::default())
+            .add_plugins(CachePlugin::default());
+
+        app.world_mut().resource_mut::<GlobalPathfindingCache>().cache_mut().cache_passability(2, 2, 1.0, true);
+        app.update();
+        assert_eq!(app.world_mut().resource_mut::<GlobalPathfindingCache>().cache_mut().get_passability(2, 2, 1.0), Some(true));
+
+        app.world_mut().resource_mut::<TerrainChanges>().add_change(3, 3, 2);
+        app.update();
+        assert_eq!(app.world_mut().resource_mut::<GlobalPathfindingCache>().cache_mut().get_passability(2, 2, 1.0), None,
+                   "Terrain change should reach the global cache through the plugin's systems");
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::async_pathfinding::{
+        PathfindingRequest, PathfindingTask, PathfindingRequestCounter, GlobalPathfindingCache,
+        PathfindingCompleted, PathfindingFailed,
+        spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding,
+    };
+    use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+    use crate::systems::pathfinding_heatmap::{PathfindingHeatmap, HeatmapLayer, sync_heatmap_recording};
+    use crate::tests::{setup_test_app, create_test_ground_configs, create_test_terrain_map};
+
+    #[test]
+    fn test_debug_modes_cycle_back_to_off() {
+        let mut mode = DebugMode::Off;
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            mode = mode.next();
+            seen.push(mode);
+        }
+        assert_eq!(seen, vec![DebugMode::Pawns, DebugMode::PathfindingHeatmap, DebugMode::Population, DebugMode::Passability, DebugMode::Off]);
+    }
+
+    #[test]
+    fn test_traced_search_matches_untraced_path() {
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let start = terrain_map.tile_to_world_coords(4, 1);
+        let goal = terrain_map.tile_to_world_coords(8, 8);
+
+        let mut expanded = Vec::new();
+        let traced = terrain_map.find_path_for_size_traced(start, goal, 1.0, &ground_configs, &mut expanded);
+        let untraced = terrain_map.find_path_for_size(start, goal, 1.0, &ground_configs);
+
+        assert_eq!(traced, untraced);
+        assert!(traced.is_some());
+        assert_eq!(expanded.first(), Some(&(4, 1)), "Search should start by expanding the start tile");
+    }
+
+    #[test]
+    fn test_heat_decays_away() {
+        let mut heatmap = PathfindingHeatmap::default();
+        heatmap.record_expanded(&[(1, 1), (1, 1), (2, 2)]);
+        heatmap.record_cache_hit((1, 1));
+
+        assert_eq!(heatmap.heat(HeatmapLayer::Expanded, (1, 1)), 2.0);
+        assert_eq!(heatmap.heat(HeatmapLayer::CacheHit, (1, 1)), 1.0);
+
+        heatmap.decay(1.5);
+        assert_eq!(heatmap.heat(HeatmapLayer::Expanded, (1, 1)), 0.5);
+        assert_eq!(heatmap.heat(HeatmapLayer::Expanded, (2, 2)), 0.0);
+        assert_eq!(heatmap.heat(HeatmapLayer::CacheHit, (1, 1)), 0.0);
+    }
+
+    #[test]
+    fn test_async_searches_feed_heatmap_only_in_heatmap_mode() {
+        let mut app = setup_test_app();
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let start = terrain_map.tile_to_world_coords(4, 1);
+        let goal = terrain_map.tile_to_world_coords(8, 8);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(PathfindingRequestCounter::default());
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.insert_resource(DebugDisplayState { mode: DebugMode::PathfindingHeatmap });
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, (
+            sync_heatmap_recording,
+            spawn_cached_pathfinding_tasks.after(sync_heatmap_recording),
+            handle_completed_cached_pathfinding.after(spawn_cached_pathfinding_tasks),
+        ));
+
+        // First request is computed (expansions), second is served from the cache (cache hit)
+        for _ in 0..2 {
+            let entity = app.world_mut().spawn((
+                Transform::from_xyz(start.0, start.1, 0.0),
+                PathfindingRequest::new(start, goal, 1.0),
+            )).id();
+            for _ in 0..200 {
+                app.update();
+                if app.world().get::<PathfindingRequest>(entity).is_none() && app.world().get::<PathfindingTask>(entity).is_none() {
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+        }
+
+        let heatmap = app.world().resource::<PathfindingHeatmap>();
+        assert!(heatmap.heat(HeatmapLayer::Expanded, (4, 1)) > 0.0, "Computed search should heat its expanded tiles");
+        assert!(heatmap.heat(HeatmapLayer::CacheHit, (4, 1)) > 0.0, "Cached answer should heat the request's start tile");
+
+        // Leaving heatmap mode stops recording and clears the overlay
+        app.world_mut().resource_mut::<DebugDisplayState>().mode = DebugMode::Off;
+        app.update();
+        let heatmap = app.world().resource::<PathfindingHeatmap>();
+        assert!(!heatmap.recording);
+        assert_eq!(heatmap.heat(HeatmapLayer::Expanded, (4, 1)), 0.0);
+    }
+}
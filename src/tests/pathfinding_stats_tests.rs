@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::async_pathfinding::GlobalPathfindingCache;
+    use crate::systems::debug_display::{DebugDisplayState, DebugMode};
+    use crate::systems::pathfinding_cache::PathfindingCache;
+    use crate::systems::pathfinding_stats::{PathfindingStatsPanel, describe_cache_stats, update_pathfinding_stats_panel};
+    use crate::tests::{setup_test_app, create_test_terrain_map};
+
+    fn panel_text(app: &mut App) -> Option<String> {
+        let mut query = app.world_mut().query_filtered::<&Text, With<PathfindingStatsPanel>>();
+        query.iter(app.world()).next().map(|text| text.0.clone())
+    }
+
+    #[test]
+    fn test_stats_describe_hit_ratios_and_sizes() {
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let mut cache = PathfindingCache::with_limits(100, 200);
+        cache.cache_path((1, 1), (3, 1), 1.0, Some(vec![(0.0, 0.0)]), &terrain_map);
+        for _ in 0..3 {
+            cache.get_path((1, 1), (3, 1), 1.0);
+        }
+        cache.get_path((5, 5), (6, 6), 1.0);
+
+        let text = describe_cache_stats(&cache, 2);
+        assert!(text.contains("Path hits: 75% (3 of 4)"), "{}", text);
+        assert!(text.contains("Passability hits: 0% (0 of 0)"), "{}", text);
+        assert!(text.contains("Paths cached: 1/100"), "{}", text);
+        assert!(text.contains("Passability cached: 0/200"), "{}", text);
+        assert!(text.contains("Searches in flight: 2"), "{}", text);
+    }
+
+    #[test]
+    fn test_panel_shows_with_the_heatmap_overlay() {
+        let mut app = setup_test_app();
+        app.insert_resource(DebugDisplayState::default());
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.add_systems(Update, update_pathfinding_stats_panel);
+
+        app.update();
+        assert_eq!(panel_text(&mut app), None, "Hidden while the debug display is off");
+
+        app.world_mut().resource_mut::<DebugDisplayState>().mode = DebugMode::PathfindingHeatmap;
+        app.update();
+        let text = panel_text(&mut app).expect("The panel should show with the heatmap");
+        assert!(text.contains("Searches in flight: 0"), "{}", text);
+
+        app.world_mut().resource_mut::<DebugDisplayState>().mode = DebugMode::Off;
+        app.update();
+        assert_eq!(panel_text(&mut app), None);
+    }
+}
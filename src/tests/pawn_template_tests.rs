@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use crate::systems::config_files::ConfigPaths;
+    use crate::systems::pawn_config::{BehaviourConfig, BehaviourNode, BehaviourType, PawnConfig};
+
+    const TEMPLATES: &str = r#"
+base_animal:
+  template: true
+  tags: [animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 2
+  behaviours:
+    idle: herd
+    hunted: flee
+  eats:
+    pawns: []
+    grounds: [grass]
+  behaviour_tree:
+    action: herd
+rabbit:
+  extends: base_animal
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  max_health: 25
+  behaviours:
+    hunted: sleep
+  eats:
+    graze:
+      duration: 1.0
+      endurance: 2.0
+  behaviour_tree:
+    selector:
+      - action: flee
+hare:
+  extends: rabbit
+  sprite: "tileset::pawns::hare"
+  move_speed: 140.0
+"#;
+
+    #[test]
+    fn test_pawns_inherit_from_what_they_extend() {
+        let config = PawnConfig::from_yaml(TEMPLATES).expect("Failed to resolve templates");
+        let rabbit = config.get_pawn_definition("rabbit").unwrap();
+        assert_eq!(rabbit.max_health, 25, "Set on the pawn itself");
+        assert_eq!(rabbit.move_speed, 100.0, "Inherited");
+        assert_eq!(rabbit.tags, vec!["small", "animal"], "Lists are replaced, not added to");
+        assert!(matches!(rabbit.behaviours.idle, Some(BehaviourConfig::Simple(BehaviourType::Herd))), "Sections merge key by key");
+        assert!(matches!(rabbit.behaviours.hunted, Some(BehaviourConfig::Simple(BehaviourType::Sleep))));
+        assert_eq!(rabbit.eats.grounds, vec!["grass"]);
+        assert_eq!(rabbit.eats.graze_config().duration, 1.0);
+        assert!(matches!(rabbit.behaviour_tree, Some(BehaviourNode::Selector(_))), "Trees are replaced whole");
+
+        let hare = config.get_pawn_definition("hare").unwrap();
+        assert_eq!(hare.sprite, "tileset::pawns::hare");
+        assert_eq!(hare.move_speed, 140.0);
+        assert_eq!(hare.max_health, 25, "Through rabbit from base_animal");
+    }
+
+    #[test]
+    fn test_templates_are_not_pawns() {
+        let config = PawnConfig::from_yaml(TEMPLATES).expect("Failed to resolve templates");
+        let mut pawn_types = config.get_pawn_types();
+        pawn_types.sort();
+        assert_eq!(pawn_types, vec!["hare", "rabbit"], "base_animal has no sprite, but is only a template");
+    }
+
+    #[test]
+    fn test_extends_loops_are_reported() {
+        let error = PawnConfig::from_yaml("a:\n  extends: b\nb:\n  extends: c\nc:\n  extends: a\n").unwrap_err();
+        assert_eq!(error.to_string(), "pawns extend each other in a loop: a -> b -> c -> a");
+
+        let error = PawnConfig::from_yaml("a:\n  extends: a\n").unwrap_err();
+        assert_eq!(error.to_string(), "pawns extend each other in a loop: a -> a");
+
+        let error = PawnConfig::from_yaml("a:\n  extends: nothing\n").unwrap_err();
+        assert_eq!(error.to_string(), "a extends nothing, which isn't in pawns.yaml");
+    }
+
+    #[test]
+    fn test_loading_pawns_yaml_resolves_templates() {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "pawn_templates", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pawns.yaml"), TEMPLATES).unwrap();
+        let config = ConfigPaths { dirs: vec![dir.clone()], embedded: false, mods: Vec::new() }.load("pawns.yaml", PawnConfig::from_yaml);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(config.expect("Failed to load").get_pawn_definition("hare").unwrap().spawn_count, 2);
+    }
+}
@@ -1,10 +1,16 @@
 #[cfg(test)]
 mod tests {
     use bevy::prelude::*;
-    use crate::systems::pawn::{Pawn, Health, Endurance, PawnTarget, move_pawn_to_target, endurance_health_loss_system, pawn_death_system};
-    use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnBehaviours, PawnEats};
+    use bevy::render::render_asset::RenderAssetUsages;
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+    use crate::systems::inventory::ItemConfig;
+    use crate::systems::pawn::{Pawn, Health, Endurance, PawnTarget, Size, TilesetManager, move_pawn_to_target, endurance_health_loss_system, pawn_death_system, direct_sprite_paths};
+    use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnBehaviours, PawnEats, ActivitySchedule};
+    use crate::systems::faction::FactionConfig;
     use crate::resources::GameConfig;
-    use crate::tests::setup_test_app;
+    use crate::systems::world_gen::{TerrainMap, load_tileset_indices};
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
 
     fn create_test_pawn_config() -> PawnConfig {
         let mut pawns = std::collections::HashMap::new();
@@ -18,8 +24,10 @@ mod tests {
             defence: 5,
             attack_speed: 1.0,
             reach: 1,
+            attack: None,
             size: 1.0,
             spawn_count: 1,
+            faction: None,
             behaviours: PawnBehaviours {
                 idle: None,
                 hunted: None,
@@ -27,11 +35,40 @@ mod tests {
                 eat: None,
                 controlled: None,
                 flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
             },
-            eats: PawnEats { pawns: vec![] },
+            eats: PawnEats { pawns: vec![], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim: false,
+            swim_speed: None,
+            swim_endurance_multiplier: None,
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
         });
         
-        PawnConfig { pawns }
+        PawnConfig { pawns, factions: FactionConfig::default() }
     }
 
     fn create_test_config() -> GameConfig {
@@ -48,6 +85,7 @@ mod tests {
             show_fps: false,
             endurance_cost_per_cell: 1.0,
             health_loss_interval: 5.0,
+            ..GameConfig::default()
         }
     }
 
@@ -80,10 +118,13 @@ mod tests {
         
         app.insert_resource(config);
         app.insert_resource(pawn_config);
+        app.insert_resource(TerrainMap::new(32, 32, 16.0)); // All dirt - passable
+        app.insert_resource(create_test_ground_configs());
         
         // Spawn a pawn with target
         let entity = app.world_mut().spawn((
             Pawn::new("test_pawn".to_string()),
+            Size { value: 1.0 },
             Health::new(50),
             Endurance::new(30),
             Transform::from_translation(Vec3::new(0.0, 0.0, 100.0)),
@@ -277,4 +318,161 @@ mod tests {
         assert!(app.world().get_entity(dying_pawn).is_err(), "Dying pawn should be despawned");
         assert!(app.world().get_entity(weak_pawn).is_ok(), "Weak pawn should survive with 1 HP");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_deviation_from_path() {
+        let mut target = PawnTarget::new(Vec3::ZERO);
+        target.set_path(vec![(0.0, 0.0), (100.0, 0.0), (100.0, 100.0)]);
+
+        // Before the first waypoint is reached we measure straight to it
+        assert_eq!(target.deviation_from_path(Vec3::new(0.0, 10.0, 100.0)), 10.0);
+
+        target.advance_waypoint();
+        assert_eq!(target.deviation_from_path(Vec3::new(50.0, 0.0, 100.0)), 0.0);
+        assert_eq!(target.deviation_from_path(Vec3::new(50.0, -30.0, 100.0)), 30.0);
+        assert_eq!(target.deviation_from_path(Vec3::new(-40.0, 0.0, 100.0)), 40.0, "Distance is clamped to the segment");
+    }
+
+    fn setup_off_path_app() -> (App, TerrainMap) {
+        let mut app = setup_test_app();
+        app.insert_resource(create_test_config());
+        app.insert_resource(create_test_pawn_config());
+        app.insert_resource(create_test_ground_configs());
+        app.add_systems(Update, move_pawn_to_target);
+
+        let ground_configs = create_test_ground_configs();
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+        let mut terrain_map = TerrainMap::new(32, 32, 16.0);
+        // Stone wall along x = 20
+        for y in 0..32 {
+            terrain_map.set_tile(20, y, stone);
+        }
+        (app, terrain_map)
+    }
+
+    #[test]
+    fn test_knocked_off_path_behind_wall_repaths() {
+        let (mut app, terrain_map) = setup_off_path_app();
+        let mut target = PawnTarget::new(Vec3::ZERO);
+        target.set_path(vec![terrain_map.tile_to_world_coords(10, 10), terrain_map.tile_to_world_coords(14, 10)]);
+        target.advance_waypoint();
+
+        // Pawn was knocked to the far side of the wall
+        let (x, y) = terrain_map.tile_to_world_coords(24, 10);
+        app.insert_resource(terrain_map);
+        let entity = app.world_mut().spawn((
+            Pawn::new("test_pawn".to_string()),
+            Size { value: 1.0 },
+            Endurance::new(30),
+            Transform::from_xyz(x, y, 100.0),
+            target,
+        )).id();
+
+        app.update();
+
+        assert!(app.world().get::<PawnTarget>(entity).is_none(), "Old path should be dropped");
+        assert!(app.world().get::<PathfindingRequest>(entity).is_some(), "A new path should be requested");
+    }
+
+    #[test]
+    fn test_off_path_with_clear_line_keeps_target() {
+        let (mut app, terrain_map) = setup_off_path_app();
+        let mut target = PawnTarget::new(Vec3::ZERO);
+        target.set_path(vec![terrain_map.tile_to_world_coords(4, 10), terrain_map.tile_to_world_coords(10, 10)]);
+        target.advance_waypoint();
+
+        // Off the path but with nothing in the way
+        let (x, y) = terrain_map.tile_to_world_coords(7, 14);
+        app.insert_resource(terrain_map);
+        let entity = app.world_mut().spawn((
+            Pawn::new("test_pawn".to_string()),
+            Size { value: 1.0 },
+            Endurance::new(30),
+            Transform::from_xyz(x, y, 100.0),
+            target,
+        )).id();
+
+        app.update();
+
+        assert!(app.world().get::<PawnTarget>(entity).is_some());
+        assert!(app.world().get::<PathfindingRequest>(entity).is_none());
+    }
+
+    fn create_test_image(size: u32) -> Image {
+        Image::new_fill(
+            Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn test_sprites_of_a_tileset_share_one_atlas_layout() {
+        let mut tileset_manager = TilesetManager::default();
+        tileset_manager.load_tileset_from_str("pawns", "tileset_name: pawns\ntile_size: 16\ntiles_per_row: 4\ntotal_tiles: 2\nsprites: []\n")
+            .expect("Tileset should parse");
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+
+        let first = tileset_manager.create_atlas_layout("pawns", &mut layouts).unwrap();
+        let second = tileset_manager.create_atlas_layout("pawns", &mut layouts).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(layouts.len(), 1);
+        assert!(tileset_manager.create_atlas_layout("missing", &mut layouts).is_none());
+    }
+
+    #[test]
+    fn test_tilesets_are_read_from_the_assets_folder_given() {
+        let assets = std::env::temp_dir().join(format!("elementals_{}_{}", "tileset_assets", std::process::id()));
+        std::fs::create_dir_all(assets.join("tilesets")).unwrap();
+        std::fs::write(assets.join("tilesets/pawns.yaml"), "tileset_name: pawns\ntile_size: 16\ntiles_per_row: 4\ntotal_tiles: 1\nsprites:\n  - name: wolf\n    index: 0\n    x: 0\n    y: 0\n    width: 16\n    height: 16\n").unwrap();
+
+        let mut tileset_manager = TilesetManager::new(assets.clone());
+        let loaded = tileset_manager.load_tileset("pawns");
+        let tileset_indices = load_tileset_indices(&assets.join("tilesets"));
+        std::fs::remove_dir_all(&assets).unwrap();
+
+        loaded.expect("Tileset should load from outside the working directory");
+        assert_eq!(tileset_manager.get_sprite_index("pawns", "wolf"), Some(0));
+        assert_eq!(tileset_indices.keys().collect::<Vec<_>>(), vec!["pawns"]);
+        assert!(load_tileset_indices(&assets.join("tilesets")).is_empty(), "A missing folder has no tilesets");
+    }
+
+    #[test]
+    fn test_atlas_layout_steps_over_tileset_padding() {
+        let mut tileset_manager = TilesetManager::default();
+        tileset_manager.load_tileset_from_str("pawns", "tileset_name: pawns\ntile_size: 16\ntiles_per_row: 2\ntotal_tiles: 3\npadding: 2\nsprites: []\n")
+            .expect("Tileset should parse");
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+
+        let handle = tileset_manager.create_atlas_layout("pawns", &mut layouts).unwrap();
+        let layout = layouts.get(&handle).unwrap();
+        assert_eq!(layout.textures[1], URect::new(18, 0, 34, 16));
+        assert_eq!(layout.textures[2], URect::new(0, 18, 16, 34));
+    }
+
+    #[test]
+    fn test_direct_sprites_are_packed_into_one_atlas() {
+        let items = ItemConfig::from_yaml("stone:\n  sprite: sprites/stone.png\n  weight: 1.0\nberry:\n  sprite: \"tileset::props::berry\"\n  weight: 0.5\n")
+            .expect("Items should parse");
+        let paths = direct_sprite_paths(&create_test_pawn_config(), &items);
+        assert_eq!(paths, vec!["sprites/stone.png".to_string(), "test.png".to_string()]);
+
+        let mut tileset_manager = TilesetManager::default();
+        let mut images = Assets::<Image>::default();
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+        let sprites: Vec<(String, Image)> = paths.into_iter().zip([create_test_image(8), create_test_image(16)]).collect();
+        tileset_manager.pack_sprites(&sprites, &mut images, &mut layouts).expect("Sprites should pack");
+
+        let stone = tileset_manager.packed_sprite("sprites/stone.png").unwrap();
+        let pawn = tileset_manager.packed_sprite("test.png").unwrap();
+        assert_eq!(stone.image, pawn.image, "Both should be drawn from the same texture");
+        assert_eq!(stone.texture_atlas.as_ref().unwrap().index, 0);
+        assert_eq!(pawn.texture_atlas.as_ref().unwrap().index, 1);
+        let layout = layouts.get(&pawn.texture_atlas.unwrap().layout).unwrap();
+        assert_eq!(layout.textures[1].size(), UVec2::splat(16));
+        assert!(tileset_manager.is_packed(&stone));
+        assert!(tileset_manager.packed_sprite("unpacked.png").is_none());
+    }
+}
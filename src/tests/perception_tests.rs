@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::ActiveBehaviour;
+    use crate::systems::needs::needs_behaviour_switching_system;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+    use crate::systems::perception::{Threatened, threat_perception_system};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const PERCEPTION_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    looking_for_food: eat
+    hunted: flee
+  eats:
+    pawns: []
+  perception:
+    detection_radius: 5
+    line_of_sight: true
+hare:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: flee
+  eats:
+    pawns: []
+  perception: {}
+mole:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 20
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    hunted: flee
+  eats:
+    pawns: []
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 10
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: [small, animal]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const STONE: usize = 2;
+    const WATER: usize = 3;
+
+    fn setup_perception_app(terrain_map: TerrainMap) -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(PERCEPTION_PAWNS_YAML).expect("Perception config should parse");
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(300)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, threat_perception_system);
+        app
+    }
+
+    fn spawn_at(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(30),
+            Endurance::new(10),
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    fn move_to(app: &mut App, entity: Entity, tile: (i32, i32)) {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().get_mut::<Transform>(entity).unwrap().translation = Vec3::new(x, y, 100.0);
+    }
+
+    fn state(app: &App, entity: Entity) -> String {
+        app.world().get::<CurrentBehavior>(entity).unwrap().state.clone()
+    }
+
+    #[test]
+    fn test_line_of_sight_blocked_by_solid_ground_only() {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        terrain_map.set_tile(5, 5, STONE);
+        terrain_map.set_tile(5, 10, WATER);
+        let ground_configs = create_test_ground_configs();
+        let from = terrain_map.tile_to_world_coords(2, 5);
+        let behind_stone = terrain_map.tile_to_world_coords(8, 5);
+        let across_water = terrain_map.tile_to_world_coords(8, 10);
+
+        assert!(!terrain_map.has_line_of_sight(from, behind_stone, &ground_configs));
+        assert!(terrain_map.has_line_of_sight(terrain_map.tile_to_world_coords(2, 10), across_water, &ground_configs),
+                "Pawns can see across water");
+        assert!(terrain_map.has_line_of_sight(from, terrain_map.tile_to_world_coords(2, 9), &ground_configs));
+    }
+
+    #[test]
+    fn test_prey_switches_to_hunted_and_back_when_safe() {
+        let mut app = setup_perception_app(TerrainMap::new(20, 20, TILE_SIZE));
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        let mole = spawn_at(&mut app, "mole", (5, 7));
+        let wolf = spawn_at(&mut app, "wolf", (15, 5));
+
+        app.update();
+        app.update();
+        assert_eq!(state(&app, deer), "idle", "Wolf is 10 tiles away, beyond the 5 tile detection radius");
+
+        move_to(&mut app, wolf, (9, 5));
+        app.update();
+        assert_eq!(state(&app, deer), "hunted");
+        assert_eq!(app.world().get::<Threatened>(deer).unwrap().predator, wolf);
+        assert_eq!(state(&app, mole), "idle", "Pawns without perception never notice predators");
+
+        move_to(&mut app, wolf, (12, 5));
+        app.update();
+        assert_eq!(state(&app, deer), "hunted", "A spotted predator stays a threat a little beyond the detection radius");
+
+        move_to(&mut app, wolf, (13, 5));
+        app.update();
+        assert_eq!(state(&app, deer), "idle");
+        assert!(app.world().get::<Threatened>(deer).is_none());
+    }
+
+    #[test]
+    fn test_predators_behind_walls_go_unseen() {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for y in 0..20 {
+            terrain_map.set_tile(7, y, STONE);
+        }
+        let mut app = setup_perception_app(terrain_map);
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        let hare = spawn_at(&mut app, "hare", (5, 6));
+        spawn_at(&mut app, "wolf", (9, 5));
+
+        app.update();
+        app.update();
+        assert_eq!(state(&app, deer), "idle", "Deer need line of sight");
+        assert_eq!(state(&app, hare), "hunted", "Hares sense predators through walls, with the default radius");
+    }
+
+    #[test]
+    fn test_dead_predators_are_no_threat() {
+        let mut app = setup_perception_app(TerrainMap::new(20, 20, TILE_SIZE));
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        let wolf = spawn_at(&mut app, "wolf", (7, 5));
+
+        app.update();
+        app.update();
+        assert_eq!(state(&app, deer), "hunted");
+
+        app.world_mut().get_mut::<Health>(wolf).unwrap().current = 0.0;
+        app.update();
+        assert_eq!(state(&app, deer), "idle");
+    }
+
+    #[test]
+    fn test_hunted_state_outranks_needs() {
+        let mut app = setup_perception_app(TerrainMap::new(20, 20, TILE_SIZE));
+        app.add_systems(Update, needs_behaviour_switching_system.after(threat_perception_system));
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 1.0;
+        spawn_at(&mut app, "wolf", (7, 5));
+
+        app.update();
+        app.update();
+        app.update();
+        assert_eq!(state(&app, deer), "hunted", "Hungry prey still runs first");
+    }
+
+    #[test]
+    fn test_behaviour_tree_pawns_are_only_flagged() {
+        let mut app = setup_perception_app(TerrainMap::new(20, 20, TILE_SIZE));
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        app.world_mut().entity_mut(deer).insert(ActiveBehaviour { action: BehaviourConfig::Simple(BehaviourType::Eat) });
+        spawn_at(&mut app, "wolf", (7, 5));
+
+        app.update();
+        app.update();
+        assert!(app.world().get::<Threatened>(deer).is_some());
+        assert_eq!(state(&app, deer), "idle", "Trees react through their threatened condition instead");
+    }
+}
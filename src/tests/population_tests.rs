@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::systems::pawn_config::{PawnConfig, PawnType};
+    use crate::systems::population::{PopulationManager, due_arrivals, pick_edge_tile};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::create_test_ground_configs;
+
+    const PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 5
+  eats:
+    pawns: []
+  population:
+    min: 2
+    max: 4
+    interval: 5.0
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  strength: 5
+  defence: 5
+  attack_speed: 1.0
+  reach: 1
+  size: 1.0
+  spawn_count: 1
+  eats:
+    pawns: [small]
+"#;
+
+    fn counts(rabbits: u32, wolves: u32) -> HashMap<PawnType, u32> {
+        HashMap::from([("rabbit".to_string(), rabbits), ("wolf".to_string(), wolves)])
+    }
+
+    #[test]
+    fn test_population_defaults() {
+        let pawn_config: PawnConfig = serde_yaml::from_str(&PAWNS_YAML.replace("    max: 4\n    interval: 5.0\n", "")).unwrap();
+        let population = pawn_config.get_pawn_definition("rabbit").unwrap().population.clone().unwrap();
+        assert_eq!(population.interval, 10.0);
+        assert_eq!(population.target(), 2, "Topped up to min without a max");
+        assert!(pawn_config.get_pawn_definition("wolf").unwrap().population.is_none());
+    }
+
+    #[test]
+    fn test_dwindling_species_are_topped_up_to_max() {
+        let pawn_config: PawnConfig = serde_yaml::from_str(PAWNS_YAML).unwrap();
+        let mut manager = PopulationManager::default();
+
+        assert!(due_arrivals(&mut manager, &counts(2, 0), &pawn_config, 0.0).is_empty(), "At min, and wolves can die out");
+        assert!(due_arrivals(&mut manager, &counts(1, 0), &pawn_config, 1.0).is_empty(), "The first arrival waits an interval");
+        assert!(due_arrivals(&mut manager, &counts(1, 0), &pawn_config, 5.0).is_empty());
+        assert_eq!(due_arrivals(&mut manager, &counts(1, 0), &pawn_config, 6.0), vec!["rabbit"]);
+        assert!(due_arrivals(&mut manager, &counts(2, 0), &pawn_config, 8.0).is_empty());
+        assert_eq!(due_arrivals(&mut manager, &counts(2, 0), &pawn_config, 11.0), vec!["rabbit"], "Back at min, but still short of max");
+        assert_eq!(due_arrivals(&mut manager, &counts(3, 0), &pawn_config, 16.0), vec!["rabbit"]);
+        assert!(due_arrivals(&mut manager, &counts(4, 0), &pawn_config, 21.0).is_empty());
+        assert!(manager.arrivals.is_empty(), "Done once max is reached");
+        assert!(due_arrivals(&mut manager, &counts(3, 0), &pawn_config, 30.0).is_empty(), "Not refilled until it drops below min again");
+    }
+
+    #[test]
+    fn test_edge_tiles_are_passable_and_on_the_edge() {
+        let ground_configs = create_test_ground_configs();
+        let water = ground_configs.terrain_mapping["water"];
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut terrain_map = TerrainMap::new(10, 10, 16.0);
+        for x in 0..10 {
+            for y in 0..10 {
+                terrain_map.set_tile(x, y, if x == 9 && y > 5 { grass } else { water });
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let (x, y) = pick_edge_tile(&terrain_map, &ground_configs, &mut rng).expect("There's grass on the edge");
+            assert!(x == 9 && y > 5, "({}, {}) isn't the grass on the edge", x, y);
+        }
+
+        terrain_map.set_tile(9, 6, water);
+        terrain_map.set_tile(9, 7, water);
+        terrain_map.set_tile(9, 8, water);
+        terrain_map.set_tile(9, 9, water);
+        terrain_map.set_tile(5, 5, grass);
+        assert_eq!(pick_edge_tile(&terrain_map, &ground_configs, &mut rng), None, "Grass in the middle doesn't count");
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::render_stats::{sprite_batches, describe_render_stats};
+
+    #[test]
+    fn test_sprites_sharing_an_image_batch_together() {
+        let mut images = Assets::<Image>::default();
+        let pawns = images.add(Image::default()).id();
+        let props = images.add(Image::default()).id();
+
+        assert_eq!(sprite_batches(Vec::new()), 0);
+        assert_eq!(sprite_batches(vec![(100.0, pawns), (101.0, pawns), (102.0, pawns)]), 1);
+        // Sorted by depth first, so a prop between two pawns splits them
+        assert_eq!(sprite_batches(vec![(102.0, pawns), (100.0, pawns), (101.0, props)]), 3);
+        assert_eq!(sprite_batches(vec![(102.0, pawns), (100.0, props), (101.0, props)]), 2);
+    }
+
+    #[test]
+    fn test_render_stats_text() {
+        let mut images = Assets::<Image>::default();
+        let pawns = images.add(Image::default()).id();
+        let icons = images.add(Image::default()).id();
+
+        let text = describe_render_stats(&[(100.0, pawns), (101.0, pawns), (200.0, icons)]);
+        assert_eq!(text, "Rendering\nSprites drawn: 3\nSprite images: 2\nSprite draw calls: 2");
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::config_files::ConfigPaths;
+    use crate::systems::replay::{PlayerCommand, PlayerCommands, Replay, ReplayMode, TimedCommand, replay_path, take_player_commands};
+    use crate::systems::save::WorldMetadata;
+    use crate::systems::sim_tick::SimTick;
+    use crate::tests::setup_test_app;
+
+    fn create_test_replay() -> Replay {
+        let metadata = WorldMetadata::new("Replayed", 42, &GameConfig::default(), &ConfigPaths { dirs: Vec::new(), embedded: false, mods: Vec::new() });
+        let mut replay = Replay::new(&metadata);
+        replay.commands = vec![
+            TimedCommand { tick: 2, command: PlayerCommand::SelectAll },
+            TimedCommand { tick: 2, command: PlayerCommand::Steer { direction: (0.0, 1.0) } },
+            TimedCommand { tick: 5, command: PlayerCommand::Order { position: (48.0, -16.0) } },
+        ];
+        replay.end_tick = 6;
+        replay
+    }
+
+    fn setup_replay_app(mode: ReplayMode) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(SimTick::default());
+        app.insert_resource(PlayerCommands::default());
+        app.insert_resource(mode);
+        app.add_systems(Update, take_player_commands);
+        app
+    }
+
+    /// Give `live` as if from the keyboard, then run the tick after the current one
+    fn run_tick(app: &mut App, live: Vec<PlayerCommand>) -> Vec<PlayerCommand> {
+        app.world_mut().resource_mut::<SimTick>().tick += 1;
+        app.world_mut().resource_mut::<PlayerCommands>().queued.extend(live);
+        app.update();
+        app.world().resource::<PlayerCommands>().this_tick.clone()
+    }
+
+    #[test]
+    fn test_replays_round_trip_through_a_file() {
+        let replay = create_test_replay();
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", "replay", std::process::id()));
+        let path = replay_path(&dir, 1234);
+        assert!(path.ends_with("replay_1234.yaml"));
+
+        replay.save_to_file(&path).expect("Replay should save");
+        let loaded = Replay::load_from_file(&path).expect("Replay should load");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.metadata.seed, 42);
+        assert_eq!(loaded.end_tick, 6);
+        assert_eq!(loaded.commands, replay.commands);
+    }
+
+    #[test]
+    fn test_commands_due_picks_up_where_it_left_off() {
+        let replay = create_test_replay();
+        assert_eq!(replay.commands_due(0, 1), (vec![], 0));
+        let (due, next) = replay.commands_due(0, 2);
+        assert_eq!(due, vec![PlayerCommand::SelectAll, PlayerCommand::Steer { direction: (0.0, 1.0) }]);
+        assert_eq!(next, 2);
+        assert_eq!(replay.commands_due(next, 4), (vec![], 2));
+        assert_eq!(replay.commands_due(next, 5).1, 3);
+        assert_eq!(replay.commands_due(3, 9), (vec![], 3), "Nothing left once they've all been given");
+    }
+
+    #[test]
+    fn test_recording_stamps_commands_with_their_tick() {
+        let replay = Replay::new(&create_test_replay().metadata);
+        let mut app = setup_replay_app(ReplayMode::Recording(replay));
+
+        assert_eq!(run_tick(&mut app, vec![PlayerCommand::Hold]), vec![PlayerCommand::Hold], "Recorded commands are still carried out");
+        assert!(run_tick(&mut app, vec![]).is_empty(), "Each command is carried out once");
+        run_tick(&mut app, vec![PlayerCommand::Feed, PlayerCommand::ClearSelection]);
+
+        let ReplayMode::Recording(replay) = app.world().resource::<ReplayMode>() else {
+            panic!("Should still be recording");
+        };
+        assert_eq!(replay.commands, vec![
+            TimedCommand { tick: 1, command: PlayerCommand::Hold },
+            TimedCommand { tick: 3, command: PlayerCommand::Feed },
+            TimedCommand { tick: 3, command: PlayerCommand::ClearSelection },
+        ]);
+    }
+
+    #[test]
+    fn test_playback_gives_the_recorded_commands_instead_of_live_ones() {
+        let mut app = setup_replay_app(ReplayMode::Playing { replay: create_test_replay(), next: 0 });
+
+        assert!(run_tick(&mut app, vec![PlayerCommand::Feed]).is_empty(), "Live commands are ignored while a replay plays");
+        assert_eq!(run_tick(&mut app, vec![]).len(), 2);
+        for _ in 3..5 {
+            assert!(run_tick(&mut app, vec![]).is_empty());
+        }
+        assert_eq!(run_tick(&mut app, vec![]), vec![PlayerCommand::Order { position: (48.0, -16.0) }]);
+        assert!(matches!(app.world().resource::<ReplayMode>(), ReplayMode::Playing { .. }));
+
+        run_tick(&mut app, vec![]);
+        assert!(matches!(app.world().resource::<ReplayMode>(), ReplayMode::Off), "The replay ends at its end tick");
+        assert_eq!(run_tick(&mut app, vec![PlayerCommand::Feed]), vec![PlayerCommand::Feed], "Then the player takes over");
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use std::collections::BTreeMap;
+    use crate::resources::GameConfig;
+    use crate::systems::bestiary::{BestiaryProgress, DiscoveryLevel};
+    use crate::systems::camera_bookmarks::{CameraBookmark, CameraBookmarks};
+    use crate::systems::config_files::ConfigPaths;
+    use crate::systems::den::{Den, place_dens};
+    use crate::systems::fog_of_war::{Exploration, TileBits};
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::pawn::{Pawn, TilesetManager};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::replay::ReplayMode;
+    use crate::systems::save::{
+        WorldMetadata, WorldOptions, SaveFile, Thumbnail, THUMBNAIL_SIZE, LoadRequest, WorldLoaded,
+        save_path, list_saves, fnv1a_hash, quick_load, clear_world_for_load,
+    };
+    use crate::systems::scent::ScentMap;
+    use crate::systems::sim_rng::SimRng;
+    use crate::systems::sim_tick::SimTick;
+    use crate::systems::spawn::{SpawnConfig, spawn_all_pawns};
+    use crate::systems::world_gen::{TerrainMap, TerrainChanges};
+    use crate::tests::{create_test_terrain_map, create_test_ground_configs, setup_test_app};
+
+    fn create_test_metadata(name: &str, created_at: u64) -> WorldMetadata {
+        let mut config_hashes = BTreeMap::new();
+        config_hashes.insert("pawns.yaml".to_string(), 1);
+        config_hashes.insert("grounds.yaml".to_string(), 2);
+
+        WorldMetadata {
+            name: name.to_string(),
+            seed: 42,
+            created_at,
+            playtime_secs: 12.5,
+            sim_tick: 900,
+            options: WorldOptions {
+                map_width: 10,
+                map_height: 10,
+                tile_size: 32.0,
+            },
+            mods: vec!["better_wolves".to_string()],
+            config_hashes,
+        }
+    }
+
+    fn create_test_save_dir(test_name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("elementals_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_save_file_round_trip() {
+        let dir = create_test_save_dir("round_trip");
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let metadata = create_test_metadata("Round Trip", 100);
+        let mut bestiary = BestiaryProgress::default();
+        bestiary.record_kill("wolf");
+
+        let path = save_path(&dir, "world");
+        let mut save = SaveFile::new(&metadata, &terrain_map, &bestiary);
+        save.camera_bookmarks[2] = Some(CameraBookmark { x: 64.0, y: -32.0, zoom: 1.5 });
+        save.explored = TileBits::new(10, 10);
+        save.explored.set(3, 4);
+        save.save_to_file(&path).expect("Save should succeed");
+
+        let loaded = SaveFile::load_from_file(&path).expect("Load should succeed");
+        assert_eq!(loaded.metadata.name, "Round Trip");
+        assert_eq!(loaded.metadata.seed, 42);
+        assert_eq!(loaded.metadata.sim_tick, 900);
+        assert_eq!(loaded.metadata.options, metadata.options);
+        assert_eq!(loaded.metadata.config_hashes, metadata.config_hashes);
+        assert_eq!(loaded.tiles, terrain_map.tiles);
+        assert_eq!(loaded.bestiary.level("wolf"), DiscoveryLevel::Killed);
+        assert_eq!(loaded.bestiary.level("rabbit"), DiscoveryLevel::Unknown);
+        assert_eq!(loaded.camera_bookmarks[2], Some(CameraBookmark { x: 64.0, y: -32.0, zoom: 1.5 }));
+        assert_eq!(loaded.camera_bookmarks[0], None);
+        assert!(loaded.explored.get(3, 4) && !loaded.explored.get(4, 3));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_list_saves_newest_first() {
+        let dir = create_test_save_dir("list_saves");
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+
+        SaveFile::new(&create_test_metadata("Old", 100), &terrain_map, &BestiaryProgress::default())
+            .save_to_file(&save_path(&dir, "old")).unwrap();
+        SaveFile::new(&create_test_metadata("New", 200), &terrain_map, &BestiaryProgress::default())
+            .save_to_file(&save_path(&dir, "new")).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a save").unwrap();
+
+        let saves = list_saves(&dir);
+        assert_eq!(saves.len(), 2, "Only yaml save files should be listed");
+        assert_eq!(saves[0].1.name, "New");
+        assert_eq!(saves[1].1.name, "Old");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_thumbnail_is_listed_with_its_save() {
+        let dir = create_test_save_dir("thumbnail");
+        let terrain_map = create_test_terrain_map(10, 10, 32.0);
+        let mut save = SaveFile::new(&create_test_metadata("Pictured", 100), &terrain_map, &BestiaryProgress::default());
+        save.thumbnail = Thumbnail::from_minimap(&[10, 20, 30, 255, 40, 50, 60, 255], 2, 1);
+        save.save_to_file(&save_path(&dir, "pictured")).unwrap();
+
+        let saves = list_saves(&dir);
+        assert_eq!(saves[0].2, Thumbnail { width: 2, height: 1, pixels: vec![10, 20, 30, 40, 50, 60] });
+        assert!(saves[0].2.to_image().is_some());
+        assert!(Thumbnail::default().to_image().is_none(), "Saves from before thumbnails have none");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_thumbnail_shrinks_large_maps_keeping_their_shape() {
+        let (width, height) = (THUMBNAIL_SIZE * 4, THUMBNAIL_SIZE * 2);
+        let mut rgba = vec![0; (width * height * 4) as usize];
+        rgba[0] = 200; // Top-left tile
+        let thumbnail = Thumbnail::from_minimap(&rgba, width, height);
+        assert_eq!((thumbnail.width, thumbnail.height), (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2));
+        assert_eq!(thumbnail.pixels.len(), (THUMBNAIL_SIZE * THUMBNAIL_SIZE / 2 * 3) as usize);
+        assert_eq!(thumbnail.pixels[0], 200);
+    }
+
+    #[test]
+    fn test_saves_of_another_size_are_rejected() {
+        let metadata = create_test_metadata("Sized", 0);
+        assert_eq!(metadata.map_size_mismatch(10, 10), None);
+        assert_eq!(metadata.map_size_mismatch(20, 10).as_deref(), Some("Saved map is 10x10 but the current map is 20x10"));
+    }
+
+    #[test]
+    fn test_list_saves_missing_directory() {
+        let dir = create_test_save_dir("missing_dir");
+        assert!(list_saves(&dir).is_empty());
+    }
+
+    #[test]
+    fn test_matching_configs_produce_no_warnings() {
+        let metadata = create_test_metadata("Match", 0);
+        let warnings = metadata.compatibility_warnings(&metadata.mods, &metadata.config_hashes);
+        assert!(warnings.is_empty(), "Unexpected warnings: {:?}", warnings);
+    }
+
+    #[test]
+    fn test_changed_configs_and_mods_are_reported() {
+        let metadata = create_test_metadata("Mismatch", 0);
+
+        let mut current_hashes = BTreeMap::new();
+        current_hashes.insert("pawns.yaml".to_string(), 99); // changed
+        current_hashes.insert("props.yaml".to_string(), 3); // new, grounds.yaml missing
+        let current_mods = vec!["faster_rabbits".to_string()];
+
+        let warnings = metadata.compatibility_warnings(&current_mods, &current_hashes);
+        assert_eq!(warnings.len(), 5, "Unexpected warnings: {:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("pawns.yaml has changed")));
+        assert!(warnings.iter().any(|w| w.contains("grounds.yaml is missing")));
+        assert!(warnings.iter().any(|w| w.contains("props.yaml was not present")));
+        assert!(warnings.iter().any(|w| w.contains("better_wolves")));
+        assert!(warnings.iter().any(|w| w.contains("faster_rabbits")));
+    }
+
+    #[test]
+    fn test_fnv1a_hash_is_stable() {
+        // Reference values for FNV-1a 64-bit
+        assert_eq!(fnv1a_hash(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(fnv1a_hash(b"water"), fnv1a_hash(b"stone"));
+    }
+
+    const LOAD_PAWNS_YAML: &str = r#"
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 2
+  strength: 8
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  den:
+    count: 1
+    grounds: [grass]
+  behaviours:
+    idle: null
+  eats:
+    pawns: []
+"#;
+
+    fn setup_load_app(terrain_map: TerrainMap, seed: u32) -> App {
+        let mut app = setup_test_app();
+        app.add_plugins(AssetPlugin::default());
+        app.init_asset::<Image>();
+        app.init_asset::<TextureAtlasLayout>();
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig::default());
+        app.insert_resource(serde_yaml::from_str::<PawnConfig>(LOAD_PAWNS_YAML).expect("Pawns should parse"));
+        app.insert_resource(SpawnConfig::from_yaml("rabbit_warren:\n  pawn: rabbit\n  count: 4\n  grounds: [grass]\n").unwrap());
+        app.insert_resource(TilesetManager::default());
+        app.insert_resource(ConfigPaths { dirs: Vec::new(), embedded: false, mods: Vec::new() });
+        app.insert_resource(create_test_metadata("Current", 0));
+        app.insert_resource(SimRng::from_seed(seed));
+        app.insert_resource(SimTick::default());
+        app.insert_resource(TerrainChanges::default());
+        app.insert_resource(BestiaryProgress::default());
+        app.insert_resource(CameraBookmarks::default());
+        app.insert_resource(Exploration::new(10, 10));
+        app.insert_resource(ScentMap::default());
+        app.insert_resource(ReplayMode::default());
+        app.insert_resource(InputMap::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.add_event::<LoadRequest>();
+        app.add_event::<WorldLoaded>();
+        app.add_systems(Update, (
+            quick_load,
+            (clear_world_for_load, place_dens, spawn_all_pawns).chain().after(quick_load).run_if(on_event::<WorldLoaded>),
+        ));
+        app
+    }
+
+    fn pawn_tiles(app: &mut App) -> Vec<(i32, i32)> {
+        let terrain_map = app.world().resource::<TerrainMap>().clone();
+        let mut query = app.world_mut().query_filtered::<&Transform, With<Pawn>>();
+        let mut tiles: Vec<(i32, i32)> = query.iter(app.world())
+            .filter_map(|transform| terrain_map.world_to_tile_coords(transform.translation.x, transform.translation.y))
+            .collect();
+        tiles.sort();
+        tiles
+    }
+
+    #[test]
+    fn test_loading_over_another_world_restocks_it_from_the_save() {
+        let dir = create_test_save_dir("load_over");
+        let ground_configs = create_test_ground_configs();
+        let (grass, stone) = (ground_configs.terrain_mapping["grass"], ground_configs.terrain_mapping["stone"]);
+
+        // The saved world is walled off with stone down its left half
+        let mut saved_map = create_test_terrain_map(10, 10, 32.0);
+        for x in 0..5 {
+            for y in 0..10 {
+                saved_map.set_tile(x, y, stone);
+            }
+        }
+        let mut metadata = create_test_metadata("Saved", 100);
+        metadata.seed = 5;
+        let path = save_path(&dir, "saved");
+        SaveFile::new(&metadata, &saved_map, &BestiaryProgress::default()).save_to_file(&path).unwrap();
+
+        let mut current_map = create_test_terrain_map(10, 10, 32.0);
+        for x in 0..10 {
+            for y in 0..10 {
+                current_map.set_tile(x, y, grass);
+            }
+        }
+        let mut app = setup_load_app(current_map, 1);
+        let (old_x, old_y) = app.world().resource::<TerrainMap>().tile_to_world_coords(1, 1);
+        let old_pawn = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Transform::from_xyz(old_x, old_y, 100.0))).id();
+        let old_den = app.world_mut().spawn((Den { species: "rabbit".to_string() }, Transform::from_xyz(old_x, old_y, 1.0))).id();
+        app.world_mut().resource_mut::<ScentMap>().deposit((1, 1), old_pawn);
+
+        app.world_mut().send_event(LoadRequest(path.clone()));
+        app.update();
+
+        assert!(app.world().get_entity(old_pawn).is_err(), "The old world's pawns are gone");
+        assert!(app.world().get_entity(old_den).is_err(), "And its dens");
+        assert!(app.world().resource::<ScentMap>().tiles.is_empty(), "And the trails they left");
+        let loaded_map = app.world().resource::<TerrainMap>().clone();
+        assert_eq!(loaded_map.tiles, saved_map.tiles);
+        let tiles = pawn_tiles(&mut app);
+        assert_eq!(tiles.len(), 4, "The loaded world is stocked as a new one would be");
+        assert!(tiles.iter().all(|&(x, y)| loaded_map.tiles[x as usize][y as usize] == grass), "Nobody stands in the stone: {:?}", tiles);
+
+        // Loading the same save again plays out the same way, whatever world it's loaded over
+        let mut again = setup_load_app(saved_map.clone(), 2);
+        again.world_mut().send_event(LoadRequest(path));
+        again.update();
+        assert_eq!(pawn_tiles(&mut again), tiles);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
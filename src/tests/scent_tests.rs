@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::perception::Vision;
+    use crate::systems::scent::{ScentMap, SCENT_STRENGTH, scent_deposit_system, scent_decay_system};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const TRACKING_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    looking_for_food: hunt_solo
+  eats:
+    pawns: [small, animal]
+  vision:
+    range: 12
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const STONE: usize = 2;
+
+    fn create_tracking_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(TRACKING_PAWNS_YAML).expect("Tracking config should parse")
+    }
+
+    #[test]
+    fn test_scent_fades_and_goes_cold() {
+        let rabbit = Entity::from_raw(1);
+        let fox = Entity::from_raw(2);
+        let mut scent_map = ScentMap::default();
+        scent_map.deposit((3, 3), rabbit);
+        scent_map.deposit((3, 3), fox);
+        assert_eq!(scent_map.strength_at((3, 3), rabbit), SCENT_STRENGTH);
+
+        scent_map.decay(0.4);
+        scent_map.deposit((3, 3), fox);
+        assert!((scent_map.strength_at((3, 3), rabbit) - 0.6).abs() < 0.001);
+        assert_eq!(scent_map.strength_at((3, 3), fox), SCENT_STRENGTH, "Depositing again freshens the scent");
+        assert_eq!(scent_map.strength_at((4, 3), rabbit), 0.0);
+
+        scent_map.decay(0.6);
+        assert_eq!(scent_map.strength_at((3, 3), rabbit), 0.0);
+        assert_eq!(scent_map.tiles[&(3, 3)].len(), 1, "Cold scents are forgotten");
+        scent_map.decay(1.0);
+        assert!(scent_map.tiles.is_empty());
+    }
+
+    #[test]
+    fn test_trail_leads_to_freshest_scent() {
+        let rabbit = Entity::from_raw(1);
+        let mut scent_map = ScentMap::default();
+        // Rabbit ran east along row 5, leaving fresher scent the further it got
+        for x in 0..10 {
+            scent_map.decay(0.05);
+            scent_map.deposit((x, 5), rabbit);
+        }
+
+        assert_eq!(scent_map.follow_trail((0, 5), rabbit), Some((3, 5)), "Freshest scent within sniffing range");
+        assert_eq!(scent_map.follow_trail((3, 5), rabbit), Some((6, 5)));
+        assert_eq!(scent_map.follow_trail((1, 7), rabbit), Some((4, 5)), "Picks the trail up from off to the side");
+        assert_eq!(scent_map.follow_trail((9, 5), rabbit), None, "End of the trail");
+        assert_eq!(scent_map.follow_trail((0, 0), Entity::from_raw(2)), None, "No trail for other pawns");
+    }
+
+    #[test]
+    fn test_only_living_prey_leave_scent() {
+        let mut app = setup_test_app();
+        app.insert_resource(create_tracking_pawn_config());
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(ScentMap::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(200)));
+        app.add_systems(Update, (scent_decay_system, scent_deposit_system.after(scent_decay_system)));
+
+        let rabbit = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Health::new(25), Transform::from_xyz(0.0, 0.0, 100.0))).id();
+        let wolf = app.world_mut().spawn((Pawn::new("wolf".to_string()), Health::new(100), Transform::from_xyz(32.0, 0.0, 100.0))).id();
+        let dead_rabbit = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Health::new(25), Transform::from_xyz(-32.0, 0.0, 100.0))).id();
+        app.world_mut().get_mut::<Health>(dead_rabbit).unwrap().current = 0.0;
+
+        let tile = |x: f32| TerrainMap::new(20, 20, TILE_SIZE).world_to_tile_coords(x, 0.0).unwrap();
+
+        app.update();
+        let scent_map = app.world().resource::<ScentMap>();
+        assert_eq!(scent_map.strength_at(tile(0.0), rabbit), SCENT_STRENGTH);
+        assert_eq!(scent_map.strength_at(tile(32.0), wolf), 0.0, "Nothing eats wolves");
+        assert_eq!(scent_map.strength_at(tile(-32.0), dead_rabbit), 0.0);
+
+        app.world_mut().get_mut::<Transform>(rabbit).unwrap().translation.x = 16.0;
+        app.update();
+        app.update();
+        let scent_map = app.world().resource::<ScentMap>();
+        let old_scent = scent_map.strength_at(tile(0.0), rabbit);
+        assert!(old_scent > 0.0 && old_scent < SCENT_STRENGTH, "The old tile fades while the rabbit's new one stays fresh");
+        assert_eq!(scent_map.strength_at(tile(16.0), rabbit), SCENT_STRENGTH);
+    }
+
+    fn setup_tracking_app() -> (App, Entity, Entity) {
+        // Wolf at (5, 5) can't see past the stone ridge in column 10 to the rabbit at (14, 5)
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for y in 0..15 {
+            terrain_map.set_tile(10, y, STONE);
+        }
+        let (wolf_x, wolf_y) = terrain_map.tile_to_world_coords(5, 5);
+        let (rabbit_x, rabbit_y) = terrain_map.tile_to_world_coords(14, 5);
+        let config = create_tracking_pawn_config();
+        let vision = config.get_pawn_definition("wolf").unwrap().vision.clone().unwrap();
+
+        let mut app = setup_test_app();
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.insert_resource(ScentMap::default());
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, hunt_solo_ai_system);
+
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(25),
+            Transform::from_xyz(rabbit_x, rabbit_y, 100.0),
+        )).id();
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.target_entity = Some(rabbit);
+        let wolf = app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            Vision::new(&vision),
+            hunt_ai,
+            Transform::from_xyz(wolf_x, wolf_y, 100.0),
+        )).id();
+        (app, wolf, rabbit)
+    }
+
+    #[test]
+    fn test_hunter_follows_trail_when_prey_out_of_sight() {
+        let (mut app, wolf, rabbit) = setup_tracking_app();
+        // The rabbit fled north-east past the wolf and around the ridge
+        let mut scent_map = ScentMap::default();
+        for tile in [(4, 4), (5, 6), (6, 7), (7, 8)] {
+            scent_map.decay(0.05);
+            scent_map.deposit(tile, rabbit);
+        }
+        app.insert_resource(scent_map);
+
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(wolf).expect("Wolf should follow the trail");
+        let goal = app.world().resource::<TerrainMap>().world_to_tile_coords(request.goal.0, request.goal.1);
+        assert_eq!(goal, Some((7, 8)), "Heads up the trail, not straight for the hidden rabbit");
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, Some(rabbit));
+    }
+
+    #[test]
+    fn test_hunter_gives_up_on_a_cold_trail() {
+        let (mut app, wolf, _) = setup_tracking_app();
+
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(wolf).is_none());
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, None, "No scent, no idea where the prey went");
+    }
+}
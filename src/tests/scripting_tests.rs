@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::ai_tick::AiTickIntervals;
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::attack::Attacking;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig};
+    use crate::systems::scripting::{ScriptedAI, ScriptingPlugin};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const SCRIPT_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 1
+  reach: 2
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle:
+      script: "ai/ambusher.rhai"
+    hunted: flee
+  eats:
+    pawns: [small, animal]
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 100
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    /// A scripted wolf with a rabbit just over a tile away
+    fn setup_script_app(pawns_yaml: &str) -> (App, Entity, Entity) {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(pawns_yaml).expect("Script config should parse");
+        let ai_ticks = AiTickIntervals { script: 0.0, ..AiTickIntervals::default() };
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ai_ticks, ..GameConfig::default() });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_plugins(ScriptingPlugin::default());
+
+        let rabbit = app.world_mut().spawn((
+            Pawn::new("rabbit".to_string()),
+            Health::new(100),
+            Transform::from_xyz(100.0, 80.0, 100.0),
+        )).id();
+        let wolf = app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            Transform::from_xyz(80.0, 80.0, 100.0),
+        )).id();
+        (app, wolf, rabbit)
+    }
+
+    #[test]
+    fn test_script_behaviour_parses() {
+        let config: PawnConfig = serde_yaml::from_str(SCRIPT_PAWNS_YAML).expect("Script config should parse");
+        match config.get_behaviour_config("wolf", "idle") {
+            Some(BehaviourConfig::Script { script }) => assert_eq!(script, "ai/ambusher.rhai"),
+            other => panic!("Expected a script behaviour, got {:?}", other),
+        }
+        assert_eq!(config.get_behaviour_config("wolf", "idle").unwrap().name(), "script");
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    #[test]
+    fn test_scripts_idle_without_the_feature() {
+        let (mut app, wolf, rabbit) = setup_script_app(SCRIPT_PAWNS_YAML);
+        for _ in 0..5 {
+            app.update();
+        }
+
+        assert!(app.world().get::<ScriptedAI>(wolf).unwrap().failed, "The script is reported as failed");
+        assert!(app.world().get::<PathfindingRequest>(wolf).is_none());
+        assert!(app.world().get::<Attacking>(wolf).is_none());
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 100.0);
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_ambusher_pounces_on_close_prey() {
+        let (mut app, wolf, rabbit) = setup_script_app(SCRIPT_PAWNS_YAML);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert!(!app.world().get::<ScriptedAI>(wolf).unwrap().failed);
+        assert_eq!(app.world().get::<Health>(rabbit).unwrap().current, 75.0, "Strength 30 against defence 5");
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_runaway_scripts_are_stopped() {
+        let path = std::env::temp_dir().join("elementals_runaway_script.rhai");
+        std::fs::write(&path, "loop { }").unwrap();
+        let pawns_yaml = SCRIPT_PAWNS_YAML.replace("ai/ambusher.rhai", path.to_str().unwrap());
+        let (mut app, wolf, _) = setup_script_app(&pawns_yaml);
+        app.update();
+
+        assert!(app.world().get::<ScriptedAI>(wolf).unwrap().failed, "The operation limit ends the loop");
+    }
+}
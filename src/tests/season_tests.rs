@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use std::collections::BTreeMap;
+    use crate::resources::GameConfig;
+    use crate::systems::seasons::{Season, SeasonSettings, Seasons, update_season, repaint_for_season};
+    use crate::systems::world_clock::WorldClock;
+    use crate::systems::world_gen::{GroundConfigs, SpriteInfo, TerrainChanges, TerrainMap, TilesetIndex};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+
+    /// Test grounds with a grounds tileset to look their sprites up in
+    fn create_seasonal_ground_configs() -> GroundConfigs {
+        let mut ground_configs = create_test_ground_configs();
+        let sprites = ["dirt", "grass", "grass_autumn", "grass_winter"]
+            .iter()
+            .enumerate()
+            .map(|(index, name)| SpriteInfo { name: name.to_string(), index: index as u32, x: index as u32 * 16, y: 0, width: 16, height: 16 })
+            .collect();
+        ground_configs.tileset_indices.insert("grounds".to_string(), TilesetIndex {
+            tileset_name: "grounds".to_string(),
+            tile_size: 16,
+            tiles_per_row: 16,
+            total_tiles: 4,
+            padding: 0,
+            sprites,
+        });
+        ground_configs
+    }
+
+    /// 4x4 of grass with dirt down the left-hand column
+    fn setup_season_app(days_per_season: u64) -> App {
+        let mut app = setup_test_app();
+        let mut terrain_map = TerrainMap::new(4, 4, 16.0);
+        for x in 0..4 {
+            for y in 0..4 {
+                terrain_map.set_tile(x, y, if x == 0 { DIRT } else { GRASS });
+            }
+        }
+        let mut config = GameConfig::default();
+        config.seasons.days_per_season = days_per_season;
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_seasonal_ground_configs());
+        app.insert_resource(TerrainChanges::default());
+        app.insert_resource(WorldClock::default());
+        app.insert_resource(Seasons::default());
+        app.add_systems(Update, (update_season, repaint_for_season.after(update_season)));
+        app
+    }
+
+    #[test]
+    fn test_seasons_follow_the_calendar() {
+        assert_eq!(Season::for_day(0, 7), Season::Spring, "A new world starts in spring");
+        assert_eq!(Season::for_day(6, 7), Season::Spring);
+        assert_eq!(Season::for_day(7, 7), Season::Summer);
+        assert_eq!(Season::for_day(20, 7), Season::Autumn);
+        assert_eq!(Season::for_day(27, 7), Season::Winter);
+        assert_eq!(Season::for_day(28, 7), Season::Spring, "Back round to spring after a year");
+        assert_eq!(Season::for_day(3, 0), Season::Winter, "Zero-day seasons count as a day each");
+
+        let settings = SeasonSettings {
+            regrowth: BTreeMap::from([(Season::Winter, 0.25)]),
+            endurance_decay: BTreeMap::from([(Season::Winter, -1.0)]),
+            ..Default::default()
+        };
+        assert_eq!(settings.regrowth(Season::Winter), 0.25);
+        assert_eq!(settings.regrowth(Season::Summer), 1.0, "Seasons left out go at the usual pace");
+        assert_eq!(settings.endurance_decay(Season::Winter), 0.0, "Needs never refill on their own");
+    }
+
+    #[test]
+    fn test_seasonal_sprites_fall_back_to_the_usual_one() {
+        let ground_configs = create_seasonal_ground_configs();
+        assert!(ground_configs.is_seasonal(GRASS));
+        assert!(!ground_configs.is_seasonal(DIRT));
+        assert_eq!(ground_configs.resolve_seasonal_sprite_index(GRASS, None), Some(1));
+        assert_eq!(ground_configs.resolve_seasonal_sprite_index(GRASS, Some(Season::Summer)), Some(1));
+        assert_eq!(ground_configs.resolve_seasonal_sprite_index(GRASS, Some(Season::Autumn)), Some(2));
+        assert_eq!(ground_configs.resolve_seasonal_sprite_index(GRASS, Some(Season::Winter)), Some(3));
+        assert_eq!(ground_configs.resolve_seasonal_sprite_index(DIRT, Some(Season::Winter)), Some(0));
+    }
+
+    #[test]
+    fn test_turning_season_repaints_seasonal_grounds() {
+        let mut app = setup_season_app(2);
+        app.update();
+        let seasons = app.world().resource::<Seasons>().clone();
+        assert_eq!(seasons.current, Some(Season::Spring));
+        assert_eq!(seasons.regrowth, 1.5);
+        assert_eq!(seasons.endurance_decay, 1.0);
+        let mut repainted = app.world().resource::<TerrainChanges>().repainted_tiles.clone();
+        assert_eq!(repainted.len(), 12, "Every grass tile is redrawn for the first season, the dirt isn't");
+        assert!(app.world().resource::<TerrainChanges>().changed_tiles.is_empty(),
+                "Repainting isn't a change of ground, so pathfinding caches are left alone");
+
+        app.world_mut().resource_mut::<TerrainChanges>().clear();
+        app.update();
+        assert!(app.world().resource::<TerrainChanges>().repainted_tiles.is_empty(), "Nothing more to do until the season turns");
+
+        app.world_mut().resource_mut::<WorldClock>().day = 5;
+        app.update();
+        let seasons = app.world().resource::<Seasons>().clone();
+        assert_eq!(seasons.current, Some(Season::Autumn));
+        assert_eq!(seasons.regrowth, 0.75);
+        assert_eq!(seasons.endurance_decay, 1.1);
+        let terrain_changes = app.world().resource::<TerrainChanges>();
+        repainted.sort();
+        let mut autumn = terrain_changes.repainted_tiles.clone();
+        autumn.sort();
+        assert_eq!(autumn, repainted);
+    }
+}
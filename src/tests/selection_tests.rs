@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::pawn::{Pawn, PawnTarget, Size, Health, Endurance, CurrentBehavior};
+    use crate::systems::selection::{Selected, SelectionRing, SelectionPanel, pawn_at_position, pawns_in_box, update_selection_rings, update_selection_panel};
+    use crate::tests::setup_test_app;
+
+    fn setup_selection_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.add_systems(Update, (update_selection_rings, update_selection_panel));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, position: Vec2, size: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            Transform::from_translation(position.extend(100.0)),
+            Size { value: size },
+            Health::new(20),
+            Endurance::new(10),
+            CurrentBehavior { state: "idle".to_string() },
+        )).with_children(|parent| {
+            parent.spawn((Sprite::default(), Visibility::Hidden, SelectionRing));
+        }).id()
+    }
+
+    fn panel_text(app: &mut App) -> Option<String> {
+        let mut panels = app.world_mut().query_filtered::<&Text, With<SelectionPanel>>();
+        panels.iter(app.world()).next().map(|text| text.0.clone())
+    }
+
+    fn ring_count(app: &mut App) -> usize {
+        let mut rings = app.world_mut().query_filtered::<&Visibility, With<SelectionRing>>();
+        rings.iter(app.world()).filter(|visibility| **visibility != Visibility::Hidden).count()
+    }
+
+    #[test]
+    fn test_click_picks_the_nearest_pawn_under_the_cursor() {
+        let tile_size = GameConfig::default().tile_size;
+        let mut world = World::new();
+        let small = world.spawn((Transform::from_xyz(0.0, 0.0, 100.0), Size { value: 1.0 })).id();
+        let big = world.spawn((Transform::from_xyz(tile_size, 0.0, 100.0), Size { value: 3.0 })).id();
+        let mut query = world.query::<(Entity, &Transform, &Size)>();
+
+        let mut click = |position: Vec2| pawn_at_position(position, tile_size, query.iter(&world));
+        assert_eq!(click(Vec2::new(tile_size * 0.1, 0.0)), Some(small));
+        assert_eq!(click(Vec2::new(tile_size * 0.9, 0.0)), Some(big));
+        // Only the big pawn's body reaches this far
+        assert_eq!(click(Vec2::new(tile_size * 2.4, 0.0)), Some(big));
+        assert_eq!(click(Vec2::new(tile_size * 5.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_box_selects_pawns_inside_it_whichever_way_it_was_dragged() {
+        let mut world = World::new();
+        let inside = world.spawn(Transform::from_xyz(10.0, 10.0, 100.0)).id();
+        let also_inside = world.spawn(Transform::from_xyz(40.0, 30.0, 100.0)).id();
+        world.spawn(Transform::from_xyz(80.0, 10.0, 100.0));
+        let mut query = world.query::<(Entity, &Transform)>();
+
+        let mut boxed = pawns_in_box(Vec2::new(50.0, 0.0), Vec2::new(0.0, 40.0), query.iter(&world));
+        boxed.sort();
+        let mut expected = vec![inside, also_inside];
+        expected.sort();
+        assert_eq!(boxed, expected);
+    }
+
+    #[test]
+    fn test_selected_pawn_gets_a_ring_and_panel() {
+        let mut app = setup_selection_app();
+        let wolf = spawn_pawn(&mut app, "wolf", Vec2::new(32.0, 0.0), 2.0);
+        let mut target = PawnTarget::new(Vec3::new(96.0, 0.0, 100.0));
+        target.set_path(vec![(64.0, 0.0), (96.0, 0.0)]);
+        app.world_mut().entity_mut(wolf).insert((Selected, target));
+
+        app.update();
+
+        assert_eq!(ring_count(&mut app), 1);
+        let text = panel_text(&mut app).expect("Selecting a pawn should open the panel");
+        assert!(text.starts_with("wolf"), "Panel should name the pawn: {}", text);
+        assert!(text.contains("Health: 20/20"));
+        assert!(text.contains("Behaviour: idle"));
+        assert!(text.contains("-> (64, 0)") && text.contains("-> (96, 0)"), "Panel should list the path: {}", text);
+    }
+
+    #[test]
+    fn test_panel_follows_the_selected_pawn() {
+        let mut app = setup_selection_app();
+        let wolf = spawn_pawn(&mut app, "wolf", Vec2::ZERO, 1.0);
+        app.world_mut().entity_mut(wolf).insert(Selected);
+        app.update();
+
+        app.world_mut().get_mut::<Health>(wolf).unwrap().current = 5.0;
+        app.world_mut().get_mut::<CurrentBehavior>(wolf).unwrap().state = "flee".to_string();
+        app.update();
+
+        let text = panel_text(&mut app).unwrap();
+        assert!(text.contains("Health: 5/20") && text.contains("Behaviour: flee"), "Panel should update: {}", text);
+    }
+
+    #[test]
+    fn test_panel_lists_a_multiple_selection() {
+        let mut app = setup_selection_app();
+        for (pawn_type, x) in [("wolf", 0.0), ("player", 40.0), ("wolf", 80.0)] {
+            let pawn = spawn_pawn(&mut app, pawn_type, Vec2::new(x, 0.0), 1.0);
+            app.world_mut().entity_mut(pawn).insert(Selected);
+        }
+        app.update();
+
+        assert_eq!(ring_count(&mut app), 3);
+        assert_eq!(panel_text(&mut app).unwrap(), "3 pawns selected\nplayer, wolf, wolf");
+    }
+
+    #[test]
+    fn test_deselecting_hides_ring_and_removes_panel() {
+        let mut app = setup_selection_app();
+        let rabbit = spawn_pawn(&mut app, "rabbit", Vec2::ZERO, 1.0);
+        app.world_mut().entity_mut(rabbit).insert(Selected);
+        app.update();
+        assert_eq!(ring_count(&mut app), 1);
+
+        app.world_mut().entity_mut(rabbit).remove::<Selected>();
+        app.update();
+
+        assert_eq!(ring_count(&mut app), 0);
+        assert!(panel_text(&mut app).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::resources::{GameConfig, set_yaml_value};
+    use crate::systems::menu::{MenuButton, apply_setting_button};
+
+    const SETTINGS: &str = "# World
+world:
+  map_width: 64
+  map_height: 64
+  tile_size: 32.0
+
+camera:
+  movement_speed: 200.0 # Pixels a second
+  zoom_min: 0.1
+  zoom_max: 10.0
+  mouse_sensitivity: 1.0
+
+game:
+  window_title: \"Test\"
+  target_fps: 60
+  show_fps: true
+";
+
+    #[test]
+    fn test_changing_a_value_keeps_its_comment() {
+        let edited = set_yaml_value(SETTINGS, "camera", "movement_speed", "350.0");
+        assert!(edited.contains("  movement_speed: 350.0 # Pixels a second\n"));
+        assert!(edited.starts_with("# World\nworld:\n"), "The rest of the file is left as it was");
+        assert_eq!(edited.lines().count(), SETTINGS.lines().count());
+    }
+
+    #[test]
+    fn test_missing_keys_go_at_the_end_of_their_section() {
+        let edited = set_yaml_value(SETTINGS, "camera", "bounds_padding", "4.0");
+        assert!(edited.contains("  mouse_sensitivity: 1.0\n  bounds_padding: 4.0\n\ngame:"));
+
+        let edited = set_yaml_value(SETTINGS, "game", "volume", "0.5");
+        assert!(edited.ends_with("  show_fps: true\n  volume: 0.5\n"));
+    }
+
+    #[test]
+    fn test_missing_sections_are_added() {
+        let edited = set_yaml_value(SETTINGS, "audio", "volume", "0.5");
+        assert!(edited.ends_with("  show_fps: true\n\naudio:\n  volume: 0.5\n"));
+        assert_eq!(set_yaml_value("", "game", "show_fps", "false"), "game:\n  show_fps: false\n");
+    }
+
+    #[test]
+    fn test_saved_settings_load_back() {
+        let path = std::env::temp_dir().join(format!("elementals_settings_{}.yaml", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, SETTINGS).unwrap();
+
+        let mut config = GameConfig::from_yaml(&std::fs::read_to_string(path).unwrap()).unwrap();
+        for button in [MenuButton::FasterCamera, MenuButton::MoreZoomMin, MenuButton::QuieterVolume, MenuButton::QuieterVolume, MenuButton::ToggleFps] {
+            assert!(apply_setting_button(&mut config, &button));
+        }
+        config.save_settings(path).unwrap();
+        let loaded = GameConfig::from_yaml(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.camera_speed, 250.0);
+        assert_eq!(loaded.zoom_min, 0.15);
+        assert_eq!(loaded.volume, 0.8, "Written rounded, not as 0.79999995");
+        assert!(!loaded.show_fps);
+        assert!(content.contains("  movement_speed: 250.0 # Pixels a second\n"));
+        assert_eq!(loaded.window_title, "Test", "Settings the menu doesn't change are kept");
+    }
+
+    #[test]
+    fn test_settings_stay_within_limits() {
+        let mut config = GameConfig { zoom_min: 1.0, zoom_max: 1.0, volume: 1.0, ..GameConfig::default() };
+        apply_setting_button(&mut config, &MenuButton::MoreZoomMin);
+        apply_setting_button(&mut config, &MenuButton::LessZoomMax);
+        apply_setting_button(&mut config, &MenuButton::LouderVolume);
+        assert_eq!((config.zoom_min, config.zoom_max), (1.0, 1.0), "The closest zoom can't pass the furthest");
+        assert_eq!(config.volume, 1.0);
+        assert!(!apply_setting_button(&mut config, &MenuButton::Back), "Only settings buttons are saved");
+    }
+}
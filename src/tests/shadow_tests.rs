@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::pawn::{Pawn, Size, Health, pawn_death_system};
+    use crate::systems::shadows::{PawnShadow, shadow_size, update_pawn_shadows};
+    use crate::tests::setup_test_app;
+
+    fn setup_shadow_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig::default());
+        app.add_systems(Update, (update_pawn_shadows, pawn_death_system));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, size: f32) -> (Entity, Entity) {
+        let tile_size = GameConfig::default().tile_size;
+        let mut shadow = Entity::PLACEHOLDER;
+        let pawn = app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            Transform::from_xyz(0.0, 0.0, 100.0),
+            Size { value: size },
+            Health::new(20),
+        )).with_children(|parent| {
+            let sprite = Sprite { custom_size: Some(shadow_size(size, tile_size)), ..default() };
+            shadow = parent.spawn((sprite, PawnShadow)).id();
+        }).id();
+        (pawn, shadow)
+    }
+
+    fn shadow_width(app: &App, shadow: Entity) -> f32 {
+        app.world().get::<Sprite>(shadow).unwrap().custom_size.unwrap().x
+    }
+
+    #[test]
+    fn test_shadows_grow_with_their_pawns() {
+        let mut app = setup_shadow_app();
+        let (pup, pup_shadow) = spawn_pawn(&mut app, 0.5);
+        let (_, wolf_shadow) = spawn_pawn(&mut app, 1.0);
+        app.update();
+        assert!(shadow_width(&app, pup_shadow) < shadow_width(&app, wolf_shadow));
+        let size = shadow_size(1.0, 16.0);
+        assert_eq!(size.x, size.y * 2.0, "Flattened onto the ground");
+
+        app.world_mut().get_mut::<Size>(pup).unwrap().value = 1.0;
+        app.update();
+        assert_eq!(shadow_width(&app, pup_shadow), shadow_width(&app, wolf_shadow));
+    }
+
+    #[test]
+    fn test_shadows_go_with_their_pawns() {
+        let mut app = setup_shadow_app();
+        let (wolf, shadow) = spawn_pawn(&mut app, 1.0);
+        app.update();
+        assert_eq!(app.world().get::<Parent>(shadow).map(|parent| parent.get()), Some(wolf));
+
+        app.world_mut().get_mut::<Health>(wolf).unwrap().current = 0.0;
+        app.update();
+        assert!(app.world().get_entity(wolf).is_err());
+        assert!(app.world().get_entity(shadow).is_err(), "No shadow left behind where the pawn died");
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use crate::systems::den::pick_den_tiles;
+    use crate::systems::pawn_config::DenConfig;
+    use crate::systems::sim_rng::SimRng;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::create_test_ground_configs;
+
+    fn draws(rng: &mut SimRng) -> Vec<u32> {
+        (0..8).map(|_| rng.gen_range(0..1000)).collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_draws() {
+        assert_eq!(draws(&mut SimRng::from_seed(42)), draws(&mut SimRng::from_seed(42)));
+        assert_ne!(draws(&mut SimRng::from_seed(42)), draws(&mut SimRng::from_seed(43)));
+    }
+
+    #[test]
+    fn test_each_tick_of_a_loaded_world_has_its_own_stream() {
+        assert_eq!(draws(&mut SimRng::at_tick(7, 300)), draws(&mut SimRng::at_tick(7, 300)), "Loading the same save plays out the same way");
+        assert_ne!(draws(&mut SimRng::at_tick(7, 300)), draws(&mut SimRng::at_tick(7, 301)));
+        assert_ne!(draws(&mut SimRng::at_tick(7, 300)), draws(&mut SimRng::from_seed(7)));
+    }
+
+    #[test]
+    fn test_worlds_with_the_same_seed_place_dens_alike() {
+        let ground_configs = create_test_ground_configs();
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut terrain_map = TerrainMap::new(40, 40, 16.0);
+        for x in 0..40 {
+            for y in 0..40 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+        let den: DenConfig = serde_yaml::from_str("count: 3\ngrounds: [grass]").unwrap();
+        let first = pick_den_tiles(&den, &terrain_map, &ground_configs, &mut SimRng::from_seed(9));
+        let second = pick_den_tiles(&den, &terrain_map, &ground_configs, &mut SimRng::from_seed(9));
+        assert_eq!(first.len(), 3);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::keybinds::InputMap;
+    use crate::systems::sim_tick::{SimTick, SimulationTick, SimulationSpeed, TickPeriod, advance_sim_tick, simulation_speed_input, apply_simulation_speed, SECONDS_PER_DAY};
+    use crate::tests::setup_test_app;
+
+    #[derive(Resource, Default)]
+    struct ReceivedTicks(Vec<SimulationTick>);
+
+    fn collect_ticks(mut received: ResMut<ReceivedTicks>, mut tick_events: EventReader<SimulationTick>) {
+        received.0.extend(tick_events.read().copied());
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        let mut sim_tick = SimTick::new(2);
+        assert_eq!(sim_tick.ticks_per(TickPeriod::Second), 2);
+        assert_eq!(sim_tick.ticks_per(TickPeriod::Minute), 120);
+        assert_eq!(sim_tick.ticks_per(TickPeriod::Day), 2 * SECONDS_PER_DAY);
+
+        assert!(!sim_tick.is_boundary(TickPeriod::Second), "Tick zero is not a boundary");
+
+        sim_tick.tick = 120;
+        assert!(sim_tick.is_boundary(TickPeriod::Second));
+        assert!(sim_tick.is_boundary(TickPeriod::Minute));
+        assert!(!sim_tick.is_boundary(TickPeriod::Day));
+
+        sim_tick.tick = 2 * SECONDS_PER_DAY * 3;
+        assert_eq!(sim_tick.day(), 3);
+    }
+
+    #[test]
+    fn test_zero_ticks_per_second_is_clamped() {
+        assert_eq!(SimTick::new(0).ticks_per_second, 1);
+    }
+
+    #[test]
+    fn test_advance_emits_period_events() {
+        let mut app = setup_test_app();
+        app.insert_resource(SimTick::new(2));
+        app.insert_resource(ReceivedTicks::default());
+        app.add_event::<SimulationTick>();
+        app.add_systems(Update, (advance_sim_tick, collect_ticks.after(advance_sim_tick)));
+
+        for _ in 0..120 {
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<SimTick>().tick, 120);
+
+        let received = &app.world().resource::<ReceivedTicks>().0;
+        let seconds = received.iter().filter(|e| e.period == TickPeriod::Second).count();
+        let minutes: Vec<_> = received.iter().filter(|e| e.period == TickPeriod::Minute).collect();
+        assert_eq!(seconds, 60);
+        assert_eq!(minutes.len(), 1);
+        assert_eq!(minutes[0].tick, 120);
+        assert!(received.iter().all(|e| e.period != TickPeriod::Day));
+    }
+
+    fn setup_speed_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.insert_resource(InputMap::default());
+        app.insert_resource(ButtonInput::<KeyCode>::default());
+        app.insert_resource(ButtonInput::<MouseButton>::default());
+        app.insert_resource(SimulationSpeed::default());
+        app.add_systems(Update, (simulation_speed_input, apply_simulation_speed.after(simulation_speed_input)));
+        app.update();
+        app
+    }
+
+    /// Press a key for a frame, then run another so the virtual clock picks the change up
+    fn press(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+        app.update();
+    }
+
+    fn virtual_delta(app: &App) -> Duration {
+        app.world().resource::<Time<Virtual>>().delta()
+    }
+
+    #[test]
+    fn test_speed_keys_scale_the_virtual_clock() {
+        let mut app = setup_speed_app();
+        app.update();
+        assert_eq!(virtual_delta(&app), Duration::from_millis(100));
+
+        press(&mut app, KeyCode::Digit3);
+        assert_eq!(app.world().resource::<SimulationSpeed>().multiplier, 4.0);
+        assert_eq!(virtual_delta(&app), Duration::from_millis(400));
+
+        press(&mut app, KeyCode::Digit2);
+        assert_eq!(virtual_delta(&app), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_pause_halts_the_virtual_clock_until_resumed() {
+        let mut app = setup_speed_app();
+        press(&mut app, KeyCode::Digit2);
+
+        press(&mut app, KeyCode::Space);
+        assert!(app.world().resource::<SimulationSpeed>().paused);
+        assert_eq!(virtual_delta(&app), Duration::ZERO);
+        app.update();
+        assert_eq!(virtual_delta(&app), Duration::ZERO, "Nothing moves on while paused");
+
+        press(&mut app, KeyCode::Space);
+        assert_eq!(virtual_delta(&app), Duration::from_millis(200), "Resuming keeps the speed it had");
+
+        // Picking a speed while paused resumes at that speed
+        press(&mut app, KeyCode::Space);
+        press(&mut app, KeyCode::Digit1);
+        assert!(!app.world().resource::<SimulationSpeed>().paused);
+        assert_eq!(virtual_delta(&app), Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::audio::SoundConfig;
+    use crate::systems::soundscape::{AmbientLoopConfig, AmbientTime, MusicTrack, Soundscape, ambient_level, ground_shares, update_music};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const WATER: usize = 3;
+
+    fn ambient(grounds: &[&str], time: AmbientTime) -> AmbientLoopConfig {
+        AmbientLoopConfig {
+            sound: "sounds/ambient.ogg".to_string(),
+            grounds: grounds.iter().map(|ground| ground.to_string()).collect(),
+            time,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_ground_around_the_view_is_shared_out() {
+        // Water filling the left half of a dirt map
+        let mut terrain_map = TerrainMap::new(20, 20, 16.0);
+        for x in 0..10 {
+            for y in 0..20 {
+                terrain_map.set_tile(x, y, WATER);
+            }
+        }
+        assert_eq!(terrain_map.tiles[15][15], DIRT);
+        let ground_configs = create_test_ground_configs();
+
+        let (over_water_x, _) = terrain_map.tile_to_world_coords(2, 10);
+        let (over_dirt_x, y) = terrain_map.tile_to_world_coords(17, 10);
+        let over_water = ground_shares(&terrain_map, &ground_configs, Vec2::new(over_water_x, y), 2.0);
+        let over_dirt = ground_shares(&terrain_map, &ground_configs, Vec2::new(over_dirt_x, y), 2.0);
+        assert_eq!(over_water.get("water"), Some(&1.0));
+        assert_eq!(over_dirt.get("dirt"), Some(&1.0));
+
+        let (shore_x, _) = terrain_map.tile_to_world_coords(10, 10);
+        let shore = ground_shares(&terrain_map, &ground_configs, Vec2::new(shore_x, y), 2.0);
+        assert!(shore["water"] > 0.3 && shore["water"] < 0.5, "Two columns of five are water: {:?}", shore);
+        assert!((shore["water"] + shore["dirt"] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_ambience_follows_ground_and_daylight() {
+        let shares: HashMap<String, f32> = [("grass".to_string(), 0.75), ("water".to_string(), 0.25)].into_iter().collect();
+
+        assert_eq!(ambient_level(&ambient(&["grass"], AmbientTime::Day), &shares, 1.0), 0.75);
+        assert_eq!(ambient_level(&ambient(&["grass"], AmbientTime::Day), &shares, 0.0), 0.0);
+        assert_eq!(ambient_level(&ambient(&["grass"], AmbientTime::Night), &shares, 0.0), 0.75);
+        assert_eq!(ambient_level(&ambient(&["water"], AmbientTime::Always), &shares, 0.5), 0.25);
+        assert_eq!(ambient_level(&ambient(&["grass", "water"], AmbientTime::Always), &shares, 0.5), 1.0);
+        assert_eq!(ambient_level(&ambient(&[], AmbientTime::Night), &shares, 0.5), 0.5, "No grounds plays everywhere");
+        assert_eq!(ambient_level(&ambient(&["stone"], AmbientTime::Always), &shares, 1.0), 0.0);
+    }
+
+    fn tracks(app: &mut App) -> Vec<(f32, bool)> {
+        let mut query = app.world_mut().query::<&MusicTrack>();
+        query.iter(app.world()).map(|track| (track.level, track.fading_out)).collect()
+    }
+
+    #[test]
+    fn test_music_crossfades_between_tracks() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+        app.insert_resource(GameConfig::default());
+        let mut sound_config = SoundConfig::default();
+        sound_config.music.track_secs = 10.0;
+        sound_config.music.crossfade_secs = 2.0;
+        app.insert_resource(sound_config);
+        app.insert_resource(Soundscape { tracks: vec![Handle::default(), Handle::default()], ..default() });
+        app.add_systems(Update, update_music);
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Soundscape>().current_track, 0);
+        assert_eq!(tracks(&mut app), vec![(0.5, false)], "First track fading in");
+
+        for _ in 0..9 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Soundscape>().current_track, 1);
+        let mut playing = tracks(&mut app);
+        playing.sort_by_key(|(_, fading_out)| *fading_out);
+        assert_eq!(playing, vec![(0.0, false), (0.5, true)], "The next track starts as the first fades");
+
+        app.update();
+        assert_eq!(tracks(&mut app), vec![(0.5, false)], "The first track's gone once faded out");
+        app.update();
+        assert_eq!(tracks(&mut app), vec![(1.0, false)]);
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use rand::prelude::*;
+    use rand::rngs::StdRng;
+    use std::time::Instant;
+    use crate::resources::GameConfig;
+    use crate::systems::pawn::Pawn;
+    use crate::systems::spatial_grid::{SpatialGrid, SpatialGridPlugin, SPATIAL_CELL_TILES};
+    use crate::tests::setup_test_app;
+
+    const TILE_SIZE: f32 = 16.0;
+    const CELL_SIZE: f32 = TILE_SIZE * SPATIAL_CELL_TILES;
+
+    /// `count` pawns scattered over a `tiles` by `tiles` map, always in the same places
+    fn scatter(count: u32, tiles: f32) -> Vec<(Entity, Vec2)> {
+        let mut rng = StdRng::seed_from_u64(42);
+        (0..count)
+            .map(|index| {
+                let position = Vec2::new(rng.gen_range(0.0..tiles), rng.gen_range(0.0..tiles)) * TILE_SIZE;
+                (Entity::from_raw(index), position)
+            })
+            .collect()
+    }
+
+    fn build_grid(pawns: &[(Entity, Vec2)]) -> SpatialGrid {
+        let mut grid = SpatialGrid::new(CELL_SIZE);
+        for (entity, position) in pawns {
+            grid.insert(*entity, *position);
+        }
+        grid
+    }
+
+    fn brute_force_within(pawns: &[(Entity, Vec2)], center: Vec2, radius: f32) -> Vec<Entity> {
+        let mut found: Vec<Entity> = pawns.iter().filter(|(_, position)| center.distance(*position) <= radius).map(|(entity, _)| *entity).collect();
+        found.sort();
+        found
+    }
+
+    fn brute_force_nearest(pawns: &[(Entity, Vec2)], center: Vec2, accept: impl Fn(Entity) -> bool) -> Option<Entity> {
+        pawns
+            .iter()
+            .filter(|(entity, _)| accept(*entity))
+            .min_by(|a, b| center.distance(a.1).total_cmp(&center.distance(b.1)))
+            .map(|(entity, _)| *entity)
+    }
+
+    #[test]
+    fn test_within_matches_brute_force() {
+        let pawns = scatter(300, 100.0);
+        let grid = build_grid(&pawns);
+        for (center, radius) in [(Vec2::new(800.0, 800.0), 100.0), (Vec2::ZERO, 300.0), (Vec2::new(-500.0, 0.0), 50.0), (Vec2::new(1600.0, 0.0), 5000.0)] {
+            let mut found: Vec<Entity> = grid.within(center, radius).map(|(entity, _)| entity).collect();
+            found.sort();
+            assert_eq!(found, brute_force_within(&pawns, center, radius), "Radius {} around {}", radius, center);
+        }
+        assert_eq!(SpatialGrid::new(CELL_SIZE).within(Vec2::ZERO, 100.0).count(), 0);
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let pawns = scatter(300, 100.0);
+        let grid = build_grid(&pawns);
+        let odd_only = |entity: Entity| entity.index() % 2 == 1;
+        for center in [Vec2::new(800.0, 800.0), Vec2::ZERO, Vec2::new(-2000.0, 3000.0), Vec2::new(1599.0, 1.0)] {
+            let nearest = grid.nearest(center, f32::INFINITY, |entity, _| odd_only(entity)).map(|(entity, _)| entity);
+            assert_eq!(nearest, brute_force_nearest(&pawns, center, odd_only), "Around {}", center);
+        }
+        assert_eq!(grid.nearest(Vec2::ZERO, f32::INFINITY, |_, _| false), None);
+    }
+
+    #[test]
+    fn test_nearest_only_looks_close_by() {
+        let mut grid = SpatialGrid::new(CELL_SIZE);
+        let near = Entity::from_raw(1);
+        grid.insert(near, Vec2::new(10.0, 0.0));
+        for index in 2..100 {
+            grid.insert(Entity::from_raw(index), Vec2::new(CELL_SIZE * 10.0 + index as f32, 0.0));
+        }
+
+        let mut asked = 0;
+        let nearest = grid.nearest(Vec2::ZERO, f32::INFINITY, |_, _| {
+            asked += 1;
+            true
+        });
+        assert_eq!(nearest, Some((near, 10.0)));
+        assert_eq!(asked, 1, "The far cluster is never considered");
+
+        assert_eq!(grid.nearest(Vec2::ZERO, 5.0, |_, _| true), None, "Nothing within the radius");
+    }
+
+    #[test]
+    fn test_grid_is_rebuilt_each_frame() {
+        let mut app = setup_test_app();
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_plugins(SpatialGridPlugin::default());
+        let pawn = app.world_mut().spawn((Pawn::new("rabbit".to_string()), Transform::from_xyz(20.0, 20.0, 100.0))).id();
+        app.world_mut().spawn(Transform::from_xyz(20.0, 20.0, 100.0)); // Not a pawn
+
+        app.update();
+        let found: Vec<Entity> = app.world().resource::<SpatialGrid>().within(Vec2::new(20.0, 20.0), 1.0).map(|(entity, _)| entity).collect();
+        assert_eq!(found, vec![pawn]);
+
+        app.world_mut().get_mut::<Transform>(pawn).unwrap().translation.x = 500.0;
+        app.update();
+        let grid = app.world().resource::<SpatialGrid>();
+        assert_eq!(grid.within(Vec2::new(20.0, 20.0), 1.0).count(), 0);
+        assert_eq!(grid.within(Vec2::new(500.0, 20.0), 1.0).count(), 1);
+    }
+
+    #[test]
+    fn test_performance_benchmark_grid_vs_brute_force() {
+        // 1000 pawns, every one looking around itself as hunters and prey do each frame
+        let pawns = scatter(1000, 200.0);
+        let radius = 8.0 * TILE_SIZE;
+
+        let start_time = Instant::now();
+        let mut brute_force_hits = 0;
+        let mut brute_force_nearest_hits = 0;
+        for (entity, position) in &pawns {
+            brute_force_hits += brute_force_within(&pawns, *position, radius).len();
+            brute_force_nearest_hits += brute_force_nearest(&pawns, *position, |other| other != *entity).is_some() as usize;
+        }
+        let brute_force_duration = start_time.elapsed();
+
+        let start_time = Instant::now();
+        let grid = build_grid(&pawns);
+        let mut grid_hits = 0;
+        let mut grid_nearest_hits = 0;
+        for (entity, position) in &pawns {
+            grid_hits += grid.within(*position, radius).count();
+            grid_nearest_hits += grid.nearest(*position, f32::INFINITY, |other, _| other != *entity).is_some() as usize;
+        }
+        let grid_duration = start_time.elapsed();
+
+        println!("1000 pawns - Brute force: {:?}, Spatial grid (including build): {:?}", brute_force_duration, grid_duration);
+        assert_eq!(grid_hits, brute_force_hits);
+        assert_eq!(grid_nearest_hits, brute_force_nearest_hits);
+        assert!(grid_duration < brute_force_duration / 2,
+               "Spatial grid queries should be at least 2x faster than checking every pawn");
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use crate::systems::spawn::{SpawnConfig, SpawnGroup, SpawnPlacement, pick_spawn_tiles, spawn_group_count, DEFAULT_CLUSTER_RADIUS};
+    use crate::systems::world_gen::{TerrainMap, GroundConfigs};
+    use crate::tests::create_test_ground_configs;
+
+    const SPAWNS_YAML: &str = r#"
+warren:
+  pawn: rabbit
+  density: 5.0
+  grounds: [grass]
+pack:
+  pawn: wolf
+  count: 2
+  min_distance: 6
+  placement: scattered
+"#;
+
+    /// Dirt map with a 10x10 patch of grass in the corner
+    fn create_spawn_terrain(ground_configs: &GroundConfigs) -> TerrainMap {
+        let dirt = ground_configs.terrain_mapping["dirt"];
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut terrain_map = TerrainMap::new(30, 30, 16.0);
+        for x in 0..30 {
+            for y in 0..30 {
+                terrain_map.set_tile(x, y, if x < 10 && y < 10 { grass } else { dirt });
+            }
+        }
+        terrain_map
+    }
+
+    fn group(yaml: &str) -> SpawnGroup {
+        serde_yaml::from_str(yaml).expect("Spawn group should parse")
+    }
+
+    #[test]
+    fn test_spawn_groups_parse_with_defaults() {
+        let config: SpawnConfig = serde_yaml::from_str(SPAWNS_YAML).expect("Spawns should parse");
+        let warren = &config.groups["warren"];
+        assert_eq!(warren.placement, SpawnPlacement::Clustered);
+        assert_eq!(warren.cluster_radius, DEFAULT_CLUSTER_RADIUS);
+        assert_eq!(warren.min_distance, 0);
+        assert_eq!(config.groups["pack"].placement, SpawnPlacement::Scattered);
+
+        let names: Vec<&String> = config.sorted_groups().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["pack", "warren"]);
+        assert!(config.has_groups_for("rabbit"));
+        assert!(!config.has_groups_for("player"));
+    }
+
+    #[test]
+    fn test_density_counts_the_groups_grounds() {
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_spawn_terrain(&ground_configs);
+        let warren = group("pawn: rabbit\ndensity: 5.0\ngrounds: [grass]");
+        assert_eq!(spawn_group_count(&warren, &terrain_map, &ground_configs), 5, "5 per 100 tiles of the 100 grass tiles");
+        let anywhere = group("pawn: rabbit\ndensity: 1.0");
+        assert_eq!(spawn_group_count(&anywhere, &terrain_map, &ground_configs), 9);
+        let counted = group("pawn: rabbit\ncount: 3\ndensity: 50.0");
+        assert_eq!(spawn_group_count(&counted, &terrain_map, &ground_configs), 3, "A count wins");
+    }
+
+    #[test]
+    fn test_clustered_groups_stay_together_on_their_grounds() {
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_spawn_terrain(&ground_configs);
+        let grass = ground_configs.terrain_mapping["grass"];
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let warren = group("pawn: rabbit\ngrounds: [grass]\ncluster_radius: 2");
+        let tiles = pick_spawn_tiles(&warren, 6, &terrain_map, &ground_configs, &[], &mut rng);
+        assert_eq!(tiles.len(), 6);
+        for a in &tiles {
+            assert_eq!(terrain_map.tiles[a.0 as usize][a.1 as usize], grass);
+            for b in &tiles {
+                assert!((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2) <= 16, "{:?} and {:?} are further apart than the cluster", a, b);
+            }
+        }
+
+        let crowded = group("pawn: rabbit\ngrounds: [grass]\ncluster_radius: 0");
+        let tiles = pick_spawn_tiles(&crowded, 3, &terrain_map, &ground_configs, &[], &mut rng);
+        assert_eq!(tiles.len(), 3, "Pawns double up once the cluster is full");
+        assert!(tiles.iter().all(|tile| *tile == tiles[0]));
+    }
+
+    #[test]
+    fn test_scattered_groups_keep_their_distance() {
+        let ground_configs = create_test_ground_configs();
+        let terrain_map = create_spawn_terrain(&ground_configs);
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let pack = group("pawn: wolf\nplacement: scattered\nmin_distance: 8");
+        let taken = [(0, 0), (29, 29)];
+        let tiles = pick_spawn_tiles(&pack, 20, &terrain_map, &ground_configs, &taken, &mut rng);
+        assert_eq!(tiles.len(), 20);
+        for (index, tile) in tiles.iter().enumerate() {
+            assert!(!tiles[..index].contains(tile), "Scattered pawns get a tile each");
+            for other in &taken {
+                assert!((other.0 - tile.0).pow(2) + (other.1 - tile.1).pow(2) >= 64, "{:?} is too close to {:?}", tile, other);
+            }
+        }
+
+        let nowhere = group("pawn: wolf\ngrounds: [water]");
+        assert!(pick_spawn_tiles(&nowhere, 2, &terrain_map, &ground_configs, &[], &mut rng).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use crate::systems::combat::{CombatEvent, DeathEvent};
+    use crate::systems::pawn::{Pawn, Health, Endurance};
+    use crate::systems::statistics::{POPULATION_HISTORY_LEN, Statistics, SpeciesSummary, line_strips, record_population_events, sample_population, summarize_population};
+    use crate::tests::setup_test_app;
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, health: f32, endurance: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            Health { current: health, max: 100.0 },
+            Endurance { current: endurance, max: 100.0, health_loss_timer: 0.0 },
+        )).id()
+    }
+
+    #[test]
+    fn test_population_is_summarized_per_species() {
+        let mut app = setup_test_app();
+        spawn_pawn(&mut app, "wolf", 100.0, 50.0);
+        spawn_pawn(&mut app, "wolf", 50.0, 100.0);
+        spawn_pawn(&mut app, "rabbit", 20.0, 10.0);
+
+        let mut query = app.world_mut().query::<(&Pawn, &Health, &Endurance)>();
+        let species = summarize_population(query.iter(app.world()));
+        assert_eq!(species.len(), 2);
+        assert_eq!(species["wolf"], SpeciesSummary { count: 2, average_health: 0.75, average_endurance: 0.75 });
+        assert_eq!(species["rabbit"], SpeciesSummary { count: 1, average_health: 0.2, average_endurance: 0.1 });
+    }
+
+    #[test]
+    fn test_births_and_deaths_are_counted_over_the_last_minute() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(20)));
+        app.world_mut().resource_mut::<Time<Virtual>>().set_max_delta(Duration::from_secs(20));
+        app.insert_resource(Statistics::default());
+        app.add_systems(Update, record_population_events);
+
+        let wolf = spawn_pawn(&mut app, "wolf", 100.0, 100.0);
+        app.update();
+        assert_eq!(app.world().resource::<Statistics>().births_per_minute(), 0, "The starting population isn't born");
+
+        let cub = spawn_pawn(&mut app, "wolf", 100.0, 100.0);
+        app.world_mut().send_event(CombatEvent { attacker: wolf, victim: cub, damage: 5.0 });
+        app.world_mut().send_event(DeathEvent { victim_type: "wolf".to_string(), position: Vec2::ZERO, killer: Some(wolf) });
+        app.update();
+        let statistics = app.world().resource::<Statistics>();
+        assert_eq!((statistics.births_per_minute(), statistics.deaths_per_minute(), statistics.blows_per_minute()), (1, 1, 1));
+
+        // 20 seconds a frame, so they're over a minute old four frames later
+        for _ in 0..4 {
+            app.update();
+        }
+        let statistics = app.world().resource::<Statistics>();
+        assert_eq!((statistics.births_per_minute(), statistics.deaths_per_minute(), statistics.blows_per_minute()), (0, 0, 0));
+        assert_eq!((statistics.total_births, statistics.total_deaths), (1, 1));
+    }
+
+    #[test]
+    fn test_population_history_keeps_the_latest_samples() {
+        let mut statistics = Statistics::default();
+        for wolves in 0..POPULATION_HISTORY_LEN + 5 {
+            let counts: BTreeMap<String, usize> = [("wolf".to_string(), wolves), ("rabbit".to_string(), 3), ("deer".to_string(), 1)].into();
+            statistics.record_sample(counts);
+        }
+        let wolves = statistics.history_of(|pawn_type| pawn_type == "wolf");
+        assert_eq!(wolves.len(), POPULATION_HISTORY_LEN);
+        assert_eq!((wolves[0], wolves[POPULATION_HISTORY_LEN - 1]), (5, POPULATION_HISTORY_LEN + 4), "The oldest samples make way");
+        assert!(statistics.history_of(|pawn_type| pawn_type != "wolf").iter().all(|prey| *prey == 4));
+        assert_eq!(statistics.samples_taken, POPULATION_HISTORY_LEN as u64 + 5);
+    }
+
+    #[test]
+    fn test_population_is_sampled_every_few_seconds() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+        app.world_mut().resource_mut::<Time<Virtual>>().set_max_delta(Duration::from_secs(1));
+        app.insert_resource(Statistics::default());
+        app.add_systems(Update, sample_population);
+        spawn_pawn(&mut app, "wolf", 100.0, 100.0);
+
+        for _ in 0..11 {
+            app.update();
+        }
+        // Taken at 0, 5 and 10 seconds
+        assert_eq!(app.world().resource::<Statistics>().history_of(|_| true), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_line_strips_join_each_sample_to_the_last() {
+        let size = Vec2::new(POPULATION_HISTORY_LEN as f32 * 2.0, 102.0);
+        let strips = line_strips(&[0, 10, 10, 5], 10, size);
+        assert_eq!(strips, vec![
+            (0.0, 100.0, 2.0),  // Starts at the bottom
+            (2.0, 0.0, 102.0),  // Climbs to the top
+            (4.0, 0.0, 2.0),    // Stays there
+            (6.0, 0.0, 52.0),   // Drops halfway
+        ]);
+    }
+}
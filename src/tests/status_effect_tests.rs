@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::systems::combat::CombatEvent;
+    use crate::systems::pawn::{Pawn, Health};
+    use crate::systems::pawn_config::PawnConfig;
+    use crate::systems::sim_tick::{SimulationTick, TickPeriod};
+    use crate::systems::status_effects::{
+        StatusEffectConfig, StatusEffects, Stacking, ApplyStatusEffect,
+        inflict_attack_effects, ground_status_effects, apply_status_effects, status_effect_system,
+    };
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const STATUS_EFFECTS_YAML: &str = r#"
+poison:
+  duration: 2.0
+  damage_per_second: 2.0
+  stacking: stack
+  max_stacks: 2
+slow:
+  duration: 4.0
+  speed_multiplier: 0.5
+regen:
+  duration: 1.0
+  heal_per_second: 10.0
+  stacking: ignore
+burn:
+  duration: 3.0
+  damage_per_second: 5.0
+"#;
+
+    const SNAKE_PAWNS_YAML: &str = r#"
+snake:
+  sprite: "tileset::pawns::wolf"
+  tags: [small, animal]
+  move_speed: 60.0
+  max_health: 20
+  max_endurance: 10
+  defence: 0
+  strength: 5
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  inflicts: [poison, slow]
+  eats:
+    pawns: []
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn effect_config() -> StatusEffectConfig {
+        serde_yaml::from_str(STATUS_EFFECTS_YAML).expect("Status effects should parse")
+    }
+
+    fn setup_effects_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(SNAKE_PAWNS_YAML).expect("Snake config should parse");
+        app.insert_resource(config);
+        app.insert_resource(effect_config());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.add_systems(Update, (
+            inflict_attack_effects,
+            apply_status_effects.after(inflict_attack_effects),
+            status_effect_system.after(apply_status_effects),
+        ));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, health: f32) -> Entity {
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            Health { current: health, max: 25.0 },
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id()
+    }
+
+    fn stacks(app: &App, entity: Entity, name: &str) -> Option<u32> {
+        app.world()
+            .get::<StatusEffects>(entity)
+            .and_then(|effects| effects.effects.iter().find(|effect| effect.name == name))
+            .map(|effect| effect.stacks)
+    }
+
+    #[test]
+    fn test_status_effect_config_defaults() {
+        let config = effect_config();
+        let slow = config.get("slow").unwrap();
+        assert_eq!((slow.stacking, slow.max_stacks, slow.damage_per_second), (Stacking::Refresh, 1, 0.0));
+        assert_eq!(config.get("burn").unwrap().speed_multiplier, 1.0, "Effects leave speed alone unless they say otherwise");
+        assert!(config.get("frostbite").is_none());
+    }
+
+    #[test]
+    fn test_stacking_rules() {
+        let config = effect_config();
+        let mut effects = StatusEffects::default();
+        for _ in 0..3 {
+            effects.apply("poison", config.get("poison").unwrap());
+        }
+        effects.apply("slow", config.get("slow").unwrap());
+        effects.effects.iter_mut().for_each(|effect| effect.remaining = 0.5);
+        effects.apply("slow", config.get("slow").unwrap());
+        effects.apply("regen", config.get("regen").unwrap());
+        effects.effects.iter_mut().filter(|effect| effect.name == "regen").for_each(|effect| effect.remaining = 0.5);
+        effects.apply("regen", config.get("regen").unwrap());
+
+        let find = |name: &str| effects.effects.iter().find(|effect| effect.name == name).unwrap();
+        assert_eq!(find("poison").stacks, 2, "Capped at max_stacks");
+        assert_eq!((find("slow").stacks, find("slow").remaining), (1, 4.0), "Refreshed, not stacked");
+        assert_eq!(find("regen").remaining, 0.5, "Re-applying an ignore effect does nothing");
+        assert_eq!(effects.speed_multiplier(), 0.5);
+    }
+
+    #[test]
+    fn test_effects_tick_and_wear_off() {
+        let mut app = setup_effects_app();
+        let poisoned = spawn_pawn(&mut app, "rabbit", 20.0);
+        let healing = spawn_pawn(&mut app, "rabbit", 20.0);
+        app.world_mut().send_event(ApplyStatusEffect { target: poisoned, effect: "poison".to_string() });
+        app.world_mut().send_event(ApplyStatusEffect { target: poisoned, effect: "poison".to_string() });
+        app.world_mut().send_event(ApplyStatusEffect { target: healing, effect: "regen".to_string() });
+        app.update();
+        assert_eq!(stacks(&app, poisoned, "poison"), Some(2));
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let health = app.world().get::<Health>(poisoned).unwrap().current;
+        assert!((health - 16.0).abs() < 0.01, "Two stacks at 2 a second for a second, got {}", health);
+        assert_eq!(app.world().get::<Health>(healing).unwrap().current, 25.0, "Healing stops at max health");
+        assert!(app.world().get::<StatusEffects>(healing).is_none(), "Regen has worn off");
+
+        for _ in 0..15 {
+            app.update();
+        }
+        let health = app.world().get::<Health>(poisoned).unwrap().current;
+        assert!((health - 12.0).abs() < 0.01, "Poison stops after its duration, got {}", health);
+        assert!(app.world().get::<StatusEffects>(poisoned).is_none());
+    }
+
+    #[test]
+    fn test_blows_inflict_effects() {
+        let mut app = setup_effects_app();
+        let snake = spawn_pawn(&mut app, "snake", 20.0);
+        let rabbit = spawn_pawn(&mut app, "rabbit", 25.0);
+        app.world_mut().send_event(CombatEvent { attacker: snake, victim: rabbit, damage: 0.0 });
+        app.world_mut().send_event(CombatEvent { attacker: rabbit, victim: snake, damage: 0.0 });
+        app.update();
+
+        assert_eq!(stacks(&app, rabbit, "poison"), Some(1));
+        assert_eq!(app.world().get::<StatusEffects>(rabbit).unwrap().speed_multiplier(), 0.5, "The snake's bite slows too");
+        assert!(app.world().get::<StatusEffects>(snake).is_none(), "Rabbits don't inflict anything");
+    }
+
+    #[test]
+    fn test_ground_effects() {
+        let mut app = setup_effects_app();
+        let mut ground_configs = create_test_ground_configs();
+        ground_configs.configs.get_mut("dirt").unwrap().status_effect = Some("burn".to_string());
+        let (dirt, grass) = (ground_configs.terrain_mapping["dirt"], ground_configs.terrain_mapping["grass"]);
+        let mut terrain_map = TerrainMap::new(10, 10, TILE_SIZE);
+        terrain_map.set_tile(2, 2, dirt);
+        terrain_map.set_tile(6, 6, grass);
+        let on_dirt = terrain_map.tile_to_world_coords(2, 2);
+        let elsewhere = terrain_map.tile_to_world_coords(6, 6);
+        app.insert_resource(ground_configs);
+        app.insert_resource(terrain_map);
+        app.add_event::<SimulationTick>();
+        app.add_systems(Update, ground_status_effects.before(apply_status_effects));
+
+        let burning = spawn_pawn(&mut app, "rabbit", 25.0);
+        app.world_mut().get_mut::<Transform>(burning).unwrap().translation = Vec3::new(on_dirt.0, on_dirt.1, 100.0);
+        let safe = spawn_pawn(&mut app, "rabbit", 25.0);
+        app.world_mut().get_mut::<Transform>(safe).unwrap().translation = Vec3::new(elsewhere.0, elsewhere.1, 100.0);
+        app.update();
+        assert!(app.world().get::<StatusEffects>(burning).is_none(), "Ground effects wait for a simulated second");
+
+        app.world_mut().send_event(SimulationTick { tick: 20, period: TickPeriod::Second });
+        app.update();
+
+        assert_eq!(stacks(&app, burning, "burn"), Some(1));
+        assert!(app.world().get::<Health>(burning).unwrap().current < 25.0);
+        assert!(app.world().get::<StatusEffects>(safe).is_none());
+    }
+}
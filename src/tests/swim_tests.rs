@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::async_pathfinding::{
+        PathfindingRequest, PathfindingTask, PathfindingRequestCounter, GlobalPathfindingCache,
+        PathfindingCompleted, PathfindingFailed, spawn_cached_pathfinding_tasks, handle_completed_cached_pathfinding,
+    };
+    use crate::systems::pathfinding_heatmap::PathfindingHeatmap;
+    use crate::systems::pawn::{Pawn, Health, Endurance, PawnTarget, Size, Swimmer, move_pawn_to_target, drowning_system};
+    use crate::systems::pawn_config::{PawnConfig, PawnDefinition, PawnBehaviours, PawnEats, ActivitySchedule};
+    use crate::systems::faction::FactionConfig;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    fn create_swim_pawn_definition(can_swim: bool) -> PawnDefinition {
+        PawnDefinition {
+            sprite: "test.png".to_string(),
+            tags: vec![],
+            move_speed: 100.0,
+            max_health: 50,
+            max_endurance: 100,
+            strength: 10,
+            defence: 0,
+            attack_speed: 1.0,
+            reach: 1,
+            attack: None,
+            size: 1.0,
+            spawn_count: 1,
+            faction: None,
+            behaviours: PawnBehaviours {
+                idle: None,
+                hunted: None,
+                looking_for_food: None,
+                eat: None,
+                controlled: None,
+                flee: None,
+                thirsty: None,
+                tired: None,
+                resting: None,
+            },
+            eats: PawnEats { pawns: vec![], grounds: vec![], graze: None, meal: None },
+            infuses: None,
+            charge: None,
+            can_swim,
+            swim_speed: Some(50.0),
+            swim_endurance_multiplier: Some(4.0),
+            behaviour_tree: None,
+            pack: None,
+            herd: None,
+            aging: None,
+            needs: None,
+            schedule: ActivitySchedule::Always,
+            rest: None,
+            perception: None,
+            vision: None,
+            utility: None,
+            tame: None,
+            follow: None,
+            den: None,
+            migrate: None,
+            stealth: 0.0,
+            ambush: None,
+            inflicts: Vec::new(),
+            health_regen: None,
+            growth: None,
+            population: None,
+            can_carry: false,
+        }
+    }
+
+    /// Grass map split by a water river running down column `river_x`
+    fn create_river_terrain(width: u32, height: u32, river_x: u32) -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(width, height, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap_or(&1);
+        let water = *ground_configs.terrain_mapping.get("water").unwrap_or(&3);
+        for x in 0..width {
+            for y in 0..height {
+                terrain_map.set_tile(x, y, if x == river_x { water } else { grass });
+            }
+        }
+        terrain_map
+    }
+
+    #[test]
+    fn test_swimmers_see_water_as_passable() {
+        let ground_configs = create_test_ground_configs();
+        let swimmer_configs = ground_configs.for_swimmers();
+        let water = *ground_configs.terrain_mapping.get("water").unwrap();
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap();
+
+        assert!(ground_configs.is_swimmable(water));
+        assert!(!ground_configs.is_swimmable(grass));
+        assert!(!ground_configs.is_passable(water));
+        assert!(swimmer_configs.is_passable(water));
+        assert!(!swimmer_configs.is_passable(stone), "Swimming doesn't get anyone through stone");
+        assert!(swimmer_configs.is_passable(grass));
+    }
+
+    #[test]
+    fn test_only_swimmers_can_path_across_river() {
+        let terrain_map = create_river_terrain(12, 8, 6);
+        let ground_configs = create_test_ground_configs();
+        let swimmer_configs = ground_configs.for_swimmers();
+        let west = terrain_map.tile_to_world_coords(2, 4);
+        let east = terrain_map.tile_to_world_coords(10, 4);
+
+        // Walkers and swimmers share the map's nav and region caches - they must not mix
+        assert!(terrain_map.find_path_for_size(west, east, 1.0, &ground_configs).is_none());
+        assert!(terrain_map.find_path_for_size(west, east, 1.0, &swimmer_configs).is_some());
+        assert!(terrain_map.find_path_for_size(west, east, 1.0, &ground_configs).is_none());
+
+        assert!(!terrain_map.are_connected(west, east, 1.0, &ground_configs));
+        assert!(terrain_map.are_connected(west, east, 1.0, &swimmer_configs));
+    }
+
+    #[test]
+    fn test_swimming_is_slower_and_more_tiring() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.insert_resource(GameConfig {
+            tile_size: 32.0,
+            map_width: 20,
+            map_height: 8,
+            endurance_cost_per_cell: 1.0,
+            ..GameConfig::default()
+        });
+        let mut pawns = std::collections::HashMap::new();
+        pawns.insert("otter".to_string(), create_swim_pawn_definition(true));
+        app.insert_resource(PawnConfig { pawns, factions: FactionConfig::default() });
+
+        // Water along the bottom rows, grass above
+        let mut terrain_map = TerrainMap::new(20, 8, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let water = *ground_configs.terrain_mapping.get("water").unwrap();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap();
+        for x in 0..20 {
+            for y in 0..8 {
+                terrain_map.set_tile(x, y, if y < 3 { water } else { grass });
+            }
+        }
+
+        let spawn_otter = |app: &mut App, from: (f32, f32), to: (f32, f32)| {
+            app.world_mut().spawn((
+                Pawn::new("otter".to_string()),
+                Swimmer,
+                Size { value: 1.0 },
+                Health::new(50),
+                Endurance::new(100),
+                Transform::from_xyz(from.0, from.1, 100.0),
+                PawnTarget::new(Vec3::new(to.0, to.1, 100.0)),
+            )).id()
+        };
+        let swimming = spawn_otter(&mut app, terrain_map.tile_to_world_coords(2, 1), terrain_map.tile_to_world_coords(17, 1));
+        let walking = spawn_otter(&mut app, terrain_map.tile_to_world_coords(2, 5), terrain_map.tile_to_world_coords(17, 5));
+        let swim_start = terrain_map.tile_to_world_coords(2, 1).0;
+        let walk_start = terrain_map.tile_to_world_coords(2, 5).0;
+
+        app.insert_resource(terrain_map);
+        app.insert_resource(ground_configs);
+        app.add_systems(Update, move_pawn_to_target);
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let swum = app.world().get::<Transform>(swimming).unwrap().translation.x - swim_start;
+        let walked = app.world().get::<Transform>(walking).unwrap().translation.x - walk_start;
+        assert!(walked > 0.0, "Walker should have moved");
+        assert!((swum / walked - 0.5).abs() < 0.01, "Swim speed is half the move speed, got {} vs {}", swum, walked);
+
+        let swim_cost = 100.0 - app.world().get::<Endurance>(swimming).unwrap().current;
+        let walk_cost = 100.0 - app.world().get::<Endurance>(walking).unwrap().current;
+        assert!((swim_cost / walk_cost - 2.0).abs() < 0.01,
+                "Half the distance at four times the cost per cell should cost twice as much, got {} vs {}", swim_cost, walk_cost);
+    }
+
+    #[test]
+    fn test_drowning() {
+        let mut app = setup_test_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        let terrain_map = create_river_terrain(12, 8, 6);
+        let in_water = terrain_map.tile_to_world_coords(6, 4);
+        let on_land = terrain_map.tile_to_world_coords(2, 4);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.add_systems(Update, drowning_system);
+
+        let mut spawn_pawn = |position: (f32, f32), swimmer: bool, endurance: f32| {
+            let mut entity = app.world_mut().spawn((
+                Pawn::new("test_pawn".to_string()),
+                Health::new(50),
+                Endurance { current: endurance, max: 100.0, health_loss_timer: 0.0 },
+                Transform::from_xyz(position.0, position.1, 100.0),
+            ));
+            if swimmer {
+                entity.insert(Swimmer);
+            }
+            entity.id()
+        };
+        let sinking = spawn_pawn(in_water, false, 100.0);
+        let swimming = spawn_pawn(in_water, true, 100.0);
+        let exhausted = spawn_pawn(in_water, true, 0.0);
+        let ashore = spawn_pawn(on_land, false, 100.0);
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let health = |entity: Entity| app.world().get::<Health>(entity).unwrap().current;
+        assert!(health(sinking) < 50.0, "Non-swimmers drown in water");
+        assert_eq!(health(swimming), 50.0, "Swimmers with endurance stay afloat");
+        assert!(health(exhausted) < 50.0, "Exhausted swimmers drown");
+        assert_eq!(health(ashore), 50.0, "Nobody drowns on land");
+    }
+
+    #[test]
+    fn test_swimmer_requests_cross_water_and_skip_path_cache() {
+        let mut app = setup_test_app();
+        let terrain_map = create_river_terrain(12, 8, 6);
+        let west = terrain_map.tile_to_world_coords(2, 4);
+        let east = terrain_map.tile_to_world_coords(10, 4);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(PathfindingRequestCounter::default());
+        app.insert_resource(GlobalPathfindingCache::default());
+        app.insert_resource(PathfindingHeatmap::default());
+        app.add_event::<PathfindingCompleted>();
+        app.add_event::<PathfindingFailed>();
+        app.add_systems(Update, (
+            spawn_cached_pathfinding_tasks,
+            handle_completed_cached_pathfinding.after(spawn_cached_pathfinding_tasks),
+        ));
+
+        let walker = app.world_mut().spawn((
+            Transform::from_xyz(west.0, west.1, 0.0),
+            PathfindingRequest::new(west, east, 1.0),
+        )).id();
+        let swimmer = app.world_mut().spawn((
+            Transform::from_xyz(west.0, west.1, 0.0),
+            Swimmer,
+            PathfindingRequest::new(west, east, 1.0),
+        )).id();
+
+        for _ in 0..200 {
+            app.update();
+            let pending = [walker, swimmer].iter().any(|&entity| {
+                app.world().get::<PathfindingRequest>(entity).is_some() || app.world().get::<PathfindingTask>(entity).is_some()
+            });
+            if !pending {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(app.world().get::<PawnTarget>(walker).is_none(), "Walkers can't cross the river");
+        assert!(app.world().get::<PawnTarget>(swimmer).is_some(), "Swimmers can");
+
+        // Only the walker's "no path" was cached, so it can't be served to a swimmer later
+        let mut global_cache = app.world_mut().resource_mut::<GlobalPathfindingCache>();
+        assert_eq!(global_cache.cache_mut().path_entry_count(), 1);
+    }
+}
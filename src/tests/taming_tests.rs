@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{ActiveBehaviour, HuntSoloAI};
+    use crate::systems::async_pathfinding::PathfindingRequest;
+    use crate::systems::bestiary::{BestiaryProgress, DiscoveryLevel};
+    use crate::systems::follow::{FollowTarget, follow_ai_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, BehaviourConfig, BehaviourType};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::taming::{FeedPawn, Tameness, Owner, FollowOwnerAI, FEED_ENDURANCE, feeding_system, follow_owner_system};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const TAMING_PAWNS_YAML: &str = r#"
+player:
+  sprite: "tileset::pawns::player"
+  tags: [medium, humanoid]
+  move_speed: 150.0
+  max_health: 100
+  max_endurance: 100
+  defence: 10
+  strength: 20
+  attack_speed: 2
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+  eats:
+    pawns: []
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  tame:
+    feedings: 2
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+  eats:
+    pawns: [small, animal]
+bear:
+  sprite: "tileset::pawns::bear"
+  tags: [large, animal]
+  move_speed: 100.0
+  max_health: 200
+  max_endurance: 60
+  defence: 20
+  strength: 40
+  attack_speed: 1
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+  eats:
+    pawns: [humanoid]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn setup_taming_app() -> App {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(TAMING_PAWNS_YAML).expect("Taming config should parse");
+        app.insert_resource(config);
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.init_resource::<BestiaryProgress>();
+        app.add_event::<FeedPawn>();
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, (feeding_system, follow_owner_system.after(feeding_system), follow_ai_system.after(follow_owner_system)));
+        app
+    }
+
+    fn spawn_pawn(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(100),
+            Endurance { current: 30.0, ..Endurance::new(100) },
+            Size { value: 1.0 },
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    fn feed(app: &mut App, feeder: Entity, pawn: Entity) {
+        app.world_mut().send_event(FeedPawn { feeder, pawn });
+        app.update();
+    }
+
+    #[test]
+    fn test_tame_config_defaults() {
+        let config: PawnConfig = serde_yaml::from_str(TAMING_PAWNS_YAML).expect("Taming config should parse");
+        let tame = config.get_pawn_definition("wolf").unwrap().tame.clone().unwrap();
+        assert_eq!(tame.feedings, 2);
+        assert!(config.get_pawn_definition("bear").unwrap().tame.is_none());
+    }
+
+    #[test]
+    fn test_feeding_tames_after_enough_feedings() {
+        let mut app = setup_taming_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let wolf = spawn_pawn(&mut app, "wolf", (6, 5));
+
+        feed(&mut app, player, wolf);
+        assert_eq!(app.world().get::<Tameness>(wolf).unwrap().feedings, 1);
+        assert!(app.world().get::<Owner>(wolf).is_none(), "One feeding isn't enough");
+        assert_eq!(app.world().get::<Endurance>(player).unwrap().current, 30.0 - FEED_ENDURANCE);
+        assert_eq!(app.world().get::<Endurance>(wolf).unwrap().current, 30.0 + FEED_ENDURANCE);
+
+        feed(&mut app, player, wolf);
+        assert_eq!(app.world().get::<Owner>(wolf).unwrap().owner, player);
+        assert!(app.world().get::<Tameness>(wolf).is_none());
+        assert!(matches!(
+            app.world().get::<ActiveBehaviour>(wolf).unwrap().action,
+            BehaviourConfig::Simple(BehaviourType::Follow)
+        ));
+        assert_eq!(app.world().get::<CurrentBehavior>(wolf).unwrap().state, "follow");
+        assert_eq!(app.world().resource::<BestiaryProgress>().level("wolf"), DiscoveryLevel::Tamed);
+    }
+
+    #[test]
+    fn test_only_tameable_pawns_are_tamed_and_feeding_needs_food() {
+        let mut app = setup_taming_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let bear = spawn_pawn(&mut app, "bear", (6, 5));
+        let wolf = spawn_pawn(&mut app, "wolf", (5, 6));
+
+        for _ in 0..3 {
+            feed(&mut app, player, bear);
+        }
+        assert!(app.world().get::<Owner>(bear).is_none());
+        assert!(app.world().get::<Tameness>(bear).is_none());
+
+        app.world_mut().get_mut::<Endurance>(player).unwrap().current = FEED_ENDURANCE - 1.0;
+        feed(&mut app, player, wolf);
+        assert!(app.world().get::<Tameness>(wolf).is_none(), "Nothing to feed it with");
+    }
+
+    #[test]
+    fn test_tamed_pawn_follows_its_owner() {
+        let mut app = setup_taming_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let wolf = spawn_pawn(&mut app, "wolf", (6, 5));
+        feed(&mut app, player, wolf);
+        feed(&mut app, player, wolf);
+        assert_eq!(app.world().get::<FollowTarget>(wolf), Some(&FollowTarget { leader: player }));
+
+        app.update();
+        assert!(app.world().get::<PathfindingRequest>(wolf).is_none(), "Close enough already");
+
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(12, 5);
+        app.world_mut().get_mut::<Transform>(player).unwrap().translation = Vec3::new(x, y, 100.0);
+        app.update();
+        let request = app.world().get::<PathfindingRequest>(wolf).expect("The wolf should head after its owner");
+        assert_eq!(request.goal, (x, y));
+    }
+
+    #[test]
+    fn test_tamed_pawn_defends_its_owner() {
+        let mut app = setup_taming_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let wolf = spawn_pawn(&mut app, "wolf", (6, 5));
+        let bear = spawn_pawn(&mut app, "bear", (8, 5));
+        app.world_mut().entity_mut(wolf).insert((Owner { owner: player }, FollowOwnerAI::default()));
+        app.world_mut().entity_mut(bear).insert(HuntSoloAI { target_entity: Some(player), ..HuntSoloAI::new() });
+
+        app.update();
+        assert_eq!(app.world().get::<FollowOwnerAI>(wolf).unwrap().defending, Some(bear));
+        assert_eq!(app.world().get::<HuntSoloAI>(wolf).unwrap().target_entity, Some(bear));
+        assert!(matches!(
+            app.world().get::<ActiveBehaviour>(wolf).unwrap().action,
+            BehaviourConfig::Simple(BehaviourType::HuntSolo)
+        ));
+
+        // Once the bear is gone the wolf comes back to heel
+        app.world_mut().despawn(bear);
+        app.world_mut().get_mut::<HuntSoloAI>(wolf).unwrap().target_entity = None;
+        app.update();
+        assert_eq!(app.world().get::<FollowOwnerAI>(wolf).unwrap().defending, None);
+        assert_eq!(app.world().get::<CurrentBehavior>(wolf).unwrap().state, "follow");
+    }
+
+    #[test]
+    fn test_tamed_pawn_goes_wild_when_its_owner_dies() {
+        let mut app = setup_taming_app();
+        let player = spawn_pawn(&mut app, "player", (5, 5));
+        let wolf = spawn_pawn(&mut app, "wolf", (6, 5));
+        feed(&mut app, player, wolf);
+        feed(&mut app, player, wolf);
+        assert!(app.world().get::<Owner>(wolf).is_some());
+
+        app.world_mut().get_mut::<Health>(player).unwrap().current = 0.0;
+        app.update();
+        assert!(app.world().get::<Owner>(wolf).is_none());
+        assert!(app.world().get::<ActiveBehaviour>(wolf).is_none());
+        assert_eq!(app.world().get::<CurrentBehavior>(wolf).unwrap().state, "idle");
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use crate::systems::config_validation::check_grounds;
+    use crate::systems::status_effects::StatusEffectConfig;
+    use crate::systems::terrain_effects::{
+        TerrainEffectKind, TerrainEffectMaterial, TerrainEffectOverlays, TerrainEffectTile,
+        edge_mask, edge_distances_within, depth_level, update_terrain_effects,
+        EDGE_N, EDGE_NE, EDGE_E, EDGE_SE, EDGE_S, EDGE_SW, EDGE_W, EDGE_NW,
+    };
+    use crate::systems::world_gen::{GroundConfig, TerrainChanges, TerrainMap};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+    const STONE: usize = 2;
+    const WATER: usize = 3;
+
+    /// A lake filling all but a dirt border one tile wide
+    fn create_lake(size: u32) -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(size, size, 16.0);
+        for x in 0..size {
+            for y in 0..size {
+                let border = x == 0 || y == 0 || x == size - 1 || y == size - 1;
+                terrain_map.set_tile(x, y, if border { DIRT } else { WATER });
+            }
+        }
+        terrain_map
+    }
+
+    fn edge_distances(terrain_map: &TerrainMap, terrain_type: usize) -> Vec<Vec<u32>> {
+        edge_distances_within(terrain_map, terrain_type, (0, 0), (terrain_map.width, terrain_map.height))
+    }
+
+    fn setup_effect_app(terrain_map: TerrainMap) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(TerrainChanges::default());
+        app.init_resource::<Assets<TerrainEffectMaterial>>();
+        app.insert_resource(TerrainEffectOverlays::new(1, Handle::default()));
+        app.add_systems(Update, update_terrain_effects);
+        app
+    }
+
+    fn change_tiles(app: &mut App, tiles: &[(u32, u32, usize)]) {
+        let mut terrain_changes = TerrainChanges::default();
+        for &(x, y, terrain_type) in tiles {
+            app.world_mut().resource_mut::<TerrainMap>().set_tile(x, y, terrain_type);
+            terrain_changes.add_change(x, y, terrain_type);
+        }
+        app.insert_resource(terrain_changes);
+        app.update();
+    }
+
+    fn material_of(app: &App, tile: (u32, u32)) -> TerrainEffectMaterial {
+        let (entity, _) = app.world().resource::<TerrainEffectOverlays>().tiles[&tile];
+        let handle = app.world().get::<MeshMaterial2d<TerrainEffectMaterial>>(entity).unwrap().0.clone();
+        app.world().resource::<Assets<TerrainEffectMaterial>>().get(&handle).unwrap().clone()
+    }
+
+    #[test]
+    fn test_edge_mask_marks_the_shores() {
+        let terrain_map = create_lake(5);
+        assert_eq!(edge_mask(&terrain_map, 2, 2), 0, "The middle of the lake is out of sight of land");
+        assert_eq!(edge_mask(&terrain_map, 1, 1), EDGE_W | EDGE_SW | EDGE_S | EDGE_SE | EDGE_NW);
+        assert_eq!(edge_mask(&terrain_map, 2, 3), EDGE_NW | EDGE_N | EDGE_NE);
+        assert_eq!(edge_mask(&terrain_map, 3, 2), EDGE_NE | EDGE_E | EDGE_SE);
+        assert_eq!(edge_mask(&terrain_map, 0, 0), EDGE_NE, "Dirt has its edges against the water too");
+
+        let mut open_sea = TerrainMap::new(3, 3, 16.0);
+        for x in 0..3 {
+            for y in 0..3 {
+                open_sea.set_tile(x, y, WATER);
+            }
+        }
+        assert_eq!(edge_mask(&open_sea, 0, 0), 0, "No foam along the edge of the map");
+        open_sea.set_tile(1, 1, DIRT);
+        assert_eq!(edge_mask(&open_sea, 0, 0), EDGE_NE);
+        assert_eq!(edge_mask(&open_sea, 1, 0), EDGE_N);
+    }
+
+    #[test]
+    fn test_water_gets_deeper_away_from_the_shore() {
+        let terrain_map = create_lake(9);
+        let distances = edge_distances(&terrain_map, WATER);
+        assert_eq!(distances[0][4], 0, "Land is the shore");
+        assert_eq!(distances[1][4], 1);
+        assert_eq!(distances[2][2], 2, "Diagonal steps count as one");
+        assert_eq!(distances[4][4], 4);
+
+        assert_eq!(depth_level(1, 0.0), 0, "Water along the shore is the shallowest");
+        assert_eq!(depth_level(1, 1.0), 0, "However the noise falls");
+        assert!(depth_level(3, 0.0) < depth_level(5, 0.0));
+        assert_eq!(depth_level(7, 0.0), 7, "The deepest from DEEP_TILES out");
+        assert_eq!(depth_level(u32::MAX, -1.0), 5, "Noise can lift even open sea out of the deepest");
+        assert!(depth_level(4, -1.0) < depth_level(4, 0.0) && depth_level(4, 0.0) < depth_level(4, 1.0));
+    }
+
+    #[test]
+    fn test_edge_distances_within_a_window_match_the_whole_map() {
+        let terrain_map = create_lake(20);
+        let whole = edge_distances(&terrain_map, WATER);
+        let window = edge_distances_within(&terrain_map, WATER, (0, 5), (8, 15));
+        assert_eq!(window.len(), 8);
+        assert_eq!(window[0].len(), 10);
+        for x in 0..5 {
+            for y in 0..10 {
+                assert_eq!(window[x][y], whole[x][y + 5], "The shore on the left is nearer these than any outside the window");
+            }
+        }
+        let open_water = edge_distances_within(&terrain_map, WATER, (5, 5), (10, 10));
+        assert_eq!(open_water[2][2], u32::MAX, "No land inside the window at all");
+    }
+
+    #[test]
+    fn test_terrain_edits_redraw_the_water() {
+        let mut app = setup_effect_app(create_lake(5));
+
+        // Every tile is new water to a world with no overlays yet
+        let lake: Vec<_> = (1..4).flat_map(|x| (1..4).map(move |y| (x, y, WATER))).collect();
+        change_tiles(&mut app, &lake);
+        assert_eq!(app.world_mut().query::<&TerrainEffectTile>().iter(app.world()).count(), 9,
+                   "Dirt has no effect, so only the water is drawn over");
+        assert_eq!(app.world().resource::<TerrainEffectOverlays>().tiles[&(2, 2)].1, (WATER, 0, 1));
+        let corner = material_of(&app, (1, 1));
+        assert_eq!(corner.edge_mask, EDGE_W | EDGE_SW | EDGE_S | EDGE_SE | EDGE_NW);
+        assert_eq!(corner.kind, 0);
+
+        // Filling in the middle of the lake brings the shore to every tile left
+        let (middle, _) = app.world().resource::<TerrainEffectOverlays>().tiles[&(2, 2)];
+        change_tiles(&mut app, &[(2, 2, DIRT)]);
+        assert!(app.world().get_entity(middle).is_err(), "No water left to draw over the filled tile");
+        let overlays = app.world().resource::<TerrainEffectOverlays>();
+        assert_eq!(overlays.tiles.len(), 8);
+        assert_eq!(overlays.tiles[&(2, 1)].1.1 & EDGE_N, EDGE_N);
+        assert_eq!(material_of(&app, (2, 3)).edge_mask, EDGE_NW | EDGE_N | EDGE_NE | EDGE_S,
+                   "The material is swapped for one with the new shore");
+    }
+
+    #[test]
+    fn test_each_ground_gets_its_own_effect() {
+        let mut app = setup_effect_app(create_lake(5));
+        let lake: Vec<_> = (1..4).flat_map(|x| (1..4).map(move |y| (x, y, WATER))).collect();
+        change_tiles(&mut app, &lake);
+
+        // Grass sown in the middle of the lake sways rather than shimmers
+        let (water_overlay, _) = app.world().resource::<TerrainEffectOverlays>().tiles[&(2, 2)];
+        change_tiles(&mut app, &[(2, 2, GRASS)]);
+        assert!(app.world().get_entity(water_overlay).is_err());
+        assert_eq!(app.world().resource::<TerrainEffectOverlays>().tiles[&(2, 2)].1.0, GRASS);
+        let grass = material_of(&app, (2, 2));
+        assert_eq!(grass.kind, 2);
+        assert_eq!(grass.strength, 0.6);
+        assert_eq!(grass.edge_mask, 0xff, "Water all around");
+        assert_eq!(material_of(&app, (1, 1)).strength, 1.0);
+
+        // Swapping ground for some without an effect only takes overlays away
+        change_tiles(&mut app, &[(2, 2, DIRT)]);
+        assert!(!app.world().resource::<TerrainEffectOverlays>().tiles.contains_key(&(2, 2)));
+        let before = app.world().resource::<TerrainEffectOverlays>().tiles.len();
+        change_tiles(&mut app, &[(0, 0, STONE)]);
+        assert_eq!(app.world().resource::<TerrainEffectOverlays>().tiles.len(), before);
+    }
+
+    #[test]
+    fn test_effects_are_read_and_checked() {
+        let mut grounds: HashMap<String, GroundConfig> = serde_yaml::from_str(r#"
+lava:
+  sprite: "tileset::grounds::lava"
+  passable: false
+  effect: { kind: lava, speed: 0.5, color: [255, 40, 0] }
+  height_min: 0.0
+  height_max: 1.0
+"#).expect("Failed to parse grounds");
+        let effect = grounds["lava"].effect.clone().unwrap();
+        assert_eq!(effect.kind, TerrainEffectKind::Lava);
+        assert_eq!(effect.speed, 0.5);
+        assert_eq!(effect.strength, 1.0, "Drawn at full strength unless it says otherwise");
+        assert_eq!(effect.color, Some([255, 40, 0]));
+
+        let problems = |grounds: &HashMap<String, GroundConfig>| -> Vec<String> {
+            check_grounds(grounds, &HashMap::new(), &StatusEffectConfig::default(), Path::new("assets"))
+                .iter()
+                .map(|problem| problem.to_string())
+                .filter(|problem| problem.contains(".effect."))
+                .collect()
+        };
+        assert!(problems(&grounds).is_empty());
+        let lava = grounds.get_mut("lava").unwrap().effect.as_mut().unwrap();
+        lava.strength = 1.5;
+        lava.speed = -1.0;
+        assert_eq!(problems(&grounds), vec![
+            "grounds.yaml: lava.effect.strength: 1.5 is outside 0 to 1",
+            "grounds.yaml: lava.effect.speed: -1 is negative",
+        ]);
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::infusion::TerrainInfluence;
+    use crate::systems::input::{tile_center, world_to_tile};
+    use crate::systems::tile_tooltip::describe_tile;
+    use crate::tests::{create_test_ground_configs, create_test_terrain_map};
+
+    #[test]
+    fn test_world_positions_map_to_tiles_and_back() {
+        let config = GameConfig { map_width: 10, map_height: 10, tile_size: 32.0, ..GameConfig::default() };
+        assert_eq!(world_to_tile(Vec2::new(-160.0, -160.0), &config), (0, 0));
+        assert_eq!(world_to_tile(Vec2::new(1.0, -1.0), &config), (5, 4));
+        assert_eq!(world_to_tile(Vec2::new(-170.0, 0.0), &config), (-1, 5), "Off the map gives tiles outside it");
+        assert_eq!(tile_center((5, 4), &config), Vec2::new(16.0, -16.0));
+        assert_eq!(world_to_tile(tile_center((7, 2), &config), &config), (7, 2));
+    }
+
+    #[test]
+    fn test_tooltip_describes_ground_and_occupants() {
+        let terrain_map = create_test_terrain_map(9, 9, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let influence = TerrainInfluence::default();
+
+        let grass = describe_tile((0, 0), &terrain_map, &ground_configs, &influence, &["wolf", "rabbit"]);
+        assert_eq!(grass, "Tile (0, 0)\nGround: grass\nPassable: yes\nMove cost: 1 (1.4 diagonally)\nPawns: wolf, rabbit");
+
+        let stone = describe_tile((4, 4), &terrain_map, &ground_configs, &influence, &[]);
+        assert!(stone.contains("Ground: stone\nPassable: no\nMove cost: -\nPawns: none"), "{}", stone);
+
+        let water = describe_tile((1, 4), &terrain_map, &ground_configs, &influence, &[]);
+        assert!(water.contains("Ground: water\nPassable: swimmers only"), "{}", water);
+    }
+
+    #[test]
+    fn test_tooltip_shows_infusion_progress() {
+        let terrain_map = create_test_terrain_map(9, 9, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let mut influence = TerrainInfluence::default();
+        let water = ground_configs.terrain_mapping["water"];
+        let dirt = ground_configs.terrain_mapping["dirt"];
+        influence.accumulate((0, 0), water, 40.0);
+        influence.accumulate((0, 0), dirt, 25.0);
+
+        let infused = describe_tile((0, 0), &terrain_map, &ground_configs, &influence, &[]);
+        assert!(infused.ends_with("Pawns: none\nInfusing: dirt 25%\nInfusing: water 40%"), "{}", infused);
+
+        let untouched = describe_tile((1, 1), &terrain_map, &ground_configs, &influence, &[]);
+        assert!(!untouched.contains("Infusing"), "{}", untouched);
+    }
+}
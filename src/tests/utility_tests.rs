@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::resources::GameConfig;
+    use crate::systems::needs::needs_behaviour_switching_system;
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior};
+    use crate::systems::pawn_config::{PawnConfig, ResponseCurve};
+    use crate::systems::perception::{Threatened, threat_perception_system};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::utility::{UtilityInputs, best_utility_state, utility_behaviour_system};
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const UTILITY_PAWNS_YAML: &str = r#"
+deer:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 30
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    idle: null
+    looking_for_food: eat
+    hunted: flee
+  eats:
+    pawns: []
+  perception:
+    detection_radius: 5
+  utility:
+    hysteresis: 0.15
+    options:
+      - state: idle
+        weight: 0.3
+      - state: looking_for_food
+        considerations:
+          - input: hunger
+            curve:
+              power:
+                exponent: 2
+      - state: hunted
+        considerations:
+          - input: threat
+            curve:
+              logistic:
+                midpoint: 0.3
+                steepness: 12
+          - input: health
+            curve:
+              step:
+                threshold: 0.2
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 10
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: [small, animal]
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+
+    fn create_utility_pawn_config() -> PawnConfig {
+        serde_yaml::from_str(UTILITY_PAWNS_YAML).expect("Utility config should parse")
+    }
+
+    fn setup_utility_app() -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_utility_pawn_config());
+        app.insert_resource(TerrainMap::new(20, 20, TILE_SIZE));
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_systems(Update, utility_behaviour_system);
+        app
+    }
+
+    fn spawn_at(app: &mut App, pawn_type: &str, tile: (i32, i32)) -> Entity {
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(tile.0, tile.1);
+        app.world_mut().spawn((
+            Pawn::new(pawn_type.to_string()),
+            CurrentBehavior { state: "idle".to_string() },
+            Health::new(30),
+            Endurance::new(10),
+            Transform::from_xyz(x, y, 100.0),
+        )).id()
+    }
+
+    fn state(app: &App, entity: Entity) -> String {
+        app.world().get::<CurrentBehavior>(entity).unwrap().state.clone()
+    }
+
+    #[test]
+    fn test_response_curves() {
+        let linear = ResponseCurve::default();
+        assert_eq!(linear.evaluate(0.4), 0.4);
+        assert_eq!(linear.evaluate(1.5), 1.0, "Scores are clamped");
+        let inverted = ResponseCurve::Linear { slope: -1.0, intercept: 1.0 };
+        assert!((inverted.evaluate(0.25) - 0.75).abs() < 0.001);
+        assert_eq!(ResponseCurve::Power { exponent: 2.0 }.evaluate(0.5), 0.25);
+
+        let logistic = ResponseCurve::Logistic { midpoint: 0.5, steepness: 10.0 };
+        assert!((logistic.evaluate(0.5) - 0.5).abs() < 0.001);
+        assert!(logistic.evaluate(0.1) < 0.05);
+        assert!(logistic.evaluate(0.9) > 0.95);
+
+        let step = ResponseCurve::Step { threshold: 0.3 };
+        assert_eq!(step.evaluate(0.29), 0.0);
+        assert_eq!(step.evaluate(0.3), 1.0);
+    }
+
+    #[test]
+    fn test_highest_score_wins_with_hysteresis() {
+        let config = create_utility_pawn_config();
+        let utility = config.get_pawn_definition("deer").unwrap().utility.clone().unwrap();
+        let inputs = |hunger: f32| UtilityInputs { hunger, health: 1.0, ..UtilityInputs::default() };
+
+        assert_eq!(best_utility_state(&utility, &inputs(0.3), "idle").unwrap().0, "idle");
+        assert_eq!(best_utility_state(&utility, &inputs(0.8), "idle").unwrap().0, "looking_for_food");
+        assert_eq!(best_utility_state(&utility, &inputs(0.8), "looking_for_food").unwrap().0, "looking_for_food");
+        assert_eq!(best_utility_state(&utility, &inputs(0.5), "idle").unwrap().0, "idle",
+                   "0.25 for food doesn't beat idle's 0.3 plus its hysteresis");
+        assert_eq!(best_utility_state(&utility, &inputs(0.5), "looking_for_food").unwrap().0, "looking_for_food",
+                   "Nor does idle's 0.3 beat an already hungry pawn's 0.25 plus hysteresis");
+    }
+
+    #[test]
+    fn test_pawns_switch_to_the_best_scoring_state() {
+        let mut app = setup_utility_app();
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+
+        app.update();
+        assert_eq!(state(&app, deer), "idle");
+
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 2.0;
+        app.update();
+        assert_eq!(state(&app, deer), "looking_for_food");
+
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 5.0;
+        app.update();
+        assert_eq!(state(&app, deer), "looking_for_food", "Hysteresis keeps it eating a little longer");
+
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 9.0;
+        app.update();
+        assert_eq!(state(&app, deer), "idle");
+
+        app.world_mut().get_mut::<CurrentBehavior>(deer).unwrap().state = "controlled".to_string();
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 0.0;
+        app.update();
+        assert_eq!(state(&app, deer), "controlled", "Player-controlled pawns are left alone");
+    }
+
+    #[test]
+    fn test_threat_proximity_drives_hunted_state() {
+        let mut app = setup_utility_app();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(300)));
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, (
+            threat_perception_system.before(utility_behaviour_system),
+            needs_behaviour_switching_system.after(utility_behaviour_system),
+        ));
+        let deer = spawn_at(&mut app, "deer", (5, 5));
+        let wolf = spawn_at(&mut app, "wolf", (9, 5));
+
+        app.update();
+        app.update();
+        assert!(app.world().get::<Threatened>(deer).is_some(), "Perception still flags the threat");
+        assert_eq!(state(&app, deer), "idle", "A predator 4 tiles off on a 5 tile radius isn't worth running from yet");
+
+        let (x, y) = app.world().resource::<TerrainMap>().tile_to_world_coords(7, 5);
+        app.world_mut().get_mut::<Transform>(wolf).unwrap().translation = Vec3::new(x, y, 100.0);
+        app.world_mut().get_mut::<Endurance>(deer).unwrap().current = 1.0;
+        app.update();
+        assert_eq!(state(&app, deer), "hunted", "Closer predators outscore even a hungry stomach");
+
+        app.world_mut().get_mut::<Health>(deer).unwrap().current = 3.0;
+        app.update();
+        assert_eq!(state(&app, deer), "looking_for_food", "Too badly hurt to run, by this deer's curves");
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::seasons::{Season, Seasons};
+    use crate::systems::sim_tick::{SimTick, SimulationTick, TickPeriod};
+    use crate::systems::vegetation::{VegetationGrowth, RegrowingTile, vegetation_growth_system};
+    use crate::systems::world_gen::{TerrainMap, TerrainChanges};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+    const STONE: usize = 2;
+
+    fn create_grass_map() -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(10, 10, 16.0);
+        for x in 0..10 {
+            for y in 0..10 {
+                terrain_map.set_tile(x, y, GRASS);
+            }
+        }
+        terrain_map
+    }
+
+    fn setup_growth_app(tick: u64) -> App {
+        let mut app = setup_test_app();
+        app.insert_resource(create_grass_map());
+        app.insert_resource(TerrainChanges::default());
+        app.insert_resource(VegetationGrowth::default());
+        let mut sim_tick = SimTick::new(2);
+        sim_tick.tick = tick;
+        app.insert_resource(sim_tick);
+        app.add_event::<SimulationTick>();
+        app.add_systems(Update, vegetation_growth_system);
+        app
+    }
+
+    fn advance_to(app: &mut App, tick: u64) {
+        app.world_mut().resource_mut::<SimTick>().tick = tick;
+        app.world_mut().send_event(SimulationTick { tick, period: TickPeriod::Second });
+        app.update();
+    }
+
+    #[test]
+    fn test_regrow_time_comes_from_ground_config() {
+        let ground_configs = create_test_ground_configs();
+        assert_eq!(ground_configs.regrow_secs(GRASS), Some(60));
+        assert_eq!(ground_configs.regrow_secs(DIRT), None, "Only vegetation grows back");
+    }
+
+    #[test]
+    fn test_clearing_only_tracks_vegetation() {
+        let ground_configs = create_test_ground_configs();
+        let mut terrain_map = create_grass_map();
+        terrain_map.set_tile(5, 5, DIRT);
+        let mut terrain_changes = TerrainChanges::default();
+        let mut vegetation = VegetationGrowth::default();
+        let sim_tick = SimTick { tick: 100, ..SimTick::new(2) };
+
+        assert!(vegetation.clear_tile((1, 1), DIRT, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick));
+        assert!(vegetation.clear_tile((5, 5), STONE, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick));
+        assert!(!vegetation.clear_tile((1, 1), DIRT, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick),
+                "Clearing to the same ground is a no-op");
+        assert!(!vegetation.clear_tile((10, 0), DIRT, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick));
+
+        assert_eq!(terrain_map.tiles[1][1], DIRT);
+        assert_eq!(terrain_changes.changed_tiles, vec![(1, 1, DIRT), (5, 5, STONE)]);
+        assert_eq!(vegetation.tiles.len(), 1, "Dirt turned to stone never grows back");
+        assert_eq!(vegetation.tiles[0].grows_back, GRASS);
+        assert_eq!(vegetation.tiles[0].regrow_tick, 100 + 60 * 2);
+
+        // Cleared again before growing back: still grass underneath, on the original schedule
+        assert!(vegetation.clear_tile((1, 1), STONE, &mut terrain_map, &mut terrain_changes, &ground_configs, &sim_tick));
+        assert_eq!(vegetation.tiles.len(), 1);
+        assert_eq!(vegetation.tiles[0].grows_back, GRASS);
+        assert_eq!(vegetation.tiles[0].cleared_to, STONE);
+        assert_eq!(vegetation.tiles[0].regrow_tick, 100 + 60 * 2);
+    }
+
+    #[test]
+    fn test_vegetation_regrows_on_schedule() {
+        let mut app = setup_growth_app(0);
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(1, 1, DIRT);
+        app.world_mut().resource_mut::<VegetationGrowth>().tiles.push(RegrowingTile {
+            tile: (1, 1), grows_back: GRASS, cleared_to: DIRT, regrow_tick: 40,
+        });
+
+        app.update();
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[1][1], DIRT, "Nothing happens between second boundaries");
+
+        advance_to(&mut app, 20);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[1][1], DIRT, "Too early to grow back");
+
+        advance_to(&mut app, 40);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[1][1], GRASS);
+        assert!(app.world().resource::<TerrainChanges>().changed_tiles.contains(&(1, 1, GRASS)),
+                "Regrowth goes through TerrainChanges so visuals and caches catch up");
+        assert!(app.world().resource::<VegetationGrowth>().tiles.is_empty());
+    }
+
+    #[test]
+    fn test_changed_tiles_do_not_regrow() {
+        let mut app = setup_growth_app(0);
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(2, 2, STONE);
+        app.world_mut().resource_mut::<VegetationGrowth>().tiles.push(RegrowingTile {
+            tile: (2, 2), grows_back: GRASS, cleared_to: DIRT, regrow_tick: 40,
+        });
+
+        advance_to(&mut app, 40);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[2][2], STONE, "Tiles changed since clearing stay as they are");
+        assert!(app.world().resource::<VegetationGrowth>().tiles.is_empty());
+    }
+
+    #[test]
+    fn test_seasons_speed_up_and_slow_down_regrowth() {
+        let mut app = setup_growth_app(0);
+        app.insert_resource(Seasons { current: Some(Season::Spring), regrowth: 2.0, ..Default::default() });
+        app.world_mut().resource_mut::<VegetationGrowth>().tiles.push(RegrowingTile {
+            tile: (1, 1), grows_back: GRASS, cleared_to: DIRT, regrow_tick: 40,
+        });
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(1, 1, DIRT);
+
+        for tick in [10, 20, 30] {
+            advance_to(&mut app, tick);
+        }
+        assert_eq!(app.world().resource::<VegetationGrowth>().tiles[0].regrow_tick, 34, "A second's extra growth for every second of spring");
+        advance_to(&mut app, 34);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[1][1], GRASS, "Grown back ahead of schedule");
+
+        app.insert_resource(Seasons { current: Some(Season::Winter), regrowth: 0.5, ..Default::default() });
+        app.world_mut().resource_mut::<TerrainMap>().set_tile(2, 2, DIRT);
+        app.world_mut().resource_mut::<VegetationGrowth>().tiles.push(RegrowingTile {
+            tile: (2, 2), grows_back: GRASS, cleared_to: DIRT, regrow_tick: 40,
+        });
+        advance_to(&mut app, 40);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[2][2], DIRT, "Winter puts regrowth off");
+        advance_to(&mut app, 42);
+        assert_eq!(app.world().resource::<TerrainMap>().tiles[2][2], GRASS);
+    }
+}
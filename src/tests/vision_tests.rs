@@ -0,0 +1,170 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::ai::{HuntSoloAI, hunt_solo_ai_system};
+    use crate::systems::pawn::{Pawn, Health, Endurance, CurrentBehavior, Size};
+    use crate::systems::pawn_config::{PawnConfig, VisionConfig};
+    use crate::systems::perception::{Vision, update_vision_facing};
+    use crate::systems::spatial_grid::SpatialGridPlugin;
+    use crate::systems::world_gen::TerrainMap;
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const HUNTER_PAWNS_YAML: &str = r#"
+wolf:
+  sprite: "tileset::pawns::wolf"
+  tags: [medium, animal]
+  move_speed: 120.0
+  max_health: 100
+  max_endurance: 60
+  defence: 10
+  strength: 30
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  behaviours:
+    looking_for_food: hunt_solo
+  eats:
+    pawns: [small, animal]
+  vision:
+    range: 12
+rabbit:
+  sprite: "tileset::pawns::rabbit"
+  tags: [small, animal]
+  move_speed: 100.0
+  max_health: 25
+  max_endurance: 10
+  defence: 5
+  strength: 5
+  attack_speed: 3
+  reach: 1
+  size: 1
+  spawn_count: 1
+  eats:
+    pawns: []
+"#;
+
+    const TILE_SIZE: f32 = 16.0;
+    const STONE: usize = 2;
+    const WATER: usize = 3;
+
+    /// Dirt map with a stone ridge down column 10, open at the top
+    fn create_ridge_terrain() -> TerrainMap {
+        let mut terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        for y in 0..15 {
+            terrain_map.set_tile(10, y, STONE);
+        }
+        terrain_map
+    }
+
+    fn world(terrain_map: &TerrainMap, tile: (i32, i32)) -> Vec2 {
+        let (x, y) = terrain_map.tile_to_world_coords(tile.0, tile.1);
+        Vec2::new(x, y)
+    }
+
+    #[test]
+    fn test_line_of_sight_raycasts_through_tiles() {
+        let terrain_map = create_ridge_terrain();
+        let ground_configs = create_test_ground_configs();
+        let sight = |a: (i32, i32), b: (i32, i32)| {
+            let (a, b) = (world(&terrain_map, a), world(&terrain_map, b));
+            terrain_map.has_line_of_sight((a.x, a.y), (b.x, b.y), &ground_configs)
+        };
+
+        assert!(!sight((5, 5), (14, 5)), "Stone blocks sight");
+        assert!(!sight((5, 2), (14, 9)), "Diagonals are blocked too");
+        assert!(sight((5, 17), (14, 17)), "Clear over the top of the ridge");
+        assert!(sight((5, 5), (9, 14)));
+        assert!(sight((5, 5), (5, 5)));
+        assert!(sight((9, 5), (10, 5)), "The tiles at either end never block");
+
+        let mut lake = TerrainMap::new(20, 20, TILE_SIZE);
+        for y in 0..20 {
+            lake.set_tile(10, y, WATER);
+        }
+        let (a, b) = (world(&lake, (5, 5)), world(&lake, (14, 5)));
+        assert!(lake.has_line_of_sight((a.x, a.y), (b.x, b.y), &ground_configs), "Water is impassable but see-through");
+    }
+
+    #[test]
+    fn test_vision_range_and_field_of_view() {
+        let terrain_map = TerrainMap::new(20, 20, TILE_SIZE);
+        let ground_configs = create_test_ground_configs();
+        let all_round = Vision::new(&VisionConfig { range: 5, fov: None, memory: 10.0 });
+        let mut forward = Vision::new(&VisionConfig { range: 5, fov: Some(90.0), memory: 10.0 });
+        let eye = world(&terrain_map, (10, 10));
+
+        assert!(all_round.can_see(eye, world(&terrain_map, (6, 10)), &terrain_map, &ground_configs));
+        assert!(!all_round.can_see(eye, world(&terrain_map, (4, 10)), &terrain_map, &ground_configs), "Out of range");
+
+        assert!(forward.can_see(eye, world(&terrain_map, (13, 11)), &terrain_map, &ground_configs), "Ahead, facing east");
+        assert!(!forward.can_see(eye, world(&terrain_map, (7, 10)), &terrain_map, &ground_configs), "Behind");
+        assert!(!forward.can_see(eye, world(&terrain_map, (10, 13)), &terrain_map, &ground_configs), "Off to the side");
+        forward.facing = Vec2::NEG_X;
+        assert!(forward.can_see(eye, world(&terrain_map, (7, 10)), &terrain_map, &ground_configs));
+    }
+
+    #[test]
+    fn test_pawns_face_the_way_they_move() {
+        let mut app = setup_test_app();
+        app.add_systems(Update, update_vision_facing);
+        let pawn = app.world_mut().spawn((
+            Vision::new(&VisionConfig { range: 5, fov: Some(90.0), memory: 10.0 }),
+            Transform::from_xyz(0.0, 0.0, 100.0),
+        )).id();
+
+        app.update();
+        assert_eq!(app.world().get::<Vision>(pawn).unwrap().facing, Vec2::X, "Standing still keeps the default facing");
+
+        app.world_mut().get_mut::<Transform>(pawn).unwrap().translation = Vec3::new(0.0, -10.0, 100.0);
+        app.update();
+        assert_eq!(app.world().get::<Vision>(pawn).unwrap().facing, Vec2::NEG_Y);
+
+        app.update();
+        assert_eq!(app.world().get::<Vision>(pawn).unwrap().facing, Vec2::NEG_Y, "Stopping keeps the last facing");
+    }
+
+    #[test]
+    fn test_hunters_cannot_see_prey_through_stone() {
+        let mut app = setup_test_app();
+        let config: PawnConfig = serde_yaml::from_str(HUNTER_PAWNS_YAML).expect("Hunter config should parse");
+        let vision = config.get_pawn_definition("wolf").unwrap().vision.clone().unwrap();
+        let terrain_map = create_ridge_terrain();
+        let hunter_pos = world(&terrain_map, (5, 5));
+        let hidden_pos = world(&terrain_map, (14, 5));
+        let visible_pos = world(&terrain_map, (5, 16));
+        app.insert_resource(config);
+        app.insert_resource(terrain_map);
+        app.insert_resource(create_test_ground_configs());
+        app.insert_resource(GameConfig { tile_size: TILE_SIZE, ..GameConfig::default() });
+        app.add_plugins(SpatialGridPlugin::default());
+        app.add_systems(Update, hunt_solo_ai_system);
+
+        let mut hunt_ai = HuntSoloAI::new();
+        hunt_ai.search_timer = 2.0;
+        let hunter = app.world_mut().spawn((
+            Pawn::new("wolf".to_string()),
+            CurrentBehavior { state: "looking_for_food".to_string() },
+            Health::new(100),
+            Endurance::new(60),
+            Size { value: 1.0 },
+            Vision::new(&vision),
+            hunt_ai,
+            Transform::from_translation(hunter_pos.extend(100.0)),
+        )).id();
+        let spawn_rabbit = |app: &mut App, position: Vec2| {
+            app.world_mut().spawn((
+                Pawn::new("rabbit".to_string()),
+                Health::new(25),
+                Transform::from_translation(position.extend(100.0)),
+            )).id()
+        };
+        spawn_rabbit(&mut app, hidden_pos);
+        let visible = spawn_rabbit(&mut app, visible_pos);
+
+        app.update();
+        assert_eq!(app.world().get::<HuntSoloAI>(hunter).unwrap().target_entity, Some(visible),
+                   "The closer rabbit is hidden behind the ridge");
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use std::collections::BTreeMap;
+    use crate::resources::GameConfig;
+    use crate::systems::config_validation::check_weather;
+    use crate::systems::pawn_config::VisionConfig;
+    use crate::systems::perception::Vision;
+    use crate::systems::sim_rng::SimRng;
+    use crate::systems::sim_tick::{SimTick, SimulationTick, TickPeriod};
+    use crate::systems::weather::{Weather, WeatherKind, WeatherSettings, weather_system, apply_weather_to_vision};
+    use crate::tests::{setup_test_app, create_test_ground_configs};
+
+    const DIRT: usize = 0;
+    const GRASS: usize = 1;
+
+    fn settings_for(biome: &[(WeatherKind, u32)]) -> WeatherSettings {
+        WeatherSettings {
+            biome: "test".to_string(),
+            biomes: BTreeMap::from([("test".to_string(), biome.iter().copied().collect())]),
+            ..Default::default()
+        }
+    }
+
+    fn setup_weather_app(settings: WeatherSettings) -> App {
+        let mut app = setup_test_app();
+        let mut config = GameConfig::default();
+        config.weather = settings;
+        app.insert_resource(config);
+        app.insert_resource(SimTick::new(2));
+        app.add_event::<SimulationTick>();
+        app.add_systems(Update, (weather_system, apply_weather_to_vision.after(weather_system)));
+        app
+    }
+
+    fn advance_to(app: &mut App, tick: u64) {
+        app.world_mut().resource_mut::<SimTick>().tick = tick;
+        app.world_mut().send_event(SimulationTick { tick, period: TickPeriod::Second });
+        app.update();
+    }
+
+    #[test]
+    fn test_weather_is_picked_by_the_biomes_frequencies() {
+        let mut rng = SimRng::from_seed(7);
+        let snowy = settings_for(&[(WeatherKind::Snow, 1)]);
+        assert!((0..20).all(|_| snowy.roll_weather(&mut rng) == WeatherKind::Snow));
+
+        let unknown = WeatherSettings { biome: "swamp".to_string(), ..Default::default() };
+        assert_eq!(unknown.roll_weather(&mut rng), WeatherKind::Clear, "A biome with no weather listed stays clear");
+        let calm = settings_for(&[(WeatherKind::Rain, 0)]);
+        assert_eq!(calm.roll_weather(&mut rng), WeatherKind::Clear);
+
+        let mixed = settings_for(&[(WeatherKind::Clear, 3), (WeatherKind::Rain, 1)]);
+        let rainy = (0..2000).filter(|_| mixed.roll_weather(&mut rng) == WeatherKind::Rain).count();
+        assert!((350..650).contains(&rainy), "About a quarter of spells should be rain, got {} of 2000", rainy);
+
+        let settings = WeatherSettings { min_secs: 30, max_secs: 40, ..Default::default() };
+        assert!((0..50).all(|_| (30..=40).contains(&settings.roll_duration(&mut rng))));
+    }
+
+    #[test]
+    fn test_weather_changes_when_the_spell_runs_out() {
+        let settings = WeatherSettings { min_secs: 10, max_secs: 10, ..settings_for(&[(WeatherKind::Rain, 1)]) };
+        let mut app = setup_weather_app(settings);
+
+        advance_to(&mut app, 2);
+        let weather = app.world().resource::<Weather>().clone();
+        assert_eq!(weather.kind, WeatherKind::Rain, "A new world rolls its weather on the first second");
+        assert_eq!(weather.changes_at, 2 + 10 * 2);
+
+        app.world_mut().resource_mut::<GameConfig>().weather = WeatherSettings { min_secs: 10, max_secs: 10, ..settings_for(&[(WeatherKind::Fog, 1)]) };
+        advance_to(&mut app, 20);
+        assert_eq!(app.world().resource::<Weather>().kind, WeatherKind::Rain, "Still raining until the spell is over");
+        advance_to(&mut app, 22);
+        assert_eq!(app.world().resource::<Weather>().kind, WeatherKind::Fog);
+        assert_eq!(app.world().resource::<Weather>().changes_at, 42);
+    }
+
+    #[test]
+    fn test_snow_settles_and_melts() {
+        let settings = WeatherSettings { min_secs: 100, max_secs: 100, snow_cover_secs: 4, ..settings_for(&[(WeatherKind::Snow, 1)]) };
+        let mut app = setup_weather_app(settings);
+
+        for second in 1..=2 {
+            advance_to(&mut app, second * 2);
+        }
+        assert!((app.world().resource::<Weather>().snow_cover - 0.5).abs() < 1e-5, "Half covered after half of snow_cover_secs");
+        for second in 3..=6 {
+            advance_to(&mut app, second * 2);
+        }
+        assert_eq!(app.world().resource::<Weather>().snow_cover, 1.0, "Cover stops at fully buried");
+
+        app.world_mut().resource_mut::<Weather>().kind = WeatherKind::Clear;
+        advance_to(&mut app, 14);
+        assert!((app.world().resource::<Weather>().snow_cover - 0.75).abs() < 1e-5, "Melting once the snow stops");
+        for second in 8..=12 {
+            advance_to(&mut app, second * 2);
+        }
+        assert_eq!(app.world().resource::<Weather>().snow_cover, 0.0);
+    }
+
+    #[test]
+    fn test_rain_turns_dirt_to_mud() {
+        let ground_configs = create_test_ground_configs();
+        assert_eq!(ground_configs.rain_speed(DIRT), 0.7);
+        assert_eq!(ground_configs.rain_speed(GRASS), 1.0, "Grounds without rain_speed aren't slowed");
+        assert!(ground_configs.takes_snow(GRASS));
+        assert!(!ground_configs.takes_snow(DIRT));
+
+        let weather = |kind| Weather { kind, ..Default::default() };
+        assert_eq!(weather(WeatherKind::Rain).speed_multiplier(DIRT, &ground_configs), 0.7);
+        assert_eq!(weather(WeatherKind::Storm).speed_multiplier(DIRT, &ground_configs), 0.7);
+        assert_eq!(weather(WeatherKind::Rain).speed_multiplier(GRASS, &ground_configs), 1.0);
+        assert_eq!(weather(WeatherKind::Clear).speed_multiplier(DIRT, &ground_configs), 1.0);
+        assert_eq!(weather(WeatherKind::Snow).speed_multiplier(DIRT, &ground_configs), 1.0);
+    }
+
+    #[test]
+    fn test_fog_closes_in_on_vision() {
+        let settings = WeatherSettings { min_secs: 5, max_secs: 5, fog_vision: 0.5, ..settings_for(&[(WeatherKind::Fog, 1)]) };
+        let mut app = setup_weather_app(settings);
+        let watcher = app.world_mut().spawn(Vision::new(&VisionConfig { range: 10, fov: None, memory: 10.0 })).id();
+
+        advance_to(&mut app, 2);
+        assert_eq!(app.world().get::<Vision>(watcher).unwrap().range, 5.0);
+
+        app.world_mut().resource_mut::<GameConfig>().weather = WeatherSettings { min_secs: 5, max_secs: 5, ..settings_for(&[(WeatherKind::Clear, 1)]) };
+        advance_to(&mut app, 12);
+        assert_eq!(app.world().get::<Vision>(watcher).unwrap().range, 10.0, "Full range back once the fog lifts");
+    }
+
+    #[test]
+    fn test_weather_settings_are_read_and_checked() {
+        let config = GameConfig::from_yaml(r#"
+world: { map_width: 32, map_height: 32, tile_size: 16.0 }
+camera: { movement_speed: 200.0, zoom_min: 0.1, zoom_max: 10.0, mouse_sensitivity: 1.0 }
+game: { window_title: "Test", target_fps: 60, show_fps: false }
+weather:
+  biome: tundra
+  fog_vision: 0.25
+  biomes:
+    tundra: { snow: 3, fog: 1 }
+"#).expect("Failed to parse settings");
+        assert_eq!(config.weather.biome, "tundra");
+        assert_eq!(config.weather.fog_vision, 0.25);
+        assert_eq!(config.weather.min_secs, WeatherSettings::default().min_secs, "Left out settings keep their defaults");
+        assert_eq!(config.weather.biomes["tundra"].get(&WeatherKind::Snow), Some(&3));
+        assert!(check_weather(&config.weather).is_empty());
+        assert!(check_weather(&WeatherSettings::default()).is_empty());
+
+        let broken = WeatherSettings { biome: "swamp".to_string(), min_secs: 60, max_secs: 30, ..Default::default() };
+        let found: Vec<String> = check_weather(&broken).iter().map(|problem| problem.to_string()).collect();
+        assert_eq!(found, vec![
+            "settings.yaml: weather.biome: no biome named swamp in weather.biomes (there's arid, temperate, tundra)",
+            "settings.yaml: weather.max_secs: 30 is below min_secs 60",
+        ]);
+    }
+}
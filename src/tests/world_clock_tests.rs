@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::systems::ai::BehaviourContext;
+    use crate::systems::lighting::night_tint_alpha;
+    use crate::systems::pawn_config::{ActivitySchedule, BehaviourCondition};
+    use crate::systems::sim_tick::{SimTick, TickPeriod, SECONDS_PER_DAY};
+    use crate::systems::world_clock::{WorldClock, DayPhase, update_world_clock, DAWN, DUSK, TWILIGHT};
+    use crate::tests::setup_test_app;
+
+    fn clock_at_seconds(seconds: u64) -> WorldClock {
+        let mut sim_tick = SimTick::new(2);
+        sim_tick.tick = seconds * 2;
+        WorldClock::from_tick(&sim_tick)
+    }
+
+    #[test]
+    fn test_new_world_starts_at_dawn() {
+        let clock = WorldClock::default();
+        assert_eq!(clock.day, 0);
+        assert_eq!(clock.time_of_day, DAWN);
+        assert_eq!(clock.phase(), DayPhase::Day);
+        assert_eq!(clock.hour_minute(), (6, 0));
+    }
+
+    #[test]
+    fn test_clock_cycles_day_and_night() {
+        let day_length = ((DUSK - DAWN) * SECONDS_PER_DAY as f32) as u64;
+        assert_eq!(clock_at_seconds(day_length - 1).phase(), DayPhase::Day);
+        assert_eq!(clock_at_seconds(day_length).phase(), DayPhase::Night);
+        assert_eq!(clock_at_seconds(day_length).hour_minute(), (18, 0));
+
+        let next_dawn = clock_at_seconds(SECONDS_PER_DAY);
+        assert_eq!(next_dawn.day, 1);
+        assert_eq!(next_dawn.phase(), DayPhase::Day);
+
+        let midnight = clock_at_seconds(SECONDS_PER_DAY * 3 / 4);
+        assert_eq!(midnight.day, 1, "Days turn over at midnight");
+        assert_eq!(midnight.time_of_day, 0.0);
+    }
+
+    #[test]
+    fn test_schedules_rest_outside_active_hours() {
+        assert!(ActivitySchedule::Diurnal.rests_during(DayPhase::Night));
+        assert!(!ActivitySchedule::Diurnal.rests_during(DayPhase::Day));
+        assert!(ActivitySchedule::Nocturnal.rests_during(DayPhase::Day));
+        assert!(!ActivitySchedule::Always.rests_during(DayPhase::Night));
+        assert!(!ActivitySchedule::Always.rests_during(DayPhase::Day));
+    }
+
+    #[test]
+    fn test_clock_follows_sim_tick() {
+        let mut app = setup_test_app();
+        app.insert_resource(SimTick::new(2));
+        app.insert_resource(WorldClock::default());
+        app.add_systems(Update, update_world_clock);
+
+        app.world_mut().resource_mut::<SimTick>().tick = SECONDS_PER_DAY;
+        app.update();
+        let clock = app.world().resource::<WorldClock>();
+        assert_eq!(clock.phase(), DayPhase::Night, "Half a day after dawn");
+        assert_eq!(clock.hour_minute(), (18, 0));
+    }
+
+    #[test]
+    fn test_daylight_eases_in_and_out_over_twilight() {
+        let at = |time_of_day: f32| WorldClock { day: 0, time_of_day }.daylight();
+        assert_eq!(at(0.5), 1.0, "Noon is full day");
+        assert_eq!(at(0.0), 0.0, "Midnight is full night");
+        assert!(at(DAWN + TWILIGHT / 2.0) > 0.9999, "Full day once twilight is over");
+        assert!(at(DUSK + TWILIGHT / 2.0) < 1e-4, "Full night once twilight is over");
+        assert!((at(DAWN) - 0.5).abs() < 1e-5, "Halfway light at sunrise");
+        assert!(at(DUSK - TWILIGHT / 4.0) > at(DUSK) && at(DUSK) > at(DUSK + TWILIGHT / 4.0), "Getting darker through dusk");
+
+        assert_eq!(night_tint_alpha(1.0, 0.6), 0.0);
+        assert!((night_tint_alpha(0.0, 0.6) - 0.6).abs() < 1e-5);
+        assert_eq!(night_tint_alpha(0.0, 0.0), 0.0, "A darkness of 0 turns the tint off");
+    }
+
+    #[test]
+    fn test_day_length_comes_from_the_sim_tick() {
+        let mut sim_tick = SimTick::new(2).with_day_length(600);
+        assert_eq!(sim_tick.ticks_per(TickPeriod::Day), 1200);
+        sim_tick.tick = 600; // 300 seconds, half a short day after dawn
+        let clock = WorldClock::from_tick(&sim_tick);
+        assert_eq!(clock.phase(), DayPhase::Night);
+        assert_eq!(clock.hour_minute(), (18, 0));
+
+        sim_tick.tick = 1200;
+        assert_eq!(WorldClock::from_tick(&sim_tick).day, 1);
+        assert_eq!(sim_tick.day(), 1);
+    }
+
+    #[test]
+    fn test_behaviour_trees_can_tell_it_is_night() {
+        let night = BehaviourContext { night: true, ..Default::default() };
+        assert!(night.check(&BehaviourCondition::Night));
+        assert!(!night.check(&BehaviourCondition::RestTime), "Night isn't rest time for a nocturnal pawn");
+        assert!(!BehaviourContext::default().check(&BehaviourCondition::Night));
+    }
+}
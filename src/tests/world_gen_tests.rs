@@ -1,4 +1,4 @@
-use crate::systems::world_gen::TerrainMap;
+use crate::systems::world_gen::{TerrainMap, TerrainChanges};
 use crate::tests::{create_test_terrain_map, create_test_ground_configs};
 
 #[cfg(test)]
@@ -242,4 +242,94 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_are_connected_regions() {
+        let mut terrain_map = TerrainMap::new(10, 10, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap_or(&2);
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+        for x in 0..10 {
+            for y in 0..10 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+        // Stone wall splitting the map at x = 5
+        for y in 0..10 {
+            terrain_map.set_tile(5, y, stone);
+        }
+
+        let left = terrain_map.tile_to_world_coords(1, 1);
+        let left_far = terrain_map.tile_to_world_coords(3, 8);
+        let right = terrain_map.tile_to_world_coords(8, 8);
+        let wall = terrain_map.tile_to_world_coords(5, 5);
+
+        assert!(terrain_map.are_connected(left, left_far, 1.0, &ground_configs));
+        assert!(!terrain_map.are_connected(left, right, 1.0, &ground_configs), "Wall should separate the regions");
+        assert!(!terrain_map.are_connected(left, wall, 1.0, &ground_configs), "Impassable goal is never reachable");
+        assert!(!terrain_map.are_connected(left, (5000.0, 5000.0), 1.0, &ground_configs), "Out of bounds is never reachable");
+
+        // Opening a gap in the wall must invalidate the cached regions
+        terrain_map.set_tile(5, 4, grass);
+        assert!(terrain_map.are_connected(left, right, 1.0, &ground_configs), "Gap should connect the regions");
+        assert!(terrain_map.find_path(left, right, &ground_configs).is_some());
+    }
+
+    #[test]
+    fn test_are_connected_respects_size_tier() {
+        let mut terrain_map = TerrainMap::new(12, 12, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap_or(&2);
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+        for x in 0..12 {
+            for y in 0..12 {
+                let terrain = if x == 6 && y != 5 { stone } else { grass };
+                terrain_map.set_tile(x, y, terrain);
+            }
+        }
+
+        // A one-tile gap at (6, 5) lets small pawns through but not huge ones
+        let left = terrain_map.tile_to_world_coords(2, 5);
+        let right = terrain_map.tile_to_world_coords(10, 5);
+        assert!(terrain_map.are_connected(left, right, 1.0, &ground_configs));
+        assert!(!terrain_map.are_connected(left, right, 3.0, &ground_configs));
+        assert!(terrain_map.find_path_for_size(left, right, 3.0, &ground_configs).is_none());
+    }
+
+    #[test]
+    fn test_nav_grid_follows_terrain_edits() {
+        let mut terrain_map = TerrainMap::new(20, 12, 32.0);
+        let ground_configs = create_test_ground_configs();
+        let grass = *ground_configs.terrain_mapping.get("grass").unwrap_or(&2);
+        let stone = *ground_configs.terrain_mapping.get("stone").unwrap_or(&3);
+        for x in 0..20 {
+            for y in 0..12 {
+                terrain_map.set_tile(x, y, grass);
+            }
+        }
+
+        let start = terrain_map.tile_to_world_coords(2, 6);
+        let goal = terrain_map.tile_to_world_coords(17, 6);
+        let open_path = terrain_map.find_path_for_size(start, goal, 1.0, &ground_configs).expect("Open map should have a path");
+
+        // Snapshot taken before the wall goes up, the way pathfinding tasks copy the map
+        let snapshot = terrain_map.clone();
+
+        // Wall off the goal - the nav chunks around the wall must be rebuilt
+        for y in 0..12 {
+            terrain_map.set_tile(12, y, stone);
+        }
+        assert!(terrain_map.find_path_for_size(start, goal, 1.0, &ground_configs).is_none(), "Wall should block the path");
+        assert_eq!(snapshot.find_path_for_size(start, goal, 1.0, &ground_configs), Some(open_path.clone()),
+                   "Snapshot shares nav chunks but must not see later edits");
+
+        // Knock the wall down through the world-position setter as well
+        let mut terrain_changes = TerrainChanges::default();
+        for y in 0..12 {
+            let (world_x, world_y) = terrain_map.tile_to_world_coords(12, y);
+            terrain_map.set_tile_at_world_pos(world_x, world_y, grass, &mut terrain_changes);
+        }
+        assert_eq!(terrain_map.find_path_for_size(start, goal, 1.0, &ground_configs), Some(open_path));
+        assert!(terrain_map.are_connected(start, goal, 1.0, &ground_configs));
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use crate::resources::GameConfig;
+    use crate::systems::interpolation::SmoothMotion;
+    use crate::systems::pawn::Pawn;
+    use crate::systems::y_sort::{DepthLayer, YSort, YSortSettings, Y_SORT_Z, sorted_z, y_sort_system};
+    use crate::tests::setup_test_app;
+
+    fn setup_y_sort_app(layers: Vec<DepthLayer>) -> App {
+        let mut app = setup_test_app();
+        let mut config = GameConfig::default();
+        config.y_sort = YSortSettings { layers };
+        app.insert_resource(config);
+        app.add_systems(Update, y_sort_system);
+        app
+    }
+
+    fn z_of(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Transform>(entity).unwrap().translation.z
+    }
+
+    #[test]
+    fn test_lower_on_the_map_is_drawn_in_front() {
+        let world_height = 512.0;
+        assert_eq!(sorted_z(256.0, world_height, 0), Y_SORT_Z, "The top edge is furthest back");
+        assert!(sorted_z(-100.0, world_height, 0) > sorted_z(100.0, world_height, 0));
+        assert!(sorted_z(-256.0, world_height, 0) <= 100.0, "Stays under the night tint");
+        assert_eq!(sorted_z(1000.0, world_height, 0), Y_SORT_Z, "Off the map counts as its edge");
+
+        let pawn = sorted_z(10.0, world_height, 3);
+        let prop = sorted_z(10.0, world_height, 0);
+        assert!(pawn > prop, "Later layers are drawn over earlier ones at the same height");
+        assert!(pawn < sorted_z(9.0, world_height, 0), "Even a pixel lower outweighs the layer");
+    }
+
+    #[test]
+    fn test_sorted_layers_follow_their_height() {
+        let mut app = setup_y_sort_app(vec![DepthLayer::Props, DepthLayer::Pawns]);
+        let far_tree = app.world_mut().spawn((YSort(DepthLayer::Props), Transform::from_xyz(0.0, 100.0, 2.0))).id();
+        let near_tree = app.world_mut().spawn((YSort(DepthLayer::Props), Transform::from_xyz(0.0, -100.0, 2.0))).id();
+        let den = app.world_mut().spawn((YSort(DepthLayer::Dens), Transform::from_xyz(0.0, 0.0, 10.0))).id();
+        let position = Vec3::new(0.0, 0.0, 100.0);
+        let pawn = app.world_mut().spawn((YSort(DepthLayer::Pawns), Pawn { pawn_type: "rabbit".to_string() }, Transform::from_translation(position), SmoothMotion::at(position))).id();
+        let newborn = app.world_mut().spawn((YSort(DepthLayer::Pawns), Pawn { pawn_type: "rabbit".to_string() }, Transform::from_translation(position))).id();
+        app.update();
+
+        assert!(z_of(&app, near_tree) > z_of(&app, pawn), "The pawn is behind the tree below it...");
+        assert!(z_of(&app, pawn) > z_of(&app, far_tree), "...and in front of the one above it");
+        assert_eq!(z_of(&app, den), 10.0, "Dens aren't sorted, so they keep their depth");
+        assert_eq!(z_of(&app, newborn), 100.0, "Pawns the simulation hasn't stepped yet are left alone");
+
+        // Walking down past the near tree brings the pawn in front of it
+        app.world_mut().get_mut::<Transform>(pawn).unwrap().translation = Vec3::new(0.0, -120.0, 100.0);
+        app.update();
+        assert!(z_of(&app, pawn) > z_of(&app, near_tree));
+    }
+
+    #[test]
+    fn test_y_sort_settings_are_read() {
+        let config = GameConfig::from_yaml(r#"
+world: { map_width: 32, map_height: 32, tile_size: 16.0 }
+camera: { movement_speed: 200.0, zoom_min: 0.1, zoom_max: 10.0, mouse_sensitivity: 1.0 }
+game: { window_title: "Test", target_fps: 60, show_fps: false }
+y_sort:
+  layers: [pawns, items]
+"#).expect("Failed to parse settings");
+        assert_eq!(config.y_sort.layer_index(DepthLayer::Pawns), Some(0));
+        assert_eq!(config.y_sort.layer_index(DepthLayer::Items), Some(1));
+        assert_eq!(config.y_sort.layer_index(DepthLayer::Props), None);
+        assert_eq!(YSortSettings::default().layer_index(DepthLayer::Pawns), Some(3), "Pawns go over everything by default");
+    }
+}